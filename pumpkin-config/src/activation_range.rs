@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Entities further away from every player than their activation range tick at a reduced rate:
+/// they keep moving and syncing their position, but only run their AI every `inactive_tick_interval` ticks.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ActivationRangeConfig {
+    /// Whether activation ranges are enabled. Disabled by default to match vanilla.
+    pub enabled: bool,
+    /// The activation range in blocks for monsters.
+    pub monsters: u32,
+    /// The activation range in blocks for animals and other creatures.
+    pub animals: u32,
+    /// The activation range in blocks for water mobs.
+    pub water: u32,
+    /// The activation range in blocks for every other entity.
+    pub misc: u32,
+    /// How often, in ticks, an inactive entity still runs its AI.
+    pub inactive_tick_interval: u32,
+}
+
+impl Default for ActivationRangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            monsters: 32,
+            animals: 32,
+            water: 16,
+            misc: 16,
+            inactive_tick_interval: 20,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// When idle players count as away from keyboard. Kicking idle players is set up with
+/// `player_idle_timeout` in the basic configuration.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AfkConfig {
+    /// Seconds without any action after which a player is marked as AFK. `0` never marks players.
+    pub after_seconds: u32,
+    /// Whether AFK players get an `[AFK]` tag behind their name in the tab list.
+    pub mark_in_tab_list: bool,
+}
+
+impl Default for AfkConfig {
+    fn default() -> Self {
+        Self {
+            after_seconds: 300,
+            mark_in_tab_list: false,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Hides ores from clients that see through blocks. Chunks are sent with the ores nobody can see
+/// replaced, and the real blocks are only sent once something around them changes.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct AntiXrayConfig {
+    /// Settings for every world that has none of its own.
+    pub default: AntiXraySettings,
+    /// Settings for single worlds, keyed by their name like `the_nether`. They replace the
+    /// default settings as a whole.
+    pub worlds: BTreeMap<String, AntiXraySettings>,
+}
+
+impl AntiXrayConfig {
+    #[must_use]
+    pub fn for_world(&self, name: &str) -> &AntiXraySettings {
+        self.worlds.get(name).unwrap_or(&self.default)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AntiXraySettings {
+    /// Disabled by default to match vanilla.
+    pub enabled: bool,
+    pub engine_mode: AntiXrayEngineMode,
+    /// The blocks to hide, like `minecraft:diamond_ore`.
+    pub hidden_blocks: Vec<String>,
+    /// The blocks that are sent as fake ores in the `fake_ores` mode.
+    pub replacement_blocks: Vec<String>,
+    /// Blocks at this height or above are sent as they are.
+    pub max_block_height: i32,
+    /// How far around a changed block hidden blocks are revealed.
+    pub update_radius: u8,
+}
+
+/// How hidden blocks are sent.
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AntiXrayEngineMode {
+    /// Hidden blocks look like the stone around them.
+    #[default]
+    HideOres,
+    /// Hidden blocks and the replacement blocks around them look like random hidden blocks, so
+    /// real ores are lost among fake ones. Uses more bandwidth.
+    FakeOres,
+}
+
+impl Default for AntiXraySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            engine_mode: AntiXrayEngineMode::HideOres,
+            hidden_blocks: [
+                "minecraft:coal_ore",
+                "minecraft:deepslate_coal_ore",
+                "minecraft:copper_ore",
+                "minecraft:deepslate_copper_ore",
+                "minecraft:iron_ore",
+                "minecraft:deepslate_iron_ore",
+                "minecraft:gold_ore",
+                "minecraft:deepslate_gold_ore",
+                "minecraft:redstone_ore",
+                "minecraft:deepslate_redstone_ore",
+                "minecraft:lapis_ore",
+                "minecraft:deepslate_lapis_ore",
+                "minecraft:diamond_ore",
+                "minecraft:deepslate_diamond_ore",
+                "minecraft:emerald_ore",
+                "minecraft:deepslate_emerald_ore",
+                "minecraft:nether_gold_ore",
+                "minecraft:nether_quartz_ore",
+                "minecraft:ancient_debris",
+            ]
+            .map(String::from)
+            .to_vec(),
+            replacement_blocks: [
+                "minecraft:stone",
+                "minecraft:deepslate",
+                "minecraft:andesite",
+                "minecraft:diorite",
+                "minecraft:granite",
+                "minecraft:tuff",
+                "minecraft:netherrack",
+                "minecraft:end_stone",
+            ]
+            .map(String::from)
+            .to_vec(),
+            max_block_height: 64,
+            update_radius: 2,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Lets chunks make up for the time they spent unloaded once they are loaded again, e.g. crops
+/// grow and furnaces keep smelting as if a player had stayed nearby. Useful for farm heavy
+/// servers that unload chunks quickly.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct CatchUpConfig {
+    /// Whether chunks catch up when they are loaded. Disabled by default to match vanilla.
+    pub enabled: bool,
+    /// The longest time in ticks that is made up for, chunks that were unloaded for longer
+    /// only catch up on this much.
+    pub max_ticks: u32,
+    /// The maximum number of random ticks simulated for a single chunk. The missed random ticks
+    /// are spread evenly over the blocks that catch up.
+    pub max_random_ticks: u32,
+}
+
+impl Default for CatchUpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_ticks: 72_000,
+            max_random_ticks: 8192,
+        }
+    }
+}
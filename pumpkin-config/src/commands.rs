@@ -12,6 +12,11 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The `op` permission level of everyone that is not in the `ops` file.
     pub default_op_level: PermissionLvl,
+    /// How many command suggestion requests a player may send per second, more are ignored.
+    /// 0 disables the limit.
+    pub suggestion_rate_limit: u32,
+    /// The most blocks `/schem save` may save at once. Vanilla structure blocks save up to 48³.
+    pub max_schem_volume: u64,
 }
 
 impl Default for CommandsConfig {
@@ -21,6 +26,8 @@ impl Default for CommandsConfig {
             log_console: true,
             use_tty: true,
             default_op_level: PermissionLvl::Zero,
+            suggestion_rate_limit: 20,
+            max_schem_volume: 48 * 48 * 48,
         }
     }
 }
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContentFilterConfig {
+    /// Case insensitive regular expressions, text matching any of them gets filtered
+    pub patterns: Vec<String>,
+    /// Whether every player sees filtered text, not only the ones that enabled text filtering
+    pub filter_for_everyone: bool,
+    /// Whether chat messages get filtered
+    pub chat: bool,
+    /// Whether sign text gets filtered
+    pub signs: bool,
+    /// Whether book pages and titles get filtered
+    pub books: bool,
+    /// Whether item names entered in an anvil get filtered
+    pub anvil_renames: bool,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            filter_for_everyone: false,
+            chat: true,
+            signs: true,
+            books: true,
+            anvil_renames: true,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Sends debug shapes to clients to help developing the server. Players should never see these,
+/// so keep this disabled outside of development.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct DebugConfig {
+    /// Whether debug shapes are sent at all.
+    pub enabled: bool,
+    /// Marks the steps of mobs walking to their destination.
+    pub paths: bool,
+    /// Sends points of interest like beds and job sites when they are added or removed.
+    /// The vanilla client only draws them with its debug renderers enabled.
+    pub poi: bool,
+    /// Marks the corners of the chunk a player walks into.
+    pub chunk_borders: bool,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            paths: true,
+            poi: true,
+            chunk_borders: false,
+        }
+    }
+}
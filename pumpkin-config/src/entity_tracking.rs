@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Sends movement updates of far away entities less often. Each player sees entities in rings
+/// around them: entities in outer rings only get every n-th movement update through, saving
+/// bandwidth on entities that are too far away for the difference to show.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityTrackingConfig {
+    /// Whether interest rings are used. Disabled by default to match vanilla.
+    pub enabled: bool,
+    /// The rings from the inside out. Entities beyond the last ring but still within their
+    /// tracking range are updated like those in the last ring.
+    pub rings: Vec<InterestRing>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct InterestRing {
+    /// How far the ring reaches from the player in blocks, measured along the x or z axis.
+    pub distance: u32,
+    /// Only every n-th movement update of entities in this ring is sent.
+    pub update_interval: u32,
+}
+
+impl EntityTrackingConfig {
+    /// How many movement updates of an entity `distance` blocks away make up one sent update.
+    #[must_use]
+    pub fn update_interval(&self, distance: f64) -> u32 {
+        self.rings
+            .iter()
+            .find(|ring| distance <= f64::from(ring.distance))
+            .or(self.rings.last())
+            .map_or(1, |ring| ring.update_interval.max(1))
+    }
+
+    pub fn validate(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        assert!(
+            self.rings
+                .windows(2)
+                .all(|rings| rings[0].distance < rings[1].distance),
+            "Entity tracking rings have to be ordered from the inside out"
+        );
+    }
+}
+
+impl Default for EntityTrackingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rings: vec![
+                InterestRing {
+                    distance: 32,
+                    update_interval: 1,
+                },
+                InterestRing {
+                    distance: 64,
+                    update_interval: 2,
+                },
+                InterestRing {
+                    distance: 128,
+                    update_interval: 4,
+                },
+            ],
+        }
+    }
+}
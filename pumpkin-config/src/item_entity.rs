@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// Rules for dropped items: which of them survive what, and who may pick up death drops.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ItemEntityConfig {
+    /// Items that explosions do not destroy. Vanilla only spares the nether star, so it survives
+    /// the explosion of the wither that dropped it.
+    pub explosion_immune: Vec<String>,
+    /// Items that fire and lava do not destroy, netherite in vanilla.
+    pub fire_immune: Vec<String>,
+    /// Items that cacti do not destroy.
+    pub cactus_immune: Vec<String>,
+    /// Whether items immune to any of the above glow, so they are easy to find.
+    pub glow_immune_items: bool,
+    /// Whether the items a player drops on death glow.
+    pub glow_death_drops: bool,
+    /// Ticks after a player's death during which only they can pick up the items they dropped.
+    /// With 0 anyone can pick them up right away, like in vanilla.
+    pub death_drop_protection_ticks: u32,
+}
+
+impl Default for ItemEntityConfig {
+    fn default() -> Self {
+        Self {
+            explosion_immune: vec!["minecraft:nether_star".to_string()],
+            fire_immune: [
+                "minecraft:ancient_debris",
+                "minecraft:netherite_block",
+                "minecraft:netherite_ingot",
+                "minecraft:netherite_scrap",
+                "minecraft:netherite_sword",
+                "minecraft:netherite_shovel",
+                "minecraft:netherite_pickaxe",
+                "minecraft:netherite_axe",
+                "minecraft:netherite_hoe",
+                "minecraft:netherite_helmet",
+                "minecraft:netherite_chestplate",
+                "minecraft:netherite_leggings",
+                "minecraft:netherite_boots",
+            ]
+            .map(String::from)
+            .to_vec(),
+            cactus_immune: Vec::new(),
+            glow_immune_items: false,
+            glow_death_drops: false,
+            death_drop_protection_ticks: 0,
+        }
+    }
+}
@@ -14,16 +14,34 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use activation_range::ActivationRangeConfig;
+pub use afk::AfkConfig;
+pub use anti_xray::{AntiXrayConfig, AntiXrayEngineMode, AntiXraySettings};
+pub use catch_up::CatchUpConfig;
 pub use chat::ChatConfig;
 pub use commands::CommandsConfig;
+pub use content_filter::ContentFilterConfig;
+pub use debug::DebugConfig;
+pub use entity_tracking::{EntityTrackingConfig, InterestRing};
+pub use item_entity::ItemEntityConfig;
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
 pub use pvp::PVPConfig;
 pub use server_links::ServerLinksConfig;
+pub use tick::TickConfig;
+pub use worlds::{GameRuleSetting, WorldConfig, WorldDimension, WorldsConfig};
 
+mod activation_range;
+mod afk;
+mod anti_xray;
+mod catch_up;
 mod commands;
+mod content_filter;
+mod debug;
+mod entity_tracking;
+mod item_entity;
 
 mod chat;
 pub mod chunk;
@@ -31,7 +49,9 @@ pub mod op;
 mod player_data;
 mod pvp;
 mod server_links;
+mod tick;
 pub mod whitelist;
+mod worlds;
 
 use networking::NetworkingConfig;
 use player_data::PlayerDataConfig;
@@ -91,13 +111,23 @@ pub struct AdvancedConfiguration {
     pub logging: LoggingConfig,
     pub resource_pack: ResourcePackConfig,
     pub chunk: ChunkConfig,
+    pub catch_up: CatchUpConfig,
     pub networking: NetworkingConfig,
     pub commands: CommandsConfig,
     pub chat: ChatConfig,
+    pub content_filter: ContentFilterConfig,
     pub pvp: PVPConfig,
+    pub afk: AfkConfig,
+    pub item_entity: ItemEntityConfig,
     pub server_links: ServerLinksConfig,
     pub player_data: PlayerDataConfig,
     pub fun: FunConfig,
+    pub tick: TickConfig,
+    pub activation_range: ActivationRangeConfig,
+    pub entity_tracking: EntityTrackingConfig,
+    pub worlds: WorldsConfig,
+    pub anti_xray: AntiXrayConfig,
+    pub debug: DebugConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -153,6 +183,10 @@ pub struct BasicConfiguration {
     pub white_list: bool,
     /// Whether to enforce the whitelist
     pub enforce_whitelist: bool,
+    /// Whether players may join through a transfer from another server
+    pub accept_transfers: bool,
+    /// Minutes a player may stay idle before being kicked. `0` disables the kick.
+    pub player_idle_timeout: u32,
 }
 
 impl Default for BasicConfiguration {
@@ -183,6 +217,8 @@ impl Default for BasicConfiguration {
             allow_chat_reports: false,
             white_list: false,
             enforce_whitelist: false,
+            accept_transfers: false,
+            player_idle_timeout: 0,
         }
     }
 }
@@ -310,7 +346,9 @@ impl LoadConfiguration for AdvancedConfiguration {
     }
 
     fn validate(&self) {
-        self.resource_pack.validate()
+        self.resource_pack.validate();
+        self.entity_tracking.validate();
+        self.worlds.validate();
     }
 }
 
@@ -17,6 +17,8 @@ pub struct AuthenticationConfig {
     pub player_profile: PlayerProfileConfig,
     /// Texture handling.
     pub textures: TextureConfig,
+    /// How many days a cached player profile is used before it is looked up again.
+    pub profile_cache_days: u32,
 }
 
 impl Default for AuthenticationConfig {
@@ -31,6 +33,7 @@ impl Default for AuthenticationConfig {
             services_url: None,
             connect_timeout: 5000,
             read_timeout: 5000,
+            profile_cache_days: 30,
         }
     }
 }
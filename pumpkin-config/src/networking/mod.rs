@@ -1,4 +1,5 @@
 use auth::AuthenticationConfig;
+use packet_capture::PacketCaptureConfig;
 use proxy::ProxyConfig;
 use query::QueryConfig;
 use rcon::RCONConfig;
@@ -9,6 +10,7 @@ use crate::{CompressionConfig, LANBroadcastConfig};
 pub mod auth;
 pub mod compression;
 pub mod lan_broadcast;
+pub mod packet_capture;
 pub mod proxy;
 pub mod query;
 pub mod rcon;
@@ -21,4 +23,5 @@ pub struct NetworkingConfig {
     pub proxy: ProxyConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub packet_capture: PacketCaptureConfig,
 }
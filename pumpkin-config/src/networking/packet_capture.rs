@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct PacketCaptureConfig {
+    /// Logs every matching packet at debug level
+    pub log: bool,
+    /// Writes every matching packet of each Java connection to a capture file, which can be fed
+    /// back through the packet handlers with `/packetreplay`
+    pub capture: bool,
+    /// The directory capture files are written to
+    pub directory: String,
+    /// Whether packets sent by clients are included
+    pub serverbound: bool,
+    /// Whether packets sent by the server are included
+    pub clientbound: bool,
+    /// Packet ids to include, every packet is included when empty
+    pub packet_ids: Vec<i32>,
+    /// Packet ids to leave out
+    pub excluded_packet_ids: Vec<i32>,
+}
+
+impl PacketCaptureConfig {
+    /// Whether packets should be looked at at all.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        (self.log || self.capture) && (self.serverbound || self.clientbound)
+    }
+
+    /// Whether a packet with `id` passes the id filters.
+    #[must_use]
+    pub fn matches_id(&self, id: i32) -> bool {
+        (self.packet_ids.is_empty() || self.packet_ids.contains(&id))
+            && !self.excluded_packet_ids.contains(&id)
+    }
+}
+
+impl Default for PacketCaptureConfig {
+    fn default() -> Self {
+        Self {
+            log: false,
+            capture: false,
+            directory: "packet_captures".to_string(),
+            serverbound: true,
+            clientbound: true,
+            packet_ids: Vec::new(),
+            excluded_packet_ids: Vec::new(),
+        }
+    }
+}
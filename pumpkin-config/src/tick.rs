@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct TickConfig {
+    /// The maximum number of scheduled block ticks processed per game tick.
+    /// Ticks above this budget are deferred to the next game tick.
+    pub max_block_ticks: usize,
+    /// The maximum number of scheduled fluid ticks processed per game tick.
+    /// Ticks above this budget are deferred to the next game tick.
+    pub max_fluid_ticks: usize,
+    /// Whether entities are ticked in parallel, sharded by region. Block changes, spawns and player
+    /// collisions are committed in a fixed order afterwards. Off by default: what entities do to
+    /// each other (damage, item pickups) still happens in whatever order the regions get scheduled.
+    pub parallel_entity_ticking: bool,
+    /// The maximum number of neighbor updates a single block change may chain into.
+    /// Further updates are skipped and a warning is logged, like vanilla's `max-chained-neighbor-updates`.
+    pub max_chained_neighbor_updates: usize,
+    /// The maximum number of block changes world edit sessions (e.g. `/fill`) apply per game tick.
+    /// Larger edits are spread over multiple ticks.
+    pub max_world_edit_changes: usize,
+}
+
+impl Default for TickConfig {
+    fn default() -> Self {
+        Self {
+            max_block_ticks: 65536,
+            max_fluid_ticks: 65536,
+            parallel_entity_ticking: false,
+            max_chained_neighbor_updates: 1_000_000,
+            max_world_edit_changes: 32768,
+        }
+    }
+}
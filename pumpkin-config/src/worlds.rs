@@ -0,0 +1,103 @@
+use std::{collections::BTreeMap, fmt, path::PathBuf};
+
+use pumpkin_util::Difficulty;
+use serde::{Deserialize, Serialize};
+
+/// The names of the default overworld, nether and end.
+const DEFAULT_WORLD_NAMES: [&str; 3] = ["overworld", "the_nether", "the_end"];
+
+/// Worlds loaded next to the default overworld, nether and end. Players move between them with
+/// `/world tp`.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct WorldsConfig {
+    pub additional: Vec<WorldConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct WorldConfig {
+    /// The name the world is known by, also the name of its folder next to the default world.
+    pub name: String,
+    /// The seed used when the world is created. An empty seed picks a random one.
+    #[serde(default)]
+    pub seed: String,
+    /// Which vanilla terrain the world generates.
+    #[serde(default)]
+    pub generator: WorldDimension,
+    /// The dimension type, deciding things like the height, sky light and ambient light.
+    /// Defaults to the one belonging to the generator.
+    #[serde(default)]
+    pub dimension_type: Option<WorldDimension>,
+    /// Overrides the difficulty stored in the world.
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    /// Game rules set whenever the world is loaded, keyed by their vanilla name like
+    /// `doDaylightCycle`.
+    #[serde(default)]
+    pub gamerules: BTreeMap<String, GameRuleSetting>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldDimension {
+    #[default]
+    Overworld,
+    Nether,
+    End,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum GameRuleSetting {
+    Bool(bool),
+    Int(i64),
+}
+
+impl fmt::Display for GameRuleSetting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bool(value) => value.fmt(f),
+            Self::Int(value) => value.fmt(f),
+        }
+    }
+}
+
+impl WorldConfig {
+    #[must_use]
+    pub fn dimension_type(&self) -> WorldDimension {
+        self.dimension_type.unwrap_or(self.generator)
+    }
+
+    #[must_use]
+    pub fn get_world_path(&self) -> PathBuf {
+        format!("./{}", self.name).parse().unwrap()
+    }
+}
+
+impl WorldsConfig {
+    pub fn validate(&self) {
+        for (i, world) in self.additional.iter().enumerate() {
+            assert!(
+                !world.name.is_empty()
+                    && world
+                        .name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+                "World names may only contain letters, digits, '_' and '-', got {:?}",
+                world.name
+            );
+            assert!(
+                !DEFAULT_WORLD_NAMES.contains(&world.name.as_str()),
+                "The world name {:?} is taken by a default world",
+                world.name
+            );
+            assert!(
+                self.additional[..i]
+                    .iter()
+                    .all(|other| other.name != world.name),
+                "There are two worlds named {:?}",
+                world.name
+            );
+        }
+    }
+}
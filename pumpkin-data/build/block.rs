@@ -115,7 +115,7 @@ impl ToTokens for PropertyStruct {
                 value
             };
             quote! {
-                #value => Self::#ident
+                #value => Some(Self::#ident)
             }
         });
         let to_values = self.values.iter().map(|value| {
@@ -161,9 +161,13 @@ impl ToTokens for PropertyStruct {
                 }
 
                 fn from_value(value: &str) -> Self {
+                    Self::try_from_value(value).unwrap_or_else(|| panic!("Invalid value: {value}"))
+                }
+
+                fn try_from_value(value: &str) -> Option<Self> {
                     match value {
                         #(#from_values),*,
-                        _ => panic!("Invalid value: {value}"),
+                        _ => None,
                     }
                 }
 
@@ -283,6 +287,30 @@ impl ToTokens for BlockPropertyStruct {
             }
         });
 
+        let try_from_props_values = self.data.variant_mappings.iter().map(|entry| {
+            let key = &entry.original_name;
+            let field_name = Ident::new_raw(&entry.original_name, Span::call_site());
+            match &entry.property_type {
+                PropertyType::Bool => quote! {
+                    #key => {
+                        block_props.#field_name = match *value {
+                            "true" => true,
+                            "false" => false,
+                            _ => return None,
+                        }
+                    }
+                },
+                PropertyType::Enum { name } => {
+                    let enum_ident = Ident::new(name, Span::call_site());
+                    quote! {
+                        #key => {
+                            block_props.#field_name = #enum_ident::try_from_value(value)?
+                        }
+                    }
+                }
+            }
+        });
+
         tokens.extend(quote! {
             #[derive(Clone, Copy, Debug, Eq, PartialEq)]
             pub struct #name {
@@ -353,6 +381,19 @@ impl ToTokens for BlockPropertyStruct {
                     }
                     block_props
                 }
+                fn try_from_props(props: &[(&str, &str)], block: &Block) -> Option<Self> {
+                    if !Self::handles_block_id(block.id) {
+                        return None;
+                    }
+                    let mut block_props = Self::default(block);
+                    for (key, value) in props {
+                        match *key {
+                            #(#try_from_props_values),*,
+                            _ => return None,
+                        }
+                    }
+                    Some(block_props)
+                }
             }
         });
     }
@@ -673,6 +714,7 @@ pub(crate) fn build() -> TokenStream {
     let mut random_tick_states = Vec::new();
     let mut block_properties_from_state_and_block_id = TokenStream::new();
     let mut block_properties_from_props_and_name = TokenStream::new();
+    let mut block_properties_try_from_props_and_name = TokenStream::new();
     let mut existing_item_ids: Vec<u16> = Vec::new();
     let mut constants = TokenStream::new();
     let mut block_state_to_bedrock = Vec::new();
@@ -764,6 +806,10 @@ pub(crate) fn build() -> TokenStream {
             block_properties_from_props_and_name.extend(quote! {
                 #id_lit => Box::new(#property_name::from_props(props, &Block::#const_block_name)),
             });
+
+            block_properties_try_from_props_and_name.extend(quote! {
+                #id_lit => Box::new(#property_name::try_from_props(props, &Block::#const_block_name)?),
+            });
         }
 
         block_properties.push(BlockPropertyStruct {
@@ -943,6 +989,9 @@ pub(crate) fn build() -> TokenStream {
 
             // Convert properties to a block state, and add them onto the default state.
             fn from_props(props: &[(&str, &str)], block: &Block) -> Self where Self: Sized;
+
+            // Like `from_props`, but returns `None` on an unknown block, key or value instead of panicking.
+            fn try_from_props(props: &[(&str, &str)], block: &Block) -> Option<Self> where Self: Sized;
         }
 
         pub trait EnumVariants {
@@ -951,6 +1000,7 @@ pub(crate) fn build() -> TokenStream {
             fn from_index(index: u16) -> Self;
             fn to_value(&self) -> &str;
             fn from_value(value: &str) -> Self;
+            fn try_from_value(value: &str) -> Option<Self> where Self: Sized;
         }
 
         pub static COLLISION_SHAPES: &[CollisionShape] = &[
@@ -1090,6 +1140,47 @@ pub(crate) fn build() -> TokenStream {
                     _ => panic!("Invalid props")
                 }
             }
+
+            #[doc = r" Try to get the properties of the block, returning `None` for an unknown key or value."]
+            pub fn try_from_properties(&self, props: &[(&str, &str)]) -> Option<Box<dyn BlockProperties>> {
+                Some(match self.id {
+                    #block_properties_try_from_props_and_name
+                    _ => return None,
+                })
+            }
+
+            #[doc = r" Get the typed properties of a state of this block."]
+            #[doc = r""]
+            #[doc = r" Returns `None` if the block does not use `P` or the state does not belong to this block."]
+            pub fn get<P: BlockProperties>(&self, state_id: u16) -> Option<P> {
+                let first = self.states.first()?.id;
+                let last = self.states.last()?.id;
+                if !P::handles_block_id(self.id) || !(first..=last).contains(&state_id) {
+                    return None;
+                }
+                Some(P::from_index(state_id - first))
+            }
+
+            #[doc = r" Set a single property of a state of this block by name."]
+            #[doc = r""]
+            #[doc = r" Returns the new state id, or `None` if the block has no such property or the value is invalid."]
+            pub fn with_property(&self, state_id: u16, name: &str, value: &str) -> Option<u16> {
+                let props = self.properties(state_id)?.to_props();
+                if !props.iter().any(|(key, _)| key == name) {
+                    return None;
+                }
+                let props: Vec<(&str, &str)> = props
+                    .iter()
+                    .map(|(key, current)| {
+                        if key == name {
+                            (key.as_str(), value)
+                        } else {
+                            (key.as_str(), current.as_str())
+                        }
+                    })
+                    .collect();
+                Some(self.try_from_properties(&props)?.to_state_id(self))
+            }
         }
 
         #(#properties)*
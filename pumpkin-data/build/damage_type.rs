@@ -6,8 +6,8 @@ use std::{collections::BTreeMap, fs};
 use syn::{Ident, LitInt};
 
 #[derive(Deserialize)]
-struct DamageTypeEntry {
-    id: u8,
+pub struct DamageTypeEntry {
+    pub id: u8,
     components: DamageTypeData,
 }
 
@@ -56,6 +56,7 @@ pub(crate) fn build() -> TokenStream {
 
     let mut constants = Vec::new();
     let mut type_from_name = TokenStream::new();
+    let mut type_from_id = TokenStream::new();
 
     for (name, entry) in damage_types {
         let const_ident = format_ident!("{}", name.to_shouty_snake_case());
@@ -91,8 +92,13 @@ pub(crate) fn build() -> TokenStream {
         let scaling = quote! {DamageScaling::#scaling_ident};
         let id_lit = LitInt::new(&entry.id.to_string(), proc_macro2::Span::call_site());
 
+        type_from_id.extend(quote! {
+            #id_lit => Some(Self::#const_ident),
+        });
+
         constants.push(quote! {
             pub const #const_ident: DamageType = DamageType {
+                registry_key: #resource_name,
                 death_message_type: #death_message_type,
                 exhaustion: #exhaustion,
                 effects: #effects,
@@ -104,8 +110,11 @@ pub(crate) fn build() -> TokenStream {
     }
 
     quote! {
+        use crate::tag::{RegistryKey, Taggable};
+
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct DamageType {
+            pub registry_key: &'static str,
             pub death_message_type: DeathMessageType,
             pub exhaustion: f32,
             pub effects: Option<DamageEffects>,
@@ -149,6 +158,30 @@ pub(crate) fn build() -> TokenStream {
                 }
             }
 
+            #[doc = r" Get a damage type by its network id in the `minecraft:damage_type` registry."]
+            pub const fn from_id(id: u8) -> Option<Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+        }
+
+        impl Taggable for DamageType {
+            #[inline]
+            fn tag_key() -> RegistryKey {
+                RegistryKey::DamageType
+            }
+
+            #[inline]
+            fn registry_key(&self) -> &str {
+                self.registry_key
+            }
+
+            #[inline]
+            fn registry_id(&self) -> u16 {
+                u16::from(self.id)
+            }
         }
     }
 }
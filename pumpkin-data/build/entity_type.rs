@@ -23,6 +23,8 @@ pub struct EntityType {
     pub can_spawn_far_from_player: bool,
     pub dimension: [f32; 2],
     pub eye_height: f32,
+    pub client_tracking_range: i32,
+    pub update_interval: i32,
     pub spawn_restriction: SpawnRestriction,
 }
 
@@ -111,6 +113,8 @@ impl ToTokens for NamedEntityType<'_> {
         let summonable = entity.summonable;
         let fire_immune = entity.fire_immune;
         let eye_height = entity.eye_height;
+        let client_tracking_range = entity.client_tracking_range;
+        let update_interval = entity.update_interval;
         if entity.mob.is_none() && name != "player" {
             panic!("missing field 'mob', entity name {name}");
         }
@@ -147,6 +151,8 @@ impl ToTokens for NamedEntityType<'_> {
                 loot_table: #loot_table,
                 dimension: [#dimension0, #dimension1], // Correctly construct the array
                 eye_height: #eye_height,
+                client_tracking_range: #client_tracking_range,
+                update_interval: #update_interval,
                 spawn_restriction: #spawn_restriction,
                 resource_name: #name,
             }
@@ -204,6 +210,10 @@ pub(crate) fn build() -> TokenStream {
             pub loot_table: Option<LootTable>,
             pub dimension: [f32; 2],
             pub eye_height: f32,
+            /// How far away players can see this entity, in chunks.
+            pub client_tracking_range: i32,
+            /// How often movement updates are sent to players, in ticks.
+            pub update_interval: i32,
             pub spawn_restriction: SpawnRestriction,
             pub resource_name: &'static str,
         }
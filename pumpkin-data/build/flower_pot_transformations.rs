@@ -1,25 +1,66 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::{collections::BTreeMap, fs};
+
+use crate::block::BlockAssets;
+use crate::item::Item;
+
 pub(crate) fn build() -> TokenStream {
-    println!("cargo:rerun-if-changed=../assets/flower_pot_transformations.json");
+    println!("cargo:rerun-if-changed=../assets/blocks.json");
+    println!("cargo:rerun-if-changed=../assets/items.json");
+
+    let blocks_assets: BlockAssets =
+        serde_json::from_str(&fs::read_to_string("../assets/blocks.json").unwrap())
+            .expect("Failed to parse blocks.json");
+    let items: BTreeMap<String, Item> =
+        serde_json::from_str(&fs::read_to_string("../assets/items.json").unwrap())
+            .expect("Failed to parse items.json");
 
-    let flower_pot_transformation: BTreeMap<u16, u16> = serde_json::from_str(
-        &fs::read_to_string("../assets/flower_pot_transformations.json").unwrap(),
-    )
-    .expect("Failed to parse flower_pot_transformations.json");
-    let mut variants = TokenStream::new();
+    let mut potted_variants = TokenStream::new();
+    let mut content_variants = TokenStream::new();
 
-    for (item_id, potted_block_id) in flower_pot_transformation {
-        variants.extend(quote! {
+    // Every potted block is named after the plant in it, except for azaleas which are potted as
+    // `potted_azalea_bush` and `potted_flowering_azalea_bush`
+    for block in &blocks_assets.blocks {
+        let Some(plant) = block.name.strip_prefix("potted_") else {
+            continue;
+        };
+        let item = items
+            .get(plant)
+            .or_else(|| {
+                plant
+                    .strip_suffix("_bush")
+                    .and_then(|plant| items.get(plant))
+            })
+            .unwrap_or_else(|| panic!("No item can be potted as {}", block.name));
+
+        let item_id = item.id;
+        let potted_block_id = block.id;
+        potted_variants.extend(quote! {
             #item_id => #potted_block_id,
         });
+        content_variants.extend(quote! {
+            #potted_block_id => #item_id,
+        });
     }
+
     quote! {
+        /// The id of the potted block that `item_id` turns an empty flower pot into, or 0 if the
+        /// item can't be potted.
         #[must_use]
         pub const fn get_potted_item(item_id: u16) -> u16 {
             match item_id {
-                #variants
+                #potted_variants
+                _ => 0,
+            }
+        }
+
+        /// The id of the item planted in the potted block `block_id`, or 0 if it is not a potted
+        /// plant.
+        #[must_use]
+        pub const fn get_potted_content(block_id: u16) -> u16 {
+            match block_id {
+                #content_variants
                 _ => 0,
             }
         }
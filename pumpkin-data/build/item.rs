@@ -518,17 +518,39 @@ pub(crate) fn build() -> TokenStream {
             #constants
 
             pub fn translated_name(&self) -> TextComponent {
-                TextComponent::translate(
-                    self.components
-                        .iter()
-                        .find_map(|(id, data)| if id == &ItemName {
-                            Some(data.as_any().downcast_ref::<ItemNameImpl>().unwrap().name)
-                        } else {
-                            None
-                        }
-                    ).unwrap(),
-                    &[],
-                )
+                TextComponent::translate(self.get_component::<ItemNameImpl>().unwrap().name, &[])
+            }
+
+            #[doc = "Get a default component of this item, as generated from the item data."]
+            pub fn get_component<T: DataComponentImpl + 'static>(&self) -> Option<&'static T> {
+                self.components
+                    .iter()
+                    .find_map(|(id, data)| (*id == T::get_enum()).then(|| get::<T>(*data)))
+            }
+
+            #[doc = "The default max stack size of this item."]
+            pub fn max_stack_size(&self) -> u8 {
+                self.get_component::<MaxStackSizeImpl>().map_or(1, |data| data.size)
+            }
+
+            #[doc = "The default max damage of this item, if it can be damaged."]
+            pub fn max_damage(&self) -> Option<i32> {
+                self.get_component::<MaxDamageImpl>().map(|data| data.max_damage)
+            }
+
+            #[doc = "The default food properties of this item, if it can be eaten."]
+            pub fn food(&self) -> Option<&'static FoodImpl> {
+                self.get_component::<FoodImpl>()
+            }
+
+            #[doc = "The default tool rules of this item, if it is a tool."]
+            pub fn tool(&self) -> Option<&'static ToolImpl> {
+                self.get_component::<ToolImpl>()
+            }
+
+            #[doc = "The default slot this item can be equipped in, if it is equippable."]
+            pub fn equippable_slot(&self) -> Option<&'static EquipmentSlot> {
+                self.get_component::<EquippableImpl>().map(|data| data.slot)
             }
 
             #[doc = "Try to parse an item from a resource location string."]
@@ -195,7 +195,7 @@ pub enum LootConditionStruct {
         properties: BTreeMap<String, String>,
     },
     #[serde(rename = "minecraft:match_tool")]
-    MatchTool,
+    MatchTool { predicate: ItemPredicateStruct },
     #[serde(rename = "minecraft:table_bonus")]
     TableBonus,
     #[serde(rename = "minecraft:survives_explosion")]
@@ -216,6 +216,32 @@ pub enum LootConditionStruct {
     EnchantmentActiveCheck,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemPredicateStruct {
+    #[serde(default)]
+    items: Option<String>,
+    #[serde(default)]
+    predicates: ItemSubPredicatesStruct,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ItemSubPredicatesStruct {
+    #[serde(rename = "minecraft:enchantments", default)]
+    enchantments: Vec<EnchantmentPredicateStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicateStruct {
+    enchantments: String,
+    #[serde(default)]
+    levels: Option<LevelsStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LevelsStruct {
+    min: Option<i32>,
+}
+
 impl ToTokens for LootConditionStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = match self {
@@ -236,7 +262,22 @@ impl ToTokens for LootConditionStruct {
                     .collect();
                 quote! { LootCondition::BlockStateProperty { block: #block, properties: &[#(#properties),*] } }
             }
-            LootConditionStruct::MatchTool => quote! { LootCondition::MatchTool },
+            LootConditionStruct::MatchTool { predicate } => {
+                let items = match &predicate.items {
+                    Some(items) => quote! { Some(#items) },
+                    None => quote! { None },
+                };
+                let enchantments = predicate.predicates.enchantments.iter().map(|enchantment| {
+                    let name = &enchantment.enchantments;
+                    let min = enchantment
+                        .levels
+                        .as_ref()
+                        .and_then(|levels| levels.min)
+                        .unwrap_or(1);
+                    quote! { (#name, #min) }
+                });
+                quote! { LootCondition::MatchTool { items: #items, enchantments: &[#(#enchantments),*] } }
+            }
             LootConditionStruct::TableBonus => quote! { LootCondition::TableBonus },
             LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
             LootConditionStruct::DamageSourceProperties => {
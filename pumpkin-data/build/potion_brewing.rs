@@ -110,5 +110,14 @@ pub(crate) fn build() -> TokenStream {
 
         pub const ITEM_RECIPES: [ItemRecipe; #item_len] = [#item];
         pub const POTION_RECIPES: [PotionRecipe; #potion_len] = [#potion];
+
+        /// Whether the item can go into the ingredient slot of a brewing stand.
+        pub fn is_brewing_ingredient(item: &Item) -> bool {
+            ITEM_RECIPES
+                .iter()
+                .flat_map(|recipe| recipe.ingredient)
+                .chain(POTION_RECIPES.iter().flat_map(|recipe| recipe.ingredient))
+                .any(|ingredient| ingredient.id == item.id)
+        }
     }
 }
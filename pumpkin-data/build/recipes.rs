@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, fs};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
 
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
@@ -74,6 +77,8 @@ impl ToTokens for CookingRecipeStruct {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct CraftingShapedRecipeStruct {
+    #[serde(skip)]
+    id: String,
     category: Option<RecipeCategoryTypes>,
     group: Option<String>,
     show_notification: Option<bool>,
@@ -84,6 +89,7 @@ pub struct CraftingShapedRecipeStruct {
 
 impl ToTokens for CraftingShapedRecipeStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let id = &self.id;
         let category = match &self.category {
             Some(category) => category.to_token_stream(),
             None => RecipeCategoryTypes::Misc.to_token_stream(),
@@ -110,6 +116,7 @@ impl ToTokens for CraftingShapedRecipeStruct {
 
         tokens.extend(quote! {
             CraftingRecipeTypes::CraftingShaped {
+                id: #id,
                 category: #category,
                 group: #group,
                 show_notification: #show_notification,
@@ -123,6 +130,8 @@ impl ToTokens for CraftingShapedRecipeStruct {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct CraftingShapelessRecipeStruct {
+    #[serde(skip)]
+    id: String,
     category: Option<RecipeCategoryTypes>,
     group: Option<String>,
     ingredients: Vec<RecipeIngredientTypes>,
@@ -131,6 +140,7 @@ pub struct CraftingShapelessRecipeStruct {
 
 impl ToTokens for CraftingShapelessRecipeStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let id = &self.id;
         let category = match &self.category {
             Some(category) => category.to_token_stream(),
             None => RecipeCategoryTypes::Misc.to_token_stream(),
@@ -148,6 +158,7 @@ impl ToTokens for CraftingShapelessRecipeStruct {
 
         tokens.extend(quote! {
             CraftingRecipeTypes::CraftingShapeless {
+                id: #id,
                 category: #category,
                 group: #group,
                 ingredients: &[#(#ingredients),*],
@@ -159,6 +170,8 @@ impl ToTokens for CraftingShapelessRecipeStruct {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct CraftingTransmuteRecipeStruct {
+    #[serde(skip)]
+    id: String,
     category: Option<RecipeCategoryTypes>,
     group: Option<String>,
     input: RecipeIngredientTypes,
@@ -168,6 +181,7 @@ pub struct CraftingTransmuteRecipeStruct {
 
 impl ToTokens for CraftingTransmuteRecipeStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let id = &self.id;
         let category = match &self.category {
             Some(category) => category.to_token_stream(),
             None => RecipeCategoryTypes::Misc.to_token_stream(),
@@ -182,6 +196,7 @@ impl ToTokens for CraftingTransmuteRecipeStruct {
 
         tokens.extend(quote! {
             CraftingRecipeTypes::CraftingTransmute {
+                id: #id,
                 category: #category,
                 group: #group,
                 input: #input,
@@ -306,6 +321,19 @@ impl ToTokens for RecipeCategoryTypes {
     }
 }
 
+/// `recipes.json` doesn't contain the recipe ids, so they are derived from the result instead. The
+/// recipes are sorted by id, so the first recipe for an item is named after it like in vanilla, e.g.
+/// `minecraft:stick`, and the later ones get a number appended, e.g. `minecraft:stick_2`.
+fn recipe_id(result: &str, recipes_per_result: &mut HashMap<String, u32>) -> String {
+    let count = recipes_per_result.entry(result.to_string()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        result.to_string()
+    } else {
+        format!("{result}_{count}")
+    }
+}
+
 pub(crate) fn build() -> TokenStream {
     println!("cargo:rerun-if-changed=../assets/recipes.json");
 
@@ -315,6 +343,7 @@ pub(crate) fn build() -> TokenStream {
 
     let mut crafting_recipes = Vec::new();
     let mut cooking_recipes = Vec::new();
+    let mut recipes_per_result = HashMap::new();
 
     for recipe in recipes_assets {
         match recipe {
@@ -336,13 +365,16 @@ pub(crate) fn build() -> TokenStream {
                 };
                 cooking_recipes.push(campfire_token);
             }
-            RecipeTypes::CraftingShaped(recipe) => {
+            RecipeTypes::CraftingShaped(mut recipe) => {
+                recipe.id = recipe_id(&recipe.result.id, &mut recipes_per_result);
                 crafting_recipes.push(recipe.to_token_stream());
             }
-            RecipeTypes::CraftingShapeless(recipe) => {
+            RecipeTypes::CraftingShapeless(mut recipe) => {
+                recipe.id = recipe_id(&recipe.result.id, &mut recipes_per_result);
                 crafting_recipes.push(recipe.to_token_stream());
             }
-            RecipeTypes::CraftingTransmute(recipe) => {
+            RecipeTypes::CraftingTransmute(mut recipe) => {
+                recipe.id = recipe_id(&recipe.result.id, &mut recipes_per_result);
                 crafting_recipes.push(recipe.to_token_stream());
             }
             RecipeTypes::CraftingDecoratedPot(recipe) => {
@@ -380,6 +412,7 @@ pub(crate) fn build() -> TokenStream {
         #[derive(Clone, Debug)]
         pub enum CraftingRecipeTypes {
             CraftingShaped {
+                id: &'static str,
                 category: RecipeCategoryTypes,
                 group: Option<&'static str>,
                 show_notification: bool,
@@ -388,12 +421,14 @@ pub(crate) fn build() -> TokenStream {
                 result: RecipeResultStruct,
             },
             CraftingShapeless {
+                id: &'static str,
                 category: RecipeCategoryTypes,
                 group: Option<&'static str>,
                 ingredients: &'static [RecipeIngredientTypes],
                 result: RecipeResultStruct,
             },
             CraftingTransmute {
+                id: &'static str,
                 category: RecipeCategoryTypes,
                 group: Option<&'static str>,
                 input: RecipeIngredientTypes,
@@ -2,6 +2,7 @@ use std::{collections::BTreeMap, fs};
 
 use crate::biome::Biome;
 use crate::block::BlockAssets;
+use crate::damage_type::DamageTypeEntry;
 use crate::enchantments::Enchantment;
 use crate::entity_type::EntityType;
 use crate::fluid::Fluid;
@@ -40,6 +41,7 @@ pub(crate) fn build() -> TokenStream {
     println!("cargo:rerun-if-changed=../assets/biome.json");
     println!("cargo:rerun-if-changed=../assets/fluids.json");
     println!("cargo:rerun-if-changed=../assets/entities.json");
+    println!("cargo:rerun-if-changed=../assets/damage_type.json");
 
     let tags: BTreeMap<String, BTreeMap<String, Vec<String>>> =
         serde_json::from_str(&fs::read_to_string("../assets/tags.json").unwrap())
@@ -71,6 +73,10 @@ pub(crate) fn build() -> TokenStream {
         serde_json::from_str(&fs::read_to_string("../assets/entities.json").unwrap())
             .expect("Failed to parse entities.json");
 
+    let damage_types: BTreeMap<String, DamageTypeEntry> =
+        serde_json::from_str(&fs::read_to_string("../assets/damage_type.json").unwrap())
+            .expect("Failed to parse damage_type.json");
+
     let registry_key_enum = EnumCreator {
         name: "RegistryKey".to_string(),
         value: tags.keys().map(|key| key.to_string()).collect(),
@@ -128,6 +134,10 @@ pub(crate) fn build() -> TokenStream {
                         let id = entities.get(v).unwrap().id;
                         quote! { #id }
                     }).collect::<Vec<_>>(),
+                    t if t == "damage_type" => values.iter().map(|v| {
+                        let id = u16::from(damage_types.get(v).unwrap().id);
+                        quote! { #id }
+                    }).collect::<Vec<_>>(),
                     &_ => Vec::new(),
                 };
                 let mapped_name = format_ident!("{}", tag_name.replace(":", "_").replace("/", "_").to_uppercase());
@@ -4,14 +4,18 @@ use crate::attributes::Attributes;
 use crate::data_component::DataComponent;
 use crate::data_component::DataComponent::*;
 use crate::entity_type::EntityType;
+use crate::item::Item;
 use crate::tag::{Tag, Taggable};
 use crate::{AttributeModifierSlot, Block, Enchantment};
 use crc_fast::CrcAlgorithm::Crc32Iscsi;
 use crc_fast::Digest;
+use pumpkin_nbt::Nbt;
 use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::deserializer::from_bytes_unnamed;
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_util::registry::RegistryEntryList;
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::style::Style;
+use pumpkin_util::text::{TextComponent, TextContent};
 use serde::de::SeqAccess;
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Serialize, de};
@@ -19,6 +23,7 @@ use std::any::Any;
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::io::Cursor;
 
 pub trait DataComponentImpl: Send + Sync + Debug {
     fn write_data(&self) -> NbtTag {
@@ -43,6 +48,11 @@ pub fn read_data(id: DataComponent, data: &NbtTag) -> Option<Box<dyn DataCompone
         MaxStackSize => Some(MaxStackSizeImpl::read_data(data)?.to_dyn()),
         Enchantments => Some(EnchantmentsImpl::read_data(data)?.to_dyn()),
         Damage => Some(DamageImpl::read_data(data)?.to_dyn()),
+        PotDecorations => Some(PotDecorationsImpl::read_data(data)?.to_dyn()),
+        CustomData => Some(CustomDataImpl::read_data(data)?.to_dyn()),
+        CustomName => Some(CustomNameImpl::read_data(data)?.to_dyn()),
+        WritableBookContent => Some(WritableBookContentImpl::read_data(data)?.to_dyn()),
+        WrittenBookContent => Some(WrittenBookContentImpl::read_data(data)?.to_dyn()),
         _ => todo!(),
     }
 }
@@ -51,7 +61,10 @@ pub fn read_data(id: DataComponent, data: &NbtTag) -> Option<Box<dyn DataCompone
 macro_rules! default_impl {
     ($t: ident) => {
         fn equal(&self, other: &dyn DataComponentImpl) -> bool {
-            self == get::<Self>(other)
+            other
+                .as_any()
+                .downcast_ref::<Self>()
+                .is_some_and(|other| self == other)
         }
         #[inline]
         fn get_enum() -> DataComponent
@@ -98,9 +111,69 @@ pub fn get<T: DataComponentImpl + 'static>(value: &dyn DataComponentImpl) -> &T
 pub fn get_mut<T: DataComponentImpl + 'static>(value: &mut dyn DataComponentImpl) -> &mut T {
     value.as_mut_any().downcast_mut::<T>().unwrap()
 }
+/// A component the server can't read yet, kept as the network bytes it was received as so it can
+/// be sent back to clients unchanged.
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct CustomDataImpl;
+pub struct RawComponentImpl {
+    pub id: DataComponent,
+    pub bytes: Vec<u8>,
+}
+impl DataComponentImpl for RawComponentImpl {
+    fn write_data(&self) -> NbtTag {
+        NbtTag::ByteArray(self.bytes.clone().into_boxed_slice())
+    }
+    /// Not the hash vanilla computes for the actual component, so clients comparing hashes resend
+    /// stacks carrying raw components.
+    fn get_hash(&self) -> i32 {
+        get_nbt_hash(&self.write_data()) as i32
+    }
+    fn equal(&self, other: &dyn DataComponentImpl) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|other| self == other)
+    }
+    fn get_enum() -> DataComponent
+    where
+        Self: Sized,
+    {
+        panic!("raw components stand in for any component, so they can't be looked up by type")
+    }
+    fn get_self_enum(&self) -> DataComponent {
+        self.id
+    }
+    fn to_dyn(self) -> Box<dyn DataComponentImpl> {
+        Box::new(self)
+    }
+    fn clone_dyn(&self) -> Box<dyn DataComponentImpl> {
+        Box::new(self.clone())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Free-form NBT that vanilla never interprets, so plugins can attach their own data to stacks.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CustomDataImpl {
+    pub data: NbtCompound,
+}
+impl CustomDataImpl {
+    fn read_data(data: &NbtTag) -> Option<Self> {
+        data.extract_compound()
+            .map(|data| Self { data: data.clone() })
+    }
+}
 impl DataComponentImpl for CustomDataImpl {
+    fn write_data(&self) -> NbtTag {
+        NbtTag::Compound(self.data.clone())
+    }
+    fn get_hash(&self) -> i32 {
+        get_compound_hash(&self.data) as i32
+    }
     default_impl!(CustomData);
 }
 
@@ -152,10 +225,20 @@ impl DataComponentImpl for DamageImpl {
 pub struct UnbreakableImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct CustomNameImpl {
-    // TODO make TextComponent const
-    pub name: &'static str,
+    pub name: TextComponent,
+}
+impl CustomNameImpl {
+    fn read_data(data: &NbtTag) -> Option<Self> {
+        read_text_component(data).map(|name| Self { name })
+    }
 }
 impl DataComponentImpl for CustomNameImpl {
+    fn write_data(&self) -> NbtTag {
+        write_text_component(&self.name)
+    }
+    fn get_hash(&self) -> i32 {
+        get_nbt_hash(&self.write_data()) as i32
+    }
     default_impl!(CustomName);
 }
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -169,7 +252,12 @@ impl DataComponentImpl for ItemNameImpl {
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct ItemModelImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct LoreImpl;
+pub struct LoreImpl {
+    pub lines: Vec<TextComponent>,
+}
+impl DataComponentImpl for LoreImpl {
+    default_impl!(Lore);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct RarityImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -207,6 +295,91 @@ fn get_i32_hash(val: i32) -> u32 {
     digest.finalize() as u32
 }
 
+/// Hashes `compound` the way vanilla's `HashOps` does, where map entries are sorted by the
+/// hashes of their keys and then their values.
+fn get_compound_hash(compound: &NbtCompound) -> u32 {
+    let mut entries: Vec<(u32, u32)> = compound
+        .child_tags
+        .iter()
+        .map(|(key, value)| (get_utf16_str_hash(key), get_nbt_hash(value)))
+        .collect();
+    entries.sort_unstable();
+
+    let mut digest = Digest::new(Crc32Iscsi);
+    digest.update(&[2u8]);
+    for (key, value) in entries {
+        digest.update(&key.to_le_bytes());
+        digest.update(&value.to_le_bytes());
+    }
+    digest.update(&[3u8]);
+    digest.finalize() as u32
+}
+
+/// Like [`get_str_hash`], but also correct for strings that are not ASCII.
+fn get_utf16_str_hash(val: &str) -> u32 {
+    let mut digest = Digest::new(Crc32Iscsi);
+    digest.update(&[12u8]);
+    digest.update(&(val.encode_utf16().count() as u32).to_le_bytes());
+    for unit in val.encode_utf16() {
+        digest.update(&unit.to_le_bytes());
+    }
+    digest.finalize() as u32
+}
+
+fn get_nbt_hash(tag: &NbtTag) -> u32 {
+    let mut digest = Digest::new(Crc32Iscsi);
+    match tag {
+        NbtTag::End => digest.update(&[1u8]),
+        NbtTag::Byte(val) => digest.update(&[6u8, *val as u8]),
+        NbtTag::Short(val) => {
+            digest.update(&[7u8]);
+            digest.update(&val.to_le_bytes());
+        }
+        NbtTag::Int(val) => return get_i32_hash(*val),
+        NbtTag::Long(val) => {
+            digest.update(&[9u8]);
+            digest.update(&val.to_le_bytes());
+        }
+        NbtTag::Float(val) => {
+            digest.update(&[10u8]);
+            digest.update(&val.to_bits().to_le_bytes());
+        }
+        NbtTag::Double(val) => {
+            digest.update(&[11u8]);
+            digest.update(&val.to_bits().to_le_bytes());
+        }
+        NbtTag::String(val) => return get_utf16_str_hash(val),
+        NbtTag::List(list) => {
+            digest.update(&[4u8]);
+            for element in list {
+                digest.update(&get_nbt_hash(element).to_le_bytes());
+            }
+            digest.update(&[5u8]);
+        }
+        NbtTag::Compound(compound) => return get_compound_hash(compound),
+        NbtTag::ByteArray(vals) => {
+            digest.update(&[14u8]);
+            digest.update(vals);
+            digest.update(&[15u8]);
+        }
+        NbtTag::IntArray(vals) => {
+            digest.update(&[16u8]);
+            for val in vals {
+                digest.update(&val.to_le_bytes());
+            }
+            digest.update(&[17u8]);
+        }
+        NbtTag::LongArray(vals) => {
+            digest.update(&[18u8]);
+            for val in vals {
+                digest.update(&val.to_le_bytes());
+            }
+            digest.update(&[19u8]);
+        }
+    }
+    digest.finalize() as u32
+}
+
 #[test]
 fn test_hash() {
     assert_eq!(get_str_hash("minecraft:sharpness"), 2734053906u32);
@@ -219,6 +392,8 @@ fn test_hash() {
         -1580618251i32
     );
     assert_eq!(MaxStackSizeImpl { size: 99 }.get_hash(), -1632321551i32);
+    assert_eq!(get_utf16_str_hash("minecraft:sharpness"), 2734053906u32);
+    assert_eq!(get_nbt_hash(&NbtTag::Int(3)), 3795317917u32);
 }
 
 impl DataComponentImpl for EnchantmentsImpl {
@@ -276,8 +451,26 @@ pub struct AttributeModifiersImpl {
 impl DataComponentImpl for AttributeModifiersImpl {
     default_impl!(AttributeModifiers);
 }
-#[derive(Clone, Debug, Hash, PartialEq)]
-pub struct CustomModelDataImpl;
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomModelDataImpl {
+    pub floats: Vec<f32>,
+    pub flags: Vec<bool>,
+    pub strings: Vec<String>,
+    pub colors: Vec<i32>,
+}
+impl DataComponentImpl for CustomModelDataImpl {
+    default_impl!(CustomModelData);
+}
+impl Hash for CustomModelDataImpl {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for float in &self.floats {
+            float.to_bits().hash(state);
+        }
+        self.flags.hash(state);
+        self.strings.hash(state);
+        self.colors.hash(state);
+    }
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct TooltipDisplayImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -285,7 +478,12 @@ pub struct RepairCostImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct CreativeSlotLockImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct EnchantmentGlintOverrideImpl;
+pub struct EnchantmentGlintOverrideImpl {
+    pub has_glint: bool,
+}
+impl DataComponentImpl for EnchantmentGlintOverrideImpl {
+    default_impl!(EnchantmentGlintOverride);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct IntangibleProjectileImpl;
 #[derive(Clone, Debug, PartialEq)]
@@ -608,10 +806,201 @@ pub struct PotionContentsImpl;
 pub struct PotionDurationScaleImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct SuspiciousStewEffectsImpl;
+/// Player written text together with what players that filter text see instead, like vanilla's
+/// `Filterable`.
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct WritableBookContentImpl;
-#[derive(Clone, Debug, Hash, PartialEq)]
-pub struct WrittenBookContentImpl;
+pub struct Filterable<T> {
+    pub raw: T,
+    /// `None` if nothing was filtered
+    pub filtered: Option<T>,
+}
+impl<T> Filterable<T> {
+    #[must_use]
+    pub const fn passthrough(raw: T) -> Self {
+        Self {
+            raw,
+            filtered: None,
+        }
+    }
+
+    /// The text shown to a player, depending on whether they filter text.
+    #[must_use]
+    pub fn get(&self, filtering: bool) -> &T {
+        if filtering {
+            self.filtered.as_ref().unwrap_or(&self.raw)
+        } else {
+            &self.raw
+        }
+    }
+
+    #[must_use]
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Filterable<U> {
+        Filterable {
+            raw: f(self.raw),
+            filtered: self.filtered.map(f),
+        }
+    }
+
+    fn write_data(&self, write: impl Fn(&T) -> NbtTag) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        compound.put("raw", write(&self.raw));
+        if let Some(filtered) = &self.filtered {
+            compound.put("filtered", write(filtered));
+        }
+        NbtTag::Compound(compound)
+    }
+
+    /// Like vanilla, this also accepts just the raw text.
+    fn read_data(data: &NbtTag, read: impl Fn(&NbtTag) -> Option<T>) -> Option<Self> {
+        if let Some(compound) = data.extract_compound()
+            && let Some(raw) = compound.get("raw")
+        {
+            return Some(Self {
+                raw: read(raw)?,
+                filtered: match compound.get("filtered") {
+                    Some(filtered) => Some(read(filtered)?),
+                    None => None,
+                },
+            });
+        }
+        read(data).map(Self::passthrough)
+    }
+}
+
+/// Stores plain text components as strings, like vanilla does.
+fn write_text_component(text: &TextComponent) -> NbtTag {
+    if let TextContent::Text { text: plain } = &text.0.content
+        && text.0.style == Style::default()
+        && text.0.extra.is_empty()
+    {
+        return NbtTag::String(plain.to_string());
+    }
+    from_bytes_unnamed::<NbtCompound>(Cursor::new(text.encode())).map_or_else(
+        |_| NbtTag::String(text.clone().get_text()),
+        NbtTag::Compound,
+    )
+}
+
+fn read_text_component(data: &NbtTag) -> Option<TextComponent> {
+    match data {
+        NbtTag::String(text) => Some(TextComponent::text(text.clone())),
+        NbtTag::Compound(compound) => {
+            let bytes = Nbt::new(String::new(), compound.clone()).write_unnamed();
+            from_bytes_unnamed(Cursor::new(bytes)).ok()
+        }
+        _ => None,
+    }
+}
+
+fn read_string(data: &NbtTag) -> Option<String> {
+    data.extract_string().map(str::to_string)
+}
+
+/// The pages of a book and quill.
+#[derive(Clone, Debug, Default, Hash, PartialEq)]
+pub struct WritableBookContentImpl {
+    pub pages: Vec<Filterable<String>>,
+}
+impl WritableBookContentImpl {
+    pub const MAX_PAGES: usize = 100;
+    /// In characters
+    pub const MAX_PAGE_LENGTH: usize = 1024;
+
+    fn read_data(data: &NbtTag) -> Option<Self> {
+        let pages = match data.extract_compound()?.get_list("pages") {
+            Some(pages) => pages
+                .iter()
+                .map(|page| Filterable::read_data(page, read_string))
+                .collect::<Option<_>>()?,
+            None => Vec::new(),
+        };
+        Some(Self { pages })
+    }
+}
+impl DataComponentImpl for WritableBookContentImpl {
+    fn write_data(&self) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        if !self.pages.is_empty() {
+            compound.put_list(
+                "pages",
+                self.pages
+                    .iter()
+                    .map(|page| page.write_data(|page| NbtTag::String(page.clone())))
+                    .collect(),
+            );
+        }
+        NbtTag::Compound(compound)
+    }
+    fn get_hash(&self) -> i32 {
+        get_nbt_hash(&self.write_data()) as i32
+    }
+    default_impl!(WritableBookContent);
+}
+/// The contents of a signed book.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct WrittenBookContentImpl {
+    pub title: Filterable<String>,
+    pub author: String,
+    /// 0 for the original, counting up for copies of it
+    pub generation: i32,
+    pub pages: Vec<Filterable<TextComponent>>,
+    /// Whether selectors and scores in the pages were already resolved
+    pub resolved: bool,
+}
+impl WrittenBookContentImpl {
+    /// In characters
+    pub const MAX_TITLE_LENGTH: usize = 32;
+    pub const MAX_GENERATION: i32 = 3;
+
+    fn read_data(data: &NbtTag) -> Option<Self> {
+        let compound = data.extract_compound()?;
+        let pages = match compound.get_list("pages") {
+            Some(pages) => pages
+                .iter()
+                .map(|page| Filterable::read_data(page, read_text_component))
+                .collect::<Option<_>>()?,
+            None => Vec::new(),
+        };
+        Some(Self {
+            title: Filterable::read_data(compound.get("title")?, read_string)?,
+            author: compound.get_string("author")?.to_string(),
+            generation: compound.get_int("generation").unwrap_or(0),
+            pages,
+            resolved: compound.get_bool("resolved").unwrap_or(false),
+        })
+    }
+}
+impl DataComponentImpl for WrittenBookContentImpl {
+    /// Leaves out fields that have their default value, like vanilla, so the hash matches.
+    fn write_data(&self) -> NbtTag {
+        let mut compound = NbtCompound::new();
+        compound.put(
+            "title",
+            self.title.write_data(|title| NbtTag::String(title.clone())),
+        );
+        compound.put_string("author", self.author.clone());
+        if self.generation != 0 {
+            compound.put_int("generation", self.generation);
+        }
+        if !self.pages.is_empty() {
+            compound.put_list(
+                "pages",
+                self.pages
+                    .iter()
+                    .map(|page| page.write_data(write_text_component))
+                    .collect(),
+            );
+        }
+        if self.resolved {
+            compound.put_bool("resolved", true);
+        }
+        NbtTag::Compound(compound)
+    }
+    fn get_hash(&self) -> i32 {
+        get_nbt_hash(&self.write_data()) as i32
+    }
+    default_impl!(WrittenBookContent);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct TrimImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -653,8 +1042,55 @@ pub struct NoteBlockSoundImpl;
 pub struct BannerPatternsImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct BaseColorImpl;
+/// The sherds on the four sides of a decorated pot, bricks stand for undecorated sides.
 #[derive(Clone, Debug, Hash, PartialEq)]
-pub struct PotDecorationsImpl;
+pub struct PotDecorationsImpl {
+    /// Ordered back, left, right and front, like in vanilla
+    pub sherds: [&'static Item; 4],
+}
+impl Default for PotDecorationsImpl {
+    fn default() -> Self {
+        Self {
+            sherds: [&Item::BRICK; 4],
+        }
+    }
+}
+impl PotDecorationsImpl {
+    /// Reads a list of up to four item ids, missing sides are bricks.
+    pub fn read_data(data: &NbtTag) -> Option<Self> {
+        let mut decorations = Self::default();
+        for (side, id) in decorations.sherds.iter_mut().zip(data.extract_list()?) {
+            *side = Item::from_registry_key(id.extract_string()?)?;
+        }
+        Some(decorations)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sherds.iter().all(|sherd| *sherd == &Item::BRICK)
+    }
+}
+impl DataComponentImpl for PotDecorationsImpl {
+    fn write_data(&self) -> NbtTag {
+        NbtTag::List(
+            self.sherds
+                .iter()
+                .map(|sherd| NbtTag::String(format!("minecraft:{}", sherd.registry_key)))
+                .collect(),
+        )
+    }
+    fn get_hash(&self) -> i32 {
+        let mut digest = Digest::new(Crc32Iscsi);
+        digest.update(&[4u8]);
+        for sherd in self.sherds {
+            digest
+                .update(&get_str_hash(&format!("minecraft:{}", sherd.registry_key)).to_le_bytes());
+        }
+        digest.update(&[5u8]);
+        digest.finalize() as i32
+    }
+    default_impl!(PotDecorations);
+}
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub struct ContainerImpl;
 #[derive(Clone, Debug, Hash, PartialEq)]
@@ -0,0 +1,341 @@
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, AtomicU8, Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::data_component_impl::{CustomNameImpl, DataComponentImpl};
+use pumpkin_data::screen::WindowType;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::block::entities::PropertyDelegate;
+use pumpkin_world::inventory::{Inventory, new_slot};
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+use crate::crafting::crafting_inventory::CraftingInventory;
+use crate::player::player_inventory::PlayerInventory;
+use crate::screen_handler::{
+    InventoryPlayer, ScreenHandler, ScreenHandlerBehaviour, ScreenHandlerListener, ScreenProperty,
+};
+use crate::slot::{NormalSlot, Slot};
+
+/// The longest name an item can be given, in characters.
+pub const MAX_NAME_LENGTH: usize = 50;
+
+/// The levels renaming an item costs.
+const RENAME_COST: i32 = 1;
+
+/// The levels the output costs, shown by the client as property 0.
+#[derive(Debug, Default)]
+struct LevelCost(AtomicI32);
+
+impl PropertyDelegate for LevelCost {
+    fn get_property(&self, _index: i32) -> i32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set_property(&self, _index: i32, value: i32) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    fn get_properties_size(&self) -> i32 {
+        1
+    }
+}
+
+/// The output slot of an anvil, holding the renamed input item.
+///
+/// Note: Only renaming is implemented so far, so nothing comes out while there is an item in the
+/// second input slot. Like [`crate::crafting::crafting_screen_handler::ResultSlot`], the result is
+/// stored in the slot directly.
+#[derive(Debug)]
+pub struct AnvilOutputSlot {
+    inventory: Arc<dyn Inventory>,
+    id: AtomicU8,
+    result: Arc<OrderedMutex<ItemStack>>,
+    /// What the player typed into the name field, `None` until they typed anything
+    name: Mutex<Option<String>>,
+    level_cost: Arc<LevelCost>,
+}
+
+impl AnvilOutputSlot {
+    fn new(inventory: Arc<dyn Inventory>) -> Self {
+        Self {
+            inventory,
+            id: AtomicU8::new(0),
+            result: new_slot(ItemStack::EMPTY.clone()),
+            name: Mutex::new(None),
+            level_cost: Arc::new(LevelCost::default()),
+        }
+    }
+
+    /// AnvilMenu#createResult
+    async fn refill_output(&self) -> ItemStack {
+        let input = self.inventory.get_stack(0).await.lock().await.clone();
+        let has_addition = !self.inventory.get_stack(1).await.lock().await.is_empty();
+
+        let mut cost = 0;
+        let mut result = input.clone();
+        if !input.is_empty() && !has_addition {
+            let current_name = input.get_data_component::<CustomNameImpl>();
+            match self.name.lock().await.as_deref() {
+                Some(name) if !name.trim().is_empty() => {
+                    if current_name.is_none_or(|current| current.name.clone().get_text() != name) {
+                        result.set_data_component(
+                            CustomNameImpl {
+                                name: TextComponent::text(name.to_string()),
+                            }
+                            .to_dyn(),
+                        );
+                        cost = RENAME_COST;
+                    }
+                }
+                // Clearing the name field removes the custom name
+                Some(_) if current_name.is_some() => {
+                    result.remove_data_component::<CustomNameImpl>();
+                    cost = RENAME_COST;
+                }
+                _ => {}
+            }
+        }
+        if cost == 0 {
+            result = ItemStack::EMPTY.clone();
+        }
+
+        self.level_cost.0.store(cost, Ordering::Relaxed);
+        *self.result.lock().await = result.clone();
+        result
+    }
+}
+
+#[async_trait]
+impl Slot for AnvilOutputSlot {
+    fn get_inventory(&self) -> Arc<dyn Inventory> {
+        self.inventory.clone()
+    }
+
+    fn get_index(&self) -> usize {
+        999 // this slot does not belong to any inventory
+    }
+
+    fn set_id(&self, id: usize) {
+        self.id.store(id as u8, Ordering::Relaxed);
+    }
+
+    async fn on_take_item(&self, player: &dyn InventoryPlayer, _stack: &ItemStack) {
+        if !player.has_infinite_materials() {
+            player
+                .add_experience_levels(-self.level_cost.0.load(Ordering::Relaxed))
+                .await;
+        }
+        // TODO: Damage the anvil and play its sound
+        self.inventory.set_stack(0, ItemStack::EMPTY.clone()).await;
+        self.refill_output().await;
+        self.mark_dirty().await;
+    }
+
+    async fn can_insert(&self, _stack: &ItemStack) -> bool {
+        false
+    }
+
+    async fn get_stack(&self) -> Arc<OrderedMutex<ItemStack>> {
+        self.result.clone()
+    }
+
+    async fn get_cloned_stack(&self) -> ItemStack {
+        self.result.lock().await.clone()
+    }
+
+    async fn has_stack(&self) -> bool {
+        !self.result.lock().await.is_empty()
+    }
+
+    async fn set_stack(&self, _stack: ItemStack) {
+        self.refill_output().await;
+    }
+
+    async fn set_stack_prev(&self, _stack: ItemStack, _previous_stack: ItemStack) {
+        self.refill_output().await;
+    }
+
+    async fn mark_dirty(&self) {
+        self.inventory.mark_dirty();
+    }
+
+    async fn take_stack(&self, _amount: u8) -> ItemStack {
+        // The output can't be split
+        self.result.lock().await.clone()
+    }
+
+    async fn can_take_items(&self, player: &dyn InventoryPlayer) -> bool {
+        let cost = self.level_cost.0.load(Ordering::Relaxed);
+        cost > 0 && (player.has_infinite_materials() || player.get_experience_level() >= cost)
+    }
+}
+
+#[async_trait]
+impl ScreenHandlerListener for AnvilOutputSlot {
+    async fn on_slot_update(
+        &self,
+        screen_handler: &ScreenHandlerBehaviour,
+        slot: u8,
+        _stack: ItemStack,
+    ) {
+        if slot < 2 {
+            let result = self.refill_output().await;
+
+            let next_revision = screen_handler.next_revision();
+            if let Some(sync_handler) = screen_handler.sync_handler.as_ref() {
+                sync_handler
+                    .update_slot(screen_handler, 2, &result, next_revision)
+                    .await;
+            }
+        }
+    }
+
+    async fn on_property_update(
+        &self,
+        screen_handler: &ScreenHandlerBehaviour,
+        property: u8,
+        value: i32,
+    ) {
+        if let Some(sync_handler) = screen_handler.sync_handler.as_ref() {
+            sync_handler
+                .update_property(screen_handler, property as i32, value)
+                .await;
+        }
+    }
+}
+
+// AnvilMenu
+pub struct AnvilScreenHandler {
+    behaviour: ScreenHandlerBehaviour,
+    inventory: Arc<dyn Inventory>,
+    output: Arc<AnvilOutputSlot>,
+}
+
+impl AnvilScreenHandler {
+    pub async fn new(sync_id: u8, player_inventory: &Arc<PlayerInventory>) -> Self {
+        let inventory: Arc<dyn Inventory> = Arc::new(CraftingInventory::new(2, 1));
+        let output = Arc::new(AnvilOutputSlot::new(inventory.clone()));
+
+        let mut handler = Self {
+            behaviour: ScreenHandlerBehaviour::new(sync_id, Some(WindowType::Anvil)),
+            inventory: inventory.clone(),
+            output: output.clone(),
+        };
+
+        handler.add_slot(Arc::new(NormalSlot::new(inventory.clone(), 0)));
+        handler.add_slot(Arc::new(NormalSlot::new(inventory, 1)));
+        handler.add_slot(output.clone());
+        handler.add_property(ScreenProperty::new(output.level_cost.clone(), 0));
+        handler.add_listener(output).await;
+
+        let player_inventory: Arc<dyn Inventory> = player_inventory.clone();
+        handler.add_player_slots(&player_inventory);
+
+        handler
+    }
+
+    /// Sets the name typed into the name field, which the output gets. Control characters are
+    /// removed like in vanilla, and names longer than [`MAX_NAME_LENGTH`] are ignored.
+    pub async fn set_new_item_name(&self, name: &str) {
+        let name: String = name
+            .chars()
+            .filter(|c| *c != '§' && *c >= ' ' && *c != '\x7F')
+            .collect();
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return;
+        }
+        *self.output.name.lock().await = Some(name);
+        self.output.refill_output().await;
+    }
+}
+
+#[async_trait]
+impl ScreenHandler for AnvilScreenHandler {
+    async fn on_closed(&mut self, player: &dyn InventoryPlayer) {
+        self.default_on_closed(player).await;
+        self.drop_inventory(player, self.inventory.clone()).await;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_behaviour(&self) -> &ScreenHandlerBehaviour {
+        &self.behaviour
+    }
+
+    fn get_behaviour_mut(&mut self) -> &mut ScreenHandlerBehaviour {
+        &mut self.behaviour
+    }
+
+    /// ItemCombinerMenu#quickMoveStack
+    async fn quick_move(&mut self, player: &dyn InventoryPlayer, slot_index: i32) -> ItemStack {
+        const OUTPUT_SLOT: i32 = 2;
+        const PLAYER_SLOTS_START: i32 = 3;
+        const HOTBAR_START: i32 = 30;
+        const PLAYER_SLOTS_END: i32 = 39;
+
+        let slot = self.get_behaviour().slots[slot_index as usize].clone();
+        if !slot.has_stack().await {
+            return ItemStack::EMPTY.clone();
+        }
+        if slot_index == OUTPUT_SLOT && !slot.can_take_items(player).await {
+            return ItemStack::EMPTY.clone();
+        }
+
+        let slot_stack = slot.get_stack().await;
+        let mut slot_stack = slot_stack.lock().await;
+        let stack_prev = slot_stack.clone();
+
+        let moved = if slot_index <= OUTPUT_SLOT {
+            self.insert_item(
+                &mut slot_stack,
+                PLAYER_SLOTS_START,
+                PLAYER_SLOTS_END,
+                slot_index == OUTPUT_SLOT,
+            )
+            .await
+        } else if self
+            .insert_item(&mut slot_stack, 0, OUTPUT_SLOT, false)
+            .await
+        {
+            true
+        } else if slot_index < HOTBAR_START {
+            self.insert_item(&mut slot_stack, HOTBAR_START, PLAYER_SLOTS_END, false)
+                .await
+        } else {
+            self.insert_item(&mut slot_stack, PLAYER_SLOTS_START, HOTBAR_START, false)
+                .await
+        };
+        if !moved {
+            return ItemStack::EMPTY.clone();
+        }
+
+        let stack = slot_stack.clone();
+        drop(slot_stack); // release the lock before calling other methods
+
+        if slot_index == OUTPUT_SLOT {
+            slot.on_take_item(player, &stack_prev).await;
+            if !stack.is_empty() {
+                player.drop_item(stack, false).await;
+            }
+            return stack_prev;
+        }
+
+        if stack.is_empty() {
+            slot.set_stack_prev(ItemStack::EMPTY.clone(), stack_prev.clone())
+                .await;
+        } else {
+            slot.mark_dirty().await;
+        }
+        if stack.item_count == stack_prev.item_count {
+            return ItemStack::EMPTY.clone();
+        }
+        slot.on_take_item(player, &stack).await;
+
+        stack_prev
+    }
+}
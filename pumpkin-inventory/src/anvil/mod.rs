@@ -0,0 +1 @@
+pub mod anvil_screen_handler;
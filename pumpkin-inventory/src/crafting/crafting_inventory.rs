@@ -2,10 +2,10 @@ use std::any::Any;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_world::{inventory::split_stack, item::ItemStack};
-use tokio::sync::Mutex;
 
-use pumpkin_world::inventory::{Clearable, Inventory};
+use pumpkin_world::inventory::{Clearable, Inventory, new_slot};
 
 use super::recipes::RecipeInputInventory;
 
@@ -13,7 +13,7 @@ use super::recipes::RecipeInputInventory;
 pub struct CraftingInventory {
     pub width: u8,
     pub height: u8,
-    pub items: Vec<Arc<Mutex<ItemStack>>>,
+    pub items: Vec<Arc<OrderedMutex<ItemStack>>>,
 }
 
 impl CraftingInventory {
@@ -25,7 +25,7 @@ impl CraftingInventory {
                 // Creates a Vec with different Mutexes for each slot
                 let mut v = Vec::with_capacity(width as usize * height as usize);
                 (0..width as usize * height as usize)
-                    .for_each(|_| v.push(Arc::new(Mutex::new(ItemStack::EMPTY.clone()))));
+                    .for_each(|_| v.push(new_slot(ItemStack::EMPTY.clone())));
                 v
             },
         }
@@ -48,7 +48,7 @@ impl Inventory for CraftingInventory {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
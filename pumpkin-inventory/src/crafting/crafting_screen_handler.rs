@@ -2,7 +2,7 @@ use std::any::Any;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU8, Ordering};
 
-use super::recipes::{RecipeFinderScreenHandler, RecipeInputInventory};
+use super::recipes::{RecipeBook, RecipeFinderScreenHandler, RecipeInputInventory};
 use crate::crafting::crafting_inventory::CraftingInventory;
 use crate::player::player_inventory::PlayerInventory;
 use crate::screen_handler::{
@@ -15,9 +15,9 @@ use pumpkin_data::recipes::{CraftingRecipeTypes, RECIPES_CRAFTING, RecipeResultS
 use pumpkin_data::screen::WindowType;
 use pumpkin_data::tag;
 use pumpkin_data::tag::Taggable;
-use pumpkin_world::inventory::Inventory;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_world::inventory::{Inventory, new_slot};
 use pumpkin_world::item::ItemStack;
-use tokio::sync::Mutex;
 
 /// CraftingResultSlot.java
 ///
@@ -28,8 +28,10 @@ use tokio::sync::Mutex;
 pub struct ResultSlot {
     pub inventory: Arc<dyn RecipeInputInventory>,
     pub id: AtomicU8,
-    pub result: Arc<Mutex<ItemStack>>,
+    pub result: Arc<OrderedMutex<ItemStack>>,
     recipe_cache: AtomicCell<Option<&'static CraftingRecipeTypes>>,
+    /// The recipes the crafting player has unlocked, `None` lets anything be crafted
+    recipe_book: Option<Arc<RecipeBook>>,
 }
 
 fn is_symmetrical_horizontally(pattern: &'static [&'static str]) -> bool {
@@ -235,12 +237,16 @@ async fn recipe_matches<'a>(
 impl ResultSlot {
     fn stat_crafted(&self, _crafted_amount: u8, _player: &dyn InventoryPlayer) {}
 
-    pub fn new(inventory: Arc<dyn RecipeInputInventory>) -> Self {
+    pub fn new(
+        inventory: Arc<dyn RecipeInputInventory>,
+        recipe_book: Option<Arc<RecipeBook>>,
+    ) -> Self {
         Self {
             inventory,
             id: AtomicU8::new(0),
-            result: Arc::new(Mutex::new(ItemStack::EMPTY.clone())),
+            result: new_slot(ItemStack::EMPTY.clone()),
             recipe_cache: AtomicCell::new(None),
+            recipe_book,
         }
     }
 
@@ -315,6 +321,12 @@ impl ResultSlot {
         let result = self
             .match_recipe()
             .await
+            // With limited crafting, locked recipes give nothing, whatever the client shows
+            .filter(|(_, recipe)| {
+                self.recipe_book
+                    .as_ref()
+                    .is_none_or(|recipe_book| recipe_book.can_craft(recipe))
+            })
             .map(|x| ItemStack::from(x.0))
             .unwrap_or(ItemStack::EMPTY.clone());
         *self.result.lock().await = result.clone();
@@ -358,7 +370,7 @@ impl Slot for ResultSlot {
         false
     }
 
-    async fn get_stack(&self) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self) -> Arc<OrderedMutex<ItemStack>> {
         self.result.clone()
     }
 
@@ -434,8 +446,12 @@ impl ScreenHandlerListener for ResultSlot {
 pub trait CraftingScreenHandler<I: RecipeInputInventory>:
     RecipeFinderScreenHandler + ScreenHandler
 {
-    async fn add_recipe_slots(&mut self, crafing_inventory: Arc<dyn RecipeInputInventory>) {
-        let result_slot = Arc::new(ResultSlot::new(crafing_inventory.clone()));
+    async fn add_recipe_slots(
+        &mut self,
+        crafing_inventory: Arc<dyn RecipeInputInventory>,
+        recipe_book: Option<Arc<RecipeBook>>,
+    ) {
+        let result_slot = Arc::new(ResultSlot::new(crafing_inventory.clone(), recipe_book));
         self.add_slot(result_slot.clone());
 
         let width = crafing_inventory.get_width();
@@ -458,7 +474,11 @@ pub struct CraftingTableScreenHandler {
 }
 
 impl CraftingTableScreenHandler {
-    pub async fn new(sync_id: u8, player_inventory: &Arc<PlayerInventory>) -> Self {
+    pub async fn new(
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        recipe_book: Option<Arc<RecipeBook>>,
+    ) -> Self {
         let crafting_inventory: Arc<dyn RecipeInputInventory> =
             Arc::new(CraftingInventory::new(3, 3));
 
@@ -468,7 +488,7 @@ impl CraftingTableScreenHandler {
         };
 
         crafting_table_handler
-            .add_recipe_slots(crafting_inventory)
+            .add_recipe_slots(crafting_inventory, recipe_book)
             .await;
 
         // Add player inventory slots
@@ -1,4 +1,16 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+use pumpkin_data::item::Item;
+use pumpkin_data::recipes::{
+    CraftingRecipeTypes, RECIPES_CRAFTING, RecipeCategoryTypes, RecipeIngredientTypes,
+    RecipeResultStruct,
+};
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{IdSet, RecipeBookEntry, RecipeDisplay, SlotDisplay};
 use pumpkin_world::inventory::Inventory;
+use pumpkin_world::item::ItemStack;
 
 // RecipeMatcher.java
 pub struct RecipeMatcher {}
@@ -16,3 +28,337 @@ pub trait RecipeInputInventory: Inventory {
     // createRecipeInput
     // createPositionedRecipeInput
 }
+
+/// The recipes using each item that was asked for, by item id.
+static RECIPES_USING: LazyLock<Mutex<HashMap<u16, Arc<[&'static str]>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The index of each recipe in [`RECIPES_CRAFTING`] by recipe id, which is also the display id
+/// the client knows the recipe by.
+static RECIPE_INDICES: LazyLock<HashMap<&'static str, usize>> = LazyLock::new(|| {
+    RECIPES_CRAFTING
+        .iter()
+        .enumerate()
+        .filter_map(|(index, recipe)| Some((RecipeBook::recipe_id(recipe)?, index)))
+        .collect()
+});
+
+/// The ids of the recipe groups, which the client uses to show recipes of a group together.
+static RECIPE_GROUPS: LazyLock<HashMap<&'static str, i32>> = LazyLock::new(|| {
+    let mut groups = HashMap::new();
+    for recipe in RECIPES_CRAFTING {
+        if let Some(group) = recipe_group(recipe) {
+            let next = groups.len() as i32;
+            groups.entry(group).or_insert(next);
+        }
+    }
+    groups
+});
+
+/// The recipes a player has unlocked, by recipe id, e.g. `minecraft:oak_planks`.
+// ServerRecipeBook.java
+#[derive(Debug, Default)]
+pub struct RecipeBook {
+    unlocked: RwLock<HashSet<String>>,
+    /// Items the player has had, the recipes using them are already unlocked
+    seen_items: Mutex<HashSet<u16>>,
+    /// Whether only unlocked recipes can be crafted, the `doLimitedCrafting` game rule
+    limited_crafting: AtomicBool,
+}
+
+impl RecipeBook {
+    /// The id of the recipe, or `None` for recipes that are not in the recipe book and can always
+    /// be crafted.
+    pub fn recipe_id(recipe: &CraftingRecipeTypes) -> Option<&'static str> {
+        match recipe {
+            CraftingRecipeTypes::CraftingShaped { id, .. }
+            | CraftingRecipeTypes::CraftingShapeless { id, .. }
+            | CraftingRecipeTypes::CraftingTransmute { id, .. } => Some(*id),
+            CraftingRecipeTypes::CraftingDecoratedPot { .. }
+            | CraftingRecipeTypes::CraftingSpecial => None,
+        }
+    }
+
+    /// The display id the client knows the recipe with `id` by, `None` for unknown recipes.
+    pub fn display_id(id: &str) -> Option<VarInt> {
+        RECIPE_INDICES.get(id).map(|index| VarInt(*index as i32))
+    }
+
+    /// The entry for the recipe with `id` in the client's recipe book, `None` for unknown recipes.
+    /// `flags` are the [`RecipeBookEntry`] flags.
+    pub fn display_entry(id: &str, flags: u8) -> Option<RecipeBookEntry> {
+        let index = *RECIPE_INDICES.get(id)?;
+        let recipe = &RECIPES_CRAFTING[index];
+        let crafting_station = item_display(&Item::CRAFTING_TABLE);
+        let (display, requirements): (_, Vec<&RecipeIngredientTypes>) = match recipe {
+            CraftingRecipeTypes::CraftingShaped {
+                key,
+                pattern,
+                result,
+                ..
+            } => {
+                let ingredients: Vec<_> = pattern
+                    .iter()
+                    .flat_map(|row| row.chars())
+                    .map(|c| key.iter().find(|(key, _)| *key == c).map(|(_, i)| i))
+                    .collect();
+                let display = RecipeDisplay::CraftingShaped {
+                    width: VarInt(pattern.first().map_or(0, |row| row.len()) as i32),
+                    height: VarInt(pattern.len() as i32),
+                    ingredients: ingredients
+                        .iter()
+                        .map(|i| i.map_or(SlotDisplay::Empty, ingredient_display))
+                        .collect(),
+                    result: result_display(result),
+                    crafting_station,
+                };
+                (display, ingredients.into_iter().flatten().collect())
+            }
+            CraftingRecipeTypes::CraftingShapeless {
+                ingredients,
+                result,
+                ..
+            } => {
+                let display = RecipeDisplay::CraftingShapeless {
+                    ingredients: ingredients.iter().map(ingredient_display).collect(),
+                    result: result_display(result),
+                    crafting_station,
+                };
+                (display, ingredients.iter().collect())
+            }
+            CraftingRecipeTypes::CraftingTransmute {
+                input,
+                material,
+                result,
+                ..
+            } => {
+                let display = RecipeDisplay::CraftingShapeless {
+                    ingredients: vec![ingredient_display(input), ingredient_display(material)],
+                    result: result_display(result),
+                    crafting_station,
+                };
+                (display, vec![input, material])
+            }
+            CraftingRecipeTypes::CraftingDecoratedPot { .. }
+            | CraftingRecipeTypes::CraftingSpecial => return None,
+        };
+        Some(RecipeBookEntry {
+            display_id: VarInt(index as i32),
+            display,
+            group: recipe_group(recipe).map(|group| VarInt(RECIPE_GROUPS[group])),
+            category: recipe_category(recipe),
+            crafting_requirements: Some(requirements.into_iter().map(ingredient_ids).collect()),
+            flags,
+        })
+    }
+
+    /// Whether the client should show a toast when the recipe with `id` is unlocked.
+    pub fn shows_notification(id: &str) -> bool {
+        RECIPE_INDICES.get(id).is_some_and(|index| {
+            !matches!(
+                RECIPES_CRAFTING[*index],
+                CraftingRecipeTypes::CraftingShaped {
+                    show_notification: false,
+                    ..
+                }
+            )
+        })
+    }
+
+    pub fn set_limited_crafting(&self, limited_crafting: bool) {
+        self.limited_crafting
+            .store(limited_crafting, Ordering::Relaxed);
+    }
+
+    /// Whether the player may craft `recipe`, which is always the case without limited crafting.
+    pub fn can_craft(&self, recipe: &CraftingRecipeTypes) -> bool {
+        !self.limited_crafting.load(Ordering::Relaxed)
+            || Self::recipe_id(recipe).is_none_or(|id| self.is_unlocked(id))
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.read().unwrap().contains(id)
+    }
+
+    /// Unlocks the recipe with `id`, returns whether it was locked before.
+    pub fn unlock(&self, id: &str) -> bool {
+        self.unlocked.write().unwrap().insert(id.to_string())
+    }
+
+    /// Locks the recipe with `id` again, returns whether it was unlocked before.
+    pub fn lock(&self, id: &str) -> bool {
+        self.unlocked.write().unwrap().remove(id)
+    }
+
+    /// The ids of all unlocked recipes, sorted.
+    pub fn unlocked(&self) -> Vec<String> {
+        let mut unlocked: Vec<_> = self.unlocked.read().unwrap().iter().cloned().collect();
+        unlocked.sort_unstable();
+        unlocked
+    }
+
+    /// Unlocks every recipe `item` is an ingredient of, like vanilla's recipe advancements do
+    /// once the player gets hold of an ingredient. Returns the recipes that were unlocked.
+    pub fn unlock_recipes_using(&self, item: &Item) -> Vec<&'static str> {
+        if !self.seen_items.lock().unwrap().insert(item.id) {
+            return Vec::new();
+        }
+        let recipes = RECIPES_USING
+            .lock()
+            .unwrap()
+            .entry(item.id)
+            .or_insert_with(|| recipes_using(item).into())
+            .clone();
+        recipes
+            .iter()
+            .copied()
+            .filter(|id| self.unlock(id))
+            .collect()
+    }
+}
+
+fn recipes_using(item: &Item) -> Vec<&'static str> {
+    let mut recipes: Vec<_> = RECIPES_CRAFTING
+        .iter()
+        .filter(|recipe| ingredients(recipe).iter().any(|i| i.match_item(item)))
+        .filter_map(RecipeBook::recipe_id)
+        .collect();
+    recipes.sort_unstable();
+    recipes.dedup();
+    recipes
+}
+
+fn recipe_group(recipe: &CraftingRecipeTypes) -> Option<&'static str> {
+    match recipe {
+        CraftingRecipeTypes::CraftingShaped { group, .. }
+        | CraftingRecipeTypes::CraftingShapeless { group, .. }
+        | CraftingRecipeTypes::CraftingTransmute { group, .. } => *group,
+        CraftingRecipeTypes::CraftingDecoratedPot { .. } | CraftingRecipeTypes::CraftingSpecial => {
+            None
+        }
+    }
+}
+
+/// The recipe book tab the recipe is shown in.
+fn recipe_category(recipe: &CraftingRecipeTypes) -> VarInt {
+    let category = match recipe {
+        CraftingRecipeTypes::CraftingShaped { category, .. }
+        | CraftingRecipeTypes::CraftingShapeless { category, .. }
+        | CraftingRecipeTypes::CraftingTransmute { category, .. }
+        | CraftingRecipeTypes::CraftingDecoratedPot { category } => category,
+        CraftingRecipeTypes::CraftingSpecial => &RecipeCategoryTypes::Misc,
+    };
+    // The crafting categories of the recipe book category registry
+    VarInt(match category {
+        RecipeCategoryTypes::Building => 0,
+        RecipeCategoryTypes::Restone => 1,
+        RecipeCategoryTypes::Equipment => 2,
+        RecipeCategoryTypes::Misc | RecipeCategoryTypes::Food | RecipeCategoryTypes::Blocks => 3,
+    })
+}
+
+fn item_display(item: &Item) -> SlotDisplay {
+    SlotDisplay::Item(VarInt(i32::from(item.id)))
+}
+
+fn item_id(id: &str) -> Option<VarInt> {
+    Item::from_registry_key(id).map(|item| VarInt(i32::from(item.id)))
+}
+
+fn ingredient_display(ingredient: &RecipeIngredientTypes) -> SlotDisplay {
+    match ingredient {
+        RecipeIngredientTypes::Simple(id) => {
+            item_id(id).map_or(SlotDisplay::Empty, SlotDisplay::Item)
+        }
+        RecipeIngredientTypes::Tagged(tag) => {
+            SlotDisplay::Tag(tag.trim_start_matches('#').to_string())
+        }
+        RecipeIngredientTypes::OneOf(ids) => SlotDisplay::Composite(
+            ids.iter()
+                .filter_map(|id| item_id(id))
+                .map(SlotDisplay::Item)
+                .collect(),
+        ),
+    }
+}
+
+fn ingredient_ids(ingredient: &RecipeIngredientTypes) -> IdSet {
+    match ingredient {
+        RecipeIngredientTypes::Simple(id) => IdSet::Ids(item_id(id).into_iter().collect()),
+        RecipeIngredientTypes::Tagged(tag) => IdSet::Tag(tag.trim_start_matches('#').to_string()),
+        RecipeIngredientTypes::OneOf(ids) => {
+            IdSet::Ids(ids.iter().filter_map(|id| item_id(id)).collect())
+        }
+    }
+}
+
+fn result_display(result: &RecipeResultStruct) -> SlotDisplay {
+    let Some(item) = Item::from_registry_key(result.id) else {
+        return SlotDisplay::Empty;
+    };
+    if result.count == 1 {
+        item_display(item)
+    } else {
+        SlotDisplay::ItemStack(ItemStack::new(result.count, item).into())
+    }
+}
+
+fn ingredients(recipe: &CraftingRecipeTypes) -> Vec<&RecipeIngredientTypes> {
+    match recipe {
+        CraftingRecipeTypes::CraftingShaped { key, .. } => {
+            key.iter().map(|(_, ingredient)| ingredient).collect()
+        }
+        CraftingRecipeTypes::CraftingShapeless { ingredients, .. } => ingredients.iter().collect(),
+        CraftingRecipeTypes::CraftingTransmute {
+            input, material, ..
+        } => vec![input, material],
+        CraftingRecipeTypes::CraftingDecoratedPot { .. } | CraftingRecipeTypes::CraftingSpecial => {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use pumpkin_data::item::Item;
+    use pumpkin_data::recipes::RECIPES_CRAFTING;
+
+    use super::RecipeBook;
+
+    #[test]
+    fn limited_crafting_needs_unlocked_recipes() {
+        let book = RecipeBook::default();
+        let planks = RECIPES_CRAFTING
+            .iter()
+            .find(|recipe| RecipeBook::recipe_id(recipe) == Some("minecraft:oak_planks"))
+            .unwrap();
+
+        assert!(book.can_craft(planks));
+        book.set_limited_crafting(true);
+        assert!(!book.can_craft(planks));
+
+        assert!(
+            book.unlock_recipes_using(&Item::OAK_LOG)
+                .contains(&"minecraft:oak_planks")
+        );
+        assert!(book.can_craft(planks));
+        // Items are only looked at once
+        assert!(book.unlock_recipes_using(&Item::OAK_LOG).is_empty());
+
+        assert!(book.lock("minecraft:oak_planks"));
+        assert!(!book.can_craft(planks));
+    }
+
+    #[test]
+    fn recipe_ids_are_unique() {
+        let mut ids = HashSet::new();
+        for id in RECIPES_CRAFTING.iter().filter_map(RecipeBook::recipe_id) {
+            assert!(ids.insert(id), "{id} is used by several recipes");
+            assert!(RecipeBook::display_entry(id, 0).is_some());
+        }
+        // Later recipes for the same item are numbered
+        assert!(ids.contains("minecraft:stick_2"));
+    }
+}
@@ -1,11 +1,14 @@
-use std::{any::Any, sync::Arc};
+use std::{
+    any::Any,
+    sync::{Arc, Weak},
+};
 
 use async_trait::async_trait;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_world::{
-    inventory::{Clearable, Inventory},
+    inventory::{Clearable, Inventory, InventoryListener},
     item::ItemStack,
 };
-use tokio::sync::Mutex;
 
 #[derive(Debug)]
 pub struct DoubleInventory {
@@ -29,7 +32,7 @@ impl Inventory for DoubleInventory {
         self.first.is_empty().await && self.second.is_empty().await
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         if slot >= self.first.size() {
             self.second.get_stack(slot - self.first.size()).await
         } else {
@@ -72,6 +75,12 @@ impl Inventory for DoubleInventory {
         self.second.mark_dirty();
     }
 
+    /// Subscribes `listener` to both halves, which is told about the half that changed.
+    fn add_listener(&self, listener: Weak<dyn InventoryListener>) -> bool {
+        let first = self.first.add_listener(listener.clone());
+        self.second.add_listener(listener) && first
+    }
+
     fn on_open(&self) {
         self.first.on_open();
         self.second.on_open();
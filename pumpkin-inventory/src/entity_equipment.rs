@@ -1,13 +1,14 @@
 use std::{collections::HashMap, sync::Arc};
 
 use pumpkin_data::data_component_impl::EquipmentSlot;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_world::inventory::new_slot;
 use pumpkin_world::item::ItemStack;
-use tokio::sync::Mutex;
 
 // EntityEquipment.java
 #[derive(Debug, Clone)]
 pub struct EntityEquipment {
-    pub equipment: HashMap<EquipmentSlot, Arc<Mutex<ItemStack>>>,
+    pub equipment: HashMap<EquipmentSlot, Arc<OrderedMutex<ItemStack>>>,
 }
 
 impl Default for EntityEquipment {
@@ -25,18 +26,18 @@ impl EntityEquipment {
 
     pub async fn put(&mut self, slot: &EquipmentSlot, stack: ItemStack) -> ItemStack {
         self.equipment
-            .insert(slot.clone(), Arc::new(Mutex::new(stack)))
-            .unwrap_or(Arc::new(Mutex::new(ItemStack::EMPTY.clone())))
+            .insert(slot.clone(), new_slot(stack))
+            .unwrap_or(new_slot(ItemStack::EMPTY.clone()))
             .lock()
             .await
             .clone()
     }
 
-    pub fn get(&self, slot: &EquipmentSlot) -> Arc<Mutex<ItemStack>> {
+    pub fn get(&self, slot: &EquipmentSlot) -> Arc<OrderedMutex<ItemStack>> {
         self.equipment
             .get(slot)
             .cloned()
-            .unwrap_or(Arc::new(Mutex::new(ItemStack::EMPTY.clone())))
+            .unwrap_or(new_slot(ItemStack::EMPTY.clone()))
     }
 
     pub async fn is_empty(&self) -> bool {
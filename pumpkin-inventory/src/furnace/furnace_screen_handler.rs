@@ -1,7 +1,7 @@
 use std::{any::Any, sync::Arc};
 
 use async_trait::async_trait;
-use pumpkin_data::fuels::is_fuel;
+use pumpkin_data::{fuels::is_fuel, screen::WindowType};
 use pumpkin_world::{block::entities::BlockEntity, inventory::Inventory, item::ItemStack};
 
 use crate::{
@@ -20,8 +20,10 @@ pub struct FurnaceScreenHandler {
 }
 
 impl FurnaceScreenHandler {
+    /// Smokers and blast furnaces use this handler as well, with their own `window_type`.
     pub async fn new(
         sync_id: u8,
+        window_type: WindowType,
         player_inventory: &Arc<PlayerInventory>,
         inventory: Arc<dyn Inventory>,
         furnace_block_entity: Arc<dyn BlockEntity>,
@@ -29,10 +31,7 @@ impl FurnaceScreenHandler {
         let furnace_property_delegate = furnace_block_entity.to_property_delegate().unwrap();
         let mut handler = Self {
             inventory,
-            behaviour: ScreenHandlerBehaviour::new(
-                sync_id,
-                Some(pumpkin_data::screen::WindowType::Furnace),
-            ),
+            behaviour: ScreenHandlerBehaviour::new(sync_id, Some(window_type)),
         };
 
         struct FurnaceScreenListener;
@@ -103,8 +102,9 @@ impl ScreenHandler for FurnaceScreenHandler {
         &mut self.behaviour
     }
 
-    async fn quick_move(&mut self, _player: &dyn InventoryPlayer, slot_index: i32) -> ItemStack {
+    async fn quick_move(&mut self, player: &dyn InventoryPlayer, slot_index: i32) -> ItemStack {
         const FUEL_SLOT: i32 = 1;
+        const OUTPUT_SLOT: i32 = 2;
 
         let mut stack_left = ItemStack::EMPTY.clone();
         let slot = self.get_behaviour().slots[slot_index as usize].clone();
@@ -130,12 +130,18 @@ impl ScreenHandler for FurnaceScreenHandler {
             return ItemStack::EMPTY.clone();
         }
 
+        let taken = stack_left.item_count - stack.item_count;
         if stack.is_empty() {
             drop(stack);
             slot.set_stack(ItemStack::EMPTY.clone()).await;
         } else {
+            drop(stack);
             slot.mark_dirty().await;
         }
+        if slot_index == OUTPUT_SLOT {
+            slot.on_take_item(player, &stack_left.copy_with_count(taken))
+                .await;
+        }
 
         stack_left
     }
@@ -2,9 +2,11 @@ use std::sync::{Arc, atomic::AtomicU8};
 
 use async_trait::async_trait;
 use pumpkin_data::{fuels::is_fuel, item::Item};
-use pumpkin_world::inventory::Inventory;
+use pumpkin_world::{
+    block::entities::furnace::FurnaceBlockEntity, inventory::Inventory, item::ItemStack,
+};
 
-use crate::slot::Slot;
+use crate::{screen_handler::InventoryPlayer, slot::Slot};
 
 #[derive(Debug, Clone, Copy)]
 pub enum FurnaceSlotType {
@@ -50,7 +52,19 @@ impl Slot for FurnaceSlot {
         self.inventory.mark_dirty();
     }
 
-    async fn can_insert(&self, stack: &pumpkin_world::item::ItemStack) -> bool {
+    /// Taking the output pays out the experience the furnace collected.
+    async fn on_take_item(&self, player: &dyn InventoryPlayer, _stack: &ItemStack) {
+        if let FurnaceSlotType::Side = self.slot_type
+            && let Some(furnace) = self.inventory.as_any().downcast_ref::<FurnaceBlockEntity>()
+        {
+            player
+                .award_experience(furnace.take_experience().await)
+                .await;
+        }
+        self.mark_dirty().await;
+    }
+
+    async fn can_insert(&self, stack: &ItemStack) -> bool {
         match self.slot_type {
             FurnaceSlotType::Top => true,
             FurnaceSlotType::Bottom => is_fuel(stack.item.id) || stack.item.id == Item::BUCKET.id,
@@ -1,3 +1,4 @@
+pub mod anvil;
 pub mod container_click;
 pub mod crafting;
 pub mod double;
@@ -4,8 +4,9 @@ use async_trait::async_trait;
 use pumpkin_data::data_component_impl::EquipmentSlot;
 use pumpkin_protocol::java::client::play::CSetPlayerInventory;
 use pumpkin_util::Hand;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_world::inventory::split_stack;
-use pumpkin_world::inventory::{Clearable, Inventory};
+use pumpkin_world::inventory::{Clearable, Inventory, new_slot};
 use pumpkin_world::item::ItemStack;
 use std::any::Any;
 use std::array::from_fn;
@@ -16,7 +17,7 @@ use tokio::sync::Mutex;
 
 #[derive(Debug)]
 pub struct PlayerInventory {
-    pub main_inventory: [Arc<Mutex<ItemStack>>; Self::MAIN_SIZE],
+    pub main_inventory: [Arc<OrderedMutex<ItemStack>>; Self::MAIN_SIZE],
     pub equipment_slots: Arc<HashMap<usize, EquipmentSlot>>,
     selected_slot: AtomicU8,
     pub entity_equipment: Arc<Mutex<EntityEquipment>>,
@@ -34,7 +35,7 @@ impl PlayerInventory {
     ) -> Self {
         Self {
             // Normal syntax can't be used here because Arc doesn't implement Copy
-            main_inventory: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            main_inventory: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             equipment_slots,
             selected_slot: AtomicU8::new(0),
             entity_equipment,
@@ -42,14 +43,14 @@ impl PlayerInventory {
     }
 
     /// getSelectedStack in source
-    pub fn held_item(&self) -> Arc<Mutex<ItemStack>> {
+    pub fn held_item(&self) -> Arc<OrderedMutex<ItemStack>> {
         self.main_inventory
             .get(self.get_selected_slot() as usize)
             .unwrap()
             .clone()
     }
 
-    pub async fn get_stack_in_hand(&self, hand: Hand) -> Arc<Mutex<ItemStack>> {
+    pub async fn get_stack_in_hand(&self, hand: Hand) -> Arc<OrderedMutex<ItemStack>> {
         match hand {
             Hand::Left => self.off_hand_item().await,
             Hand::Right => self.held_item(),
@@ -57,7 +58,7 @@ impl PlayerInventory {
     }
 
     /// getOffHandStack in source
-    pub async fn off_hand_item(&self) -> Arc<Mutex<ItemStack>> {
+    pub async fn off_hand_item(&self) -> Arc<OrderedMutex<ItemStack>> {
         let slot = self
             .equipment_slots
             .get(&PlayerInventory::OFF_HAND_SLOT)
@@ -348,7 +349,7 @@ impl Inventory for PlayerInventory {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         if slot < self.main_inventory.len() {
             self.main_inventory[slot].clone()
         } else {
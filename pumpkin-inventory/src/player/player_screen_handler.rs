@@ -1,7 +1,7 @@
 use super::player_inventory::PlayerInventory;
 use crate::crafting::crafting_inventory::CraftingInventory;
 use crate::crafting::crafting_screen_handler::CraftingScreenHandler;
-use crate::crafting::recipes::{RecipeFinderScreenHandler, RecipeInputInventory};
+use crate::crafting::recipes::{RecipeBook, RecipeFinderScreenHandler, RecipeInputInventory};
 use crate::screen_handler::{InventoryPlayer, ScreenHandler, ScreenHandlerBehaviour};
 use crate::slot::{ArmorSlot, NormalSlot, Slot};
 use async_trait::async_trait;
@@ -42,6 +42,7 @@ impl PlayerScreenHandler {
         player_inventory: &Arc<PlayerInventory>,
         window_type: Option<WindowType>,
         sync_id: u8,
+        recipe_book: Option<Arc<RecipeBook>>,
     ) -> Self {
         let crafting_inventory: Arc<dyn RecipeInputInventory> =
             Arc::new(CraftingInventory::new(2, 2));
@@ -52,7 +53,7 @@ impl PlayerScreenHandler {
         };
 
         player_screen_handler
-            .add_recipe_slots(crafting_inventory)
+            .add_recipe_slots(crafting_inventory, recipe_book)
             .await;
 
         for i in 0..4 {
@@ -1,5 +1,6 @@
 use crate::{
     container_click::MouseClick,
+    crafting::recipes::RecipeBook,
     player::player_inventory::PlayerInventory,
     slot::{NormalSlot, Slot},
     sync_handler::{SyncHandler, TrackedStack},
@@ -17,13 +18,15 @@ use pumpkin_protocol::{
         server::play::SlotActionType,
     },
 };
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::{
     block::entities::PropertyDelegate,
-    inventory::{ComparableInventory, Inventory},
+    inventory::{ComparableInventory, Inventory, InventoryListener, new_slot},
 };
 use std::cmp::max;
+use std::sync::Weak;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::{any::Any, collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
@@ -72,6 +75,19 @@ pub trait InventoryPlayer: Send + Sync {
     async fn enqueue_property_packet(&self, packet: &CSetContainerProperty);
     async fn enqueue_slot_set_packet(&self, packet: &CSetPlayerInventory);
     async fn enqueue_set_held_item_packet(&self, packet: &CSetSelectedSlot);
+    /// The recipes the player has unlocked, `None` lets them craft anything.
+    fn get_recipe_book(&self) -> Option<Arc<RecipeBook>> {
+        None
+    }
+    /// Drops experience orbs worth `amount` at the player, like the experience of a furnace when
+    /// its output is taken.
+    async fn award_experience(&self, _amount: u32) {}
+    /// The experience level of the player, which anvils charge.
+    fn get_experience_level(&self) -> i32 {
+        0
+    }
+    /// Adds `levels` experience levels, negative to take them away.
+    async fn add_experience_levels(&self, _levels: i32) {}
 }
 
 pub async fn offer_or_drop_stack(player: &dyn InventoryPlayer, stack: ItemStack) {
@@ -370,10 +386,29 @@ pub trait ScreenHandler: Send + Sync {
     }
 
     async fn send_content_updates(&mut self) {
+        self.send_slot_updates(true).await;
+    }
+
+    /// Like [`Self::send_content_updates`], but skips the slots of inventories this screen handler
+    /// listens to, as changes to those are sent right away, see [`listen_to_inventories`].
+    async fn send_polled_content_updates(&mut self) {
+        self.send_slot_updates(false).await;
+    }
+
+    async fn send_slot_updates(&mut self, listened_slots: bool) {
         let slots_len = self.get_behaviour().slots.len();
 
         for i in 0..slots_len {
             let slot = self.get_behaviour().slots[i].clone();
+            if !listened_slots
+                && self
+                    .get_behaviour()
+                    .listened_inventories
+                    .iter()
+                    .any(|inventory| Arc::ptr_eq(inventory, &slot.get_inventory()))
+            {
+                continue;
+            }
             let stack = slot.get_cloned_stack().await;
 
             self.update_tracked_slot(i, stack.clone()).await;
@@ -926,7 +961,7 @@ pub struct ScreenHandlerBehaviour {
     pub sync_handler: Option<Arc<SyncHandler>>,
     //TODO: Check if this is needed
     pub tracked_stacks: Vec<ItemStack>,
-    pub cursor_stack: Arc<Mutex<ItemStack>>,
+    pub cursor_stack: Arc<OrderedMutex<ItemStack>>,
     pub previous_tracked_stacks: Vec<TrackedStack>,
     pub previous_cursor_stack: TrackedStack,
     pub revision: AtomicU32,
@@ -935,6 +970,9 @@ pub struct ScreenHandlerBehaviour {
     pub tracked_property_values: Vec<i32>,
     pub window_type: Option<WindowType>,
     pub drag_slots: Vec<u32>,
+    /// The inventories that tell this screen handler about changes, see [`listen_to_inventories`]
+    pub listened_inventories: Vec<Arc<dyn Inventory>>,
+    inventory_listener: Option<Arc<dyn InventoryListener>>,
 }
 
 impl ScreenHandlerBehaviour {
@@ -945,7 +983,7 @@ impl ScreenHandlerBehaviour {
             listeners: Vec::new(),
             sync_handler: None,
             tracked_stacks: Vec::new(),
-            cursor_stack: Arc::new(Mutex::new(ItemStack::EMPTY.clone())),
+            cursor_stack: new_slot(ItemStack::EMPTY.clone()),
             previous_tracked_stacks: Vec::new(),
             previous_cursor_stack: TrackedStack::EMPTY,
             revision: AtomicU32::new(0),
@@ -954,6 +992,8 @@ impl ScreenHandlerBehaviour {
             tracked_property_values: Vec::new(),
             window_type,
             drag_slots: Vec::new(),
+            listened_inventories: Vec::new(),
+            inventory_listener: None,
         }
     }
 
@@ -962,3 +1002,41 @@ impl ScreenHandlerBehaviour {
         self.revision.fetch_and(32767, Ordering::Relaxed) & 32767
     }
 }
+
+/// Sends the changes of a screen handler to its player once an inventory shown in it changed.
+struct InventorySyncListener {
+    screen_handler: Weak<Mutex<dyn ScreenHandler>>,
+}
+
+impl InventoryListener for InventorySyncListener {
+    fn on_inventory_changed(&self, _inventory: &dyn Inventory) {
+        if let Some(screen_handler) = self.screen_handler.upgrade() {
+            tokio::spawn(async move {
+                screen_handler.lock().await.send_content_updates().await;
+            });
+        }
+    }
+}
+
+/// Subscribes `screen_handler` to the inventories of its slots which support listeners, so changes
+/// to them are sent as soon as they happen instead of being found by
+/// [`ScreenHandler::send_polled_content_updates`]. The subscriptions end with the screen handler.
+pub async fn listen_to_inventories(screen_handler: &Arc<Mutex<dyn ScreenHandler>>) {
+    let listener: Arc<dyn InventoryListener> = Arc::new(InventorySyncListener {
+        screen_handler: Arc::downgrade(screen_handler),
+    });
+    let mut screen_handler = screen_handler.lock().await;
+    let behaviour = screen_handler.get_behaviour_mut();
+
+    let mut listened: Vec<Arc<dyn Inventory>> = Vec::new();
+    for slot in &behaviour.slots {
+        let inventory = slot.get_inventory();
+        if !listened.iter().any(|other| Arc::ptr_eq(other, &inventory))
+            && inventory.add_listener(Arc::downgrade(&listener))
+        {
+            listened.push(inventory);
+        }
+    }
+    behaviour.listened_inventories = listened;
+    behaviour.inventory_listener = Some(listener);
+}
@@ -11,10 +11,10 @@ use std::{
 use crate::screen_handler::InventoryPlayer;
 use async_trait::async_trait;
 use pumpkin_data::data_component_impl::EquipmentSlot;
-use pumpkin_data::item::Item;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_world::inventory::Inventory;
 use pumpkin_world::item::ItemStack;
-use tokio::{sync::Mutex, time::timeout};
+use tokio::time::timeout;
 
 // Slot.java
 // This is a trait due to crafting slots being a thing
@@ -47,7 +47,7 @@ pub trait Slot: Send + Sync + Debug {
         true
     }
 
-    async fn get_stack(&self) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self) -> Arc<OrderedMutex<ItemStack>> {
         self.get_inventory().get_stack(self.get_index()).await
     }
 
@@ -276,12 +276,10 @@ impl Slot for ArmorSlot {
 
     async fn can_insert(&self, stack: &ItemStack) -> bool {
         match self.equipment_slot {
-            EquipmentSlot::Head(_) => {
-                stack.is_helmet() || stack.is_skull() || stack.item == &Item::CARVED_PUMPKIN
-            }
-            EquipmentSlot::Chest(_) => stack.is_chestplate() || stack.item == &Item::ELYTRA,
-            EquipmentSlot::Legs(_) => stack.is_leggings(),
-            EquipmentSlot::Feet(_) => stack.is_boots(),
+            EquipmentSlot::Head(_)
+            | EquipmentSlot::Chest(_)
+            | EquipmentSlot::Legs(_)
+            | EquipmentSlot::Feet(_) => stack.get_equippable_slot() == Some(&self.equipment_slot),
             _ => true,
         }
     }
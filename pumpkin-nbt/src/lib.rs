@@ -15,6 +15,7 @@ use thiserror::Error;
 pub mod compound;
 pub mod deserializer;
 pub mod nbt_compress;
+pub mod path;
 pub mod serializer;
 pub mod tag;
 
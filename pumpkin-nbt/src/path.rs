@@ -0,0 +1,292 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{compound::NbtCompound, tag::NbtTag};
+
+/// A path to a tag inside a compound, written like vanilla's NBT paths,
+/// e.g. `sections[0].block_states.palette[-1].Name` or `Inventory[0]."minecraft:custom_data"`.
+///
+/// Negative indices count from the end of lists and arrays.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NbtPath {
+    nodes: Vec<NbtPathNode>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NbtPathNode {
+    /// A tag in a compound by name.
+    Key(String),
+    /// An element of a list or array.
+    Index(i32),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NbtPathError {
+    #[error("The NBT path is empty")]
+    Empty,
+    #[error("Expected a key at position {0} of the NBT path")]
+    ExpectedKey(usize),
+    #[error("Unexpected {0:?} at position {1} of the NBT path")]
+    UnexpectedChar(char, usize),
+    #[error("Unterminated quoted key in the NBT path")]
+    UnterminatedQuote,
+    #[error("Invalid index {0:?} in the NBT path")]
+    InvalidIndex(String),
+}
+
+impl NbtPath {
+    pub fn nodes(&self) -> &[NbtPathNode] {
+        &self.nodes
+    }
+
+    /// Returns the tag the path leads to in `root`, or `None` if there is no such tag.
+    ///
+    /// Elements of byte, int and long arrays are not stored as tags, so they are returned owned.
+    pub fn get<'a>(&self, root: &'a NbtCompound) -> Option<Cow<'a, NbtTag>> {
+        let Some((NbtPathNode::Key(first), rest)) = self.nodes.split_first() else {
+            return None;
+        };
+        let mut current = root.get(first)?;
+        for (i, node) in rest.iter().enumerate() {
+            current = match (node, current) {
+                (NbtPathNode::Key(key), NbtTag::Compound(compound)) => compound.get(key)?,
+                (NbtPathNode::Index(index), NbtTag::List(list)) => {
+                    list.get(resolve_index(*index, list.len())?)?
+                }
+                // Array elements are no tags of their own, so nothing can follow them
+                (NbtPathNode::Index(index), array) if i + 1 == rest.len() => {
+                    return array_element(array, *index).map(Cow::Owned);
+                }
+                _ => return None,
+            };
+        }
+        Some(Cow::Borrowed(current))
+    }
+}
+
+fn resolve_index(index: i32, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize).filter(|index| *index < len)
+    }
+}
+
+fn array_element(array: &NbtTag, index: i32) -> Option<NbtTag> {
+    match array {
+        NbtTag::ByteArray(bytes) => bytes
+            .get(resolve_index(index, bytes.len())?)
+            .map(|byte| NbtTag::Byte(*byte as i8)),
+        NbtTag::IntArray(ints) => ints
+            .get(resolve_index(index, ints.len())?)
+            .map(|int| NbtTag::Int(*int)),
+        NbtTag::LongArray(longs) => longs
+            .get(resolve_index(index, longs.len())?)
+            .map(|long| NbtTag::Long(*long)),
+        _ => None,
+    }
+}
+
+impl FromStr for NbtPath {
+    type Err = NbtPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = path.chars().collect();
+        let mut nodes = Vec::new();
+        let mut position = 0;
+        // Keys are needed at the start and after every dot
+        let mut expect_key = true;
+
+        while position < chars.len() {
+            match chars[position] {
+                '[' => {
+                    if expect_key {
+                        return Err(NbtPathError::ExpectedKey(position));
+                    }
+                    let end = chars[position..]
+                        .iter()
+                        .position(|c| *c == ']')
+                        .map(|end| position + end)
+                        .ok_or(NbtPathError::UnexpectedChar('[', position))?;
+                    let index: String = chars[position + 1..end].iter().collect();
+                    let index = index
+                        .trim()
+                        .parse()
+                        .map_err(|_| NbtPathError::InvalidIndex(index))?;
+                    nodes.push(NbtPathNode::Index(index));
+                    position = end + 1;
+                    expect_key = false;
+                }
+                '.' if !expect_key => {
+                    position += 1;
+                    expect_key = true;
+                }
+                '"' if expect_key => {
+                    let mut key = String::new();
+                    position += 1;
+                    loop {
+                        match chars.get(position) {
+                            None => return Err(NbtPathError::UnterminatedQuote),
+                            Some('"') => break,
+                            Some('\\') => {
+                                key.push(
+                                    *chars
+                                        .get(position + 1)
+                                        .ok_or(NbtPathError::UnterminatedQuote)?,
+                                );
+                                position += 2;
+                            }
+                            Some(c) => {
+                                key.push(*c);
+                                position += 1;
+                            }
+                        }
+                    }
+                    nodes.push(NbtPathNode::Key(key));
+                    position += 1;
+                    expect_key = false;
+                }
+                c if expect_key && !matches!(c, '.' | ']' | '"') => {
+                    let end = chars[position..]
+                        .iter()
+                        .position(|c| matches!(c, '.' | '[' | ']' | '"'))
+                        .map_or(chars.len(), |end| position + end);
+                    nodes.push(NbtPathNode::Key(chars[position..end].iter().collect()));
+                    position = end;
+                    expect_key = false;
+                }
+                c => return Err(NbtPathError::UnexpectedChar(c, position)),
+            }
+        }
+
+        if nodes.is_empty() {
+            Err(NbtPathError::Empty)
+        } else if expect_key {
+            Err(NbtPathError::ExpectedKey(position))
+        } else {
+            Ok(Self { nodes })
+        }
+    }
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                NbtPathNode::Key(key) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    if key.is_empty() || key.contains(['.', '[', ']', '"', ' ']) {
+                        write!(f, "\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))?;
+                    } else {
+                        f.write_str(key)?;
+                    }
+                }
+                NbtPathNode::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use crate::{
+        compound::NbtCompound,
+        path::{NbtPath, NbtPathError, NbtPathNode},
+        tag::NbtTag,
+    };
+
+    fn chunk() -> NbtCompound {
+        let mut palette_entry = NbtCompound::new();
+        palette_entry.put_string("Name", "minecraft:stone".to_string());
+        let mut block_states = NbtCompound::new();
+        block_states.put_list("palette", vec![NbtTag::Compound(palette_entry)]);
+        let mut section = NbtCompound::new();
+        section.put_component("block_states", block_states);
+        section.put_byte("Y", -4);
+
+        let mut chunk = NbtCompound::new();
+        chunk.put_list("sections", vec![NbtTag::Compound(section)]);
+        chunk.put("Heightmap", NbtTag::LongArray(vec![1, 2, 3]));
+        chunk.put_int("minecraft:data version", 4438);
+        chunk
+    }
+
+    #[test]
+    fn parse() {
+        let path: NbtPath = "sections[0].block_states.palette[-1].Name".parse().unwrap();
+        assert_eq!(
+            path.nodes(),
+            [
+                NbtPathNode::Key("sections".to_string()),
+                NbtPathNode::Index(0),
+                NbtPathNode::Key("block_states".to_string()),
+                NbtPathNode::Key("palette".to_string()),
+                NbtPathNode::Index(-1),
+                NbtPathNode::Key("Name".to_string()),
+            ]
+        );
+        assert_eq!(
+            path.to_string(),
+            "sections[0].block_states.palette[-1].Name"
+        );
+
+        let quoted: NbtPath = r#""minecraft:data version""#.parse().unwrap();
+        assert_eq!(
+            quoted.nodes(),
+            [NbtPathNode::Key("minecraft:data version".to_string())]
+        );
+        assert_eq!(quoted.to_string().parse::<NbtPath>(), Ok(quoted));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<NbtPath>(), Err(NbtPathError::Empty));
+        assert_eq!("a.".parse::<NbtPath>(), Err(NbtPathError::ExpectedKey(2)));
+        assert_eq!("[0]".parse::<NbtPath>(), Err(NbtPathError::ExpectedKey(0)));
+        assert_eq!(
+            "a[x]".parse::<NbtPath>(),
+            Err(NbtPathError::InvalidIndex("x".to_string()))
+        );
+        assert_eq!(
+            "\"a".parse::<NbtPath>(),
+            Err(NbtPathError::UnterminatedQuote)
+        );
+        assert_eq!(
+            "a..b".parse::<NbtPath>(),
+            Err(NbtPathError::UnexpectedChar('.', 2))
+        );
+    }
+
+    #[test]
+    fn get() {
+        let chunk = chunk();
+        let get = |path: &str| {
+            path.parse::<NbtPath>()
+                .unwrap()
+                .get(&chunk)
+                .map(Cow::into_owned)
+        };
+
+        assert_eq!(
+            get("sections[0].block_states.palette[-1].Name"),
+            Some(NbtTag::String("minecraft:stone".to_string()))
+        );
+        assert_eq!(get("sections[-1].Y"), Some(NbtTag::Byte(-4)));
+        assert_eq!(get("Heightmap[1]"), Some(NbtTag::Long(2)));
+        assert_eq!(get("Heightmap[-3]"), Some(NbtTag::Long(1)));
+        assert_eq!(get(r#""minecraft:data version""#), Some(NbtTag::Int(4438)));
+
+        assert_eq!(get("sections[1]"), None);
+        assert_eq!(get("sections[-2]"), None);
+        assert_eq!(get("Heightmap[3]"), None);
+        assert_eq!(get("Heightmap[0].x"), None);
+        assert_eq!(get("sections.Y"), None);
+        assert_eq!(get("missing"), None);
+    }
+}
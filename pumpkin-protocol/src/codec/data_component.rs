@@ -1,13 +1,45 @@
 use crate::codec::var_int::VarInt;
-use pumpkin_data::Enchantment;
+use crate::ser::deserializer::Deserializer;
+use crate::ser::{NetworkReadExt, ReadingError};
 use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::data_component_impl::{
-    DamageImpl, DataComponentImpl, EnchantmentsImpl, MaxStackSizeImpl, get,
+    CustomDataImpl, CustomModelDataImpl, CustomNameImpl, DamageImpl, DataComponentImpl,
+    EnchantmentGlintOverrideImpl, EnchantmentsImpl, Filterable, FoodImpl, IDSet, LoreImpl,
+    MaxDamageImpl, MaxStackSizeImpl, PotDecorationsImpl, RawComponentImpl, ToolImpl, ToolRule,
+    WritableBookContentImpl, WrittenBookContentImpl, get,
 };
-use serde::de;
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::{RegistryKey, get_registry_key_tags};
+use pumpkin_data::{Block, Enchantment};
+use pumpkin_nbt::deserializer::{NbtReadHelper, from_bytes_unnamed};
+use pumpkin_nbt::{COMPOUND_ID, Nbt, STRING_ID, get_nbt_string};
+use pumpkin_util::text::{TextComponent, TextComponentBase};
 use serde::de::SeqAccess;
 use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, de};
 use std::borrow::Cow;
+use std::io::Cursor;
+
+/// Lengths are sent by the client, so no more than this many elements are allocated before they
+/// have actually been read.
+const MAX_PREALLOCATED: usize = 256;
+
+/// An empty vector with room for up to `len` elements, see [`MAX_PREALLOCATED`].
+pub(crate) fn preallocate<V>(len: usize) -> Vec<V> {
+    Vec::with_capacity(len.min(MAX_PREALLOCATED))
+}
+
+/// Reads a length VarInt, rejecting negative ones.
+pub(crate) fn read_len<'a, A: SeqAccess<'a>>(
+    seq: &mut A,
+    missing: &'static str,
+) -> Result<usize, A::Error> {
+    let len = seq
+        .next_element::<VarInt>()?
+        .ok_or(de::Error::custom(missing))?
+        .0;
+    usize::try_from(len).map_err(|_| de::Error::custom("Negative length VarInt!"))
+}
 
 trait DataComponentCodec<Impl: DataComponentImpl> {
     fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error>;
@@ -52,11 +84,8 @@ impl DataComponentCodec<Self> for EnchantmentsImpl {
         Ok(())
     }
     fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
-        let len = seq
-            .next_element::<VarInt>()?
-            .ok_or(de::Error::custom("No EnchantmentsImpl len VarInt!"))?
-            .0 as usize;
-        let mut enc = Vec::with_capacity(len);
+        let len = read_len(seq, "No EnchantmentsImpl len VarInt!")?;
+        let mut enc = preallocate(len);
         for _ in 0..len {
             let id = seq
                 .next_element::<VarInt>()?
@@ -79,16 +108,523 @@ impl DataComponentCodec<Self> for EnchantmentsImpl {
     }
 }
 
+impl DataComponentCodec<Self> for PotDecorationsImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        let ids: Vec<VarInt> = self
+            .sherds
+            .iter()
+            .map(|sherd| VarInt::from(i32::from(sherd.id)))
+            .collect();
+        serialize_list(&ids, seq)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let mut decorations = Self::default();
+        let ids: Vec<VarInt> = deserialize_list(seq)?;
+        for (side, id) in decorations.sherds.iter_mut().zip(ids) {
+            *side = u16::try_from(id.0)
+                .ok()
+                .and_then(Item::from_id)
+                .ok_or(de::Error::custom(
+                    "PotDecorationsImpl Item VarInt Incorrect!",
+                ))?;
+        }
+        Ok(decorations)
+    }
+}
+
+impl DataComponentCodec<Self> for MaxDamageImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.max_damage))
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let max_damage = seq
+            .next_element::<VarInt>()?
+            .ok_or(de::Error::custom("No max damage VarInt!"))?
+            .0;
+        Ok(Self { max_damage })
+    }
+}
+
+impl DataComponentCodec<Self> for EnchantmentGlintOverrideImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<bool>("", &self.has_glint)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let has_glint = seq
+            .next_element::<bool>()?
+            .ok_or(de::Error::custom("No enchantment glint override bool!"))?;
+        Ok(Self { has_glint })
+    }
+}
+
+impl DataComponentCodec<Self> for CustomModelDataImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        serialize_list(&self.floats, seq)?;
+        serialize_list(&self.flags, seq)?;
+        serialize_list(&self.strings, seq)?;
+        serialize_list(&self.colors, seq)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        Ok(Self {
+            floats: deserialize_list(seq)?,
+            flags: deserialize_list(seq)?,
+            strings: deserialize_list(seq)?,
+            colors: deserialize_list(seq)?,
+        })
+    }
+}
+
+impl DataComponentCodec<Self> for FoodImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.nutrition))?;
+        seq.serialize_field::<f32>("", &self.saturation)?;
+        seq.serialize_field::<bool>("", &self.can_always_eat)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let nutrition = seq
+            .next_element::<VarInt>()?
+            .ok_or(de::Error::custom("No FoodImpl nutrition VarInt!"))?
+            .0;
+        let saturation = seq
+            .next_element::<f32>()?
+            .ok_or(de::Error::custom("No FoodImpl saturation f32!"))?;
+        let can_always_eat = seq
+            .next_element::<bool>()?
+            .ok_or(de::Error::custom("No FoodImpl can always eat bool!"))?;
+        Ok(Self {
+            nutrition,
+            saturation,
+            can_always_eat,
+        })
+    }
+}
+
+impl DataComponentCodec<Self> for ToolImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.rules.len() as i32))?;
+        for rule in self.rules.iter() {
+            serialize_block_set(&rule.blocks, seq)?;
+            seq.serialize_field::<Option<f32>>("", &rule.speed)?;
+            seq.serialize_field::<Option<bool>>("", &rule.correct_for_drops)?;
+        }
+        seq.serialize_field::<f32>("", &self.default_mining_speed)?;
+        seq.serialize_field::<VarInt>("", &VarInt(self.damage_per_block as i32))?;
+        seq.serialize_field::<bool>("", &self.can_destroy_blocks_in_creative)
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let len = read_len(seq, "No ToolImpl rules len VarInt!")?;
+        let mut rules = preallocate(len);
+        for _ in 0..len {
+            let blocks = deserialize_block_set(seq)?;
+            let speed = seq
+                .next_element::<Option<f32>>()?
+                .ok_or(de::Error::custom("No ToolRule speed!"))?;
+            let correct_for_drops = seq
+                .next_element::<Option<bool>>()?
+                .ok_or(de::Error::custom("No ToolRule correct for drops!"))?;
+            rules.push(ToolRule {
+                blocks,
+                speed,
+                correct_for_drops,
+            });
+        }
+        let default_mining_speed = seq
+            .next_element::<f32>()?
+            .ok_or(de::Error::custom("No ToolImpl default mining speed f32!"))?;
+        let damage_per_block = seq
+            .next_element::<VarInt>()?
+            .ok_or(de::Error::custom("No ToolImpl damage per block VarInt!"))?
+            .0 as u32;
+        let can_destroy_blocks_in_creative = seq
+            .next_element::<bool>()?
+            .ok_or(de::Error::custom("No ToolImpl can destroy blocks bool!"))?;
+        Ok(Self {
+            rules: Cow::from(rules),
+            default_mining_speed,
+            damage_per_block,
+            can_destroy_blocks_in_creative,
+        })
+    }
+}
+
+impl LoreImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.lines.len() as i32))?;
+        for line in &self.lines {
+            seq.serialize_field::<TextComponent>("", line)?;
+        }
+        Ok(())
+    }
+
+    /// Lines are NBT encoded, so we need the byte length of the whole component to read them.
+    fn deserialize<'a, A: SeqAccess<'a>>(byte_len: usize, seq: &mut A) -> Result<Self, A::Error> {
+        let len = seq
+            .next_element::<VarInt>()?
+            .ok_or(de::Error::custom("No LoreImpl len VarInt!"))?;
+        let line_count =
+            usize::try_from(len.0).map_err(|_| de::Error::custom("Negative LoreImpl len!"))?;
+        let remaining = byte_len
+            .checked_sub(len.written_size())
+            .ok_or(de::Error::custom("LoreImpl byte length too short!"))?;
+        let mut bytes = preallocate(remaining);
+        for _ in 0..remaining {
+            bytes.push(
+                seq.next_element::<u8>()?
+                    .ok_or(de::Error::custom("No LoreImpl line byte!"))?,
+            );
+        }
+
+        let mut cursor = Cursor::new(bytes);
+        let mut lines = preallocate(line_count);
+        for _ in 0..line_count {
+            lines.push(read_text_component(&mut cursor).map_err(de::Error::custom)?);
+        }
+        Ok(Self { lines })
+    }
+}
+
+impl CustomDataImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        let bytes = Nbt::new(String::new(), self.data.clone()).write_unnamed();
+        for byte in bytes.iter() {
+            seq.serialize_field::<u8>("", byte)?;
+        }
+        Ok(())
+    }
+
+    /// The data is a single network NBT compound, so like lore it needs the byte length to be read.
+    fn deserialize<'a, A: SeqAccess<'a>>(byte_len: usize, seq: &mut A) -> Result<Self, A::Error> {
+        let mut bytes = preallocate(byte_len);
+        for _ in 0..byte_len {
+            bytes.push(
+                seq.next_element::<u8>()?
+                    .ok_or(de::Error::custom("No CustomDataImpl byte!"))?,
+            );
+        }
+        let nbt = Nbt::read_unnamed(&mut NbtReadHelper::new(Cursor::new(bytes)))
+            .map_err(de::Error::custom)?;
+        Ok(Self { data: nbt.root_tag })
+    }
+}
+
+/// Reads all `byte_len` bytes of a component that can't be read element by element, because it
+/// contains NBT.
+fn read_bytes<'a, A: SeqAccess<'a>>(
+    byte_len: usize,
+    seq: &mut A,
+) -> Result<Cursor<Vec<u8>>, A::Error> {
+    let mut bytes = preallocate(byte_len);
+    for _ in 0..byte_len {
+        bytes.push(
+            seq.next_element::<u8>()?
+                .ok_or(de::Error::custom("No component byte!"))?,
+        );
+    }
+    Ok(Cursor::new(bytes))
+}
+
+/// Fails if `cursor` wasn't read to the end.
+fn expect_read(cursor: &Cursor<Vec<u8>>) -> Result<(), String> {
+    let left = cursor.get_ref().len() as u64 - cursor.position();
+    if left == 0 {
+        Ok(())
+    } else {
+        Err(format!("{left} bytes left over after the component"))
+    }
+}
+
+impl CustomNameImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<TextComponent>("", &self.name)
+    }
+
+    /// The name is NBT encoded, so like lore it needs the byte length to be read.
+    fn deserialize<'a, A: SeqAccess<'a>>(byte_len: usize, seq: &mut A) -> Result<Self, A::Error> {
+        let mut cursor = read_bytes(byte_len, seq)?;
+        let name = read_text_component(&mut cursor).map_err(de::Error::custom)?;
+        expect_read(&cursor).map_err(de::Error::custom)?;
+        Ok(Self { name })
+    }
+}
+
+impl DataComponentCodec<Self> for WritableBookContentImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.pages.len() as i32))?;
+        for page in &self.pages {
+            seq.serialize_field::<str>("", &page.raw)?;
+            seq.serialize_field::<Option<String>>("", &page.filtered)?;
+        }
+        Ok(())
+    }
+    fn deserialize<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<Self, A::Error> {
+        let len = read_len(seq, "No WritableBookContentImpl page count VarInt!")?;
+        if len > Self::MAX_PAGES {
+            return Err(de::Error::custom("Too many WritableBookContentImpl pages!"));
+        }
+        let mut pages = preallocate(len);
+        for _ in 0..len {
+            let raw = seq
+                .next_element::<String>()?
+                .ok_or(de::Error::custom("No WritableBookContentImpl page String!"))?;
+            let filtered = seq
+                .next_element::<Option<String>>()?
+                .ok_or(de::Error::custom(
+                    "No WritableBookContentImpl filtered page!",
+                ))?;
+            let too_long = |page: &String| page.chars().count() > Self::MAX_PAGE_LENGTH;
+            if too_long(&raw) || filtered.as_ref().is_some_and(too_long) {
+                return Err(de::Error::custom("WritableBookContentImpl page too long!"));
+            }
+            pages.push(Filterable { raw, filtered });
+        }
+        Ok(Self { pages })
+    }
+}
+
+impl WrittenBookContentImpl {
+    fn serialize<T: SerializeStruct>(&self, seq: &mut T) -> Result<(), T::Error> {
+        seq.serialize_field::<str>("", &self.title.raw)?;
+        seq.serialize_field::<Option<String>>("", &self.title.filtered)?;
+        seq.serialize_field::<str>("", &self.author)?;
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.generation))?;
+        seq.serialize_field::<VarInt>("", &VarInt::from(self.pages.len() as i32))?;
+        for page in &self.pages {
+            seq.serialize_field::<TextComponent>("", &page.raw)?;
+            seq.serialize_field::<Option<TextComponent>>("", &page.filtered)?;
+        }
+        seq.serialize_field::<bool>("", &self.resolved)
+    }
+
+    /// The pages are NBT encoded, so like lore it needs the byte length to be read.
+    fn deserialize<'a, A: SeqAccess<'a>>(byte_len: usize, seq: &mut A) -> Result<Self, A::Error> {
+        let mut cursor = read_bytes(byte_len, seq)?;
+        Self::read(&mut cursor).map_err(de::Error::custom)
+    }
+
+    fn read(cursor: &mut Cursor<Vec<u8>>) -> Result<Self, ReadingError> {
+        let title = Filterable {
+            raw: cursor.get_string_bounded(Self::MAX_TITLE_LENGTH)?,
+            filtered: cursor
+                .get_option(|cursor| cursor.get_string_bounded(Self::MAX_TITLE_LENGTH))?,
+        };
+        let author = cursor.get_string()?;
+        let generation = cursor.get_var_int()?.0;
+        if !(0..=Self::MAX_GENERATION).contains(&generation) {
+            return Err(ReadingError::Message(format!(
+                "Invalid book generation {generation}"
+            )));
+        }
+        let len = usize::try_from(cursor.get_var_int()?.0)
+            .map_err(|_| ReadingError::Message("Negative page count".to_string()))?;
+        let mut pages = preallocate(len);
+        for _ in 0..len {
+            let raw = read_text_component(cursor).map_err(ReadingError::Message)?;
+            let filtered = if cursor.get_bool()? {
+                Some(read_text_component(cursor).map_err(ReadingError::Message)?)
+            } else {
+                None
+            };
+            pages.push(Filterable { raw, filtered });
+        }
+        let resolved = cursor.get_bool()?;
+        expect_read(cursor).map_err(ReadingError::Message)?;
+        Ok(Self {
+            title,
+            author,
+            generation,
+            pages,
+            resolved,
+        })
+    }
+}
+
+/// Reads a network NBT text component, which is either a plain string or a compound.
+fn read_text_component(cursor: &mut Cursor<Vec<u8>>) -> Result<TextComponent, String> {
+    let start = cursor.position();
+    let mut reader = NbtReadHelper::new(&mut *cursor);
+    match reader.get_u8_be().map_err(|err| err.to_string())? {
+        STRING_ID => get_nbt_string(&mut reader)
+            .map(TextComponent::text)
+            .map_err(|err| err.to_string()),
+        COMPOUND_ID => {
+            cursor.set_position(start);
+            from_bytes_unnamed::<TextComponentBase>(&mut *cursor)
+                .map(TextComponent)
+                .map_err(|err| err.to_string())
+        }
+        id => Err(format!("Invalid text component NBT tag {id}")),
+    }
+}
+
+fn serialize_list<T: SerializeStruct, V: Serialize>(
+    values: &[V],
+    seq: &mut T,
+) -> Result<(), T::Error> {
+    seq.serialize_field::<VarInt>("", &VarInt::from(values.len() as i32))?;
+    for value in values {
+        seq.serialize_field::<V>("", value)?;
+    }
+    Ok(())
+}
+
+fn deserialize_list<'a, A: SeqAccess<'a>, V: Deserialize<'a>>(
+    seq: &mut A,
+) -> Result<Vec<V>, A::Error> {
+    let len = read_len(seq, "No list len VarInt!")?;
+    let mut values = preallocate(len);
+    for _ in 0..len {
+        values.push(
+            seq.next_element::<V>()?
+                .ok_or(de::Error::custom("No list element!"))?,
+        );
+    }
+    Ok(values)
+}
+
+/// Block sets are either a tag (`0` followed by the tag name) or `len + 1` followed by block ids.
+fn serialize_block_set<T: SerializeStruct>(blocks: &IDSet, seq: &mut T) -> Result<(), T::Error> {
+    match blocks {
+        IDSet::Tag(tag) => {
+            let name = get_registry_key_tags(&RegistryKey::Block)
+                .entries()
+                .find(|(_, other)| other.1 == tag.1)
+                .map(|(name, _)| *name);
+            if let Some(name) = name {
+                seq.serialize_field::<VarInt>("", &VarInt(0))?;
+                seq.serialize_field::<str>("", name)
+            } else {
+                seq.serialize_field::<VarInt>("", &VarInt(tag.1.len() as i32 + 1))?;
+                for id in tag.1 {
+                    seq.serialize_field::<VarInt>("", &VarInt::from(*id))?;
+                }
+                Ok(())
+            }
+        }
+        IDSet::Blocks(blocks) => {
+            seq.serialize_field::<VarInt>("", &VarInt(blocks.len() as i32 + 1))?;
+            for block in blocks.iter() {
+                seq.serialize_field::<VarInt>("", &VarInt::from(block.id))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn deserialize_block_set<'a, A: SeqAccess<'a>>(seq: &mut A) -> Result<IDSet, A::Error> {
+    let kind = read_len(seq, "No block set type VarInt!")?;
+    if kind == 0 {
+        let name = seq
+            .next_element::<String>()?
+            .ok_or(de::Error::custom("No block set tag String!"))?;
+        let tag = get_registry_key_tags(&RegistryKey::Block)
+            .get(name.as_str())
+            .copied()
+            .ok_or(de::Error::custom("Unknown block tag!"))?;
+        return Ok(IDSet::Tag(tag));
+    }
+    let mut blocks = preallocate(kind - 1);
+    for _ in 1..kind {
+        let id = seq
+            .next_element::<VarInt>()?
+            .ok_or(de::Error::custom("No block set id VarInt!"))?
+            .0;
+        let id = u16::try_from(id).map_err(|_| de::Error::custom("Invalid block id VarInt!"))?;
+        blocks.push(Block::from_id(id));
+    }
+    Ok(IDSet::Blocks(Cow::from(blocks)))
+}
+
+/// Reads the component `id` taking up `byte_len` bytes. Components the server does not know how to
+/// read yet are kept as a [`RawComponentImpl`].
+///
+/// The component is parsed from exactly these bytes, so a malformed component fails on its own
+/// instead of throwing off everything read after it.
 pub fn deserialize<'a, A: SeqAccess<'a>>(
     id: DataComponent,
+    byte_len: usize,
     seq: &mut A,
 ) -> Result<Box<dyn DataComponentImpl>, A::Error> {
-    match id {
+    let mut bytes = preallocate(byte_len);
+    for _ in 0..byte_len {
+        bytes.push(
+            seq.next_element::<u8>()?
+                .ok_or(de::Error::custom("Component data is too short!"))?,
+        );
+    }
+    match decode(id, &bytes).map_err(de::Error::custom)? {
+        Some(component) => Ok(component),
+        None => Ok(RawComponentImpl { id, bytes }.to_dyn()),
+    }
+}
+
+/// Parses the component `id` from `bytes`, failing if any of them are left over.
+fn decode(
+    id: DataComponent,
+    bytes: &[u8],
+) -> Result<Option<Box<dyn DataComponentImpl>>, ReadingError> {
+    struct ComponentVisitor {
+        id: DataComponent,
+        byte_len: usize,
+    }
+    impl<'de> de::Visitor<'de> for ComponentVisitor {
+        type Value = Option<Box<dyn DataComponentImpl>>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "a {} component", self.id.to_name())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            deserialize_known(self.id, self.byte_len, &mut seq)
+        }
+    }
+
+    let mut rest = bytes;
+    let component = de::Deserializer::deserialize_seq(
+        &mut Deserializer::new(&mut rest),
+        ComponentVisitor {
+            id,
+            byte_len: bytes.len(),
+        },
+    )?;
+    if component.is_some() && !rest.is_empty() {
+        return Err(ReadingError::Message(format!(
+            "{} bytes left over after the {} component",
+            rest.len(),
+            id.to_name()
+        )));
+    }
+    Ok(component)
+}
+
+fn deserialize_known<'a, A: SeqAccess<'a>>(
+    id: DataComponent,
+    byte_len: usize,
+    seq: &mut A,
+) -> Result<Option<Box<dyn DataComponentImpl>>, A::Error> {
+    let component = match id {
         DataComponent::MaxStackSize => Ok(MaxStackSizeImpl::deserialize(seq)?.to_dyn()),
         DataComponent::Enchantments => Ok(EnchantmentsImpl::deserialize(seq)?.to_dyn()),
         DataComponent::Damage => Ok(DamageImpl::deserialize(seq)?.to_dyn()),
-        _ => todo!("{} not yet implemented", id.to_name()),
-    }
+        DataComponent::MaxDamage => Ok(MaxDamageImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::EnchantmentGlintOverride => {
+            Ok(EnchantmentGlintOverrideImpl::deserialize(seq)?.to_dyn())
+        }
+        DataComponent::CustomData => Ok(CustomDataImpl::deserialize(byte_len, seq)?.to_dyn()),
+        DataComponent::Lore => Ok(LoreImpl::deserialize(byte_len, seq)?.to_dyn()),
+        DataComponent::CustomModelData => Ok(CustomModelDataImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::Food => Ok(FoodImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::Tool => Ok(ToolImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::PotDecorations => Ok(PotDecorationsImpl::deserialize(seq)?.to_dyn()),
+        DataComponent::CustomName => Ok(CustomNameImpl::deserialize(byte_len, seq)?.to_dyn()),
+        DataComponent::WritableBookContent => {
+            Ok(WritableBookContentImpl::deserialize(seq)?.to_dyn())
+        }
+        DataComponent::WrittenBookContent => {
+            Ok(WrittenBookContentImpl::deserialize(byte_len, seq)?.to_dyn())
+        }
+        _ => return Ok(None),
+    };
+    component.map(Some)
 }
 pub fn serialize<T: SerializeStruct>(
     id: DataComponent,
@@ -99,6 +635,27 @@ pub fn serialize<T: SerializeStruct>(
         DataComponent::MaxStackSize => get::<MaxStackSizeImpl>(value).serialize(seq),
         DataComponent::Enchantments => get::<EnchantmentsImpl>(value).serialize(seq),
         DataComponent::Damage => get::<DamageImpl>(value).serialize(seq),
-        _ => todo!("{} not yet implemented", id.to_name()),
+        DataComponent::MaxDamage => get::<MaxDamageImpl>(value).serialize(seq),
+        DataComponent::EnchantmentGlintOverride => {
+            get::<EnchantmentGlintOverrideImpl>(value).serialize(seq)
+        }
+        DataComponent::CustomData => get::<CustomDataImpl>(value).serialize(seq),
+        DataComponent::Lore => get::<LoreImpl>(value).serialize(seq),
+        DataComponent::CustomModelData => get::<CustomModelDataImpl>(value).serialize(seq),
+        DataComponent::Food => get::<FoodImpl>(value).serialize(seq),
+        DataComponent::Tool => get::<ToolImpl>(value).serialize(seq),
+        DataComponent::PotDecorations => get::<PotDecorationsImpl>(value).serialize(seq),
+        DataComponent::CustomName => get::<CustomNameImpl>(value).serialize(seq),
+        DataComponent::WritableBookContent => get::<WritableBookContentImpl>(value).serialize(seq),
+        DataComponent::WrittenBookContent => get::<WrittenBookContentImpl>(value).serialize(seq),
+        _ => {
+            let Some(raw) = value.as_any().downcast_ref::<RawComponentImpl>() else {
+                todo!("{} not yet implemented", id.to_name())
+            };
+            for byte in &raw.bytes {
+                seq.serialize_field::<u8>("", byte)?;
+            }
+            Ok(())
+        }
     }
 }
@@ -1,5 +1,5 @@
 use crate::VarInt;
-use crate::codec::data_component::{deserialize, serialize};
+use crate::codec::data_component::{deserialize, preallocate, read_len, serialize};
 use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::item::Item;
 use pumpkin_world::item::ItemStack;
@@ -35,17 +35,13 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                         .next_element::<VarInt>()?
                         .ok_or(de::Error::custom("No item id VarInt!"))?;
 
-                    let num_components_to_add = seq
-                        .next_element::<VarInt>()?
-                        .ok_or(de::Error::custom("No component add length VarInt!"))?
-                        .0 as usize;
-                    let num_components_to_remove = seq
-                        .next_element::<VarInt>()?
-                        .ok_or(de::Error::custom("No component remove length VarInt!"))?
-                        .0 as usize;
+                    let num_components_to_add =
+                        read_len(&mut seq, "No component add length VarInt!")?;
+                    let num_components_to_remove =
+                        read_len(&mut seq, "No component remove length VarInt!")?;
 
                     let mut patch =
-                        Vec::with_capacity(num_components_to_add + num_components_to_remove);
+                        preallocate(num_components_to_add.saturating_add(num_components_to_remove));
                     for _ in 0..num_components_to_add {
                         let id = seq
                             .next_element::<VarInt>()?
@@ -55,10 +51,10 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                             .map_err(|_| de::Error::custom("Unknown component id VarInt!"))?;
                         let id = DataComponent::try_from_id(id)
                             .ok_or(de::Error::custom("Unknown component id VarInt!"))?;
-                        let _byte_len = seq
-                            .next_element::<VarInt>()?
-                            .ok_or(de::Error::custom("No data len VarInt!"))?;
-                        patch.push((id, Some(deserialize(id, &mut seq)?)))
+                        let byte_len = read_len(&mut seq, "No data len VarInt!")?;
+                        // Items restored by creative players, e.g. from saved hotbars, may carry
+                        // components the server can't read yet, those are kept as they are
+                        patch.push((id, Some(deserialize(id, byte_len, &mut seq)?)));
                     }
                     for _ in 0..num_components_to_remove {
                         let id = seq
@@ -306,3 +302,335 @@ impl<'de> Deserialize<'de> for ItemComponentHash {
         deserializer.deserialize_seq(Visitor)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use pumpkin_data::Block;
+    use pumpkin_data::data_component::DataComponent;
+    use pumpkin_data::data_component_impl::{
+        CustomModelDataImpl, CustomNameImpl, DamageImpl, DataComponentImpl,
+        EnchantmentGlintOverrideImpl, Filterable, FoodImpl, IDSet, LoreImpl, RawComponentImpl,
+        ToolImpl, ToolRule, WritableBookContentImpl, WrittenBookContentImpl,
+    };
+    use pumpkin_data::item::Item;
+    use pumpkin_data::tag::{RegistryKey, get_registry_key_tags};
+    use pumpkin_util::text::TextComponent;
+    use pumpkin_world::item::ItemStack;
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Serialize, Serializer as _};
+
+    use super::ItemStackSerializer;
+    use crate::VarInt;
+    use crate::codec::data_component::serialize;
+    use crate::ser::{deserializer::Deserializer, serializer::Serializer};
+
+    fn stack(patch: Vec<Box<dyn DataComponentImpl>>) -> ItemStack {
+        ItemStack::new_with_component(
+            1,
+            &Item::DIAMOND_SWORD,
+            patch
+                .into_iter()
+                .map(|data| (data.get_self_enum(), Some(data)))
+                .collect(),
+        )
+    }
+
+    fn var_int(value: i32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        VarInt(value).encode(&mut buf).unwrap();
+        buf
+    }
+
+    fn encode_component(id: DataComponent, data: &dyn DataComponentImpl) -> Vec<u8> {
+        let mut buf = Vec::new();
+        {
+            let mut serializer = Serializer::new(&mut buf);
+            let mut seq = (&mut serializer).serialize_struct("", 0).unwrap();
+            serialize(id, data, &mut seq).unwrap();
+            seq.end().unwrap();
+        }
+        buf
+    }
+
+    /// Encodes the stack like the client does for untrusted slots, with every component prefixed by its length.
+    fn encode_untrusted(stack: &ItemStack) -> Vec<u8> {
+        let mut buf = var_int(stack.item_count.into());
+        buf.extend(var_int(stack.item.id.into()));
+        buf.extend(var_int(stack.patch.len() as i32));
+        buf.extend(var_int(0));
+        for (id, data) in &stack.patch {
+            let component = encode_component(*id, data.as_deref().unwrap());
+            buf.extend(var_int(id.to_id().into()));
+            buf.extend(var_int(component.len() as i32));
+            buf.extend(component);
+        }
+        buf
+    }
+
+    fn assert_round_trip(stack: ItemStack) {
+        let bytes = encode_untrusted(&stack);
+        let decoded = ItemStackSerializer::deserialize(&mut Deserializer::new(bytes.as_slice()))
+            .unwrap()
+            .to_stack();
+
+        assert_eq!(decoded.item.id, stack.item.id);
+        assert_eq!(decoded.item_count, stack.item_count);
+        assert_eq!(decoded.patch.len(), stack.patch.len());
+        for ((id, data), (decoded_id, decoded_data)) in stack.patch.iter().zip(&decoded.patch) {
+            assert_eq!(id, decoded_id);
+            assert!(
+                data.as_deref()
+                    .unwrap()
+                    .equal(decoded_data.as_deref().unwrap()),
+                "{} did not round trip",
+                id.to_name()
+            );
+        }
+    }
+
+    #[test]
+    fn serialize_damage_glint_and_food() {
+        let stack = stack(vec![
+            DamageImpl { damage: 5 }.to_dyn(),
+            EnchantmentGlintOverrideImpl { has_glint: true }.to_dyn(),
+            FoodImpl {
+                nutrition: 4,
+                saturation: 2.4,
+                can_always_eat: false,
+            }
+            .to_dyn(),
+        ]);
+        let mut bytes = Vec::new();
+        ItemStackSerializer(Cow::Borrowed(&stack))
+            .serialize(&mut Serializer::new(&mut bytes))
+            .unwrap();
+
+        let mut expected = var_int(1);
+        expected.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        expected.extend([3, 0]);
+        expected.extend([DataComponent::Damage.to_id(), 5]);
+        expected.extend([DataComponent::EnchantmentGlintOverride.to_id(), 1]);
+        expected.extend([DataComponent::Food.to_id(), 4]);
+        expected.extend(2.4f32.to_be_bytes());
+        expected.push(0);
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn round_trip_simple_components() {
+        assert_round_trip(stack(vec![
+            DamageImpl { damage: 1200 }.to_dyn(),
+            EnchantmentGlintOverrideImpl { has_glint: false }.to_dyn(),
+            FoodImpl {
+                nutrition: 6,
+                saturation: 7.2,
+                can_always_eat: true,
+            }
+            .to_dyn(),
+        ]));
+    }
+
+    #[test]
+    fn round_trip_custom_model_data() {
+        assert_round_trip(stack(vec![
+            CustomModelDataImpl {
+                floats: vec![1.0, 0.5],
+                flags: vec![true, false],
+                strings: vec!["pumpkin".to_string()],
+                colors: vec![0x00FF_8800],
+            }
+            .to_dyn(),
+        ]));
+    }
+
+    #[test]
+    fn round_trip_lore() {
+        assert_round_trip(stack(vec![
+            LoreImpl {
+                lines: vec![TextComponent::text("first"), TextComponent::text("second")],
+            }
+            .to_dyn(),
+        ]));
+    }
+
+    #[test]
+    fn round_trip_books_and_names() {
+        assert_round_trip(stack(vec![
+            CustomNameImpl {
+                name: TextComponent::text("Named").italic(),
+            }
+            .to_dyn(),
+            WritableBookContentImpl {
+                pages: vec![
+                    Filterable::passthrough("first".to_string()),
+                    Filterable {
+                        raw: "bad word".to_string(),
+                        filtered: Some("### word".to_string()),
+                    },
+                ],
+            }
+            .to_dyn(),
+            WrittenBookContentImpl {
+                title: Filterable::passthrough("Title".to_string()),
+                author: "Player".to_string(),
+                generation: 1,
+                pages: vec![Filterable {
+                    raw: TextComponent::text("bad word"),
+                    filtered: Some(TextComponent::text("### word")),
+                }],
+                resolved: true,
+            }
+            .to_dyn(),
+        ]));
+    }
+
+    #[test]
+    fn keep_unknown_components() {
+        let mut bytes = var_int(1);
+        bytes.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        bytes.extend([2, 0]);
+        bytes.extend(var_int(DataComponent::Rarity.to_id().into()));
+        bytes.extend([1, 3]);
+        bytes.extend(var_int(DataComponent::Damage.to_id().into()));
+        bytes.extend([1, 5]);
+
+        let decoded = ItemStackSerializer::deserialize(&mut Deserializer::new(bytes.as_slice()))
+            .unwrap()
+            .to_stack();
+
+        assert_eq!(decoded.patch.len(), 2);
+        assert!(
+            decoded.patch[0]
+                .1
+                .as_deref()
+                .unwrap()
+                .equal(&RawComponentImpl {
+                    id: DataComponent::Rarity,
+                    bytes: vec![3],
+                })
+        );
+
+        // Sent back to clients exactly as it was received
+        let mut encoded = Vec::new();
+        ItemStackSerializer(Cow::Borrowed(&decoded))
+            .serialize(&mut Serializer::new(&mut encoded))
+            .unwrap();
+        let mut expected = var_int(1);
+        expected.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        expected.extend([2, 0]);
+        expected.extend([DataComponent::Rarity.to_id(), 3]);
+        expected.extend([DataComponent::Damage.to_id(), 5]);
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn round_trip_tool() {
+        let pickaxe = get_registry_key_tags(&RegistryKey::Block)
+            .get("minecraft:mineable/pickaxe")
+            .copied()
+            .unwrap();
+        assert_round_trip(stack(vec![
+            ToolImpl {
+                rules: Cow::Owned(vec![
+                    ToolRule {
+                        blocks: IDSet::Blocks(Cow::Owned(vec![&Block::COBWEB])),
+                        speed: Some(15.0),
+                        correct_for_drops: Some(true),
+                    },
+                    ToolRule {
+                        blocks: IDSet::Tag(pickaxe),
+                        speed: None,
+                        correct_for_drops: None,
+                    },
+                ]),
+                default_mining_speed: 1.0,
+                damage_per_block: 2,
+                can_destroy_blocks_in_creative: false,
+            }
+            .to_dyn(),
+        ]));
+    }
+
+    /// A diamond sword with 5 damage and the lore line "Sharp", laid out byte by byte like the
+    /// 1.21.8 client writes it into a set creative slot packet rather than encoded by Pumpkin.
+    #[test]
+    fn decode_vanilla_creative_slot() {
+        let bytes = [
+            0x01, // count
+            0xFF, 0x06, // minecraft:diamond_sword (895)
+            0x02, 0x00, // 2 components added, none removed
+            0x03, 0x01, 0x05, // minecraft:damage, 1 byte, 5
+            0x08, 0x09, // minecraft:lore, 9 bytes
+            0x01, // 1 line
+            0x08, 0x00, 0x05, b'S', b'h', b'a', b'r', b'p', // NBT string tag "Sharp"
+        ];
+        let decoded = ItemStackSerializer::deserialize(&mut Deserializer::new(bytes.as_slice()))
+            .unwrap()
+            .to_stack();
+
+        assert_eq!(decoded.item.id, Item::DIAMOND_SWORD.id);
+        assert_eq!(decoded.item_count, 1);
+        assert!(
+            decoded.patch[0]
+                .1
+                .as_deref()
+                .unwrap()
+                .equal(&DamageImpl { damage: 5 })
+        );
+        assert!(decoded.patch[1].1.as_deref().unwrap().equal(&LoreImpl {
+            lines: vec![TextComponent::text("Sharp")],
+        }));
+    }
+
+    #[test]
+    fn reject_component_length_mismatch() {
+        let mut bytes = var_int(1);
+        bytes.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        bytes.extend([2, 0]);
+        // The damage claims two bytes but only needs one
+        bytes.extend(var_int(DataComponent::Damage.to_id().into()));
+        bytes.extend([2, 5, 0]);
+        bytes.extend(var_int(
+            DataComponent::EnchantmentGlintOverride.to_id().into(),
+        ));
+        bytes.extend([1, 1]);
+        assert!(
+            ItemStackSerializer::deserialize(&mut Deserializer::new(bytes.as_slice())).is_err()
+        );
+
+        // A list running past its component must not read into the next one
+        let mut bytes = var_int(1);
+        bytes.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        bytes.extend([2, 0]);
+        bytes.extend(var_int(DataComponent::CustomModelData.to_id().into()));
+        bytes.extend([1, 4]);
+        bytes.extend(var_int(DataComponent::Damage.to_id().into()));
+        bytes.extend([1, 5]);
+        assert!(
+            ItemStackSerializer::deserialize(&mut Deserializer::new(bytes.as_slice())).is_err()
+        );
+    }
+
+    #[test]
+    fn reject_bad_list_lengths() {
+        let mut bytes = var_int(1);
+        bytes.extend(var_int(Item::DIAMOND_SWORD.id.into()));
+        bytes.extend([1, 0]);
+        bytes.extend(var_int(DataComponent::CustomModelData.to_id().into()));
+        bytes.extend(var_int(5));
+
+        // A huge length must fail once the data runs out instead of allocating it all up front
+        let mut huge = bytes.clone();
+        huge.extend(var_int(i32::MAX));
+        assert!(ItemStackSerializer::deserialize(&mut Deserializer::new(huge.as_slice())).is_err());
+
+        let mut negative = bytes;
+        negative.extend(var_int(-1));
+        assert!(
+            ItemStackSerializer::deserialize(&mut Deserializer::new(negative.as_slice())).is_err()
+        );
+    }
+}
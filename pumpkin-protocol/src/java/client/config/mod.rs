@@ -2,22 +2,26 @@ mod add_resource_pack;
 mod config_disconnect;
 mod cookie_request;
 mod finish_config;
+mod keep_alive;
 mod known_packs;
 mod plugin_message;
 mod registry_data;
 mod server_links;
 mod store_cookie;
 mod transfer;
+mod update_enabled_features;
 mod update_tags;
 
 pub use add_resource_pack::*;
 pub use config_disconnect::*;
 pub use cookie_request::*;
 pub use finish_config::*;
+pub use keep_alive::*;
 pub use known_packs::*;
 pub use plugin_message::*;
 pub use registry_data::*;
 pub use server_links::*;
 pub use store_cookie::*;
 pub use transfer::*;
+pub use update_enabled_features::*;
 pub use update_tags::*;
@@ -20,7 +20,7 @@ impl<'a> CRegistryData<'a> {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct RegistryEntry {
     pub entry_id: ResourceLocation,
     #[serde(serialize_with = "network_serialize_no_prefix")]
@@ -44,10 +44,13 @@ impl RegistryEntry {
         }
     }
     pub fn from_nbt_custom(name: &str, nbt: &impl Serialize) -> Self {
+        Self::from_nbt_with_id(ResourceLocation::pumpkin(name), nbt)
+    }
+    pub fn from_nbt_with_id(entry_id: ResourceLocation, nbt: &impl Serialize) -> Self {
         let mut data_buf = Vec::new();
         pumpkin_nbt::serializer::to_bytes_unnamed(nbt, &mut data_buf).unwrap();
         RegistryEntry {
-            entry_id: ResourceLocation::pumpkin(name),
+            entry_id,
             data: Some(data_buf.into_boxed_slice()),
         }
     }
@@ -0,0 +1,16 @@
+use pumpkin_data::packet::clientbound::CONFIG_UPDATE_ENABLED_FEATURES;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Tells the client which feature flags are enabled, e.g. `minecraft:vanilla`.
+#[derive(Serialize)]
+#[packet(CONFIG_UPDATE_ENABLED_FEATURES)]
+pub struct CUpdateEnabledFeatures<'a> {
+    pub features: &'a [&'a str],
+}
+
+impl<'a> CUpdateEnabledFeatures<'a> {
+    pub fn new(features: &'a [&'a str]) -> Self {
+        Self { features }
+    }
+}
@@ -6,11 +6,13 @@ use pumpkin_macros::packet;
 use pumpkin_nbt::END_ID;
 use pumpkin_util::math::position::get_local_cord;
 use pumpkin_world::chunk::format::LightContainer;
-use pumpkin_world::chunk::{ChunkData, palette::NetworkPalette};
+use pumpkin_world::chunk::{ChunkData, ChunkSections, palette::NetworkPalette};
 use std::io::Write;
 
 #[packet(PLAY_LEVEL_CHUNK_WITH_LIGHT)]
-pub struct CChunkData<'a>(pub &'a ChunkData);
+/// A chunk sent with the given blocks, which may differ from the chunk's own ones when some are
+/// hidden from the player.
+pub struct CChunkData<'a>(pub &'a ChunkData, pub &'a ChunkSections);
 
 impl ClientPacket for CChunkData<'_> {
     fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
@@ -22,34 +24,25 @@ impl ClientPacket for CChunkData<'_> {
         write.write_i32_be(self.0.position.y)?;
 
         let heightmaps = &self.0.heightmap;
-        // the heighmap is a map, we put 3 values in so the size is 3
-        write.write_var_int(&VarInt(3))?;
-
-        // heighmap index
-        write.write_var_int(&VarInt(1))?;
-        // write long array
-        write.write_var_int(&VarInt(heightmaps.world_surface.len() as i32))?;
-        for mb in &heightmaps.world_surface {
-            write.write_i64_be(*mb)?;
-        }
-        // heighmap index
-        write.write_var_int(&VarInt(4))?;
-        // write long array
-        write.write_var_int(&VarInt(heightmaps.motion_blocking.len() as i32))?;
-        for mb in &heightmaps.motion_blocking {
-            write.write_i64_be(*mb)?;
-        }
-        // heighmap index
-        write.write_var_int(&VarInt(5))?;
-        // write long array
-        write.write_var_int(&VarInt(heightmaps.motion_blocking_no_leaves.len() as i32))?;
-        for mb in &heightmaps.motion_blocking {
-            write.write_i64_be(*mb)?;
+        // Only the heightmaps the client uses are sent, keyed by their vanilla type id
+        let client_heightmaps = [
+            (1, &heightmaps.world_surface),
+            (4, &heightmaps.motion_blocking),
+            (5, &heightmaps.motion_blocking_no_leaves),
+        ];
+        write.write_var_int(&VarInt(client_heightmaps.len() as i32))?;
+        for (heightmap_id, data) in client_heightmaps {
+            write.write_var_int(&VarInt(heightmap_id))?;
+            // write long array
+            write.write_var_int(&VarInt(data.len() as i32))?;
+            for long in data.iter() {
+                write.write_i64_be(*long)?;
+            }
         }
 
         {
             let mut blocks_and_biomes_buf = Vec::new();
-            for section in &self.0.section.sections {
+            for section in &self.1.sections {
                 // Block count
                 let non_empty_block_count = section.block_states.non_air_block_count() as i16;
                 blocks_and_biomes_buf.write_i16_be(non_empty_block_count)?;
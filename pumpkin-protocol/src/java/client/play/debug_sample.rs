@@ -0,0 +1,22 @@
+use pumpkin_data::packet::clientbound::PLAY_DEBUG_SAMPLE;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// One sample of a debug graph, e.g. the time the last tick took split into its parts.
+#[derive(Serialize)]
+#[packet(PLAY_DEBUG_SAMPLE)]
+pub struct CDebugSample<'a> {
+    pub sample: &'a [i64],
+    pub sample_type: VarInt,
+}
+
+impl<'a> CDebugSample<'a> {
+    pub fn new(sample: &'a [i64], sample_type: VarInt) -> Self {
+        Self {
+            sample,
+            sample_type,
+        }
+    }
+}
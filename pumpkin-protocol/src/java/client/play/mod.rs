@@ -17,7 +17,9 @@ mod combat_death;
 mod command_suggestions;
 mod commands;
 mod cookie_request;
+mod custom_payload;
 mod damage_event;
+mod debug_sample;
 mod disconnect;
 mod disguised_chat_message;
 mod display_objective;
@@ -47,6 +49,8 @@ mod player_info_update;
 mod player_position;
 mod player_remove;
 mod player_spawn_position;
+mod recipe_book_add;
+mod recipe_book_remove;
 mod remove_entities;
 mod remove_mob_effect;
 mod reset_score;
@@ -57,14 +61,17 @@ mod set_border_lerp_size;
 mod set_border_size;
 mod set_border_warning_delay;
 mod set_border_warning_distance;
+mod set_camera;
 mod set_container_content;
 mod set_container_property;
 mod set_container_slot;
 mod set_cursor_slot;
+mod set_entity_link;
 mod set_equipment;
 mod set_experience;
 mod set_health;
 mod set_held_item;
+mod set_passengers;
 mod set_player_inventory;
 mod set_time;
 mod set_title;
@@ -107,7 +114,9 @@ pub use combat_death::*;
 pub use command_suggestions::*;
 pub use commands::*;
 pub use cookie_request::*;
+pub use custom_payload::*;
 pub use damage_event::*;
+pub use debug_sample::*;
 pub use disconnect::*;
 pub use disguised_chat_message::*;
 pub use display_objective::*;
@@ -137,6 +146,8 @@ pub use player_info_update::*;
 pub use player_position::*;
 pub use player_remove::*;
 pub use player_spawn_position::*;
+pub use recipe_book_add::*;
+pub use recipe_book_remove::*;
 pub use remove_entities::*;
 pub use remove_mob_effect::*;
 pub use reset_score::*;
@@ -147,14 +158,17 @@ pub use set_border_lerp_size::*;
 pub use set_border_size::*;
 pub use set_border_warning_delay::*;
 pub use set_border_warning_distance::*;
+pub use set_camera::*;
 pub use set_container_content::*;
 pub use set_container_property::*;
 pub use set_container_slot::*;
 pub use set_cursor_slot::*;
+pub use set_entity_link::*;
 pub use set_equipment::*;
 pub use set_experience::*;
 pub use set_health::*;
 pub use set_held_item::*;
+pub use set_passengers::*;
 pub use set_player_inventory::*;
 pub use set_time::*;
 pub use set_title::*;
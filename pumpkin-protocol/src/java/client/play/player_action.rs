@@ -1,3 +1,5 @@
+use pumpkin_util::text::TextComponent;
+
 use crate::{Property, VarInt};
 
 pub enum PlayerAction<'a> {
@@ -8,8 +10,9 @@ pub enum PlayerAction<'a> {
     InitializeChat(Option<InitChat>),
     UpdateGameMode(VarInt),
     UpdateListed(bool),
-    UpdateLatency(u8),
-    UpdateDisplayName(u8),
+    UpdateLatency(VarInt),
+    /// The name shown in the tab list, the player's own name if `None`.
+    UpdateDisplayName(Option<TextComponent>),
     UpdateListOrder,
 }
 
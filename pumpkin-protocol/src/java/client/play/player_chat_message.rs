@@ -90,6 +90,9 @@ impl ClientPacket for CPlayerChatMessage {
             FilterType::FullyFiltered => 1,
             FilterType::PartiallyFiltered(_) => 2,
         }))?;
+        if let FilterType::PartiallyFiltered(mask) = &self.filter_type {
+            mask.encode(&mut write)?;
+        }
         write.write_var_int(&self.chat_type)?;
         write.write_slice(&self.sender_name.encode())?;
         write.write_option(&self.target_name, |p, v| p.write_slice(&v.encode()))?;
@@ -108,6 +111,6 @@ pub enum FilterType {
     PassThrough,
     /// Message is fully filtered
     FullyFiltered,
-    /// Only some characters in the message are filtered
+    /// Only some characters in the message are filtered, one bit per UTF-16 code unit
     PartiallyFiltered(BitSet),
 }
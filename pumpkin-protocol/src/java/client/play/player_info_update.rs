@@ -79,8 +79,10 @@ impl ClientPacket for CPlayerInfoUpdate<'_> {
                     }
                     PlayerAction::UpdateGameMode(gamemode) => p.write_var_int(gamemode)?,
                     PlayerAction::UpdateListed(listed) => p.write_bool(*listed)?,
-                    PlayerAction::UpdateLatency(_) => todo!(),
-                    PlayerAction::UpdateDisplayName(_) => todo!(),
+                    PlayerAction::UpdateLatency(latency) => p.write_var_int(latency)?,
+                    PlayerAction::UpdateDisplayName(display_name) => {
+                        p.write_option(display_name, |p, v| p.write_slice(&v.encode()))?;
+                    }
                     PlayerAction::UpdateListOrder => todo!(),
                 }
             }
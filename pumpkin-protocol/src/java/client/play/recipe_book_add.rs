@@ -0,0 +1,171 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_RECIPE_BOOK_ADD;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::{
+    ClientPacket, VarInt, WritingError,
+    codec::item_stack_seralizer::ItemStackSerializer,
+    ser::{NetworkWriteExt, serializer::Serializer},
+};
+
+#[packet(PLAY_RECIPE_BOOK_ADD)]
+pub struct CRecipeBookAdd {
+    pub entries: Vec<RecipeBookEntry>,
+    /// Whether the entries replace everything in the client's recipe book
+    pub replace: bool,
+}
+
+impl CRecipeBookAdd {
+    pub fn new(entries: Vec<RecipeBookEntry>, replace: bool) -> Self {
+        Self { entries, replace }
+    }
+}
+
+impl ClientPacket for CRecipeBookAdd {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_list(&self.entries, |p, entry| {
+            p.write_var_int(&entry.display_id)?;
+            entry.display.write(p)?;
+            p.write_option(&entry.group, |p, group| p.write_var_int(group))?;
+            p.write_var_int(&entry.category)?;
+            p.write_option(&entry.crafting_requirements, |p, requirements| {
+                p.write_list(requirements, |p, ids| ids.write(p))
+            })?;
+            p.write_u8(entry.flags)
+        })?;
+        write.write_bool(self.replace)
+    }
+}
+
+pub struct RecipeBookEntry {
+    /// The id the client refers to the recipe with, e.g. when removing it again
+    pub display_id: VarInt,
+    pub display: RecipeDisplay,
+    pub group: Option<VarInt>,
+    pub category: VarInt,
+    /// The items each ingredient accepts, which the client uses to tell whether the recipe can be
+    /// crafted
+    pub crafting_requirements: Option<Vec<IdSet>>,
+    pub flags: u8,
+}
+
+impl RecipeBookEntry {
+    /// Shows a toast for the recipe.
+    pub const SHOW_NOTIFICATION: u8 = 0x01;
+    /// Highlights the recipe as new in the recipe book.
+    pub const HIGHLIGHT: u8 = 0x02;
+}
+
+pub enum RecipeDisplay {
+    CraftingShapeless {
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+    CraftingShaped {
+        width: VarInt,
+        height: VarInt,
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+}
+
+impl RecipeDisplay {
+    fn write(&self, write: &mut impl Write) -> Result<(), WritingError> {
+        match self {
+            Self::CraftingShapeless {
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                write.write_var_int(&VarInt(0))?;
+                write.write_list(ingredients, |p, slot| slot.write(p))?;
+                result.write(write)?;
+                crafting_station.write(write)
+            }
+            Self::CraftingShaped {
+                width,
+                height,
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                write.write_var_int(&VarInt(1))?;
+                write.write_var_int(width)?;
+                write.write_var_int(height)?;
+                write.write_list(ingredients, |p, slot| slot.write(p))?;
+                result.write(write)?;
+                crafting_station.write(write)
+            }
+        }
+    }
+}
+
+/// What the client shows in a slot of a recipe.
+pub enum SlotDisplay {
+    Empty,
+    /// An item, by its id
+    Item(VarInt),
+    ItemStack(ItemStackSerializer<'static>),
+    /// Cycles through the items of a tag
+    Tag(String),
+    /// Cycles through the displays
+    Composite(Vec<SlotDisplay>),
+}
+
+impl SlotDisplay {
+    fn write(&self, write: &mut impl Write) -> Result<(), WritingError> {
+        match self {
+            Self::Empty => write.write_var_int(&VarInt(0)),
+            Self::Item(item) => {
+                write.write_var_int(&VarInt(2))?;
+                write.write_var_int(item)
+            }
+            Self::ItemStack(stack) => {
+                write.write_var_int(&VarInt(3))?;
+                let mut serializer = Serializer::new(write);
+                stack.serialize(&mut serializer)
+            }
+            Self::Tag(tag) => {
+                write.write_var_int(&VarInt(4))?;
+                write.write_string(tag)
+            }
+            Self::Composite(displays) => {
+                write.write_var_int(&VarInt(7))?;
+                write.write_list(displays, |p, display| display.write(p))
+            }
+        }
+    }
+}
+
+/// A set of registry entries, either a tag or the ids themselves.
+pub enum IdSet {
+    Tag(String),
+    Ids(Vec<VarInt>),
+}
+
+impl IdSet {
+    fn write(&self, write: &mut impl Write) -> Result<(), WritingError> {
+        match self {
+            Self::Tag(tag) => {
+                write.write_var_int(&VarInt(0))?;
+                write.write_string(tag)
+            }
+            Self::Ids(ids) => {
+                let len = i32::try_from(ids.len() + 1).map_err(|_| {
+                    WritingError::Message(format!("{} isn't representable as a VarInt", ids.len()))
+                })?;
+                write.write_var_int(&VarInt(len))?;
+                for id in ids {
+                    write.write_var_int(id)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use pumpkin_data::packet::clientbound::PLAY_RECIPE_BOOK_REMOVE;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Removes recipes from the client's recipe book, by the display ids they were added with.
+#[derive(Serialize)]
+#[packet(PLAY_RECIPE_BOOK_REMOVE)]
+pub struct CRecipeBookRemove<'a> {
+    pub display_ids: &'a [VarInt],
+}
+
+impl<'a> CRecipeBookRemove<'a> {
+    pub fn new(display_ids: &'a [VarInt]) -> Self {
+        Self { display_ids }
+    }
+}
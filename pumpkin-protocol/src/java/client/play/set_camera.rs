@@ -0,0 +1,19 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_CAMERA;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Makes the client view the world from the entity with the given id, or from its own player
+/// again if it is the id of the player.
+#[derive(Serialize)]
+#[packet(PLAY_SET_CAMERA)]
+pub struct CSetCamera {
+    pub camera_id: VarInt,
+}
+
+impl CSetCamera {
+    pub fn new(camera_id: VarInt) -> Self {
+        Self { camera_id }
+    }
+}
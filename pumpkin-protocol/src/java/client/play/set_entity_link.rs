@@ -0,0 +1,20 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_ENTITY_LINK;
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+/// Attaches an entity to another one using a leash. The holding entity is `-1` to detach it.
+#[derive(Serialize, Deserialize)]
+#[packet(PLAY_SET_ENTITY_LINK)]
+pub struct CSetEntityLink {
+    pub attached_entity_id: i32,
+    pub holding_entity_id: i32,
+}
+
+impl CSetEntityLink {
+    pub fn new(attached_entity_id: i32, holding_entity_id: i32) -> Self {
+        Self {
+            attached_entity_id,
+            holding_entity_id,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_PASSENGERS;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Replaces the passengers riding an entity, an empty list dismounts all of them.
+#[derive(Serialize)]
+#[packet(PLAY_SET_PASSENGERS)]
+pub struct CSetPassengers<'a> {
+    pub entity_id: VarInt,
+    pub passenger_ids: &'a [VarInt],
+}
+
+impl<'a> CSetPassengers<'a> {
+    pub fn new(entity_id: VarInt, passenger_ids: &'a [VarInt]) -> Self {
+        Self {
+            entity_id,
+            passenger_ids,
+        }
+    }
+}
@@ -137,6 +137,13 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
     /// -   `Packet ID`: The ID of the packet.
     /// -   `Data`: The packet's data.
     pub async fn write_packet(&mut self, packet_data: Bytes) -> Result<(), PacketEncodeError> {
+        self.append_packet(packet_data).await?;
+        self.flush().await
+    }
+
+    /// Like [`Self::write_packet`], but leaves the packet in the writer's buffer until the next
+    /// [`Self::flush`], so several packets can go out with a single write to the socket.
+    pub async fn append_packet(&mut self, packet_data: Bytes) -> Result<(), PacketEncodeError> {
         // We need to know the length of the compressed buffer and serde is not async :(
         // We need to write to a buffer here 😔
 
@@ -256,12 +263,15 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
                 .await
                 .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
         }
+        Ok(())
+    }
 
+    /// Sends all appended packets that are still buffered.
+    pub async fn flush(&mut self) -> Result<(), PacketEncodeError> {
         self.writer
             .flush()
             .await
-            .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
-        Ok(())
+            .map_err(|err| PacketEncodeError::Message(err.to_string()))
     }
 }
 
@@ -555,6 +565,34 @@ mod tests {
         assert_eq!(buffer, expected_payload);
     }
 
+    /// Test that appended packets come out exactly like separately written ones once flushed
+    #[tokio::test]
+    async fn test_append_packets_then_flush() {
+        let first = CStatusResponse::new(String::from("{\"description\": \"First\"}"));
+        let second = CStatusResponse::new(String::from("{\"description\": \"Second\"}"));
+
+        let mut expected = build_packet_with_encoder(&first, Some((16, 6)), None)
+            .await
+            .into_vec();
+        expected.extend_from_slice(&build_packet_with_encoder(&second, Some((16, 6)), None).await);
+
+        let mut buf = Vec::new();
+        let mut encoder = TCPNetworkEncoder::new(tokio::io::BufWriter::new(&mut buf));
+        encoder.set_compression((16, 6));
+        for packet in [&first, &second] {
+            let mut packet_buf = Vec::new();
+            packet_buf
+                .write_var_int(&VarInt(CStatusResponse::PACKET_ID))
+                .unwrap();
+            packet.write_packet_data(&mut packet_buf).unwrap();
+            encoder.append_packet(packet_buf.into()).await.unwrap();
+        }
+        encoder.flush().await.unwrap();
+        drop(encoder);
+
+        assert_eq!(buf, expected);
+    }
+
     /// Test encoding with maximum length payload
     #[tokio::test]
     async fn test_encode_with_maximum_string_length() {
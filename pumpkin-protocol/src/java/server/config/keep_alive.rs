@@ -0,0 +1,9 @@
+use pumpkin_data::packet::serverbound::CONFIG_KEEP_ALIVE;
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[packet(CONFIG_KEEP_ALIVE)]
+pub struct SConfigKeepAlive {
+    pub keep_alive_id: i64,
+}
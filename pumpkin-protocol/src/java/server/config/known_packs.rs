@@ -1,12 +1,41 @@
+use std::io::Read;
+
 use pumpkin_data::packet::serverbound::CONFIG_SELECT_KNOWN_PACKS;
 use pumpkin_macros::packet;
-use serde::Serialize;
 
-use crate::VarInt;
+use crate::{ReadingError, ServerPacket, ser::NetworkReadExt};
+
+/// The vanilla server only accepts this many known packs from the client.
+const MAX_KNOWN_PACKS: usize = 64;
 
-#[derive(serde::Deserialize, Serialize)]
+/// The data packs the client has a copy of, out of the ones the server offered in `CKnownPacks`.
 #[packet(CONFIG_SELECT_KNOWN_PACKS)]
 pub struct SKnownPacks {
-    pub known_pack_count: VarInt,
-    // known_packs: &'a [KnownPack]
+    pub known_packs: Vec<SKnownPack>,
+}
+
+pub struct SKnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+impl ServerPacket for SKnownPacks {
+    fn read(read: impl Read) -> Result<Self, ReadingError> {
+        let mut read = read;
+        let count = read.get_var_int()?.0 as usize;
+        if count > MAX_KNOWN_PACKS {
+            return Err(ReadingError::TooLarge("SKnownPacks".to_string()));
+        }
+
+        let mut known_packs = Vec::with_capacity(count);
+        for _ in 0..count {
+            known_packs.push(SKnownPack {
+                namespace: read.get_string()?,
+                id: read.get_string()?,
+                version: read.get_string()?,
+            });
+        }
+        Ok(Self { known_packs })
+    }
 }
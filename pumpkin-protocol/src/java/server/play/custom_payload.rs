@@ -1,7 +1,25 @@
+use std::io::Read;
+
 use pumpkin_data::packet::serverbound::PLAY_CUSTOM_PAYLOAD;
 use pumpkin_macros::packet;
+use pumpkin_util::resource_location::ResourceLocation;
+
+use crate::{ServerPacket, ser::NetworkReadExt, ser::ReadingError};
+
+const MAX_PAYLOAD_SIZE: usize = 32767;
 
 #[packet(PLAY_CUSTOM_PAYLOAD)]
 pub struct SCustomPayload {
-    // TODO
+    pub channel: ResourceLocation,
+    pub data: Box<[u8]>,
+}
+
+impl ServerPacket for SCustomPayload {
+    fn read(read: impl Read) -> Result<Self, ReadingError> {
+        let mut read = read;
+        Ok(Self {
+            channel: read.get_resource_location()?,
+            data: read.read_remaining_to_boxed_slice(MAX_PAYLOAD_SIZE)?,
+        })
+    }
 }
@@ -0,0 +1,12 @@
+use pumpkin_data::packet::serverbound::PLAY_DEBUG_SAMPLE_SUBSCRIPTION;
+use pumpkin_macros::packet;
+use serde::Deserialize;
+
+use crate::VarInt;
+
+/// Sent repeatedly while the client shows a debug graph, to keep receiving its samples.
+#[derive(Deserialize)]
+#[packet(PLAY_DEBUG_SAMPLE_SUBSCRIPTION)]
+pub struct SDebugSampleSubscription {
+    pub sample_type: VarInt,
+}
@@ -0,0 +1,42 @@
+use std::io::Read;
+
+use pumpkin_data::packet::serverbound::PLAY_EDIT_BOOK;
+use pumpkin_macros::packet;
+
+use crate::{
+    ServerPacket,
+    ser::{NetworkReadExt, ReadingError},
+};
+
+#[packet(PLAY_EDIT_BOOK)]
+pub struct SEditBook {
+    /// The hotbar slot of the book, or 40 for the off hand
+    pub slot: i32,
+    pub pages: Vec<String>,
+    /// Only present when the book gets signed
+    pub title: Option<String>,
+}
+
+const MAX_PAGES: usize = 100;
+const MAX_PAGE_LENGTH: usize = 1024;
+const MAX_TITLE_LENGTH: usize = 32;
+
+impl ServerPacket for SEditBook {
+    fn read(read: impl Read) -> Result<Self, ReadingError> {
+        let mut read = read;
+
+        let slot = read.get_var_int()?.0;
+        let page_count = read.get_var_int()?.0 as usize;
+        if page_count > MAX_PAGES {
+            return Err(ReadingError::TooLarge(format!(
+                "{page_count} book pages (max {MAX_PAGES})"
+            )));
+        }
+        let pages = (0..page_count)
+            .map(|_| read.get_string_bounded(MAX_PAGE_LENGTH))
+            .collect::<Result<_, _>>()?;
+        let title = read.get_option(|read| read.get_string_bounded(MAX_TITLE_LENGTH))?;
+
+        Ok(Self { slot, pages, title })
+    }
+}
@@ -11,6 +11,8 @@ mod command_suggestion;
 mod confirm_teleport;
 mod cookie_response;
 mod custom_payload;
+mod debug_sample_subscription;
+mod edit_book;
 mod interact;
 mod keep_alive;
 mod pick_item;
@@ -25,9 +27,11 @@ mod player_position;
 mod player_position_rotation;
 mod player_rotation;
 mod player_session;
+mod rename_item;
 mod set_command_block;
 mod set_creative_slot;
 mod set_held_item;
+mod spectator_teleport;
 mod swing_arm;
 mod update_sign;
 mod use_item;
@@ -46,6 +50,8 @@ pub use command_suggestion::*;
 pub use confirm_teleport::*;
 pub use cookie_response::*;
 pub use custom_payload::*;
+pub use debug_sample_subscription::*;
+pub use edit_book::*;
 pub use interact::*;
 pub use keep_alive::*;
 pub use pick_item::*;
@@ -60,9 +66,11 @@ pub use player_position::*;
 pub use player_position_rotation::*;
 pub use player_rotation::*;
 pub use player_session::*;
+pub use rename_item::*;
 pub use set_command_block::*;
 pub use set_creative_slot::*;
 pub use set_held_item::*;
+pub use spectator_teleport::*;
 pub use swing_arm::*;
 pub use update_sign::*;
 pub use use_item::*;
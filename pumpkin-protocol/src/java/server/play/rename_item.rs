@@ -0,0 +1,10 @@
+use pumpkin_data::packet::serverbound::PLAY_RENAME_ITEM;
+use pumpkin_macros::packet;
+use serde::Deserialize;
+
+/// Sent while typing a new name into an anvil.
+#[derive(Deserialize)]
+#[packet(PLAY_RENAME_ITEM)]
+pub struct SRenameItem {
+    pub item_name: String,
+}
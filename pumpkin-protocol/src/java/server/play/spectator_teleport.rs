@@ -0,0 +1,24 @@
+use std::io::Read;
+
+use pumpkin_data::packet::serverbound::PLAY_TELEPORT_TO_ENTITY;
+use pumpkin_macros::packet;
+
+use crate::{
+    ServerPacket,
+    ser::{NetworkReadExt, ReadingError},
+};
+
+/// Sent by spectators picking an entity to teleport to from the spectator menu. The entity may
+/// be in another world.
+#[packet(PLAY_TELEPORT_TO_ENTITY)]
+pub struct SSpectatorTeleport {
+    pub target: uuid::Uuid,
+}
+
+impl ServerPacket for SSpectatorTeleport {
+    fn read(mut read: impl Read) -> Result<Self, ReadingError> {
+        Ok(Self {
+            target: read.get_uuid()?,
+        })
+    }
+}
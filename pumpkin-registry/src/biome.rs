@@ -1,70 +1,138 @@
+use std::{fs, path::Path};
+
+use indexmap::IndexMap;
 use pumpkin_protocol::codec::var_int::VarInt;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Biome {
-    has_precipitation: bool,
-    temperature: f32,
+    pub has_precipitation: bool,
+    pub temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    temperature_modifier: Option<String>,
-    downfall: f32,
-    effects: BiomeEffects,
+    pub temperature_modifier: Option<String>,
+    pub downfall: f32,
+    pub effects: BiomeEffects,
 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct BiomeEffects {
-    fog_color: i32,
-    water_color: i32,
-    water_fog_color: i32,
-    sky_color: i32,
+pub struct BiomeEffects {
+    pub fog_color: i32,
+    pub water_color: i32,
+    pub water_fog_color: i32,
+    pub sky_color: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foliage_color: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    foliage_color: Option<i32>,
+    pub dry_foliage_color: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    grass_color: Option<i32>,
+    pub grass_color: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    grass_color_modifier: Option<String>,
+    pub grass_color_modifier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    particle: Option<Particle>,
+    pub particle: Option<Particle>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    ambient_sound: Option<String>,
+    pub ambient_sound: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    mood_sound: Option<MoodSound>,
+    pub mood_sound: Option<MoodSound>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    additions_sound: Option<AdditionsSound>,
-    //   #[serde(skip_serializing_if = "Option::is_none")]
-    //   music: Option<Vec<DataPool<Music>>>,
-    music_volume: f32,
+    pub additions_sound: Option<AdditionsSound>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub music: Option<Vec<DataPool<Music>>>,
+    #[serde(default = "default_music_volume")]
+    pub music_volume: f32,
+}
+
+const fn default_music_volume() -> f32 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct Particle {
-    options: ParticleOptions,
-    probability: f32,
+pub struct Particle {
+    pub options: ParticleOptions,
+    pub probability: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ParticleOptions {
-    r#type: String,
+pub struct ParticleOptions {
+    pub r#type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    value: Option<VarInt>,
+    pub value: Option<VarInt>,
 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct MoodSound {
-    sound: String,
-    tick_delay: i32,
-    block_search_extent: i32,
-    offset: f64,
+pub struct MoodSound {
+    pub sound: String,
+    pub tick_delay: i32,
+    pub block_search_extent: i32,
+    pub offset: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct AdditionsSound {
-    sound: String,
-    tick_chance: f64,
+pub struct AdditionsSound {
+    pub sound: String,
+    pub tick_chance: f64,
 }
 
-// #[derive(Debug, Clone, Serialize, Deserialize)]
-// struct Music {
-//     sound: String,
-//     min_delay: i32,
-//     max_delay: i32,
-//     replace_current_music: bool,
-// }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Music {
+    pub sound: String,
+    pub min_delay: i32,
+    pub max_delay: i32,
+    pub replace_current_music: bool,
+}
+
+/// A weighted entry, as used by the vanilla `music` list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataPool<T> {
+    pub data: T,
+    pub weight: i32,
+}
+
+/// Collects biome definitions from every datapack inside `datapacks_dir`.
+///
+/// Biomes are read from `<pack>/data/<namespace>/worldgen/biome/<path>.json` and keyed as
+/// `namespace:path`. Packs are applied in alphabetical order, so later packs override earlier ones.
+pub fn load_datapack_biomes(datapacks_dir: &Path) -> IndexMap<String, Biome> {
+    let mut biomes = IndexMap::new();
+    let Ok(packs) = fs::read_dir(datapacks_dir) else {
+        return biomes;
+    };
+
+    let mut packs: Vec<_> = packs.filter_map(Result::ok).map(|e| e.path()).collect();
+    packs.sort();
+
+    for pack in packs {
+        let Ok(namespaces) = fs::read_dir(pack.join("data")) else {
+            continue;
+        };
+        for namespace in namespaces.filter_map(Result::ok) {
+            let namespace_name = namespace.file_name().to_string_lossy().into_owned();
+            let biome_dir = namespace.path().join("worldgen").join("biome");
+            let Ok(files) = fs::read_dir(&biome_dir) else {
+                continue;
+            };
+            for file in files.filter_map(Result::ok) {
+                let path = file.path();
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                    continue;
+                };
+                let biome = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|s| serde_json::from_str::<Biome>(&s).map_err(|err| err.to_string()));
+                match biome {
+                    Ok(biome) => {
+                        biomes.insert(format!("{namespace_name}:{name}"), biome);
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to load biome {}: {err}", path.display());
+                    }
+                }
+            }
+        }
+    }
+
+    biomes
+}
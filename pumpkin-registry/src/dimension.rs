@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,10 +13,10 @@ pub struct Dimension {
     has_ceiling: bool,
     has_raids: bool,
     has_skylight: bool,
-    height: i32,
+    pub height: i32,
     infiniburn: String,
-    logical_height: i32,
-    min_y: i32,
+    pub logical_height: i32,
+    pub min_y: i32,
     monster_spawn_block_light_limit: i32,
     monster_spawn_light_level: MonsterSpawnLightLevel,
     natural: bool,
@@ -23,6 +25,23 @@ pub struct Dimension {
     ultrawarm: bool,
 }
 
+impl Dimension {
+    /// Monsters don't spawn where the block light is above this.
+    #[must_use]
+    pub const fn monster_spawn_block_light_limit(&self) -> i32 {
+        self.monster_spawn_block_light_limit
+    }
+
+    /// The range the highest light level a monster may spawn at is picked from, uniformly.
+    #[must_use]
+    pub const fn monster_spawn_light_level(&self) -> RangeInclusive<i32> {
+        match &self.monster_spawn_light_level {
+            MonsterSpawnLightLevel::Int(level) => *level..=*level,
+            MonsterSpawnLightLevel::Tagged(tagged) => tagged.min_inclusive..=tagged.max_inclusive,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum DimensionEffects {
     #[serde(rename = "minecraft:overworld")]
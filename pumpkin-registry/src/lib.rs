@@ -1,4 +1,4 @@
-use std::sync::LazyLock;
+use std::{str::FromStr, sync::LazyLock};
 
 use banner_pattern::BannerPattern;
 use biome::Biome;
@@ -7,7 +7,7 @@ use chat_type::{ChatType, Decoration};
 use chicken::ChickenVariant;
 use cow::CowVariant;
 use damage_type::DamageType;
-use dimension::Dimension;
+pub use dimension::Dimension;
 use enchantment::Enchantment;
 use frog::FrogVariant;
 use indexmap::IndexMap;
@@ -18,13 +18,14 @@ use pig::PigVariant;
 use pumpkin_protocol::java::client::config::RegistryEntry;
 use pumpkin_util::include_json_static;
 use pumpkin_util::resource_location::ResourceLocation;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
 use trim_material::TrimMaterial;
 use trim_pattern::TrimPattern;
 use wolf::{WolfSoundVariant, WolfVariant};
 
 mod banner_pattern;
-mod biome;
+pub mod biome;
 mod cat;
 mod chat_type;
 mod chicken;
@@ -44,6 +45,16 @@ mod wolf;
 pub static SYNCED_REGISTRIES: LazyLock<SyncedRegistry> =
     LazyLock::new(|| include_json_static!("../../assets/synced_registries.json", SyncedRegistry));
 
+/// The synced registries exactly as in the vanilla data, for clients that do not know the vanilla
+/// core pack and so need the full contents of every entry.
+static VANILLA_REGISTRY_DATA: LazyLock<IndexMap<String, IndexMap<String, Value>>> =
+    LazyLock::new(|| {
+        include_json_static!(
+            "../../assets/synced_registries.json",
+            IndexMap<String, IndexMap<String, Value>>
+        )
+    });
+
 pub struct Registry {
     pub registry_id: ResourceLocation,
     pub registry_entries: Vec<RegistryEntry>,
@@ -91,6 +102,27 @@ impl VanillaDimensionType {
         }
     }
 
+    /// Returns the synced `minecraft:dimension_type` data for this dimension.
+    pub fn dimension_data(&self) -> &'static Dimension {
+        let name = match self {
+            Self::Overworld => "overworld",
+            Self::OverworldCaves => "overworld_caves",
+            Self::TheEnd => "the_end",
+            Self::TheNether => "the_nether",
+        };
+        &SYNCED_REGISTRIES.dimension_type[name]
+    }
+
+    /// The lowest buildable Y level.
+    pub fn min_y(&self) -> i32 {
+        self.dimension_data().min_y
+    }
+
+    /// The total buildable height, starting at [`Self::min_y`].
+    pub fn height(&self) -> i32 {
+        self.dimension_data().height
+    }
+
     pub fn from_resource_location_string(resource_location: &str) -> Option<Self> {
         match resource_location {
             "minecraft:overworld" => Some(Self::Overworld),
@@ -103,12 +135,56 @@ impl VanillaDimensionType {
 }
 
 impl Registry {
+    /// Returns a copy of this registry with the data of every vanilla entry filled in, for clients
+    /// that can not look the entries up in their own copy of the vanilla core pack.
+    pub fn with_vanilla_data(&self) -> Self {
+        let vanilla = VANILLA_REGISTRY_DATA.get(&self.registry_id.path);
+        let registry_entries = self
+            .registry_entries
+            .iter()
+            .map(|entry| {
+                let value = vanilla
+                    .filter(|_| entry.data.is_none() && entry.entry_id.namespace == "minecraft")
+                    .and_then(|vanilla| vanilla.get(&entry.entry_id.path));
+                match value {
+                    Some(value) => {
+                        RegistryEntry::from_nbt_with_id(entry.entry_id.clone(), &NbtValue(value))
+                    }
+                    None => entry.clone(),
+                }
+            })
+            .collect();
+        Self {
+            registry_id: self.registry_id.clone(),
+            registry_entries,
+        }
+    }
+
     pub fn get_synced() -> Vec<Self> {
-        let registry_entries = SYNCED_REGISTRIES
+        Self::get_synced_with_biomes(&IndexMap::new())
+    }
+
+    /// Builds the synced registries, applying the given biome definitions on top of the vanilla ones.
+    ///
+    /// Biomes are keyed by their full resource location (`namespace:path`). Overridden vanilla biomes
+    /// are sent with their full data, since the client can't rely on its built-in pack for them, while
+    /// new biomes are appended after the vanilla ones so that existing biome IDs stay stable.
+    pub fn get_synced_with_biomes(biome_overrides: &IndexMap<String, Biome>) -> Vec<Self> {
+        let mut registry_entries: Vec<RegistryEntry> = SYNCED_REGISTRIES
             .biome
             .iter()
-            .map(|(name, _nbt)| RegistryEntry::none(name))
+            .map(
+                |(name, _nbt)| match biome_overrides.get(&format!("minecraft:{name}")) {
+                    Some(biome) => RegistryEntry::from_nbt(name, biome),
+                    None => RegistryEntry::none(name),
+                },
+            )
             .collect();
+        registry_entries.extend(biome_overrides.iter().filter_map(|(name, biome)| {
+            let id = ResourceLocation::from_str(name).ok()?;
+            (id.namespace != "minecraft" || !SYNCED_REGISTRIES.biome.contains_key(&id.path))
+                .then(|| RegistryEntry::from_nbt_with_id(id, biome))
+        }));
         let biome = Registry {
             registry_id: ResourceLocation::vanilla("worldgen/biome"),
             registry_entries,
@@ -267,6 +343,36 @@ impl Registry {
             registry_entries,
         };
 
+        let registry_entries = SYNCED_REGISTRIES
+            .trim_pattern
+            .iter()
+            .map(|(name, _nbt)| RegistryEntry::none(name))
+            .collect();
+        let trim_pattern = Registry {
+            registry_id: ResourceLocation::vanilla("trim_pattern"),
+            registry_entries,
+        };
+
+        let registry_entries = SYNCED_REGISTRIES
+            .trim_material
+            .iter()
+            .map(|(name, _nbt)| RegistryEntry::none(name))
+            .collect();
+        let trim_material = Registry {
+            registry_id: ResourceLocation::vanilla("trim_material"),
+            registry_entries,
+        };
+
+        let registry_entries = SYNCED_REGISTRIES
+            .instrument
+            .iter()
+            .map(|(name, _nbt)| RegistryEntry::none(name))
+            .collect();
+        let instrument = Registry {
+            registry_id: ResourceLocation::vanilla("instrument"),
+            registry_entries,
+        };
+
         vec![
             cat_variant,
             chicken_variant,
@@ -275,8 +381,8 @@ impl Registry {
             pig_variant,
             biome,
             chat_type,
-            // trim_pattern,
-            // trim_material,
+            trim_pattern,
+            trim_material,
             wolf_variant,
             painting_variant,
             wolf_sound_variant,
@@ -285,7 +391,32 @@ impl Registry {
             banner_pattern,
             enchantment,
             jukebox_song,
-            // instrument,
+            instrument,
         ]
     }
 }
+
+/// Writes a JSON value of the vanilla data the way the client reads it from NBT. Whole numbers
+/// become ints, since NBT has no unsigned types.
+struct NbtValue<'a>(&'a Value);
+
+impl Serialize for NbtValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => match i32::try_from(value) {
+                    Ok(value) => serializer.serialize_i32(value),
+                    Err(_) => serializer.serialize_i64(value),
+                },
+                None => serializer.serialize_f64(number.as_f64().unwrap_or_default()),
+            },
+            Value::String(value) => serializer.serialize_str(value),
+            Value::Array(values) => serializer.collect_seq(values.iter().map(NbtValue)),
+            Value::Object(values) => {
+                serializer.collect_map(values.iter().map(|(key, value)| (key, NbtValue(value))))
+            }
+        }
+    }
+}
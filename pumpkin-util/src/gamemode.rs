@@ -23,6 +23,69 @@ impl GameMode {
     }
 }
 
+/// What players in each game mode can and can't do. Code that behaves differently per game mode
+/// asks these instead of comparing game modes itself.
+impl GameMode {
+    /// Whether blocks break at the first hit, without dropping anything.
+    #[must_use]
+    pub const fn breaks_blocks_instantly(self) -> bool {
+        matches!(self, Self::Creative)
+    }
+
+    /// Whether placed blocks, used items and spent ammunition are never used up.
+    #[must_use]
+    pub const fn has_infinite_materials(self) -> bool {
+        matches!(self, Self::Creative)
+    }
+
+    /// Whether the player takes damage, apart from damage that bypasses invulnerability like
+    /// falling out of the world.
+    #[must_use]
+    pub const fn takes_damage(self) -> bool {
+        matches!(self, Self::Survival | Self::Adventure)
+    }
+
+    /// Whether actions like sprinting and jumping use up food.
+    #[must_use]
+    pub const fn has_hunger(self) -> bool {
+        matches!(self, Self::Survival | Self::Adventure)
+    }
+
+    /// Whether the player can place and break blocks. Adventure players can only break blocks
+    /// their tool says it can break.
+    #[must_use]
+    pub const fn can_modify_world(self) -> bool {
+        matches!(self, Self::Survival | Self::Creative)
+    }
+
+    /// Whether the player can use blocks, entities and containers rather than just look at them.
+    #[must_use]
+    pub const fn can_interact(self) -> bool {
+        !matches!(self, Self::Spectator)
+    }
+
+    /// Whether the player collides with blocks, triggering things like pressure plates.
+    #[must_use]
+    pub const fn has_collision(self) -> bool {
+        !matches!(self, Self::Spectator)
+    }
+
+    /// Whether the player may fly.
+    #[must_use]
+    pub const fn may_fly(self) -> bool {
+        matches!(self, Self::Creative | Self::Spectator)
+    }
+
+    /// How far away the player can reach blocks.
+    #[must_use]
+    pub const fn block_interaction_range(self) -> f64 {
+        match self {
+            Self::Creative => 5.0,
+            _ => 4.5,
+        }
+    }
+}
+
 impl TryFrom<i8> for GameMode {
     type Error = ();
 
@@ -8,6 +8,7 @@ pub use permission::PermissionLvl;
 pub mod biome;
 pub mod difficulty;
 pub mod gamemode;
+pub mod lock_order;
 pub mod loot_table;
 pub mod math;
 pub mod noise;
@@ -0,0 +1,446 @@
+//! Locks that remember the order they are taken in.
+//!
+//! Every lock belongs to a class, e.g. the players of any world or the chunk queue of any player.
+//! With the `lock-order-audit` feature, taking a lock of class `B` while holding one of class `A`
+//! records that `A` comes before `B`. As soon as some code also takes `A` while holding `B`, two
+//! tasks can end up waiting for each other forever, so both places are logged as a warning long
+//! before the deadlock actually happens. Taking a lock of a class that is already held is reported
+//! too, as tokio's locks are not reentrant.
+//!
+//! On top of the recorded order, the classes in [`LOCK_ORDER`] have a fixed rank. Taking a ranked
+//! lock while holding one ranked after it is reported right away, even if no other code has taken
+//! them the other way round yet.
+//!
+//! Without the feature these are thin wrappers around tokio's locks.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::Location,
+};
+
+use tokio::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+/// The order locks of these classes have to be taken in. While holding a lock of one class, only
+/// locks of the classes listed after it may be taken. Classes not listed here are only checked
+/// against the order they are actually taken in.
+pub const LOCK_ORDER: &[&str] = &[
+    "player.current_screen_handler",
+    // Item stacks are held while using them on blocks, which changes chunks and notifies players
+    "inventory.slot",
+    "world.players",
+    "world.entities",
+    "player.chunk_manager",
+    "level.chunk",
+    "level.entity_chunk",
+];
+
+/// The position of `class` in [`LOCK_ORDER`], if it is listed.
+#[must_use]
+pub fn rank(class: &str) -> Option<usize> {
+    LOCK_ORDER.iter().position(|ranked| *ranked == class)
+}
+
+/// A [`Mutex`] that takes part in lock order auditing.
+pub struct OrderedMutex<T: ?Sized> {
+    class: &'static str,
+    inner: Mutex<T>,
+}
+
+/// A [`RwLock`] that takes part in lock order auditing. Readers and writers are treated alike.
+pub struct OrderedRwLock<T: ?Sized> {
+    class: &'static str,
+    inner: RwLock<T>,
+}
+
+/// Holds one of the ordered locks, and marks its class as held until dropped.
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct OrderedGuard<G> {
+    // Declared first so the lock is released before its class stops being held
+    guard: G,
+    _held: audit::Held,
+}
+
+pub type OrderedMutexGuard<'a, T> = OrderedGuard<MutexGuard<'a, T>>;
+pub type OrderedReadGuard<'a, T> = OrderedGuard<RwLockReadGuard<'a, T>>;
+pub type OrderedWriteGuard<'a, T> = OrderedGuard<RwLockWriteGuard<'a, T>>;
+
+impl<T> OrderedMutex<T> {
+    pub fn new(class: &'static str, value: T) -> Self {
+        Self {
+            class,
+            inner: Mutex::new(value),
+        }
+    }
+}
+
+impl<T: ?Sized> OrderedMutex<T> {
+    #[must_use]
+    pub const fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Locks the mutex, recording the caller as the place the lock was taken.
+    #[track_caller]
+    pub fn lock(&self) -> impl Future<Output = OrderedMutexGuard<'_, T>> {
+        let held = audit::acquire(self.class, Location::caller());
+        async move {
+            OrderedGuard {
+                guard: self.inner.lock().await,
+                _held: held,
+            }
+        }
+    }
+
+    /// Locks the mutex if it is free right now. Only a successful attempt marks the class as
+    /// held.
+    #[track_caller]
+    pub fn try_lock(&self) -> Result<OrderedMutexGuard<'_, T>, TryLockError> {
+        let guard = self.inner.try_lock()?;
+        Ok(OrderedGuard {
+            guard,
+            _held: audit::acquire(self.class, Location::caller()),
+        })
+    }
+}
+
+impl<T> OrderedRwLock<T> {
+    pub fn new(class: &'static str, value: T) -> Self {
+        Self {
+            class,
+            inner: RwLock::new(value),
+        }
+    }
+}
+
+impl<T: ?Sized> OrderedRwLock<T> {
+    #[must_use]
+    pub const fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Locks for reading, recording the caller as the place the lock was taken.
+    #[track_caller]
+    pub fn read(&self) -> impl Future<Output = OrderedReadGuard<'_, T>> {
+        let held = audit::acquire(self.class, Location::caller());
+        async move {
+            OrderedGuard {
+                guard: self.inner.read().await,
+                _held: held,
+            }
+        }
+    }
+
+    /// Locks for writing, recording the caller as the place the lock was taken.
+    #[track_caller]
+    pub fn write(&self) -> impl Future<Output = OrderedWriteGuard<'_, T>> {
+        let held = audit::acquire(self.class, Location::caller());
+        async move {
+            OrderedGuard {
+                guard: self.inner.write().await,
+                _held: held,
+            }
+        }
+    }
+}
+
+impl<G: Deref> Deref for OrderedGuard<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for OrderedGuard<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for OrderedMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedMutex")
+            .field("class", &self.class)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for OrderedRwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedRwLock")
+            .field("class", &self.class)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(not(feature = "lock-order-audit"))]
+mod audit {
+    use std::panic::Location;
+
+    pub struct Held;
+
+    #[inline]
+    pub const fn acquire(_class: &'static str, _location: &'static Location<'static>) -> Held {
+        Held
+    }
+}
+
+#[cfg(feature = "lock-order-audit")]
+mod audit {
+    use std::{
+        collections::{HashMap, HashSet, VecDeque},
+        panic::Location,
+        sync::{LazyLock, Mutex, PoisonError},
+        thread::{self, ThreadId},
+    };
+
+    type Site = &'static Location<'static>;
+
+    /// Whoever holds a lock. Guards are usually held across awaits, so this is the task when
+    /// running on tokio and the thread otherwise.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    enum Owner {
+        Task(tokio::task::Id),
+        Thread(ThreadId),
+    }
+
+    impl Owner {
+        fn current() -> Self {
+            tokio::task::try_id().map_or_else(|| Self::Thread(thread::current().id()), Self::Task)
+        }
+    }
+
+    #[derive(Default)]
+    struct LockOrder {
+        /// The classes each owner holds right now, and where it took them.
+        held: HashMap<Owner, Vec<(&'static str, Site)>>,
+        /// For each class, the classes taken while holding it. The sites are where the held
+        /// lock and the new lock were taken the first time this happened.
+        after: HashMap<&'static str, HashMap<&'static str, (Site, Site)>>,
+        /// Problems already logged, so every one is only reported once.
+        reported: HashSet<(&'static str, &'static str)>,
+    }
+
+    static LOCK_ORDER: LazyLock<Mutex<LockOrder>> = LazyLock::new(Mutex::default);
+
+    pub struct Held {
+        owner: Owner,
+        class: &'static str,
+    }
+
+    pub fn acquire(class: &'static str, location: Site) -> Held {
+        let owner = Owner::current();
+        let mut order = LOCK_ORDER.lock().unwrap_or_else(PoisonError::into_inner);
+        for report in order.record(owner, class, location) {
+            log::warn!("{report}");
+        }
+        Held { owner, class }
+    }
+
+    impl Drop for Held {
+        fn drop(&mut self) {
+            let mut order = LOCK_ORDER.lock().unwrap_or_else(PoisonError::into_inner);
+            order.release(self.owner, self.class);
+        }
+    }
+
+    impl LockOrder {
+        /// Records that `owner` is about to take a lock of `class` at `location` and returns a
+        /// report for every new way this could deadlock.
+        fn record(&mut self, owner: Owner, class: &'static str, location: Site) -> Vec<String> {
+            let mut reports = Vec::new();
+            let held = self.held.entry(owner).or_default().clone();
+            for (held_class, held_at) in held {
+                if held_class == class {
+                    if self.reported.insert((class, class)) {
+                        reports.push(format!(
+                            "Lock order: `{class}` is taken at {location} while already held \
+                             since {held_at}; this deadlocks as soon as another task waits for it \
+                             in between"
+                        ));
+                    }
+                    continue;
+                }
+                self.after
+                    .entry(held_class)
+                    .or_default()
+                    .entry(class)
+                    .or_insert((held_at, location));
+                let against_rank = matches!(
+                    (super::rank(held_class), super::rank(class)),
+                    (Some(held_rank), Some(rank)) if held_rank > rank
+                );
+                if against_rank && self.reported.insert((held_class, class)) {
+                    reports.push(format!(
+                        "Lock order: `{held_class}` (taken at {held_at}) is held while taking \
+                         `{class}` at {location}, but `{class}` comes first in `LOCK_ORDER`. \
+                         Take `{class}` before `{held_class}`, or release `{held_class}` first"
+                    ));
+                } else if let Some(path) = self.path(class, held_class)
+                    && self.reported.insert((held_class, class))
+                {
+                    reports.push(format!(
+                        "Lock order: `{held_class}` (taken at {held_at}) is held while taking \
+                         `{class}` at {location}, but elsewhere they are taken the other way \
+                         round: {}. Two tasks doing both can deadlock; always take these locks in \
+                         the same order",
+                        self.describe(&path)
+                    ));
+                }
+            }
+            self.held.entry(owner).or_default().push((class, location));
+            reports
+        }
+
+        fn release(&mut self, owner: Owner, class: &'static str) {
+            let Some(held) = self.held.get_mut(&owner) else {
+                return;
+            };
+            if let Some(index) = held.iter().rposition(|(held, _)| *held == class) {
+                held.remove(index);
+            }
+            if held.is_empty() {
+                self.held.remove(&owner);
+            }
+        }
+
+        /// The classes on the shortest chain of recorded orders leading from `from` to `to`.
+        fn path(&self, from: &'static str, to: &'static str) -> Option<Vec<&'static str>> {
+            let mut previous = HashMap::from([(from, from)]);
+            let mut queue = VecDeque::from([from]);
+            while let Some(class) = queue.pop_front() {
+                if class == to {
+                    let mut path = vec![to];
+                    let mut class = to;
+                    while class != from {
+                        class = previous[class];
+                        path.push(class);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                for &next in self
+                    .after
+                    .get(class)
+                    .into_iter()
+                    .flat_map(|after| after.keys())
+                {
+                    if !previous.contains_key(next) {
+                        previous.insert(next, class);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            None
+        }
+
+        fn describe(&self, path: &[&'static str]) -> String {
+            path.windows(2)
+                .map(|pair| {
+                    let (held_at, taken_at) = self.after[pair[0]][pair[1]];
+                    format!(
+                        "`{}` (taken at {held_at}) before `{}` (taken at {taken_at})",
+                        pair[0], pair[1]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", then ")
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// A new owner. Thread ids can't be made up, so every one is a thread of its own.
+        fn owner() -> Owner {
+            Owner::Thread(thread::spawn(|| thread::current().id()).join().unwrap())
+        }
+
+        #[test]
+        fn consistent_order() {
+            let mut order = LockOrder::default();
+            let (a, b) = (owner(), owner());
+            for owner in [a, b] {
+                assert!(order.record(owner, "world", Location::caller()).is_empty());
+                assert!(order.record(owner, "chunks", Location::caller()).is_empty());
+                order.release(owner, "chunks");
+                order.release(owner, "world");
+            }
+            assert!(order.held.is_empty());
+        }
+
+        #[test]
+        fn inverted_order() {
+            let mut order = LockOrder::default();
+            let (a, b) = (owner(), owner());
+            order.record(a, "world", Location::caller());
+            order.record(a, "chunks", Location::caller());
+            order.release(a, "chunks");
+            order.release(a, "world");
+
+            order.record(b, "chunks", Location::caller());
+            assert_eq!(order.record(b, "world", Location::caller()).len(), 1);
+            // The same problem is only reported once
+            order.release(b, "world");
+            assert!(order.record(b, "world", Location::caller()).is_empty());
+        }
+
+        #[test]
+        fn inverted_through_other_lock() {
+            let mut order = LockOrder::default();
+            let a = owner();
+            order.record(a, "world", Location::caller());
+            order.record(a, "players", Location::caller());
+            order.release(a, "players");
+            order.release(a, "world");
+            order.record(a, "players", Location::caller());
+            order.record(a, "inventory", Location::caller());
+            order.release(a, "inventory");
+            order.release(a, "players");
+
+            assert_eq!(
+                order.path("world", "inventory"),
+                Some(vec!["world", "players", "inventory"])
+            );
+            order.record(a, "inventory", Location::caller());
+            assert_eq!(order.record(a, "world", Location::caller()).len(), 1);
+        }
+
+        #[test]
+        fn against_rank() {
+            let mut order = LockOrder::default();
+            let a = owner();
+            assert!(
+                order
+                    .record(a, "inventory.slot", Location::caller())
+                    .is_empty()
+            );
+            assert!(
+                order
+                    .record(a, "level.chunk", Location::caller())
+                    .is_empty()
+            );
+            order.release(a, "level.chunk");
+            order.release(a, "inventory.slot");
+
+            // Reported the first time, without the other order ever being taken
+            order.record(a, "level.chunk", Location::caller());
+            assert_eq!(
+                order.record(a, "world.players", Location::caller()).len(),
+                1
+            );
+        }
+
+        #[test]
+        fn reentrant() {
+            let mut order = LockOrder::default();
+            let a = owner();
+            order.record(a, "players", Location::caller());
+            assert_eq!(order.record(a, "players", Location::caller()).len(), 1);
+        }
+    }
+}
@@ -56,7 +56,12 @@ pub enum LootCondition {
         block: &'static str,
         properties: &'static [(&'static str, &'static str)],
     },
-    MatchTool,
+    MatchTool {
+        /// An item id or an item tag (prefixed with `#`) the tool has to match.
+        items: Option<&'static str>,
+        /// Enchantments with their minimum level the tool has to have.
+        enchantments: &'static [(&'static str, i32)],
+    },
     TableBonus,
     SurvivesExplosion,
     DamageSourceProperties,
@@ -1,4 +1,4 @@
-use crate::math::{vector2::Vector2, vector3::Axis};
+use crate::math::vector3::Axis;
 
 use super::{position::BlockPos, vector3::Vector3};
 
@@ -8,39 +8,6 @@ pub struct BoundingBox {
     pub max: Vector3<f64>,
 }
 
-#[derive(Clone, Copy, Debug)]
-
-struct BoundingPlane {
-    pub min: Vector2<f64>,
-    pub max: Vector2<f64>,
-}
-
-impl BoundingPlane {
-    pub fn intersects(&self, other: &Self) -> bool {
-        self.min.x < other.max.x
-            && self.max.x > other.min.x
-            && self.min.y < other.max.y
-            && self.max.y > other.min.y
-    }
-
-    // Projecting a 3D box into 2D
-    pub fn from_box(bounding_box: &BoundingBox, excluded: Axis) -> Self {
-        let [axis1, axis2] = Axis::excluding(excluded);
-
-        Self {
-            min: Vector2::new(
-                bounding_box.get_side(false).get_axis(axis1),
-                bounding_box.get_side(false).get_axis(axis2),
-            ),
-
-            max: Vector2::new(
-                bounding_box.get_side(true).get_axis(axis1),
-                bounding_box.get_side(true).get_axis(axis2),
-            ),
-        }
-    }
-}
-
 impl BoundingBox {
     pub fn new_default(size: &EntityDimensions) -> Self {
         Self::new_from_pos(0., 0., 0., size)
@@ -95,39 +62,28 @@ impl BoundingBox {
         if max { self.max } else { self.min }
     }
 
-    pub fn calculate_collision_time(
-        &self,
-        other: &Self,
-        movement: Vector3<f64>,
-        axis: Axis,
-        max_time: f64, // Start with 1.0
-    ) -> Option<f64> {
-        let movement_on_axis = movement.get_axis(axis);
+    /// Clamps `offset`, a movement of this box along `axis`, so that it stops right at `other`
+    /// instead of moving into it. Boxes that don't overlap this one on the other two axes never
+    /// block it.
+    pub fn calculate_max_offset(&self, other: &Self, axis: Axis, offset: f64) -> f64 {
+        const EPSILON: f64 = 1.0E-7;
 
-        if movement_on_axis == 0.0 {
-            return None;
+        let [axis1, axis2] = Axis::excluding(axis);
+        let overlaps = |axis: Axis| {
+            self.min.get_axis(axis) < other.max.get_axis(axis) - EPSILON
+                && self.max.get_axis(axis) > other.min.get_axis(axis) + EPSILON
+        };
+        if !overlaps(axis1) || !overlaps(axis2) {
+            return offset;
         }
 
-        let move_positive = movement_on_axis.is_sign_positive();
-        let self_plane_const = self.get_side(move_positive).get_axis(axis);
-        let other_plane_const = other.get_side(!move_positive).get_axis(axis);
-        let collision_time = (other_plane_const - self_plane_const) / movement_on_axis;
-
-        if collision_time < 0.0 || collision_time >= max_time {
-            return None;
+        if offset > 0.0 && other.min.get_axis(axis) >= self.max.get_axis(axis) - EPSILON {
+            offset.min(other.min.get_axis(axis) - self.max.get_axis(axis))
+        } else if offset < 0.0 && other.max.get_axis(axis) <= self.min.get_axis(axis) + EPSILON {
+            offset.max(other.max.get_axis(axis) - self.min.get_axis(axis))
+        } else {
+            offset
         }
-
-        let self_moved = self.shift(movement * collision_time);
-
-        let self_plane_moved = BoundingPlane::from_box(&self_moved, axis);
-
-        let other_plane = BoundingPlane::from_box(other, axis);
-
-        if !self_plane_moved.intersects(&other_plane) {
-            return None;
-        }
-
-        Some(collision_time)
     }
 
     pub fn min_block_pos(&self) -> BlockPos {
@@ -200,3 +156,39 @@ pub struct EntityDimensions {
     pub width: f32,
     pub height: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box(x: f64, y: f64, z: f64) -> BoundingBox {
+        BoundingBox::new(
+            Vector3::new(x, y, z),
+            Vector3::new(x + 1.0, y + 1.0, z + 1.0),
+        )
+    }
+
+    #[test]
+    fn stops_at_obstacle() {
+        let moving = unit_box(0.0, 0.0, 0.0);
+        let obstacle = unit_box(1.5, 0.0, 0.0);
+        assert_eq!(moving.calculate_max_offset(&obstacle, Axis::X, 2.0), 0.5);
+        assert_eq!(moving.calculate_max_offset(&obstacle, Axis::X, 0.25), 0.25);
+        assert_eq!(moving.calculate_max_offset(&obstacle, Axis::X, -1.0), -1.0);
+    }
+
+    #[test]
+    fn lands_on_slab() {
+        let moving = unit_box(0.0, 1.0, 0.0);
+        let slab = BoundingBox::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.5, 1.0));
+        assert_eq!(moving.calculate_max_offset(&slab, Axis::Y, -1.0), -0.5);
+    }
+
+    #[test]
+    fn ignores_touching_neighbours() {
+        let moving = unit_box(0.0, 0.0, 0.0);
+        // Only touches the moving box along Z, so it can slide past it along X
+        let neighbour = unit_box(1.0, 0.0, 1.0);
+        assert_eq!(moving.calculate_max_offset(&neighbour, Axis::X, 1.0), 1.0);
+    }
+}
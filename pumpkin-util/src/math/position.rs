@@ -253,6 +253,13 @@ impl BlockPos {
             y: y_rem,
         }
     }
+    /// Packs the position into a long the way the protocol sends it.
+    pub const fn as_long(&self) -> i64 {
+        ((self.0.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
+            | (self.0.y as i64 & 0xFFF)
+    }
+
     pub fn from_i64(encoded_position: i64) -> Self {
         BlockPos(Vector3 {
             x: (encoded_position >> 38) as i32,
@@ -363,10 +370,7 @@ impl BlockPos {
 
 impl Serialize for BlockPos {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let long = ((self.0.x as i64 & 0x3FFFFFF) << 38)
-            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
-            | (self.0.y as i64 & 0xFFF);
-        serializer.serialize_i64(long)
+        serializer.serialize_i64(self.as_long())
     }
 }
 
@@ -140,9 +140,53 @@ impl TextComponentBase {
         }
     }
 
+    /// Sets the locale of every custom translation in this component and its children.
+    pub fn localize(&mut self, locale: Locale) {
+        if let TextContent::Custom {
+            locale: custom_locale,
+            with,
+            ..
+        } = &mut self.content
+        {
+            *custom_locale = locale;
+            for substitution in with {
+                substitution.localize(locale);
+            }
+        }
+        for child in &mut self.extra {
+            child.localize(locale);
+        }
+        match &mut self.style.hover_event {
+            Some(HoverEvent::ShowText { value }) => {
+                for hover_component in value {
+                    hover_component.localize(locale);
+                }
+            }
+            Some(HoverEvent::ShowEntity {
+                name: Some(name), ..
+            }) => {
+                for name_component in name {
+                    name_component.localize(locale);
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn to_translated(self) -> Self {
         // Divide the translation into slices and inserts the substitutions
         let component = match self.content {
+            // The client has every vanilla key and renders it in its own language
+            TextContent::Custom { key, with, .. } if key.starts_with("minecraft:") => {
+                TextComponentBase {
+                    content: TextContent::Translate {
+                        translate: Cow::Owned(key["minecraft:".len()..].to_string()),
+                        with: with.into_iter().map(Self::to_translated).collect(),
+                    },
+                    style: self.style,
+                    extra: self.extra,
+                }
+            }
             TextContent::Custom { key, with, locale } => {
                 let translation = get_translation(key, locale);
                 let mut translation_parent = translation.clone();
@@ -280,6 +324,14 @@ impl TextComponent {
         })
     }
 
+    /// Resolves the custom translations in this component in `locale`, usually the locale of the
+    /// player it is sent to.
+    #[must_use]
+    pub fn localized(mut self, locale: Locale) -> Self {
+        self.0.localize(locale);
+        self
+    }
+
     pub fn add_child(mut self, child: TextComponent) -> Self {
         self.0.extra.push(child.0);
         self
@@ -447,7 +499,8 @@ pub enum TextContent {
 mod test {
     use pumpkin_nbt::serializer::to_bytes_unnamed;
 
-    use crate::text::{TextComponent, color::NamedColor};
+    use crate::text::{TextComponent, TextContent, color::NamedColor};
+    use crate::translation::Locale;
 
     #[test]
     fn test_serialize_text_component() {
@@ -471,4 +524,38 @@ mod test {
 
         assert_eq!(bytes, expected_bytes);
     }
+
+    #[test]
+    fn custom_translation_follows_player_locale() {
+        let message = TextComponent::custom("pumpkin", "commands.error.internal", Locale::EnUs, []);
+        assert_eq!(
+            message.clone().get_text(),
+            "Internal error (See logs for details)"
+        );
+
+        // Mexican Spanish has no catalog of its own and falls back to European Spanish
+        let localized = message.localized(Locale::EsMx);
+        assert_eq!(
+            localized.0.get_text(Locale::EsMx),
+            "Error interno (consulta los registros para más detalles)"
+        );
+    }
+
+    #[test]
+    fn vanilla_custom_translation_is_sent_as_translatable() {
+        let message = TextComponent::custom(
+            "minecraft",
+            "multiplayer.player.joined",
+            Locale::FrFr,
+            [TextComponent::text("NAME")],
+        );
+        let translated = message.0.to_translated();
+        assert_eq!(
+            translated.content,
+            TextContent::Translate {
+                translate: "multiplayer.player.joined".into(),
+                with: vec![TextComponent::text("NAME").0],
+            }
+        );
+    }
 }
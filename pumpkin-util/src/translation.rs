@@ -57,16 +57,18 @@ pub fn add_translation_file<P: Into<String>>(namespace: P, file_path: P, locale:
     }
 }
 
+/// Looks up `key` for `locale`, falling back along [`Locale::fallback`] and finally to the key itself.
 pub fn get_translation<P: Into<String>>(key: P, locale: Locale) -> String {
     let translations = TRANSLATIONS.lock().unwrap();
     let key = key.into().to_lowercase();
-    match translations[locale as usize].get(&key) {
-        Some(translation) => translation.clone(),
-        None => match translations[Locale::EnUs as usize].get(&key) {
-            Some(translation) => translation.clone(),
-            None => key,
-        },
+    let mut locale = Some(locale);
+    while let Some(current) = locale {
+        if let Some(translation) = translations[current as usize].get(&key) {
+            return translation.clone();
+        }
+        locale = current.fallback();
     }
+    key
 }
 
 pub fn reorder_substitutions(
@@ -355,6 +357,25 @@ impl Locale {
     pub const fn last() -> Self {
         Locale::ZlmArab
     }
+
+    /// The locale to look a translation up in when this one does not have it.
+    ///
+    /// Regional variants fall back to the variant Pumpkin ships translations for, every other
+    /// locale falls back to American English, which has every key.
+    pub const fn fallback(self) -> Option<Self> {
+        match self {
+            Locale::EnUs => None,
+            Locale::EsAr
+            | Locale::EsCl
+            | Locale::EsEc
+            | Locale::EsMx
+            | Locale::EsUy
+            | Locale::EsVe
+            | Locale::Esan => Some(Locale::EsEs),
+            Locale::FrCa => Some(Locale::FrFr),
+            _ => Some(Locale::EnUs),
+        }
+    }
 }
 
 impl FromStr for Locale {
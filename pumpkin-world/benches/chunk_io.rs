@@ -5,13 +5,12 @@ use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use pumpkin_data::BlockDirection;
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use pumpkin_world::{
-    chunk::ChunkData,
     dimension::Dimension,
     global_path,
-    level::{ChunkEntry, Level},
+    level::{ChunkEntry, Level, SyncChunk},
     world::{BlockAccessor, BlockRegistryExt},
 };
-use tokio::{runtime::Runtime, sync::RwLock};
+use tokio::runtime::Runtime;
 
 async fn test_reads(level: &Arc<Level>, positions: Vec<Vector2<i32>>) {
     let level = level.clone();
@@ -42,7 +41,7 @@ async fn test_reads_parallel(level: &Arc<Level>, positions: Vec<Vector2<i32>>, t
 }
 */
 
-async fn test_writes(level: &Arc<Level>, chunks: Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)>) {
+async fn test_writes(level: &Arc<Level>, chunks: Vec<(Vector2<i32>, SyncChunk)>) {
     level
         .write_chunks(
             chunks
@@ -56,7 +55,7 @@ async fn test_writes(level: &Arc<Level>, chunks: Vec<(Vector2<i32>, Arc<RwLock<C
 /*
 async fn test_writes_parallel(
     level: &Arc<Level>,
-    chunks: Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)>,
+    chunks: Vec<(Vector2<i32>, SyncChunk)>,
     threads: usize,
 ) {
     let mut tasks = JoinSet::new();
@@ -98,10 +97,7 @@ impl BlockRegistryExt for BlockRegistry {
     }
 }
 
-fn initialize_level(
-    async_handler: &Runtime,
-    root_dir: PathBuf,
-) -> Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> {
+fn initialize_level(async_handler: &Runtime, root_dir: PathBuf) -> Vec<(Vector2<i32>, SyncChunk)> {
     println!("Initializing data...");
     // Initial writes
     let mut chunks = Vec::new();
@@ -0,0 +1,109 @@
+use std::{hint::black_box, sync::Arc};
+
+use async_trait::async_trait;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use pumpkin_data::BlockDirection;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use pumpkin_world::{
+    chunk::{ChunkData, format::anvil::SingleChunkDataSerializer},
+    dimension::Dimension,
+    level::Level,
+    world::{BlockAccessor, BlockRegistryExt},
+};
+use tokio::runtime::Runtime;
+
+const SEED: i64 = 123;
+/// Overworld chunks from the origin out to this distance are generated as fixtures
+const FIXTURE_RADIUS: i32 = 2;
+
+struct BlockRegistry;
+
+#[async_trait]
+impl BlockRegistryExt for BlockRegistry {
+    fn can_place_at(
+        &self,
+        _block: &pumpkin_data::Block,
+        _block_accessor: &dyn BlockAccessor,
+        _block_pos: &BlockPos,
+        _face: BlockDirection,
+    ) -> bool {
+        true
+    }
+}
+
+/// Generates the fixture chunks around the origin. Their content only depends on the seed, so
+/// every run serializes the same terrain.
+fn generate_fixtures(async_handler: &Runtime) -> Vec<ChunkData> {
+    let root_dir = temp_dir::TempDir::new().unwrap();
+    async_handler.block_on(async {
+        let level = Level::from_root_folder(
+            root_dir.path().to_path_buf(),
+            Arc::new(BlockRegistry),
+            SEED,
+            Dimension::Overworld,
+        );
+        let positions = (-FIXTURE_RADIUS..=FIXTURE_RADIUS)
+            .flat_map(|x| (-FIXTURE_RADIUS..=FIXTURE_RADIUS).map(move |z| Vector2::new(x, z)))
+            .collect();
+        let mut receiver = level.receive_chunks(positions);
+
+        let mut chunks = Vec::new();
+        while let Some((chunk, _)) = receiver.recv().await {
+            let bytes = chunk.read().await.to_bytes().await.unwrap();
+            // Round trip so the fixtures own their data instead of sharing it with the level
+            let position = chunk.read().await.position;
+            chunks.push(ChunkData::from_bytes(bytes, position).unwrap());
+        }
+        chunks.sort_unstable_by_key(|chunk| (chunk.position.x, chunk.position.y));
+        chunks
+    })
+}
+
+fn bench_chunk_serialization(c: &mut Criterion) {
+    let async_handler = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let generated = generate_fixtures(&async_handler);
+    let empty = ChunkData::empty(Vector2::new(0, 0), -64, 24);
+
+    let fixtures = [("empty", vec![empty]), ("generated", generated)];
+
+    let mut serialize_group = c.benchmark_group("serialize_chunks");
+    for (name, chunks) in &fixtures {
+        serialize_group.bench_with_input(BenchmarkId::from_parameter(name), chunks, |b, chunks| {
+            b.to_async(&async_handler).iter(async || {
+                for chunk in chunks {
+                    black_box(chunk.to_bytes().await.unwrap());
+                }
+            });
+        });
+    }
+    serialize_group.finish();
+
+    let mut deserialize_group = c.benchmark_group("deserialize_chunks");
+    for (name, chunks) in &fixtures {
+        let serialized = async_handler.block_on(async {
+            let mut serialized = Vec::with_capacity(chunks.len());
+            for chunk in chunks {
+                serialized.push((chunk.position, chunk.to_bytes().await.unwrap()));
+            }
+            serialized
+        });
+        deserialize_group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &serialized,
+            |b, serialized| {
+                b.iter(|| {
+                    for (position, bytes) in serialized {
+                        // Cloning `Bytes` only bumps a reference count
+                        black_box(ChunkData::from_bytes(bytes.clone(), *position).unwrap());
+                    }
+                });
+            },
+        );
+    }
+    deserialize_group.finish();
+}
+
+criterion_group!(benches, bench_chunk_serialization);
+criterion_main!(benches);
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use pumpkin_data::block_properties::{BarrelLikeProperties, BlockProperties};
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::{Block, FacingExt};
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::random::xoroshiro128::Xoroshiro;
@@ -14,13 +15,12 @@ use std::{
         atomic::{AtomicBool, Ordering},
     },
 };
-use tokio::sync::Mutex;
 
 use crate::block::viewer::{ViewerCountListener, ViewerCountTracker};
 use crate::world::{BlockFlags, SimpleWorld};
 use crate::{
     inventory::{
-        split_stack, {Clearable, Inventory},
+        new_slot, split_stack, {Clearable, Inventory, InventoryListeners},
     },
     item::ItemStack,
 };
@@ -30,8 +30,9 @@ use super::BlockEntity;
 #[derive(Debug)]
 pub struct BarrelBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 27],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 27],
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 
     // Viewer
     pub viewers: ViewerCountTracker,
@@ -53,8 +54,9 @@ impl BlockEntity for BarrelBlockEntity {
     {
         let barrel = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         };
 
@@ -106,16 +108,23 @@ impl BarrelBlockEntity {
     pub fn new(position: BlockPos) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         }
     }
 
     async fn set_open(&self, world: &Arc<dyn SimpleWorld>, open: bool) {
-        let state = world.get_block_state(&self.position).await;
+        let (block, state) = world.get_block_and_state(&self.position).await;
+        if block != &Block::BARREL {
+            return;
+        }
         let mut properties = BarrelLikeProperties::from_state_id(state.id, &Block::BARREL);
-
+        // Another viewer may already have opened it
+        if properties.open == open {
+            return;
+        }
         properties.open = open;
 
         world
@@ -167,7 +176,7 @@ impl Inventory for BarrelBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -196,6 +205,11 @@ impl Inventory for BarrelBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -0,0 +1,417 @@
+use std::any::Any;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::{
+    HorizontalFacingExt,
+    block_properties::{BeeNestLikeProperties, BlockProperties, EnumVariants, Integer0To5},
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+    tag::{self, Taggable},
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use rand::{Rng, rng};
+use tokio::sync::Mutex;
+
+use crate::{
+    block::entities::BlockEntity,
+    world::{BlockFlags, SimpleWorld},
+};
+
+const BEES: &str = "bees";
+const ENTITY_DATA: &str = "entity_data";
+const TICKS_IN_HIVE: &str = "ticks_in_hive";
+const MIN_TICKS_IN_HIVE: &str = "min_ticks_in_hive";
+const FLOWER_POS: &str = "flower_pos";
+
+/// The bee's own data the hive writes before letting it out.
+const HAS_NECTAR: &str = "HasNectar";
+const HIVE_POS: &str = "hive_pos";
+const CROPS_GROWN_SINCE_POLLINATION: &str = "CropsGrownSincePollination";
+
+/// Parts of a bee's data that describe it outside of the hive and are dropped when it enters.
+const IGNORED_BEE_TAGS: [&str; 20] = [
+    "Air",
+    "DeathTime",
+    "FallDistance",
+    "FallFlying",
+    "Fire",
+    "HurtByTimestamp",
+    "HurtTime",
+    "Motion",
+    "NoGravity",
+    "OnGround",
+    "PortalCooldown",
+    "Pos",
+    "Rotation",
+    "Passengers",
+    "leash",
+    "UUID",
+    "CannotEnterHiveTicks",
+    "TicksSincePollination",
+    CROPS_GROWN_SINCE_POLLINATION,
+    HIVE_POS,
+];
+
+/// A bee resting inside a hive.
+#[derive(Clone, Debug)]
+pub struct Occupant {
+    pub entity_data: NbtCompound,
+    pub ticks_in_hive: i32,
+    /// The bee leaves once it spent this many ticks inside
+    pub min_ticks_in_hive: i32,
+}
+
+impl Occupant {
+    fn has_nectar(&self) -> bool {
+        self.entity_data.get_bool(HAS_NECTAR).unwrap_or(false)
+    }
+
+    fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put_component(ENTITY_DATA, self.entity_data.clone());
+        nbt.put_int(TICKS_IN_HIVE, self.ticks_in_hive);
+        nbt.put_int(MIN_TICKS_IN_HIVE, self.min_ticks_in_hive);
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        Some(Self {
+            entity_data: nbt.get_compound(ENTITY_DATA)?.clone(),
+            ticks_in_hive: nbt.get_int(TICKS_IN_HIVE).unwrap_or(0),
+            min_ticks_in_hive: nbt.get_int(MIN_TICKS_IN_HIVE).unwrap_or(0),
+        })
+    }
+}
+
+/// Why a bee leaves its hive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BeeReleaseStatus {
+    /// The bee brought nectar and turned it into honey
+    HoneyDelivered,
+    /// The bee rested long enough
+    BeeReleased,
+    /// The hive was broken or harvested without smoke, the bees leave whatever the weather
+    Emergency,
+}
+
+/// The block entity of bee nests and beehives, holding the bees that are inside.
+pub struct BeehiveBlockEntity {
+    pub position: BlockPos,
+    pub bees: Mutex<Vec<Occupant>>,
+    /// The flower the last bee to enter found, given to bees leaving without one of their own
+    pub flower_pos: AtomicCell<Option<BlockPos>>,
+    pub dirty: AtomicBool,
+}
+
+impl BeehiveBlockEntity {
+    pub const ID: &'static str = "minecraft:beehive";
+    pub const MAX_OCCUPANTS: usize = 3;
+    pub const MAX_HONEY_LEVEL: u16 = 5;
+    /// How long a bee with nectar stays inside to make honey.
+    const MIN_OCCUPATION_TICKS_NECTAR: i32 = 2400;
+    /// How long a bee without nectar rests inside.
+    const MIN_OCCUPATION_TICKS_NECTARLESS: i32 = 600;
+
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            bees: Mutex::new(Vec::new()),
+            flower_pos: AtomicCell::new(None),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn is_full(&self) -> bool {
+        self.bees.lock().await.len() >= Self::MAX_OCCUPANTS
+    }
+
+    pub async fn has_bees(&self) -> bool {
+        !self.bees.lock().await.is_empty()
+    }
+
+    /// Takes in a bee given by its saved data, returns false if the hive is full. The caller
+    /// removes the bee from the world.
+    pub async fn add_bee(
+        &self,
+        mut entity_data: NbtCompound,
+        flower_pos: Option<BlockPos>,
+    ) -> bool {
+        let mut bees = self.bees.lock().await;
+        if bees.len() >= Self::MAX_OCCUPANTS {
+            return false;
+        }
+
+        entity_data
+            .child_tags
+            .retain(|(key, _)| !IGNORED_BEE_TAGS.contains(&key.as_str()));
+        let min_ticks_in_hive = if entity_data.get_bool(HAS_NECTAR).unwrap_or(false) {
+            Self::MIN_OCCUPATION_TICKS_NECTAR
+        } else {
+            Self::MIN_OCCUPATION_TICKS_NECTARLESS
+        };
+        bees.push(Occupant {
+            entity_data,
+            ticks_in_hive: 0,
+            min_ticks_in_hive,
+        });
+        drop(bees);
+
+        if flower_pos.is_some() {
+            self.flower_pos.store(flower_pos);
+        }
+        self.dirty.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Lets out every bee that can leave for `status`.
+    pub async fn release_all(&self, world: &Arc<dyn SimpleWorld>, status: BeeReleaseStatus) {
+        let mut bees = self.bees.lock().await;
+        let mut kept = Vec::with_capacity(bees.len());
+        for bee in bees.drain(..) {
+            if !self.release_occupant(world, &bee, status).await {
+                kept.push(bee);
+            }
+        }
+        *bees = kept;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Spawns `bee` in front of the hive, returns false if it has to stay inside for now.
+    async fn release_occupant(
+        &self,
+        world: &Arc<dyn SimpleWorld>,
+        bee: &Occupant,
+        status: BeeReleaseStatus,
+    ) -> bool {
+        if status != BeeReleaseStatus::Emergency
+            && (world.is_night().await || world.is_raining().await)
+        {
+            return false;
+        }
+
+        let (block, state) = world.get_block_and_state(&self.position).await;
+        // Once the hive is gone its bees come out where it stood
+        let mut props = block
+            .is_tagged_with_by_tag(&tag::Block::MINECRAFT_BEEHIVES)
+            .then(|| BeeNestLikeProperties::from_state_id(state.id, block));
+        let direction = props
+            .as_ref()
+            .map(|props| props.facing.to_block_direction());
+        let blocked = match direction {
+            Some(direction) => !world
+                .get_block_state(&self.position.offset(direction.to_offset()))
+                .await
+                .get_block_collision_shapes()
+                .is_empty(),
+            None => true,
+        };
+        if blocked && status != BeeReleaseStatus::Emergency {
+            return false;
+        }
+
+        let mut entity_data = bee.entity_data.clone();
+        entity_data.child_tags.retain(|(key, _)| {
+            key != HAS_NECTAR && key != HIVE_POS && key != CROPS_GROWN_SINCE_POLLINATION
+        });
+        let pos = self.position.0;
+        entity_data.put(HIVE_POS, NbtTag::IntArray(vec![pos.x, pos.y, pos.z]));
+        if let Some(flower) = self.flower_pos.load()
+            && !entity_data
+                .child_tags
+                .iter()
+                .any(|(key, _)| key == FLOWER_POS)
+            && rng().random::<f32>() < 0.9
+        {
+            let flower = flower.0;
+            entity_data.put(
+                FLOWER_POS,
+                NbtTag::IntArray(vec![flower.x, flower.y, flower.z]),
+            );
+        }
+        entity_data.put_bool(
+            HAS_NECTAR,
+            bee.has_nectar() && status != BeeReleaseStatus::HoneyDelivered,
+        );
+
+        if status == BeeReleaseStatus::HoneyDelivered
+            && let Some(props) = props.as_mut()
+        {
+            let honey_level = props.honey_level.to_index();
+            if honey_level < Self::MAX_HONEY_LEVEL {
+                let added = if rng().random_range(0..100) == 0 {
+                    2
+                } else {
+                    1
+                };
+                props.honey_level =
+                    Integer0To5::from_index((honey_level + added).min(Self::MAX_HONEY_LEVEL));
+                world
+                    .clone()
+                    .set_block_state(
+                        &self.position,
+                        props.to_state_id(block),
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+            }
+        }
+
+        let [width, height] = EntityType::BEE.dimension;
+        let distance = if blocked {
+            0.0
+        } else {
+            0.55 + f64::from(width) / 2.0
+        };
+        let offset = direction.map_or(Vector3::new(0.0, 0.0, 0.0), |direction| {
+            direction.to_offset().to_f64()
+        });
+        let position = Vector3::new(
+            distance.mul_add(offset.x, f64::from(pos.x) + 0.5),
+            f64::from(pos.y) + 0.5 - f64::from(height) / 2.0,
+            distance.mul_add(offset.z, f64::from(pos.z) + 0.5),
+        );
+        if !world.clone().spawn_from_nbt(&entity_data, position).await {
+            return false;
+        }
+        world
+            .play_sound(
+                Sound::BlockBeehiveExit,
+                SoundCategory::Blocks,
+                &self.position.to_centered_f64(),
+            )
+            .await;
+        true
+    }
+}
+
+#[async_trait]
+impl BlockEntity for BeehiveBlockEntity {
+    fn resource_location(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let bees = nbt
+            .get_list(BEES)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|bee| match bee {
+                NbtTag::Compound(bee) => Occupant::from_nbt(bee),
+                _ => None,
+            })
+            .take(Self::MAX_OCCUPANTS)
+            .collect();
+        let flower_pos = nbt.get_int_array(FLOWER_POS).and_then(|pos| match pos {
+            [x, y, z] => Some(BlockPos::new(*x, *y, *z)),
+            _ => None,
+        });
+
+        Self {
+            position,
+            bees: Mutex::new(bees),
+            flower_pos: AtomicCell::new(flower_pos),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let bees = self.bees.lock().await;
+        nbt.put_list(
+            BEES,
+            bees.iter()
+                .map(|bee| NbtTag::Compound(bee.to_nbt()))
+                .collect(),
+        );
+        if let Some(flower) = self.flower_pos.load() {
+            let flower = flower.0;
+            nbt.put(
+                FLOWER_POS,
+                NbtTag::IntArray(vec![flower.x, flower.y, flower.z]),
+            );
+        }
+    }
+
+    async fn tick(&self, world: Arc<dyn SimpleWorld>) {
+        let mut bees = self.bees.lock().await;
+        if bees.is_empty() {
+            return;
+        }
+
+        let mut kept = Vec::with_capacity(bees.len());
+        for mut bee in bees.drain(..) {
+            bee.ticks_in_hive += 1;
+            if bee.ticks_in_hive > bee.min_ticks_in_hive {
+                let status = if bee.has_nectar() {
+                    BeeReleaseStatus::HoneyDelivered
+                } else {
+                    BeeReleaseStatus::BeeReleased
+                };
+                if self.release_occupant(&world, &bee, status).await {
+                    continue;
+                }
+            }
+            kept.push(bee);
+        }
+        *bees = kept;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    async fn on_block_replaced(self: Arc<Self>, world: Arc<dyn SimpleWorld>, _position: BlockPos) {
+        // Bees don't survive in a hive that is gone
+        self.release_all(&world, BeeReleaseStatus::Emergency).await;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::BeehiveBlockEntity;
+    use crate::block::entities::BlockEntity;
+
+    #[tokio::test]
+    async fn bees_are_saved_with_the_hive() {
+        let hive = BeehiveBlockEntity::new(BlockPos::new(1, 2, 3));
+        for i in 0..BeehiveBlockEntity::MAX_OCCUPANTS {
+            let mut bee = NbtCompound::new();
+            bee.put_string("id", "minecraft:bee".to_string());
+            bee.put_bool("HasNectar", i == 0);
+            bee.put_int("UUID", 7);
+            assert!(hive.add_bee(bee, Some(BlockPos::new(4, 5, 6))).await);
+        }
+        assert!(hive.is_full().await);
+        assert!(!hive.add_bee(NbtCompound::new(), None).await);
+
+        let mut nbt = NbtCompound::new();
+        hive.write_nbt(&mut nbt).await;
+        let loaded = BeehiveBlockEntity::from_nbt(&nbt, hive.position);
+        let bees = loaded.bees.lock().await;
+        assert_eq!(bees.len(), BeehiveBlockEntity::MAX_OCCUPANTS);
+        // Bees with nectar stay longer to make honey
+        assert!(bees[0].min_ticks_in_hive > bees[1].min_ticks_in_hive);
+        assert!(bees[0].entity_data.get_int("UUID").is_none());
+        assert_eq!(loaded.flower_pos.load(), Some(BlockPos::new(4, 5, 6)));
+    }
+}
@@ -0,0 +1,207 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    BlockDirection,
+    item::Item,
+    potion_brewing::is_brewing_ingredient,
+    tag::{self, Taggable},
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_util::math::position::BlockPos;
+use std::any::Any;
+use std::array::from_fn;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU8, AtomicU16, Ordering},
+};
+
+use crate::{
+    block::entities::BlockEntity,
+    inventory::{Clearable, Inventory, InventoryListeners, SidedInventory, new_slot, split_stack},
+    item::ItemStack,
+};
+
+/// The slot holding the item the potions are brewed with.
+pub const INGREDIENT_SLOT: usize = 3;
+/// The slot holding the blaze powder that fuels brewing.
+pub const FUEL_SLOT: usize = 4;
+
+#[derive(Debug)]
+pub struct BrewingStandBlockEntity {
+    pub position: BlockPos,
+    /// Three bottles, the ingredient and the fuel
+    pub items: [Arc<OrderedMutex<ItemStack>>; 5],
+    pub brew_time: AtomicU16,
+    /// Brewing operations left before the next blaze powder is used up
+    pub fuel: AtomicU8,
+    pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
+}
+
+#[async_trait]
+impl BlockEntity for BrewingStandBlockEntity {
+    fn resource_location(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let brewing_stand = Self {
+            position,
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
+            brew_time: AtomicU16::new(nbt.get_short("BrewTime").unwrap_or(0) as u16),
+            fuel: AtomicU8::new(nbt.get_byte("Fuel").unwrap_or(0) as u8),
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        };
+        brewing_stand.read_data(nbt, &brewing_stand.items);
+
+        brewing_stand
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_short("BrewTime", self.brew_time.load(Ordering::Relaxed) as i16);
+        nbt.put_byte("Fuel", self.fuel.load(Ordering::Relaxed) as i8);
+        self.write_data(nbt, &self.items, true).await;
+    }
+
+    fn get_inventory(self: Arc<Self>) -> Option<Arc<dyn Inventory>> {
+        Some(self)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl BrewingStandBlockEntity {
+    pub const ID: &'static str = "minecraft:brewing_stand";
+
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
+            brew_time: AtomicU16::new(0),
+            fuel: AtomicU8::new(0),
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Inventory for BrewingStandBlockEntity {
+    fn size(&self) -> usize {
+        self.items.len()
+    }
+
+    async fn is_empty(&self) -> bool {
+        for slot in self.items.iter() {
+            if !slot.lock().await.is_empty() {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
+        self.items[slot].clone()
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        let mut removed = ItemStack::EMPTY.clone();
+        let mut guard = self.items[slot].lock().await;
+        std::mem::swap(&mut removed, &mut *guard);
+        removed
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        split_stack(&self.items, slot, amount).await
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        *self.items[slot].lock().await = stack;
+    }
+
+    fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
+    }
+
+    fn is_valid_slot_for(&self, slot: usize, stack: &ItemStack) -> bool {
+        match slot {
+            INGREDIENT_SLOT => is_brewing_ingredient(stack.item),
+            FUEL_SLOT => stack
+                .item
+                .is_tagged_with_by_tag(&tag::Item::MINECRAFT_BREWING_FUEL),
+            _ => [
+                Item::POTION.id,
+                Item::SPLASH_POTION.id,
+                Item::LINGERING_POTION.id,
+                Item::GLASS_BOTTLE.id,
+            ]
+            .contains(&stack.item.id),
+        }
+    }
+
+    fn as_sided(&self) -> Option<&dyn SidedInventory> {
+        Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[async_trait]
+impl SidedInventory for BrewingStandBlockEntity {
+    fn get_available_slots(&self, side: BlockDirection) -> &'static [usize] {
+        match side {
+            BlockDirection::Up => &[INGREDIENT_SLOT],
+            BlockDirection::Down => &[0, 1, 2, INGREDIENT_SLOT],
+            _ => &[0, 1, 2, FUEL_SLOT],
+        }
+    }
+
+    async fn can_insert(
+        &self,
+        slot: usize,
+        stack: &ItemStack,
+        _side: Option<BlockDirection>,
+    ) -> bool {
+        if !self.is_valid_slot_for(slot, stack) {
+            return false;
+        }
+        // Each bottle slot holds a single bottle, even of glass bottles which stack
+        slot >= INGREDIENT_SLOT || self.items[slot].lock().await.is_empty()
+    }
+
+    async fn can_extract(&self, slot: usize, stack: &ItemStack, _side: BlockDirection) -> bool {
+        // The ingredient only comes out once a brew has turned it into an empty bottle
+        slot != INGREDIENT_SLOT || stack.item.id == Item::GLASS_BOTTLE.id
+    }
+}
+
+#[async_trait]
+impl Clearable for BrewingStandBlockEntity {
+    async fn clear(&self) {
+        for slot in self.items.iter() {
+            *slot.lock().await = ItemStack::EMPTY.clone();
+        }
+    }
+}
@@ -13,15 +13,15 @@ use pumpkin_data::{
     block_properties::{BlockProperties, ChestLikeProperties, ChestType},
     sound::{Sound, SoundCategory},
 };
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::{
     math::{position::BlockPos, vector3::Vector3},
     random::{RandomImpl, get_seed, xoroshiro128::Xoroshiro},
 };
-use tokio::sync::Mutex;
 
 use crate::{
     block::viewer::{ViewerCountListener, ViewerCountTracker},
-    inventory::{Clearable, Inventory, split_stack},
+    inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack},
     item::ItemStack,
     world::SimpleWorld,
 };
@@ -31,8 +31,9 @@ use super::BlockEntity;
 #[derive(Debug)]
 pub struct ChestBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 27],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 27],
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 
     // Viewer
     pub viewers: ViewerCountTracker,
@@ -54,8 +55,9 @@ impl BlockEntity for ChestBlockEntity {
     {
         let chest = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         };
 
@@ -119,8 +121,9 @@ impl ChestBlockEntity {
     pub fn new(position: BlockPos) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         }
     }
@@ -175,7 +178,7 @@ impl Inventory for ChestBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -204,6 +207,11 @@ impl Inventory for ChestBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
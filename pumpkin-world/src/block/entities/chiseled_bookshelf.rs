@@ -3,6 +3,7 @@ use log::warn;
 use pumpkin_data::Block;
 use pumpkin_data::block_properties::{BlockProperties, ChiseledBookshelfLikeProperties};
 use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use std::any::Any;
 use std::{
@@ -12,11 +13,10 @@ use std::{
         atomic::{AtomicBool, AtomicI8, Ordering},
     },
 };
-use tokio::sync::Mutex;
 
 use crate::{
     block::entities::BlockEntity,
-    inventory::{Clearable, Inventory, split_stack},
+    inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack},
     item::ItemStack,
     world::{BlockFlags, SimpleWorld},
 };
@@ -24,9 +24,10 @@ use crate::{
 #[derive(Debug)]
 pub struct ChiseledBookshelfBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 6],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 6],
     pub last_interacted_slot: AtomicI8,
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 }
 
 const LAST_INTERACTED_SLOT: &str = "last_interacted_slot";
@@ -47,11 +48,12 @@ impl BlockEntity for ChiseledBookshelfBlockEntity {
     {
         let chiseled_bookshelf = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             last_interacted_slot: AtomicI8::new(
                 nbt.get_int(LAST_INTERACTED_SLOT).unwrap_or(-1) as i8
             ),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
         };
 
         chiseled_bookshelf.read_data(nbt, &chiseled_bookshelf.items);
@@ -86,9 +88,10 @@ impl ChiseledBookshelfBlockEntity {
     pub fn new(position: BlockPos) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             last_interacted_slot: AtomicI8::new(-1),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
         }
     }
 
@@ -130,6 +133,11 @@ impl Inventory for ChiseledBookshelfBlockEntity {
         self.items.len()
     }
 
+    fn get_max_count_per_stack(&self) -> u8 {
+        // Every slot holds a single book
+        1
+    }
+
     async fn is_empty(&self) -> bool {
         for slot in self.items.iter() {
             if !slot.lock().await.is_empty() {
@@ -140,7 +148,7 @@ impl Inventory for ChiseledBookshelfBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -161,6 +169,11 @@ impl Inventory for ChiseledBookshelfBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,24 +1,81 @@
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Weak};
 
 use async_trait::async_trait;
+use pumpkin_data::Block;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::math::position::BlockPos;
 
 use super::BlockEntity;
+use crate::inventory::{Inventory, InventoryListener};
+use crate::world::SimpleWorld;
 
 pub struct ComparatorBlockEntity {
     pub position: BlockPos,
     pub output_signal: AtomicU8,
+    /// The inventory the comparator reads and the listener it is subscribed with
+    source: parking_lot::Mutex<Option<(Weak<dyn Inventory>, Arc<dyn InventoryListener>)>>,
+    /// Whether the comparator looked for its source since it was loaded
+    watching: AtomicBool,
+}
+
+/// Updates a comparator when the inventory it reads changes.
+struct SourceListener {
+    world: Weak<dyn SimpleWorld>,
+    position: BlockPos,
+}
+
+impl InventoryListener for SourceListener {
+    fn on_inventory_changed(&self, _inventory: &dyn Inventory) {
+        if let Some(world) = self.world.upgrade() {
+            let position = self.position;
+            tokio::spawn(async move {
+                world.update_neighbor(&position, &Block::COMPARATOR).await;
+            });
+        }
+    }
 }
 
 impl ComparatorBlockEntity {
     pub const ID: &'static str = "minecraft:comparator";
     pub fn new(position: BlockPos) -> Self {
+        Self::with_output_signal(position, 0)
+    }
+
+    fn with_output_signal(position: BlockPos, output_signal: u8) -> Self {
         Self {
             position,
-            output_signal: AtomicU8::new(0),
+            output_signal: AtomicU8::new(output_signal),
+            source: parking_lot::Mutex::new(None),
+            watching: AtomicBool::new(false),
         }
     }
+
+    /// Subscribes to changes of `inventory`, the inventory the comparator reads its signal from,
+    /// so the comparator gets updated when its contents change. Replaces the subscription to the
+    /// previous source.
+    pub fn watch(&self, world: &Arc<dyn SimpleWorld>, inventory: Option<&Arc<dyn Inventory>>) {
+        self.watching.store(true, Ordering::Relaxed);
+        let mut source = self.source.lock();
+        let Some(inventory) = inventory else {
+            *source = None;
+            return;
+        };
+        if source
+            .as_ref()
+            .is_some_and(|(current, _)| Weak::ptr_eq(current, &Arc::downgrade(inventory)))
+        {
+            return;
+        }
+
+        let listener: Arc<dyn InventoryListener> = Arc::new(SourceListener {
+            world: Arc::downgrade(world),
+            position: self.position,
+        });
+        *source = inventory
+            .add_listener(Arc::downgrade(&listener))
+            .then(|| (Arc::downgrade(inventory), listener));
+    }
 }
 
 const OUTPUT_SIGNAL: &str = "OutputSignal";
@@ -38,9 +95,15 @@ impl BlockEntity for ComparatorBlockEntity {
         Self: Sized,
     {
         let output_signal = nbt.get_int(OUTPUT_SIGNAL).unwrap_or(0) as u8;
-        Self {
-            position,
-            output_signal: AtomicU8::new(output_signal),
+        Self::with_output_signal(position, output_signal)
+    }
+
+    async fn tick(&self, world: Arc<dyn SimpleWorld>) {
+        // A loaded comparator finds the inventory to watch through a neighbor update
+        if !self.watching.swap(true, Ordering::Relaxed) {
+            world
+                .update_neighbor(&self.position, &Block::COMPARATOR)
+                .await;
         }
     }
 
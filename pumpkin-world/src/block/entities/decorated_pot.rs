@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use pumpkin_data::data_component_impl::{DataComponentImpl, PotDecorationsImpl};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_util::math::position::BlockPos;
+use std::any::Any;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    block::entities::BlockEntity,
+    inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack},
+    item::ItemStack,
+};
+
+#[derive(Debug)]
+pub struct DecoratedPotBlockEntity {
+    pub position: BlockPos,
+    pub sherds: PotDecorationsImpl,
+    /// A decorated pot holds a single stack of one item
+    pub item: [Arc<OrderedMutex<ItemStack>>; 1],
+    pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
+}
+
+const SHERDS: &str = "sherds";
+const ITEM: &str = "item";
+
+#[async_trait]
+impl BlockEntity for DecoratedPotBlockEntity {
+    fn resource_location(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let sherds = nbt
+            .get(SHERDS)
+            .and_then(PotDecorationsImpl::read_data)
+            .unwrap_or_default();
+        let item = nbt
+            .get_compound(ITEM)
+            .and_then(ItemStack::read_item_stack)
+            .unwrap_or_else(|| ItemStack::EMPTY.clone());
+
+        Self {
+            position,
+            sherds,
+            item: [new_slot(item)],
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        }
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if !self.sherds.is_empty() {
+            nbt.put(SHERDS, self.sherds.write_data());
+        }
+        let item = self.item[0].lock().await;
+        if !item.is_empty() {
+            let mut item_nbt = NbtCompound::new();
+            item.write_item_stack(&mut item_nbt);
+            nbt.put_component(ITEM, item_nbt);
+        }
+    }
+
+    fn chunk_data_nbt(&self) -> Option<NbtCompound> {
+        // The client only needs the sherds to render the pot
+        let mut nbt = NbtCompound::new();
+        nbt.put(SHERDS, self.sherds.write_data());
+        Some(nbt)
+    }
+
+    fn get_inventory(self: Arc<Self>) -> Option<Arc<dyn Inventory>> {
+        Some(self)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn dynamic_drops(&self) -> Vec<ItemStack> {
+        self.sherds
+            .sherds
+            .iter()
+            .map(|sherd| ItemStack::new(1, *sherd))
+            .collect()
+    }
+
+    fn components(&self) -> Vec<Box<dyn DataComponentImpl>> {
+        if self.sherds.is_empty() {
+            Vec::new()
+        } else {
+            vec![self.sherds.clone().to_dyn()]
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl DecoratedPotBlockEntity {
+    pub const ID: &'static str = "minecraft:decorated_pot";
+
+    pub fn new(position: BlockPos, sherds: PotDecorationsImpl) -> Self {
+        Self {
+            position,
+            sherds,
+            item: [new_slot(ItemStack::EMPTY.clone())],
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        }
+    }
+
+    /// Whether `stack` can be put into the pot, either because it is empty or because it already
+    /// holds the same item and has room left.
+    pub async fn can_insert(&self, stack: &ItemStack) -> bool {
+        let item = self.item[0].lock().await;
+        item.is_empty()
+            || (item.are_items_and_components_equal(stack)
+                && item.item_count < item.get_max_stack_size())
+    }
+
+    /// The comparator signal, which grows with how full the pot is.
+    pub async fn get_comparator_output(&self) -> u8 {
+        let item = self.item[0].lock().await;
+        if item.is_empty() {
+            return 0;
+        }
+        let fullness = f32::from(item.item_count) / f32::from(item.get_max_stack_size());
+        (fullness * 14.0).floor() as u8 + 1
+    }
+}
+
+#[async_trait]
+impl Inventory for DecoratedPotBlockEntity {
+    fn size(&self) -> usize {
+        self.item.len()
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.item[0].lock().await.is_empty()
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
+        self.item[slot].clone()
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        let mut removed = ItemStack::EMPTY.clone();
+        let mut guard = self.item[slot].lock().await;
+        std::mem::swap(&mut removed, &mut *guard);
+        removed
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        split_stack(&self.item, slot, amount).await
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        *self.item[slot].lock().await = stack;
+    }
+
+    fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[async_trait]
+impl Clearable for DecoratedPotBlockEntity {
+    async fn clear(&self) {
+        *self.item[0].lock().await = ItemStack::EMPTY.clone();
+    }
+}
@@ -1,20 +1,21 @@
 use crate::block::entities::BlockEntity;
-use crate::inventory::{Clearable, Inventory, split_stack};
+use crate::inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack};
 use crate::item::ItemStack;
 use async_trait::async_trait;
+use pumpkin_util::lock_order::{OrderedMutex, OrderedMutexGuard};
 use pumpkin_util::math::position::BlockPos;
 use rand::{Rng, rng};
 use std::any::Any;
 use std::array::from_fn;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::sync::{Mutex, MutexGuard};
 
 #[derive(Debug)]
 pub struct DropperBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 9],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 9],
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 }
 
 #[async_trait]
@@ -31,8 +32,9 @@ impl BlockEntity for DropperBlockEntity {
     {
         let dropper = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
         };
 
         dropper.read_data(nbt, &dropper.items);
@@ -66,11 +68,12 @@ impl DropperBlockEntity {
     pub fn new(position: BlockPos) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
         }
     }
-    pub async fn get_random_slot(&self) -> Option<MutexGuard<'_, ItemStack>> {
+    pub async fn get_random_slot(&self) -> Option<OrderedMutexGuard<'_, ItemStack>> {
         // this.unpackLootTable(null);
         let mut ret = None;
         let mut j = 1;
@@ -103,7 +106,7 @@ impl Inventory for DropperBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -124,6 +127,11 @@ impl Inventory for DropperBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -0,0 +1,143 @@
+use std::any::Any;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicI32, AtomicI64, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+
+use crate::{block::entities::BlockEntity, world::SimpleWorld};
+
+pub struct EndGatewayBlockEntity {
+    pub position: BlockPos,
+    /// Ticks since the gateway was created, it shows its beam for a while after that
+    pub age: AtomicI64,
+    /// Ticks until the gateway can teleport again
+    pub teleport_cooldown: AtomicI32,
+    /// Where the gateway sends entities, searched for the first time it is used
+    pub exit_portal: AtomicCell<Option<BlockPos>>,
+    /// Whether entities arrive exactly at the exit instead of a safe spot near it
+    pub exact_teleport: AtomicBool,
+    pub dirty: AtomicBool,
+}
+
+const AGE: &str = "Age";
+const EXIT_PORTAL: &str = "exit_portal";
+const EXACT_TELEPORT: &str = "ExactTeleport";
+
+impl EndGatewayBlockEntity {
+    pub const ID: &'static str = "minecraft:end_gateway";
+    /// The block event type that shows the beam on the client.
+    pub const BEAM_EVENT_TYPE: u8 = 1;
+    /// Ticks after its creation in which the gateway shows its beam and does not teleport.
+    const SPAWN_BEAM_TICKS: i64 = 200;
+    /// Ticks the gateway shows its beam after teleporting and does not teleport again.
+    const COOLDOWN_TICKS: i32 = 40;
+    /// The gateway flashes its beam this often on its own.
+    const IDLE_BEAM_INTERVAL: i64 = 2400;
+
+    pub fn new(position: BlockPos, exit_portal: Option<BlockPos>, exact_teleport: bool) -> Self {
+        Self {
+            position,
+            age: AtomicI64::new(0),
+            teleport_cooldown: AtomicI32::new(0),
+            exit_portal: AtomicCell::new(exit_portal),
+            exact_teleport: AtomicBool::new(exact_teleport),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the gateway was generated just now and is still showing its spawn beam.
+    pub fn is_recently_generated(&self) -> bool {
+        self.age.load(Ordering::Relaxed) < Self::SPAWN_BEAM_TICKS
+    }
+
+    /// Whether the gateway teleported recently and has to cool down first.
+    pub fn is_cooling_down(&self) -> bool {
+        self.teleport_cooldown.load(Ordering::Relaxed) > 0
+    }
+
+    /// Shows the beam and blocks teleports for a short while.
+    pub async fn start_teleport_cooldown(&self, world: &dyn SimpleWorld) {
+        self.teleport_cooldown
+            .store(Self::COOLDOWN_TICKS, Ordering::Relaxed);
+        world
+            .add_synced_block_event(self.position, Self::BEAM_EVENT_TYPE, 0)
+            .await;
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    pub fn set_exit_portal(&self, exit_portal: BlockPos, exact_teleport: bool) {
+        self.exit_portal.store(Some(exit_portal));
+        self.exact_teleport.store(exact_teleport, Ordering::Relaxed);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl BlockEntity for EndGatewayBlockEntity {
+    fn resource_location(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let exit_portal = nbt.get_int_array(EXIT_PORTAL).and_then(|pos| match pos {
+            [x, y, z] => Some(BlockPos::new(*x, *y, *z)),
+            _ => None,
+        });
+        let gateway = Self::new(
+            position,
+            exit_portal,
+            nbt.get_bool(EXACT_TELEPORT).unwrap_or(false),
+        );
+        gateway
+            .age
+            .store(nbt.get_long(AGE).unwrap_or(0), Ordering::Relaxed);
+        gateway
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_long(AGE, self.age.load(Ordering::Relaxed));
+        if let Some(exit_portal) = self.exit_portal.load() {
+            let pos = exit_portal.0;
+            nbt.put(EXIT_PORTAL, NbtTag::IntArray(vec![pos.x, pos.y, pos.z]));
+        }
+        if self.exact_teleport.load(Ordering::Relaxed) {
+            nbt.put_bool(EXACT_TELEPORT, true);
+        }
+    }
+
+    fn chunk_data_nbt(&self) -> Option<NbtCompound> {
+        // The client needs the age to render the beam of new gateways
+        let mut nbt = NbtCompound::new();
+        nbt.put_long(AGE, self.age.load(Ordering::Relaxed));
+        Some(nbt)
+    }
+
+    async fn tick(&self, world: Arc<dyn SimpleWorld>) {
+        let age = self.age.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.is_cooling_down() {
+            self.teleport_cooldown.fetch_sub(1, Ordering::Relaxed);
+        } else if age % Self::IDLE_BEAM_INTERVAL == 0 {
+            self.start_teleport_cooldown(world.as_ref()).await;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     array::from_fn,
+    collections::HashMap,
     sync::{
         Arc,
         atomic::{AtomicBool, AtomicU16, Ordering},
@@ -8,34 +9,153 @@ use std::{
 
 use async_trait::async_trait;
 use pumpkin_data::{
+    BlockDirection,
     block_properties::{BlockProperties, FurnaceLikeProperties},
     fuels::get_item_burn_ticks,
     item::Item,
     recipe_remainder::get_recipe_remainder_id,
-    recipes::{CookingRecipe, CookingRecipeType, RECIPES_COOKING},
+    recipes::{CookingRecipe, CookingRecipeType, RECIPES_COOKING, RecipeCategoryTypes},
+    screen::WindowType,
 };
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
+use rand::{Rng, rng};
 use tokio::sync::Mutex;
 
 use crate::{
-    inventory::{Clearable, Inventory, split_stack},
+    inventory::{Clearable, Inventory, InventoryListeners, SidedInventory, new_slot, split_stack},
     item::ItemStack,
     world::{BlockFlags, SimpleWorld},
 };
 
 use super::{BlockEntity, PropertyDelegate};
 
+/// The blocks sharing the furnace block entity. They differ in the recipes they cook and in how
+/// fast they burn their fuel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FurnaceKind {
+    Furnace,
+    /// Only cooks food
+    Smoker,
+    /// Only smelts ores, raw metals and metal tools and armor
+    BlastFurnace,
+}
+
+impl FurnaceKind {
+    pub const ALL: [Self; 3] = [Self::Furnace, Self::Smoker, Self::BlastFurnace];
+
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Furnace => "minecraft:furnace",
+            Self::Smoker => "minecraft:smoker",
+            Self::BlastFurnace => "minecraft:blast_furnace",
+        }
+    }
+
+    #[must_use]
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+
+    #[must_use]
+    pub const fn window_type(self) -> WindowType {
+        match self {
+            Self::Furnace => WindowType::Furnace,
+            Self::Smoker => WindowType::Smoker,
+            Self::BlastFurnace => WindowType::BlastFurnace,
+        }
+    }
+
+    /// The translation key of the title of the screen.
+    #[must_use]
+    pub const fn translation_key(self) -> &'static str {
+        match self {
+            Self::Furnace => "container.furnace",
+            Self::Smoker => "container.smoker",
+            Self::BlastFurnace => "container.blast_furnace",
+        }
+    }
+
+    /// The recipe this kind of furnace cooks `item` with, if any.
+    #[must_use]
+    pub fn get_cooking_recipe(self, item: &Item) -> Option<&'static CookingRecipe> {
+        RECIPES_COOKING
+            .iter()
+            .find_map(|recipe| match (self, recipe) {
+                (Self::Furnace, CookingRecipeType::Smelting(recipe))
+                | (Self::Smoker, CookingRecipeType::Smoking(recipe))
+                | (Self::BlastFurnace, CookingRecipeType::Blasting(recipe))
+                    if recipe.ingredient.match_item(item) =>
+                {
+                    Some(recipe)
+                }
+                _ => None,
+            })
+    }
+
+    /// How long `item` keeps this kind of furnace lit. Smokers and blast furnaces cook twice as
+    /// fast, so they burn through their fuel twice as fast too.
+    #[must_use]
+    pub fn fuel_ticks(self, item: &Item) -> u16 {
+        let ticks = get_item_burn_ticks(item.id).unwrap_or(0);
+        match self {
+            Self::Furnace => ticks,
+            Self::Smoker | Self::BlastFurnace => ticks / 2,
+        }
+    }
+
+    /// The recipe book tab of the client a cooking recipe of the given category shows up in.
+    #[must_use]
+    pub const fn recipe_book_category(self, category: &RecipeCategoryTypes) -> &'static str {
+        match (self, category) {
+            (Self::Furnace, RecipeCategoryTypes::Food) => "minecraft:furnace_food",
+            (Self::Furnace, RecipeCategoryTypes::Blocks) => "minecraft:furnace_blocks",
+            (Self::Furnace, _) => "minecraft:furnace_misc",
+            (Self::Smoker, _) => "minecraft:smoker_food",
+            (Self::BlastFurnace, RecipeCategoryTypes::Blocks) => "minecraft:blast_furnace_blocks",
+            (Self::BlastFurnace, _) => "minecraft:blast_furnace_misc",
+        }
+    }
+
+    /// The recipe of this kind of furnace stored under `id` in `RecipesUsed`. Recipe ids aren't
+    /// part of the generated data, so recipes are looked up by their result, which is what
+    /// vanilla ids like `minecraft:iron_ingot_from_blasting_raw_iron` start with.
+    fn get_recipe_by_id(self, id: &str) -> Option<&'static CookingRecipe> {
+        let result = id.split_once("_from_").map_or(id, |(result, _)| result);
+        RECIPES_COOKING
+            .iter()
+            .find_map(|recipe| match (self, recipe) {
+                (Self::Furnace, CookingRecipeType::Smelting(recipe))
+                | (Self::Smoker, CookingRecipeType::Smoking(recipe))
+                | (Self::BlastFurnace, CookingRecipeType::Blasting(recipe))
+                    if recipe.result.id == result =>
+                {
+                    Some(recipe)
+                }
+                _ => None,
+            })
+    }
+}
+
+const RECIPES_USED: &str = "RecipesUsed";
+
 #[derive(Debug)]
 pub struct FurnaceBlockEntity {
     pub position: BlockPos,
+    pub kind: FurnaceKind,
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 
     pub cooking_time_spent: AtomicU16,
     pub cooking_total_time: AtomicU16,
     pub lit_time_remaining: AtomicU16,
     pub lit_total_time: AtomicU16,
 
-    pub items: [Arc<Mutex<ItemStack>>; 3],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 3],
+    /// How often each recipe was cooked since the experience was last paid out.
+    pub recipes_used: Mutex<Vec<(&'static CookingRecipe, u32)>>,
 }
 
 impl FurnaceBlockEntity {
@@ -44,26 +164,6 @@ impl FurnaceBlockEntity {
         self.lit_time_remaining.load(Ordering::Relaxed) > 0
     }
 
-    pub fn get_furnace_cooking_recipe(item: &Item) -> Option<&CookingRecipe> {
-        if let Some(recipe_type) = RECIPES_COOKING.iter().find(|recipe| match recipe {
-            CookingRecipeType::Smelting(smelting_recipe) => {
-                smelting_recipe.ingredient.match_item(item)
-            }
-            _ => false,
-        }) {
-            match recipe_type {
-                CookingRecipeType::Smelting(cooking_recipe) => {
-                    return Some(cooking_recipe);
-                }
-                _ => {
-                    return None;
-                }
-            }
-        }
-
-        None
-    }
-
     async fn can_accept_recipe_output(
         &self,
         recipe: Option<&CookingRecipe>,
@@ -95,7 +195,7 @@ impl FurnaceBlockEntity {
         false
     }
 
-    async fn craft_recipe(&self, recipe: Option<&CookingRecipe>) -> bool {
+    async fn craft_recipe(&self, recipe: Option<&'static CookingRecipe>) -> bool {
         let can_accepet_output = self
             .can_accept_recipe_output(recipe, self.get_max_count_per_stack())
             .await;
@@ -116,6 +216,7 @@ impl FurnaceBlockEntity {
                 } else if side_items.are_items_and_components_equal(&output_item_stack) {
                     side_items.increment(1);
                 }
+                self.set_recipe_used(recipe).await;
             }
 
             let bottom_items = self.items[1].lock().await;
@@ -136,6 +237,64 @@ impl FurnaceBlockEntity {
         false
     }
 
+    async fn set_recipe_used(&self, recipe: &'static CookingRecipe) {
+        let mut recipes_used = self.recipes_used.lock().await;
+        if let Some((_, count)) = recipes_used
+            .iter_mut()
+            .find(|(used, _)| std::ptr::eq(*used, recipe))
+        {
+            *count += 1;
+        } else {
+            recipes_used.push((recipe, 1));
+        }
+    }
+
+    /// Takes the experience collected by cooking since it was last taken, for when a player takes
+    /// the output or the furnace is broken. Fractions of a point are rounded up at random.
+    pub async fn take_experience(&self) -> u32 {
+        let recipes_used = std::mem::take(&mut *self.recipes_used.lock().await);
+        if recipes_used.is_empty() {
+            return 0;
+        }
+        self.mark_dirty();
+
+        let mut rng = rng();
+        recipes_used
+            .into_iter()
+            .map(|(recipe, count)| {
+                let experience = count as f32 * recipe.experience;
+                let fraction = experience.fract();
+                experience as u32 + u32::from(fraction > 0.0 && rng.random::<f32>() < fraction)
+            })
+            .sum()
+    }
+
+    fn read_recipes_used(
+        kind: FurnaceKind,
+        nbt: &NbtCompound,
+    ) -> Vec<(&'static CookingRecipe, u32)> {
+        let mut recipes_used: Vec<(&'static CookingRecipe, u32)> = Vec::new();
+        let Some(compound) = nbt.get_compound(RECIPES_USED) else {
+            return recipes_used;
+        };
+        for (id, count) in &compound.child_tags {
+            let (Some(recipe), Some(count)) = (kind.get_recipe_by_id(id), count.extract_int())
+            else {
+                continue;
+            };
+            let count = count.max(0) as u32;
+            if let Some((_, total)) = recipes_used
+                .iter_mut()
+                .find(|(used, _)| std::ptr::eq(*used, recipe))
+            {
+                *total += count;
+            } else {
+                recipes_used.push((recipe, count));
+            }
+        }
+        recipes_used
+    }
+
     pub async fn get_cook_progress(&self) -> f32 {
         let current = self.cooking_time_spent.load(Ordering::Relaxed) as i32;
         let total = self.cooking_total_time.load(Ordering::Relaxed) as i32;
@@ -168,7 +327,7 @@ impl BlockEntity for FurnaceBlockEntity {
         let top_items = self.items[0].lock().await;
         let is_top_items_empty = top_items.is_empty();
 
-        let furnace_recipe = Self::get_furnace_cooking_recipe(top_items.item);
+        let furnace_recipe = self.kind.get_cooking_recipe(top_items.item);
         drop(top_items);
 
         let can_accepet_output = self
@@ -180,7 +339,7 @@ impl BlockEntity for FurnaceBlockEntity {
             if !self.is_burning() && can_accepet_output {
                 let mut bottom_items = self.items[1].lock().await;
 
-                let fuel_ticks = get_item_burn_ticks(bottom_items.item.id).unwrap_or(0);
+                let fuel_ticks = self.kind.fuel_ticks(bottom_items.item);
                 self.lit_time_remaining.store(fuel_ticks, Ordering::Relaxed);
                 self.lit_total_time.store(fuel_ticks, Ordering::Relaxed);
 
@@ -260,12 +419,22 @@ impl BlockEntity for FurnaceBlockEntity {
         }
 
         if is_dirty {
-            self.is_dirty();
+            self.mark_dirty();
+        }
+    }
+
+    async fn catch_up(&self, world: Arc<dyn SimpleWorld>, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick(world.clone()).await;
+            // Nothing happens in an unlit furnace without progress until someone changes its items
+            if !self.is_burning() && self.cooking_time_spent.load(Ordering::Relaxed) == 0 {
+                break;
+            }
         }
     }
 
     fn resource_location(&self) -> &'static str {
-        Self::ID
+        self.kind.id()
     }
 
     fn get_position(&self) -> BlockPos {
@@ -293,14 +462,21 @@ impl BlockEntity for FurnaceBlockEntity {
                 .map_or(0, |lit_time_remaining| lit_time_remaining as u16),
         );
 
+        let kind = nbt
+            .get_string("id")
+            .and_then(FurnaceKind::from_id)
+            .unwrap_or(FurnaceKind::Furnace);
         let furnace = Self {
             position,
+            kind,
             dirty: AtomicBool::new(false),
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            listeners: InventoryListeners::default(),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             cooking_total_time,
             cooking_time_spent,
             lit_total_time,
             lit_time_remaining,
+            recipes_used: Mutex::new(Self::read_recipes_used(kind, nbt)),
         };
         furnace.read_data(nbt, &furnace.items);
 
@@ -325,6 +501,16 @@ impl BlockEntity for FurnaceBlockEntity {
             self.lit_time_remaining.load(Ordering::Relaxed) as i16,
         );
         self.write_data(nbt, &self.items, true).await;
+
+        let mut counts: HashMap<&str, i32> = HashMap::new();
+        for (recipe, count) in self.recipes_used.lock().await.iter() {
+            *counts.entry(recipe.result.id).or_default() += *count as i32;
+        }
+        let mut recipes_used = NbtCompound::new();
+        for (id, count) in counts {
+            recipes_used.put_int(id, count);
+        }
+        nbt.put_component(RECIPES_USED, recipes_used);
         // Safety precaution
         // self.clear().await;
     }
@@ -347,16 +533,22 @@ impl BlockEntity for FurnaceBlockEntity {
 }
 
 impl FurnaceBlockEntity {
-    pub const ID: &'static str = "minecraft:furnace";
-    pub fn new(position: BlockPos) -> Self {
+    pub const ID: &'static str = FurnaceKind::Furnace.id();
+    pub const SMOKER_ID: &'static str = FurnaceKind::Smoker.id();
+    pub const BLAST_FURNACE_ID: &'static str = FurnaceKind::BlastFurnace.id();
+
+    pub fn new(position: BlockPos, kind: FurnaceKind) -> Self {
         Self {
             position,
+            kind,
             dirty: AtomicBool::new(false),
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            listeners: InventoryListeners::default(),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             cooking_total_time: AtomicU16::new(0),
             cooking_time_spent: AtomicU16::new(0),
             lit_total_time: AtomicU16::new(0),
             lit_time_remaining: AtomicU16::new(0),
+            recipes_used: Mutex::new(Vec::new()),
         }
     }
 }
@@ -377,7 +569,7 @@ impl Inventory for FurnaceBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -403,7 +595,7 @@ impl Inventory for FurnaceBlockEntity {
         drop(furnace_stack);
 
         if slot == 0 && !is_same_item {
-            if let Some(recipe) = Self::get_furnace_cooking_recipe(stack.item) {
+            if let Some(recipe) = self.kind.get_cooking_recipe(stack.item) {
                 self.cooking_total_time
                     .store(recipe.cookingtime as u16, Ordering::Relaxed);
             } else {
@@ -416,6 +608,24 @@ impl Inventory for FurnaceBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
+    }
+
+    fn is_valid_slot_for(&self, slot: usize, stack: &ItemStack) -> bool {
+        match slot {
+            // The result slot is only ever filled by cooking
+            2 => false,
+            1 => get_item_burn_ticks(stack.item.id).is_some() || stack.item.id == Item::BUCKET.id,
+            _ => true,
+        }
+    }
+
+    fn as_sided(&self) -> Option<&dyn SidedInventory> {
+        Some(self)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -423,6 +633,40 @@ impl Inventory for FurnaceBlockEntity {
     }
 }
 
+#[async_trait]
+impl SidedInventory for FurnaceBlockEntity {
+    fn get_available_slots(&self, side: BlockDirection) -> &'static [usize] {
+        match side {
+            BlockDirection::Up => &[0],
+            BlockDirection::Down => &[2, 1],
+            _ => &[1],
+        }
+    }
+
+    async fn can_insert(
+        &self,
+        slot: usize,
+        stack: &ItemStack,
+        _side: Option<BlockDirection>,
+    ) -> bool {
+        if !self.is_valid_slot_for(slot, stack) {
+            return false;
+        }
+        // An empty bucket only goes in to catch the water of a drying sponge, so one is enough
+        slot != 1
+            || stack.item.id != Item::BUCKET.id
+            || self.items[1].lock().await.item.id != Item::BUCKET.id
+    }
+
+    async fn can_extract(&self, slot: usize, stack: &ItemStack, side: BlockDirection) -> bool {
+        // Fuel only comes out of the bottom once it has turned into a bucket
+        side != BlockDirection::Down
+            || slot != 1
+            || stack.item.id == Item::WATER_BUCKET.id
+            || stack.item.id == Item::BUCKET.id
+    }
+}
+
 #[async_trait]
 impl Clearable for FurnaceBlockEntity {
     async fn clear(&self) {
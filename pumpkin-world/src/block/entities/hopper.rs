@@ -1,26 +1,27 @@
 use crate::BlockStateId;
 use crate::block::entities::BlockEntity;
-use crate::inventory::{Clearable, Inventory, split_stack};
+use crate::inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack};
 use crate::item::ItemStack;
 use crate::world::SimpleWorld;
 use async_trait::async_trait;
 use pumpkin_data::block_properties::{BlockProperties, HopperFacing, HopperLikeProperties};
 use pumpkin_data::tag::Taggable;
-use pumpkin_data::{Block, tag};
+use pumpkin_data::{Block, BlockDirection, tag};
 use pumpkin_nbt::tag::NbtTag;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
 use std::any::Any;
 use std::array::from_fn;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64};
-use tokio::sync::Mutex;
 
 #[derive(Debug)]
 pub struct HopperBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 5],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 5],
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
     pub facing: HopperFacing,
     pub cooldown_time: AtomicI32,
     pub ticked_game_time: AtomicI64,
@@ -37,6 +38,86 @@ pub fn to_offset(facing: &HopperFacing) -> Vector3<i32> {
     .into()
 }
 
+pub fn to_direction(facing: &HopperFacing) -> BlockDirection {
+    match facing {
+        HopperFacing::Down => BlockDirection::Down,
+        HopperFacing::North => BlockDirection::North,
+        HopperFacing::South => BlockDirection::South,
+        HopperFacing::West => BlockDirection::West,
+        HopperFacing::East => BlockDirection::East,
+    }
+}
+
+/// The slots of a container automation can reach, all of them unless it has per-face rules and
+/// the item comes through a face.
+fn get_slots(container: &dyn Inventory, side: Option<BlockDirection>) -> Vec<usize> {
+    if let Some(sided) = container.as_sided()
+        && let Some(side) = side
+    {
+        sided.get_available_slots(side).to_vec()
+    } else {
+        (0..container.size()).collect()
+    }
+}
+
+async fn can_place_item(
+    container: &dyn Inventory,
+    slot: usize,
+    stack: &ItemStack,
+    side: Option<BlockDirection>,
+) -> bool {
+    if !container.is_valid_slot_for(slot, stack) {
+        return false;
+    }
+    match container.as_sided() {
+        Some(sided) => sided.can_insert(slot, stack, side).await,
+        None => true,
+    }
+}
+
+async fn can_take_item(
+    container: &dyn Inventory,
+    to: &dyn Inventory,
+    slot: usize,
+    stack: &ItemStack,
+    side: BlockDirection,
+) -> bool {
+    if !container.can_transfer_to(to, slot, stack) {
+        return false;
+    }
+    match container.as_sided() {
+        Some(sided) => sided.can_extract(slot, stack, side).await,
+        None => true,
+    }
+}
+
+/// The container at a position, offered either by the block itself or by its block entity.
+pub async fn get_container_at(
+    world: &Arc<dyn SimpleWorld>,
+    position: &BlockPos,
+) -> Option<Arc<dyn Inventory>> {
+    if let Some(container) = world.clone().get_block_container(position).await {
+        return Some(container);
+    }
+    world.get_block_entity(position).await?.get_inventory()
+}
+
+/// Whether every slot reachable through `side` holds a full stack.
+pub async fn is_full_container(container: &dyn Inventory, side: BlockDirection) -> bool {
+    for slot in get_slots(container, Some(side)) {
+        let stack = container.get_stack(slot).await;
+        let stack = stack.lock().await;
+        if stack.item_count
+            < stack
+                .get_max_stack_size()
+                .min(container.get_max_count_per_stack())
+        {
+            return false;
+        }
+    }
+    true
+}
+
 #[async_trait]
 impl BlockEntity for HopperBlockEntity {
     async fn write_nbt(&self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
@@ -58,8 +139,9 @@ impl BlockEntity for HopperBlockEntity {
     {
         let hopper = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             facing: HopperFacing::Down,
             cooldown_time: AtomicI32::from(nbt.get_int("TransferCooldown").unwrap_or(-1)),
             ticked_game_time: AtomicI64::new(0),
@@ -121,8 +203,9 @@ impl HopperBlockEntity {
     pub fn new(position: BlockPos, facing: HopperFacing) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             facing,
             cooldown_time: AtomicI32::new(-1),
             ticked_game_time: AtomicI64::new(0),
@@ -163,18 +246,20 @@ impl HopperBlockEntity {
     async fn suck_in_items(&self, world: &Arc<dyn SimpleWorld>) -> bool {
         // TODO getEntityContainer
         let pos_up = &self.position.up();
-        if let Some(entity) = world.get_block_entity(pos_up).await
-            && let Some(container) = entity.get_inventory()
-        {
-            // TODO check WorldlyContainer
-            for i in 0..container.size() {
+        if let Some(container) = get_container_at(world, pos_up).await {
+            for i in get_slots(container.as_ref(), Some(BlockDirection::Down)) {
                 let bind = container.get_stack(i).await;
                 let mut item = bind.lock().await;
-                if !item.is_empty() && container.can_transfer_to(self, i, &item) {
-                    //TODO WorldlyContainer
+                if !item.is_empty()
+                    && can_take_item(container.as_ref(), self, i, &item, BlockDirection::Down).await
+                {
                     let backup = item.clone();
                     let one_item = item.split(1);
-                    if Self::add_one_item(container.as_ref(), self, one_item).await {
+                    if Self::add_one_item(container.as_ref(), self, one_item, None).await {
+                        drop(item);
+                        if let Some(sided) = container.as_sided() {
+                            sided.on_items_moved().await;
+                        }
                         return true;
                     }
                     *item = backup;
@@ -195,31 +280,19 @@ impl HopperBlockEntity {
     async fn eject_items(&self, world: &Arc<dyn SimpleWorld>) -> bool {
         // TODO getEntityContainer
 
-        if let Some(entity) = world
-            .get_block_entity(&self.position.offset(to_offset(&self.facing)))
-            .await
-            && let Some(container) = entity.get_inventory()
+        if let Some(container) =
+            get_container_at(world, &self.position.offset(to_offset(&self.facing))).await
         {
-            // TODO check WorldlyContainer
-            let mut is_full = true;
-            for i in 0..container.size() {
-                let bind = container.get_stack(i).await;
-                let item = bind.lock().await;
-                if item.item_count < item.get_max_stack_size() {
-                    is_full = false;
-                    break;
-                }
-            }
-            if is_full {
+            let side = to_direction(&self.facing).opposite();
+            if is_full_container(container.as_ref(), side).await {
                 return false;
             }
             for i in &self.items {
                 let mut item = i.lock().await;
                 if !item.is_empty() {
-                    //TODO WorldlyContainer
                     let backup = item.clone();
                     let one_item = item.split(1);
-                    if Self::add_one_item(self, container.as_ref(), one_item).await {
+                    if Self::add_one_item(self, container.as_ref(), one_item, Some(side)).await {
                         return true;
                     }
                     *item = backup;
@@ -228,22 +301,32 @@ impl HopperBlockEntity {
         }
         false
     }
-    pub async fn add_one_item(from: &dyn Inventory, to: &dyn Inventory, item: ItemStack) -> bool {
+    /// Moves a single item into `to`, entering through `side` if it comes from a neighbouring block.
+    pub async fn add_one_item(
+        from: &dyn Inventory,
+        to: &dyn Inventory,
+        item: ItemStack,
+        side: Option<BlockDirection>,
+    ) -> bool {
         let mut success = false;
         let to_empty = to.is_empty().await;
-        for j in 0..to.size() {
-            if to.is_valid_slot_for(j, &item) {
+        for j in get_slots(to, side) {
+            if can_place_item(to, j, &item, side).await {
                 let bind = to.get_stack(j).await;
                 let mut dst = bind.lock().await;
                 if dst.is_empty() {
                     *dst = item.clone();
                     success = true;
-                } else if dst.item_count < dst.get_max_stack_size() && dst.item == item.item {
+                } else if dst.item_count
+                    < dst.get_max_stack_size().min(to.get_max_count_per_stack())
+                    && dst.item == item.item
+                {
                     // TODO check Components equal
                     dst.item_count += 1;
                     success = true;
                 }
                 if success {
+                    drop(dst);
                     if to_empty
                         && let Some(hopper) = to.as_any().downcast_ref::<HopperBlockEntity>()
                         && hopper
@@ -275,6 +358,9 @@ impl HopperBlockEntity {
                         }
                     }
                     to.mark_dirty();
+                    if let Some(sided) = to.as_sided() {
+                        sided.on_items_moved().await;
+                    }
                     return true;
                 }
             }
@@ -299,7 +385,7 @@ impl Inventory for HopperBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -320,6 +406,11 @@ impl Inventory for HopperBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -335,3 +426,63 @@ impl Clearable for HopperBlockEntity {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::{BlockDirection, block_properties::HopperFacing, item::Item};
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::HopperBlockEntity;
+    use crate::block::entities::brewing_stand::{BrewingStandBlockEntity, INGREDIENT_SLOT};
+    use crate::block::entities::jukebox::JukeboxBlockEntity;
+    use crate::inventory::Inventory;
+    use crate::item::ItemStack;
+
+    #[tokio::test]
+    async fn brewing_stand_faces() {
+        let hopper = HopperBlockEntity::new(BlockPos::new(0, 1, 0), HopperFacing::Down);
+        let brewing_stand = BrewingStandBlockEntity::new(BlockPos::new(0, 0, 0));
+        let add = |item: &'static Item, side| {
+            HopperBlockEntity::add_one_item(&hopper, &brewing_stand, ItemStack::new(1, item), side)
+        };
+
+        // From above only the ingredient slot can be filled
+        assert!(!add(&Item::POTION, Some(BlockDirection::Up)).await);
+        assert!(add(&Item::NETHER_WART, Some(BlockDirection::Up)).await);
+        assert_eq!(
+            brewing_stand
+                .get_stack(INGREDIENT_SLOT)
+                .await
+                .lock()
+                .await
+                .item
+                .id,
+            Item::NETHER_WART.id
+        );
+
+        // From the sides every bottle slot takes a single bottle
+        for _ in 0..3 {
+            assert!(add(&Item::GLASS_BOTTLE, Some(BlockDirection::North)).await);
+        }
+        assert!(!add(&Item::GLASS_BOTTLE, Some(BlockDirection::North)).await);
+        assert!(!add(&Item::NETHER_WART, Some(BlockDirection::North)).await);
+    }
+
+    #[tokio::test]
+    async fn jukebox_takes_one_record() {
+        let hopper = HopperBlockEntity::new(BlockPos::new(0, 1, 0), HopperFacing::Down);
+        let jukebox = JukeboxBlockEntity::new(BlockPos::new(0, 0, 0));
+        let add = |item: &'static Item| {
+            HopperBlockEntity::add_one_item(
+                &hopper,
+                &jukebox,
+                ItemStack::new(1, item),
+                Some(BlockDirection::Up),
+            )
+        };
+
+        assert!(!add(&Item::STONE).await);
+        assert!(add(&Item::MUSIC_DISC_CAT).await);
+        assert!(!add(&Item::MUSIC_DISC_CAT).await);
+    }
+}
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    Block,
+    block_properties::{BlockProperties, JukeboxLikeProperties},
+    data_component_impl::JukeboxPlayableImpl,
+    world::WorldEvent,
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::lock_order::OrderedMutex;
+use pumpkin_util::math::position::BlockPos;
+use std::any::Any;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    block::entities::BlockEntity,
+    inventory::{Clearable, Inventory, InventoryListeners, new_slot, split_stack},
+    item::ItemStack,
+    world::{BlockFlags, SimpleWorld},
+};
+
+#[derive(Debug)]
+pub struct JukeboxBlockEntity {
+    pub position: BlockPos,
+    /// The record being played, put in by a player or a hopper
+    pub record: [Arc<OrderedMutex<ItemStack>>; 1],
+    pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
+}
+
+const RECORD_ITEM: &str = "RecordItem";
+
+#[async_trait]
+impl BlockEntity for JukeboxBlockEntity {
+    fn resource_location(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let record = nbt
+            .get_compound(RECORD_ITEM)
+            .and_then(ItemStack::read_item_stack)
+            .unwrap_or_else(|| ItemStack::EMPTY.clone());
+
+        Self {
+            position,
+            record: [new_slot(record)],
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        }
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let record = self.record[0].lock().await;
+        if !record.is_empty() {
+            let mut record_nbt = NbtCompound::new();
+            record.write_item_stack(&mut record_nbt);
+            nbt.put_component(RECORD_ITEM, record_nbt);
+        }
+    }
+
+    async fn tick(&self, world: Arc<dyn SimpleWorld>) {
+        // The record may change without a player, so the block follows whatever is inside
+        let song = self.record[0]
+            .lock()
+            .await
+            .get_data_component::<JukeboxPlayableImpl>()
+            .map(|playable| playable.song);
+        let state_id = world.get_block_state(&self.position).await.id;
+        if JukeboxLikeProperties::from_state_id(state_id, &Block::JUKEBOX).has_record
+            == song.is_some()
+        {
+            return;
+        }
+
+        let new_state = JukeboxLikeProperties {
+            has_record: song.is_some(),
+        };
+        world
+            .clone()
+            .set_block_state(
+                &self.position,
+                new_state.to_state_id(&Block::JUKEBOX),
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        if let Some(song) = song {
+            world.play_jukebox_song(self.position, song).await;
+        } else {
+            world
+                .sync_world_event(WorldEvent::JukeboxStopsPlaying, self.position, 0)
+                .await;
+        }
+    }
+
+    fn get_inventory(self: Arc<Self>) -> Option<Arc<dyn Inventory>> {
+        Some(self)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl JukeboxBlockEntity {
+    pub const ID: &'static str = "minecraft:jukebox";
+
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            record: [new_slot(ItemStack::EMPTY.clone())],
+            dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Inventory for JukeboxBlockEntity {
+    fn size(&self) -> usize {
+        self.record.len()
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.record[0].lock().await.is_empty()
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
+        self.record[slot].clone()
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        let mut removed = ItemStack::EMPTY.clone();
+        let mut guard = self.record[slot].lock().await;
+        std::mem::swap(&mut removed, &mut *guard);
+        removed
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        split_stack(&self.record, slot, amount).await
+    }
+
+    fn get_max_count_per_stack(&self) -> u8 {
+        1
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        *self.record[slot].lock().await = stack;
+    }
+
+    fn mark_dirty(&self) {
+        self.dirty.store(true, Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
+    }
+
+    fn is_valid_slot_for(&self, _slot: usize, stack: &ItemStack) -> bool {
+        stack.get_data_component::<JukeboxPlayableImpl>().is_some()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[async_trait]
+impl Clearable for JukeboxBlockEntity {
+    async fn clear(&self) {
+        *self.record[0].lock().await = ItemStack::EMPTY.clone();
+    }
+}
@@ -114,7 +114,9 @@ impl BlockEntity for MobSpawnerBlockEntity {
                 {
                     continue;
                 }
-                world.clone().spawn_from_type(entity_type, spawn_pos).await;
+                if !world.clone().spawn_from_type(entity_type, spawn_pos).await {
+                    continue;
+                }
                 world
                     .sync_world_event(WorldEvent::SpawnerSpawnsMob, self.position, 0)
                     .await;
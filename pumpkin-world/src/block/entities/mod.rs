@@ -3,12 +3,19 @@ use std::{any::Any, sync::Arc};
 use async_trait::async_trait;
 use barrel::BarrelBlockEntity;
 use bed::BedBlockEntity;
+use beehive::BeehiveBlockEntity;
+use brewing_stand::BrewingStandBlockEntity;
 use chest::ChestBlockEntity;
 use comparator::ComparatorBlockEntity;
+use decorated_pot::DecoratedPotBlockEntity;
+use end_gateway::EndGatewayBlockEntity;
 use end_portal::EndPortalBlockEntity;
 use furnace::FurnaceBlockEntity;
+use jukebox::JukeboxBlockEntity;
 use piston::PistonBlockEntity;
-use pumpkin_data::{Block, block_properties::BLOCK_ENTITY_TYPES};
+use pumpkin_data::{
+    Block, block_properties::BLOCK_ENTITY_TYPES, data_component_impl::DataComponentImpl,
+};
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::math::position::BlockPos;
 use sign::SignBlockEntity;
@@ -18,19 +25,25 @@ use crate::block::entities::mob_spawner::MobSpawnerBlockEntity;
 use crate::block::entities::shulker_box::ShulkerBoxBlockEntity;
 use crate::{
     BlockStateId, block::entities::chiseled_bookshelf::ChiseledBookshelfBlockEntity,
-    block::entities::dropper::DropperBlockEntity, inventory::Inventory, world::SimpleWorld,
+    block::entities::dropper::DropperBlockEntity, inventory::Inventory, item::ItemStack,
+    world::SimpleWorld,
 };
 
 pub mod barrel;
 pub mod bed;
+pub mod beehive;
+pub mod brewing_stand;
 pub mod chest;
 pub mod chiseled_bookshelf;
 pub mod command_block;
 pub mod comparator;
+pub mod decorated_pot;
 pub mod dropper;
+pub mod end_gateway;
 pub mod end_portal;
 pub mod furnace;
 pub mod hopper;
+pub mod jukebox;
 pub mod mob_spawner;
 pub mod piston;
 pub mod shulker_box;
@@ -44,6 +57,9 @@ pub trait BlockEntity: Send + Sync {
     where
         Self: Sized;
     async fn tick(&self, _world: Arc<dyn SimpleWorld>) {}
+    /// Called when the chunk is loaded again after it spent `ticks` unloaded, if catching up is
+    /// enabled in the config. Block entities that work over time can simulate what they missed.
+    async fn catch_up(&self, _world: Arc<dyn SimpleWorld>, _ticks: u32) {}
     fn resource_location(&self) -> &'static str;
     fn get_position(&self) -> BlockPos;
     async fn write_internal(&self, nbt: &mut NbtCompound) {
@@ -77,6 +93,14 @@ pub trait BlockEntity: Send + Sync {
     fn is_dirty(&self) -> bool {
         false
     }
+    /// The stacks dropped by a `minecraft:dynamic` loot table entry when the block is broken.
+    fn dynamic_drops(&self) -> Vec<ItemStack> {
+        Vec::new()
+    }
+    /// The components a `minecraft:copy_components` loot function can copy onto the dropped item.
+    fn components(&self) -> Vec<Box<dyn DataComponentImpl>> {
+        Vec::new()
+    }
 
     fn as_any(&self) -> &dyn Any;
     fn to_property_delegate(self: Arc<Self>) -> Option<Arc<dyn PropertyDelegate>> {
@@ -112,10 +136,25 @@ pub fn block_entity_from_nbt(nbt: &NbtCompound) -> Option<Arc<dyn BlockEntity>>
         EndPortalBlockEntity::ID => {
             Arc::new(block_entity_from_generic::<EndPortalBlockEntity>(nbt))
         }
+        EndGatewayBlockEntity::ID => {
+            Arc::new(block_entity_from_generic::<EndGatewayBlockEntity>(nbt))
+        }
         ChiseledBookshelfBlockEntity::ID => Arc::new(block_entity_from_generic::<
             ChiseledBookshelfBlockEntity,
         >(nbt)),
-        FurnaceBlockEntity::ID => Arc::new(block_entity_from_generic::<FurnaceBlockEntity>(nbt)),
+        FurnaceBlockEntity::ID
+        | FurnaceBlockEntity::SMOKER_ID
+        | FurnaceBlockEntity::BLAST_FURNACE_ID => {
+            Arc::new(block_entity_from_generic::<FurnaceBlockEntity>(nbt))
+        }
+        DecoratedPotBlockEntity::ID => {
+            Arc::new(block_entity_from_generic::<DecoratedPotBlockEntity>(nbt))
+        }
+        JukeboxBlockEntity::ID => Arc::new(block_entity_from_generic::<JukeboxBlockEntity>(nbt)),
+        BrewingStandBlockEntity::ID => {
+            Arc::new(block_entity_from_generic::<BrewingStandBlockEntity>(nbt))
+        }
+        BeehiveBlockEntity::ID => Arc::new(block_entity_from_generic::<BeehiveBlockEntity>(nbt)),
         _ => return None,
     })
 }
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::random::xoroshiro128::Xoroshiro;
 use pumpkin_util::random::{RandomImpl, get_seed};
@@ -8,13 +9,12 @@ use std::{
     array::from_fn,
     sync::{Arc, atomic::AtomicBool},
 };
-use tokio::sync::Mutex;
 
 use crate::block::viewer::{ViewerCountListener, ViewerCountTracker};
 use crate::world::SimpleWorld;
 use crate::{
     inventory::{
-        split_stack, {Clearable, Inventory},
+        new_slot, split_stack, {Clearable, Inventory, InventoryListeners},
     },
     item::ItemStack,
 };
@@ -24,8 +24,9 @@ use super::BlockEntity;
 #[derive(Debug)]
 pub struct ShulkerBoxBlockEntity {
     pub position: BlockPos,
-    pub items: [Arc<Mutex<ItemStack>>; 27],
+    pub items: [Arc<OrderedMutex<ItemStack>>; 27],
     pub dirty: AtomicBool,
+    pub listeners: InventoryListeners,
 
     // Viewer
     pub viewers: ViewerCountTracker,
@@ -47,8 +48,9 @@ impl BlockEntity for ShulkerBoxBlockEntity {
     {
         let shulker_box = Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         };
 
@@ -120,8 +122,9 @@ impl ShulkerBoxBlockEntity {
     pub fn new(position: BlockPos) -> Self {
         Self {
             position,
-            items: from_fn(|_| Arc::new(Mutex::new(ItemStack::EMPTY.clone()))),
+            items: from_fn(|_| new_slot(ItemStack::EMPTY.clone())),
             dirty: AtomicBool::new(false),
+            listeners: InventoryListeners::default(),
             viewers: ViewerCountTracker::new(),
         }
     }
@@ -157,7 +160,7 @@ impl Inventory for ShulkerBoxBlockEntity {
         true
     }
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
         self.items[slot].clone()
     }
 
@@ -186,6 +189,11 @@ impl Inventory for ShulkerBoxBlockEntity {
 
     fn mark_dirty(&self) {
         self.dirty.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.listeners.notify(self);
+    }
+
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        Some(&self.listeners)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -10,7 +10,7 @@ use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_util::math::position::BlockPos;
 use tokio::sync::Mutex;
 
-#[derive(Clone, Default, FromPrimitive)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, FromPrimitive)]
 #[repr(i8)]
 pub enum DyeColor {
     White = 0,
@@ -121,6 +121,8 @@ pub struct Text {
     pub has_glowing_text: AtomicBool,
     color: AtomicI8,
     pub messages: Arc<std::sync::Mutex<[String; 4]>>,
+    /// What players with text filtering enabled see instead of `messages`
+    pub filtered_messages: Arc<std::sync::Mutex<[String; 4]>>,
 }
 
 impl Clone for Text {
@@ -129,6 +131,7 @@ impl Clone for Text {
             has_glowing_text: AtomicBool::new(self.has_glowing_text.load(Ordering::Relaxed)),
             color: AtomicI8::new(self.color.load(Ordering::Relaxed)),
             messages: self.messages.clone(),
+            filtered_messages: self.filtered_messages.clone(),
         }
     }
 }
@@ -139,6 +142,7 @@ impl Default for Text {
             has_glowing_text: AtomicBool::new(false),
             color: AtomicI8::new(DyeColor::default() as i8),
             messages: Default::default(),
+            filtered_messages: Default::default(),
         }
     }
 }
@@ -151,15 +155,18 @@ impl From<Text> for NbtTag {
             value.has_glowing_text.load(Ordering::Relaxed),
         );
         nbt.put_string("color", value.get_color().into());
+        let messages = value.messages.lock().unwrap().clone();
+        let filtered_messages = value.filtered_messages.lock().unwrap().clone();
+        // Vanilla only stores the filtered lines if they differ
+        if filtered_messages != messages {
+            nbt.put_list(
+                "filtered_messages",
+                filtered_messages.into_iter().map(NbtTag::String).collect(),
+            );
+        }
         nbt.put_list(
             "messages",
-            value
-                .messages
-                .lock()
-                .unwrap()
-                .iter()
-                .map(|s| NbtTag::String(s.clone()))
-                .collect(),
+            messages.into_iter().map(NbtTag::String).collect(),
         );
         NbtTag::Compound(nbt)
     }
@@ -170,22 +177,27 @@ impl From<NbtTag> for Text {
         let nbt = tag.extract_compound().unwrap();
         let has_glowing_text = nbt.get_bool("has_glowing_text").unwrap_or(false);
         let color = nbt.get_string("color").unwrap();
-        let messages: Vec<String> = nbt
-            .get_list("messages")
-            .unwrap()
-            .iter()
-            .filter_map(|tag| tag.extract_string().map(|s| s.to_string()))
-            .collect();
+        let read_lines = |name: &str| -> Option<[String; 4]> {
+            let lines: Vec<String> = nbt
+                .get_list(name)?
+                .iter()
+                .filter_map(|tag| tag.extract_string().map(|s| s.to_string()))
+                .collect();
+            Some([
+                // its important that we use unwrap_or since otherwise we may crash on older versions
+                lines.first().unwrap_or(&"".to_string()).clone(),
+                lines.get(1).unwrap_or(&"".to_string()).clone(),
+                lines.get(2).unwrap_or(&"".to_string()).clone(),
+                lines.get(3).unwrap_or(&"".to_string()).clone(),
+            ])
+        };
+        let messages = read_lines("messages").unwrap();
+        let filtered_messages = read_lines("filtered_messages").unwrap_or_else(|| messages.clone());
         Self {
             has_glowing_text: AtomicBool::new(has_glowing_text),
             color: AtomicI8::new(DyeColor::from(color) as i8),
-            messages: Arc::new(std::sync::Mutex::new([
-                // its important that we use unwrap_or since otherwise we may crash on older versions
-                messages.first().unwrap_or(&"".to_string()).clone(),
-                messages.get(1).unwrap_or(&"".to_string()).clone(),
-                messages.get(2).unwrap_or(&"".to_string()).clone(),
-                messages.get(3).unwrap_or(&"".to_string()).clone(),
-            ])),
+            messages: Arc::new(std::sync::Mutex::new(messages)),
+            filtered_messages: Arc::new(std::sync::Mutex::new(filtered_messages)),
         }
     }
 }
@@ -195,10 +207,26 @@ impl Text {
         Self {
             has_glowing_text: AtomicBool::new(false),
             color: AtomicI8::new(DyeColor::default() as i8),
+            filtered_messages: Arc::new(std::sync::Mutex::new(messages.clone())),
             messages: Arc::new(std::sync::Mutex::new(messages)),
         }
     }
 
+    /// Sets the raw lines together with what players that filter text see instead.
+    pub fn set_messages(&self, messages: [String; 4], filtered_messages: [String; 4]) {
+        *self.messages.lock().unwrap() = messages;
+        *self.filtered_messages.lock().unwrap() = filtered_messages;
+    }
+
+    /// Whether any of the lines has text on it.
+    pub fn has_message(&self) -> bool {
+        self.messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| !line.is_empty())
+    }
+
     pub fn get_color(&self) -> DyeColor {
         self.color.load(Ordering::Relaxed).into()
     }
@@ -1,18 +1,21 @@
 use async_trait::async_trait;
 use bytes::*;
 use flate2::read::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder};
-use itertools::Itertools;
 use lz4_java_wrc::Context;
 use pumpkin_config::advanced_config;
 use pumpkin_util::math::vector2::Vector2;
+use sha2::{Digest, Sha256};
 use std::{
     collections::HashSet,
-    io::{Read, SeekFrom, Write},
+    fs,
+    io::{Read, Seek, SeekFrom, Write},
     marker::PhantomData,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering::Relaxed},
     time::{SystemTime, UNIX_EPOCH},
 };
 use tokio::{
+    fs::File,
     io::{AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufWriter},
     sync::Mutex,
 };
@@ -107,13 +110,26 @@ struct AnvilChunkMetadata {
     serialized_data: AnvilChunkData,
     timestamp: u32,
 
-    // NOTE: This is only valid if our WriteAction is `Parts`
-    file_sector_offset: u32,
+    /// Where the chunk is according to the header on disk, as `(sector offset << 8) | sector count`,
+    /// or 0 if it was never written. This can differ from `serialized_data` until the next write.
+    file_location: AtomicU32,
+}
+
+/// A chunk listed in the header of a region file, see [`inspect_region`].
+pub struct RegionEntry {
+    /// The position of the chunk inside its region, from 0 to 31 on both axes.
+    pub local_position: Vector2<i32>,
+    /// When the chunk was last saved, in seconds since the Unix epoch.
+    pub timestamp: u32,
+    /// The first sector of the chunk, counting the two header sectors.
+    pub sector_offset: u32,
+    pub sector_count: u32,
+    /// The uncompressed NBT of the chunk, or why it can't be read.
+    pub data: Result<Bytes, ChunkReadingError>,
 }
 
 pub struct AnvilChunkFile<S: SingleChunkDataSerializer> {
     chunks_data: [Option<AnvilChunkMetadata>; CHUNK_COUNT],
-    end_sector: u32,
     write_action: Mutex<WriteAction>,
 
     _dummy: PhantomData<S>,
@@ -256,7 +272,11 @@ impl AnvilChunkData {
     fn from_bytes(bytes: Bytes) -> Result<Self, ChunkReadingError> {
         let mut bytes = bytes;
         // Minus one for the compression byte
-        let length = bytes.get_u32() as usize - 1;
+        let length = (bytes.get_u32() as usize).checked_sub(1).ok_or_else(|| {
+            ChunkReadingError::ParsingError(ChunkParsingError::ErrorDeserializingChunk(
+                "Chunk length is zero".to_string(),
+            ))
+        })?;
 
         if length > bytes.len() {
             return Err(ChunkReadingError::ParsingError(
@@ -302,14 +322,21 @@ impl AnvilChunkData {
     where
         S: SingleChunkDataSerializer,
     {
-        if let Some(compression) = self.compression {
-            let decompress_bytes = compression
-                .decompress_data(&self.compressed_data)
-                .map_err(ChunkReadingError::Compression)?;
+        S::from_bytes(self.decompressed()?, pos)
+    }
 
-            S::from_bytes(decompress_bytes.into(), pos)
-        } else {
-            S::from_bytes(self.compressed_data.clone(), pos)
+    /// The uncompressed NBT of the chunk.
+    fn decompressed(&self) -> Result<Bytes, ChunkReadingError> {
+        match self.compression {
+            // Custom compression is identified by a name we don't know any of
+            Some(Compression::Custom) => Err(ChunkReadingError::Compression(
+                CompressionError::UnknownCompression,
+            )),
+            Some(compression) => compression
+                .decompress_data(&self.compressed_data)
+                .map(Bytes::from)
+                .map_err(ChunkReadingError::Compression),
+            None => Ok(self.compressed_data.clone()),
         }
     }
 
@@ -353,6 +380,11 @@ impl<S: SingleChunkDataSerializer> AnvilChunkFile<S> {
         index as usize
     }
 
+    /// Writes the chunks at `indices` without touching the rest of the file.
+    ///
+    /// The chunks go to sectors the header on disk doesn't point at, and are synced before the
+    /// header is replaced through a journal. Killing the server at any point leaves either the
+    /// old or the new version of every chunk, see [`recover_region_folder`].
     async fn write_indices(&self, path: &Path, indices: &[usize]) -> Result<(), std::io::Error> {
         log::trace!("Writing in place: {path:?}");
 
@@ -365,90 +397,75 @@ impl<S: SingleChunkDataSerializer> AnvilChunkFile<S> {
             .open(path)
             .await?;
 
-        let mut write = BufWriter::new(file);
-        // The first two sectors are reserved for the location table
-        for (index, metadata) in self.chunks_data.iter().enumerate() {
-            if let Some(chunk) = metadata {
-                let chunk_data = &chunk.serialized_data;
-                let sector_count = chunk_data.sector_count();
-                log::trace!(
-                    "Writing position for chunk {} - {}:{}",
-                    index,
-                    chunk.file_sector_offset,
-                    sector_count
-                );
-                write
-                    .write_u32((chunk.file_sector_offset << 8) | sector_count)
-                    .await?;
-            } else {
-                // If the chunk is not present, we write 0 to the location and timestamp tables
-                write.write_u32(0).await?;
-            };
-        }
-
-        for metadata in &self.chunks_data {
-            if let Some(chunk) = metadata {
-                write.write_u32(chunk.timestamp).await?;
-            } else {
-                // If the chunk is not present, we write 0 to the location and timestamp tables
-                write.write_u32(0).await?;
-            }
-        }
+        // The old copies of the chunks we write stay in use until the new header is in place
+        let mut used_sectors = self
+            .chunks_data
+            .iter()
+            .flatten()
+            .map(|chunk| chunk.file_location.load(Relaxed))
+            .filter(|location| *location != 0)
+            .map(|location| (location >> 8, location & 0xFF))
+            .collect::<Vec<_>>();
 
         let mut chunks = indices
             .iter()
             .map(|index| {
-                (
-                    index,
-                    self.chunks_data[*index]
-                        .as_ref()
-                        .expect("We are trying to write a chunk, but it does not exist!"),
-                )
+                let chunk = self.chunks_data[*index]
+                    .as_ref()
+                    .expect("We are trying to write a chunk, but it does not exist!");
+                let sector_count = chunk.serialized_data.sector_count();
+                let offset = find_free_sectors(&mut used_sectors, sector_count);
+                (*index, chunk, (offset << 8) | sector_count)
             })
             .collect::<Vec<_>>();
 
         // Sort such that writes are in order
-        chunks.sort_by_key(|chunk| chunk.1.file_sector_offset);
+        chunks.sort_by_key(|(_, _, location)| *location);
 
-        #[cfg(debug_assertions)]
-        {
-            // Verify we are actually two sectors into the file
-            let current_pos = write.stream_position().await?;
-            assert!(current_pos as usize == 2 * SECTOR_BYTES);
-        }
-
-        let mut current_sector = 2;
-        for (index, chunk) in chunks {
-            debug_assert!(
-                current_sector <= chunk.file_sector_offset,
-                "Current sector is {} but we want to write to {}!",
-                current_sector,
-                chunk.file_sector_offset
-            );
-
-            // Seek only if we need to
-            if chunk.file_sector_offset != current_sector {
-                log::trace!("Seeking to sector {}", chunk.file_sector_offset);
-                let _ = write
-                    .seek(SeekFrom::Start(
-                        chunk.file_sector_offset as u64 * SECTOR_BYTES as u64,
-                    ))
-                    .await?;
-                current_sector = chunk.file_sector_offset;
-            }
+        let mut write = BufWriter::new(file);
+        for (index, chunk, location) in &chunks {
             log::trace!(
                 "Writing chunk {} - {}:{}",
                 index,
-                current_sector,
-                chunk.serialized_data.sector_count()
+                location >> 8,
+                location & 0xFF
             );
-
-            current_sector += chunk.serialized_data.sector_count();
-
+            write
+                .seek(SeekFrom::Start(
+                    u64::from(location >> 8) * SECTOR_BYTES as u64,
+                ))
+                .await?;
             chunk.serialized_data.write(&mut write).await?;
         }
+        write.flush().await?;
+        let mut file = write.into_inner();
+        file.sync_data().await?;
+
+        let header = self.header(|index, chunk| {
+            chunks
+                .iter()
+                .find(|(written, _, _)| *written == index)
+                .map_or_else(|| chunk.file_location.load(Relaxed), |chunk| chunk.2)
+        });
+        commit_header(path, &mut file, &header).await?;
+
+        for (_, chunk, location) in chunks {
+            chunk.file_location.store(location, Relaxed);
+        }
+        Ok(())
+    }
 
-        write.flush().await
+    /// The location and timestamp tables, with the location of each chunk taken from `location`.
+    fn header(&self, location: impl Fn(usize, &AnvilChunkMetadata) -> u32) -> Vec<u8> {
+        let mut header = Vec::with_capacity(SECTOR_BYTES * 2);
+        for (index, metadata) in self.chunks_data.iter().enumerate() {
+            // If the chunk is not present, we write 0 to the location and timestamp tables
+            header.put_u32(metadata.as_ref().map_or(0, |chunk| location(index, chunk)));
+        }
+        for metadata in &self.chunks_data {
+            header.put_u32(metadata.as_ref().map_or(0, |chunk| chunk.timestamp));
+        }
+        header
     }
 
     /// Write entire file, disregarding saved offsets
@@ -496,10 +513,22 @@ impl<S: SingleChunkDataSerializer> AnvilChunkFile<S> {
         }
 
         write.flush().await?;
+        // The data has to be on disk before the rename, or a crash could leave the renamed file
+        // empty
+        write.get_ref().sync_all().await?;
         // The rename of the file works like an atomic operation ensuring
         // that the data is not corrupted before the rename is completed
         tokio::fs::rename(temp_path, path).await?;
 
+        let mut current_sector: u32 = 2;
+        for chunk in self.chunks_data.iter().flatten() {
+            let sector_count = chunk.serialized_data.sector_count();
+            chunk
+                .file_location
+                .store((current_sector << 8) | sector_count, Relaxed);
+            current_sector += sector_count;
+        }
+
         log::trace!("Wrote file to Disk: {path:?}");
         Ok(())
     }
@@ -510,8 +539,6 @@ impl<S: SingleChunkDataSerializer> Default for AnvilChunkFile<S> {
         Self {
             chunks_data: [const { None }; CHUNK_COUNT],
             write_action: Mutex::new(WriteAction::Pass),
-            // Two sectors for offset + timestamp
-            end_sector: 2,
             _dummy: Default::default(),
         }
     }
@@ -524,6 +551,176 @@ pub trait SingleChunkDataSerializer: Send + Sync + Sized + Dirtiable {
     fn position(&self) -> &Vector2<i32>;
 }
 
+/// Lists every chunk of a region file. Unlike loading the region, a damaged chunk doesn't stop
+/// the others from being read, so this is meant for finding out what is wrong with a file.
+pub fn inspect_region(mut bytes: Bytes) -> Result<Vec<RegionEntry>, ChunkReadingError> {
+    if bytes.len() < SECTOR_BYTES * 2 {
+        return Err(ChunkReadingError::InvalidHeader);
+    }
+    let headers = bytes.split_to(SECTOR_BYTES * 2);
+    let (mut location_bytes, mut timestamp_bytes) = headers.split_at(SECTOR_BYTES);
+
+    let mut entries = Vec::new();
+    for i in 0..CHUNK_COUNT {
+        let timestamp = timestamp_bytes.get_u32();
+        let location = location_bytes.get_u32();
+        let sector_count = location & 0xFF;
+        let sector_offset = location >> 8;
+        if sector_offset == 0 || sector_count == 0 {
+            continue;
+        }
+
+        let start = (sector_offset as usize).saturating_sub(2) * SECTOR_BYTES;
+        let end = start + sector_count as usize * SECTOR_BYTES;
+        let data = if sector_offset < 2 {
+            Err(ChunkReadingError::ParsingError(
+                ChunkParsingError::ErrorDeserializingChunk(format!(
+                    "The chunk starts at sector {sector_offset}, inside the header"
+                )),
+            ))
+        } else if end > bytes.len() {
+            Err(ChunkReadingError::ParsingError(
+                ChunkParsingError::ErrorDeserializingChunk(format!(
+                    "The file ends {} bytes before the end of the chunk",
+                    end - bytes.len()
+                )),
+            ))
+        } else {
+            AnvilChunkData::from_bytes(bytes.slice(start..end))
+                .and_then(|chunk| chunk.decompressed())
+        };
+
+        entries.push(RegionEntry {
+            local_position: Vector2::new(
+                i as i32 % REGION_SIZE as i32,
+                i as i32 / REGION_SIZE as i32,
+            ),
+            timestamp,
+            sector_offset,
+            sector_count,
+            data,
+        });
+    }
+    Ok(entries)
+}
+
+/// Returns the first sector offset with room for `sector_count` sectors between the `used`
+/// ranges of `(offset, count)`, and marks them as used.
+fn find_free_sectors(used: &mut Vec<(u32, u32)>, sector_count: u32) -> u32 {
+    used.sort_unstable();
+    // The first two sectors are reserved for the location table
+    let mut offset = 2;
+    for (used_offset, used_count) in used.iter() {
+        if *used_offset >= offset + sector_count {
+            break;
+        }
+        offset = offset.max(used_offset + used_count);
+    }
+    used.push((offset, sector_count));
+    offset
+}
+
+/// Where the header of the region file at `path` is written before it replaces the old one.
+fn journal_path(path: &Path) -> PathBuf {
+    path.with_extension("mca.journal")
+}
+
+/// Replaces the header of the region file at `path` with `header`. Header writes are not atomic,
+/// so it goes to a journal first: if the server is killed while the header is written, the
+/// journal is replayed on the next start.
+async fn commit_header(path: &Path, file: &mut File, header: &[u8]) -> Result<(), std::io::Error> {
+    let journal_path = journal_path(path);
+    let mut journal = File::create(&journal_path).await?;
+    journal.write_all(header).await?;
+    journal.write_all(&Sha256::digest(header)).await?;
+    journal.sync_all().await?;
+
+    file.seek(SeekFrom::Start(0)).await?;
+    file.write_all(header).await?;
+    file.sync_data().await?;
+    tokio::fs::remove_file(journal_path).await
+}
+
+/// Repairs the region files in `folder` after the server was killed while saving them, and is
+/// meant to run before any of them is read.
+///
+/// Complete header journals are replayed. Journals torn by the crash and temporary files of whole
+/// region writes are moved to the `quarantine` folder: the region file still has its old header
+/// then, which only points at chunk data that was fully written.
+pub fn recover_region_folder(folder: &Path) -> Result<(), std::io::Error> {
+    if !folder.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name.ends_with(".mca.journal") {
+            let region_path = path.with_extension("");
+            if replay_journal(&region_path, &path)? {
+                log::info!("Finished the interrupted save of {}", region_path.display());
+            } else {
+                log::warn!(
+                    "The header of {} was torn by an interrupted save, keeping the previous one",
+                    region_path.display()
+                );
+                quarantine(folder, &path)?;
+            }
+        } else if name.starts_with("r.") && name.ends_with(".tmp") {
+            log::warn!("Found the incomplete region file {}", path.display());
+            quarantine(folder, &path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the header in the journal to the region file if the journal is complete and the
+/// header only points at sectors that exist. Returns whether it was written.
+fn replay_journal(region_path: &Path, journal_path: &Path) -> Result<bool, std::io::Error> {
+    let journal = fs::read(journal_path)?;
+    let Some((header, checksum)) = journal.split_at_checked(SECTOR_BYTES * 2) else {
+        return Ok(false);
+    };
+    if checksum != Sha256::digest(header).as_slice() {
+        return Ok(false);
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(region_path)?;
+    let file_sectors = file.metadata()?.len().div_ceil(SECTOR_BYTES as u64);
+    // The chunk data is synced before the journal is written, so it has to be there
+    let fits = header[..SECTOR_BYTES].chunks_exact(4).all(|location| {
+        let location = u32::from_be_bytes(location.try_into().unwrap());
+        let (offset, count) = (location >> 8, location & 0xFF);
+        offset == 0 || count == 0 || (offset >= 2 && u64::from(offset + count) <= file_sectors)
+    });
+    if !fits {
+        return Ok(false);
+    }
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(header)?;
+    file.sync_data()?;
+    fs::remove_file(journal_path)?;
+    Ok(true)
+}
+
+/// Moves a file left behind by an interrupted save to the `quarantine` folder, out of the way but
+/// not lost.
+fn quarantine(folder: &Path, path: &Path) -> Result<(), std::io::Error> {
+    let quarantine = folder.join("quarantine");
+    fs::create_dir_all(&quarantine)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{timestamp}"));
+    fs::rename(path, quarantine.join(name))
+}
+
 #[async_trait]
 impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
     type Data = S;
@@ -569,24 +766,18 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
 
         let mut chunk_file = AnvilChunkFile::default();
 
-        let mut last_offset = 2;
         for i in 0..CHUNK_COUNT {
             let timestamp = timestamp_bytes.get_u32();
             let location = location_bytes.get_u32();
 
             let sector_count = (location & 0xFF) as usize;
             let sector_offset = (location >> 8) as usize;
-            let end_offset = sector_offset + sector_count;
 
             // If the sector offset or count is 0, the chunk is not present (we should not parse empty chunks)
             if sector_offset == 0 || sector_count == 0 {
                 continue;
             }
 
-            if end_offset > last_offset {
-                last_offset = end_offset;
-            }
-
             // We always subtract 2 for the first two sectors for the timestamp and location tables
             // that we walked earlier
             let bytes_offset = (sector_offset - 2) * SECTOR_BYTES;
@@ -610,11 +801,10 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
             chunk_file.chunks_data[i] = Some(AnvilChunkMetadata {
                 serialized_data,
                 timestamp,
-                file_sector_offset: sector_offset as u32,
+                file_location: AtomicU32::new(location),
             });
         }
 
-        chunk_file.end_sector = last_offset as u32;
         Ok(chunk_file)
     }
 
@@ -636,155 +826,16 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
             *write_action = WriteAction::All;
         }
 
-        match &*write_action {
-            WriteAction::All => {
-                log::trace!("Write action is all: setting chunk in place");
-                // Doesn't matter, just add the data
-                self.chunks_data[index] = Some(AnvilChunkMetadata {
-                    serialized_data: new_chunk_data,
-                    timestamp: epoch,
-                    file_sector_offset: 0,
-                });
-            }
-            _ => {
-                match self.chunks_data[index].as_ref() {
-                    None => {
-                        log::trace!(
-                            "Chunk {} does not exist, appending to EOF: {}:{}",
-                            index,
-                            self.end_sector,
-                            new_chunk_data.sector_count()
-                        );
-                        // This chunk didn't exist before; append to EOF
-                        let new_eof = self.end_sector + new_chunk_data.sector_count();
-                        self.chunks_data[index] = Some(AnvilChunkMetadata {
-                            serialized_data: new_chunk_data,
-                            timestamp: epoch,
-                            file_sector_offset: self.end_sector,
-                        });
-                        self.end_sector = new_eof;
-                        write_action.maybe_update_chunk_index(index);
-                    }
-                    Some(old_chunk) => {
-                        if old_chunk.serialized_data.sector_count() == new_chunk_data.sector_count()
-                        {
-                            log::trace!(
-                                "Chunk {} exists, writing in place: {}:{}",
-                                index,
-                                old_chunk.file_sector_offset,
-                                new_chunk_data.sector_count()
-                            );
-                            // We can just add it
-                            self.chunks_data[index] = Some(AnvilChunkMetadata {
-                                serialized_data: new_chunk_data,
-                                timestamp: epoch,
-                                file_sector_offset: old_chunk.file_sector_offset,
-                            });
-                            write_action.maybe_update_chunk_index(index);
-                        } else {
-                            // Walk back the end of the list; seeing if there's something that can fit
-                            // in our spot. Here we play a game between is it worth it to do all
-                            // this swapping. I figure if we don't find it after 64 chunks, just
-                            // re-write the whole file instead
-                            // The number is a guestimation and no rigorious thought when into it.
-                            // The more we leapfrog like this, there is a higher
-                            // (abiet still small) of these chunks being corrupted if we are doing a
-                            // write operation when there is an un-clean shutdown
-                            //
-                            // Writing all is "safer" in the sense that no chunks will corrupt,
-                            // but will still roll back the entire region if
-                            // there is an unclean shutdown
-
-                            let mut chunks = self
-                                .chunks_data
-                                .iter()
-                                .enumerate()
-                                .filter_map(|(index, chunk)| {
-                                    chunk.as_ref().map(|chunk| (index, chunk))
-                                })
-                                .collect::<Vec<_>>();
-                            chunks.sort_by_key(|chunk| chunk.1.file_sector_offset);
-
-                            let mut chunks_to_shift = chunks
-                                .into_iter()
-                                .rev()
-                                .take(64)
-                                .take_while_inclusive(|chunk| {
-                                    chunk.1.serialized_data.sector_count()
-                                        != old_chunk.serialized_data.sector_count()
-                                })
-                                .collect::<Vec<_>>();
-
-                            if chunks_to_shift.last().is_none_or(|chunk| chunk.0 == index) {
-                                log::trace!(
-                                    "Unable to find a chunk to swap with; falling back to serialize all",
-                                );
-
-                                // give up...
-                                *write_action = WriteAction::All;
-                                self.chunks_data[index] = Some(AnvilChunkMetadata {
-                                    serialized_data: new_chunk_data,
-                                    timestamp: epoch,
-                                    file_sector_offset: 0,
-                                });
-                            } else {
-                                // swap last element of the chunks to shift (the first because we
-                                // reversed it) and shift the rest down
-                                let swap = chunks_to_shift
-                                    .pop()
-                                    .expect("We just checked that this exists");
-
-                                let indices_to_shift = chunks_to_shift
-                                    .iter()
-                                    .map(|(index, _)| index)
-                                    .copied()
-                                    .collect::<Vec<_>>();
-                                let swapped_sectors = swap.1.serialized_data.sector_count();
-                                let new_sectors = new_chunk_data.sector_count();
-                                let swapped_index = swap.0;
-                                let old_offset = old_chunk.file_sector_offset;
-                                self.chunks_data[index] = Some(AnvilChunkMetadata {
-                                    serialized_data: new_chunk_data,
-                                    timestamp: epoch,
-                                    file_sector_offset: swap.1.file_sector_offset,
-                                });
-                                write_action.maybe_update_chunk_index(index);
-
-                                self.chunks_data[swapped_index]
-                                    .as_mut()
-                                    .expect("We checked if this was none")
-                                    .file_sector_offset = old_offset;
-                                write_action.maybe_update_chunk_index(swapped_index);
-
-                                // Then offset everything else
-
-                                // If positive, now larger -> shift right, else shift left
-                                let offset = new_sectors as i64 - swapped_sectors as i64;
-
-                                log::trace!(
-                                    "Swapping {index} with {swapped_index}, shifting all chunks {swapped_index} and after by {offset}"
-                                );
-
-                                for shift_index in indices_to_shift {
-                                    let chunk_data = self.chunks_data[shift_index]
-                                        .as_mut()
-                                        .expect("We checked if this was none");
-                                    let new_offset = chunk_data.file_sector_offset as i64 + offset;
-                                    chunk_data.file_sector_offset = new_offset as u32;
-                                    write_action.maybe_update_chunk_index(shift_index);
-                                }
-
-                                // If the shift is negative then there will be trailing data, but i
-                                // think that's fine
-
-                                let new_end = self.end_sector as i64 + offset;
-                                self.end_sector = new_end as u32;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        // The old copy stays where it is on disk until the next write put the new one elsewhere
+        let file_location = self.chunks_data[index]
+            .as_ref()
+            .map_or(0, |chunk| chunk.file_location.load(Relaxed));
+        self.chunks_data[index] = Some(AnvilChunkMetadata {
+            serialized_data: new_chunk_data,
+            timestamp: epoch,
+            file_location: AtomicU32::new(file_location),
+        });
+        write_action.maybe_update_chunk_index(index);
 
         Ok(())
     }
@@ -824,16 +875,21 @@ mod tests {
     use async_trait::async_trait;
     use pumpkin_config::{AdvancedConfiguration, advanced_config, override_config_for_testing};
     use pumpkin_data::BlockDirection;
+    use pumpkin_util::lock_order::OrderedRwLock;
     use pumpkin_util::math::position::BlockPos;
     use pumpkin_util::math::vector2::Vector2;
     use std::fs;
     use std::path::PathBuf;
     use std::sync::Arc;
     use temp_dir::TempDir;
-    use tokio::sync::RwLock;
 
     use crate::chunk::ChunkData;
-    use crate::chunk::format::anvil::{AnvilChunkFile, SingleChunkDataSerializer};
+    use sha2::{Digest, Sha256};
+
+    use crate::chunk::format::anvil::{
+        AnvilChunkFile, SECTOR_BYTES, SingleChunkDataSerializer, find_free_sectors, journal_path,
+        recover_region_folder,
+    };
     use crate::chunk::io::file_manager::{ChunkFileManager, PathFromLevelFolder};
     use crate::chunk::io::{FileIO, LoadedData};
     use crate::dimension::Dimension;
@@ -859,7 +915,7 @@ mod tests {
         saver: &ChunkFileManager<AnvilChunkFile<S>>,
         folder: &LevelFolder,
         chunks: &[(Vector2<i32>, SyncChunk)],
-    ) -> Box<[Arc<RwLock<S>>]>
+    ) -> Box<[Arc<OrderedRwLock<S>>]>
     where
         S: SingleChunkDataSerializer + PathFromLevelFolder + 'static,
     {
@@ -1279,6 +1335,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn free_sectors() {
+        let mut used = vec![(10, 2), (2, 3), (6, 2)];
+        // Fits in the gap between sectors 5 and 6
+        assert_eq!(find_free_sectors(&mut used, 1), 5);
+        // Too large for the gap between 8 and 10
+        assert_eq!(find_free_sectors(&mut used, 3), 12);
+        assert_eq!(find_free_sectors(&mut used, 2), 8);
+        assert_eq!(find_free_sectors(&mut Vec::new(), 4), 2);
+    }
+
+    #[test]
+    fn replay_header_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let region_path = temp_dir.path().join("r.0.0.mca");
+        // Two header sectors and one chunk sector
+        fs::write(&region_path, vec![0; SECTOR_BYTES * 3]).unwrap();
+
+        let mut header = vec![0; SECTOR_BYTES * 2];
+        header[..4].copy_from_slice(&((2 << 8) | 1u32).to_be_bytes());
+        let mut journal = header.clone();
+        journal.extend_from_slice(&Sha256::digest(&header));
+        fs::write(journal_path(&region_path), journal).unwrap();
+
+        recover_region_folder(temp_dir.path()).unwrap();
+        assert!(!journal_path(&region_path).exists());
+        assert_eq!(fs::read(&region_path).unwrap()[..SECTOR_BYTES * 2], header);
+    }
+
+    #[test]
+    fn quarantine_torn_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let region_path = temp_dir.path().join("r.0.0.mca");
+        fs::write(&region_path, vec![0; SECTOR_BYTES * 2]).unwrap();
+        // The checksum is missing, as if the server was killed while writing the journal
+        fs::write(journal_path(&region_path), vec![1; SECTOR_BYTES * 2]).unwrap();
+        fs::write(temp_dir.path().join("r.0.0.tmp"), [1]).unwrap();
+
+        recover_region_folder(temp_dir.path()).unwrap();
+        assert!(!journal_path(&region_path).exists());
+        assert!(!temp_dir.path().join("r.0.0.tmp").exists());
+        assert_eq!(
+            fs::read_dir(temp_dir.path().join("quarantine"))
+                .unwrap()
+                .count(),
+            2
+        );
+        // The previous header is kept
+        assert_eq!(fs::read(&region_path).unwrap(), vec![0; SECTOR_BYTES * 2]);
+    }
+
     // TODO
     /*
     #[test]
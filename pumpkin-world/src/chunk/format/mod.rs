@@ -14,11 +14,12 @@ use crate::{
         format::anvil::{SingleChunkDataSerializer, WORLD_DATA_VERSION},
         io::{Dirtiable, file_manager::PathFromLevelFolder},
     },
-    generation::section_coords,
-    level::LevelFolder,
+    generation::{blender::BlendingData, section_coords},
+    level::{CHUNK_LOCK_CLASS, ENTITY_CHUNK_LOCK_CLASS, LevelFolder},
+    poi::{ChunkPoiStorage, PoiRecord},
     tick::{ScheduledTick, scheduler::ChunkTickScheduler},
 };
-use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::{lock_order::OrderedRwLock, math::vector2::Vector2};
 use serde::{Deserialize, Serialize};
 
 use super::{
@@ -46,7 +47,7 @@ impl SingleChunkDataSerializer for ChunkData {
 
     #[inline]
     async fn to_bytes(&self) -> Result<Bytes, ChunkSerializingError> {
-        self.internal_to_bytes().await
+        self.internal_to_bytes()
     }
 
     #[inline]
@@ -56,12 +57,15 @@ impl SingleChunkDataSerializer for ChunkData {
 }
 
 impl PathFromLevelFolder for ChunkData {
+    const LOCK_CLASS: &'static str = CHUNK_LOCK_CLASS;
+
     #[inline]
     fn file_path(folder: &LevelFolder, file_name: &str) -> PathBuf {
         folder.region_folder.join(file_name)
     }
 }
 
+#[async_trait]
 impl Dirtiable for ChunkData {
     #[inline]
     fn mark_dirty(&mut self, flag: bool) {
@@ -72,6 +76,24 @@ impl Dirtiable for ChunkData {
     fn is_dirty(&self) -> bool {
         self.dirty
     }
+
+    async fn prepare_save(lock: &OrderedRwLock<Self>) {
+        // Writing a block entity locks its inventory slots, which are taken before chunks
+        let block_entities: Vec<_> = {
+            let chunk = lock.read().await;
+            if !chunk.dirty {
+                return;
+            }
+            chunk.block_entities.values().cloned().collect()
+        };
+        let block_entity_nbt = join_all(block_entities.iter().map(|block_entity| async move {
+            let mut nbt = NbtCompound::new();
+            block_entity.write_internal(&mut nbt).await;
+            nbt
+        }))
+        .await;
+        lock.write().await.block_entity_nbt = block_entity_nbt;
+    }
 }
 
 impl ChunkData {
@@ -145,6 +167,7 @@ impl ChunkData {
                         .unwrap_or_default()
                 })
                 .collect(),
+            is_lit: chunk_data.light_correct,
         };
 
         let sub_chunks = chunk_data
@@ -163,8 +186,13 @@ impl ChunkData {
             .collect();
         let min_y = section_coords::section_to_block(chunk_data.min_y_section);
         let section = ChunkSections::new(sub_chunks, min_y);
+        // Chunks saved before POIs were tracked have to be scanned once
+        let poi = match chunk_data.poi {
+            Some(records) => ChunkPoiStorage::from_records(records),
+            None => ChunkPoiStorage::scan(&section, position),
+        };
 
-        Ok(ChunkData {
+        let mut chunk = ChunkData {
             section,
             heightmap: chunk_data.heightmaps,
             position,
@@ -182,12 +210,30 @@ impl ChunkData {
                 }
                 block_entities
             },
+            block_entity_nbt: Vec::new(),
             light_engine,
             status: chunk_data.status,
-        })
+            poi,
+            blending_data: None,
+            last_update: chunk_data.last_update,
+        };
+        if !chunk.heightmap.is_complete() {
+            chunk.heightmap = chunk.calculate_heightmap();
+        }
+        // Chunks from older versions were generated differently, so new chunks next to them have
+        // to blend into their terrain
+        let is_outdated =
+            chunk_data.data_version < WORLD_DATA_VERSION && chunk.status == ChunkStatus::Full;
+        chunk.blending_data = match chunk_data.blending_data {
+            Some(blending_data) if blending_data.has_heights() => Some(blending_data),
+            Some(_) => Some(BlendingData::from_chunk(&chunk)),
+            None if is_outdated => Some(BlendingData::from_chunk(&chunk)),
+            None => None,
+        };
+        Ok(chunk)
     }
 
-    async fn internal_to_bytes(&self) -> Result<Bytes, ChunkSerializingError> {
+    fn internal_to_bytes(&self) -> Result<Bytes, ChunkSerializingError> {
         let sections: Vec<_> = self
             .section
             .sections
@@ -218,14 +264,13 @@ impl ChunkData {
             sections,
             block_ticks: self.block_ticks.to_vec(),
             fluid_ticks: self.fluid_ticks.to_vec(),
-            block_entities: join_all(self.block_entities.values().map(|block_entity| async move {
-                let mut nbt = NbtCompound::new();
-                block_entity.write_internal(&mut nbt).await;
-                nbt
-            }))
-            .await,
-            // we have not implemented light engine
+            block_entities: self.block_entity_nbt.clone(),
+            // We have not implemented light engine, so the light may be outdated even if it was
+            // lit when loaded
             light_correct: false,
+            poi: Some(self.poi.to_records()),
+            blending_data: self.blending_data.clone(),
+            last_update: self.last_update,
         };
 
         let mut result = Vec::new();
@@ -236,6 +281,8 @@ impl ChunkData {
 }
 
 impl PathFromLevelFolder for ChunkEntityData {
+    const LOCK_CLASS: &'static str = ENTITY_CHUNK_LOCK_CLASS;
+
     #[inline]
     fn file_path(folder: &LevelFolder, file_name: &str) -> PathBuf {
         folder.entities_folder.join(file_name)
@@ -470,6 +517,16 @@ struct ChunkNbt {
     block_entities: Vec<NbtCompound>,
     #[serde(rename = "isLightOn")]
     light_correct: bool,
+    #[serde(rename = "poi_records", default)]
+    poi: Option<Vec<PoiRecord>>,
+    #[serde(
+        rename = "blending_data",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    blending_data: Option<BlendingData>,
+    #[serde(rename = "LastUpdate", default)]
+    last_update: i64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
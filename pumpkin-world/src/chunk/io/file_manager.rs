@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use futures::future::join_all;
 use log::{error, trace};
 use num_traits::Zero;
+use pumpkin_util::lock_order::OrderedRwLock;
 use pumpkin_util::math::vector2::Vector2;
 use tokio::{
     io::AsyncReadExt,
@@ -44,6 +45,9 @@ pub struct ChunkFileManager<S: ChunkSerializer<WriteBackend = PathBuf>> {
 }
 
 pub(crate) trait PathFromLevelFolder {
+    /// The lock class the data is wrapped in once loaded.
+    const LOCK_CLASS: &'static str;
+
     fn file_path(folder: &LevelFolder, file_name: &str) -> PathBuf;
 }
 
@@ -155,7 +159,7 @@ where
     P: PathFromLevelFolder + Send + Sync + Sized + Dirtiable + 'static,
     S: ChunkSerializer<Data = P, WriteBackend = PathBuf>,
 {
-    type Data = Arc<RwLock<S::Data>>;
+    type Data = Arc<OrderedRwLock<S::Data>>;
 
     async fn watch_chunks(&self, folder: &LevelFolder, chunks: &[Vector2<i32>]) {
         // It is intentional that regions are watched multiple times (once per chunk)
@@ -227,12 +231,13 @@ where
                 }
             };
 
-            // Intermediate channel for wrapping the data with the Arc<RwLock>
+            // Intermediate channel for wrapping the data with the Arc<OrderedRwLock>
             let (send, mut recv) = mpsc::channel::<LoadedData<S::Data, ChunkReadingError>>(1);
 
             let intermediary = async {
                 while let Some(data) = recv.recv().await {
-                    let wrapped_data = data.map_loaded(|data| Arc::new(RwLock::new(data)));
+                    let wrapped_data =
+                        data.map_loaded(|data| Arc::new(OrderedRwLock::new(P::LOCK_CLASS, data)));
                     if stream.send(wrapped_data).await.is_err() {
                         // Stream is closed, so stop unneeded computation and io
                         return;
@@ -299,6 +304,7 @@ where
                 let update_tasks = chunk_locks.into_iter().map(|chunk_lock| {
                     let chunk_serializer = chunk_serializer.clone();
                     async move {
+                        P::prepare_save(&chunk_lock).await;
                         let mut chunk = chunk_lock.write().await;
                         let chunk_is_dirty = chunk.is_dirty();
                         // Edge case: this chunk is loaded while we were saving, mark it as cleaned since we are
@@ -2,7 +2,7 @@ use std::error;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::{lock_order::OrderedRwLock, math::vector2::Vector2};
 
 use super::{ChunkReadingError, ChunkWritingError};
 use crate::level::LevelFolder;
@@ -34,9 +34,15 @@ impl<D: Send, E: error::Error> LoadedData<D, E> {
     }
 }
 
-pub trait Dirtiable {
+#[async_trait]
+pub trait Dirtiable: Send + Sync + Sized {
     fn is_dirty(&self) -> bool;
     fn mark_dirty(&mut self, flag: bool);
+
+    /// Called right before the data behind `lock` is saved, without holding the lock. Anything
+    /// saving has to take locks ranked before it for, see
+    /// [`pumpkin_util::lock_order::LOCK_ORDER`], is gathered here.
+    async fn prepare_save(_lock: &OrderedRwLock<Self>) {}
 }
 
 /// Trait to handle the IO of chunks
@@ -1,4 +1,6 @@
 use crate::block::entities::BlockEntity;
+use crate::generation::blender::BlendingData;
+use crate::poi::ChunkPoiStorage;
 use crate::tick::scheduler::ChunkTickScheduler;
 use palette::{BiomePalette, BlockPalette};
 use pumpkin_data::block_properties::blocks_movement;
@@ -11,7 +13,6 @@ use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::nbt_long_array;
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use serde::{Deserialize, Serialize};
-use std::ops::{BitAnd, BitOr};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
 
@@ -77,8 +78,17 @@ pub struct ChunkData {
     pub block_ticks: ChunkTickScheduler<&'static Block>,
     pub fluid_ticks: ChunkTickScheduler<&'static Fluid>,
     pub block_entities: HashMap<BlockPos, Arc<dyn BlockEntity>>,
+    /// The block entities as written by the last [`io::Dirtiable::prepare_save`], which is what
+    /// gets saved.
+    pub block_entity_nbt: Vec<NbtCompound>,
     pub light_engine: ChunkLight,
     pub status: ChunkStatus,
+    pub poi: ChunkPoiStorage,
+    /// Set if newly generated chunks next to this one have to blend their terrain into it
+    pub blending_data: Option<BlendingData>,
+    /// The world age when the chunk was last saved, 0 once the chunk caught up on the time it
+    /// spent unloaded or if that is unknown.
+    pub last_update: i64,
 
     pub dirty: bool,
 }
@@ -139,14 +149,77 @@ pub struct SubChunk {
 pub struct ChunkLight {
     pub sky_light: Box<[LightContainer]>,
     pub block_light: Box<[LightContainer]>,
+    /// Whether the light levels were computed, like vanilla's `isLightOn`. There is no light
+    /// engine yet, so only chunks saved lit by vanilla have real light levels.
+    pub is_lit: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightType {
+    /// Light coming from the sky, before it is darkened by the time of day or weather.
+    Sky,
+    /// Light emitted by blocks like torches.
+    Block,
+}
+
+impl ChunkLight {
+    /// The light level `relative_y` blocks above the bottom of the chunk, `None` if the chunk
+    /// isn't lit or the position is outside of its sections.
+    pub fn get(
+        &self,
+        light_type: LightType,
+        relative_x: usize,
+        relative_y: usize,
+        relative_z: usize,
+    ) -> Option<u8> {
+        if !self.is_lit {
+            return None;
+        }
+        let sections = match light_type {
+            LightType::Sky => &self.sky_light,
+            LightType::Block => &self.block_light,
+        };
+        sections
+            .get(relative_y / LightContainer::DIM)
+            .map(|light| light.get(relative_x, relative_y % LightContainer::DIM, relative_z))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChunkHeightmapType {
     WorldSurface = 0,
     MotionBlocking = 1,
     MotionBlockingNoLeaves = 2,
+    OceanFloor = 3,
 }
+
+impl ChunkHeightmapType {
+    pub const ALL: [Self; 4] = [
+        Self::WorldSurface,
+        Self::MotionBlocking,
+        Self::MotionBlockingNoLeaves,
+        Self::OceanFloor,
+    ];
+
+    /// Whether a block counts for this heightmap, i.e. the heightmap can't go below it.
+    pub fn is_opaque(self, state_id: BlockStateId) -> bool {
+        let block_state = BlockState::from_id(state_id);
+        match self {
+            Self::WorldSurface => !block_state.is_air(),
+            Self::OceanFloor => blocks_movement(block_state),
+            Self::MotionBlocking | Self::MotionBlockingNoLeaves => {
+                let block = Block::from_state_id(state_id);
+                let is_motion_blocking = blocks_movement(block_state)
+                    || Fluid::from_registry_key(block.registry_key())
+                        .is_some_and(|fluid| !fluid.states.is_empty());
+                is_motion_blocking
+                    && (self == Self::MotionBlocking
+                        || !block.is_tagged_with_by_tag(&MINECRAFT_LEAVES))
+            }
+        }
+    }
+}
+
 impl TryFrom<usize> for ChunkHeightmapType {
     type Error = &'static str;
 
@@ -155,98 +228,100 @@ impl TryFrom<usize> for ChunkHeightmapType {
             0 => Ok(ChunkHeightmapType::WorldSurface),
             1 => Ok(ChunkHeightmapType::MotionBlocking),
             2 => Ok(ChunkHeightmapType::MotionBlockingNoLeaves),
-            _ => Err("Invalid usize value for ChunkHeightmapType. The value should be 0~2."),
+            3 => Ok(ChunkHeightmapType::OceanFloor),
+            _ => Err("Invalid usize value for ChunkHeightmapType. The value should be 0~3."),
         }
     }
 }
 
+/// Bits used by a single heightmap entry.
+const HEIGHTMAP_BITS: usize = 9;
+const HEIGHTMAP_MASK: u64 = (1 << HEIGHTMAP_BITS) - 1;
+/// Entries never span two longs, so the last bit of each long is unused.
+const HEIGHTMAP_ENTRIES_PER_LONG: usize = 64 / HEIGHTMAP_BITS;
+const HEIGHTMAP_LONGS: usize = CHUNK_AREA.div_ceil(HEIGHTMAP_ENTRIES_PER_LONG);
+
+/// Each entry is the height of the first free block above the highest block counting for the
+/// heightmap, relative to the bottom of the world. `0` means there is no such block in the column.
+///
+/// The entries are packed the same way as vanilla, so they can be saved and sent as is.
+/// Chunks saved without some of the heightmaps get them recalculated when loaded.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "UPPERCASE")]
 pub struct ChunkHeightmaps {
-    #[serde(serialize_with = "nbt_long_array")]
+    #[serde(serialize_with = "nbt_long_array", default)]
     pub world_surface: Box<[i64]>,
-    #[serde(serialize_with = "nbt_long_array")]
+    #[serde(serialize_with = "nbt_long_array", default)]
     pub motion_blocking: Box<[i64]>,
-    #[serde(serialize_with = "nbt_long_array")]
+    #[serde(serialize_with = "nbt_long_array", default)]
     pub motion_blocking_no_leaves: Box<[i64]>,
+    #[serde(serialize_with = "nbt_long_array", default)]
+    pub ocean_floor: Box<[i64]>,
 }
 
 impl ChunkHeightmaps {
-    pub fn set(&mut self, _type: ChunkHeightmapType, pos: BlockPos, min_y: i32) {
-        let data = match _type {
+    fn data(&self, _type: ChunkHeightmapType) -> &[i64] {
+        match _type {
+            ChunkHeightmapType::WorldSurface => &self.world_surface,
+            ChunkHeightmapType::MotionBlocking => &self.motion_blocking,
+            ChunkHeightmapType::MotionBlockingNoLeaves => &self.motion_blocking_no_leaves,
+            ChunkHeightmapType::OceanFloor => &self.ocean_floor,
+        }
+    }
+
+    fn data_mut(&mut self, _type: ChunkHeightmapType) -> &mut [i64] {
+        match _type {
             ChunkHeightmapType::WorldSurface => &mut self.world_surface,
             ChunkHeightmapType::MotionBlocking => &mut self.motion_blocking,
             ChunkHeightmapType::MotionBlockingNoLeaves => &mut self.motion_blocking_no_leaves,
-        };
-
-        let local_x = (pos.0.x & 15) as usize;
-        let local_z = (pos.0.z & 15) as usize;
-
-        let adjust_height = (pos.0.y + min_y.abs()) as usize;
-
-        assert!(adjust_height <= 2 << 9);
-
-        //chunk column index in 16*16 chunk.
-        let column_idx = local_z * 16 + local_x;
+            ChunkHeightmapType::OceanFloor => &mut self.ocean_floor,
+        }
+    }
 
-        // Each height value uses 9 bits, calculate starting bit position
-        let bit_start_idx = column_idx * 9;
+    /// Whether every heightmap is present, which is not the case for chunks saved before all of
+    /// them were tracked.
+    pub fn is_complete(&self) -> bool {
+        ChunkHeightmapType::ALL
+            .iter()
+            .all(|_type| self.data(*_type).len() == HEIGHTMAP_LONGS)
+    }
 
-        // Find where these 9 bits start within a 64-bit packed array element
-        // We use bit_start_index % 63, which means the last bit of i64 won't be used,
-        // but this avoids the hassle of bit concatenation.
-        let packed_array_bit_start_idx = bit_start_idx as u32 % 63;
+    /// Returns the index of the long holding the entry and the offset of the entry in it.
+    fn locate(x: i32, z: i32) -> (usize, usize) {
+        let column_idx = (z & 15) as usize * 16 + (x & 15) as usize;
+        (
+            column_idx / HEIGHTMAP_ENTRIES_PER_LONG,
+            (column_idx % HEIGHTMAP_ENTRIES_PER_LONG) * HEIGHTMAP_BITS,
+        )
+    }
 
-        let mask = {
-            if packed_array_bit_start_idx == 0 {
-                //0b0000_0000_0111_1111_...
-                !(0x1FF << (64 - 9))
-            } else {
-                !(0x1FF << (64 - packed_array_bit_start_idx - 9))
-            }
-        };
+    fn set_raw(&mut self, _type: ChunkHeightmapType, x: i32, z: i32, value: u64) {
+        debug_assert!(value <= HEIGHTMAP_MASK);
+        let (long_idx, offset) = Self::locate(x, z);
+        let data = self.data_mut(_type);
+        let packed = (data[long_idx] as u64 & !(HEIGHTMAP_MASK << offset)) | (value << offset);
+        data[long_idx] = packed as i64;
+    }
 
-        let height_bit_bytes = adjust_height
-            .wrapping_shl(64 - 9 - packed_array_bit_start_idx)
-            .to_ne_bytes();
-        let height = i64::from_ne_bytes(height_bit_bytes);
+    fn get_raw(&self, _type: ChunkHeightmapType, x: i32, z: i32) -> i32 {
+        let (long_idx, offset) = Self::locate(x, z);
+        ((self.data(_type)[long_idx] as u64 >> offset) & HEIGHTMAP_MASK) as i32
+    }
 
-        let packed_array_idx = column_idx / 7;
+    /// Sets the highest block counting for the heightmap in the column of `pos` to `pos`.
+    pub fn set(&mut self, _type: ChunkHeightmapType, pos: BlockPos, min_y: i32) {
+        self.set_raw(_type, pos.0.x, pos.0.z, (pos.0.y + 1 - min_y) as u64);
+    }
 
-        data[packed_array_idx] = data[packed_array_idx].bitand(mask).bitor(height);
+    /// Marks the column as not having any block counting for the heightmap.
+    pub fn clear(&mut self, _type: ChunkHeightmapType, x: i32, z: i32) {
+        self.set_raw(_type, x, z, 0);
     }
 
+    /// Returns the y of the highest block counting for the heightmap, or `min_y - 1` if the column
+    /// has none.
     pub fn get_height(&self, _type: ChunkHeightmapType, x: i32, z: i32, min_y: i32) -> i32 {
-        let data = match _type {
-            ChunkHeightmapType::WorldSurface => &self.world_surface,
-            ChunkHeightmapType::MotionBlocking => &self.motion_blocking,
-            ChunkHeightmapType::MotionBlockingNoLeaves => &self.motion_blocking_no_leaves,
-        };
-
-        let local_x = (x & 15) as usize;
-        let local_z = (z & 15) as usize;
-
-        let column_idx = local_z * 16 + local_x;
-        let bit_start_idx = column_idx * 9;
-
-        let packed_array_bit_start_idx = bit_start_idx as u32 % 63;
-
-        let mask = {
-            if packed_array_bit_start_idx == 0 {
-                //0b1111_1111_1000_0000_...
-                0x1ff << (64 - 9)
-            } else {
-                0x1ff << (64 - packed_array_bit_start_idx - 9)
-            }
-        };
-
-        let packed_array_idx = column_idx / 7;
-
-        let height_bit_bytes_i64 = data[packed_array_idx].bitand(mask).to_ne_bytes();
-
-        (u64::from_ne_bytes(height_bit_bytes_i64)
-            .wrapping_shr(64 - (packed_array_bit_start_idx + 9)) as i32)
-            - min_y.abs()
+        self.get_raw(_type, x, z) + min_y - 1
     }
 
     pub fn log_heightmap(&self, _type: ChunkHeightmapType, min_y: i32) {
@@ -276,11 +351,10 @@ impl ChunkHeightmaps {
 impl Default for ChunkHeightmaps {
     fn default() -> Self {
         Self {
-            // 9 bits per entry
-            // 0 packed into an i64 7 times.
-            motion_blocking: vec![0; 37].into_boxed_slice(),
-            motion_blocking_no_leaves: vec![0; 37].into_boxed_slice(),
-            world_surface: vec![0; 37].into_boxed_slice(),
+            world_surface: vec![0; HEIGHTMAP_LONGS].into_boxed_slice(),
+            motion_blocking: vec![0; HEIGHTMAP_LONGS].into_boxed_slice(),
+            motion_blocking_no_leaves: vec![0; HEIGHTMAP_LONGS].into_boxed_slice(),
+            ocean_floor: vec![0; HEIGHTMAP_LONGS].into_boxed_slice(),
         }
     }
 }
@@ -366,7 +440,6 @@ impl ChunkSections {
         relative_z: usize,
         block_state_id: BlockStateId,
     ) -> BlockStateId {
-        // TODO @LUK_ESC? update the heightmap
         self.set_block_no_heightmap_update(relative_x, relative_y, relative_z, block_state_id)
     }
 
@@ -430,6 +503,28 @@ impl ChunkSections {
 }
 
 impl ChunkData {
+    /// A fully generated chunk that contains nothing but air.
+    pub fn empty(position: Vector2<i32>, min_y: i32, section_count: usize) -> Self {
+        Self {
+            section: ChunkSections::new(
+                vec![SubChunk::default(); section_count].into_boxed_slice(),
+                min_y,
+            ),
+            heightmap: ChunkHeightmaps::default(),
+            position,
+            block_ticks: ChunkTickScheduler::default(),
+            fluid_ticks: ChunkTickScheduler::default(),
+            block_entities: HashMap::new(),
+            block_entity_nbt: Vec::new(),
+            light_engine: ChunkLight::default(),
+            status: ChunkStatus::Full,
+            poi: ChunkPoiStorage::default(),
+            blending_data: None,
+            last_update: 0,
+            dirty: false,
+        }
+    }
+
     /// Gets the given block in the chunk
     #[inline]
     pub fn get_relative_block(
@@ -451,9 +546,14 @@ impl ChunkData {
         relative_z: usize,
         block_state_id: BlockStateId,
     ) {
-        // TODO @LUK_ESC? update the heightmap
         self.section
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
+        self.update_heightmaps(
+            relative_x,
+            relative_y as i32 + self.section.min_y,
+            relative_z,
+            block_state_id,
+        );
     }
 
     /// Sets the given block in the chunk, returning the old block
@@ -473,7 +573,46 @@ impl ChunkData {
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
     }
 
-    //TODO: Tracking heightmaps update.
+    /// Keeps the heightmaps up to date after the block at `y` (absolute) of the column was set
+    /// to `block_state_id`.
+    pub fn update_heightmaps(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) {
+        let min_y = self.section.min_y;
+        let (x, z) = (relative_x as i32, relative_z as i32);
+        for heightmap_type in ChunkHeightmapType::ALL {
+            let top = self.heightmap.get_height(heightmap_type, x, z, min_y);
+            if y < top {
+                // Blocks below the highest one never change the heightmap
+                continue;
+            }
+            if heightmap_type.is_opaque(block_state_id) {
+                if y > top {
+                    self.heightmap
+                        .set(heightmap_type, BlockPos::new(x, y, z), min_y);
+                }
+            } else if y == top {
+                // The highest block was removed, so look for the next one below it
+                let new_top = (min_y..y).rev().find(|&below| {
+                    self.section
+                        .get_block_absolute_y(relative_x, below, relative_z)
+                        .is_some_and(|state_id| heightmap_type.is_opaque(state_id))
+                });
+                match new_top {
+                    Some(new_top) => {
+                        self.heightmap
+                            .set(heightmap_type, BlockPos::new(x, new_top, z), min_y);
+                    }
+                    None => self.heightmap.clear(heightmap_type, x, z),
+                }
+            }
+        }
+    }
+
     pub fn calculate_heightmap(&mut self) -> ChunkHeightmaps {
         let highest_non_empty_subchunk = self.get_highest_non_empty_subchunk();
         let mut heightmaps = ChunkHeightmaps::default();
@@ -484,11 +623,6 @@ impl ChunkData {
             }
         }
 
-        // log::info!("WorldSurface:");
-        // heightmaps.log_heightmap(ChunkHeightmapType::WorldSurface, self.section.min_y);
-        // log::info!("MotionBlocking:");
-        // heightmaps.log_heightmap(ChunkHeightmapType::MotionBlocking, self.section.min_y);
-        // log::info!("min_y: {}", self.section.min_y);
         heightmaps
     }
 
@@ -500,52 +634,25 @@ impl ChunkData {
         x: usize,
         z: usize,
     ) {
-        let start_height = (start_sub_chunk as i32) * 16 - self.section.min_y.abs() + 15;
-        let mut has_found = [false, false, false];
+        let start_height = (start_sub_chunk as i32) * 16 + self.section.min_y + 15;
+        let mut has_found = [false; ChunkHeightmapType::ALL.len()];
 
         for y in (self.section.min_y..=start_height).rev() {
             let pos = BlockPos::new(x as i32, y, z as i32);
             let state_id = self.section.get_block_absolute_y(x, y, z).unwrap();
-            let block_state = BlockState::from_id(state_id);
-            let block = Block::from_state_id(state_id);
 
-            if !block_state.is_air() && !has_found[ChunkHeightmapType::WorldSurface as usize] {
-                heightmaps.set(ChunkHeightmapType::WorldSurface, pos, self.section.min_y);
-                has_found[ChunkHeightmapType::WorldSurface as usize] = true;
-            }
-
-            let is_motion_blocking = blocks_movement(block_state)
-                || Fluid::from_registry_key(block.registry_key())
-                    .is_some_and(|fluid| !fluid.states.is_empty());
-
-            if !has_found[ChunkHeightmapType::MotionBlocking as usize] && is_motion_blocking {
-                heightmaps.set(ChunkHeightmapType::MotionBlocking, pos, self.section.min_y);
-                has_found[ChunkHeightmapType::MotionBlocking as usize] = true;
-            }
-
-            if !has_found[ChunkHeightmapType::MotionBlockingNoLeaves as usize]
-                && is_motion_blocking
-                && !block.is_tagged_with_by_tag(&MINECRAFT_LEAVES)
-            {
-                heightmaps.set(
-                    ChunkHeightmapType::MotionBlockingNoLeaves,
-                    pos,
-                    self.section.min_y,
-                );
-                has_found[ChunkHeightmapType::MotionBlockingNoLeaves as usize] = true;
+            for heightmap_type in ChunkHeightmapType::ALL {
+                if !has_found[heightmap_type as usize] && heightmap_type.is_opaque(state_id) {
+                    heightmaps.set(heightmap_type, pos, self.section.min_y);
+                    has_found[heightmap_type as usize] = true;
+                }
             }
 
             if !has_found.contains(&false) {
                 return;
             }
         }
-
-        let pos = BlockPos::new(x as i32, self.section.min_y, z as i32);
-        for (idx, is_set) in has_found.iter().enumerate() {
-            if !(*is_set) {
-                heightmaps.set(idx.try_into().unwrap(), pos, self.section.min_y);
-            }
-        }
+        // Columns without any matching block keep the default of 0
     }
 
     pub fn get_highest_non_empty_subchunk(&self) -> usize {
@@ -573,3 +680,106 @@ pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::chunk::ChunkStatus;
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+    use std::collections::HashMap;
+
+    use super::{
+        ChunkData, ChunkHeightmapType, ChunkHeightmaps, ChunkLight, ChunkSections, SubChunk,
+    };
+
+    const MIN_Y: i32 = -64;
+
+    fn empty_chunk() -> ChunkData {
+        ChunkData {
+            section: ChunkSections::new(vec![SubChunk::default(); 24].into_boxed_slice(), MIN_Y),
+            heightmap: ChunkHeightmaps::default(),
+            position: Vector2::new(0, 0),
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: HashMap::new(),
+            block_entity_nbt: Vec::new(),
+            light_engine: ChunkLight::default(),
+            status: ChunkStatus::Full,
+            poi: Default::default(),
+            blending_data: None,
+            last_update: 0,
+            dirty: false,
+        }
+    }
+
+    fn set_block(chunk: &mut ChunkData, x: usize, y: i32, z: usize, block: &Block) {
+        chunk.set_relative_block(x, (y - MIN_Y) as usize, z, block.default_state.id);
+    }
+
+    fn assert_matches_recalculation(chunk: &mut ChunkData) {
+        let recalculated = chunk.calculate_heightmap();
+        for heightmap_type in ChunkHeightmapType::ALL {
+            for x in 0..16 {
+                for z in 0..16 {
+                    assert_eq!(
+                        chunk.heightmap.get_height(heightmap_type, x, z, MIN_Y),
+                        recalculated.get_height(heightmap_type, x, z, MIN_Y),
+                        "{heightmap_type:?} at {x} {z}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn vanilla_packing() {
+        let mut heightmaps = ChunkHeightmaps::default();
+        heightmaps.set(
+            ChunkHeightmapType::WorldSurface,
+            BlockPos::new(1, -60, 0),
+            MIN_Y,
+        );
+        // Entries are stored from the lowest bits up, as the first free height
+        assert_eq!(heightmaps.world_surface[0], 5 << 9);
+        assert_eq!(
+            heightmaps.get_height(ChunkHeightmapType::WorldSurface, 1, 0, MIN_Y),
+            -60
+        );
+        assert_eq!(
+            heightmaps.get_height(ChunkHeightmapType::WorldSurface, 0, 0, MIN_Y),
+            MIN_Y - 1
+        );
+    }
+
+    #[test]
+    fn incremental_updates() {
+        let mut chunk = empty_chunk();
+        set_block(&mut chunk, 3, 10, 4, &Block::STONE);
+        set_block(&mut chunk, 3, 20, 4, &Block::WATER);
+        set_block(&mut chunk, 3, 30, 4, &Block::OAK_LEAVES);
+        set_block(&mut chunk, 3, 40, 4, &Block::SHORT_GRASS);
+        assert_matches_recalculation(&mut chunk);
+
+        let height = |chunk: &ChunkData, heightmap_type| {
+            chunk.heightmap.get_height(heightmap_type, 3, 4, MIN_Y)
+        };
+        assert_eq!(height(&chunk, ChunkHeightmapType::WorldSurface), 40);
+        assert_eq!(height(&chunk, ChunkHeightmapType::MotionBlocking), 30);
+        assert_eq!(
+            height(&chunk, ChunkHeightmapType::MotionBlockingNoLeaves),
+            20
+        );
+        assert_eq!(height(&chunk, ChunkHeightmapType::OceanFloor), 10);
+
+        set_block(&mut chunk, 3, 40, 4, &Block::AIR);
+        set_block(&mut chunk, 3, 30, 4, &Block::AIR);
+        assert_matches_recalculation(&mut chunk);
+        assert_eq!(height(&chunk, ChunkHeightmapType::WorldSurface), 20);
+        assert_eq!(height(&chunk, ChunkHeightmapType::MotionBlocking), 20);
+
+        set_block(&mut chunk, 3, 20, 4, &Block::AIR);
+        set_block(&mut chunk, 3, 10, 4, &Block::AIR);
+        assert_matches_recalculation(&mut chunk);
+        assert_eq!(height(&chunk, ChunkHeightmapType::WorldSurface), MIN_Y - 1);
+    }
+}
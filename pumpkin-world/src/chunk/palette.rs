@@ -245,6 +245,17 @@ impl<V: Hash + Eq + Copy + Default, const DIM: usize> PalettedContainer<V, DIM>
         }
     }
 
+    /// Returns whether any value in the cube matches `predicate`, only checking the palette.
+    pub fn any<F>(&self, mut predicate: F) -> bool
+    where
+        F: FnMut(V) -> bool,
+    {
+        match self {
+            Self::Homogeneous(value) => predicate(*value),
+            Self::Heterogeneous(data) => data.palette.iter().any(|value| predicate(*value)),
+        }
+    }
+
     pub fn for_each<F>(&self, mut f: F)
     where
         F: FnMut(V),
@@ -1,6 +1,13 @@
 use enum_dispatch::enum_dispatch;
+use pumpkin_data::{Block, chunk::Biome, tag, tag::Taggable};
+use pumpkin_util::math::vector2::Vector2;
+use serde::{Deserialize, Serialize};
 
-use super::noise::router::density_function::NoisePos;
+use super::{biome_coords, noise::router::density_function::NoisePos, section_coords};
+use crate::chunk::{CHUNK_AREA, ChunkData, ChunkHeightmapType};
+
+/// How many blocks into a newly generated chunk the terrain of a blended neighbour reaches.
+pub const BLEND_DISTANCE: i32 = 8;
 
 pub struct BlendResult {
     alpha: f64,
@@ -44,3 +51,206 @@ impl BlenderImpl for NoBlendBlender {
         density
     }
 }
+
+/// Marks a chunk that was not generated by the current generator, like chunks from an older
+/// version or imported from another world. Newly generated chunks next to it move their terrain
+/// towards it and take on its biomes near the border instead of ending in a cliff.
+///
+/// Saved as `blending_data` in the chunk.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlendingData {
+    pub min_section: i32,
+    pub max_section: i32,
+    /// The surface height of every column when the chunk was marked, indexed by `z * 16 + x`.
+    /// `NaN` for columns without any ground.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub heights: Vec<f64>,
+}
+
+impl BlendingData {
+    /// Marks `chunk` with its current terrain.
+    pub fn from_chunk(chunk: &ChunkData) -> Self {
+        let min_section = section_coords::block_to_section(chunk.section.min_y);
+        let heights = (0..CHUNK_AREA)
+            .map(|i| surface_height(chunk, i % 16, i / 16).map_or(f64::NAN, f64::from))
+            .collect();
+        Self {
+            min_section,
+            max_section: min_section + chunk.section.sections.len() as i32,
+            heights,
+        }
+    }
+
+    /// Whether there is a height for every column, which is not the case for data written by
+    /// vanilla as it only keeps the heights at the border.
+    pub fn has_heights(&self) -> bool {
+        self.heights.len() == CHUNK_AREA
+    }
+}
+
+/// The highest ground block of the column, ignoring trees.
+fn surface_height(chunk: &ChunkData, x: usize, z: usize) -> Option<i32> {
+    let min_y = chunk.section.min_y;
+    let top = chunk
+        .heightmap
+        .get_height(ChunkHeightmapType::OceanFloor, x as i32, z as i32, min_y);
+    (min_y..=top).rev().find(|&y| {
+        chunk
+            .section
+            .get_block_absolute_y(x, y, z)
+            .is_some_and(|state_id| {
+                let block = Block::from_state_id(state_id);
+                ChunkHeightmapType::OceanFloor.is_opaque(state_id)
+                    && !block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_LEAVES)
+                    && !block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_LOGS)
+            })
+    })
+}
+
+/// A chunk with [`BlendingData`] next to a chunk being generated.
+pub struct BlendingNeighbour {
+    /// Where the neighbour is relative to the chunk being generated, each component is -1, 0 or 1.
+    offset: Vector2<i32>,
+    heights: Box<[f64]>,
+    min_quart_y: i32,
+    quart_height: usize,
+    /// Indexed by `(quart_x * 4 + quart_z) * quart_height + quart_y`
+    biomes: Box<[&'static Biome]>,
+}
+
+impl BlendingNeighbour {
+    /// Returns `None` if `chunk` is not marked for blending.
+    pub fn new(offset: Vector2<i32>, chunk: &ChunkData) -> Option<Self> {
+        let data = chunk.blending_data.as_ref()?;
+        let quart_height = chunk.section.sections.len() * 4;
+        let mut biomes = Vec::with_capacity(16 * quart_height);
+        for quart_x in 0..4 {
+            for quart_z in 0..4 {
+                for quart_y in 0..quart_height {
+                    let biome = chunk
+                        .section
+                        .get_noise_biome(quart_y / 4, quart_x, quart_y % 4, quart_z)
+                        .and_then(Biome::from_id)
+                        .unwrap_or(&Biome::PLAINS);
+                    biomes.push(biome);
+                }
+            }
+        }
+        Some(Self {
+            offset,
+            heights: data.heights.clone().into_boxed_slice(),
+            min_quart_y: biome_coords::from_block(chunk.section.min_y),
+            quart_height,
+            biomes: biomes.into_boxed_slice(),
+        })
+    }
+
+    /// The height of the column of this neighbour closest to the column at `x`, `z` of the chunk
+    /// being generated, and how many blocks away it is.
+    pub fn height_near(&self, x: i32, z: i32) -> Option<(f64, f64)> {
+        let (distance_x, neighbour_x) = closest_on_axis(self.offset.x, x, 16);
+        let (distance_z, neighbour_z) = closest_on_axis(self.offset.y, z, 16);
+        let height = *self
+            .heights
+            .get((neighbour_z * 16 + neighbour_x) as usize)?;
+        let distance = f64::from(distance_x).hypot(f64::from(distance_z));
+        Some((height, distance))
+    }
+
+    /// The biome of this neighbour closest to the given quart of the chunk being generated, if it
+    /// is close enough to be taken on.
+    pub fn biome_near(&self, quart_x: i32, quart_y: i32, quart_z: i32) -> Option<&'static Biome> {
+        let center_x = biome_coords::to_block(quart_x) + 2;
+        let center_z = biome_coords::to_block(quart_z) + 2;
+        let (distance_x, _) = closest_on_axis(self.offset.x, center_x, 16);
+        let (distance_z, _) = closest_on_axis(self.offset.y, center_z, 16);
+        if blend_alpha(f64::from(distance_x).hypot(f64::from(distance_z))) < 0.5 {
+            return None;
+        }
+
+        let (_, neighbour_x) = closest_on_axis(self.offset.x, quart_x, 4);
+        let (_, neighbour_z) = closest_on_axis(self.offset.y, quart_z, 4);
+        let quart_y = usize::try_from(quart_y - self.min_quart_y).ok()?;
+        if quart_y >= self.quart_height {
+            return None;
+        }
+        let index = (neighbour_x * 4 + neighbour_z) as usize * self.quart_height + quart_y;
+        self.biomes.get(index).copied()
+    }
+}
+
+/// For a coordinate `local` inside the chunk being generated, returns how far it is from the
+/// neighbour at `offset` along one axis and the closest coordinate inside that neighbour.
+fn closest_on_axis(offset: i32, local: i32, size: i32) -> (i32, i32) {
+    match offset.signum() {
+        -1 => (local + 1, size - 1),
+        1 => (size - local, 0),
+        _ => (0, local),
+    }
+}
+
+/// How strongly a column `distance` blocks away from a blended neighbour takes on its height,
+/// from almost 1 right at the border down to 0 at [`BLEND_DISTANCE`].
+pub fn blend_alpha(distance: f64) -> f64 {
+    let alpha = (1.0 - distance / f64::from(BLEND_DISTANCE)).clamp(0.0, 1.0);
+    // Smoothstep, so the blended terrain does not bend sharply where blending starts
+    alpha * alpha * (3.0 - 2.0 * alpha)
+}
+
+/// The height the surface of a column at `height` is moved to, given the heights of nearby
+/// columns of blended neighbours and their distances as returned by
+/// [`BlendingNeighbour::height_near`].
+pub fn blend_height(height: i32, neighbours: impl IntoIterator<Item = (f64, f64)>) -> i32 {
+    let mut weighted_heights = 0.0;
+    let mut weights = 0.0;
+    let mut max_alpha = 0.0_f64;
+    for (neighbour_height, distance) in neighbours {
+        let alpha = blend_alpha(distance);
+        if alpha <= 0.0 || neighbour_height.is_nan() {
+            continue;
+        }
+        weighted_heights += alpha * neighbour_height;
+        weights += alpha;
+        max_alpha = max_alpha.max(alpha);
+    }
+    if weights <= 0.0 {
+        return height;
+    }
+
+    let height = f64::from(height);
+    let target = weighted_heights / weights;
+    (height + (target - height) * max_alpha).round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alpha_falls_off_with_distance() {
+        assert!(blend_alpha(1.0) > 0.9);
+        assert!(blend_alpha(2.0) > blend_alpha(6.0));
+        assert_eq!(blend_alpha(f64::from(BLEND_DISTANCE)), 0.0);
+        assert_eq!(blend_alpha(20.0), 0.0);
+    }
+
+    #[test]
+    fn height_moves_towards_neighbours() {
+        assert_eq!(blend_height(64, []), 64);
+        assert_eq!(blend_height(64, [(100.0, 20.0)]), 64);
+        assert_eq!(blend_height(64, [(f64::NAN, 1.0)]), 64);
+
+        let next_to_border = blend_height(64, [(100.0, 1.0)]);
+        let further_in = blend_height(64, [(100.0, 5.0)]);
+        assert!(next_to_border > further_in);
+        assert!(next_to_border > 95 && next_to_border <= 100);
+        assert!(further_in > 64);
+    }
+
+    #[test]
+    fn closest_column_of_neighbour() {
+        assert_eq!(closest_on_axis(-1, 0, 16), (1, 15));
+        assert_eq!(closest_on_axis(1, 15, 16), (1, 0));
+        assert_eq!(closest_on_axis(0, 7, 16), (0, 7));
+    }
+}
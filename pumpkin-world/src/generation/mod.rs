@@ -2,7 +2,7 @@
 
 pub mod aquifer_sampler;
 mod biome;
-mod blender;
+pub mod blender;
 mod block_predicate;
 mod block_state_provider;
 pub mod carver;
@@ -24,6 +24,7 @@ use crate::generation::structure::placement::StructurePlacementCalculator;
 use crate::generation::structure::structures::StructurePosition;
 use crate::generation::structure::{STRUCTURE_SETS, STRUCTURES, Structure, StructureType};
 use crate::level::ChunkEntry;
+use crate::poi::ChunkPoiStorage;
 use crate::{
     BlockStateId,
     biome::{BiomeSupplier, MultiNoiseBiomeSupplier, end::TheEndBiomeSupplier},
@@ -39,6 +40,7 @@ use super::{
     GlobalRandomConfig,
     aquifer_sampler::{FluidLevel, FluidLevelSamplerImpl},
     biome_coords,
+    blender::{self, BlendingNeighbour},
     chunk_noise::{CHUNK_DIM, ChunkNoiseGenerator, LAVA_BLOCK, WATER_BLOCK},
     feature::placed_features::PLACED_FEATURES,
     noise::router::{
@@ -676,6 +678,108 @@ impl ProtoChunk {
         }
     }
 
+    /// Moves the surface near neighbours marked for blending towards their height and gives the
+    /// border their biomes, so the new terrain meets theirs without a cliff.
+    pub fn blend_with(&self, neighbours: &[BlendingNeighbour], settings: &GenerationSettings) {
+        if neighbours.is_empty() {
+            return;
+        }
+
+        let bottom_y = self.bottom_y() as i32;
+        let top_y = bottom_y + self.height() as i32 - 1;
+        for x in 0..CHUNK_DIM as i32 {
+            for z in 0..CHUNK_DIM as i32 {
+                let height = self.ocean_floor_height_exclusive(&Vector2::new(x, z)) - 1;
+                if height < bottom_y {
+                    continue;
+                }
+                let target = blender::blend_height(
+                    height,
+                    neighbours
+                        .iter()
+                        .filter_map(|neighbour| neighbour.height_near(x, z)),
+                )
+                .clamp(bottom_y, top_y);
+                if target != height {
+                    self.move_surface(x, z, height, target, settings);
+                }
+            }
+        }
+
+        let bottom_quart = biome_coords::from_block(bottom_y);
+        let quart_height = biome_coords::from_block(self.height() as i32);
+        let mut biome_map = self.flat_biome_map.lock();
+        for quart_x in 0..biome_coords::from_block(CHUNK_DIM as i32) {
+            for quart_z in 0..biome_coords::from_block(CHUNK_DIM as i32) {
+                for quart_y in 0..quart_height {
+                    let Some(biome) = neighbours.iter().find_map(|neighbour| {
+                        neighbour.biome_near(quart_x, bottom_quart + quart_y, quart_z)
+                    }) else {
+                        continue;
+                    };
+                    let index = self
+                        .local_biome_pos_to_biome_index(&Vector3::new(quart_x, quart_y, quart_z));
+                    biome_map[index] = biome;
+                }
+            }
+        }
+    }
+
+    /// Moves the surface block of a column from `from` to `to`, filling the column up to it with
+    /// the ground below the old surface or clearing everything above it.
+    fn move_surface(&self, x: i32, z: i32, from: i32, to: i32, settings: &GenerationSettings) {
+        let surface = self.get_block_state(&Vector3::new(x, from, z)).to_state();
+        if to > from {
+            let below = self
+                .get_block_state(&Vector3::new(x, from - 1, z))
+                .to_state();
+            let filler = if blocks_movement(below) {
+                below
+            } else {
+                self.default_block
+            };
+            for y in from..to {
+                self.set_block_state(&Vector3::new(x, y, z), filler);
+            }
+        } else {
+            let fluid = settings.default_fluid.get_state();
+            for y in to + 1..=from {
+                let state = if y < settings.sea_level {
+                    fluid
+                } else {
+                    Block::AIR.default_state
+                };
+                self.set_block_state(&Vector3::new(x, y, z), state);
+            }
+        }
+        self.set_block_state(&Vector3::new(x, to, z), surface);
+
+        if to < from {
+            // Setting blocks only ever raises the heightmaps
+            self.recalculate_height_maps(x, z);
+        }
+    }
+
+    fn recalculate_height_maps(&self, x: i32, z: i32) {
+        let index = Self::local_position_to_height_map_index(x & 15, z & 15);
+        for height_map in [
+            &self.flat_surface_height_map,
+            &self.flat_ocean_floor_height_map,
+            &self.flat_motion_blocking_height_map,
+            &self.flat_motion_blocking_no_leaves_height_map,
+        ] {
+            height_map.lock()[index] = i16::MIN;
+        }
+        let bottom_y = self.bottom_y() as i32;
+        for y in bottom_y..bottom_y + self.height() as i32 {
+            let pos = Vector3::new(x, y, z);
+            let state = self.get_block_state(&pos).to_state();
+            if !state.is_air() {
+                self.set_block_state(&pos, state);
+            }
+        }
+    }
+
     /// This generates "Structure Pieces" and "Features" also known as decorations, which include things like trees, grass, ores, and more.
     /// Essentially, it encompasses everything above the surface or underground. It's crucial that this step is executed after biomes are generated,
     /// as the decoration directly depends on the biome. Similarly, running this after the surface is built is logical, as it often involves checking block types.
@@ -981,12 +1085,15 @@ impl PendingChunk {
                             .await;
                         match chunk {
                             ChunkEntry::Pending(chunk) => Some(chunk),
-                            ChunkEntry::Full(_chunk) => {
+                            ChunkEntry::Full(chunk) => {
                                 // Dependency is already fully generated. In most cases this is fine,
                                 // but if we're at an early stage (Surface/Noise) and the neighbor is Full,
                                 // it indicates a race condition bug where multiple PendingChunk instances
-                                // were created for the same coordinate.
-                                if current_stage == ChunkStage::Surface || current_stage == ChunkStage::Noise {
+                                // were created for the same coordinate. Chunks marked for blending were
+                                // not generated here, so they are expected next to new ones.
+                                if (current_stage == ChunkStage::Surface || current_stage == ChunkStage::Noise)
+                                    && chunk.read().await.blending_data.is_none()
+                                {
                                     panic!(
                                         "Chunk Vector2 {{ x: {}, y: {} }} found neighbor Vector2 {{ x: {}, y: {} }} \
                                         is Full while at stage {:?}, required: {:?}. This indicates a race condition \
@@ -1167,6 +1274,7 @@ impl PendingChunk {
                 }
                 ChunkStage::Surface => {
                     // Generate features and structures
+                    let blending_neighbours = self.blending_neighbours(level).await;
                     let proto_chunk = self.proto_chunk.clone();
                     let level = level.clone();
                     let block_registry = generation_context.block_registry.clone();
@@ -1174,6 +1282,8 @@ impl PendingChunk {
                     let (tx, rx) = tokio::sync::oneshot::channel::<()>();
                     generation_context.thread_pool.spawn(move || {
                         let block_registry: &dyn BlockRegistryExt = block_registry.as_ref();
+                        proto_chunk
+                            .blend_with(&blending_neighbours, generation_context_clone.settings);
                         proto_chunk.generate_features_and_structure(
                             &level,
                             block_registry,
@@ -1194,6 +1304,29 @@ impl PendingChunk {
         }
     }
 
+    /// The fully generated neighbours marked for blending, see [`BlendingNeighbour`].
+    async fn blending_neighbours(&self, level: &Level) -> Vec<BlendingNeighbour> {
+        let mut neighbours = Vec::new();
+        for dx in -1..=1 {
+            for dz in -1..=1 {
+                let offset = Vector2::new(dx, dz);
+                if offset == Vector2::new(0, 0) {
+                    continue;
+                }
+                let entry = level
+                    .loaded_chunks
+                    .get(&(self.position + offset))
+                    .map(|entry| entry.clone());
+                if let Some(ChunkEntry::Full(chunk)) = entry
+                    && let Some(neighbour) = BlendingNeighbour::new(offset, &*chunk.read().await)
+                {
+                    neighbours.push(neighbour);
+                }
+            }
+        }
+        neighbours
+    }
+
     // Helper to get dependencies for the *next* stage transition.
     fn get_dependants(&self, current_stage: ChunkStage) -> Vec<(Vector2<i32>, ChunkStage)> {
         let mut deps = Vec::new();
@@ -1300,6 +1433,7 @@ impl PendingChunk {
                 block_light: (0..sections.sections.len())
                     .map(|_| LightContainer::new_empty(15))
                     .collect(),
+                is_lit: false,
             },
             section: sections,
             heightmap: Default::default(),
@@ -1308,10 +1442,15 @@ impl PendingChunk {
             block_ticks: Default::default(),
             fluid_ticks: Default::default(),
             block_entities: Default::default(),
+            block_entity_nbt: Vec::new(),
             status: status.stage.into(),
+            poi: Default::default(),
+            blending_data: None,
+            last_update: 0,
         };
 
         chunk.heightmap = chunk.calculate_heightmap();
+        chunk.poi = ChunkPoiStorage::scan(&chunk.section, chunk.position);
         chunk
     }
 }
@@ -17,6 +17,7 @@ use crate::{
 
 pub mod placement;
 pub mod structures;
+pub mod template;
 
 #[derive(Deserialize)]
 pub struct StructureSet {
@@ -0,0 +1,449 @@
+//! Structure templates: cuboid snapshots of blocks in the vanilla structure file format.
+//!
+//! A [`StructureTemplate`] can be captured from a world, saved to and loaded from `.nbt` files
+//! (the same format used by structure blocks), and placed back with a [`TemplatePlacement`]
+//! which rotates, mirrors and optionally skips air.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use pumpkin_data::Block;
+use pumpkin_nbt::{
+    compound::NbtCompound,
+    nbt_compress::{from_gzip_bytes, to_gzip_bytes},
+};
+use pumpkin_util::math::vector3::Vector3;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{BlockStateId, block::BlockStateCodec, chunk::format::anvil::WORLD_DATA_VERSION};
+
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("Failed to access the template file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to read or write the template NBT: {0}")]
+    Nbt(#[from] pumpkin_nbt::Error),
+    #[error("Block at {0:?} refers to palette entry {1}, which does not exist")]
+    InvalidPaletteIndex(Vector3<i32>, i32),
+}
+
+/// A quarter turn count around the Y axis, clockwise when seen from above.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    CounterClockwise90,
+}
+
+impl BlockRotation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "none" => Self::None,
+            "clockwise_90" => Self::Clockwise90,
+            "180" => Self::Clockwise180,
+            "counterclockwise_90" => Self::CounterClockwise90,
+            _ => return None,
+        })
+    }
+
+    const fn quarter_turns(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Clockwise90 => 1,
+            Self::Clockwise180 => 2,
+            Self::CounterClockwise90 => 3,
+        }
+    }
+
+    #[must_use]
+    pub const fn transform_pos(self, pos: Vector3<i32>) -> Vector3<i32> {
+        match self {
+            Self::None => pos,
+            Self::Clockwise90 => Vector3::new(-pos.z, pos.y, pos.x),
+            Self::Clockwise180 => Vector3::new(-pos.x, pos.y, -pos.z),
+            Self::CounterClockwise90 => Vector3::new(pos.z, pos.y, -pos.x),
+        }
+    }
+}
+
+/// Flips a template along one horizontal axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockMirror {
+    #[default]
+    None,
+    /// Flips the Z axis, so north becomes south.
+    LeftRight,
+    /// Flips the X axis, so east becomes west.
+    FrontBack,
+}
+
+impl BlockMirror {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "none" => Self::None,
+            "left_right" => Self::LeftRight,
+            "front_back" => Self::FrontBack,
+            _ => return None,
+        })
+    }
+
+    #[must_use]
+    pub const fn transform_pos(self, pos: Vector3<i32>) -> Vector3<i32> {
+        match self {
+            Self::None => pos,
+            Self::LeftRight => Vector3::new(pos.x, pos.y, -pos.z),
+            Self::FrontBack => Vector3::new(-pos.x, pos.y, pos.z),
+        }
+    }
+}
+
+/// How a template is placed into a world.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TemplatePlacement {
+    pub rotation: BlockRotation,
+    pub mirror: BlockMirror,
+    /// Keeps whatever is in the world where the template has air.
+    pub ignore_air: bool,
+}
+
+impl TemplatePlacement {
+    /// The offset from the placement origin at which a block of the template ends up. The template
+    /// is mirrored first and then rotated around its origin corner.
+    #[must_use]
+    pub const fn transform_pos(&self, pos: Vector3<i32>) -> Vector3<i32> {
+        self.rotation.transform_pos(self.mirror.transform_pos(pos))
+    }
+
+    /// Turns and flips the properties of a block state to match the placed template, e.g. the
+    /// facing of stairs or the connections of fences.
+    #[must_use]
+    pub fn transform_state(&self, state_id: BlockStateId) -> BlockStateId {
+        if self.rotation == BlockRotation::None && self.mirror == BlockMirror::None {
+            return state_id;
+        }
+        let block = Block::from_state_id(state_id);
+        let Some(properties) = block.properties(state_id) else {
+            return state_id;
+        };
+        let properties = properties.to_props();
+        let facing = properties
+            .iter()
+            .find(|(name, _)| name == "facing")
+            .map(|(_, value)| value.as_str());
+
+        let transformed: Vec<(String, String)> = properties
+            .iter()
+            .map(|(name, value)| {
+                let name = self
+                    .transform_direction(name)
+                    .unwrap_or(name.as_str())
+                    .to_string();
+                (name.clone(), self.transform_value(&name, value, facing))
+            })
+            .collect();
+        let transformed: Vec<(&str, &str)> = transformed
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        block
+            .try_from_properties(&transformed)
+            .map_or(state_id, |properties| properties.to_state_id(block))
+    }
+
+    /// Maps a horizontal direction name, returning `None` for anything else.
+    fn transform_direction(&self, direction: &str) -> Option<&'static str> {
+        const HORIZONTAL: [&str; 4] = ["north", "east", "south", "west"];
+        let mut index = HORIZONTAL.iter().position(|name| *name == direction)?;
+        index = match self.mirror {
+            BlockMirror::LeftRight if index % 2 == 0 => (index + 2) % 4,
+            BlockMirror::FrontBack if index % 2 == 1 => (index + 2) % 4,
+            _ => index,
+        };
+        Some(HORIZONTAL[(index + self.rotation.quarter_turns() as usize) % 4])
+    }
+
+    fn transform_value(&self, name: &str, value: &str, facing: Option<&str>) -> String {
+        let quarter_turns = self.rotation.quarter_turns();
+        match name {
+            "facing" => self
+                .transform_direction(value)
+                .map_or_else(|| value.to_string(), str::to_string),
+            "axis" if quarter_turns % 2 == 1 => match value {
+                "x" => "z".to_string(),
+                "z" => "x".to_string(),
+                _ => value.to_string(),
+            },
+            "rotation" => {
+                let Ok(mut rotation) = value.parse::<u8>() else {
+                    return value.to_string();
+                };
+                rotation = match self.mirror {
+                    BlockMirror::None => rotation,
+                    BlockMirror::LeftRight => (16 - rotation) % 16,
+                    BlockMirror::FrontBack => (24 - rotation) % 16,
+                };
+                ((rotation + quarter_turns * 4) % 16).to_string()
+            }
+            "hinge" if self.mirror != BlockMirror::None => match value {
+                "left" => "right".to_string(),
+                "right" => "left".to_string(),
+                _ => value.to_string(),
+            },
+            "shape" => self.transform_shape(value, facing),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Stair shapes swap sides when the stairs are flipped front to back, rail shapes are made up
+    /// of the directions they connect.
+    fn transform_shape(&self, shape: &str, facing: Option<&str>) -> String {
+        if let Some(side) = shape
+            .strip_prefix("inner_")
+            .or_else(|| shape.strip_prefix("outer_"))
+        {
+            let flipped = match (self.mirror, facing) {
+                (BlockMirror::LeftRight, Some("north" | "south"))
+                | (BlockMirror::FrontBack, Some("east" | "west")) => true,
+                _ => false,
+            };
+            if !flipped {
+                return shape.to_string();
+            }
+            let kind = &shape[..shape.len() - side.len()];
+            return match side {
+                "left" => format!("{kind}right"),
+                "right" => format!("{kind}left"),
+                _ => shape.to_string(),
+            };
+        }
+        if let Some(direction) = shape.strip_prefix("ascending_") {
+            return self
+                .transform_direction(direction)
+                .map_or_else(|| shape.to_string(), |d| format!("ascending_{d}"));
+        }
+        if let Some((first, second)) = shape.split_once('_')
+            && let (Some(first), Some(second)) = (
+                self.transform_direction(first),
+                self.transform_direction(second),
+            )
+        {
+            // Rail shapes always name north/south first, then east/west
+            const ORDER: [&str; 4] = ["north", "south", "east", "west"];
+            let index = |d: &str| ORDER.iter().position(|o| *o == d);
+            return if index(first) <= index(second) {
+                format!("{first}_{second}")
+            } else {
+                format!("{second}_{first}")
+            };
+        }
+        shape.to_string()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateBlock {
+    /// Position relative to the template origin, the lowest corner.
+    pub pos: Vector3<i32>,
+    pub state_id: BlockStateId,
+    /// Block entity data without its position.
+    pub nbt: Option<NbtCompound>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StructureTemplate {
+    pub size: Vector3<i32>,
+    pub blocks: Vec<TemplateBlock>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StructureTemplateNbt {
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+    size: Vec<i32>,
+    palette: Vec<BlockStateCodec>,
+    blocks: Vec<TemplateBlockNbt>,
+    #[serde(default)]
+    entities: Vec<NbtCompound>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TemplateBlockNbt {
+    pos: Vec<i32>,
+    state: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nbt: Option<NbtCompound>,
+}
+
+fn to_vec(pos: Vector3<i32>) -> Vec<i32> {
+    vec![pos.x, pos.y, pos.z]
+}
+
+fn from_vec(pos: &[i32]) -> Vector3<i32> {
+    match pos {
+        [x, y, z] => Vector3::new(*x, *y, *z),
+        _ => Vector3::new(0, 0, 0),
+    }
+}
+
+impl StructureTemplate {
+    pub fn load(path: &Path) -> Result<Self, TemplateError> {
+        let file = File::open(path)?;
+        let nbt: StructureTemplateNbt = from_gzip_bytes(BufReader::new(file))?;
+        Self::from_nbt(nbt)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), TemplateError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        to_gzip_bytes(&self.to_nbt(), BufWriter::new(file))?;
+        Ok(())
+    }
+
+    fn from_nbt(nbt: StructureTemplateNbt) -> Result<Self, TemplateError> {
+        let palette: Vec<BlockStateId> = nbt
+            .palette
+            .iter()
+            .map(BlockStateCodec::get_state_id)
+            .collect();
+        let blocks = nbt
+            .blocks
+            .into_iter()
+            .map(|block| {
+                let pos = from_vec(&block.pos);
+                let state_id = usize::try_from(block.state)
+                    .ok()
+                    .and_then(|index| palette.get(index))
+                    .ok_or(TemplateError::InvalidPaletteIndex(pos, block.state))?;
+                Ok(TemplateBlock {
+                    pos,
+                    state_id: *state_id,
+                    nbt: block.nbt,
+                })
+            })
+            .collect::<Result<_, TemplateError>>()?;
+        Ok(Self {
+            size: from_vec(&nbt.size),
+            blocks,
+        })
+    }
+
+    fn to_nbt(&self) -> StructureTemplateNbt {
+        let mut palette = Vec::new();
+        let mut palette_indices = HashMap::new();
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| {
+                let state = *palette_indices.entry(block.state_id).or_insert_with(|| {
+                    let block_type = Block::from_state_id(block.state_id);
+                    palette.push(BlockStateCodec {
+                        name: block_type,
+                        properties: block_type
+                            .properties(block.state_id)
+                            .map(|p| p.to_props().into_iter().collect()),
+                    });
+                    palette.len() as i32 - 1
+                });
+                TemplateBlockNbt {
+                    pos: to_vec(block.pos),
+                    state,
+                    nbt: block.nbt.clone(),
+                }
+            })
+            .collect();
+        StructureTemplateNbt {
+            data_version: WORLD_DATA_VERSION,
+            size: to_vec(self.size),
+            palette,
+            blocks,
+            entities: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{
+        Block,
+        block_properties::{
+            BlockProperties, HorizontalFacing, OakStairsLikeProperties, StairShape,
+        },
+    };
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{BlockMirror, BlockRotation, StructureTemplate, TemplateBlock, TemplatePlacement};
+
+    fn stairs(facing: HorizontalFacing, shape: StairShape) -> u16 {
+        let mut props = OakStairsLikeProperties::default(&Block::OAK_STAIRS);
+        props.facing = facing;
+        props.shape = shape;
+        props.to_state_id(&Block::OAK_STAIRS)
+    }
+
+    #[test]
+    fn rotates_positions_clockwise() {
+        let placement = TemplatePlacement {
+            rotation: BlockRotation::Clockwise90,
+            ..Default::default()
+        };
+        assert_eq!(
+            placement.transform_pos(Vector3::new(2, 1, 0)),
+            Vector3::new(0, 1, 2)
+        );
+    }
+
+    #[test]
+    fn rotates_and_mirrors_states() {
+        let placement = TemplatePlacement {
+            rotation: BlockRotation::Clockwise90,
+            ..Default::default()
+        };
+        assert_eq!(
+            placement.transform_state(stairs(HorizontalFacing::North, StairShape::Straight)),
+            stairs(HorizontalFacing::East, StairShape::Straight)
+        );
+
+        let placement = TemplatePlacement {
+            mirror: BlockMirror::LeftRight,
+            ..Default::default()
+        };
+        assert_eq!(
+            placement.transform_state(stairs(HorizontalFacing::North, StairShape::InnerLeft)),
+            stairs(HorizontalFacing::South, StairShape::InnerRight)
+        );
+        assert_eq!(
+            placement.transform_state(Block::STONE.default_state.id),
+            Block::STONE.default_state.id
+        );
+    }
+
+    #[test]
+    fn round_trips_through_nbt() {
+        let template = StructureTemplate {
+            size: Vector3::new(2, 1, 1),
+            blocks: vec![
+                TemplateBlock {
+                    pos: Vector3::new(0, 0, 0),
+                    state_id: stairs(HorizontalFacing::West, StairShape::OuterRight),
+                    nbt: None,
+                },
+                TemplateBlock {
+                    pos: Vector3::new(1, 0, 0),
+                    state_id: Block::STONE.default_state.id,
+                    nbt: None,
+                },
+            ],
+        };
+        let loaded = StructureTemplate::from_nbt(template.to_nbt()).unwrap();
+        assert_eq!(loaded, template);
+    }
+}
@@ -1,14 +1,15 @@
 use crate::item::ItemStack;
 use async_trait::async_trait;
+use pumpkin_data::BlockDirection;
 use pumpkin_data::item::Item;
 use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::lock_order::OrderedMutex;
 use std::any::Any;
 use std::{
-    fmt::Debug,
+    fmt::{self, Debug},
     hash::{Hash, Hasher},
-    sync::Arc,
+    sync::{Arc, Weak},
 };
-use tokio::sync::{Mutex, OwnedMutexGuard};
 
 // Inventory.java
 #[async_trait]
@@ -17,7 +18,7 @@ pub trait Inventory: Send + Sync + Debug + Clearable {
 
     async fn is_empty(&self) -> bool;
 
-    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>>;
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>>;
 
     async fn remove_stack(&self, slot: usize) -> ItemStack;
 
@@ -29,12 +30,27 @@ pub trait Inventory: Send + Sync + Debug + Clearable {
 
     async fn set_stack(&self, slot: usize, stack: ItemStack);
 
+    /// Called whenever the contents change. Implementations with [`Self::listeners`] should
+    /// notify them from here.
     fn mark_dirty(&self) {}
 
+    /// The listeners told about changes to this inventory, `None` if it doesn't support any.
+    fn listeners(&self) -> Option<&InventoryListeners> {
+        None
+    }
+
+    /// Subscribes `listener` to changes of this inventory until it is dropped. Returns `false`
+    /// if this inventory doesn't support listeners.
+    fn add_listener(&self, listener: Weak<dyn InventoryListener>) -> bool {
+        self.listeners()
+            .map(|listeners| listeners.add(listener))
+            .is_some()
+    }
+
     async fn write_data(
         &self,
         nbt: &mut pumpkin_nbt::compound::NbtCompound,
-        stacks: &[Arc<Mutex<ItemStack>>],
+        stacks: &[Arc<OrderedMutex<ItemStack>>],
         include_empty: bool,
     ) {
         let mut slots = Vec::new();
@@ -59,7 +75,7 @@ pub trait Inventory: Send + Sync + Debug + Clearable {
     fn read_data(
         &self,
         nbt: &pumpkin_nbt::compound::NbtCompound,
-        stacks: &[Arc<Mutex<ItemStack>>],
+        stacks: &[Arc<OrderedMutex<ItemStack>>],
     ) {
         if let Some(inventory_list) = nbt.get_list("Items") {
             for tag in inventory_list {
@@ -116,12 +132,12 @@ pub trait Inventory: Send + Sync + Debug + Clearable {
 
     async fn contains_any_predicate(
         &self,
-        predicate: &(dyn Fn(OwnedMutexGuard<ItemStack>) -> bool + Sync),
+        predicate: &(dyn Fn(&ItemStack) -> bool + Sync),
     ) -> bool {
         for i in 0..self.size() {
             let slot = self.get_stack(i).await;
-            let stack = slot.lock_owned().await;
-            if predicate(stack) {
+            let stack = slot.lock().await;
+            if predicate(&stack) {
                 return true;
             }
         }
@@ -136,9 +152,81 @@ pub trait Inventory: Send + Sync + Debug + Clearable {
 
     // TODO: canPlayerUse
 
+    /// The inventory with per-face slot rules, for containers whose slots hoppers and droppers
+    /// may only reach from certain sides.
+    fn as_sided(&self) -> Option<&dyn SidedInventory> {
+        None
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
+// SidedInventory.java
+#[async_trait]
+pub trait SidedInventory: Inventory {
+    /// The slots automation can reach through the given face of the block.
+    fn get_available_slots(&self, side: BlockDirection) -> &'static [usize];
+
+    /// Whether a stack may be inserted into the slot, through `side` if it comes from a block.
+    async fn can_insert(
+        &self,
+        slot: usize,
+        stack: &ItemStack,
+        _side: Option<BlockDirection>,
+    ) -> bool {
+        self.is_valid_slot_for(slot, stack)
+    }
+
+    /// Whether a stack may be taken out of the slot through `side`.
+    async fn can_extract(&self, _slot: usize, _stack: &ItemStack, _side: BlockDirection) -> bool {
+        true
+    }
+
+    /// Called once automation has moved an item in or out, for containers that act on the world
+    /// when that happens, like the composter composting what it was given.
+    async fn on_items_moved(&self) {}
+}
+
+// InventoryChangedListener.java
+pub trait InventoryListener: Send + Sync {
+    /// Called after the contents of `inventory` changed. This runs while the inventory is being
+    /// changed, so anything async should be spawned rather than awaited.
+    fn on_inventory_changed(&self, inventory: &dyn Inventory);
+}
+
+/// The listeners subscribed to an inventory. They are held weakly, so a listener that is dropped
+/// unsubscribes itself.
+#[derive(Default)]
+pub struct InventoryListeners {
+    listeners: parking_lot::Mutex<Vec<Weak<dyn InventoryListener>>>,
+}
+
+impl InventoryListeners {
+    pub fn add(&self, listener: Weak<dyn InventoryListener>) {
+        self.listeners.lock().push(listener);
+    }
+
+    pub fn notify(&self, inventory: &dyn Inventory) {
+        let listeners: Vec<_> = {
+            let mut listeners = self.listeners.lock();
+            listeners.retain(|listener| listener.strong_count() > 0);
+            listeners.iter().filter_map(Weak::upgrade).collect()
+        };
+        // The lock is released first so listeners can subscribe others
+        for listener in listeners {
+            listener.on_inventory_changed(inventory);
+        }
+    }
+}
+
+impl Debug for InventoryListeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InventoryListeners")
+            .field("count", &self.listeners.lock().len())
+            .finish()
+    }
+}
+
 #[async_trait]
 pub trait Clearable {
     async fn clear(&self);
@@ -160,3 +248,45 @@ impl Hash for ComparableInventory {
         ptr.hash(state);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        Arc, Weak,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::{Inventory, InventoryListener};
+    use crate::block::entities::chest::ChestBlockEntity;
+
+    #[derive(Default)]
+    struct CountingListener(AtomicU32);
+
+    impl InventoryListener for CountingListener {
+        fn on_inventory_changed(&self, inventory: &dyn Inventory) {
+            assert_eq!(inventory.size(), 27);
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn listeners_are_notified_until_dropped() {
+        let chest = ChestBlockEntity::new(BlockPos::new(0, 0, 0));
+        let listener = Arc::new(CountingListener::default());
+        let weak: Weak<dyn InventoryListener> = Arc::downgrade(&listener);
+        assert!(chest.add_listener(weak));
+
+        chest.mark_dirty();
+        chest.mark_dirty();
+        assert_eq!(listener.0.load(Ordering::Relaxed), 2);
+
+        drop(listener);
+        chest.mark_dirty();
+        assert_eq!(
+            format!("{:?}", chest.listeners),
+            "InventoryListeners { count: 0 }"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use pumpkin_util::lock_order::OrderedMutex;
 
 use crate::item::ItemStack;
 
@@ -9,8 +9,21 @@ mod inventory;
 
 pub use inventory::*;
 
+/// The lock class of inventory slots, see [`pumpkin_util::lock_order::LOCK_ORDER`].
+pub const SLOT_LOCK_CLASS: &str = "inventory.slot";
+
+/// A new inventory slot holding `stack`.
+#[must_use]
+pub fn new_slot(stack: ItemStack) -> Arc<OrderedMutex<ItemStack>> {
+    Arc::new(OrderedMutex::new(SLOT_LOCK_CLASS, stack))
+}
+
 // These are some utility functions found in Inventories.java
-pub async fn split_stack(stacks: &[Arc<Mutex<ItemStack>>], slot: usize, amount: u8) -> ItemStack {
+pub async fn split_stack(
+    stacks: &[Arc<OrderedMutex<ItemStack>>],
+    slot: usize,
+    amount: u8,
+) -> ItemStack {
     let mut stack = stacks[slot].lock().await;
     if slot < stacks.len() && !stack.is_empty() && amount > 0 {
         stack.split(amount)
@@ -18,3 +31,27 @@ pub async fn split_stack(stacks: &[Arc<Mutex<ItemStack>>], slot: usize, amount:
         ItemStack::EMPTY.clone()
     }
 }
+
+/// The signal a comparator reads from a container, which grows with how full its slots are.
+pub async fn calculate_comparator_output(inventory: &dyn Inventory) -> u8 {
+    if inventory.size() == 0 {
+        return 0;
+    }
+    let mut fullness = 0.0;
+    for slot in 0..inventory.size() {
+        let stack = inventory.get_stack(slot).await;
+        let stack = stack.lock().await;
+        if !stack.is_empty() {
+            let max_count = stack
+                .get_max_stack_size()
+                .min(inventory.get_max_count_per_stack());
+            fullness += f32::from(stack.item_count) / f32::from(max_count);
+        }
+    }
+    fullness /= inventory.size() as f32;
+    if fullness > 0.0 {
+        (fullness * 14.0).floor() as u8 + 1
+    } else {
+        0
+    }
+}
@@ -1,17 +1,23 @@
 use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::data_component::DataComponent::Enchantments;
 use pumpkin_data::data_component_impl::{
-    BlocksAttacksImpl, ConsumableImpl, DataComponentImpl, EnchantmentsImpl, IDSet,
-    MaxStackSizeImpl, ToolImpl, get, get_mut, read_data,
+    BlocksAttacksImpl, ConsumableImpl, CustomDataImpl, DamageImpl, DataComponentImpl,
+    EnchantmentsImpl, EquipmentSlot, EquippableImpl, IDSet, MaxDamageImpl, MaxStackSizeImpl,
+    RawComponentImpl, ToolImpl, get, read_data,
 };
 use pumpkin_data::item::Item;
 use pumpkin_data::recipes::RecipeResultStruct;
 use pumpkin_data::tag::Taggable;
 use pumpkin_data::{Block, Enchantment};
 use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_nbt::{Nbt, from_bytes_unnamed, to_bytes_unnamed};
 use pumpkin_util::GameMode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::cmp::{max, min};
+use std::io::Cursor;
 
 mod categories;
 
@@ -62,11 +68,10 @@ impl ItemStack {
         let to_get_id = &T::get_enum();
         for (id, component) in &self.patch {
             if id == to_get_id {
-                return if let Some(component) = component {
-                    Some(get::<T>(component.as_ref()))
-                } else {
-                    None
-                };
+                // Raw components can't be read as `T`
+                return component
+                    .as_ref()
+                    .and_then(|component| component.as_any().downcast_ref::<T>());
             }
         }
         for (id, component) in self.item.components {
@@ -80,16 +85,26 @@ impl ItemStack {
         let to_get_id = &T::get_enum();
         for (id, component) in self.patch.iter_mut() {
             if id == to_get_id {
-                return if let Some(component) = component {
-                    Some(get_mut::<T>(component.as_mut()))
-                } else {
-                    None
-                };
+                return component
+                    .as_mut()
+                    .and_then(|component| component.as_mut_any().downcast_mut::<T>());
             }
         }
         None
     }
 
+    /// Sets a component on the stack, replacing any earlier value of the same component.
+    pub fn set_data_component(&mut self, component: Box<dyn DataComponentImpl>) {
+        let id = component.get_self_enum();
+        self.patch.retain(|(existing, _)| *existing != id);
+        self.patch.push((id, Some(component)));
+    }
+
+    pub fn remove_data_component<T: DataComponentImpl + 'static>(&mut self) {
+        let id = T::get_enum();
+        self.patch.retain(|(existing, _)| *existing != id);
+    }
+
     pub const EMPTY: &'static ItemStack = &ItemStack {
         item_count: 0,
         item: &Item::AIR,
@@ -123,7 +138,7 @@ impl ItemStack {
     }
 
     pub fn is_stackable(&self) -> bool {
-        self.get_max_stack_size() > 1 // TODO: && (!this.isDamageable() || !this.isDamaged());
+        self.get_max_stack_size() > 1 && (!self.is_damageable() || !self.is_damaged())
     }
 
     pub fn is_empty(&self) -> bool {
@@ -157,7 +172,7 @@ impl ItemStack {
     }
 
     pub fn decrement_unless_creative(&mut self, gamemode: GameMode, amount: u8) {
-        if gamemode != GameMode::Creative {
+        if !gamemode.has_infinite_materials() {
             self.item_count = self.item_count.saturating_sub(amount);
         }
     }
@@ -170,6 +185,53 @@ impl ItemStack {
         self.item_count = self.item_count.saturating_add(amount);
     }
 
+    pub fn get_damage(&self) -> i32 {
+        self.get_data_component::<DamageImpl>()
+            .map_or(0, |data| data.damage)
+    }
+
+    pub fn get_max_damage(&self) -> i32 {
+        self.get_data_component::<MaxDamageImpl>()
+            .map_or(0, |data| data.max_damage)
+    }
+
+    pub fn is_damageable(&self) -> bool {
+        self.get_max_damage() > 0
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.is_damageable() && self.get_damage() > 0
+    }
+
+    /// The slot this stack can be equipped in, if it is equippable.
+    pub fn get_equippable_slot(&self) -> Option<&'static EquipmentSlot> {
+        self.get_data_component::<EquippableImpl>()
+            .map(|data| data.slot)
+    }
+
+    /// Sets the damage of this stack, clamped between `0` and its max damage.
+    pub fn set_damage(&mut self, damage: i32) {
+        let damage = damage.clamp(0, self.get_max_damage());
+        if let Some(data) = self.get_data_component_mut::<DamageImpl>() {
+            data.damage = damage;
+        } else {
+            self.patch
+                .push((DataComponent::Damage, Some(DamageImpl { damage }.to_dyn())));
+        }
+    }
+
+    /// Returns the level of `enchantment` on this stack, or `0` if it is not enchanted with it.
+    pub fn get_enchantment_level(&self, enchantment: &Enchantment) -> i32 {
+        self.get_data_component::<EnchantmentsImpl>()
+            .and_then(|data| {
+                data.enchantment
+                    .iter()
+                    .find(|(enc, _)| *enc == enchantment)
+                    .map(|(_, level)| *level)
+            })
+            .unwrap_or(0)
+    }
+
     pub fn enchant(&mut self, enchantment: &'static Enchantment, level: i32) {
         // TODO itemstack may not send update packet to client
         if level <= 0 {
@@ -196,6 +258,77 @@ impl ItemStack {
             ));
         }
     }
+
+    /// Returns the `minecraft:custom_data` of this stack. Vanilla never looks at it, so this is
+    /// where plugins keep their own data. It is saved with the stack and sent to clients as is.
+    pub fn get_custom_data(&self) -> Option<&NbtCompound> {
+        self.get_data_component::<CustomDataImpl>()
+            .map(|custom| &custom.data)
+    }
+
+    /// Replaces the custom data of this stack, removing the component if `data` is empty.
+    pub fn set_custom_data(&mut self, data: NbtCompound) {
+        if data.is_empty() {
+            self.remove_custom_data();
+        } else {
+            self.set_data_component(CustomDataImpl { data }.to_dyn());
+        }
+    }
+
+    pub fn remove_custom_data(&mut self) {
+        self.patch
+            .retain(|(id, _)| *id != DataComponent::CustomData);
+    }
+
+    /// Reads the custom data entry `key` as a `T`. Returns `Ok(None)` if there is no such entry
+    /// and an error if it doesn't have the shape of a `T`.
+    pub fn get_custom<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, pumpkin_nbt::Error> {
+        let Some(tag) = self.get_custom_data().and_then(|data| data.get(key)) else {
+            return Ok(None);
+        };
+        // NBT can only be deserialized from a compound, so the entry is wrapped in one
+        let mut wrapper = NbtCompound::new();
+        wrapper.put(CUSTOM_ENTRY, tag.clone());
+        let bytes = Nbt::new(String::new(), wrapper).write_unnamed();
+        let entry: CustomEntry<T> = from_bytes_unnamed(Cursor::new(bytes))?;
+        Ok(Some(entry.value))
+    }
+
+    /// Stores `value` as the custom data entry `key`, keeping all other entries.
+    pub fn set_custom<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), pumpkin_nbt::Error> {
+        let mut bytes = Vec::new();
+        to_bytes_unnamed(&CustomEntry { value }, &mut bytes)?;
+        let mut wrapper: NbtCompound = from_bytes_unnamed(Cursor::new(bytes))?;
+        let tag = wrapper.child_tags.pop().map_or(NbtTag::End, |(_, tag)| tag);
+
+        let mut data = self.get_custom_data().cloned().unwrap_or_default();
+        data.child_tags.retain(|(existing, _)| existing != key);
+        data.put(key, tag);
+        self.set_custom_data(data);
+        Ok(())
+    }
+
+    /// Removes the custom data entry `key`, returning whether there was one.
+    pub fn remove_custom(&mut self, key: &str) -> bool {
+        let Some(mut data) = self.get_custom_data().cloned() else {
+            return false;
+        };
+        let len = data.child_tags.len();
+        data.child_tags.retain(|(existing, _)| existing != key);
+        if data.child_tags.len() == len {
+            return false;
+        }
+        self.set_custom_data(data);
+        true
+    }
+
     pub fn are_items_and_components_equal(&self, other: &Self) -> bool {
         if self.item != other.item || self.patch.len() != other.patch.len() {
             return false;
@@ -295,10 +428,16 @@ impl ItemStack {
 
         // Create a tag compound for additional data
         let mut tag = NbtCompound::new();
+        let mut raw = NbtCompound::new();
 
         for (id, data) in &self.patch {
             if let Some(data) = data {
-                tag.put(id.to_name(), data.write_data());
+                // Vanilla could not read the network bytes of a raw component, so they are kept apart
+                if data.as_any().is::<RawComponentImpl>() {
+                    raw.put(id.to_name(), data.write_data());
+                } else {
+                    tag.put(id.to_name(), data.write_data());
+                }
             } else {
                 let name = '!'.to_string() + id.to_name();
                 tag.put(name.as_str(), NbtCompound::new());
@@ -307,6 +446,9 @@ impl ItemStack {
 
         // Store custom data like enchantments, display name, etc. would go here
         compound.put_component("components", tag);
+        if !raw.is_empty() {
+            compound.put_component(RAW_COMPONENTS, raw);
+        }
     }
 
     pub fn read_item_stack(compound: &NbtCompound) -> Option<Self> {
@@ -337,11 +479,32 @@ impl ItemStack {
                 }
             }
         }
+        if let Some(raw) = compound.get_compound(RAW_COMPONENTS) {
+            for (name, data) in &raw.child_tags {
+                let id = DataComponent::try_from_name(name)?;
+                let bytes = data.extract_byte_array()?.into_vec();
+                item_stack
+                    .patch
+                    .push((id, Some(RawComponentImpl { id, bytes }.to_dyn())));
+            }
+        }
 
         Some(item_stack)
     }
 }
 
+/// Where components only known by their network bytes are saved, see [`RawComponentImpl`].
+const RAW_COMPONENTS: &str = "pumpkin:raw_components";
+
+/// The name of the single entry [`CustomEntry`] is (de)serialized as.
+const CUSTOM_ENTRY: &str = "value";
+
+/// Wraps a typed custom data entry, as NBT always has a compound at its root.
+#[derive(Serialize, Deserialize)]
+struct CustomEntry<T> {
+    value: T,
+}
+
 impl From<&RecipeResultStruct> for ItemStack {
     fn from(value: &RecipeResultStruct) -> Self {
         Self {
@@ -352,3 +515,61 @@ impl From<&RecipeResultStruct> for ItemStack {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::data_component::DataComponent;
+    use pumpkin_data::data_component_impl::{DataComponentImpl, RawComponentImpl};
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::compound::NbtCompound;
+    use serde::{Deserialize, Serialize};
+
+    use super::ItemStack;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Owner {
+        name: String,
+        uses: i32,
+    }
+
+    #[test]
+    fn custom_data_survives_saving() {
+        let owner = Owner {
+            name: "Steve".into(),
+            uses: 3,
+        };
+        let mut stack = ItemStack::new(1, &Item::DIAMOND_SWORD);
+        stack.set_custom("owner", &owner).unwrap();
+        stack.set_custom("level", &7i32).unwrap();
+
+        let mut compound = NbtCompound::new();
+        stack.write_item_stack(&mut compound);
+        let mut stack = ItemStack::read_item_stack(&compound).unwrap();
+
+        assert_eq!(stack.get_custom::<Owner>("owner").unwrap(), Some(owner));
+        assert_eq!(stack.get_custom::<i32>("level").unwrap(), Some(7));
+        assert_eq!(stack.get_custom::<i32>("missing").unwrap(), None);
+
+        assert!(stack.remove_custom("owner"));
+        assert!(stack.remove_custom("level"));
+        assert!(stack.get_custom_data().is_none());
+    }
+
+    #[test]
+    fn raw_components_survive_saving() {
+        let raw = RawComponentImpl {
+            id: DataComponent::Rarity,
+            bytes: vec![3],
+        };
+        let mut stack = ItemStack::new(1, &Item::DIAMOND_SWORD);
+        stack.set_data_component(raw.clone().to_dyn());
+
+        let mut compound = NbtCompound::new();
+        stack.write_item_stack(&mut compound);
+        assert!(compound.get_compound("components").unwrap().is_empty());
+        let stack = ItemStack::read_item_stack(&compound).unwrap();
+
+        assert_eq!(stack.patch.len(), 1);
+        assert!(stack.patch[0].1.as_deref().unwrap().equal(&raw));
+    }
+}
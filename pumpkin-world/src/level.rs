@@ -3,8 +3,11 @@ use crate::{
     biome::hash_seed,
     block::{RawBlockState, entities::BlockEntity},
     chunk::{
-        ChunkData, ChunkEntityData, ChunkReadingError,
-        format::{anvil::AnvilChunkFile, linear::LinearFile},
+        ChunkData, ChunkEntityData, ChunkReadingError, LightType,
+        format::{
+            anvil::{AnvilChunkFile, recover_region_folder},
+            linear::LinearFile,
+        },
         io::{Dirtiable, FileIO, LoadedData, file_manager::ChunkFileManager},
     },
     dimension::Dimension,
@@ -29,6 +32,7 @@ use pumpkin_data::{
     noise_router::{END_BASE_NOISE_ROUTER, NETHER_BASE_NOISE_ROUTER, OVERWORLD_BASE_NOISE_ROUTER},
 };
 use pumpkin_data::{BlockState, biome::Biome};
+use pumpkin_util::lock_order::OrderedRwLock;
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use rand::{Rng, SeedableRng, rngs::SmallRng};
 use rayon::{ThreadPool, ThreadPoolBuilder};
@@ -37,13 +41,13 @@ use std::{
     path::PathBuf,
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
     },
 };
 use tokio::{
     select,
     sync::{
-        Notify, RwLock,
+        Notify,
         mpsc::{self, UnboundedReceiver},
         oneshot,
     },
@@ -51,13 +55,18 @@ use tokio::{
 };
 use tokio_util::task::TaskTracker;
 
-pub type SyncChunk = Arc<RwLock<ChunkData>>;
-pub type SyncEntityChunk = Arc<RwLock<ChunkEntityData>>;
+/// The lock class of loaded chunks, see [`pumpkin_util::lock_order::LOCK_ORDER`].
+pub const CHUNK_LOCK_CLASS: &str = "level.chunk";
+/// The lock class of loaded entity chunks, see [`pumpkin_util::lock_order::LOCK_ORDER`].
+pub const ENTITY_CHUNK_LOCK_CLASS: &str = "level.entity_chunk";
+
+pub type SyncChunk = Arc<OrderedRwLock<ChunkData>>;
+pub type SyncEntityChunk = Arc<OrderedRwLock<ChunkEntityData>>;
 
 #[derive(Clone)]
 pub enum ChunkEntry {
     Pending(Arc<PendingChunk>),
-    Full(Arc<RwLock<ChunkData>>),
+    Full(SyncChunk),
 }
 
 pub struct VanillaGenerationState {
@@ -136,6 +145,9 @@ pub struct Level {
 
     /// Counts the number of ticks that have been scheduled for this world
     schedule_tick_counts: AtomicU64,
+    /// The age of the world in ticks, kept up to date by the world so saved chunks know when
+    /// they were unloaded.
+    pub world_age: AtomicI64,
 
     // Chunks that are paired with chunk watchers. When a chunk is no longer watched, it is removed
     // from the loaded chunks map and sent to the underlying ChunkIO
@@ -238,7 +250,8 @@ async fn rayon_chunk_generator(
             };
 
             take_mut::take(chunk.value_mut(), |chunk| match chunk {
-                ChunkEntry::Pending(chunk) => ChunkEntry::Full(Arc::new(RwLock::new(
+                ChunkEntry::Pending(chunk) => ChunkEntry::Full(Arc::new(OrderedRwLock::new(
+                    CHUNK_LOCK_CLASS,
                     chunk.finalize(generation_settings, status.unwrap()),
                 ))),
                 ChunkEntry::Full(chunk) => ChunkEntry::Full(chunk),
@@ -281,6 +294,17 @@ impl Level {
 
         // TODO: Load info correctly based on world format type
 
+        if matches!(advanced_config().chunk.format, ChunkFormat::Anvil) {
+            for folder in [&level_folder.region_folder, &level_folder.entities_folder] {
+                if let Err(error) = recover_region_folder(folder) {
+                    log::error!(
+                        "Failed to check {} for interrupted saves: {error}",
+                        folder.display()
+                    );
+                }
+            }
+        }
+
         let seed = Seed(seed as u64);
 
         let chunk_saver: Arc<dyn FileIO<Data = SyncChunk>> = match advanced_config().chunk.format {
@@ -311,6 +335,7 @@ impl Level {
             chunk_saver,
             entity_saver,
             schedule_tick_counts: AtomicU64::new(0),
+            world_age: AtomicI64::new(0),
             loaded_chunks: Arc::new(DashMap::new()),
             loaded_entity_chunks: Arc::new(DashMap::new()),
             chunk_watchers: Arc::new(DashMap::new()),
@@ -392,7 +417,7 @@ impl Level {
                         data: HashMap::new(),
                         dirty: true,
                     };
-                    let arc_chunk = Arc::new(RwLock::new(chunk));
+                    let arc_chunk = Arc::new(OrderedRwLock::new(CHUNK_LOCK_CLASS, chunk));
 
                     // Atomic insert: only insert if not already present
                     let final_chunk = match level_clone.loaded_entity_chunks.entry(pos) {
@@ -772,7 +797,7 @@ impl Level {
         }
     }
 
-    pub async fn wait_for_chunk(&self, coord: Vector2<i32>) -> Arc<RwLock<ChunkData>> {
+    pub async fn wait_for_chunk(&self, coord: Vector2<i32>) -> SyncChunk {
         loop {
             let generation_settings = gen_settings_from_dimension(&self.generation_state.dimension);
             let chunk = self
@@ -1167,6 +1192,35 @@ impl Level {
 
         RawBlockState(id)
     }
+    /// The light level at `position`, `None` if its chunk isn't lit.
+    pub async fn get_light(
+        self: &Arc<Self>,
+        light_type: LightType,
+        position: &BlockPos,
+    ) -> Option<u8> {
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.get_chunk(chunk_coordinate).await;
+        let chunk = chunk.read().await;
+
+        let relative_y = relative.y - chunk.section.min_y;
+        if relative_y < 0 {
+            return chunk.light_engine.is_lit.then_some(0);
+        }
+        chunk
+            .light_engine
+            .get(
+                light_type,
+                relative.x as usize,
+                relative_y as usize,
+                relative.z as usize,
+            )
+            .or_else(|| {
+                // Above the highest section there is nothing but the open sky
+                let light = if light_type == LightType::Sky { 15 } else { 0 };
+                chunk.light_engine.is_lit.then_some(light)
+            })
+    }
+
     pub async fn get_rough_biome(self: &Arc<Self>, position: &BlockPos) -> &'static Biome {
         let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
         let chunk = self.get_chunk(chunk_coordinate).await;
@@ -1198,6 +1252,15 @@ impl Level {
             block_state_id,
         );
         if replaced_block_state_id != block_state_id {
+            chunk.update_heightmaps(
+                relative.x as usize,
+                relative.y,
+                relative.z as usize,
+                block_state_id,
+            );
+            chunk
+                .poi
+                .on_block_changed(*position, replaced_block_state_id, block_state_id);
             chunk.mark_dirty(true);
         }
         replaced_block_state_id
@@ -1223,17 +1286,30 @@ impl Level {
             return;
         }
 
+        let world_age = self.world_age.load(Ordering::Relaxed);
+        let catch_up = advanced_config().catch_up.enabled;
         let chunks_to_write = chunks_to_write
             .iter()
             .map(async |(pos, chunk)| match &chunk {
-                ChunkEntry::Full(chunk) => (*pos, chunk.clone()),
+                ChunkEntry::Full(chunk) => {
+                    let mut data = chunk.write().await;
+                    data.last_update = world_age;
+                    // Otherwise an unchanged chunk keeps an old time on disk and catches up on
+                    // time it spent loaded
+                    data.dirty |= catch_up;
+                    drop(data);
+                    (*pos, chunk.clone())
+                }
                 ChunkEntry::Pending(chunk) => {
                     let generation_settings =
                         gen_settings_from_dimension(&self.generation_state.dimension);
                     let status = chunk.state.clone().lock_owned().await;
                     (
                         *pos,
-                        Arc::new(RwLock::new(chunk.finalize(generation_settings, status))),
+                        Arc::new(OrderedRwLock::new(
+                            CHUNK_LOCK_CLASS,
+                            chunk.finalize(generation_settings, status),
+                        )),
                     )
                 }
             })
@@ -1270,7 +1346,36 @@ impl Level {
         }
     }
 
-    pub fn try_get_chunk(&self, coordinates: &Vector2<i32>) -> Option<Arc<RwLock<ChunkData>>> {
+    /// Adds an already generated chunk, replacing whatever was loaded at its position. Nothing is
+    /// loaded from disk or generated for that position afterwards.
+    pub fn insert_chunk(&self, chunk: ChunkData) {
+        self.loaded_chunks.insert(
+            chunk.position,
+            ChunkEntry::Full(Arc::new(OrderedRwLock::new(CHUNK_LOCK_CLASS, chunk))),
+        );
+    }
+
+    /// Advances the block tick schedulers of all loaded chunks by one tick and returns the ticks
+    /// that are due, in the order they should run.
+    pub async fn step_block_ticks(&self) -> Vec<OrderedTick<&'static Block>> {
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .filter_map(|entry| match entry.value() {
+                ChunkEntry::Full(chunk) => Some(chunk.clone()),
+                ChunkEntry::Pending(_) => None,
+            })
+            .collect();
+
+        let mut ticks = Vec::new();
+        for chunk in chunks {
+            ticks.append(&mut chunk.write().await.block_ticks.step_tick());
+        }
+        ticks.sort_unstable();
+        ticks
+    }
+
+    pub fn try_get_chunk(&self, coordinates: &Vector2<i32>) -> Option<SyncChunk> {
         if let Some(chunk) = self.loaded_chunks.try_get(coordinates).try_unwrap()
             && let ChunkEntry::Full(loaded_chunk) = &chunk.value()
         {
@@ -1282,7 +1387,7 @@ impl Level {
     pub fn try_get_entity_chunk(
         &self,
         coordinates: Vector2<i32>,
-    ) -> Option<dashmap::mapref::one::Ref<'_, Vector2<i32>, Arc<RwLock<ChunkEntityData>>>> {
+    ) -> Option<dashmap::mapref::one::Ref<'_, Vector2<i32>, SyncEntityChunk>> {
         self.loaded_entity_chunks.try_get(&coordinates).try_unwrap()
     }
 
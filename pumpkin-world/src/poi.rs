@@ -0,0 +1,443 @@
+//! Points of interest (POI) are blocks that entities search for, like beds, job site blocks and
+//! nether portals. They are stored per chunk so lookups do not have to scan blocks.
+
+use std::collections::HashMap;
+
+use pumpkin_data::{
+    Block,
+    block_properties::{BedPart, BlockProperties, WhiteBedLikeProperties},
+    tag::{self, Taggable},
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{BlockStateId, chunk::ChunkSections};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoiType {
+    Armorer,
+    Butcher,
+    Cartographer,
+    Cleric,
+    Farmer,
+    Fisherman,
+    Fletcher,
+    Leatherworker,
+    Librarian,
+    Mason,
+    Shepherd,
+    Toolsmith,
+    Weaponsmith,
+    Home,
+    Meeting,
+    Beehive,
+    BeeNest,
+    NetherPortal,
+    Lodestone,
+    LightningRod,
+}
+
+impl PoiType {
+    pub const ALL: [Self; 20] = [
+        Self::Armorer,
+        Self::Butcher,
+        Self::Cartographer,
+        Self::Cleric,
+        Self::Farmer,
+        Self::Fisherman,
+        Self::Fletcher,
+        Self::Leatherworker,
+        Self::Librarian,
+        Self::Mason,
+        Self::Shepherd,
+        Self::Toolsmith,
+        Self::Weaponsmith,
+        Self::Home,
+        Self::Meeting,
+        Self::Beehive,
+        Self::BeeNest,
+        Self::NetherPortal,
+        Self::Lodestone,
+        Self::LightningRod,
+    ];
+
+    /// Returns the POI type of a block state, if it is one.
+    pub fn from_state(state_id: BlockStateId) -> Option<Self> {
+        let block = Block::from_state_id(state_id);
+        let poi_type = if block == &Block::BLAST_FURNACE {
+            Self::Armorer
+        } else if block == &Block::SMOKER {
+            Self::Butcher
+        } else if block == &Block::CARTOGRAPHY_TABLE {
+            Self::Cartographer
+        } else if block == &Block::BREWING_STAND {
+            Self::Cleric
+        } else if block == &Block::COMPOSTER {
+            Self::Farmer
+        } else if block == &Block::BARREL {
+            Self::Fisherman
+        } else if block == &Block::FLETCHING_TABLE {
+            Self::Fletcher
+        } else if block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_CAULDRONS) {
+            Self::Leatherworker
+        } else if block == &Block::LECTERN {
+            Self::Librarian
+        } else if block == &Block::STONECUTTER {
+            Self::Mason
+        } else if block == &Block::LOOM {
+            Self::Shepherd
+        } else if block == &Block::SMITHING_TABLE {
+            Self::Toolsmith
+        } else if block == &Block::GRINDSTONE {
+            Self::Weaponsmith
+        } else if block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_BEDS) {
+            // Only the head of a bed counts, so a bed is not claimed twice
+            if WhiteBedLikeProperties::from_state_id(state_id, block).part != BedPart::Head {
+                return None;
+            }
+            Self::Home
+        } else if block == &Block::BELL {
+            Self::Meeting
+        } else if block == &Block::BEEHIVE {
+            Self::Beehive
+        } else if block == &Block::BEE_NEST {
+            Self::BeeNest
+        } else if block == &Block::NETHER_PORTAL {
+            Self::NetherPortal
+        } else if block == &Block::LODESTONE {
+            Self::Lodestone
+        } else if block == &Block::LIGHTNING_ROD {
+            Self::LightningRod
+        } else {
+            return None;
+        };
+        Some(poi_type)
+    }
+
+    /// How many entities can claim a POI of this type at once.
+    pub const fn ticket_count(self) -> u8 {
+        match self {
+            Self::Meeting => 32,
+            Self::Beehive
+            | Self::BeeNest
+            | Self::NetherPortal
+            | Self::Lodestone
+            | Self::LightningRod => 0,
+            _ => 1,
+        }
+    }
+
+    /// How close an entity has to be to the POI to use it.
+    pub const fn valid_range(self) -> u8 {
+        match self {
+            Self::Meeting => 6,
+            _ => 1,
+        }
+    }
+
+    /// Whether this is the job site of a villager profession.
+    pub const fn is_workstation(self) -> bool {
+        !matches!(
+            self,
+            Self::Home
+                | Self::Meeting
+                | Self::Beehive
+                | Self::BeeNest
+                | Self::NetherPortal
+                | Self::Lodestone
+                | Self::LightningRod
+        )
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Armorer => "armorer",
+            Self::Butcher => "butcher",
+            Self::Cartographer => "cartographer",
+            Self::Cleric => "cleric",
+            Self::Farmer => "farmer",
+            Self::Fisherman => "fisherman",
+            Self::Fletcher => "fletcher",
+            Self::Leatherworker => "leatherworker",
+            Self::Librarian => "librarian",
+            Self::Mason => "mason",
+            Self::Shepherd => "shepherd",
+            Self::Toolsmith => "toolsmith",
+            Self::Weaponsmith => "weaponsmith",
+            Self::Home => "home",
+            Self::Meeting => "meeting",
+            Self::Beehive => "beehive",
+            Self::BeeNest => "bee_nest",
+            Self::NetherPortal => "nether_portal",
+            Self::Lodestone => "lodestone",
+            Self::LightningRod => "lightning_rod",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|poi_type| poi_type.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoiRecord {
+    pub pos: BlockPos,
+    pub poi_type: PoiType,
+    pub free_tickets: u8,
+}
+
+impl PoiRecord {
+    pub fn new(pos: BlockPos, poi_type: PoiType) -> Self {
+        Self {
+            pos,
+            poi_type,
+            free_tickets: poi_type.ticket_count(),
+        }
+    }
+
+    /// Whether another entity can still claim this POI. POIs without tickets are always free.
+    pub fn is_free(&self) -> bool {
+        self.poi_type.ticket_count() == 0 || self.free_tickets > 0
+    }
+
+    /// Whether an entity has claimed this POI, e.g. a villager living in a bed.
+    pub fn is_occupied(&self) -> bool {
+        self.free_tickets < self.poi_type.ticket_count()
+    }
+
+    /// Returns `false` if there was no ticket left to take.
+    fn acquire_ticket(&mut self) -> bool {
+        if self.free_tickets == 0 {
+            return false;
+        }
+        self.free_tickets -= 1;
+        true
+    }
+
+    /// Returns `false` if no ticket was taken.
+    fn release_ticket(&mut self) -> bool {
+        if self.free_tickets >= self.poi_type.ticket_count() {
+            return false;
+        }
+        self.free_tickets += 1;
+        true
+    }
+}
+
+impl Serialize for PoiRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut nbt = NbtCompound::new();
+        nbt.put_int("x", self.pos.0.x);
+        nbt.put_int("y", self.pos.0.y);
+        nbt.put_int("z", self.pos.0.z);
+        nbt.put_string("type", format!("minecraft:{}", self.poi_type.name()));
+        nbt.put_int("free_tickets", i32::from(self.free_tickets));
+        nbt.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PoiRecord {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nbt = NbtCompound::deserialize(deserializer)?;
+        let (Some(x), Some(y), Some(z)) = (nbt.get_int("x"), nbt.get_int("y"), nbt.get_int("z"))
+        else {
+            return Err(de::Error::missing_field("x, y, z"));
+        };
+        let name = nbt
+            .get_string("type")
+            .ok_or(de::Error::missing_field("type"))?;
+        let poi_type = PoiType::from_name(name)
+            .ok_or_else(|| de::Error::custom(format!("Unknown POI type {name}")))?;
+        let free_tickets = nbt
+            .get_int("free_tickets")
+            .map_or(poi_type.ticket_count(), |tickets| {
+                tickets.clamp(0, i32::from(poi_type.ticket_count())) as u8
+            });
+
+        Ok(Self {
+            pos: BlockPos::new(x, y, z),
+            poi_type,
+            free_tickets,
+        })
+    }
+}
+
+/// The points of interest of a single chunk.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkPoiStorage {
+    records: HashMap<BlockPos, PoiRecord>,
+}
+
+impl ChunkPoiStorage {
+    /// Finds every POI in the given sections, used for chunks that were saved without POI data.
+    pub fn scan(sections: &ChunkSections, chunk_pos: Vector2<i32>) -> Self {
+        let mut storage = Self::default();
+        for (index, section) in sections.sections.iter().enumerate() {
+            if !section
+                .block_states
+                .any(|state_id| PoiType::from_state(state_id).is_some())
+            {
+                continue;
+            }
+            let base_y = sections.min_y + index as i32 * 16;
+            for y in 0..16 {
+                for z in 0..16 {
+                    for x in 0..16 {
+                        let state_id = section.block_states.get(x, y, z);
+                        if let Some(poi_type) = PoiType::from_state(state_id) {
+                            let pos = BlockPos::new(
+                                chunk_pos.x * 16 + x as i32,
+                                base_y + y as i32,
+                                chunk_pos.y * 16 + z as i32,
+                            );
+                            storage.add(pos, poi_type);
+                        }
+                    }
+                }
+            }
+        }
+        storage
+    }
+
+    pub fn from_records(records: Vec<PoiRecord>) -> Self {
+        Self {
+            records: records
+                .into_iter()
+                .map(|record| (record.pos, record))
+                .collect(),
+        }
+    }
+
+    pub fn to_records(&self) -> Vec<PoiRecord> {
+        self.records.values().cloned().collect()
+    }
+
+    pub fn get(&self, pos: &BlockPos) -> Option<&PoiRecord> {
+        self.records.get(pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PoiRecord> {
+        self.records.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn add(&mut self, pos: BlockPos, poi_type: PoiType) {
+        self.records.insert(pos, PoiRecord::new(pos, poi_type));
+    }
+
+    pub fn remove(&mut self, pos: &BlockPos) -> Option<PoiRecord> {
+        self.records.remove(pos)
+    }
+
+    /// Updates the POI at `pos` after its block state changed. Returns whether anything changed.
+    pub fn on_block_changed(
+        &mut self,
+        pos: BlockPos,
+        old_state_id: BlockStateId,
+        new_state_id: BlockStateId,
+    ) -> bool {
+        let old_type = PoiType::from_state(old_state_id);
+        let new_type = PoiType::from_state(new_state_id);
+        if old_type == new_type {
+            return false;
+        }
+        if old_type.is_some() {
+            self.remove(&pos);
+        }
+        if let Some(new_type) = new_type {
+            self.add(pos, new_type);
+        }
+        true
+    }
+
+    /// Takes a ticket of the POI at `pos`. Returns `false` if there is no free POI.
+    pub fn acquire_ticket(&mut self, pos: &BlockPos) -> bool {
+        self.records
+            .get_mut(pos)
+            .is_some_and(PoiRecord::acquire_ticket)
+    }
+
+    /// Gives back a ticket of the POI at `pos`. Returns `false` if no ticket was taken.
+    pub fn release_ticket(&mut self, pos: &BlockPos) -> bool {
+        self.records
+            .get_mut(pos)
+            .is_some_and(PoiRecord::release_ticket)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use pumpkin_data::{
+        Block,
+        block_properties::{BedPart, BlockProperties, WhiteBedLikeProperties},
+    };
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::{ChunkPoiStorage, PoiRecord, PoiType};
+
+    #[test]
+    fn bed_head_only() {
+        let mut props = WhiteBedLikeProperties::default(&Block::RED_BED);
+        props.part = BedPart::Foot;
+        assert_eq!(
+            PoiType::from_state(props.to_state_id(&Block::RED_BED)),
+            None
+        );
+        props.part = BedPart::Head;
+        assert_eq!(
+            PoiType::from_state(props.to_state_id(&Block::RED_BED)),
+            Some(PoiType::Home)
+        );
+    }
+
+    #[test]
+    fn block_changes() {
+        let pos = BlockPos::new(1, 64, 1);
+        let mut storage = ChunkPoiStorage::default();
+        assert!(storage.on_block_changed(
+            pos,
+            Block::AIR.default_state.id,
+            Block::LECTERN.default_state.id
+        ));
+        assert_eq!(storage.get(&pos).unwrap().poi_type, PoiType::Librarian);
+
+        assert!(storage.acquire_ticket(&pos));
+        assert!(!storage.acquire_ticket(&pos));
+        assert!(!storage.get(&pos).unwrap().is_free());
+        assert!(storage.release_ticket(&pos));
+        assert!(!storage.release_ticket(&pos));
+
+        assert!(storage.on_block_changed(
+            pos,
+            Block::LECTERN.default_state.id,
+            Block::AIR.default_state.id
+        ));
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn nbt_round_trip() {
+        let mut record = PoiRecord::new(BlockPos::new(-3, 12, 40), PoiType::Meeting);
+        record.free_tickets = 30;
+
+        let mut bytes = Vec::new();
+        pumpkin_nbt::to_bytes_unnamed(&record, &mut bytes).unwrap();
+        let read: PoiRecord = pumpkin_nbt::from_bytes_unnamed(Cursor::new(bytes)).unwrap();
+        assert_eq!(read, record);
+    }
+}
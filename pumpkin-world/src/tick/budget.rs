@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use pumpkin_util::math::vector2::Vector2;
+
+use crate::tick::OrderedTick;
+
+/// Limits how many scheduled ticks are processed per game tick.
+///
+/// Ticks exceeding the budget are not dropped but deferred, in their original order, to the
+/// next game tick. This keeps an overloaded world slowing down deterministically instead of
+/// freezing the whole server.
+#[derive(Debug)]
+pub struct TickBudget<T> {
+    deferred: Vec<OrderedTick<T>>,
+    /// The number of ticks processed per chunk during the last game tick.
+    chunk_tick_counts: HashMap<Vector2<i32>, u32>,
+}
+
+impl<T> TickBudget<T> {
+    /// Returns the ticks to run this game tick, deferring everything above `budget`.
+    ///
+    /// Previously deferred ticks always run before the newly collected `ticks`.
+    pub fn take(&mut self, ticks: Vec<OrderedTick<T>>, budget: usize) -> Vec<OrderedTick<T>> {
+        let mut ticks = if self.deferred.is_empty() {
+            ticks
+        } else {
+            let mut all = std::mem::take(&mut self.deferred);
+            all.extend(ticks);
+            all
+        };
+
+        if ticks.len() > budget {
+            self.deferred = ticks.split_off(budget);
+        }
+
+        self.chunk_tick_counts.clear();
+        for tick in &ticks {
+            let (chunk, _) = tick.position.chunk_and_chunk_relative_position();
+            *self.chunk_tick_counts.entry(chunk).or_default() += 1;
+        }
+
+        ticks
+    }
+
+    /// The number of ticks currently waiting for a later game tick.
+    #[must_use]
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+
+    /// The number of ticks processed per chunk during the last game tick.
+    #[must_use]
+    pub fn chunk_tick_counts(&self) -> &HashMap<Vector2<i32>, u32> {
+        &self.chunk_tick_counts
+    }
+
+    /// Returns the `count` chunks which processed the most ticks during the last game tick.
+    #[must_use]
+    pub fn busiest_chunks(&self, count: usize) -> Vec<(Vector2<i32>, u32)> {
+        let mut chunks: Vec<_> = self
+            .chunk_tick_counts
+            .iter()
+            .map(|(pos, count)| (*pos, *count))
+            .collect();
+        chunks.sort_unstable_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| (a.0.x, a.0.y).cmp(&(b.0.x, b.0.y)))
+        });
+        chunks.truncate(count);
+        chunks
+    }
+}
+
+impl<T> Default for TickBudget<T> {
+    fn default() -> Self {
+        Self {
+            deferred: Vec::new(),
+            chunk_tick_counts: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::TickBudget;
+    use crate::tick::OrderedTick;
+
+    fn ticks(range: std::ops::Range<i32>) -> Vec<OrderedTick<i32>> {
+        range
+            .map(|i| OrderedTick::new(BlockPos::new(i, 0, 0), i))
+            .collect()
+    }
+
+    #[test]
+    fn defers_ticks_over_budget() {
+        let mut budget = TickBudget::default();
+
+        let run = budget.take(ticks(0..10), 4);
+        assert_eq!(
+            run.iter().map(|t| t.value).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(budget.deferred_count(), 6);
+
+        let run = budget.take(ticks(10..12), 4);
+        assert_eq!(
+            run.iter().map(|t| t.value).collect::<Vec<_>>(),
+            [4, 5, 6, 7]
+        );
+        assert_eq!(budget.deferred_count(), 4);
+
+        let run = budget.take(Vec::new(), 100);
+        assert_eq!(
+            run.iter().map(|t| t.value).collect::<Vec<_>>(),
+            [8, 9, 10, 11]
+        );
+        assert_eq!(budget.deferred_count(), 0);
+    }
+
+    #[test]
+    fn counts_ticks_per_chunk() {
+        let mut budget = TickBudget::default();
+        budget.take(ticks(0..20), 100);
+
+        let busiest = budget.busiest_chunks(2);
+        assert_eq!(busiest.len(), 2);
+        assert_eq!(busiest[0].1, 16);
+        assert_eq!(busiest[1].1, 4);
+    }
+}
@@ -7,6 +7,7 @@ use pumpkin_util::{
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+pub mod budget;
 pub mod scheduler;
 
 const MAX_TICK_DELAY: usize = 1 << 8;
@@ -8,6 +8,7 @@ use pumpkin_data::entity::EntityType;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::world::WorldEvent;
 use pumpkin_data::{Block, BlockDirection, BlockState};
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::math::boundingbox::BoundingBox;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
@@ -25,6 +26,10 @@ bitflags! {
         const SKIP_REDSTONE_WIRE_STATE_REPLACEMENT  = 0b000_0010_0000;
         const SKIP_BLOCK_ENTITY_REPLACED_CALLBACK   = 0b000_0100_0000;
         const SKIP_BLOCK_ADDED_CALLBACK             = 0b000_1000_0000;
+        /// Don't let observers notice shape updates caused by this change.
+        const SKIP_OBSERVER_UPDATE                  = 0b001_0000_0000;
+        /// Only store the new state and send it to clients, without any block callbacks or neighbor updates.
+        const CLIENT_ONLY                           = 0b010_0000_0000;
     }
 }
 
@@ -63,19 +68,35 @@ pub trait SimpleWorld: BlockAccessor + Send + Sync {
 
     async fn is_space_empty(&self, bounding_box: BoundingBox) -> bool;
 
+    /// Spawns a new entity of `entity_type` near `position`, returns false if there is no room
+    /// for it there.
     async fn spawn_from_type(
         self: Arc<Self>,
         entity_type: &'static EntityType,
         position: Vector3<f64>,
-    );
+    ) -> bool;
+
+    /// Spawns the entity saved in `nbt` at `position`, like a bee leaving its hive. Returns false
+    /// if `nbt` doesn't name a known entity type.
+    async fn spawn_from_nbt(self: Arc<Self>, nbt: &NbtCompound, position: Vector3<f64>) -> bool;
 
     async fn add_synced_block_event(&self, pos: BlockPos, r#type: u8, data: u8);
 
     async fn sync_world_event(&self, world_event: WorldEvent, position: BlockPos, data: i32);
 
+    /// Starts playing the song of a jukebox record, given as its `jukebox_song` id.
+    async fn play_jukebox_song(&self, position: BlockPos, song: &str);
+
     async fn remove_block_entity(&self, block_pos: &BlockPos);
     async fn get_block_entity(&self, block_pos: &BlockPos) -> Option<Arc<dyn BlockEntity>>;
+    /// The container a block offers without having a block entity, like the composter's.
+    async fn get_block_container(
+        self: Arc<Self>,
+        position: &BlockPos,
+    ) -> Option<Arc<dyn Inventory>>;
     async fn get_world_age(&self) -> i64;
+    async fn is_night(&self) -> bool;
+    async fn is_raining(&self) -> bool;
 
     async fn play_sound(&self, sound: Sound, category: SoundCategory, position: &Vector3<f64>);
     async fn play_sound_fine(
@@ -270,6 +270,8 @@ mod test {
             spawn_y: 70,
             spawn_z: 160,
             spawn_angle: 0.0,
+            wandering_trader_spawn_delay: 24000,
+            wandering_trader_spawn_chance: 25,
             level_version: 19133,
             world_version: WorldVersion {
                 name: "1.21.4".to_string(),
@@ -86,12 +86,29 @@ pub struct LevelData {
     pub spawn_z: i32,
     // The Yaw rotation of the world spawn.
     pub spawn_angle: f32,
+    // Ticks until the next wandering trader spawn attempt. Defaults to 24000.
+    #[serde(default = "default_wandering_trader_spawn_delay")]
+    pub wandering_trader_spawn_delay: i32,
+    // Percent chance of the next wandering trader spawn attempt succeeding. Defaults to 25.
+    #[serde(default = "default_wandering_trader_spawn_chance")]
+    pub wandering_trader_spawn_chance: i32,
     #[serde(rename = "Version")]
     pub world_version: WorldVersion,
     #[serde(rename = "version")]
     pub level_version: i32, // TODO: Implement the rest of the fields
 }
 
+pub const DEFAULT_WANDERING_TRADER_SPAWN_DELAY: i32 = 24000;
+pub const DEFAULT_WANDERING_TRADER_SPAWN_CHANCE: i32 = 25;
+
+fn default_wandering_trader_spawn_delay() -> i32 {
+    DEFAULT_WANDERING_TRADER_SPAWN_DELAY
+}
+
+fn default_wandering_trader_spawn_chance() -> i32 {
+    DEFAULT_WANDERING_TRADER_SPAWN_CHANCE
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct WorldGenSettings {
     // the numerical seed of the world
@@ -249,6 +266,8 @@ impl Default for LevelData {
             spawn_y: 200,
             spawn_z: 0,
             spawn_angle: 0.0,
+            wandering_trader_spawn_delay: DEFAULT_WANDERING_TRADER_SPAWN_DELAY,
+            wandering_trader_spawn_chance: DEFAULT_WANDERING_TRADER_SPAWN_CHANCE,
             world_version: Default::default(),
             level_version: MAXIMUM_SUPPORTED_LEVEL_VERSION,
         }
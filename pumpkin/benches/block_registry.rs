@@ -0,0 +1,104 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin::block::registry::default_registry;
+use pumpkin_data::{
+    Block, BlockState,
+    block_properties::{BlockProperties, OakFenceLikeProperties, RedstoneWireLikeProperties},
+};
+use std::hint::black_box;
+
+fn bench_block_registry_lookup(c: &mut Criterion) {
+    let registry = default_registry();
+    // A mix of blocks with and without a registered behaviour
+    let blocks = [
+        &Block::STONE,
+        &Block::OAK_LOG,
+        &Block::REDSTONE_WIRE,
+        &Block::GRASS_BLOCK,
+        &Block::STONE_BUTTON,
+        &Block::CHEST,
+        &Block::AIR,
+        &Block::WATER,
+    ];
+
+    c.bench_function("get_pumpkin_block", |b| {
+        b.iter(|| {
+            for block in blocks {
+                black_box(registry.get_pumpkin_block(black_box(block)));
+            }
+        });
+    });
+
+    c.bench_function("get_behaviour", |b| {
+        b.iter(|| {
+            for block in blocks {
+                black_box(registry.get_behaviour(black_box(block)));
+            }
+        });
+    });
+}
+
+fn bench_block_state_lookup(c: &mut Criterion) {
+    // Every state of some blocks with many properties, as a chunk full of redstone would have
+    let wire_states: Vec<u16> = Block::REDSTONE_WIRE
+        .states
+        .iter()
+        .map(|state| state.id)
+        .collect();
+    let fence_states: Vec<u16> = Block::OAK_FENCE
+        .states
+        .iter()
+        .map(|state| state.id)
+        .collect();
+
+    c.bench_function("block_from_state_id", |b| {
+        b.iter(|| {
+            for &id in &wire_states {
+                black_box(Block::from_state_id(black_box(id)));
+            }
+        });
+    });
+
+    c.bench_function("block_state_from_id", |b| {
+        b.iter(|| {
+            for &id in &wire_states {
+                black_box(BlockState::from_id(black_box(id)));
+            }
+        });
+    });
+
+    c.bench_function("properties_from_state_id", |b| {
+        b.iter(|| {
+            for &id in &wire_states {
+                black_box(RedstoneWireLikeProperties::from_state_id(
+                    black_box(id),
+                    &Block::REDSTONE_WIRE,
+                ));
+            }
+            for &id in &fence_states {
+                black_box(OakFenceLikeProperties::from_state_id(
+                    black_box(id),
+                    &Block::OAK_FENCE,
+                ));
+            }
+        });
+    });
+
+    c.bench_function("properties_to_state_id", |b| {
+        let wire: Vec<_> = wire_states
+            .iter()
+            .map(|&id| RedstoneWireLikeProperties::from_state_id(id, &Block::REDSTONE_WIRE))
+            .collect();
+        b.iter(|| {
+            for properties in &wire {
+                black_box(black_box(properties).to_state_id(&Block::REDSTONE_WIRE));
+            }
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_block_registry_lookup,
+    bench_block_state_lookup
+);
+criterion_main!(benches);
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin_data::item::Item;
+use pumpkin_inventory::{
+    build_equipment_slots,
+    entity_equipment::EntityEquipment,
+    generic_container_screen_handler::{GenericContainerScreenHandler, create_generic_9x3},
+    player::player_inventory::PlayerInventory,
+    screen_handler::{InventoryPlayer, ScreenHandler},
+};
+use pumpkin_protocol::java::{
+    client::play::{
+        CSetContainerContent, CSetContainerProperty, CSetContainerSlot, CSetCursorItem,
+        CSetPlayerInventory, CSetSelectedSlot,
+    },
+    server::play::SlotActionType,
+};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    block::entities::chest::ChestBlockEntity, inventory::Inventory, item::ItemStack,
+};
+use tokio::sync::Mutex;
+
+/// A player without a connection, so only the click handling itself is measured.
+struct BenchPlayer {
+    inventory: Arc<PlayerInventory>,
+}
+
+#[async_trait]
+impl InventoryPlayer for BenchPlayer {
+    async fn drop_item(&self, _item: ItemStack, _retain_ownership: bool) {}
+
+    fn get_inventory(&self) -> Arc<PlayerInventory> {
+        self.inventory.clone()
+    }
+
+    fn has_infinite_materials(&self) -> bool {
+        false
+    }
+
+    async fn enqueue_inventory_packet(&self, _packet: &CSetContainerContent) {}
+
+    async fn enqueue_slot_packet(&self, _packet: &CSetContainerSlot) {}
+
+    async fn enqueue_cursor_packet(&self, _packet: &CSetCursorItem) {}
+
+    async fn enqueue_property_packet(&self, _packet: &CSetContainerProperty) {}
+
+    async fn enqueue_slot_set_packet(&self, _packet: &CSetPlayerInventory) {}
+
+    async fn enqueue_set_held_item_packet(&self, _packet: &CSetSelectedSlot) {}
+}
+
+/// A player looking into a chest that has a stack of stone in its first slot.
+async fn open_chest() -> (BenchPlayer, Mutex<GenericContainerScreenHandler>) {
+    let inventory = Arc::new(PlayerInventory::new(
+        Arc::new(Mutex::new(EntityEquipment::new())),
+        Arc::new(build_equipment_slots()),
+    ));
+    let chest = Arc::new(ChestBlockEntity::new(BlockPos::new(0, 0, 0)));
+    chest.set_stack(0, ItemStack::new(64, &Item::STONE)).await;
+
+    let handler = create_generic_9x3(1, &inventory, chest);
+    (BenchPlayer { inventory }, Mutex::new(handler))
+}
+
+fn bench_container_click(c: &mut Criterion) {
+    let async_handler = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let (player, handler) = async_handler.block_on(open_chest());
+    c.bench_function("pickup_and_place", |b| {
+        b.to_async(&async_handler).iter(async || {
+            let mut handler = handler.lock().await;
+            handler
+                .on_slot_click(0, 0, SlotActionType::Pickup, &player)
+                .await;
+            handler
+                .on_slot_click(0, 0, SlotActionType::Pickup, &player)
+                .await;
+        });
+    });
+
+    let (player, handler) = async_handler.block_on(open_chest());
+    // Shift clicking moves the stack to the last free slot of the player's inventory
+    let player_slot = async_handler.block_on(async {
+        let handler = handler.lock().await;
+        handler.get_behaviour().slots.len() as i32 - 1
+    });
+    c.bench_function("quick_move", |b| {
+        b.to_async(&async_handler).iter(async || {
+            let mut handler = handler.lock().await;
+            handler
+                .on_slot_click(0, 0, SlotActionType::QuickMove, &player)
+                .await;
+            handler
+                .on_slot_click(player_slot, 0, SlotActionType::QuickMove, &player)
+                .await;
+        });
+    });
+}
+
+criterion_group!(benches, bench_container_click);
+criterion_main!(benches);
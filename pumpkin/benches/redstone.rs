@@ -0,0 +1,64 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin::block::blocks::redstone::simulation::{CircuitBuilder, RedstoneSimulation};
+use pumpkin_util::math::position::BlockPos;
+
+/// The side length of the wire carpet. The simulation has chunks from -16 to 31 in each direction.
+const CARPET_SIZE: i32 = 24;
+/// Repeaters in the chain, each delaying the signal by two game ticks.
+const REPEATERS: u16 = 16;
+
+/// A square of redstone wire powered from one corner. Every change of the lever updates all wires
+/// and their neighbours, which is what large contraptions spend most of their time on.
+async fn build_wire_carpet() -> RedstoneSimulation {
+    let simulation = RedstoneSimulation::new();
+    let mut circuit = CircuitBuilder::new(BlockPos::new(-1, 0, 0)).lever();
+    for z in 0..CARPET_SIZE {
+        circuit = circuit.at(BlockPos::new(0, 0, z));
+        for _ in 0..CARPET_SIZE {
+            circuit = circuit.wire();
+        }
+    }
+    circuit.build(&simulation).await;
+    simulation
+}
+
+/// A lever driving a long chain of repeaters into a lamp, which runs through scheduled ticks.
+async fn build_repeater_chain() -> RedstoneSimulation {
+    let simulation = RedstoneSimulation::new();
+    let mut circuit = CircuitBuilder::new(BlockPos::new(-1, 0, 0)).lever();
+    for _ in 0..REPEATERS {
+        circuit = circuit.repeater(1);
+    }
+    circuit.lamp().build(&simulation).await;
+    simulation
+}
+
+fn bench_redstone(c: &mut Criterion) {
+    let async_handler = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let lever = BlockPos::new(-1, 0, 0);
+
+    let carpet = async_handler.block_on(build_wire_carpet());
+    c.bench_function("wire_carpet_toggle", |b| {
+        b.to_async(&async_handler).iter(async || {
+            carpet.toggle_lever(lever).await;
+            carpet.toggle_lever(lever).await;
+        });
+    });
+
+    let chain = async_handler.block_on(build_repeater_chain());
+    // Enough ticks for the signal to travel through every repeater and the lamp to react
+    let settle_ticks = usize::from(REPEATERS) * 2 + 4;
+    c.bench_function("repeater_chain_toggle", |b| {
+        b.to_async(&async_handler).iter(async || {
+            chain.toggle_lever(lever).await;
+            chain.run(settle_ticks).await;
+            chain.toggle_lever(lever).await;
+            chain.run(settle_ticks).await;
+        });
+    });
+}
+
+criterion_group!(benches, bench_redstone);
+criterion_main!(benches);
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    Block, BlockDirection, BlockState, FacingExt,
+    block_properties::{AmethystClusterLikeProperties, BlockProperties},
+};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    BlockStateId,
+    world::{BlockAccessor, BlockFlags},
+};
+use rand::Rng;
+
+use crate::block::{
+    BlockBehaviour, BlockMetadata, CanPlaceAtArgs, GetStateForNeighborUpdateArgs, OnPlaceArgs,
+    RandomTickArgs,
+};
+
+/// One in this many random ticks grows a bud on the budding amethyst.
+const GROW_CHANCE: u32 = 5;
+
+#[pumpkin_block("minecraft:budding_amethyst")]
+pub struct BuddingAmethystBlock;
+
+#[async_trait]
+impl BlockBehaviour for BuddingAmethystBlock {
+    async fn random_tick(&self, args: RandomTickArgs<'_>) {
+        let mut rng = rand::rng();
+        if rng.random_range(0..GROW_CHANCE) != 0 {
+            return;
+        }
+
+        let directions = BlockDirection::all();
+        let direction = directions[rng.random_range(0..directions.len())];
+        let position = args.position.offset(direction.to_offset());
+        let (block, state) = args.world.get_block_and_state(&position).await;
+
+        let (next, waterlogged) = if can_grow_in(state) {
+            (&Block::SMALL_AMETHYST_BUD, state.is_liquid())
+        } else {
+            // Only a bud growing out of this face can grow further
+            let Some(next) = next_stage(block) else {
+                return;
+            };
+            let props = AmethystClusterLikeProperties::from_state_id(state.id, block);
+            if props.facing.to_block_direction() != direction {
+                return;
+            }
+            (next, props.waterlogged)
+        };
+
+        let mut props = AmethystClusterLikeProperties::default(next);
+        props.facing = direction.to_facing();
+        props.waterlogged = waterlogged;
+        args.world
+            .set_block_state(&position, props.to_state_id(next), BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+}
+
+/// Amethyst buds in all their growth stages and the fully grown cluster.
+pub struct AmethystClusterBlock;
+
+impl BlockMetadata for AmethystClusterBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn ids(&self) -> &'static [&'static str] {
+        &[
+            Block::SMALL_AMETHYST_BUD.name,
+            Block::MEDIUM_AMETHYST_BUD.name,
+            Block::LARGE_AMETHYST_BUD.name,
+            Block::AMETHYST_CLUSTER.name,
+        ]
+    }
+}
+
+#[async_trait]
+impl BlockBehaviour for AmethystClusterBlock {
+    async fn on_place(&self, args: OnPlaceArgs<'_>) -> BlockStateId {
+        let mut props = AmethystClusterLikeProperties::default(args.block);
+        props.facing = args.direction.to_facing().opposite();
+        props.waterlogged = args.replacing.water_source();
+        props.to_state_id(args.block)
+    }
+
+    async fn can_place_at(&self, args: CanPlaceAtArgs<'_>) -> bool {
+        is_supported(
+            args.block_accessor,
+            args.position,
+            args.direction.opposite(),
+        )
+        .await
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        let props = AmethystClusterLikeProperties::from_state_id(args.state_id, args.block);
+        let facing = props.facing.to_block_direction();
+        if args.direction == facing.opposite()
+            && !is_supported(args.world, args.position, facing).await
+        {
+            return Block::AIR.default_state.id;
+        }
+        args.state_id
+    }
+}
+
+/// Whether a bud facing `facing` at `position` is held by a full face behind it.
+async fn is_supported(
+    world: &dyn BlockAccessor,
+    position: &BlockPos,
+    facing: BlockDirection,
+) -> bool {
+    let support = world
+        .get_block_state(&position.offset(facing.opposite().to_offset()))
+        .await;
+    support.is_side_solid(facing)
+}
+
+/// Buds only start growing in air or still water.
+fn can_grow_in(state: &BlockState) -> bool {
+    state.is_air() || state.id == Block::WATER.default_state.id
+}
+
+/// The block a bud grows into, `None` for a fully grown cluster or any other block.
+fn next_stage(block: &Block) -> Option<&'static Block> {
+    if block == &Block::SMALL_AMETHYST_BUD {
+        Some(&Block::MEDIUM_AMETHYST_BUD)
+    } else if block == &Block::MEDIUM_AMETHYST_BUD {
+        Some(&Block::LARGE_AMETHYST_BUD)
+    } else if block == &Block::LARGE_AMETHYST_BUD {
+        Some(&Block::AMETHYST_CLUSTER)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buds_grow_into_a_cluster() {
+        let mut block = &Block::SMALL_AMETHYST_BUD;
+        let mut stages = 0;
+        while let Some(next) = next_stage(block) {
+            block = next;
+            stages += 1;
+        }
+        assert_eq!(block, &Block::AMETHYST_CLUSTER);
+        assert_eq!(stages, 3);
+        assert!(next_stage(&Block::AMETHYST_BLOCK).is_none());
+    }
+}
@@ -1,12 +1,20 @@
 use crate::block::blocks::falling::FallingBlock;
+use crate::block::registry::BlockActionResult;
 use crate::block::{
-    BlockBehaviour, GetStateForNeighborUpdateArgs, OnPlaceArgs, OnScheduledTickArgs, PlacedArgs,
+    BlockBehaviour, GetStateForNeighborUpdateArgs, NormalUseArgs, OnPlaceArgs, OnScheduledTickArgs,
+    PlacedArgs,
 };
 use async_trait::async_trait;
 use pumpkin_data::block_properties::{BlockProperties, WallTorchLikeProperties};
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_inventory::anvil::anvil_screen_handler::AnvilScreenHandler;
+use pumpkin_inventory::player::player_inventory::PlayerInventory;
+use pumpkin_inventory::screen_handler::{InventoryPlayer, ScreenHandler, ScreenHandlerFactory};
 use pumpkin_macros::pumpkin_block_from_tag;
+use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
+use std::sync::Arc;
+use tokio::sync::Mutex;
 
 #[pumpkin_block_from_tag("minecraft:anvil")]
 pub struct AnvilBlock;
@@ -27,6 +35,12 @@ impl BlockBehaviour for AnvilBlock {
         props.to_state_id(args.block)
     }
 
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        args.player.open_handled_screen(&AnvilScreenFactory).await;
+
+        BlockActionResult::Success
+    }
+
     async fn placed(&self, args: PlacedArgs<'_>) {
         FallingBlock::placed(&FallingBlock, args).await;
     }
@@ -41,3 +55,23 @@ impl BlockBehaviour for AnvilBlock {
         FallingBlock::on_scheduled_tick(&FallingBlock, args).await;
     }
 }
+
+struct AnvilScreenFactory;
+
+#[async_trait]
+impl ScreenHandlerFactory for AnvilScreenFactory {
+    async fn create_screen_handler(
+        &self,
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        _player: &dyn InventoryPlayer,
+    ) -> Option<Arc<Mutex<dyn ScreenHandler>>> {
+        Some(Arc::new(Mutex::new(
+            AnvilScreenHandler::new(sync_id, player_inventory).await,
+        )))
+    }
+
+    fn get_display_name(&self) -> TextComponent {
+        TextComponent::translate("container.repair", &[])
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::block::{OnPlaceArgs, PlacedArgs};
+use crate::block::{GetComparatorOutputArgs, OnPlaceArgs, PlacedArgs};
 use crate::block::{
     registry::BlockActionResult,
     {BlockBehaviour, NormalUseArgs},
@@ -14,7 +14,7 @@ use pumpkin_macros::pumpkin_block;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
 use pumpkin_world::block::entities::barrel::BarrelBlockEntity;
-use pumpkin_world::inventory::Inventory;
+use pumpkin_world::inventory::{Inventory, calculate_comparator_output};
 use tokio::sync::Mutex;
 
 struct BarrelScreenFactory(Arc<dyn Inventory>);
@@ -68,4 +68,13 @@ impl BlockBehaviour for BarrelBlock {
             .add_block_entity(Arc::new(barrel_block_entity))
             .await;
     }
+
+    async fn get_comparator_output(&self, args: GetComparatorOutputArgs<'_>) -> Option<u8> {
+        let inventory = args
+            .world
+            .get_block_entity(args.position)
+            .await?
+            .get_inventory()?;
+        Some(calculate_comparator_output(inventory.as_ref()).await)
+    }
 }
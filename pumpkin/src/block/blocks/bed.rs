@@ -8,7 +8,6 @@ use pumpkin_data::entity::EntityType;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_macros::pumpkin_block_from_tag;
 use pumpkin_registry::VanillaDimensionType;
-use pumpkin_util::GameMode;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
@@ -127,7 +126,7 @@ impl BlockBehaviour for BedBlock {
             .break_block(
                 &other_half_pos,
                 Some(args.player.clone()),
-                if args.player.gamemode.load() == GameMode::Creative {
+                if args.player.gamemode.load().breaks_blocks_instantly() {
                     BlockFlags::SKIP_DROPS | BlockFlags::NOTIFY_NEIGHBORS
                 } else {
                     BlockFlags::NOTIFY_NEIGHBORS
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block_properties::{
+    BeeNestLikeProperties, BlockProperties, EnumVariants, Integer0To5,
+};
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::boundingbox::BoundingBox;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::block::entities::beehive::{BeeReleaseStatus, BeehiveBlockEntity};
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::world::{BlockFlags, SimpleWorld};
+
+use crate::block::blocks::campfire::is_lit_campfire_in_range;
+use crate::block::registry::BlockActionResult;
+use crate::block::{
+    BlockBehaviour, GetComparatorOutputArgs, OnBreakArgs, PlacedArgs, UseWithItemArgs,
+};
+use crate::entity::EntityBase;
+use crate::entity::player::Player;
+use crate::world::World;
+
+/// How far from a disturbed hive bees outside of it join the attack.
+const ANGER_RANGE: f64 = 8.0;
+/// How many honeycombs shearing a full hive gives.
+const HONEYCOMB_COUNT: u8 = 3;
+
+/// Bee nests and beehives. Bees bring nectar in and turn it into honey, which is harvested with
+/// shears or a glass bottle once the hive is full. Unless a campfire smokes the hive from below,
+/// harvesting or breaking it sends the bees after the player.
+pub struct BeehiveBlock;
+
+#[async_trait]
+impl BlockBehaviour for BeehiveBlock {
+    async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
+        let state_id = args.world.get_block_state_id(args.position).await;
+        let mut props = BeeNestLikeProperties::from_state_id(state_id, args.block);
+        if props.honey_level.to_index() < BeehiveBlockEntity::MAX_HONEY_LEVEL {
+            return BlockActionResult::Pass;
+        }
+
+        let mut item_stack = args.item_stack.lock().await;
+        if item_stack.item == &Item::SHEARS {
+            drop(item_stack);
+            args.world
+                .play_block_sound(
+                    Sound::BlockBeehiveShear,
+                    SoundCategory::Blocks,
+                    *args.position,
+                )
+                .await;
+            args.world
+                .drop_stack(
+                    args.position,
+                    ItemStack::new(HONEYCOMB_COUNT, &Item::HONEYCOMB),
+                )
+                .await;
+        } else if item_stack.item == &Item::GLASS_BOTTLE {
+            item_stack.decrement_unless_creative(args.player.gamemode.load(), 1);
+            drop(item_stack);
+            args.world
+                .play_sound(
+                    Sound::ItemBottleFill,
+                    SoundCategory::Blocks,
+                    &args.player.living_entity.entity.pos.load(),
+                )
+                .await;
+            let mut honey = ItemStack::new(1, &Item::HONEY_BOTTLE);
+            if !args
+                .player
+                .inventory()
+                .insert_stack_anywhere(&mut honey)
+                .await
+            {
+                args.player.drop_item(honey, false).await;
+            }
+        } else {
+            return BlockActionResult::Pass;
+        }
+
+        props.honey_level = Integer0To5::L0;
+        args.world
+            .set_block_state(
+                args.position,
+                props.to_state_id(args.block),
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        if !is_lit_campfire_in_range(args.world, args.position).await
+            && let Some(player) = args
+                .world
+                .get_player_by_uuid(args.player.gameprofile.id)
+                .await
+        {
+            disturb(args.world, args.position, &player).await;
+        }
+        BlockActionResult::Success
+    }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        if args.world.get_block_entity(args.position).await.is_none() {
+            args.world
+                .add_block_entity(Arc::new(BeehiveBlockEntity::new(*args.position)))
+                .await;
+        }
+    }
+
+    async fn on_break(&self, args: OnBreakArgs<'_>) {
+        if !args.player.is_creative() {
+            disturb(args.world, args.position, args.player).await;
+        }
+    }
+
+    async fn get_comparator_output(&self, args: GetComparatorOutputArgs<'_>) -> Option<u8> {
+        let props = BeeNestLikeProperties::from_state_id(args.state.id, args.block);
+        Some(props.honey_level.to_index() as u8)
+    }
+}
+
+/// Lets every bee out of the hive at `position` and sends them, along with the bees around it,
+/// after `player`.
+async fn disturb(world: &Arc<World>, position: &BlockPos, player: &Arc<Player>) {
+    if let Some(block_entity) = world.get_block_entity(position).await
+        && let Some(hive) = block_entity.as_any().downcast_ref::<BeehiveBlockEntity>()
+    {
+        let simple_world: Arc<dyn SimpleWorld> = world.clone();
+        hive.release_all(&simple_world, BeeReleaseStatus::Emergency)
+            .await;
+    }
+
+    let center = position.to_centered_f64();
+    let area = BoundingBox::new(
+        center.sub(&Vector3::new(ANGER_RANGE, 6.0, ANGER_RANGE)),
+        center.add(&Vector3::new(ANGER_RANGE, 6.0, ANGER_RANGE)),
+    );
+    let player: Arc<dyn EntityBase> = player.clone();
+    for entity in world.get_entities_at_box(&area).await {
+        if entity.get_entity().entity_type != &EntityType::BEE {
+            continue;
+        }
+        if let Some(mob) = entity.as_mob() {
+            let mut target = mob.get_mob_entity().target.lock().await;
+            if target.is_none() {
+                *target = Some(player.clone());
+            }
+        }
+    }
+}
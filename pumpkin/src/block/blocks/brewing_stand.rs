@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::block::{BlockBehaviour, GetComparatorOutputArgs, PlacedArgs};
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::block::entities::brewing_stand::BrewingStandBlockEntity;
+use pumpkin_world::inventory::calculate_comparator_output;
+
+#[pumpkin_block("minecraft:brewing_stand")]
+pub struct BrewingStandBlock;
+
+#[async_trait]
+impl BlockBehaviour for BrewingStandBlock {
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        let brewing_stand_block_entity = BrewingStandBlockEntity::new(*args.position);
+        args.world
+            .add_block_entity(Arc::new(brewing_stand_block_entity))
+            .await;
+    }
+
+    async fn get_comparator_output(&self, args: GetComparatorOutputArgs<'_>) -> Option<u8> {
+        let inventory = args
+            .world
+            .get_block_entity(args.position)
+            .await?
+            .get_inventory()?;
+        Some(calculate_comparator_output(inventory.as_ref()).await)
+    }
+}
@@ -17,7 +17,7 @@ use pumpkin_data::{
     sound::{Sound, SoundCategory},
 };
 use pumpkin_macros::pumpkin_block;
-use pumpkin_util::{GameMode, math::position::BlockPos};
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::world::BlockFlags;
 use rand::{Rng, rng};
 
@@ -32,20 +32,17 @@ impl CakeBlock {
         location: &BlockPos,
         state_id: u16,
     ) -> BlockActionResult {
-        match player.gamemode.load() {
-            GameMode::Survival | GameMode::Adventure => {
-                let hunger_level = player.hunger_manager.level.load();
-                if hunger_level >= 20 {
-                    return BlockActionResult::Pass;
-                }
-                player.hunger_manager.level.store(20.min(hunger_level + 2));
-                player
-                    .hunger_manager
-                    .saturation
-                    .store(player.hunger_manager.saturation.load() + 0.4);
-                player.send_health().await;
+        if player.gamemode.load().has_hunger() {
+            let hunger_level = player.hunger_manager.level.load();
+            if hunger_level >= 20 {
+                return BlockActionResult::Pass;
             }
-            GameMode::Creative | GameMode::Spectator => {}
+            player.hunger_manager.level.store(20.min(hunger_level + 2));
+            player
+                .hunger_manager
+                .saturation
+                .store(player.hunger_manager.saturation.load() + 0.4);
+            player.send_health().await;
         }
 
         let mut properties = CakeLikeProperties::from_state_id(state_id, block);
@@ -99,7 +96,7 @@ impl BlockBehaviour for CakeBlock {
                     .await;
                 }
 
-                if args.player.gamemode.load() != GameMode::Creative {
+                if !args.player.gamemode.load().has_infinite_materials() {
                     let held_item = args.player.inventory.held_item();
                     let mut held_item_guard = held_item.lock().await;
                     held_item_guard.decrement(1);
@@ -4,6 +4,7 @@ use pumpkin_data::{
     block_properties::{BlockProperties, CampfireLikeProperties},
     fluid::Fluid,
 };
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::{BlockStateId, tick::TickPriority};
 
 use crate::{
@@ -15,6 +16,7 @@ use crate::{
         },
     },
     entity::EntityBase,
+    world::World,
 };
 
 pub struct CampfireBlock;
@@ -84,3 +86,23 @@ impl BlockBehaviour for CampfireBlock {
 fn is_signal_fire_base_block(block: &Block) -> bool {
     block == &Block::HAY_BLOCK
 }
+
+/// How many blocks the smoke of a campfire rises.
+const SMOKE_RANGE: i32 = 5;
+
+/// Whether the smoke of a lit campfire reaches `position` from below, like the one calming the
+/// bees of a hive. Blocks with a collision shape in between hold the smoke back.
+pub async fn is_lit_campfire_in_range(world: &World, position: &BlockPos) -> bool {
+    for i in 1..=SMOKE_RANGE {
+        let (block, state) = world.get_block_and_state(&position.down_height(i)).await;
+        if (block == &Block::CAMPFIRE || block == &Block::SOUL_CAMPFIRE)
+            && CampfireLikeProperties::from_state_id(state.id, block).lit
+        {
+            return true;
+        }
+        if !state.get_block_collision_shapes().is_empty() {
+            return false;
+        }
+    }
+    false
+}
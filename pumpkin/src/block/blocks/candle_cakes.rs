@@ -7,7 +7,7 @@ use pumpkin_data::{
     tag::{RegistryKey, get_tag_values},
 };
 use pumpkin_macros::pumpkin_block_from_tag;
-use pumpkin_util::{GameMode, math::position::BlockPos};
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::{item::ItemStack, world::BlockFlags};
 
 use crate::{
@@ -70,14 +70,11 @@ impl CandleCakeBlock {
         location: &BlockPos,
         world: &Arc<World>,
     ) -> BlockActionResult {
-        match player.gamemode.load() {
-            GameMode::Survival | GameMode::Adventure => {
-                if player.hunger_manager.level.load() >= 20 {
-                    return BlockActionResult::Pass;
-                }
-            }
-            GameMode::Creative => {}
-            GameMode::Spectator => return BlockActionResult::Pass,
+        let gamemode = player.gamemode.load();
+        if !gamemode.can_interact()
+            || (gamemode.has_hunger() && player.hunger_manager.level.load() >= 20)
+        {
+            return BlockActionResult::Pass;
         }
 
         let candle_item = candle_from_cake(block);
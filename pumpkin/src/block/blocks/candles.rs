@@ -1,21 +1,22 @@
 use async_trait::async_trait;
-use pumpkin_data::item::Item;
 use pumpkin_data::{
-    BlockDirection,
+    Block, BlockDirection,
     block_properties::{BlockProperties, CandleLikeProperties, EnumVariants, Integer1To4},
     entity::EntityPose,
+    fluid::Fluid,
+    sound::{Sound, SoundCategory},
     tag::{RegistryKey, get_tag_values},
 };
 use pumpkin_macros::pumpkin_block_from_tag;
-use pumpkin_world::{BlockStateId, world::BlockFlags};
+use pumpkin_world::{BlockStateId, tick::TickPriority, world::BlockFlags};
 
 use crate::{
     block::{
         BlockIsReplacing,
         registry::BlockActionResult,
         {
-            BlockBehaviour, CanPlaceAtArgs, CanUpdateAtArgs, NormalUseArgs, OnPlaceArgs,
-            UseWithItemArgs,
+            BlockBehaviour, CanPlaceAtArgs, CanUpdateAtArgs, GetStateForNeighborUpdateArgs,
+            NormalUseArgs, OnPlaceArgs, UseWithItemArgs,
         },
     },
     entity::EntityBase,
@@ -31,9 +32,7 @@ impl BlockBehaviour for CandleBlock {
             && let BlockIsReplacing::Itself(state_id) = args.replacing
         {
             let mut properties = CandleLikeProperties::from_state_id(state_id, args.block);
-            if properties.candles.to_index() < 3 {
-                properties.candles = Integer1To4::from_index(properties.candles.to_index() + 1);
-            }
+            properties.candles = add_candle(properties.candles);
             return properties.to_state_id(args.block);
         }
 
@@ -43,56 +42,22 @@ impl BlockBehaviour for CandleBlock {
     }
 
     async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
-        let state = args.world.get_block_state(args.position).await;
-        let mut properties = CandleLikeProperties::from_state_id(state.id, args.block);
-
-        let item_lock = args.item_stack.lock().await;
-        let item = item_lock.item;
-        drop(item_lock);
-        match item.id {
-            id if (Item::CANDLE.id..=Item::BLACK_CANDLE.id).contains(&id)
-                && item.id == args.block.id =>
-            {
-                if properties.candles.to_index() < 3 {
-                    properties.candles = Integer1To4::from_index(properties.candles.to_index() + 1);
-                }
-
-                args.world
-                    .set_block_state(
-                        args.position,
-                        properties.to_state_id(args.block),
-                        BlockFlags::NOTIFY_ALL,
-                    )
-                    .await;
-                BlockActionResult::Consume
-            }
-            _ => {
-                if properties.lit {
-                    properties.lit = false;
-                } else {
-                    return BlockActionResult::Pass;
-                }
-
-                args.world
-                    .set_block_state(
-                        args.position,
-                        properties.to_state_id(args.block),
-                        BlockFlags::NOTIFY_ALL,
-                    )
-                    .await;
-                BlockActionResult::Consume
-            }
+        // Only an empty hand puts the candles out, more are added by placing them on top
+        if args.item_stack.lock().await.is_empty() {
+            BlockActionResult::PassToDefaultBlockAction
+        } else {
+            BlockActionResult::Pass
         }
     }
 
     async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
         let state_id = args.world.get_block_state_id(args.position).await;
         let mut properties = CandleLikeProperties::from_state_id(state_id, args.block);
-
-        if properties.lit {
-            properties.lit = false;
+        if !properties.lit {
+            return BlockActionResult::Pass;
         }
 
+        properties.lit = false;
         args.world
             .set_block_state(
                 args.position,
@@ -100,8 +65,14 @@ impl BlockBehaviour for CandleBlock {
                 BlockFlags::NOTIFY_ALL,
             )
             .await;
-
-        BlockActionResult::Consume
+        args.world
+            .play_sound(
+                Sound::BlockCandleExtinguish,
+                SoundCategory::Blocks,
+                &args.position.to_centered_f64(),
+            )
+            .await;
+        BlockActionResult::Success
     }
 
     async fn can_place_at(&self, args: CanPlaceAtArgs<'_>) -> bool {
@@ -112,6 +83,36 @@ impl BlockBehaviour for CandleBlock {
         !support_block.is_waterlogged(state.id) && state.is_center_solid(BlockDirection::Up)
     }
 
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        if args.direction == BlockDirection::Down
+            && !args
+                .world
+                .get_block_state(args.neighbor_position)
+                .await
+                .is_center_solid(BlockDirection::Up)
+        {
+            return Block::AIR.default_state.id;
+        }
+
+        let mut properties = CandleLikeProperties::from_state_id(args.state_id, args.block);
+        if properties.waterlogged {
+            // Water puts the candles out
+            properties.lit = false;
+            args.world
+                .schedule_fluid_tick(
+                    &Fluid::WATER,
+                    *args.position,
+                    Fluid::WATER.flow_speed as u8,
+                    TickPriority::Normal,
+                )
+                .await;
+        }
+        properties.to_state_id(args.block)
+    }
+
     async fn can_update_at(&self, args: CanUpdateAtArgs<'_>) -> bool {
         let b = args.world.get_block(args.position).await;
         args.player.get_entity().pose.load() != EntityPose::Crouching
@@ -120,3 +121,20 @@ impl BlockBehaviour for CandleBlock {
             && args.block.id == b.id // only the same color can update
     }
 }
+
+/// One more candle on a block, up to the four that fit.
+fn add_candle(candles: Integer1To4) -> Integer1To4 {
+    Integer1To4::from_index((candles.to_index() + 1).min(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_most_four_candles_fit() {
+        assert_eq!(add_candle(Integer1To4::L1), Integer1To4::L2);
+        assert_eq!(add_candle(Integer1To4::L3), Integer1To4::L4);
+        assert_eq!(add_candle(Integer1To4::L4), Integer1To4::L4);
+    }
+}
@@ -1,13 +1,12 @@
-use crate::block::{
-    BlockBehaviour, CanPlaceAtArgs, GetStateForNeighborUpdateArgs, OnScheduledTickArgs,
-};
+use crate::block::support::BlockSupport;
+use crate::block::{BlockBehaviour, CanPlaceAtArgs};
 use async_trait::async_trait;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_data::{Block, BlockDirection};
 use pumpkin_macros::{pumpkin_block, pumpkin_block_from_tag};
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::BlockStateId;
-use pumpkin_world::tick::TickPriority;
-use pumpkin_world::world::{BlockAccessor, BlockFlags};
+use pumpkin_world::world::BlockAccessor;
 
 #[pumpkin_block_from_tag("minecraft:wool_carpets")]
 pub struct CarpetBlock;
@@ -18,24 +17,8 @@ impl BlockBehaviour for CarpetBlock {
         can_place_at(args.block_accessor, args.position).await
     }
 
-    async fn get_state_for_neighbor_update(
-        &self,
-        args: GetStateForNeighborUpdateArgs<'_>,
-    ) -> BlockStateId {
-        if !can_place_at(args.world, args.position).await {
-            args.world
-                .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
-                .await;
-        }
-        args.state_id
-    }
-
-    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
-        if !can_place_at(args.world.as_ref(), args.position).await {
-            args.world
-                .break_block(args.position, None, BlockFlags::empty())
-                .await;
-        }
+    fn supports(&self, _block: &Block, _state_id: BlockStateId) -> &'static [BlockSupport] {
+        &[SUPPORT]
     }
 }
 
@@ -48,24 +31,8 @@ impl BlockBehaviour for MossCarpetBlock {
         can_place_at(args.block_accessor, args.position).await
     }
 
-    async fn get_state_for_neighbor_update(
-        &self,
-        args: GetStateForNeighborUpdateArgs<'_>,
-    ) -> BlockStateId {
-        if !can_place_at(args.world, args.position).await {
-            args.world
-                .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
-                .await;
-        }
-        args.state_id
-    }
-
-    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
-        if !can_place_at(args.world.as_ref(), args.position).await {
-            args.world
-                .break_block(args.position, None, BlockFlags::empty())
-                .await;
-        }
+    fn supports(&self, _block: &Block, _state_id: BlockStateId) -> &'static [BlockSupport] {
+        &[SUPPORT]
     }
 }
 
@@ -78,30 +45,13 @@ impl BlockBehaviour for PaleMossCarpetBlock {
         can_place_at(args.block_accessor, args.position).await
     }
 
-    async fn get_state_for_neighbor_update(
-        &self,
-        args: GetStateForNeighborUpdateArgs<'_>,
-    ) -> BlockStateId {
-        if !can_place_at(args.world, args.position).await {
-            args.world
-                .schedule_block_tick(args.block, *args.position, 1, TickPriority::Normal)
-                .await;
-        }
-        args.state_id
-    }
-
-    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
-        if !can_place_at(args.world.as_ref(), args.position).await {
-            args.world
-                .break_block(args.position, None, BlockFlags::empty())
-                .await;
-        }
+    fn supports(&self, _block: &Block, _state_id: BlockStateId) -> &'static [BlockSupport] {
+        &[SUPPORT]
     }
 }
 
+const SUPPORT: BlockSupport = BlockSupport::Any(BlockDirection::Down);
+
 async fn can_place_at(block_accessor: &dyn BlockAccessor, block_pos: &BlockPos) -> bool {
-    !block_accessor
-        .get_block_state(&block_pos.down())
-        .await
-        .is_air()
+    SUPPORT.is_satisfied(block_accessor, block_pos).await
 }
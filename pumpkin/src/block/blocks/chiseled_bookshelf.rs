@@ -21,12 +21,12 @@ use pumpkin_data::{
 };
 use pumpkin_inventory::screen_handler::InventoryPlayer;
 use pumpkin_macros::pumpkin_block;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use pumpkin_world::{
     BlockStateId, block::entities::chiseled_bookshelf::ChiseledBookshelfBlockEntity,
     inventory::Inventory, item::ItemStack,
 };
-use tokio::sync::Mutex;
 
 #[pumpkin_block("minecraft:chiseled_bookshelf")]
 pub struct ChiseledBookshelfBlock;
@@ -134,7 +134,7 @@ impl ChiseledBookshelfBlock {
         entity: &ChiseledBookshelfBlockEntity,
         properties: ChiseledBookshelfLikeProperties,
         slot: i8,
-        item: &Arc<Mutex<ItemStack>>,
+        item: &Arc<OrderedMutex<ItemStack>>,
     ) {
         // TODO: Increment used stats for chiseled bookshelf on the player
 
@@ -151,6 +151,7 @@ impl ChiseledBookshelfBlock {
                 item.split_unless_creative(player.gamemode.load(), 1),
             )
             .await;
+        entity.mark_dirty();
         entity.update_state(properties, world.clone(), slot).await;
 
         world
@@ -167,6 +168,7 @@ impl ChiseledBookshelfBlock {
         slot: i8,
     ) {
         let mut stack = entity.remove_stack_specific(slot as usize, 1).await;
+        entity.mark_dirty();
 
         let sound = if stack.get_item() == &Item::ENCHANTED_BOOK {
             Sound::BlockChiseledBookshelfPickupEnchanted
@@ -180,7 +182,7 @@ impl ChiseledBookshelfBlock {
             .await
         {
             // Drop the item on the ground if the player cannot hold it because of a full inventory
-            player.drop_item(stack).await;
+            player.drop_item(stack, false).await;
         }
         entity.update_state(properties, world.clone(), slot).await;
 
@@ -241,3 +243,95 @@ impl ChiseledBookshelfBlock {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pumpkin_data::BlockDirection;
+    use pumpkin_util::math::vector3::Vector3;
+
+    fn slot_for(
+        face: BlockDirection,
+        cursor: (f32, f32, f32),
+        facing: HorizontalFacing,
+    ) -> Option<i8> {
+        let cursor_pos = Vector3::new(cursor.0, cursor.1, cursor.2);
+        let hit = BlockHitResult {
+            face: &face,
+            cursor_pos: &cursor_pos,
+        };
+        ChiseledBookshelfBlock::get_slot_for_hit(&hit, facing)
+    }
+
+    #[test]
+    fn columns_split_at_vanilla_offsets() {
+        assert_eq!(ChiseledBookshelfBlock::get_column(0.0), 0);
+        assert_eq!(ChiseledBookshelfBlock::get_column(0.374), 0);
+        assert_eq!(ChiseledBookshelfBlock::get_column(0.375), 1);
+        assert_eq!(ChiseledBookshelfBlock::get_column(0.687), 1);
+        assert_eq!(ChiseledBookshelfBlock::get_column(0.6875), 2);
+        assert_eq!(ChiseledBookshelfBlock::get_column(1.0), 2);
+    }
+
+    #[test]
+    fn slots_are_read_left_to_right_from_the_front() {
+        // Seen from the front, the left edge of a north facing shelf is at x = 1
+        let north = HorizontalFacing::North;
+        assert_eq!(
+            slot_for(BlockDirection::North, (0.9, 0.9, 0.0), north),
+            Some(0)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::North, (0.5, 0.9, 0.0), north),
+            Some(1)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::North, (0.1, 0.9, 0.0), north),
+            Some(2)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::North, (0.9, 0.1, 0.0), north),
+            Some(3)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::North, (0.1, 0.1, 0.0), north),
+            Some(5)
+        );
+
+        let south = HorizontalFacing::South;
+        assert_eq!(
+            slot_for(BlockDirection::South, (0.1, 0.5, 1.0), south),
+            Some(0)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::South, (0.1, 0.49, 1.0), south),
+            Some(3)
+        );
+
+        let east = HorizontalFacing::East;
+        assert_eq!(
+            slot_for(BlockDirection::East, (1.0, 0.6, 0.9), east),
+            Some(0)
+        );
+        assert_eq!(
+            slot_for(BlockDirection::East, (1.0, 0.2, 0.5), east),
+            Some(4)
+        );
+
+        let west = HorizontalFacing::West;
+        assert_eq!(
+            slot_for(BlockDirection::West, (0.0, 0.2, 0.9), west),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn only_the_front_face_has_slots() {
+        let north = HorizontalFacing::North;
+        assert_eq!(
+            slot_for(BlockDirection::South, (0.5, 0.5, 1.0), north),
+            None
+        );
+        assert_eq!(slot_for(BlockDirection::Up, (0.5, 1.0, 0.5), north), None);
+    }
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use pumpkin_data::effect::StatusEffect;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::block::{BlockBehaviour, OnEntityCollisionArgs};
+
+#[pumpkin_block("minecraft:cobweb")]
+pub struct CobwebBlock;
+
+#[async_trait]
+impl BlockBehaviour for CobwebBlock {
+    async fn on_entity_collision(&self, args: OnEntityCollisionArgs<'_>) {
+        let mut multiplier = Vector3::new(0.25, 0.05, 0.25);
+        if let Some(living) = args.entity.get_living_entity() {
+            if living.has_effect(&StatusEffect::WEAVING).await {
+                multiplier = Vector3::new(0.5, 0.25, 0.5);
+            }
+            living.fall_distance.store(0.0);
+        }
+        args.entity
+            .get_entity()
+            .movement_multiplier
+            .store(multiplier);
+    }
+}
@@ -1,11 +1,11 @@
-use std::sync::Arc;
+use std::{any::Any, fmt::Debug, sync::Arc};
 
 use crate::{
     block::{
         registry::BlockActionResult,
         {
-            BlockBehaviour, GetComparatorOutputArgs, NormalUseArgs, OnScheduledTickArgs,
-            UseWithItemArgs,
+            BlockBehaviour, GetComparatorOutputArgs, GetContainerArgs, NormalUseArgs,
+            OnScheduledTickArgs, UseWithItemArgs,
         },
     },
     entity::{Entity, item::ItemEntity},
@@ -13,7 +13,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use pumpkin_data::{
-    Block,
+    Block, BlockDirection,
     block_properties::{BlockProperties, ComposterLikeProperties, EnumVariants, Integer0To8},
     composter_increase_chance::get_composter_increase_chance_from_item_id,
     entity::EntityType,
@@ -21,8 +21,15 @@ use pumpkin_data::{
     world::WorldEvent,
 };
 use pumpkin_macros::pumpkin_block;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::{BlockStateId, item::ItemStack, tick::TickPriority, world::BlockFlags};
+use pumpkin_world::{
+    BlockStateId,
+    inventory::{Clearable, Inventory, SidedInventory, new_slot, split_stack},
+    item::ItemStack,
+    tick::TickPriority,
+    world::BlockFlags,
+};
 use rand::Rng;
 use uuid::Uuid;
 
@@ -50,16 +57,14 @@ impl BlockBehaviour for ComposterBlock {
             self.clear_composter(args.world, args.position, state_id, args.block)
                 .await;
         }
-        if level < 7
-            && let Some(chance) =
-                get_composter_increase_chance_from_item_id(args.item_stack.lock().await.item.id)
-            && (level == 0 || rand::rng().random_bool(f64::from(chance)))
-        {
-            self.update_level_composter(args.world, args.position, state_id, args.block, level + 1)
-                .await;
-            args.world
-                .sync_world_event(WorldEvent::ComposterUsed, *args.position, 1)
-                .await;
+        if level < 7 {
+            let mut item_stack = args.item_stack.lock().await;
+            if self
+                .compost(args.world, args.position, item_stack.item)
+                .await
+            {
+                item_stack.decrement_unless_creative(args.player.gamemode.load(), 1);
+            }
         }
         BlockActionResult::Consume
     }
@@ -78,9 +83,47 @@ impl BlockBehaviour for ComposterBlock {
         let props = ComposterLikeProperties::from_state_id(args.state.id, args.block);
         Some(props.get_level())
     }
+
+    async fn get_container(&self, args: GetContainerArgs<'_>) -> Option<Arc<dyn Inventory>> {
+        let level = ComposterLikeProperties::from_state_id(args.state.id, args.block).get_level();
+        let item = if level == 8 {
+            ItemStack::new(1, &Item::BONE_MEAL)
+        } else {
+            ItemStack::EMPTY.clone()
+        };
+        Some(Arc::new(ComposterInventory {
+            world: args.world.clone(),
+            position: *args.position,
+            level,
+            item: [new_slot(item)],
+        }))
+    }
 }
 
 impl ComposterBlock {
+    /// Puts an item into the composter, which raises the level by the item's chance, and always for
+    /// the first layer. Returns whether the composter took the item.
+    pub async fn compost(&self, world: &Arc<World>, location: &BlockPos, item: &Item) -> bool {
+        let (block, state) = world.get_block_and_state(location).await;
+        let level = ComposterLikeProperties::from_state_id(state.id, block).get_level();
+        let Some(chance) = get_composter_increase_chance_from_item_id(item.id) else {
+            return false;
+        };
+        if level >= 7 {
+            return false;
+        }
+
+        let success = level == 0 || rand::rng().random_bool(f64::from(chance));
+        if success {
+            self.update_level_composter(world, location, state.id, block, level + 1)
+                .await;
+        }
+        world
+            .sync_world_event(WorldEvent::ComposterUsed, *location, i32::from(success))
+            .await;
+        true
+    }
+
     pub async fn update_level_composter(
         &self,
         world: &Arc<World>,
@@ -149,3 +192,101 @@ impl ComposterPropertiesEx for ComposterLikeProperties {
         self.level = Integer0To8::from_index(u16::from(level));
     }
 }
+
+/// The composter as hoppers and droppers see it: a slot for one compostable item from above while
+/// it fills up, and the bone meal from below once it is done.
+struct ComposterInventory {
+    world: Arc<World>,
+    position: BlockPos,
+    level: u8,
+    item: [Arc<OrderedMutex<ItemStack>>; 1],
+}
+
+impl Debug for ComposterInventory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComposterInventory")
+            .field("position", &self.position)
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Inventory for ComposterInventory {
+    fn size(&self) -> usize {
+        self.item.len()
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.item[0].lock().await.is_empty()
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<OrderedMutex<ItemStack>> {
+        self.item[slot].clone()
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        let mut removed = ItemStack::EMPTY.clone();
+        let mut guard = self.item[slot].lock().await;
+        std::mem::swap(&mut removed, &mut *guard);
+        removed
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        split_stack(&self.item, slot, amount).await
+    }
+
+    fn get_max_count_per_stack(&self) -> u8 {
+        1
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        *self.item[slot].lock().await = stack;
+    }
+
+    fn is_valid_slot_for(&self, _slot: usize, stack: &ItemStack) -> bool {
+        self.level < 7 && get_composter_increase_chance_from_item_id(stack.item.id).is_some()
+    }
+
+    fn as_sided(&self) -> Option<&dyn SidedInventory> {
+        Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[async_trait]
+impl SidedInventory for ComposterInventory {
+    fn get_available_slots(&self, side: BlockDirection) -> &'static [usize] {
+        match (side, self.level) {
+            (BlockDirection::Up, 0..7) | (BlockDirection::Down, 8) => &[0],
+            _ => &[],
+        }
+    }
+
+    async fn on_items_moved(&self) {
+        let item = std::mem::replace(&mut *self.item[0].lock().await, ItemStack::EMPTY.clone());
+        if self.level == 8 {
+            // The bone meal was taken out
+            if item.is_empty() {
+                let (block, state) = self.world.get_block_and_state(&self.position).await;
+                ComposterBlock
+                    .update_level_composter(&self.world, &self.position, state.id, block, 0)
+                    .await;
+            }
+        } else if !item.is_empty() {
+            ComposterBlock
+                .compost(&self.world, &self.position, item.item)
+                .await;
+        }
+    }
+}
+
+#[async_trait]
+impl Clearable for ComposterInventory {
+    async fn clear(&self) {
+        *self.item[0].lock().await = ItemStack::EMPTY.clone();
+    }
+}
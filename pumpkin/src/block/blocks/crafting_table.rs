@@ -31,10 +31,11 @@ impl ScreenHandlerFactory for CraftingTableScreenFactory {
         &self,
         sync_id: u8,
         player_inventory: &Arc<PlayerInventory>,
-        _player: &dyn InventoryPlayer,
+        player: &dyn InventoryPlayer,
     ) -> Option<Arc<Mutex<dyn ScreenHandler>>> {
         Some(Arc::new(Mutex::new(
-            CraftingTableScreenHandler::new(sync_id, player_inventory).await,
+            CraftingTableScreenHandler::new(sync_id, player_inventory, player.get_recipe_book())
+                .await,
         )))
     }
 
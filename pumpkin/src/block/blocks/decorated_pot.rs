@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use crate::{
+    block::{
+        registry::BlockActionResult,
+        {
+            BlockBehaviour, GetComparatorOutputArgs, NormalUseArgs, OnBreakArgs, OnPlaceArgs,
+            OnSyncedBlockEventArgs, PlacedArgs, PlayerPlacedArgs, UseWithItemArgs,
+        },
+    },
+    entity::EntityBase,
+};
+use async_trait::async_trait;
+use pumpkin_data::{
+    block_properties::{BlockProperties, DecoratedPotLikeProperties},
+    data_component_impl::{EnchantmentsImpl, PotDecorationsImpl},
+    item::Item,
+    particle::Particle,
+    sound::{Sound, SoundCategory},
+    tag,
+    tag::Taggable,
+};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::{
+    BlockStateId, block::entities::decorated_pot::DecoratedPotBlockEntity, inventory::Inventory,
+    world::BlockFlags,
+};
+
+/// The block event type that makes the pot wobble on the client.
+const WOBBLE_EVENT_TYPE: u8 = 1;
+/// Wobble directions, the pot wobbles happily when an item goes in and shakes its head otherwise.
+const WOBBLE_POSITIVE: u8 = 0;
+const WOBBLE_NEGATIVE: u8 = 1;
+
+#[pumpkin_block("minecraft:decorated_pot")]
+pub struct DecoratedPotBlock;
+
+#[async_trait]
+impl BlockBehaviour for DecoratedPotBlock {
+    async fn on_place(&self, args: OnPlaceArgs<'_>) -> BlockStateId {
+        let mut properties = DecoratedPotLikeProperties::default(args.block);
+        properties.facing = args.player.get_entity().get_horizontal_facing();
+        properties.waterlogged = args.replacing.water_source();
+        properties.to_state_id(args.block)
+    }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        let block_entity =
+            DecoratedPotBlockEntity::new(*args.position, PotDecorationsImpl::default());
+        args.world.add_block_entity(Arc::new(block_entity)).await;
+    }
+
+    async fn player_placed(&self, args: PlayerPlacedArgs<'_>) {
+        // The pot may have been placed from either hand
+        let inventory = args.player.inventory();
+        let main_hand = inventory.held_item();
+        let held = if main_hand.lock().await.item == &Item::DECORATED_POT {
+            main_hand
+        } else {
+            inventory.off_hand_item().await
+        };
+        let Some(sherds) = held
+            .lock()
+            .await
+            .get_data_component::<PotDecorationsImpl>()
+            .cloned()
+        else {
+            return;
+        };
+        let block_entity = DecoratedPotBlockEntity::new(*args.position, sherds);
+        args.world.add_block_entity(Arc::new(block_entity)).await;
+    }
+
+    async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
+        let Some(block_entity) = args.world.get_block_entity(args.position).await else {
+            return BlockActionResult::Pass;
+        };
+        let Some(pot) = block_entity
+            .as_any()
+            .downcast_ref::<DecoratedPotBlockEntity>()
+        else {
+            return BlockActionResult::Pass;
+        };
+
+        let mut stack = args.item_stack.lock().await;
+        if stack.is_empty() || !pot.can_insert(&stack).await {
+            return BlockActionResult::PassToDefaultBlockAction;
+        }
+
+        let inserted = stack.split_unless_creative(args.player.gamemode.load(), 1);
+        drop(stack);
+        let fullness = {
+            let mut item = pot.item[0].lock().await;
+            if item.is_empty() {
+                *item = inserted;
+            } else {
+                item.increment(1);
+            }
+            f32::from(item.item_count) / f32::from(item.get_max_stack_size())
+        };
+        pot.mark_dirty();
+        args.world.update_block_entity(&block_entity).await;
+
+        args.world
+            .add_synced_block_event(*args.position, WOBBLE_EVENT_TYPE, WOBBLE_POSITIVE)
+            .await;
+        args.world
+            .play_sound_fine(
+                Sound::BlockDecoratedPotInsert,
+                SoundCategory::Blocks,
+                &args.position.to_centered_f64(),
+                1.0,
+                0.7 + 0.5 * fullness,
+            )
+            .await;
+        args.world
+            .spawn_particle(
+                args.position.to_f64().add_raw(0.5, 1.2, 0.5),
+                Vector3::new(0.0, 0.0, 0.0),
+                0.0,
+                7,
+                Particle::DustPlume,
+            )
+            .await;
+        BlockActionResult::Success
+    }
+
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        if args.world.get_block_entity(args.position).await.is_none() {
+            return BlockActionResult::Pass;
+        }
+        args.world
+            .play_sound(
+                Sound::BlockDecoratedPotInsertFail,
+                SoundCategory::Blocks,
+                &args.position.to_centered_f64(),
+            )
+            .await;
+        args.world
+            .add_synced_block_event(*args.position, WOBBLE_EVENT_TYPE, WOBBLE_NEGATIVE)
+            .await;
+        BlockActionResult::Success
+    }
+
+    async fn on_synced_block_event(&self, args: OnSyncedBlockEventArgs<'_>) -> bool {
+        // Wobbling is only an animation, the client takes care of it
+        args.r#type == WOBBLE_EVENT_TYPE
+    }
+
+    async fn on_break(&self, args: OnBreakArgs<'_>) {
+        // Pots broken with a weapon or tool shatter into their sherds, unless the tool is
+        // enchanted to keep them whole
+        let tool = args.player.inventory().held_item();
+        let tool = tool.lock().await;
+        let shatters = tool
+            .get_item()
+            .is_tagged_with_by_tag(&tag::Item::MINECRAFT_BREAKS_DECORATED_POTS)
+            && !tool
+                .get_data_component::<EnchantmentsImpl>()
+                .is_some_and(|enchantments| {
+                    enchantments.enchantment.iter().any(|(enchantment, _)| {
+                        enchantment.is_tagged_with_by_tag(
+                            &tag::Enchantment::MINECRAFT_PREVENTS_DECORATED_POT_SHATTERING,
+                        )
+                    })
+                });
+        drop(tool);
+        if !shatters {
+            return;
+        }
+
+        // The loot table drops the sherds instead of the pot for a cracked pot
+        let mut properties = DecoratedPotLikeProperties::from_state_id(args.state_id, args.block);
+        properties.cracked = true;
+        args.world
+            .set_block_state(
+                args.position,
+                properties.to_state_id(args.block),
+                BlockFlags::empty(),
+            )
+            .await;
+    }
+
+    async fn get_comparator_output(&self, args: GetComparatorOutputArgs<'_>) -> Option<u8> {
+        let block_entity = args.world.get_block_entity(args.position).await?;
+        let pot = block_entity
+            .as_any()
+            .downcast_ref::<DecoratedPotBlockEntity>()?;
+        Some(pot.get_comparator_output().await)
+    }
+}
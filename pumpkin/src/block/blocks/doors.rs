@@ -21,6 +21,7 @@ use std::sync::Arc;
 
 use crate::block::BlockBehaviour;
 use crate::block::CanPlaceAtArgs;
+use crate::block::ExplodeArgs;
 use crate::block::GetStateForNeighborUpdateArgs;
 use crate::block::NormalUseArgs;
 use crate::block::OnNeighborUpdateArgs;
@@ -35,44 +36,41 @@ use crate::world::World;
 
 type DoorProperties = pumpkin_data::block_properties::OakDoorLikeProperties;
 
-async fn toggle_door(player: &Player, world: &Arc<World>, block_pos: &BlockPos) {
-    let (block, block_state) = world.get_block_and_state_id(block_pos).await;
-    let mut door_props = DoorProperties::from_state_id(block_state, block);
-    door_props.open = !door_props.open;
+/// Opens or closes the door at `block_pos`. The sound is not sent to `player`, who plays it
+/// client side.
+async fn toggle_door(player: Option<&Player>, world: &Arc<World>, block_pos: &BlockPos) {
+    let Some((_, new_state_id)) = world
+        .modify_block_state::<DoorProperties>(block_pos, BlockFlags::NOTIFY_LISTENERS, |props| {
+            props.open = !props.open
+        })
+        .await
+    else {
+        return;
+    };
+    let block = Block::from_state_id(new_state_id);
+    let door_props = DoorProperties::from_state_id(new_state_id, block);
 
     let other_half = match door_props.half {
         DoubleBlockHalf::Upper => BlockDirection::Down,
         DoubleBlockHalf::Lower => BlockDirection::Up,
     };
     let other_pos = block_pos.offset(other_half.to_offset());
-
-    let (other_block, other_state_id) = world.get_block_and_state_id(&other_pos).await;
-    let mut other_door_props = DoorProperties::from_state_id(other_state_id, other_block);
-    other_door_props.open = door_props.open;
-
     world
-        .play_block_sound_expect(
-            player,
-            get_sound(block, door_props.open),
-            SoundCategory::Blocks,
-            *block_pos,
-        )
+        .modify_block_state::<DoorProperties>(&other_pos, BlockFlags::NOTIFY_LISTENERS, |props| {
+            props.open = door_props.open
+        })
         .await;
 
-    world
-        .set_block_state(
-            block_pos,
-            door_props.to_state_id(block),
-            BlockFlags::NOTIFY_LISTENERS,
-        )
-        .await;
-    world
-        .set_block_state(
-            &other_pos,
-            other_door_props.to_state_id(other_block),
-            BlockFlags::NOTIFY_LISTENERS,
-        )
-        .await;
+    let sound = get_sound(block, door_props.open);
+    if let Some(player) = player {
+        world
+            .play_block_sound_expect(player, sound, SoundCategory::Blocks, *block_pos)
+            .await;
+    } else {
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, *block_pos)
+            .await;
+    }
 }
 
 fn can_open_door(block: &Block) -> bool {
@@ -204,11 +202,23 @@ impl BlockBehaviour for DoorBlock {
             return BlockActionResult::Pass;
         }
 
-        toggle_door(args.player, args.world, args.position).await;
+        toggle_door(Some(args.player), args.world, args.position).await;
 
         BlockActionResult::Success
     }
 
+    async fn on_wind_burst(&self, args: ExplodeArgs<'_>) {
+        let state_id = args.world.get_block_state_id(args.position).await;
+        let door_props = DoorProperties::from_state_id(state_id, args.block);
+        // Only the lower half reacts, so a burst catching both halves does not toggle it twice
+        if can_open_door(args.block)
+            && !door_props.powered
+            && door_props.half == DoubleBlockHalf::Lower
+        {
+            toggle_door(None, args.world, args.position).await;
+        }
+    }
+
     async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
         let block_state = args.world.get_block_state(args.position).await;
         let mut door_props = DoorProperties::from_state_id(block_state.id, args.block);
@@ -0,0 +1,272 @@
+use std::sync::{Arc, atomic::Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_registry::VanillaDimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{block::entities::end_gateway::EndGatewayBlockEntity, world::BlockFlags};
+use rand::Rng;
+
+use crate::{
+    block::{BlockBehaviour, OnEntityCollisionArgs, OnSyncedBlockEventArgs, PlacedArgs},
+    entity::EntityBase,
+    world::{World, portal::PortalTeleport},
+};
+
+/// How far out gateways on the main island send entities at first.
+const EXIT_SEARCH_DISTANCE: f64 = 1024.0;
+/// The step and the number of steps used to find the edge of the outer islands.
+const EXIT_SEARCH_STEP: f64 = 16.0;
+const EXIT_SEARCH_STEPS: u32 = 16;
+/// The y level islands are generated at when there is no land to put an exit gateway on.
+const ISLAND_Y: f64 = 75.0;
+
+#[pumpkin_block("minecraft:end_gateway")]
+pub struct EndGatewayBlock;
+
+#[async_trait]
+impl BlockBehaviour for EndGatewayBlock {
+    async fn on_entity_collision(&self, args: OnEntityCollisionArgs<'_>) {
+        let Some(block_entity) = args.world.get_block_entity(args.position).await else {
+            return;
+        };
+        let Some(gateway) = block_entity
+            .as_any()
+            .downcast_ref::<EndGatewayBlockEntity>()
+        else {
+            return;
+        };
+        if gateway.is_recently_generated() || gateway.is_cooling_down() {
+            return;
+        }
+
+        let entity = args.entity.get_entity();
+        if entity.has_vehicle().await {
+            return;
+        }
+        // Entities standing in the gateway have to step out before it takes them again
+        if entity.portal_cooldown.load(Ordering::Relaxed) > 0 {
+            entity
+                .portal_cooldown
+                .store(entity.default_portal_cooldown(), Ordering::Relaxed);
+            return;
+        }
+        let Some(entity) = args.world.get_entity_by_uuid(entity.entity_uuid).await else {
+            return;
+        };
+
+        let exit = match gateway.exit_portal.load() {
+            Some(exit) => exit,
+            // Gateways on the main island find and build their exit the first time they are used
+            None if args.world.dimension_type == VanillaDimensionType::TheEnd => {
+                let exit = setup_exit_portal(args.world, *args.position).await;
+                gateway.set_exit_portal(exit, false);
+                exit
+            }
+            None => return,
+        };
+        let target = if gateway.exact_teleport.load(Ordering::Relaxed) {
+            exit
+        } else {
+            find_exit_portal_pos(args.world, exit.up_height(2), 5, false)
+                .await
+                .up()
+        };
+
+        entity.get_entity().portal_cooldown.store(
+            entity.get_entity().default_portal_cooldown(),
+            Ordering::Relaxed,
+        );
+        args.world
+            .queue_portal_teleport(PortalTeleport {
+                entity,
+                world: args.world.clone(),
+                position: target.to_f64().add_raw(0.5, 0.0, 0.5),
+            })
+            .await;
+        gateway.start_teleport_cooldown(args.world.as_ref()).await;
+    }
+
+    async fn on_synced_block_event(&self, args: OnSyncedBlockEventArgs<'_>) -> bool {
+        // The beam is only an effect, the client takes care of it
+        args.r#type == EndGatewayBlockEntity::BEAM_EVENT_TYPE
+    }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        let block_entity = EndGatewayBlockEntity::new(*args.position, None, false);
+        args.world.add_block_entity(Arc::new(block_entity)).await;
+    }
+}
+
+/// Finds land on the outer islands in the direction of the gateway at `gateway_pos`, generating
+/// an island if there is none, and builds an exit gateway leading back there.
+async fn setup_exit_portal(world: &Arc<World>, gateway_pos: BlockPos) -> BlockPos {
+    let target = find_teleport_location(world, gateway_pos).await;
+    let chunk = Vector2::new(
+        (target.x.floor() as i32) >> 4,
+        (target.z.floor() as i32) >> 4,
+    );
+    let land = if let Some(land) = find_portal_position(world, chunk).await {
+        land
+    } else {
+        let island = BlockPos::floored(target.x + 0.5, ISLAND_Y, target.z + 0.5);
+        generate_end_island(world, island).await;
+        island
+    };
+
+    // The exit gateway floats a bit above the land
+    let exit = find_exit_portal_pos(world, land, 16, true)
+        .await
+        .up_height(10);
+    place_gateway(world, exit, gateway_pos).await;
+    exit
+}
+
+/// Goes out from the main island in the direction of `gateway_pos` until reaching the edge of the
+/// outer islands.
+async fn find_teleport_location(world: &World, gateway_pos: BlockPos) -> Vector3<f64> {
+    let direction =
+        Vector3::new(f64::from(gateway_pos.0.x), 0.0, f64::from(gateway_pos.0.z)).normalize();
+    let mut target = direction * EXIT_SEARCH_DISTANCE;
+    for _ in 0..EXIT_SEARCH_STEPS {
+        if is_chunk_empty(world, target).await {
+            break;
+        }
+        target = target + direction * -EXIT_SEARCH_STEP;
+    }
+    for _ in 0..EXIT_SEARCH_STEPS {
+        if !is_chunk_empty(world, target).await {
+            break;
+        }
+        target = target + direction * EXIT_SEARCH_STEP;
+    }
+    target
+}
+
+async fn is_chunk_empty(world: &World, pos: Vector3<f64>) -> bool {
+    let start_x = (pos.x.floor() as i32) & !15;
+    let start_z = (pos.z.floor() as i32) & !15;
+    for x in start_x..start_x + 16 {
+        for z in start_z..start_z + 16 {
+            if world.get_top_block(Vector2::new(x, z)).await >= world.min_y {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The end stone block closest to the world origin in `chunk` with room for a gateway above it.
+async fn find_portal_position(world: &World, chunk: Vector2<i32>) -> Option<BlockPos> {
+    let mut closest: Option<(BlockPos, i32)> = None;
+    for x in chunk.x * 16..chunk.x * 16 + 16 {
+        for z in chunk.y * 16..chunk.y * 16 + 16 {
+            let top = world.get_top_block(Vector2::new(x, z)).await;
+            for y in 30..=top {
+                let pos = BlockPos::new(x, y, z);
+                if world.get_block(&pos).await != &Block::END_STONE
+                    || world.get_block_state(&pos.up()).await.is_full_cube()
+                    || world
+                        .get_block_state(&pos.up_height(2))
+                        .await
+                        .is_full_cube()
+                {
+                    continue;
+                }
+                let distance = x * x + y * y + z * z;
+                if closest.is_none_or(|(_, closest)| distance < closest) {
+                    closest = Some((pos, distance));
+                }
+            }
+        }
+    }
+    closest.map(|(pos, _)| pos)
+}
+
+/// The highest full block within `radius` columns around `pos`, or `pos` itself if there is none.
+/// The column of `pos` and bedrock only count when `force` is set.
+async fn find_exit_portal_pos(world: &World, pos: BlockPos, radius: i32, force: bool) -> BlockPos {
+    let mut highest: Option<BlockPos> = None;
+    for dx in -radius..=radius {
+        for dz in -radius..=radius {
+            if dx == 0 && dz == 0 && !force {
+                continue;
+            }
+            let (x, z) = (pos.0.x + dx, pos.0.z + dz);
+            let bottom = highest.map_or(world.min_y, |highest| highest.0.y);
+            let mut y = world.get_top_block(Vector2::new(x, z)).await;
+            while y > bottom {
+                let candidate = BlockPos::new(x, y, z);
+                let (block, state) = world.get_block_and_state(&candidate).await;
+                if state.is_full_cube() && (force || block != &Block::BEDROCK) {
+                    highest = Some(candidate);
+                    break;
+                }
+                y -= 1;
+            }
+        }
+    }
+    highest.unwrap_or(pos)
+}
+
+/// Generates a small floating island of end stone hanging down from `pos`.
+async fn generate_end_island(world: &Arc<World>, pos: BlockPos) {
+    let mut rng = rand::rng();
+    let mut radius = f64::from(rng.random_range(4..7));
+    let mut y = 0;
+    while radius > 0.5 {
+        let reach = radius.ceil() as i32;
+        for x in -reach..=reach {
+            for z in -reach..=reach {
+                if f64::from(x * x + z * z) <= (radius + 1.0) * (radius + 1.0) {
+                    world
+                        .set_block_state(
+                            &pos.offset(Vector3::new(x, y, z)),
+                            Block::END_STONE.default_state.id,
+                            BlockFlags::NOTIFY_ALL,
+                        )
+                        .await;
+                }
+            }
+        }
+        radius -= f64::from(rng.random_range(0..2)) + 0.5;
+        y -= 1;
+    }
+}
+
+/// Builds a gateway at `pos` framed by bedrock above and below, leading to `exit`.
+async fn place_gateway(world: &Arc<World>, pos: BlockPos, exit: BlockPos) {
+    for dx in -1..=1 {
+        for dy in -2..=2 {
+            for dz in -1..=1 {
+                let is_center_column = dx == 0 && dz == 0;
+                let is_cap = dy.abs() == 2;
+                let block = if is_center_column && dy == 0 {
+                    &Block::END_GATEWAY
+                } else if dy == 0 {
+                    &Block::AIR
+                } else if (dx == 0 || dz == 0) && !is_cap || is_center_column {
+                    &Block::BEDROCK
+                } else {
+                    &Block::AIR
+                };
+                world
+                    .set_block_state(
+                        &pos.offset(Vector3::new(dx, dy, dz)),
+                        block.default_state.id,
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+            }
+        }
+    }
+
+    if let Some(block_entity) = world.get_block_entity(&pos).await
+        && let Some(gateway) = block_entity
+            .as_any()
+            .downcast_ref::<EndGatewayBlockEntity>()
+    {
+        gateway.set_exit_portal(exit, false);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::block::registry::BlockActionResult;
-use crate::block::{BlockBehaviour, RandomTickArgs, UseWithItemArgs};
+use crate::block::{BlockBehaviour, NormalUseArgs, RandomTickArgs, UseWithItemArgs};
 use async_trait::async_trait;
 use pumpkin_data::Block;
-use pumpkin_data::flower_pot_transformations::get_potted_item;
+use pumpkin_data::flower_pot_transformations::{get_potted_content, get_potted_item};
+use pumpkin_data::item::Item;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_macros::pumpkin_block_from_tag;
 use pumpkin_registry::VanillaDimensionType;
+use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::BlockFlags;
 
 #[pumpkin_block_from_tag("minecraft:flower_pots")]
@@ -14,26 +16,42 @@ pub struct FlowerPotBlock;
 #[async_trait]
 impl BlockBehaviour for FlowerPotBlock {
     async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
-        let item = args.item_stack.lock().await.item;
-        //Place the flower inside the pot
-        let potted_block_id = get_potted_item(item.id);
-        if args.block.eq(&Block::FLOWER_POT) {
-            if potted_block_id != 0 {
-                args.world
-                    .set_block_state(
-                        args.position,
-                        Block::from_id(potted_block_id).default_state.id,
-                        BlockFlags::NOTIFY_ALL,
-                    )
-                    .await;
-            }
-            return BlockActionResult::Success;
-        } else if potted_block_id != 0 {
-            //if the player have an item that can be potted in his hand, nothing happens
+        let mut item_stack = args.item_stack.lock().await;
+        let potted_block_id = get_potted_item(item_stack.item.id);
+        if potted_block_id == 0 {
+            // Anything that can't be potted takes the plant out like an empty hand
+            return BlockActionResult::PassToDefaultBlockAction;
+        }
+        if args.block != &Block::FLOWER_POT {
+            // A plant can't be swapped for another one
             return BlockActionResult::Consume;
         }
 
-        //get the flower + empty the pot
+        args.world
+            .set_block_state(
+                args.position,
+                Block::from_id(potted_block_id).default_state.id,
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        item_stack.decrement_unless_creative(args.player.gamemode.load(), 1);
+        BlockActionResult::Success
+    }
+
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        let Some(content) = Item::from_id(get_potted_content(args.block.id)) else {
+            return BlockActionResult::Consume;
+        };
+
+        let mut stack = ItemStack::new(1, content);
+        if !args
+            .player
+            .inventory()
+            .insert_stack_anywhere(&mut stack)
+            .await
+        {
+            args.player.drop_item(stack, false).await;
+        }
         args.world
             .set_block_state(
                 args.position,
@@ -8,21 +8,33 @@ use pumpkin_inventory::{
 use pumpkin_macros::pumpkin_block;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::{
-    block::entities::{BlockEntity, furnace::FurnaceBlockEntity},
+    block::entities::{
+        BlockEntity,
+        furnace::{FurnaceBlockEntity, FurnaceKind},
+    },
     inventory::Inventory,
 };
 use tokio::sync::Mutex;
 
-use crate::block::BlockBehaviour;
+use crate::block::{
+    BlockBehaviour, BrokenArgs, NormalUseArgs, PlacedArgs, registry::BlockActionResult,
+};
+use crate::entity::experience_orb::ExperienceOrbEntity;
 
 struct FurnaceScreenFactory {
+    kind: FurnaceKind,
     inventory: Arc<dyn Inventory>,
     block_entity: Arc<dyn BlockEntity>,
 }
 
 impl FurnaceScreenFactory {
-    fn new(inventory: Arc<dyn Inventory>, block_entity: Arc<dyn BlockEntity>) -> Self {
+    fn new(
+        kind: FurnaceKind,
+        inventory: Arc<dyn Inventory>,
+        block_entity: Arc<dyn BlockEntity>,
+    ) -> Self {
         Self {
+            kind,
             inventory,
             block_entity,
         }
@@ -39,6 +51,7 @@ impl ScreenHandlerFactory for FurnaceScreenFactory {
     ) -> Option<Arc<Mutex<dyn pumpkin_inventory::screen_handler::ScreenHandler>>> {
         let furnace_screen_handler = FurnaceScreenHandler::new(
             sync_id,
+            self.kind.window_type(),
             player_inventory,
             self.inventory.clone(),
             self.block_entity.clone(),
@@ -48,8 +61,44 @@ impl ScreenHandlerFactory for FurnaceScreenFactory {
     }
 
     fn get_display_name(&self) -> pumpkin_util::text::TextComponent {
-        TextComponent::translate("container.furnace", &[])
+        TextComponent::translate(self.kind.translation_key(), &[])
+    }
+}
+
+/// Opens the screen of the furnace, smoker or blast furnace that was used.
+pub(super) async fn open_furnace_screen(args: &NormalUseArgs<'_>) -> BlockActionResult {
+    if let Some(block_entity) = args.world.get_block_entity(args.position).await
+        && let Some(furnace) = block_entity.as_any().downcast_ref::<FurnaceBlockEntity>()
+        && let Some(inventory) = block_entity.clone().get_inventory()
+    {
+        let furnace_screen_factory =
+            FurnaceScreenFactory::new(furnace.kind, inventory, block_entity.clone());
+        args.player
+            .open_handled_screen(&furnace_screen_factory)
+            .await;
+    }
+    BlockActionResult::Consume
+}
+
+pub(super) async fn add_furnace_block_entity(args: &PlacedArgs<'_>, kind: FurnaceKind) {
+    let furnace_block_entity = FurnaceBlockEntity::new(*args.position, kind);
+    args.world
+        .add_block_entity(Arc::new(furnace_block_entity))
+        .await;
+}
+
+/// Removes the block entity, dropping the experience it collected.
+pub(super) async fn remove_furnace_block_entity(args: &BrokenArgs<'_>) {
+    if let Some(block_entity) = args.world.get_block_entity(args.position).await
+        && let Some(furnace) = block_entity.as_any().downcast_ref::<FurnaceBlockEntity>()
+    {
+        let experience = furnace.take_experience().await;
+        if experience > 0 {
+            ExperienceOrbEntity::spawn(args.world, args.position.to_centered_f64(), experience)
+                .await;
+        }
     }
+    args.world.remove_block_entity(args.position).await;
 }
 
 #[pumpkin_block("minecraft:furnace")]
@@ -61,15 +110,7 @@ impl BlockBehaviour for FurnaceBlock {
         &self,
         args: crate::block::NormalUseArgs<'_>,
     ) -> crate::block::registry::BlockActionResult {
-        if let Some(block_entity) = args.world.get_block_entity(args.position).await
-            && let Some(inventory) = block_entity.clone().get_inventory()
-        {
-            let furnace_screen_factory = FurnaceScreenFactory::new(inventory, block_entity);
-            args.player
-                .open_handled_screen(&furnace_screen_factory)
-                .await;
-        }
-        crate::block::registry::BlockActionResult::Consume
+        open_furnace_screen(&args).await
     }
 
     //Same to normal_use
@@ -115,16 +156,13 @@ impl BlockBehaviour for FurnaceBlock {
     }
 
     async fn placed(&self, args: crate::block::PlacedArgs<'_>) {
-        let furnace_block_entity = FurnaceBlockEntity::new(*args.position);
-        args.world
-            .add_block_entity(Arc::new(furnace_block_entity))
-            .await;
+        add_furnace_block_entity(&args, FurnaceKind::Furnace).await;
     }
 
     async fn player_placed(&self, _args: crate::block::PlayerPlacedArgs<'_>) {}
 
     async fn broken(&self, args: crate::block::BrokenArgs<'_>) {
-        args.world.remove_block_entity(args.position).await;
+        remove_furnace_block_entity(&args).await;
     }
 
     async fn on_neighbor_update(&self, _args: crate::block::OnNeighborUpdateArgs<'_>) {}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use std::sync::atomic::Ordering;
+
+use crate::block::{BlockBehaviour, FallDamage, OnEntityCollisionArgs, OnLandedUponArgs};
+use crate::entity::Entity;
+
+/// Entities pressed against the side of a honey block slide down slowly.
+const SLIDE_VELOCITY: f64 = -0.05;
+
+#[pumpkin_block("minecraft:honey_block")]
+pub struct HoneyBlock;
+
+#[async_trait]
+impl BlockBehaviour for HoneyBlock {
+    async fn on_entity_collision(&self, args: OnEntityCollisionArgs<'_>) {
+        let entity = args.entity.get_entity();
+        if !is_sliding(args.position, entity) {
+            return;
+        }
+
+        let velocity = entity.velocity.load();
+        let factor = if velocity.y < -0.13 {
+            SLIDE_VELOCITY / velocity.y
+        } else {
+            1.0
+        };
+        entity.velocity.store(Vector3::new(
+            velocity.x * factor,
+            SLIDE_VELOCITY,
+            velocity.z * factor,
+        ));
+
+        if let Some(living) = args.entity.get_living_entity() {
+            living.fall_distance.store(0.0);
+        }
+        // TODO: Play slide sound and particles
+    }
+
+    async fn on_landed_upon(&self, args: OnLandedUponArgs<'_>) -> FallDamage {
+        FallDamage {
+            multiplier: 0.2,
+            ..FallDamage::new(args.fall_distance)
+        }
+    }
+}
+
+fn is_sliding(position: &BlockPos, entity: &Entity) -> bool {
+    if entity.on_ground.load(Ordering::Relaxed) {
+        return false;
+    }
+    let pos = entity.pos.load();
+    if pos.y > f64::from(position.0.y) + 0.9375 - 1.0e-7 {
+        return false;
+    }
+    if entity.velocity.load().y >= -0.08 {
+        return false;
+    }
+    let dx = (f64::from(position.0.x) + 0.5 - pos.x).abs();
+    let dz = (f64::from(position.0.z) + 0.5 - pos.z).abs();
+    let max = 0.4375 + f64::from(entity.bounding_box_size.load().width) / 2.0;
+    dx + 1.0e-7 > max || dz + 1.0e-7 > max
+}
@@ -1,98 +1,107 @@
 use std::sync::Arc;
 
 use crate::block::registry::BlockActionResult;
-use crate::block::{BlockBehaviour, BrokenArgs, UseWithItemArgs};
+use crate::block::{BlockBehaviour, BrokenArgs, NormalUseArgs, PlacedArgs, UseWithItemArgs};
+use crate::entity::Entity;
+use crate::entity::item::ItemEntity;
 use crate::world::World;
 use async_trait::async_trait;
-use pumpkin_data::data_component_impl::JukeboxPlayableImpl;
+use pumpkin_data::entity::EntityType;
 use pumpkin_data::world::WorldEvent;
-use pumpkin_data::{
-    Block,
-    block_properties::{BlockProperties, JukeboxLikeProperties},
-};
 use pumpkin_macros::pumpkin_block;
-use pumpkin_registry::SYNCED_REGISTRIES;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::world::BlockFlags;
+use pumpkin_world::block::entities::jukebox::JukeboxBlockEntity;
+use pumpkin_world::inventory::Inventory;
+use rand::Rng;
+use uuid::Uuid;
 
 #[pumpkin_block("minecraft:jukebox")]
 pub struct JukeboxBlock;
 
 impl JukeboxBlock {
-    async fn has_record(&self, block: &Block, location: &BlockPos, world: &World) -> bool {
-        let state_id = world.get_block_state(location).await.id;
-        JukeboxLikeProperties::from_state_id(state_id, block).has_record
+    /// The jukebox's record slot. The block entity plays and stops the song once the record
+    /// changes, whether a player or a hopper changed it.
+    async fn get_record_inventory(world: &Arc<World>, position: &BlockPos) -> Arc<dyn Inventory> {
+        if let Some(inventory) = world
+            .get_block_entity(position)
+            .await
+            .and_then(|block_entity| block_entity.get_inventory())
+        {
+            return inventory;
+        }
+        // Jukeboxes saved before they had a block entity
+        let jukebox = Arc::new(JukeboxBlockEntity::new(*position));
+        world.add_block_entity(jukebox.clone()).await;
+        jukebox
     }
 
-    async fn set_record(
-        &self,
-        has_record: bool,
-        block: &Block,
-        location: &BlockPos,
-        world: &Arc<World>,
-    ) {
-        let new_state = JukeboxLikeProperties { has_record };
-        world
-            .set_block_state(location, new_state.to_state_id(block), BlockFlags::empty())
-            .await;
-    }
+    async fn pop_out_record(world: &Arc<World>, position: &BlockPos, inventory: &dyn Inventory) {
+        let record = inventory.remove_stack(0).await;
+        if record.is_empty() {
+            return;
+        }
+        inventory.mark_dirty();
 
-    async fn stop_music(&self, block: &Block, position: &BlockPos, world: &Arc<World>) {
-        self.set_record(false, block, position, world).await;
-        world
-            .sync_world_event(WorldEvent::JukeboxStopsPlaying, *position, 0)
-            .await;
+        let item_position = {
+            let mut rng = rand::rng();
+            position.to_centered_f64().add_raw(
+                rng.random_range(-0.35..=0.35),
+                rng.random_range(-0.35..=0.35) + 0.51,
+                rng.random_range(-0.35..=0.35),
+            )
+        };
+        let item_entity = ItemEntity::new(
+            Entity::new(
+                Uuid::new_v4(),
+                world.clone(),
+                item_position,
+                &EntityType::ITEM,
+                false,
+            ),
+            record,
+        )
+        .await;
+        world.spawn_entity(Arc::new(item_entity)).await;
     }
 }
 
 #[async_trait]
 impl BlockBehaviour for JukeboxBlock {
-    async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
-        let world = &args.player.living_entity.entity.world;
-
-        // if the jukebox already has a record, stop playing
-        if self.has_record(args.block, args.position, world).await {
-            self.stop_music(args.block, args.position, world).await;
-            return BlockActionResult::Success;
-        }
-
-        let jukebox_playable = args
-            .item_stack
-            .lock()
-            .await
-            .get_data_component::<JukeboxPlayableImpl>()
-            .map(|i| i.song);
-
-        let Some(jukebox_playable) = jukebox_playable else {
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        let inventory = Self::get_record_inventory(args.world, args.position).await;
+        if inventory.is_empty().await {
             return BlockActionResult::Pass;
-        };
+        }
+        Self::pop_out_record(args.world, args.position, inventory.as_ref()).await;
+        BlockActionResult::Success
+    }
 
-        let Some(song) = jukebox_playable.split(':').nth(1) else {
-            return BlockActionResult::Pass;
-        };
+    async fn use_with_item(&self, args: UseWithItemArgs<'_>) -> BlockActionResult {
+        let inventory = Self::get_record_inventory(args.world, args.position).await;
+        // A jukebox that already has a record gives it back, like with an empty hand
+        if !inventory.is_empty().await {
+            return BlockActionResult::PassToDefaultBlockAction;
+        }
 
-        let Some(jukebox_song) = SYNCED_REGISTRIES.jukebox_song.get_index_of(song) else {
-            log::error!("Jukebox playable song not registered!");
+        let mut item_stack = args.item_stack.lock().await;
+        if !inventory.is_valid_slot_for(0, &item_stack) {
             return BlockActionResult::Pass;
-        };
+        }
+        inventory.set_stack(0, item_stack.copy_with_count(1)).await;
+        inventory.mark_dirty();
+        item_stack.decrement_unless_creative(args.player.gamemode.load(), 1);
 
-        //TODO: Update block nbt
+        BlockActionResult::Success
+    }
 
-        self.set_record(true, args.block, args.position, world)
-            .await;
-        world
-            .sync_world_event(
-                WorldEvent::JukeboxStartsPlaying,
-                *args.position,
-                jukebox_song as i32,
-            )
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        args.world
+            .add_block_entity(Arc::new(JukeboxBlockEntity::new(*args.position)))
             .await;
-
-        BlockActionResult::Success
     }
 
     async fn broken(&self, args: BrokenArgs<'_>) {
-        // For now just stop the music at this position
+        // The record itself is dropped with the rest of the block entity's inventory
         args.world
             .sync_world_event(WorldEvent::JukeboxStopsPlaying, *args.position, 0)
             .await;
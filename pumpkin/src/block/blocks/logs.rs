@@ -1,7 +1,5 @@
 use async_trait::async_trait;
 use pumpkin_data::block_properties::BlockProperties;
-use pumpkin_data::tag::{RegistryKey, get_tag_values};
-use pumpkin_macros::pumpkin_block_from_tag;
 use pumpkin_world::BlockStateId;
 
 use crate::block::BlockBehaviour;
@@ -9,7 +7,6 @@ use crate::block::OnPlaceArgs;
 
 type LogProperties = pumpkin_data::block_properties::PaleOakWoodLikeProperties;
 
-#[pumpkin_block_from_tag("minecraft:logs")]
 pub struct LogBlock;
 
 #[async_trait]
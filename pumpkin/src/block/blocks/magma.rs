@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use pumpkin_data::damage::DamageType;
+use pumpkin_macros::pumpkin_block;
+use std::sync::atomic::Ordering;
+
+use crate::block::{BlockBehaviour, OnSteppedOnArgs};
+
+#[pumpkin_block("minecraft:magma_block")]
+pub struct MagmaBlock;
+
+#[async_trait]
+impl BlockBehaviour for MagmaBlock {
+    async fn on_stepped_on(&self, args: OnSteppedOnArgs<'_>) {
+        // TODO: Frost walker protects from the heat as well
+        if args.entity.get_living_entity().is_none()
+            || args.entity.get_entity().sneaking.load(Ordering::Relaxed)
+        {
+            return;
+        }
+        args.entity
+            .damage(args.entity.clone(), 1.0, DamageType::HOT_FLOOR)
+            .await;
+    }
+}
@@ -1,7 +1,10 @@
+pub mod amethyst;
 pub mod anvil;
 pub mod bamboo;
 pub mod barrel;
 pub mod bed;
+pub mod beehive;
+pub mod brewing_stand;
 pub mod cactus;
 pub mod cake;
 pub mod campfire;
@@ -12,11 +15,14 @@ pub mod carved_pumpkin;
 pub mod chain;
 pub mod chests;
 pub mod chiseled_bookshelf;
+pub mod cobweb;
 pub mod command;
 pub mod composter;
 pub mod crafting_table;
+pub mod decorated_pot;
 pub mod dirt_path;
 pub mod doors;
+pub mod end_gateway;
 pub mod end_portal;
 pub mod end_portal_frame;
 pub mod end_rod;
@@ -30,20 +36,24 @@ pub mod furnace;
 pub mod glass_panes;
 pub mod glazed_terracotta;
 pub mod grindstone;
+pub mod honey;
 pub mod iron_bars;
 pub mod jukebox;
 pub mod ladder;
 pub mod lanterns;
 pub mod logs;
+pub mod magma;
 pub mod nether_portal;
 pub mod note;
 pub mod piston;
 pub mod plant;
+pub mod pointed_dripstone;
 pub mod pumpkin;
 pub mod redstone;
 pub mod signs;
 pub mod skull_block;
 pub mod slabs;
+pub mod slime;
 pub mod spawner;
 pub mod stairs;
 pub mod sugar_cane;
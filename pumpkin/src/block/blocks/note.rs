@@ -5,6 +5,7 @@ use crate::block::{
 };
 use async_trait::async_trait;
 use pumpkin_data::block_properties::Axis;
+use pumpkin_data::game_event::GameEvent;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::{
     Block,
@@ -31,6 +32,7 @@ impl NoteBlock {
     pub async fn play_note(props: &NoteBlockLikeProperties, world: &World, pos: &BlockPos) {
         if !is_base_block(props.instrument) || world.get_block_state(&pos.up()).await.is_air() {
             world.add_synced_block_event(*pos, 0, 0).await;
+            world.emit_game_event(&GameEvent::NoteBlockPlay, pos).await;
         }
     }
     fn get_note_pitch(note: u16) -> f32 {
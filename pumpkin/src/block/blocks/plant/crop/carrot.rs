@@ -29,6 +29,10 @@ impl BlockBehaviour for CarrotBlock {
         .await
     }
 
+    fn catches_up_random_ticks(&self) -> bool {
+        true
+    }
+
     async fn random_tick(&self, args: RandomTickArgs<'_>) {
         <Self as CropBlockBase>::random_tick(self, args.world, args.position).await;
     }
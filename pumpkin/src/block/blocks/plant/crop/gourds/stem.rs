@@ -82,6 +82,10 @@ impl BlockBehaviour for StemBlock {
         .await
     }
 
+    fn catches_up_random_ticks(&self) -> bool {
+        true
+    }
+
     async fn random_tick(&self, args: RandomTickArgs<'_>) {
         // TODO add light level check
         let f: f32 = get_available_moisture(args.world, args.position, args.block).await;
@@ -36,6 +36,10 @@ impl BlockBehaviour for TorchFlowerBlock {
         .await
     }
 
+    fn catches_up_random_ticks(&self) -> bool {
+        true
+    }
+
     async fn random_tick(&self, args: RandomTickArgs<'_>) {
         if rand::rng().random_range(0..2) != 0 {
             <Self as CropBlockBase>::random_tick(self, args.world, args.position).await;
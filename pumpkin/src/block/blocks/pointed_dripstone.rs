@@ -0,0 +1,495 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    Block, BlockDirection, BlockState,
+    block_properties::{
+        BlockProperties, PointedDripstoneLikeProperties, Thickness, VerticalDirection,
+    },
+    damage::DamageType,
+};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{
+    BlockStateId,
+    tick::TickPriority,
+    world::{BlockAccessor, BlockFlags},
+};
+use rand::Rng;
+
+use crate::{
+    block::{
+        BlockBehaviour, CanPlaceAtArgs, FallDamage, GetStateForNeighborUpdateArgs,
+        OnLandedUponArgs, OnPlaceArgs, OnScheduledTickArgs, RandomTickArgs,
+    },
+    entity::{
+        EntityBase,
+        falling::{FallingBlockDamage, FallingEntity},
+        player::Player,
+    },
+    world::World,
+};
+
+/// Chance per random tick that a stalactite below a dripstone block with water on top grows.
+const GROWTH_CHANCE: f32 = 0.011_377_778;
+/// How far down a stalactite's tip is searched for when it grows.
+const MAX_TIP_SEARCH: i32 = 7;
+/// How far below a stalactite water can drip down to grow a stalagmite.
+const MAX_STALAGMITE_SEARCH: i32 = 10;
+/// The most damage a falling stalactite deals.
+const MAX_FALL_DAMAGE: f32 = 40.0;
+
+#[pumpkin_block("minecraft:pointed_dripstone")]
+pub struct PointedDripstoneBlock;
+
+#[async_trait]
+impl BlockBehaviour for PointedDripstoneBlock {
+    async fn on_place(&self, args: OnPlaceArgs<'_>) -> BlockStateId {
+        let preferred = Self::preferred_direction(args.player);
+        let Some(direction) =
+            Self::direction_to_place_at(args.world, args.position, preferred).await
+        else {
+            // Already rejected by `can_place_at`
+            return args.block.default_state.id;
+        };
+        // Sneaking players place a separate tip instead of merging with the one they point at
+        let try_merge = !args.player.get_entity().sneaking.load(Ordering::Relaxed);
+
+        let mut props = PointedDripstoneLikeProperties::default(args.block);
+        props.vertical_direction = direction;
+        props.thickness = Self::thickness_at(args.world, args.position, direction, try_merge).await;
+        props.waterlogged = args.replacing.water_source();
+        props.to_state_id(args.block)
+    }
+
+    async fn can_place_at(&self, args: CanPlaceAtArgs<'_>) -> bool {
+        let preferred = args
+            .player
+            .map_or(VerticalDirection::Up, Self::preferred_direction);
+        Self::direction_to_place_at(args.block_accessor, args.position, preferred)
+            .await
+            .is_some()
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        if args.direction != BlockDirection::Up && args.direction != BlockDirection::Down {
+            return args.state_id;
+        }
+        let mut props = PointedDripstoneLikeProperties::from_state_id(args.state_id, args.block);
+        let direction = props.vertical_direction;
+        // A stalactite that is about to fall keeps its shape until it does
+        if direction == VerticalDirection::Down
+            && args
+                .world
+                .is_block_tick_scheduled(args.position, args.block)
+                .await
+        {
+            return args.state_id;
+        }
+
+        if args.direction == to_block_direction(opposite(direction))
+            && !Self::can_place_with_direction(args.world, args.position, direction).await
+        {
+            // Stalactites fall down, stalagmites simply break
+            let delay = if direction == VerticalDirection::Down {
+                2
+            } else {
+                1
+            };
+            args.world
+                .schedule_block_tick(args.block, *args.position, delay, TickPriority::Normal)
+                .await;
+            return args.state_id;
+        }
+
+        let try_merge = props.thickness == Thickness::TipMerge;
+        props.thickness = Self::thickness_at(args.world, args.position, direction, try_merge).await;
+        props.to_state_id(args.block)
+    }
+
+    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
+        let state_id = args.world.get_block_state_id(args.position).await;
+        let props = PointedDripstoneLikeProperties::from_state_id(state_id, args.block);
+        if props.vertical_direction == VerticalDirection::Up {
+            if !Self::can_place_with_direction(
+                args.world.as_ref(),
+                args.position,
+                VerticalDirection::Up,
+            )
+            .await
+            {
+                args.world
+                    .break_block(args.position, None, BlockFlags::empty())
+                    .await;
+            }
+        } else {
+            Self::spawn_falling_stalactite(args.world, *args.position).await;
+        }
+    }
+
+    async fn random_tick(&self, args: RandomTickArgs<'_>) {
+        if rand::rng().random::<f32>() >= GROWTH_CHANCE {
+            return;
+        }
+        let state = args.world.get_block_state(args.position).await;
+        if Self::is_held_by_dripstone(args.world, args.position, state).await {
+            Self::try_grow(args.world, args.position).await;
+        }
+    }
+
+    async fn on_landed_upon(&self, args: OnLandedUponArgs<'_>) -> FallDamage {
+        let state_id = args.world.get_block_state_id(args.position).await;
+        let props = PointedDripstoneLikeProperties::from_state_id(state_id, args.block);
+        if props.vertical_direction == VerticalDirection::Up && props.thickness == Thickness::Tip {
+            // Landing on the tip of a stalagmite hurts a lot more than the ground
+            return FallDamage {
+                fall_distance: args.fall_distance + 2.5,
+                multiplier: 2.0,
+                damage_type: DamageType::STALAGMITE,
+            };
+        }
+        FallDamage::new(args.fall_distance)
+    }
+}
+
+impl PointedDripstoneBlock {
+    /// Players looking up place stalactites, players looking down place stalagmites.
+    fn preferred_direction(player: &Player) -> VerticalDirection {
+        if player.get_entity().pitch.load() < 0.0 {
+            VerticalDirection::Down
+        } else {
+            VerticalDirection::Up
+        }
+    }
+
+    /// The direction the dripstone points to at `pos`, trying `preferred` first.
+    async fn direction_to_place_at(
+        world: &dyn BlockAccessor,
+        pos: &BlockPos,
+        preferred: VerticalDirection,
+    ) -> Option<VerticalDirection> {
+        if Self::can_place_with_direction(world, pos, preferred).await {
+            Some(preferred)
+        } else if Self::can_place_with_direction(world, pos, opposite(preferred)).await {
+            Some(opposite(preferred))
+        } else {
+            None
+        }
+    }
+
+    /// Whether dripstone pointing in `direction` is held at `pos`, either by a full face or by
+    /// more dripstone pointing the same way.
+    async fn can_place_with_direction(
+        world: &dyn BlockAccessor,
+        pos: &BlockPos,
+        direction: VerticalDirection,
+    ) -> bool {
+        let support = offset(pos, opposite(direction));
+        let state = world.get_block_state(&support).await;
+        state.is_side_solid(to_block_direction(direction)) || points(state, direction)
+    }
+
+    async fn thickness_at(
+        world: &dyn BlockAccessor,
+        pos: &BlockPos,
+        direction: VerticalDirection,
+        try_merge: bool,
+    ) -> Thickness {
+        let ahead = world.get_block_state(&offset(pos, direction)).await;
+        let behind = world
+            .get_block_state(&offset(pos, opposite(direction)))
+            .await;
+        Self::thickness(direction, ahead, behind, try_merge)
+    }
+
+    /// The shape of dripstone pointing in `direction`, going from a thick base over the middle
+    /// and frustum to the tip, depending on the blocks `ahead` of and `behind` it.
+    fn thickness(
+        direction: VerticalDirection,
+        ahead: &BlockState,
+        behind: &BlockState,
+        try_merge: bool,
+    ) -> Thickness {
+        match properties(ahead) {
+            // Two tips pointing at each other merge into one
+            Some(props) if props.vertical_direction == opposite(direction) => {
+                if try_merge || props.thickness == Thickness::TipMerge {
+                    Thickness::TipMerge
+                } else {
+                    Thickness::Tip
+                }
+            }
+            Some(props) if props.vertical_direction == direction => match props.thickness {
+                Thickness::Tip | Thickness::TipMerge => Thickness::Frustum,
+                _ if points(behind, direction) => Thickness::Middle,
+                _ => Thickness::Base,
+            },
+            _ => Thickness::Tip,
+        }
+    }
+
+    /// Lets the stalactite hanging down from `pos` fall, the tip hurts whatever it lands on.
+    async fn spawn_falling_stalactite(world: &Arc<World>, position: BlockPos) {
+        let mut pos = position;
+        loop {
+            let state = world.get_block_state(&pos).await;
+            let Some(props) = properties(state) else {
+                break;
+            };
+            if props.vertical_direction != VerticalDirection::Down {
+                break;
+            }
+
+            let is_tip = matches!(props.thickness, Thickness::Tip | Thickness::TipMerge);
+            let damage = is_tip.then(|| FallingBlockDamage {
+                per_block: (1 + position.0.y - pos.0.y).max(6) as f32,
+                max: MAX_FALL_DAMAGE,
+                damage_type: DamageType::FALLING_STALACTITE,
+            });
+            FallingEntity::replace_spawn_with(world, pos, state.id, damage, true).await;
+            if is_tip {
+                break;
+            }
+            pos = pos.down();
+        }
+    }
+
+    /// Whether the dripstone at `pos` is the top of a stalactite.
+    async fn is_held_by_dripstone(world: &World, pos: &BlockPos, state: &BlockState) -> bool {
+        points(state, VerticalDirection::Down)
+            && world.get_block(&pos.up()).await != &Block::POINTED_DRIPSTONE
+    }
+
+    /// Water seeping through the dripstone block above grows the stalactite or, dripping down
+    /// from its tip, a stalagmite below it.
+    async fn try_grow(world: &Arc<World>, pos: &BlockPos) {
+        if world.get_block(&pos.up()).await != &Block::DRIPSTONE_BLOCK
+            || world.get_block_state_id(&pos.up_height(2)).await != Block::WATER.default_state.id
+        {
+            return;
+        }
+        let Some(tip) = Self::tip_pos(world, *pos, VerticalDirection::Down).await else {
+            return;
+        };
+        let tip_state = world.get_block_state(&tip).await;
+        if !can_drip(tip_state) || !Self::can_grow_at(world, &tip, VerticalDirection::Down).await {
+            return;
+        }
+        if rand::rng().random_bool(0.5) {
+            Self::grow(world, &tip, VerticalDirection::Down).await;
+        } else {
+            Self::grow_stalagmite(world, tip).await;
+        }
+    }
+
+    /// Follows the dripstone from `pos` in `direction` to its tip.
+    async fn tip_pos(
+        world: &World,
+        mut pos: BlockPos,
+        direction: VerticalDirection,
+    ) -> Option<BlockPos> {
+        for _ in 0..MAX_TIP_SEARCH {
+            let state = world.get_block_state(&pos).await;
+            if is_tip(state, direction) {
+                return Some(pos);
+            }
+            if !points(state, direction) {
+                return None;
+            }
+            pos = offset(&pos, direction);
+        }
+        None
+    }
+
+    /// Whether the tip at `pos` has room to grow one block further.
+    async fn can_grow_at(world: &World, pos: &BlockPos, direction: VerticalDirection) -> bool {
+        let state = world.get_block_state(&offset(pos, direction)).await;
+        if has_fluid(state) {
+            return false;
+        }
+        state.is_air() || is_tip(state, opposite(direction))
+    }
+
+    /// Drips down from the stalactite tip at `tip` and grows the stalagmite the water lands on,
+    /// or starts a new one.
+    async fn grow_stalagmite(world: &Arc<World>, tip: BlockPos) {
+        let mut pos = tip;
+        for _ in 0..MAX_STALAGMITE_SEARCH {
+            pos = pos.down();
+            let state = world.get_block_state(&pos).await;
+            if has_fluid(state) {
+                return;
+            }
+            if is_tip(state, VerticalDirection::Up) {
+                if Self::can_grow_at(world, &pos, VerticalDirection::Up).await {
+                    Self::grow(world, &pos, VerticalDirection::Up).await;
+                }
+                return;
+            }
+            // The water lands on the first full floor and starts a new stalagmite there
+            if state.is_side_solid(BlockDirection::Up) {
+                if world.get_block(&pos.up()).await != &Block::WATER {
+                    Self::grow(world, &pos, VerticalDirection::Up).await;
+                }
+                return;
+            }
+            // Water only drips through blocks without collision
+            if !state.is_air() && !state.get_block_collision_shapes().is_empty() {
+                return;
+            }
+        }
+    }
+
+    /// Grows the dripstone at `pos` one block in `direction`, merging with a tip in the way.
+    async fn grow(world: &Arc<World>, pos: &BlockPos, direction: VerticalDirection) {
+        let target = offset(pos, direction);
+        let state = world.get_block_state(&target).await;
+        if is_tip(state, opposite(direction)) {
+            let (upper, lower) = if opposite(direction) == VerticalDirection::Up {
+                (target.up(), target)
+            } else {
+                (target, target.down())
+            };
+            Self::place(world, &upper, VerticalDirection::Down, Thickness::TipMerge).await;
+            Self::place(world, &lower, VerticalDirection::Up, Thickness::TipMerge).await;
+        } else if state.is_air() || Block::from_state_id(state.id) == &Block::WATER {
+            Self::place(world, &target, direction, Thickness::Tip).await;
+        }
+    }
+
+    async fn place(
+        world: &Arc<World>,
+        pos: &BlockPos,
+        direction: VerticalDirection,
+        thickness: Thickness,
+    ) {
+        let waterlogged = world.get_block_state_id(pos).await == Block::WATER.default_state.id;
+        let mut props = PointedDripstoneLikeProperties::default(&Block::POINTED_DRIPSTONE);
+        props.vertical_direction = direction;
+        props.thickness = thickness;
+        props.waterlogged = waterlogged;
+        world
+            .set_block_state(
+                pos,
+                props.to_state_id(&Block::POINTED_DRIPSTONE),
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+    }
+}
+
+fn properties(state: &BlockState) -> Option<PointedDripstoneLikeProperties> {
+    let block = Block::from_state_id(state.id);
+    (block == &Block::POINTED_DRIPSTONE)
+        .then(|| PointedDripstoneLikeProperties::from_state_id(state.id, block))
+}
+
+fn points(state: &BlockState, direction: VerticalDirection) -> bool {
+    properties(state).is_some_and(|props| props.vertical_direction == direction)
+}
+
+fn is_tip(state: &BlockState, direction: VerticalDirection) -> bool {
+    properties(state).is_some_and(|props| {
+        props.vertical_direction == direction && props.thickness == Thickness::Tip
+    })
+}
+
+/// Whether water drips from the stalactite tip `state`.
+fn can_drip(state: &BlockState) -> bool {
+    properties(state).is_some_and(|props| {
+        props.vertical_direction == VerticalDirection::Down
+            && props.thickness == Thickness::Tip
+            && !props.waterlogged
+    })
+}
+
+fn has_fluid(state: &BlockState) -> bool {
+    state.is_liquid() || properties(state).is_some_and(|props| props.waterlogged)
+}
+
+const fn opposite(direction: VerticalDirection) -> VerticalDirection {
+    match direction {
+        VerticalDirection::Up => VerticalDirection::Down,
+        VerticalDirection::Down => VerticalDirection::Up,
+    }
+}
+
+const fn to_block_direction(direction: VerticalDirection) -> BlockDirection {
+    match direction {
+        VerticalDirection::Up => BlockDirection::Up,
+        VerticalDirection::Down => BlockDirection::Down,
+    }
+}
+
+fn offset(pos: &BlockPos, direction: VerticalDirection) -> BlockPos {
+    pos.offset(to_block_direction(direction).to_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dripstone(direction: VerticalDirection, thickness: Thickness) -> &'static BlockState {
+        let mut props = PointedDripstoneLikeProperties::default(&Block::POINTED_DRIPSTONE);
+        props.vertical_direction = direction;
+        props.thickness = thickness;
+        BlockState::from_id(props.to_state_id(&Block::POINTED_DRIPSTONE))
+    }
+
+    fn air() -> &'static BlockState {
+        BlockState::from_id(Block::AIR.default_state.id)
+    }
+
+    #[test]
+    fn lone_dripstone_is_a_tip() {
+        let thickness =
+            PointedDripstoneBlock::thickness(VerticalDirection::Down, air(), air(), true);
+        assert_eq!(thickness, Thickness::Tip);
+    }
+
+    #[test]
+    fn facing_tips_merge_unless_sneaking() {
+        let stalagmite = dripstone(VerticalDirection::Up, Thickness::Tip);
+        let down = VerticalDirection::Down;
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, stalagmite, air(), true),
+            Thickness::TipMerge
+        );
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, stalagmite, air(), false),
+            Thickness::Tip
+        );
+
+        // Once merged, the other tip keeps the merge going
+        let merged = dripstone(VerticalDirection::Up, Thickness::TipMerge);
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, merged, air(), false),
+            Thickness::TipMerge
+        );
+    }
+
+    #[test]
+    fn dripstone_thickens_away_from_the_tip() {
+        let down = VerticalDirection::Down;
+        let tip = dripstone(down, Thickness::Tip);
+        let frustum = dripstone(down, Thickness::Frustum);
+        let middle = dripstone(down, Thickness::Middle);
+
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, tip, air(), true),
+            Thickness::Frustum
+        );
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, frustum, air(), true),
+            Thickness::Base
+        );
+        assert_eq!(
+            PointedDripstoneBlock::thickness(down, frustum, middle, true),
+            Thickness::Middle
+        );
+    }
+}
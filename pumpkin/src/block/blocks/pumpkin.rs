@@ -1,16 +1,11 @@
 use crate::block::UseWithItemArgs;
 use crate::block::registry::BlockActionResult;
-use crate::entity::Entity;
-use crate::entity::item::ItemEntity;
 use async_trait::async_trait;
-use pumpkin_data::Block;
-use pumpkin_data::entity::EntityType;
 use pumpkin_data::item::Item;
+use pumpkin_data::{Block, HorizontalFacingExt};
 use pumpkin_macros::pumpkin_block;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::BlockFlags;
-use std::sync::Arc;
-use uuid::Uuid;
 
 #[pumpkin_block("minecraft:pumpkin")]
 pub struct PumpkinBlock;
@@ -29,16 +24,19 @@ impl crate::block::BlockBehaviour for PumpkinBlock {
                 BlockFlags::NOTIFY_ALL,
             )
             .await;
-        let entity = Entity::new(
-            Uuid::new_v4(),
-            args.world.clone(),
-            args.position.to_f64(),
-            &EntityType::ITEM,
-            false,
-        );
-        let item_entity =
-            Arc::new(ItemEntity::new(entity, ItemStack::new(4, &Item::PUMPKIN_SEEDS)).await);
-        args.world.spawn_entity(item_entity).await;
+        let face = if args.hit.face.is_horizontal() {
+            *args.hit.face
+        } else {
+            args.player
+                .living_entity
+                .entity
+                .get_horizontal_facing()
+                .opposite()
+                .to_block_direction()
+        };
+        args.world
+            .drop_stack_from_face(args.position, face, ItemStack::new(4, &Item::PUMPKIN_SEEDS))
+            .await;
         BlockActionResult::Consume
     }
 }
@@ -75,11 +75,7 @@ pub trait RedstoneGateBlock<T: Send + BlockProperties + RedstoneGateBlockPropert
                 BlockFlags::NOTIFY_ALL,
             )
             .await;
-        for dir in BlockDirection::all() {
-            args.world
-                .update_neighbor(&args.position.offset(dir.to_offset()), args.source_block)
-                .await;
-        }
+        args.world.update_neighbors(args.position, None).await;
     }
 
     async fn update_powered(&self, world: &World, pos: BlockPos, state: &BlockState, block: &Block);
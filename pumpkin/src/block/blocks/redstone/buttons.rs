@@ -6,9 +6,6 @@ use pumpkin_data::BlockDirection;
 use pumpkin_data::HorizontalFacingExt;
 use pumpkin_data::block_properties::BlockFace;
 use pumpkin_data::block_properties::BlockProperties;
-use pumpkin_data::tag::RegistryKey;
-use pumpkin_data::tag::get_tag_values;
-use pumpkin_macros::pumpkin_block_from_tag;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::BlockStateId;
 use pumpkin_world::tick::TickPriority;
@@ -18,6 +15,7 @@ type ButtonLikeProperties = pumpkin_data::block_properties::LeverLikeProperties;
 
 use crate::block::CanPlaceAtArgs;
 use crate::block::EmitsRedstonePowerArgs;
+use crate::block::ExplodeArgs;
 use crate::block::GetRedstonePowerArgs;
 use crate::block::GetStateForNeighborUpdateArgs;
 use crate::block::OnPlaceArgs;
@@ -26,6 +24,7 @@ use crate::block::OnStateReplacedArgs;
 use crate::block::blocks::abstruct_wall_mounting::WallMountedBlock;
 use crate::block::blocks::redstone::lever::LeverLikePropertiesExt;
 use crate::block::registry::BlockActionResult;
+use crate::block::variant::BlockVariant;
 use crate::block::{BlockBehaviour, NormalUseArgs};
 use crate::world::World;
 
@@ -42,7 +41,9 @@ async fn click_button(world: &Arc<World>, block_pos: &BlockPos) {
                 BlockFlags::NOTIFY_ALL,
             )
             .await;
-        let delay = if block == &Block::STONE_BUTTON {
+        let delay = if BlockVariant::of(block, "_button")
+            .is_some_and(|button| button.is_any(&["stone", "polished_blackstone"]))
+        {
             20
         } else {
             30
@@ -54,7 +55,6 @@ async fn click_button(world: &Arc<World>, block_pos: &BlockPos) {
     }
 }
 
-#[pumpkin_block_from_tag("minecraft:buttons")]
 pub struct ButtonBlock;
 
 #[async_trait]
@@ -65,6 +65,10 @@ impl BlockBehaviour for ButtonBlock {
         BlockActionResult::Success
     }
 
+    async fn on_wind_burst(&self, args: ExplodeArgs<'_>) {
+        click_button(args.world, args.position).await;
+    }
+
     async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
         let state = args.world.get_block_state(args.position).await;
         let mut props = ButtonLikeProperties::from_state_id(state.id, args.block);
@@ -11,8 +11,10 @@ use pumpkin_data::{
 use pumpkin_macros::pumpkin_block;
 use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos};
 use pumpkin_world::{
-    BlockStateId, block::entities::comparator::ComparatorBlockEntity, tick::TickPriority,
-    world::BlockFlags,
+    BlockStateId,
+    block::entities::comparator::ComparatorBlockEntity,
+    tick::TickPriority,
+    world::{BlockFlags, SimpleWorld},
 };
 
 use crate::{
@@ -59,6 +61,7 @@ impl BlockBehaviour for ComparatorBlock {
     async fn placed(&self, args: PlacedArgs<'_>) {
         let comparator = ComparatorBlockEntity::new(*args.position);
         args.world.add_block_entity(Arc::new(comparator)).await;
+        Self::watch_source(args.world, *args.position, args.block).await;
 
         RedstoneGateBlock::update_target(
             self,
@@ -105,6 +108,7 @@ impl BlockBehaviour for ComparatorBlock {
     }
 
     async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
+        Self::watch_source(args.world, *args.position, args.block).await;
         RedstoneGateBlock::on_neighbor_update(self, args).await;
     }
 
@@ -265,6 +269,31 @@ impl RedstoneGateBlock<ComparatorLikeProperties> for ComparatorBlock {
 }
 
 impl ComparatorBlock {
+    /// Subscribes the comparator at `pos` to the inventory it reads, found like in `get_power`,
+    /// so it is updated when the contents change. See [`ComparatorBlockEntity::watch`].
+    async fn watch_source(world: &Arc<World>, pos: BlockPos, block: &Block) {
+        let Some(block_entity) = world.get_block_entity(&pos).await else {
+            return;
+        };
+        let Some(comparator) = block_entity
+            .as_any()
+            .downcast_ref::<ComparatorBlockEntity>()
+        else {
+            return;
+        };
+
+        let state = world.get_block_state(&pos).await;
+        let facing = ComparatorLikeProperties::from_state_id(state.id, block).facing;
+        let world: Arc<dyn SimpleWorld> = world.clone();
+        let mut source_pos = pos.offset(facing.to_offset());
+        let mut inventory = world.clone().get_block_container(&source_pos).await;
+        if inventory.is_none() && world.get_block_state(&source_pos).await.is_solid_block() {
+            source_pos = source_pos.offset(facing.to_offset());
+            inventory = world.clone().get_block_container(&source_pos).await;
+        }
+        comparator.watch(&world, inventory.as_ref());
+    }
+
     async fn on_use(
         &self,
         mut props: ComparatorLikeProperties,
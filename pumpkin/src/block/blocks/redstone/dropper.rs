@@ -19,10 +19,12 @@ use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
 use pumpkin_world::block::entities::dropper::DropperBlockEntity;
-use pumpkin_world::block::entities::hopper::HopperBlockEntity;
+use pumpkin_world::block::entities::hopper::{
+    HopperBlockEntity, get_container_at, is_full_container,
+};
 use pumpkin_world::inventory::Inventory;
 use pumpkin_world::tick::TickPriority;
-use pumpkin_world::world::BlockFlags;
+use pumpkin_world::world::{BlockFlags, SimpleWorld};
 use rand::{Rng, rng};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -149,40 +151,35 @@ impl BlockBehaviour for DropperBlock {
                     args.world.get_block_state(args.position).await.id,
                     args.block,
                 );
-                if let Some(entity) = args
-                    .world
-                    .get_block_entity(
-                        &args
-                            .position
-                            .offset(props.facing.to_block_direction().to_offset()),
-                    )
-                    .await
-                    && let Some(container) = entity.get_inventory()
+                let direction = props.facing.to_block_direction();
+                let world: Arc<dyn SimpleWorld> = args.world.clone();
+                if let Some(container) =
+                    get_container_at(&world, &args.position.offset(direction.to_offset())).await
                 {
-                    // TODO check WorldlyContainer
-                    let mut is_full = true;
-                    for i in 0..container.size() {
-                        let bind = container.get_stack(i).await;
-                        let item = bind.lock().await;
-                        if item.item_count < item.get_max_stack_size() {
-                            is_full = false;
-                            break;
-                        }
-                    }
-                    if is_full {
+                    let side = direction.opposite();
+                    if is_full_container(container.as_ref(), side).await {
                         return;
                     }
-                    //TODO WorldlyContainer
                     let backup = item.clone();
                     let one_item = item.split(1);
-                    if HopperBlockEntity::add_one_item(dropper, container.as_ref(), one_item).await
+                    if HopperBlockEntity::add_one_item(
+                        dropper,
+                        container.as_ref(),
+                        one_item,
+                        Some(side),
+                    )
+                    .await
                     {
+                        drop(item);
+                        dropper.mark_dirty();
                         return;
                     }
                     *item = backup;
                     return;
                 }
                 let drop_item = item.split(1);
+                drop(item);
+                dropper.mark_dirty();
                 let facing = to_normal(props.facing);
                 let mut position = args.position.to_centered_f64().add(&(facing * 0.7));
                 position.y -= match props.facing {
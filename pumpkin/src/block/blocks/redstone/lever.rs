@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::block::{
     blocks::abstruct_wall_mounting::WallMountedBlock,
     {
-        CanPlaceAtArgs, EmitsRedstonePowerArgs, GetRedstonePowerArgs,
+        CanPlaceAtArgs, EmitsRedstonePowerArgs, ExplodeArgs, GetRedstonePowerArgs,
         GetStateForNeighborUpdateArgs, OnPlaceArgs, OnStateReplacedArgs,
     },
 };
@@ -24,19 +24,17 @@ use crate::{
     world::World,
 };
 
-async fn toggle_lever(world: &Arc<World>, block_pos: &BlockPos) {
-    let (block, state) = world.get_block_and_state_id(block_pos).await;
-
-    let mut lever_props = LeverLikeProperties::from_state_id(state, block);
-    lever_props.powered = !lever_props.powered;
-    world
-        .set_block_state(
-            block_pos,
-            lever_props.to_state_id(block),
-            BlockFlags::NOTIFY_ALL,
-        )
-        .await;
-
+pub(crate) async fn toggle_lever(world: &Arc<World>, block_pos: &BlockPos) {
+    let Some((_, new_state_id)) = world
+        .modify_block_state::<LeverLikeProperties>(block_pos, BlockFlags::NOTIFY_ALL, |props| {
+            props.powered = !props.powered
+        })
+        .await
+    else {
+        return;
+    };
+
+    let lever_props = LeverLikeProperties::from_state_id(new_state_id, &Block::LEVER);
     LeverBlock::update_neighbors(world, block_pos, &lever_props).await;
 }
 
@@ -51,6 +49,10 @@ impl BlockBehaviour for LeverBlock {
         BlockActionResult::Success
     }
 
+    async fn on_wind_burst(&self, args: ExplodeArgs<'_>) {
+        toggle_lever(args.world, args.position).await;
+    }
+
     async fn emits_redstone_power(&self, _args: EmitsRedstonePowerArgs<'_>) -> bool {
         true
     }
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_util::math::position::BlockPos;
 
-use crate::world::World;
+use crate::{PLUGIN_MANAGER, plugin::block::block_redstone::RedstoneChangeEvent, world::World};
 
 pub mod buttons;
 pub mod comparator;
@@ -22,6 +22,8 @@ pub mod redstone_lamp;
 pub mod redstone_torch;
 pub mod redstone_wire;
 pub mod repeater;
+#[cfg(any(test, feature = "redstone-simulation"))]
+pub mod simulation;
 pub mod target_block;
 pub mod tripwire;
 pub mod tripwire_hook;
@@ -31,13 +33,42 @@ pub mod turbo;
 pub mod abstruct_redstone_gate;
 pub mod dispenser;
 
+/// Lets plugins intervene in a redstone power change through a [`RedstoneChangeEvent`].
+///
+/// Returns the power level that should actually be applied.
+pub async fn fire_redstone_change(
+    world: &Arc<World>,
+    block: &'static Block,
+    pos: &BlockPos,
+    old_power: u8,
+    new_power: u8,
+) -> u8 {
+    if !PLUGIN_MANAGER.has_listeners::<RedstoneChangeEvent>().await {
+        return new_power;
+    }
+    let event = PLUGIN_MANAGER
+        .fire(RedstoneChangeEvent::new(
+            world.clone(),
+            block,
+            *pos,
+            old_power,
+            new_power,
+        ))
+        .await;
+    if event.cancelled {
+        old_power
+    } else {
+        event.new_current.min(15)
+    }
+}
+
 pub async fn update_wire_neighbors(world: &Arc<World>, pos: &BlockPos) {
     for direction in BlockDirection::all() {
         let neighbor_pos = pos.offset(direction.to_offset());
         let block = world.get_block(&neighbor_pos).await;
         world
             .block_registry
-            .on_neighbor_update(world, block, &neighbor_pos, block, true)
+            .on_neighbor_update(world, &neighbor_pos, block, true)
             .await;
 
         for n_direction in BlockDirection::all() {
@@ -45,7 +76,7 @@ pub async fn update_wire_neighbors(world: &Arc<World>, pos: &BlockPos) {
             let block = world.get_block(&n_neighbor_pos).await;
             world
                 .block_registry
-                .on_neighbor_update(world, block, &n_neighbor_pos, block, true)
+                .on_neighbor_update(world, &n_neighbor_pos, block, true)
                 .await;
         }
     }
@@ -10,37 +10,26 @@ use pumpkin_world::{
     world::{BlockAccessor, BlockFlags},
 };
 
+use crate::block::support::BlockSupport;
 use crate::world::World;
 
 use super::{HorizontalFacingRailExt, Rail, RailElevation, RailProperties, StraightRailShapeExt};
 
-pub(super) async fn rail_placement_is_valid(world: &World, block: &Block, pos: &BlockPos) -> bool {
-    if !can_place_rail_at(world, pos).await {
-        return false;
-    }
-
-    let state_id = world.get_block_state_id(pos).await;
-    let rail_props = RailProperties::new(state_id, block);
-    let rail_leaning_direction = match rail_props.shape() {
-        RailShape::AscendingNorth => Some(HorizontalFacing::North),
-        RailShape::AscendingSouth => Some(HorizontalFacing::South),
-        RailShape::AscendingEast => Some(HorizontalFacing::East),
-        RailShape::AscendingWest => Some(HorizontalFacing::West),
-        _ => None,
-    };
+const SUPPORT: BlockSupport = BlockSupport::Sturdy(BlockDirection::Down);
 
-    if let Some(direction) = rail_leaning_direction
-        && !can_place_rail_at(world, &pos.offset(direction.to_offset()).up()).await
-    {
-        return false;
+/// The ground below, and for ascending rails also the block they lean on.
+pub(super) fn rail_supports(block: &Block, state_id: BlockStateId) -> &'static [BlockSupport] {
+    match RailProperties::new(state_id, block).shape() {
+        RailShape::AscendingNorth => &[SUPPORT, BlockSupport::SturdyTop(BlockDirection::North)],
+        RailShape::AscendingSouth => &[SUPPORT, BlockSupport::SturdyTop(BlockDirection::South)],
+        RailShape::AscendingEast => &[SUPPORT, BlockSupport::SturdyTop(BlockDirection::East)],
+        RailShape::AscendingWest => &[SUPPORT, BlockSupport::SturdyTop(BlockDirection::West)],
+        _ => &[SUPPORT],
     }
-
-    true
 }
 
 pub(super) async fn can_place_rail_at(world: &dyn BlockAccessor, pos: &BlockPos) -> bool {
-    let state = world.get_block_state(&pos.down()).await;
-    state.is_side_solid(BlockDirection::Up)
+    SUPPORT.is_satisfied(world, pos).await
 }
 
 pub(super) async fn compute_placed_rail_shape(
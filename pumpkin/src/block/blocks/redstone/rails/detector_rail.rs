@@ -1,18 +1,17 @@
 use async_trait::async_trait;
+use pumpkin_data::Block;
 use pumpkin_macros::pumpkin_block;
 use pumpkin_world::BlockStateId;
-use pumpkin_world::world::BlockFlags;
 
 use crate::block::BlockBehaviour;
 use crate::block::CanPlaceAtArgs;
-use crate::block::OnNeighborUpdateArgs;
 use crate::block::OnPlaceArgs;
 use crate::block::PlacedArgs;
+use crate::block::support::BlockSupport;
 
 use super::RailProperties;
 use super::common::{
-    can_place_rail_at, compute_placed_rail_shape, rail_placement_is_valid,
-    update_flanking_rails_shape,
+    can_place_rail_at, compute_placed_rail_shape, rail_supports, update_flanking_rails_shape,
 };
 
 #[pumpkin_block("minecraft:detector_rail")]
@@ -36,13 +35,8 @@ impl BlockBehaviour for DetectorRailBlock {
         update_flanking_rails_shape(args.world, args.block, args.state_id, args.position).await;
     }
 
-    async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
-        if !rail_placement_is_valid(args.world, args.block, args.position).await {
-            args.world
-                .break_block(args.position, None, BlockFlags::NOTIFY_ALL)
-                .await;
-            return;
-        }
+    fn supports(&self, block: &Block, state_id: BlockStateId) -> &'static [BlockSupport] {
+        rail_supports(block, state_id)
     }
 
     async fn can_place_at(&self, args: CanPlaceAtArgs<'_>) -> bool {
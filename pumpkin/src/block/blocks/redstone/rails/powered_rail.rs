@@ -10,6 +10,7 @@ use crate::block::OnNeighborUpdateArgs;
 use crate::block::OnPlaceArgs;
 use crate::block::OnStateReplacedArgs;
 use crate::block::PlacedArgs;
+use crate::block::support::BlockSupport;
 use crate::world::World;
 use pumpkin_data::Block;
 use pumpkin_util::math::position::BlockPos;
@@ -17,8 +18,7 @@ use pumpkin_util::math::position::BlockPos;
 use super::super::block_receives_redstone_power;
 use super::RailProperties;
 use super::common::{
-    can_place_rail_at, compute_placed_rail_shape, rail_placement_is_valid,
-    update_flanking_rails_shape,
+    can_place_rail_at, compute_placed_rail_shape, rail_supports, update_flanking_rails_shape,
 };
 
 // TODO: Fix redstone rail power extension behavior
@@ -105,14 +105,11 @@ impl BlockBehaviour for PoweredRailBlock {
         }
     }
 
-    async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
-        if !rail_placement_is_valid(args.world, args.block, args.position).await {
-            args.world
-                .break_block(args.position, None, BlockFlags::NOTIFY_ALL)
-                .await;
-            return;
-        }
+    fn supports(&self, block: &Block, state_id: BlockStateId) -> &'static [BlockSupport] {
+        rail_supports(block, state_id)
+    }
 
+    async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
         self.update_powered_state(args.world, args.block, args.position)
             .await;
 
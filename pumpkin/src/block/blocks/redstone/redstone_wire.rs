@@ -13,6 +13,7 @@ use pumpkin_world::BlockStateId;
 use pumpkin_world::world::{BlockAccessor, BlockFlags};
 
 use crate::block::registry::BlockActionResult;
+use crate::block::support::BlockSupport;
 use crate::block::{
     BrokenArgs, CanPlaceAtArgs, GetRedstonePowerArgs, GetStateForNeighborUpdateArgs,
     OnNeighborUpdateArgs, OnPlaceArgs, PlacedArgs, PrepareArgs,
@@ -23,7 +24,7 @@ use crate::{
 };
 
 use super::turbo::RedstoneWireTurbo;
-use super::{get_redstone_power_no_dust, update_wire_neighbors};
+use super::{fire_redstone_change, get_redstone_power_no_dust, update_wire_neighbors};
 
 type RedstoneWireProperties = RedstoneWireLikeProperties;
 
@@ -144,26 +145,37 @@ impl BlockBehaviour for RedstoneWireBlock {
         }
     }
 
+    fn supports(&self, _block: &Block, _state_id: BlockStateId) -> &'static [BlockSupport] {
+        &[SUPPORT]
+    }
+
     async fn on_neighbor_update(&self, args: OnNeighborUpdateArgs<'_>) {
-        if can_place_at(args.world.as_ref(), args.position).await {
-            let state = args.world.get_block_state(args.position).await;
-            let mut wire = RedstoneWireProperties::from_state_id(state.id, args.block);
-            let new_power = calculate_power(args.world, args.position).await;
-            if wire.power.to_index() as u8 != new_power {
-                wire.power = Integer0To15::from_index(new_power.into());
-                args.world
-                    .set_block_state(
-                        args.position,
-                        wire.to_state_id(&Block::REDSTONE_WIRE),
-                        BlockFlags::empty(),
-                    )
-                    .await;
-                RedstoneWireTurbo::update_surrounding_neighbors(args.world, *args.position).await;
-            }
+        let state = args.world.get_block_state(args.position).await;
+        let mut wire = RedstoneWireProperties::from_state_id(state.id, args.block);
+        let old_power = wire.power.to_index() as u8;
+        let new_power = calculate_power(args.world, args.position).await;
+        let new_power = if old_power == new_power {
+            new_power
         } else {
+            fire_redstone_change(
+                args.world,
+                &Block::REDSTONE_WIRE,
+                args.position,
+                old_power,
+                new_power,
+            )
+            .await
+        };
+        if old_power != new_power {
+            wire.power = Integer0To15::from_index(new_power.into());
             args.world
-                .break_block(args.position, None, BlockFlags::NOTIFY_ALL)
+                .set_block_state(
+                    args.position,
+                    wire.to_state_id(&Block::REDSTONE_WIRE),
+                    BlockFlags::empty(),
+                )
                 .await;
+            RedstoneWireTurbo::update_surrounding_neighbors(args.world, *args.position).await;
         }
     }
 
@@ -196,9 +208,10 @@ impl BlockBehaviour for RedstoneWireBlock {
     }
 }
 
+const SUPPORT: BlockSupport = BlockSupport::Sturdy(BlockDirection::Down);
+
 async fn can_place_at(world: &dyn BlockAccessor, block_pos: &BlockPos) -> bool {
-    let floor = world.get_block_state(&block_pos.down()).await;
-    floor.is_side_solid(BlockDirection::Up)
+    SUPPORT.is_satisfied(world, block_pos).await
 }
 
 async fn on_use(wire: RedstoneWireProperties, world: &Arc<World>, block_pos: &BlockPos) -> bool {
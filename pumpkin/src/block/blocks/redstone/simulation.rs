@@ -0,0 +1,303 @@
+//! A deterministic, in-memory world for testing redstone components.
+//!
+//! [`RedstoneSimulation`] wraps a real [`World`] whose chunks are created empty in memory instead
+//! of being loaded or generated, and scheduled block ticks only run when the test steps them.
+//! Circuits are laid out with a [`CircuitBuilder`].
+
+use std::sync::{Arc, Weak};
+
+use pumpkin_data::{
+    Block,
+    block_properties::{
+        BlockFace, BlockProperties, EnumVariants, Facing, HorizontalFacing, Integer0To15,
+        Integer1To4, LeverLikeProperties, ObserverLikeProperties, RepeaterLikeProperties,
+    },
+};
+use pumpkin_registry::VanillaDimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    BlockStateId, chunk::ChunkData, dimension::Dimension, world::BlockFlags, world_info::LevelData,
+};
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+use crate::{block::registry::default_registry, world::World};
+
+use super::{lever, redstone_wire::make_cross};
+
+/// Chunks around the origin that are created up front, in each direction.
+const CHUNK_RADIUS: i32 = 1;
+
+pub struct RedstoneSimulation {
+    pub world: Arc<World>,
+    /// Keeps the level folder alive; nothing is ever saved into it.
+    _folder: TempDir,
+}
+
+impl RedstoneSimulation {
+    pub fn new() -> Self {
+        let folder = tempfile::tempdir().unwrap();
+        let block_registry = default_registry();
+        let level =
+            Dimension::Overworld.into_level(folder.path().to_path_buf(), block_registry.clone(), 0);
+
+        let dimension_type = VanillaDimensionType::Overworld;
+        let section_count = (dimension_type.height() / 16) as usize;
+        for x in -CHUNK_RADIUS..=CHUNK_RADIUS {
+            for z in -CHUNK_RADIUS..=CHUNK_RADIUS {
+                level.insert_chunk(ChunkData::empty(
+                    Vector2::new(x, z),
+                    dimension_type.min_y(),
+                    section_count,
+                ));
+            }
+        }
+
+        let world = World::load(
+            "overworld".to_string(),
+            level,
+            Arc::new(RwLock::new(LevelData::default())),
+            dimension_type,
+            block_registry,
+            Weak::new(),
+        );
+
+        Self {
+            world: Arc::new(world),
+            _folder: folder,
+        }
+    }
+
+    /// Runs one game tick worth of scheduled block ticks.
+    pub async fn tick(&self) {
+        let block_ticks = self.world.level.step_block_ticks().await;
+        self.world.run_block_ticks(block_ticks).await;
+        self.world.flush_block_updates().await;
+    }
+
+    pub async fn run(&self, ticks: usize) {
+        for _ in 0..ticks {
+            self.tick().await;
+        }
+    }
+
+    pub async fn set_block_state(&self, position: BlockPos, state_id: BlockStateId) {
+        self.world
+            .set_block_state(&position, state_id, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+
+    /// Flips the lever at `position` like a player using it would.
+    pub async fn toggle_lever(&self, position: BlockPos) {
+        lever::toggle_lever(&self.world, &position).await;
+    }
+
+    pub async fn block(&self, position: BlockPos) -> &'static Block {
+        self.world.get_block(&position).await
+    }
+
+    pub async fn properties<P: BlockProperties>(&self, position: BlockPos) -> P {
+        let (block, state_id) = self.world.get_block_and_state_id(&position).await;
+        P::from_state_id(state_id, block)
+    }
+
+    /// The power level of the redstone wire at `position`.
+    pub async fn wire_power(&self, position: BlockPos) -> u8 {
+        self.properties::<RedstoneWireProperties>(position)
+            .await
+            .power
+            .to_index() as u8
+    }
+
+    pub async fn is_lit(&self, position: BlockPos) -> bool {
+        self.properties::<RedstoneLampProperties>(position)
+            .await
+            .lit
+    }
+
+    pub async fn is_powered(&self, position: BlockPos) -> bool {
+        let block = self.block(position).await;
+        if block == &Block::REPEATER {
+            self.properties::<RepeaterLikeProperties>(position)
+                .await
+                .powered
+        } else if block == &Block::OBSERVER {
+            self.properties::<ObserverLikeProperties>(position)
+                .await
+                .powered
+        } else if block == &Block::LEVER {
+            self.properties::<LeverLikeProperties>(position)
+                .await
+                .powered
+        } else {
+            panic!("{} has no powered property", block.name)
+        }
+    }
+}
+
+type RedstoneWireProperties = pumpkin_data::block_properties::RedstoneWireLikeProperties;
+type RedstoneLampProperties = pumpkin_data::block_properties::RedstoneOreLikeProperties;
+
+/// Lays out a circuit one block after another, heading east from the starting position.
+///
+/// Every component gets a stone block underneath so it has something to stand on. Signals flow
+/// east, so repeaters and observers are oriented accordingly.
+pub struct CircuitBuilder {
+    cursor: BlockPos,
+    blocks: Vec<(BlockPos, BlockStateId)>,
+}
+
+impl CircuitBuilder {
+    pub fn new(start: BlockPos) -> Self {
+        Self {
+            cursor: start,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Continues the circuit at `position` instead of the next block east.
+    #[must_use]
+    pub const fn at(mut self, position: BlockPos) -> Self {
+        self.cursor = position;
+        self
+    }
+
+    /// Places `state_id` at the cursor and moves the cursor one block east.
+    #[must_use]
+    pub fn block(mut self, state_id: BlockStateId) -> Self {
+        self.blocks.push((self.cursor, state_id));
+        self.cursor = self.cursor.offset(Vector3::new(1, 0, 0));
+        self
+    }
+
+    /// An unpowered lever standing on the floor.
+    #[must_use]
+    pub fn lever(self) -> Self {
+        let mut props = LeverLikeProperties::default(&Block::LEVER);
+        props.face = BlockFace::Floor;
+        props.powered = false;
+        self.block(props.to_state_id(&Block::LEVER))
+    }
+
+    #[must_use]
+    pub fn wire(self) -> Self {
+        self.block(make_cross(Integer0To15::L0).to_state_id(&Block::REDSTONE_WIRE))
+    }
+
+    /// A repeater with the given delay between 1 and 4, taking its input from the west.
+    #[must_use]
+    pub fn repeater(self, delay: u16) -> Self {
+        let mut props = RepeaterLikeProperties::default(&Block::REPEATER);
+        props.facing = HorizontalFacing::West;
+        props.delay = Integer1To4::from_index(delay - 1);
+        self.block(props.to_state_id(&Block::REPEATER))
+    }
+
+    #[must_use]
+    pub fn lamp(self) -> Self {
+        self.block(Block::REDSTONE_LAMP.default_state.id)
+    }
+
+    /// An observer watching the block to its west and powering the block to its east.
+    #[must_use]
+    pub fn observer(self) -> Self {
+        let mut props = ObserverLikeProperties::default(&Block::OBSERVER);
+        props.facing = Facing::West;
+        self.block(props.to_state_id(&Block::OBSERVER))
+    }
+
+    /// Places the floor first and then every component in the order it was added.
+    pub async fn build(self, simulation: &RedstoneSimulation) {
+        let stone = Block::STONE.default_state.id;
+        for (position, _) in &self.blocks {
+            simulation.set_block_state(position.down(), stone).await;
+        }
+        for (position, state_id) in self.blocks {
+            simulation.set_block_state(position, state_id).await;
+        }
+        simulation.world.flush_block_updates().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::Block;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{CircuitBuilder, RedstoneSimulation};
+
+    fn pos(x: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, 0, 0))
+    }
+
+    #[tokio::test]
+    async fn wire_loses_power_with_distance() {
+        let sim = RedstoneSimulation::new();
+        CircuitBuilder::new(pos(0))
+            .lever()
+            .wire()
+            .wire()
+            .wire()
+            .lamp()
+            .build(&sim)
+            .await;
+        assert!(!sim.is_lit(pos(4)).await);
+
+        sim.toggle_lever(pos(0)).await;
+        assert_eq!(sim.wire_power(pos(1)).await, 15);
+        assert_eq!(sim.wire_power(pos(2)).await, 14);
+        assert_eq!(sim.wire_power(pos(3)).await, 13);
+        assert!(sim.is_lit(pos(4)).await);
+
+        sim.toggle_lever(pos(0)).await;
+        assert_eq!(sim.wire_power(pos(1)).await, 0);
+        assert_eq!(sim.wire_power(pos(3)).await, 0);
+        // Lamps take a moment to turn off
+        sim.run(3).await;
+        assert!(sim.is_lit(pos(4)).await);
+        sim.tick().await;
+        assert!(!sim.is_lit(pos(4)).await);
+    }
+
+    #[tokio::test]
+    async fn repeater_delays_signal() {
+        let sim = RedstoneSimulation::new();
+        CircuitBuilder::new(pos(0))
+            .lever()
+            .repeater(2)
+            .build(&sim)
+            .await;
+
+        sim.toggle_lever(pos(0)).await;
+        sim.run(3).await;
+        assert!(!sim.is_powered(pos(1)).await);
+        sim.tick().await;
+        assert!(sim.is_powered(pos(1)).await);
+
+        sim.toggle_lever(pos(0)).await;
+        sim.run(3).await;
+        assert!(sim.is_powered(pos(1)).await);
+        sim.tick().await;
+        assert!(!sim.is_powered(pos(1)).await);
+    }
+
+    #[tokio::test]
+    async fn observer_emits_short_pulse() {
+        let sim = RedstoneSimulation::new();
+        CircuitBuilder::new(pos(1)).observer().build(&sim).await;
+        sim.run(4).await;
+        assert!(!sim.is_powered(pos(1)).await);
+
+        sim.set_block_state(pos(0), Block::STONE.default_state.id)
+            .await;
+        sim.tick().await;
+        assert!(!sim.is_powered(pos(1)).await);
+        sim.tick().await;
+        assert!(sim.is_powered(pos(1)).await);
+        sim.tick().await;
+        assert!(sim.is_powered(pos(1)).await);
+        sim.tick().await;
+        assert!(!sim.is_powered(pos(1)).await);
+    }
+}
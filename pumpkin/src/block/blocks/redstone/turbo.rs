@@ -13,7 +13,7 @@ use std::sync::Arc;
 
 use crate::world::World;
 
-use super::get_redstone_power_no_dust;
+use super::{fire_redstone_change, get_redstone_power_no_dust};
 
 type RedstoneWireProps = RedstoneWireLikeProperties;
 
@@ -397,6 +397,8 @@ impl RedstoneWireTurbo {
             j = wire_power;
         }
         if i.to_index() as u8 != j {
+            let j = fire_redstone_change(world, &Block::REDSTONE_WIRE, &pos, i.to_index() as u8, j)
+                .await;
             wire.power = Integer0To15::from_index(j.into());
             world
                 .set_block_state(
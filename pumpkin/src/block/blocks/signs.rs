@@ -155,6 +155,9 @@ impl BlockBehaviour for SignBlock {
                 honeycomb_item
                     .apply_to_sign(&args, &block_entity, sign_entity)
                     .await
+            } else if !text.has_message() {
+                // Dyes and ink sacs only work on sides with text on them
+                BlockActionResult::PassToDefaultBlockAction
             } else if let Some(g_ink_sac_item) =
                 pumpkin_item.as_any().downcast_ref::<GlowingInkSacItem>()
             {
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use pumpkin_macros::pumpkin_block;
+use std::sync::atomic::Ordering;
+
+use crate::block::{
+    BlockBehaviour, FallDamage, OnEntityLandArgs, OnLandedUponArgs, OnSteppedOnArgs,
+};
+
+#[pumpkin_block("minecraft:slime_block")]
+pub struct SlimeBlock;
+
+#[async_trait]
+impl BlockBehaviour for SlimeBlock {
+    async fn on_stepped_on(&self, args: OnSteppedOnArgs<'_>) {
+        let entity = args.entity.get_entity();
+        let velocity = entity.velocity.load();
+        let vertical = velocity.y.abs();
+        if vertical < 0.1 && !entity.sneaking.load(Ordering::Relaxed) {
+            let slowdown = 0.4 + vertical * 0.2;
+            entity
+                .velocity
+                .store(velocity.multiply(slowdown, 1.0, slowdown));
+        }
+    }
+
+    async fn on_entity_land(&self, args: OnEntityLandArgs<'_>) {
+        let entity = args.entity.get_entity();
+        let mut velocity = entity.velocity.load();
+        if entity.sneaking.load(Ordering::Relaxed) || velocity.y >= 0.0 {
+            velocity.y = 0.0;
+        } else {
+            // Only living entities keep all of their momentum
+            let bounciness = if args.entity.get_living_entity().is_some() {
+                1.0
+            } else {
+                0.8
+            };
+            velocity.y = -velocity.y * bounciness;
+        }
+        entity.velocity.store(velocity);
+    }
+
+    async fn on_landed_upon(&self, args: OnLandedUponArgs<'_>) -> FallDamage {
+        let multiplier = if args.entity.get_entity().sneaking.load(Ordering::Relaxed) {
+            1.0
+        } else {
+            0.0
+        };
+        FallDamage {
+            multiplier,
+            ..FallDamage::new(args.fall_distance)
+        }
+    }
+}
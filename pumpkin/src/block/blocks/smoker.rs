@@ -1,8 +1,12 @@
-use crate::block::{BlockBehaviour, OnPlaceArgs};
+use crate::block::{
+    BlockBehaviour, BrokenArgs, NormalUseArgs, OnPlaceArgs, PlacedArgs,
+    blocks::furnace::{add_furnace_block_entity, open_furnace_screen, remove_furnace_block_entity},
+    registry::BlockActionResult,
+};
 use async_trait::async_trait;
 use pumpkin_data::block_properties::{BlockProperties, FurnaceLikeProperties};
 use pumpkin_macros::pumpkin_block;
-use pumpkin_world::BlockStateId;
+use pumpkin_world::{BlockStateId, block::entities::furnace::FurnaceKind};
 
 #[pumpkin_block("minecraft:smoker")]
 pub struct SmokerBlock;
@@ -19,4 +23,16 @@ impl BlockBehaviour for SmokerBlock {
             .opposite();
         props.to_state_id(args.block)
     }
+
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        open_furnace_screen(&args).await
+    }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        add_furnace_block_entity(&args, FurnaceKind::Smoker).await;
+    }
+
+    async fn broken(&self, args: BrokenArgs<'_>) {
+        remove_furnace_block_entity(&args).await;
+    }
 }
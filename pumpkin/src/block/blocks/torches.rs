@@ -2,7 +2,7 @@ use crate::block::BlockIsReplacing;
 use crate::entity::EntityBase;
 use async_trait::async_trait;
 use pumpkin_data::BlockDirection;
-use pumpkin_data::block_properties::{BlockProperties, Facing};
+use pumpkin_data::block_properties::{BlockProperties, Facing, HorizontalFacing};
 use pumpkin_data::{Block, FacingExt, HorizontalFacingExt};
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::BlockStateId;
@@ -11,9 +11,8 @@ use pumpkin_world::world::BlockAccessor;
 type WallTorchProps = pumpkin_data::block_properties::WallTorchLikeProperties;
 // Normal tourches don't have properties
 
-use crate::block::{
-    BlockBehaviour, BlockMetadata, CanPlaceAtArgs, GetStateForNeighborUpdateArgs, OnPlaceArgs,
-};
+use crate::block::support::BlockSupport;
+use crate::block::{BlockBehaviour, BlockMetadata, CanPlaceAtArgs, OnPlaceArgs};
 
 pub struct TorchBlock;
 
@@ -105,24 +104,17 @@ impl BlockBehaviour for TorchBlock {
         false
     }
 
-    async fn get_state_for_neighbor_update(
-        &self,
-        args: GetStateForNeighborUpdateArgs<'_>,
-    ) -> BlockStateId {
-        if args.block == &Block::WALL_TORCH || args.block == &Block::SOUL_WALL_TORCH {
-            let props = WallTorchProps::from_state_id(args.state_id, args.block);
-            if props.facing.to_block_direction().opposite() == args.direction
-                && !can_place_at(args.world, args.position, props.facing.to_block_direction()).await
-            {
-                return 0;
-            }
-        } else if args.direction == BlockDirection::Down {
-            let support_block = args.world.get_block_state(&args.position.down()).await;
-            if !support_block.is_center_solid(BlockDirection::Up) {
-                return 0;
+    fn supports(&self, block: &Block, state_id: BlockStateId) -> &'static [BlockSupport] {
+        if block == &Block::WALL_TORCH || block == &Block::SOUL_WALL_TORCH {
+            match WallTorchProps::from_state_id(state_id, block).facing {
+                HorizontalFacing::North => &[BlockSupport::Sturdy(BlockDirection::South)],
+                HorizontalFacing::South => &[BlockSupport::Sturdy(BlockDirection::North)],
+                HorizontalFacing::West => &[BlockSupport::Sturdy(BlockDirection::East)],
+                HorizontalFacing::East => &[BlockSupport::Sturdy(BlockDirection::West)],
             }
+        } else {
+            &[BlockSupport::Center(BlockDirection::Down)]
         }
-        args.state_id
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::block::blocks::redstone::block_receives_redstone_power;
 use crate::block::registry::BlockActionResult;
-use crate::block::{BlockBehaviour, NormalUseArgs, OnNeighborUpdateArgs, OnPlaceArgs};
+use crate::block::{BlockBehaviour, ExplodeArgs, NormalUseArgs, OnNeighborUpdateArgs, OnPlaceArgs};
 use crate::entity::player::Player;
 use crate::world::World;
 use async_trait::async_trait;
@@ -17,27 +17,30 @@ use std::sync::Arc;
 
 type TrapDoorProperties = pumpkin_data::block_properties::OakTrapdoorLikeProperties;
 
-async fn toggle_trapdoor(player: &Player, world: &Arc<World>, block_pos: &BlockPos) {
-    let (block, block_state) = world.get_block_and_state_id(block_pos).await;
-    let mut trapdoor_props = TrapDoorProperties::from_state_id(block_state, block);
-    trapdoor_props.open = !trapdoor_props.open;
-
-    world
-        .play_block_sound_expect(
-            player,
-            get_sound(block, trapdoor_props.open),
-            SoundCategory::Blocks,
-            *block_pos,
-        )
-        .await;
-
-    world
-        .set_block_state(
+async fn toggle_trapdoor(player: Option<&Player>, world: &Arc<World>, block_pos: &BlockPos) {
+    let Some((_, new_state_id)) = world
+        .modify_block_state::<TrapDoorProperties>(
             block_pos,
-            trapdoor_props.to_state_id(block),
             BlockFlags::NOTIFY_LISTENERS,
+            |props| props.open = !props.open,
         )
-        .await;
+        .await
+    else {
+        return;
+    };
+    let block = Block::from_state_id(new_state_id);
+    let trapdoor_props = TrapDoorProperties::from_state_id(new_state_id, block);
+
+    let sound = get_sound(block, trapdoor_props.open);
+    if let Some(player) = player {
+        world
+            .play_block_sound_expect(player, sound, SoundCategory::Blocks, *block_pos)
+            .await;
+    } else {
+        world
+            .play_block_sound(sound, SoundCategory::Blocks, *block_pos)
+            .await;
+    }
 }
 
 fn can_open_trapdoor(block: &Block) -> bool {
@@ -76,11 +79,19 @@ impl BlockBehaviour for TrapDoorBlock {
             return BlockActionResult::Pass;
         }
 
-        toggle_trapdoor(args.player, args.world, args.position).await;
+        toggle_trapdoor(Some(args.player), args.world, args.position).await;
 
         BlockActionResult::Success
     }
 
+    async fn on_wind_burst(&self, args: ExplodeArgs<'_>) {
+        let state_id = args.world.get_block_state_id(args.position).await;
+        let trapdoor_props = TrapDoorProperties::from_state_id(state_id, args.block);
+        if can_open_trapdoor(args.block) && !trapdoor_props.powered {
+            toggle_trapdoor(None, args.world, args.position).await;
+        }
+    }
+
     async fn on_place(&self, args: OnPlaceArgs<'_>) -> BlockStateId {
         let mut trapdoor_props = TrapDoorProperties::default(args.block);
         trapdoor_props.waterlogged = args.replacing.water_source();
@@ -1,5 +1,5 @@
 use pumpkin_data::block_properties::Integer0To15;
-use pumpkin_data::{Block, BlockState};
+use pumpkin_data::{Block, BlockState, damage::DamageType};
 
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::random::{RandomGenerator, get_seed, xoroshiro128::Xoroshiro};
@@ -14,17 +14,21 @@ use std::sync::Arc;
 pub mod blocks;
 pub mod fluid;
 pub mod registry;
+pub mod support;
+pub mod variant;
 
 use crate::block::registry::BlockActionResult;
+use crate::block::support::BlockSupport;
 use crate::entity::EntityBase;
 use crate::server::Server;
 use async_trait::async_trait;
 use pumpkin_data::BlockDirection;
 use pumpkin_protocol::java::server::play::SUseItemOn;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::inventory::Inventory;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::{BlockAccessor, BlockFlags};
-use tokio::sync::Mutex;
 
 pub trait BlockMetadata {
     fn namespace(&self) -> &'static str;
@@ -49,12 +53,34 @@ pub trait BlockBehaviour: Send + Sync {
 
     async fn on_entity_collision(&self, _args: OnEntityCollisionArgs<'_>) {}
 
+    /// Called every tick an entity moves while standing on the block.
+    async fn on_stepped_on(&self, _args: OnSteppedOnArgs<'_>) {}
+
+    /// Called when an entity collides with the block vertically, usually by landing on it. Stops
+    /// the entity from moving up or down any further unless overridden.
+    async fn on_entity_land(&self, args: OnEntityLandArgs<'_>) {
+        let entity = args.entity.get_entity();
+        let mut velocity = entity.velocity.load();
+        velocity.y = 0.0;
+        entity.velocity.store(velocity);
+    }
+
+    /// Called when a falling entity hits the ground on the block. Returns how the fall hurts the
+    /// entity.
+    async fn on_landed_upon(&self, args: OnLandedUponArgs<'_>) -> FallDamage {
+        FallDamage::new(args.fall_distance)
+    }
+
     fn should_drop_items_on_explosion(&self) -> bool {
         true
     }
 
     async fn explode(&self, _args: ExplodeArgs<'_>) {}
 
+    /// Called for blocks caught in the burst of a wind charge, which opens doors and presses
+    /// buttons instead of destroying anything.
+    async fn on_wind_burst(&self, _args: ExplodeArgs<'_>) {}
+
     /// Handles the block event, which is an event specific to a block with an integer ID and data.
     ///
     /// returns whether the event was handled successfully
@@ -69,6 +95,12 @@ pub trait BlockBehaviour: Send + Sync {
 
     async fn random_tick(&self, _args: RandomTickArgs<'_>) {}
 
+    /// Whether the block makes up for the random ticks it missed while its chunk was unloaded,
+    /// see [`World::catch_up_chunk`].
+    fn catches_up_random_ticks(&self) -> bool {
+        false
+    }
+
     async fn can_place_at(&self, _args: CanPlaceAtArgs<'_>) -> bool {
         true
     }
@@ -77,11 +109,20 @@ pub trait BlockBehaviour: Send + Sync {
         false
     }
 
+    /// The neighbors this block needs to stay in place, see [`BlockSupport`].
+    fn supports(&self, _block: &Block, _state_id: BlockStateId) -> &'static [BlockSupport] {
+        &[]
+    }
+
     /// onBlockAdded in source code
     async fn placed(&self, _args: PlacedArgs<'_>) {}
 
     async fn player_placed(&self, _args: PlayerPlacedArgs<'_>) {}
 
+    /// onBreak in source code, called before a player removes the block so it can still change
+    /// its state and affect what it drops.
+    async fn on_break(&self, _args: OnBreakArgs<'_>) {}
+
     async fn broken(&self, _args: BrokenArgs<'_>) {}
 
     async fn on_neighbor_update(&self, _args: OnNeighborUpdateArgs<'_>) {}
@@ -118,6 +159,12 @@ pub trait BlockBehaviour: Send + Sync {
     async fn get_comparator_output(&self, _args: GetComparatorOutputArgs<'_>) -> Option<u8> {
         None
     }
+
+    /// The container the block itself offers to hoppers and droppers, for blocks that keep their
+    /// contents in their state rather than in a block entity.
+    async fn get_container(&self, _args: GetContainerArgs<'_>) -> Option<Arc<dyn Inventory>> {
+        None
+    }
 }
 
 pub struct NormalUseArgs<'a> {
@@ -136,7 +183,7 @@ pub struct UseWithItemArgs<'a> {
     pub position: &'a BlockPos,
     pub player: &'a Player,
     pub hit: &'a BlockHitResult<'a>,
-    pub item_stack: &'a Arc<Mutex<ItemStack>>,
+    pub item_stack: &'a Arc<OrderedMutex<ItemStack>>,
 }
 
 pub struct BlockHitResult<'a> {
@@ -153,6 +200,50 @@ pub struct OnEntityCollisionArgs<'a> {
     pub entity: &'a dyn EntityBase,
 }
 
+pub struct OnSteppedOnArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub state: &'a BlockState,
+    pub position: &'a BlockPos,
+    pub entity: &'a Arc<dyn EntityBase>,
+}
+
+pub struct OnEntityLandArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub position: &'a BlockPos,
+    pub entity: &'a dyn EntityBase,
+}
+
+pub struct OnLandedUponArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub position: &'a BlockPos,
+    pub entity: &'a dyn EntityBase,
+    pub fall_distance: f32,
+}
+
+/// How an entity is hurt by landing on a block.
+pub struct FallDamage {
+    /// The distance the damage is calculated from, blocks like stalagmites add to it
+    pub fall_distance: f32,
+    /// How much of the usual fall damage the entity takes
+    pub multiplier: f32,
+    pub damage_type: DamageType,
+}
+
+impl FallDamage {
+    /// The usual fall damage for falling `fall_distance` blocks.
+    #[must_use]
+    pub const fn new(fall_distance: f32) -> Self {
+        Self {
+            fall_distance,
+            multiplier: 1.0,
+            damage_type: DamageType::FALL,
+        }
+    }
+}
+
 pub struct ExplodeArgs<'a> {
     pub world: &'a Arc<World>,
     pub block: &'a Block,
@@ -223,6 +314,14 @@ pub struct PlayerPlacedArgs<'a> {
     pub player: &'a Player,
 }
 
+pub struct OnBreakArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub state_id: BlockStateId,
+    pub position: &'a BlockPos,
+    pub player: &'a Arc<Player>,
+}
+
 pub struct BrokenArgs<'a> {
     pub block: &'a Block,
     pub player: &'a Arc<Player>,
@@ -293,6 +392,13 @@ pub struct GetComparatorOutputArgs<'a> {
     pub position: &'a BlockPos,
 }
 
+pub struct GetContainerArgs<'a> {
+    pub world: &'a Arc<World>,
+    pub block: &'a Block,
+    pub state: &'a BlockState,
+    pub position: &'a BlockPos,
+}
+
 #[derive(Clone)]
 pub struct BlockEvent {
     pub pos: BlockPos,
@@ -300,6 +406,10 @@ pub struct BlockEvent {
     pub data: u8,
 }
 
+/// Drops the loot of a broken block.
+///
+/// `params` describes how the block was broken (tool, explosion, ...). Experience is only dropped if
+/// `experience` is set and the tool is not enchanted with silk touch.
 pub async fn drop_loot(
     world: &Arc<World>,
     block: &Block,
@@ -307,22 +417,58 @@ pub async fn drop_loot(
     experience: bool,
     params: LootContextParameters,
 ) {
-    if let Some(loot_table) = &block.loot_table {
-        for stack in loot_table.get_loot(params) {
-            world.drop_stack(pos, stack).await;
-        }
+    let silk_touch = params.has_silk_touch();
+    for stack in block_loot(block, params) {
+        world.drop_stack(pos, stack).await;
     }
 
-    if experience && let Some(experience) = &block.experience {
+    if experience
+        && !silk_touch
+        && let Some(experience) = &block.experience
+    {
         let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(get_seed()));
         let amount = experience.experience.get(&mut random);
-        // TODO: Silk touch gives no exp
         if amount > 0 {
             ExperienceOrbEntity::spawn(world, pos.to_f64(), amount as u32).await;
         }
     }
 }
 
+/// Rolls the loot table of a block, merging identical stacks.
+#[must_use]
+pub fn block_loot(block: &Block, params: LootContextParameters) -> Vec<ItemStack> {
+    let mut stacks = Vec::new();
+    if let Some(loot_table) = &block.loot_table {
+        for stack in loot_table.get_loot(params) {
+            let max_count = stack.get_max_stack_size();
+            merge_stack(&mut stacks, stack, max_count);
+        }
+    }
+    stacks
+}
+
+/// Adds `stack` to `stacks`, filling up identical stacks to at most `max_count` items first.
+pub fn merge_stack(stacks: &mut Vec<ItemStack>, mut stack: ItemStack, max_count: u8) {
+    for existing in stacks.iter_mut() {
+        if stack.is_empty() {
+            return;
+        }
+        merge_into(existing, &mut stack, max_count);
+    }
+    if !stack.is_empty() {
+        stacks.push(stack);
+    }
+}
+
+/// Moves items from `stack` into `existing` if both are identical, up to `max_count` items.
+pub fn merge_into(existing: &mut ItemStack, stack: &mut ItemStack, max_count: u8) {
+    if existing.item_count < max_count && existing.are_items_and_components_equal(stack) {
+        let moved = (max_count - existing.item_count).min(stack.item_count);
+        existing.increment(moved);
+        stack.decrement(moved);
+    }
+}
+
 pub async fn calc_block_breaking(
     player: &Player,
     state: &BlockState,
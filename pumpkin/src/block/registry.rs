@@ -1,15 +1,20 @@
+use crate::block::blocks::amethyst::{AmethystClusterBlock, BuddingAmethystBlock};
 use crate::block::blocks::anvil::AnvilBlock;
 use crate::block::blocks::bamboo::BambooBlock;
 use crate::block::blocks::barrel::BarrelBlock;
 use crate::block::blocks::bed::BedBlock;
+use crate::block::blocks::brewing_stand::BrewingStandBlock;
 use crate::block::blocks::cactus::CactusBlock;
 use crate::block::blocks::carpet::{CarpetBlock, MossCarpetBlock, PaleMossCarpetBlock};
 use crate::block::blocks::carved_pumpkin::CarvedPumpkinBlock;
 use crate::block::blocks::chests::ChestBlock;
+use crate::block::blocks::cobweb::CobwebBlock;
 use crate::block::blocks::command::CommandBlock;
 use crate::block::blocks::composter::ComposterBlock;
+use crate::block::blocks::decorated_pot::DecoratedPotBlock;
 use crate::block::blocks::dirt_path::DirtPathBlock;
 use crate::block::blocks::doors::DoorBlock;
+use crate::block::blocks::end_gateway::EndGatewayBlock;
 use crate::block::blocks::end_portal::EndPortalBlock;
 use crate::block::blocks::end_portal_frame::EndPortalFrameBlock;
 use crate::block::blocks::falling::FallingBlock;
@@ -21,8 +26,10 @@ use crate::block::blocks::fire::soul_fire::SoulFireBlock;
 use crate::block::blocks::furnace::FurnaceBlock;
 use crate::block::blocks::glass_panes::GlassPaneBlock;
 use crate::block::blocks::grindstone::GrindstoneBlock;
+use crate::block::blocks::honey::HoneyBlock;
 use crate::block::blocks::iron_bars::IronBarsBlock;
 use crate::block::blocks::logs::LogBlock;
+use crate::block::blocks::magma::MagmaBlock;
 use crate::block::blocks::nether_portal::NetherPortalBlock;
 use crate::block::blocks::note::NoteBlock;
 use crate::block::blocks::piston::piston::PistonBlock;
@@ -38,6 +45,7 @@ use crate::block::blocks::plant::mushroom_plant::MushroomPlantBlock;
 use crate::block::blocks::plant::sapling::SaplingBlock;
 use crate::block::blocks::plant::short_plant::ShortPlantBlock;
 use crate::block::blocks::plant::tall_plant::TallPlantBlock;
+use crate::block::blocks::pointed_dripstone::PointedDripstoneBlock;
 use crate::block::blocks::pumpkin::PumpkinBlock;
 use crate::block::blocks::redstone::buttons::ButtonBlock;
 use crate::block::blocks::redstone::comparator::ComparatorBlock;
@@ -60,6 +68,7 @@ use crate::block::blocks::redstone::tripwire::TripwireBlock;
 use crate::block::blocks::redstone::tripwire_hook::TripwireHookBlock;
 use crate::block::blocks::signs::SignBlock;
 use crate::block::blocks::slabs::SlabBlock;
+use crate::block::blocks::slime::SlimeBlock;
 use crate::block::blocks::spawner::SpawnerBlock;
 use crate::block::blocks::stairs::StairBlock;
 use crate::block::blocks::sugar_cane::SugarCaneBlock;
@@ -70,25 +79,33 @@ use crate::block::blocks::vine::VineBlock;
 use crate::block::blocks::walls::WallBlock;
 use crate::block::fluid::lava::FlowingLava;
 use crate::block::fluid::water::FlowingWater;
-use crate::block::{BlockBehaviour, BlockHitResult, BlockMetadata, OnEntityCollisionArgs};
+use crate::block::support::BlockSupport;
+use crate::block::{
+    BlockBehaviour, BlockHitResult, BlockMetadata, FallDamage, OnEntityCollisionArgs,
+    OnEntityLandArgs, OnLandedUponArgs, OnSteppedOnArgs,
+};
 use crate::entity::EntityBase;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
+use crate::world::neighbor_update::{self, NeighborUpdate};
 use pumpkin_data::fluid;
 use pumpkin_data::fluid::Fluid;
 use pumpkin_data::item::Item;
+use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_protocol::java::server::play::SUseItemOn;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::BlockStateId;
+use pumpkin_world::inventory::Inventory;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::{BlockAccessor, BlockFlags, BlockRegistryExt};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 use crate::block::blocks::banners::BannerBlock;
+use crate::block::blocks::beehive::BeehiveBlock;
 use crate::block::blocks::cake::CakeBlock;
 use crate::block::blocks::campfire::CampfireBlock;
 use crate::block::blocks::candle_cakes::CandleCakeBlock;
@@ -114,9 +131,9 @@ use super::blocks::plant::crop::gourds::stem::StemBlock;
 use super::fluid::FluidBehaviour;
 use super::{
     BrokenArgs, CanPlaceAtArgs, CanUpdateAtArgs, EmitsRedstonePowerArgs, ExplodeArgs,
-    GetRedstonePowerArgs, GetStateForNeighborUpdateArgs, NormalUseArgs, OnNeighborUpdateArgs,
-    OnPlaceArgs, OnStateReplacedArgs, OnSyncedBlockEventArgs, PlacedArgs, PlayerPlacedArgs,
-    PrepareArgs, UseWithItemArgs,
+    GetContainerArgs, GetRedstonePowerArgs, GetStateForNeighborUpdateArgs, NormalUseArgs,
+    OnBreakArgs, OnPlaceArgs, OnStateReplacedArgs, OnSyncedBlockEventArgs, PlacedArgs,
+    PlayerPlacedArgs, PrepareArgs, UseWithItemArgs,
 };
 use crate::block::blocks::blast_furnace::BlastFurnaceBlock;
 use crate::block::blocks::chain::ChainBlock;
@@ -132,6 +149,16 @@ use crate::block::blocks::shulker_box::ShulkerBoxBlock;
 use crate::block::blocks::skull_block::SkullBlock;
 use crate::block::blocks::smoker::SmokerBlock;
 
+/// Registers a shared behaviour for every block of a block tag, e.g.
+/// `register_tag_blocks!(manager, "minecraft:buttons" => ButtonBlock)`.
+///
+/// Behaviours can look up their per-block data with [`crate::block::variant::BlockVariant`].
+macro_rules! register_tag_blocks {
+    ($manager:expr, $($tag:literal => $block:expr),+ $(,)?) => {
+        $($manager.register_tag($tag, $block);)+
+    };
+}
+
 #[must_use]
 #[allow(clippy::too_many_lines)]
 pub fn default_registry() -> Arc<BlockRegistry> {
@@ -164,7 +191,6 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(GrindstoneBlock);
     manager.register(IronBarsBlock);
     manager.register(JukeboxBlock);
-    manager.register(LogBlock);
     manager.register(BambooBlock);
     manager.register(BannerBlock);
     manager.register(SignBlock);
@@ -209,21 +235,35 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(CandleCakeBlock);
     manager.register(SkullBlock);
     manager.register(ChiseledBookshelfBlock);
+    manager.register(DecoratedPotBlock);
     manager.register(LecternBlock);
     manager.register(StemBlock);
     manager.register(AttachedStemBlock);
     manager.register(ChainBlock);
     manager.register(LanternBlock);
     manager.register(EndRodBlock);
+    manager.register(SlimeBlock);
+    manager.register(HoneyBlock);
+    manager.register(CobwebBlock);
+    manager.register(MagmaBlock);
+    manager.register(PointedDripstoneBlock);
+    manager.register(BuddingAmethystBlock);
+    manager.register(AmethystClusterBlock);
+    manager.register(EndGatewayBlock);
 
     manager.register(FallingBlock);
 
+    register_tag_blocks!(manager,
+        "minecraft:logs" => LogBlock,
+        "minecraft:buttons" => ButtonBlock,
+        "minecraft:beehives" => BeehiveBlock,
+    );
+
     // Fire
     manager.register(SoulFireBlock);
     manager.register(FireBlock);
 
     // Redstone
-    manager.register(ButtonBlock);
     manager.register(LeverBlock);
     manager.register(ObserverBlock);
     manager.register(TripwireBlock);
@@ -243,6 +283,7 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(ComparatorBlock);
     manager.register(TargetBlock);
     manager.register(BarrelBlock);
+    manager.register(BrewingStandBlock);
     manager.register(HopperBlock);
     manager.register(ShulkerBoxBlock);
     manager.register(DropperBlock);
@@ -285,12 +326,31 @@ impl BlockActionResult {
     }
 }
 
-#[derive(Default)]
+/// Baseline behaviour for blocks without a registered behaviour.
+pub struct DefaultBlockBehaviour;
+
+impl BlockBehaviour for DefaultBlockBehaviour {}
+
+/// Index of [`DefaultBlockBehaviour`] in [`BlockRegistry::behaviours`].
+const DEFAULT_BEHAVIOUR: u16 = 0;
+
 pub struct BlockRegistry {
-    blocks: HashMap<&'static Block, Arc<dyn BlockBehaviour>>,
+    /// Index into `behaviours` for every block id.
+    block_handlers: Vec<u16>,
+    behaviours: Vec<Arc<dyn BlockBehaviour>>,
     fluids: HashMap<&'static Fluid, Arc<dyn FluidBehaviour>>,
 }
 
+impl Default for BlockRegistry {
+    fn default() -> Self {
+        Self {
+            block_handlers: Vec::new(),
+            behaviours: vec![Arc::new(DefaultBlockBehaviour)],
+            fluids: HashMap::new(),
+        }
+    }
+}
+
 impl BlockRegistryExt for BlockRegistry {
     fn can_place_at(
         &self,
@@ -318,14 +378,42 @@ impl BlockRegistryExt for BlockRegistry {
 impl BlockRegistry {
     pub fn register<T: BlockBehaviour + BlockMetadata + 'static>(&mut self, block: T) {
         let names = block.names();
-        let val = Arc::new(block);
-        self.blocks.reserve(names.len());
+        let handler = self.add_behaviour(Arc::new(block));
         for i in names {
-            self.blocks
-                .insert(Block::from_name(i.as_str()).unwrap(), val.clone());
+            self.set_handler(Block::from_name(i.as_str()).unwrap(), handler);
+        }
+    }
+
+    /// Registers one shared behaviour for every block in the given block tag.
+    ///
+    /// # Panics
+    /// Panics if the tag does not exist or contains an unknown block.
+    pub fn register_tag<T: BlockBehaviour + 'static>(&mut self, tag: &str, block: T) {
+        let names = get_tag_values(RegistryKey::Block, tag)
+            .unwrap_or_else(|| panic!("Unknown block tag {tag}"));
+        let handler = self.add_behaviour(Arc::new(block));
+        for name in names {
+            self.set_handler(
+                Block::from_name(name).unwrap_or_else(|| panic!("Unknown block {name} in {tag}")),
+                handler,
+            );
         }
     }
 
+    fn add_behaviour(&mut self, behaviour: Arc<dyn BlockBehaviour>) -> u16 {
+        let handler = u16::try_from(self.behaviours.len()).expect("Too many block behaviours");
+        self.behaviours.push(behaviour);
+        handler
+    }
+
+    fn set_handler(&mut self, block: &Block, handler: u16) {
+        let index = block.id as usize;
+        if index >= self.block_handlers.len() {
+            self.block_handlers.resize(index + 1, DEFAULT_BEHAVIOUR);
+        }
+        self.block_handlers[index] = handler;
+    }
+
     pub fn register_fluid<T: FluidBehaviour + BlockMetadata + 'static>(&mut self, fluid: T) {
         let names = fluid.names();
         let val = Arc::new(fluid);
@@ -344,19 +432,15 @@ impl BlockRegistry {
         r#type: u8,
         data: u8,
     ) -> bool {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .on_synced_block_event(OnSyncedBlockEventArgs {
-                    world,
-                    block,
-                    position,
-                    r#type,
-                    data,
-                })
-                .await;
-        }
-        false
+        self.get_behaviour(block)
+            .on_synced_block_event(OnSyncedBlockEventArgs {
+                world,
+                block,
+                position,
+                r#type,
+                data,
+            })
+            .await
     }
 
     pub async fn on_entity_collision(
@@ -368,19 +452,71 @@ impl BlockRegistry {
         state: &BlockState,
         server: &Server,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .on_entity_collision(OnEntityCollisionArgs {
-                    server,
-                    world,
-                    block,
-                    state,
-                    position,
-                    entity,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .on_entity_collision(OnEntityCollisionArgs {
+                server,
+                world,
+                block,
+                state,
+                position,
+                entity,
+            })
+            .await;
+    }
+
+    pub async fn on_stepped_on(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        entity: &Arc<dyn EntityBase>,
+        position: &BlockPos,
+        state: &BlockState,
+    ) {
+        self.get_behaviour(block)
+            .on_stepped_on(OnSteppedOnArgs {
+                world,
+                block,
+                state,
+                position,
+                entity,
+            })
+            .await;
+    }
+
+    pub async fn on_entity_land(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        entity: &dyn EntityBase,
+        position: &BlockPos,
+    ) {
+        self.get_behaviour(block)
+            .on_entity_land(OnEntityLandArgs {
+                world,
+                block,
+                position,
+                entity,
+            })
+            .await;
+    }
+
+    pub async fn on_landed_upon(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        entity: &dyn EntityBase,
+        position: &BlockPos,
+        fall_distance: f32,
+    ) -> FallDamage {
+        self.get_behaviour(block)
+            .on_landed_upon(OnLandedUponArgs {
+                world,
+                block,
+                position,
+                entity,
+                fall_distance,
+            })
+            .await
     }
 
     pub async fn on_entity_collision_fluid(&self, fluid: &Fluid, entity: &dyn EntityBase) {
@@ -399,33 +535,36 @@ impl BlockRegistry {
         server: &Server,
         world: &Arc<World>,
     ) -> BlockActionResult {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .normal_use(NormalUseArgs {
-                    server,
-                    world,
-                    block,
-                    position,
-                    player,
-                    hit,
-                })
-                .await;
-        }
-        BlockActionResult::Pass
+        self.get_behaviour(block)
+            .normal_use(NormalUseArgs {
+                server,
+                world,
+                block,
+                position,
+                player,
+                hit,
+            })
+            .await
     }
 
     pub async fn explode(&self, block: &Block, world: &Arc<World>, position: &BlockPos) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .explode(ExplodeArgs {
-                    world,
-                    block,
-                    position,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .explode(ExplodeArgs {
+                world,
+                block,
+                position,
+            })
+            .await;
+    }
+
+    pub async fn on_wind_burst(&self, block: &Block, world: &Arc<World>, position: &BlockPos) {
+        self.get_behaviour(block)
+            .on_wind_burst(ExplodeArgs {
+                world,
+                block,
+                position,
+            })
+            .await;
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -435,7 +574,7 @@ impl BlockRegistry {
         player: &Player,
         position: &BlockPos,
         hit: &BlockHitResult<'_>,
-        item_stack: &Arc<Mutex<ItemStack>>,
+        item_stack: &Arc<OrderedMutex<ItemStack>>,
         server: &Server,
         world: &Arc<World>,
     ) -> BlockActionResult {
@@ -486,22 +625,23 @@ impl BlockRegistry {
         direction: BlockDirection,
         use_item_on: Option<&SUseItemOn>,
     ) -> bool {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .can_place_at(CanPlaceAtArgs {
-                    server,
-                    world,
-                    block_accessor,
-                    block,
-                    position,
-                    direction,
-                    player,
-                    use_item_on,
-                })
-                .await;
-        }
-        true
+        self.get_behaviour(block)
+            .can_place_at(CanPlaceAtArgs {
+                server,
+                world,
+                block_accessor,
+                block,
+                position,
+                direction,
+                player,
+                use_item_on,
+            })
+            .await
+    }
+
+    #[must_use]
+    pub fn get_supports(&self, block: &Block, state_id: BlockStateId) -> &'static [BlockSupport] {
+        self.get_behaviour(block).supports(block, state_id)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -515,21 +655,17 @@ impl BlockRegistry {
         use_item_on: &SUseItemOn,
         player: &Player,
     ) -> bool {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .can_update_at(CanUpdateAtArgs {
-                    world,
-                    block,
-                    state_id,
-                    position,
-                    direction,
-                    player,
-                    use_item_on,
-                })
-                .await;
-        }
-        false
+        self.get_behaviour(block)
+            .can_update_at(CanUpdateAtArgs {
+                world,
+                block,
+                state_id,
+                position,
+                direction,
+                player,
+                use_item_on,
+            })
+            .await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -544,22 +680,18 @@ impl BlockRegistry {
         replacing: BlockIsReplacing,
         use_item_on: &SUseItemOn,
     ) -> BlockStateId {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .on_place(OnPlaceArgs {
-                    server,
-                    world,
-                    block,
-                    position,
-                    direction,
-                    player,
-                    replacing,
-                    use_item_on,
-                })
-                .await;
-        }
-        block.default_state.id
+        self.get_behaviour(block)
+            .on_place(OnPlaceArgs {
+                server,
+                world,
+                block,
+                position,
+                direction,
+                player,
+                replacing,
+                use_item_on,
+            })
+            .await
     }
 
     pub async fn player_placed(
@@ -571,19 +703,16 @@ impl BlockRegistry {
         direction: BlockDirection,
         player: &Player,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .player_placed(PlayerPlacedArgs {
-                    world,
-                    block,
-                    state_id,
-                    position,
-                    direction,
-                    player,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .player_placed(PlayerPlacedArgs {
+                world,
+                block,
+                state_id,
+                position,
+                direction,
+                player,
+            })
+            .await;
     }
 
     pub async fn on_placed(
@@ -595,19 +724,16 @@ impl BlockRegistry {
         old_state_id: BlockStateId,
         notify: bool,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .placed(PlacedArgs {
-                    world,
-                    block,
-                    state_id,
-                    old_state_id,
-                    position,
-                    notify,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .placed(PlacedArgs {
+                world,
+                block,
+                state_id,
+                old_state_id,
+                position,
+                notify,
+            })
+            .await;
     }
 
     pub async fn on_placed_fluid(
@@ -627,6 +753,25 @@ impl BlockRegistry {
         }
     }
 
+    pub async fn on_break(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        state_id: BlockStateId,
+        position: &BlockPos,
+        player: &Arc<Player>,
+    ) {
+        self.get_behaviour(block)
+            .on_break(OnBreakArgs {
+                world,
+                block,
+                state_id,
+                position,
+                player,
+            })
+            .await;
+    }
+
     pub async fn broken(
         &self,
         world: &Arc<World>,
@@ -636,19 +781,16 @@ impl BlockRegistry {
         server: &Server,
         state: &BlockState,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .broken(BrokenArgs {
-                    block,
-                    player,
-                    position,
-                    server,
-                    world,
-                    state,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .broken(BrokenArgs {
+                block,
+                player,
+                position,
+                server,
+                world,
+                state,
+            })
+            .await;
     }
 
     pub async fn on_state_replaced(
@@ -659,18 +801,32 @@ impl BlockRegistry {
         old_state_id: BlockStateId,
         moved: bool,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .on_state_replaced(OnStateReplacedArgs {
-                    world,
-                    block,
-                    old_state_id,
-                    position,
-                    moved,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .on_state_replaced(OnStateReplacedArgs {
+                world,
+                block,
+                old_state_id,
+                position,
+                moved,
+            })
+            .await;
+    }
+
+    pub async fn get_container(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        state: &BlockState,
+        position: &BlockPos,
+    ) -> Option<Arc<dyn Inventory>> {
+        self.get_behaviour(block)
+            .get_container(GetContainerArgs {
+                world,
+                block,
+                state,
+                position,
+            })
+            .await
     }
 
     /// Updates state of all neighbors of the block
@@ -711,18 +867,15 @@ impl BlockRegistry {
         state_id: BlockStateId,
         flags: BlockFlags,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .prepare(PrepareArgs {
-                    world,
-                    block,
-                    state_id,
-                    position,
-                    flags,
-                })
-                .await;
-        }
+        self.get_behaviour(block)
+            .prepare(PrepareArgs {
+                world,
+                block,
+                state_id,
+                position,
+                flags,
+            })
+            .await;
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -736,21 +889,17 @@ impl BlockRegistry {
         neighbor_location: &BlockPos,
         neighbor_state_id: BlockStateId,
     ) -> BlockStateId {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .get_state_for_neighbor_update(GetStateForNeighborUpdateArgs {
-                    world,
-                    block,
-                    state_id,
-                    position,
-                    direction,
-                    neighbor_position: neighbor_location,
-                    neighbor_state_id,
-                })
-                .await;
-        }
-        state_id
+        self.get_behaviour(block)
+            .get_state_for_neighbor_update(GetStateForNeighborUpdateArgs {
+                world,
+                block,
+                state_id,
+                position,
+                direction,
+                neighbor_position: neighbor_location,
+                neighbor_state_id,
+            })
+            .await
     }
 
     pub async fn update_neighbors(
@@ -761,42 +910,57 @@ impl BlockRegistry {
         flags: BlockFlags,
     ) {
         for direction in BlockDirection::abstract_block_update_order() {
-            let pos = position.offset(direction.to_offset());
-
-            Box::pin(world.replace_with_state_for_neighbor_update(
-                &pos,
-                direction.opposite(),
-                flags,
-            ))
+            neighbor_update::run(
+                world,
+                NeighborUpdate::Shape {
+                    position: position.offset(direction.to_offset()),
+                    direction: direction.opposite(),
+                    flags,
+                },
+            )
             .await;
         }
     }
 
+    /// Queues a neighbor update of the block at `position`, see [`neighbor_update`].
     pub async fn on_neighbor_update(
         &self,
         world: &Arc<World>,
-        block: &Block,
         position: &BlockPos,
         source_block: &Block,
         notify: bool,
     ) {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            pumpkin_block
-                .on_neighbor_update(OnNeighborUpdateArgs {
-                    world,
-                    block,
-                    position,
-                    source_block,
-                    notify,
-                })
-                .await;
-        }
+        neighbor_update::run(
+            world,
+            NeighborUpdate::Neighbor {
+                position: *position,
+                source_block: Block::from_id(source_block.id),
+                notify,
+            },
+        )
+        .await;
     }
 
     #[must_use]
     pub fn get_pumpkin_block(&self, block: &Block) -> Option<&Arc<dyn BlockBehaviour>> {
-        self.blocks.get(block)
+        match self.handler(block) {
+            DEFAULT_BEHAVIOUR => None,
+            handler => Some(&self.behaviours[handler as usize]),
+        }
+    }
+
+    /// Like [`Self::get_pumpkin_block`], but falls back to [`DefaultBlockBehaviour`].
+    #[must_use]
+    pub fn get_behaviour(&self, block: &Block) -> &Arc<dyn BlockBehaviour> {
+        &self.behaviours[self.handler(block) as usize]
+    }
+
+    #[inline]
+    fn handler(&self, block: &Block) -> u16 {
+        self.block_handlers
+            .get(block.id as usize)
+            .copied()
+            .unwrap_or(DEFAULT_BEHAVIOUR)
     }
 
     #[must_use]
@@ -810,17 +974,13 @@ impl BlockRegistry {
         state: &BlockState,
         direction: BlockDirection,
     ) -> bool {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .emits_redstone_power(EmitsRedstonePowerArgs {
-                    block,
-                    state,
-                    direction,
-                })
-                .await;
-        }
-        false
+        self.get_behaviour(block)
+            .emits_redstone_power(EmitsRedstonePowerArgs {
+                block,
+                state,
+                direction,
+            })
+            .await
     }
 
     pub async fn get_weak_redstone_power(
@@ -831,19 +991,15 @@ impl BlockRegistry {
         state: &BlockState,
         direction: BlockDirection,
     ) -> u8 {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .get_weak_redstone_power(GetRedstonePowerArgs {
-                    world,
-                    block,
-                    state,
-                    position,
-                    direction,
-                })
-                .await;
-        }
-        0
+        self.get_behaviour(block)
+            .get_weak_redstone_power(GetRedstonePowerArgs {
+                world,
+                block,
+                state,
+                position,
+                direction,
+            })
+            .await
     }
 
     pub async fn get_strong_redstone_power(
@@ -854,18 +1010,14 @@ impl BlockRegistry {
         state: &BlockState,
         direction: BlockDirection,
     ) -> u8 {
-        let pumpkin_block = self.get_pumpkin_block(block);
-        if let Some(pumpkin_block) = pumpkin_block {
-            return pumpkin_block
-                .get_strong_redstone_power(GetRedstonePowerArgs {
-                    world,
-                    block,
-                    state,
-                    position,
-                    direction,
-                })
-                .await;
-        }
-        0
+        self.get_behaviour(block)
+            .get_strong_redstone_power(GetRedstonePowerArgs {
+                world,
+                block,
+                state,
+                position,
+                direction,
+            })
+            .await
     }
 }
@@ -0,0 +1,50 @@
+use pumpkin_data::BlockDirection;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::world::BlockAccessor;
+
+/// What a block needs from one of its neighbors to stay in place.
+///
+/// Blocks declaring supports through [`super::BlockBehaviour::supports`] are broken (with drops)
+/// when a supporting neighbor changes and no longer satisfies its requirement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockSupport {
+    /// Any non-air block in the given direction.
+    Any(BlockDirection),
+    /// A block in the given direction whose face towards this block covers its center.
+    Center(BlockDirection),
+    /// A block in the given direction whose face towards this block is sturdy.
+    Sturdy(BlockDirection),
+    /// A block in the given direction whose top face is sturdy, like the raised side of an
+    /// ascending rail.
+    SturdyTop(BlockDirection),
+}
+
+impl BlockSupport {
+    /// The direction of the supporting block.
+    #[must_use]
+    pub const fn direction(self) -> BlockDirection {
+        match self {
+            Self::Any(direction)
+            | Self::Center(direction)
+            | Self::Sturdy(direction)
+            | Self::SturdyTop(direction) => direction,
+        }
+    }
+
+    pub async fn is_satisfied(
+        self,
+        block_accessor: &dyn BlockAccessor,
+        position: &BlockPos,
+    ) -> bool {
+        let direction = self.direction();
+        let support = block_accessor
+            .get_block_state(&position.offset(direction.to_offset()))
+            .await;
+        match self {
+            Self::Any(_) => !support.is_air(),
+            Self::Center(_) => support.is_center_solid(direction.opposite()),
+            Self::Sturdy(_) => support.is_side_solid(direction.opposite()),
+            Self::SturdyTop(_) => support.is_side_solid(BlockDirection::Up),
+        }
+    }
+}
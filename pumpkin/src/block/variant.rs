@@ -0,0 +1,35 @@
+use pumpkin_data::Block;
+
+/// Per-block data for behaviours shared by every block of a tag.
+///
+/// Tagged blocks are usually named `<variant><suffix>`, e.g. `oak_button` is the `oak` variant of
+/// `_button`. The variant can then be used to find related blocks like `oak_planks`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockVariant {
+    pub block: &'static Block,
+    pub variant: &'static str,
+}
+
+impl BlockVariant {
+    /// Splits the block name into its variant by stripping `suffix`, e.g. `_button`.
+    ///
+    /// Returns `None` if the block name does not end with `suffix`.
+    #[must_use]
+    pub fn of(block: &Block, suffix: &str) -> Option<Self> {
+        let block = Block::from_id(block.id);
+        let variant = block.name.strip_suffix(suffix)?;
+        Some(Self { block, variant })
+    }
+
+    /// The block this variant is made of, e.g. `oak_planks` for the `oak` variant with `_planks`.
+    #[must_use]
+    pub fn base_block(&self, base_suffix: &str) -> Option<&'static Block> {
+        Block::from_name(&format!("{}{base_suffix}", self.variant))
+    }
+
+    /// Whether this is one of the given variants.
+    #[must_use]
+    pub fn is_any(&self, variants: &[&str]) -> bool {
+        variants.contains(&self.variant)
+    }
+}
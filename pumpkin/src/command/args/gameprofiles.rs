@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::CommandSender;
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::data::profile_cache::resolve_profile;
+use crate::net::GameProfile;
+use crate::server::Server;
+
+use super::super::args::ArgumentConsumer;
+use super::players::PlayersArgumentConsumer;
+use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Select the profiles of online players with a selector, or any player by name
+pub struct GameProfilesArgumentConsumer;
+
+impl GetClientSideArgParser for GameProfilesArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType<'_> {
+        ArgumentType::GameProfile
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for GameProfilesArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = *args.last()?;
+
+        let profiles = if s.starts_with('@') {
+            let Some(Arg::Players(players)) =
+                PlayersArgumentConsumer.consume(src, server, args).await
+            else {
+                return None;
+            };
+            players
+                .iter()
+                .map(|player| player.gameprofile.clone())
+                .collect()
+        } else {
+            args.pop();
+            match server.get_player_by_name(s).await {
+                Some(player) => vec![player.gameprofile.clone()],
+                // Players that are not online are looked up in the profile cache or at Mojang
+                None => vec![resolve_profile(s).await?],
+            }
+        };
+
+        Some(Arg::GameProfiles(profiles))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for GameProfilesArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "targets"
+    }
+}
+
+impl<'a> FindArg<'a> for GameProfilesArgumentConsumer {
+    type Data = &'a [GameProfile];
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::GameProfiles(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -21,8 +21,9 @@ use super::{
     tree::{CommandTree, RawArgs},
 };
 use crate::entity::EntityBase;
+use crate::world::World;
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
-use crate::{entity::player::Player, server::Server};
+use crate::{entity::player::Player, net::GameProfile, server::Server};
 
 pub mod block;
 pub mod bool;
@@ -35,6 +36,7 @@ pub mod difficulty;
 pub mod entities;
 pub mod entity;
 pub mod gamemode;
+pub mod gameprofiles;
 pub mod message;
 pub mod players;
 pub mod position_2d;
@@ -49,6 +51,7 @@ pub mod sound_category;
 pub mod summonable_entities;
 pub mod textcomponent;
 pub mod time;
+pub mod world;
 
 /// see [`crate::commands::tree::builder::argument`]
 #[async_trait]
@@ -87,6 +90,7 @@ pub enum Arg<'a> {
     Entities(Vec<Arc<dyn EntityBase>>),
     Entity(Arc<dyn EntityBase>),
     Players(Vec<Arc<Player>>),
+    GameProfiles(Vec<GameProfile>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
     Pos2D(Vector2<f64>),
@@ -112,6 +116,7 @@ pub enum Arg<'a> {
     DamageType(DamageType),
     Effect(&'static StatusEffect),
     Enchantment(&'static Enchantment),
+    World(Arc<World>),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
@@ -143,6 +148,26 @@ impl<'a, T, C: FindArg<'a, Data = T> + DefaultNameArgConsumer> FindArgDefaultNam
     }
 }
 
+/// Suggests the `candidates` that start with the word being typed at the end of `input`, ignoring
+/// case. Meant for [`ArgumentConsumer::suggest`] of arguments that ask the server.
+pub fn suggest_matching<I, S>(input: &str, candidates: I) -> Vec<CommandSuggestion>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let word = input
+        .split_single_whitespace_including_empty_parts()
+        .last()
+        .unwrap_or_default()
+        .to_lowercase();
+    candidates
+        .into_iter()
+        .map(Into::into)
+        .filter(|candidate| candidate.to_lowercase().starts_with(&word))
+        .map(|candidate| CommandSuggestion::new(candidate, None))
+        .collect()
+}
+
 pub(crate) trait SplitSingleWhitespaceIncludingEmptyParts<'a> {
     /// Splits a string at every single unicode whitespace. Therefore the returned iterator sometimes contains empty strings. This is useful for command suggestions.
     ///
@@ -196,3 +221,23 @@ impl<'a, T: DoubleEndedIterator<Item = (usize, char)>> Iterator
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::suggest_matching;
+
+    #[test]
+    fn suggest_matching_filters_by_last_word() {
+        let suggestions = suggest_matching("world tp Cr", ["creative", "survival", "Crafting"]);
+        let suggestions: Vec<_> = suggestions
+            .iter()
+            .map(|suggestion| suggestion.suggestion.as_str())
+            .collect();
+        assert_eq!(suggestions, ["creative", "Crafting"]);
+    }
+
+    #[test]
+    fn suggest_matching_suggests_everything_for_empty_word() {
+        assert_eq!(suggest_matching("world tp ", ["a", "b"]).len(), 2);
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::{
+    command::{CommandSender, dispatcher::CommandError, tree::RawArgs},
+    server::Server,
+    world::World,
+};
+
+use super::{
+    Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+    suggest_matching,
+};
+
+/// A world by its name, see [`World::name`].
+pub struct WorldArgumentConsumer;
+
+impl GetClientSideArgParser for WorldArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType<'_> {
+        ArgumentType::String(
+            pumpkin_protocol::java::client::play::StringProtoArgBehavior::SingleWord,
+        )
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for WorldArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let name = args.pop()?;
+        server.get_world(name).await.map(Arg::World)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let worlds = server.worlds.read().await;
+        Ok(Some(suggest_matching(
+            input,
+            worlds.iter().map(|world| world.name.clone()),
+        )))
+    }
+}
+
+impl DefaultNameArgConsumer for WorldArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "world"
+    }
+}
+
+impl<'a> FindArg<'a> for WorldArgumentConsumer {
+    type Data = Arc<World>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::World(data)) => Ok(data.clone()),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -1,16 +1,18 @@
-use crate::entity::EntityBase;
 use crate::{
     command::{
         CommandError, CommandExecutor, CommandSender,
-        args::{Arg, ConsumedArgs, message::MsgArgConsumer, players::PlayersArgumentConsumer},
+        args::{
+            Arg, ConsumedArgs, FindArg, gameprofiles::GameProfilesArgumentConsumer,
+            message::MsgArgConsumer,
+        },
         tree::{CommandTree, builder::argument},
     },
     data::{
         SaveJSONConfiguration, banlist_serializer::BannedPlayerEntry,
         banned_player_data::BANNED_PLAYER_LIST,
     },
-    entity::player::Player,
-    net::DisconnectReason,
+    net::{DisconnectReason, GameProfile},
+    server::Server,
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
@@ -29,14 +31,12 @@ impl CommandExecutor for NoReasonExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
-        ban_player(sender, &targets[0], None).await;
+        ban_player(sender, server, &targets[0], None).await;
         Ok(())
     }
 }
@@ -48,29 +48,31 @@ impl CommandExecutor for ReasonExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
         let Some(Arg::Msg(reason)) = args.get(ARG_REASON) else {
             return Err(InvalidConsumption(Some(ARG_REASON.into())));
         };
 
-        ban_player(sender, &targets[0], Some(reason.clone())).await;
+        ban_player(sender, server, &targets[0], Some(reason.clone())).await;
         Ok(())
     }
 }
 
-async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<String>) {
+async fn ban_player(
+    sender: &CommandSender,
+    server: &Server,
+    profile: &GameProfile,
+    reason: Option<String>,
+) {
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
-    let profile = &player.gameprofile;
 
-    if banned_players.get_entry(&player.gameprofile).is_some() {
+    if banned_players.get_entry(profile).is_some() {
         sender
             .send_message(TextComponent::translate("commands.ban.failed", []))
             .await;
@@ -91,21 +93,26 @@ async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<Stri
     sender
         .send_message(TextComponent::translate(
             "commands.ban.success",
-            [player.get_display_name().await, TextComponent::text(reason)],
+            [
+                TextComponent::text(profile.name.clone()),
+                TextComponent::text(reason),
+            ],
         ))
         .await;
 
-    player
-        .kick(
-            DisconnectReason::Kicked,
-            TextComponent::translate("multiplayer.disconnect.banned", []),
-        )
-        .await;
+    if let Some(player) = server.get_player_by_uuid(profile.id).await {
+        player
+            .kick(
+                DisconnectReason::Kicked,
+                TextComponent::translate("multiplayer.disconnect.banned", []),
+            )
+            .await;
+    }
 }
 
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
-        argument(ARG_TARGET, PlayersArgumentConsumer)
+        argument(ARG_TARGET, GameProfilesArgumentConsumer)
             .execute(NoReasonExecutor)
             .then(argument(ARG_REASON, MsgArgConsumer).execute(ReasonExecutor)),
     )
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::edit_session::WorldEditSession;
+
+const NAMES: [&str; 1] = ["clone"];
+
+const DESCRIPTION: &str = "Copies blocks from one region to another.";
+
+const ARG_BEGIN: &str = "begin";
+const ARG_END: &str = "end";
+const ARG_DESTINATION: &str = "destination";
+
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Copies every block, including air
+    Replace,
+    /// Copies only non-air blocks
+    Masked,
+}
+
+struct Executor(Mode);
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let begin = BlockPosArgumentConsumer::find_arg(args, ARG_BEGIN)?;
+        let end = BlockPosArgumentConsumer::find_arg(args, ARG_END)?;
+        let destination = BlockPosArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
+        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+
+        let min = Vector3::new(
+            begin.0.x.min(end.0.x),
+            begin.0.y.min(end.0.y),
+            begin.0.z.min(end.0.z),
+        );
+        let max = Vector3::new(
+            begin.0.x.max(end.0.x),
+            begin.0.y.max(end.0.y),
+            begin.0.z.max(end.0.z),
+        );
+        let offset = destination.0.sub(&min);
+        let destination_max = max.add(&offset);
+        let overlaps = destination.0.x <= max.x
+            && destination_max.x >= min.x
+            && destination.0.y <= max.y
+            && destination_max.y >= min.y
+            && destination.0.z <= max.z
+            && destination_max.z >= min.z;
+        if overlaps {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.clone.overlap", []),
+            )));
+        }
+
+        // TODO: Copy block entities and entities
+        let mut session = WorldEditSession::new();
+        let mut cloned = 0;
+        for position in BlockPos::iterate(BlockPos(min), BlockPos(max)) {
+            let state = world.get_block_state(&position).await;
+            if matches!(self.0, Mode::Masked) && state.is_air() {
+                continue;
+            }
+            session.set_block(position.offset(offset), state.id);
+            cloned += 1;
+        }
+
+        if cloned == 0 {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.clone.failed", []),
+            )));
+        }
+        world.submit_edit(session).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.clone.success",
+                [TextComponent::text(cloned.to_string())],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_BEGIN, BlockPosArgumentConsumer).then(
+            argument(ARG_END, BlockPosArgumentConsumer).then(
+                argument(ARG_DESTINATION, BlockPosArgumentConsumer)
+                    .then(literal("replace").execute(Executor(Mode::Replace)))
+                    .then(literal("masked").execute(Executor(Mode::Masked)))
+                    .execute(Executor(Mode::Replace)),
+            ),
+        ),
+    )
+}
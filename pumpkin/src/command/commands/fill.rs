@@ -6,6 +6,7 @@ use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, literal};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::edit_session::WorldEditSession;
 
 use async_trait::async_trait;
 use pumpkin_data::Block;
@@ -75,8 +76,13 @@ impl CommandExecutor for Executor {
         // TODO: check isInWorldBounds and throw argument.pos.outofbounds
 
         let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+        // Large fills are spread over multiple ticks
+        let mut session = if matches!(mode, Mode::Strict) {
+            WorldEditSession::strict()
+        } else {
+            WorldEditSession::new()
+        };
         let mut placed_blocks = 0;
-        let mut to_update = Vec::new();
         match mode {
             Mode::Destroy => {
                 for x in start_x..=end_x {
@@ -95,15 +101,8 @@ impl CommandExecutor for Executor {
                                     BlockFlags::SKIP_DROPS | BlockFlags::FORCE_STATE,
                                 )
                                 .await;
-                            world
-                                .set_block_state(
-                                    &block_position,
-                                    block_state_id,
-                                    BlockFlags::FORCE_STATE,
-                                )
-                                .await;
+                            session.set_block(block_position, block_state_id);
                             placed_blocks += 1;
-                            to_update.push(block_position);
                         }
                     }
                 }
@@ -118,15 +117,8 @@ impl CommandExecutor for Executor {
                             {
                                 continue;
                             }
-                            world
-                                .set_block_state(
-                                    &block_position,
-                                    block_state_id,
-                                    BlockFlags::FORCE_STATE,
-                                )
-                                .await;
+                            session.set_block(block_position, block_state_id);
                             placed_blocks += 1;
-                            to_update.push(block_position);
                         }
                     }
                 }
@@ -143,15 +135,8 @@ impl CommandExecutor for Executor {
                                 {
                                     continue;
                                 }
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::FORCE_STATE,
-                                    )
-                                    .await;
+                                session.set_block(block_position, block_state_id);
                                 placed_blocks += 1;
-                                to_update.push(block_position);
                             }
                         }
                     }
@@ -174,20 +159,11 @@ impl CommandExecutor for Executor {
                                 continue;
                             }
                             if is_edge {
-                                world
-                                    .set_block_state(
-                                        &block_position,
-                                        block_state_id,
-                                        BlockFlags::FORCE_STATE,
-                                    )
-                                    .await;
+                                session.set_block(block_position, block_state_id);
                             } else {
-                                world
-                                    .set_block_state(&block_position, 0, BlockFlags::FORCE_STATE)
-                                    .await;
+                                session.set_block(block_position, 0);
                             }
                             placed_blocks += 1;
-                            to_update.push(block_position);
                         }
                     }
                 }
@@ -211,15 +187,8 @@ impl CommandExecutor for Executor {
                             {
                                 continue;
                             }
-                            world
-                                .set_block_state(
-                                    &block_position,
-                                    block_state_id,
-                                    BlockFlags::FORCE_STATE,
-                                )
-                                .await;
+                            session.set_block(block_position, block_state_id);
                             placed_blocks += 1;
-                            to_update.push(block_position);
                         }
                     }
                 }
@@ -234,13 +203,7 @@ impl CommandExecutor for Executor {
                             {
                                 continue;
                             }
-                            world
-                                .set_block_state(
-                                    &block_position,
-                                    block_state_id,
-                                    BlockFlags::SKIP_BLOCK_ADDED_CALLBACK,
-                                )
-                                .await;
+                            session.set_block(block_position, block_state_id);
                             placed_blocks += 1;
                         }
                     }
@@ -248,9 +211,7 @@ impl CommandExecutor for Executor {
             }
         }
 
-        for i in to_update {
-            world.update_neighbors(&i, None).await;
-        }
+        world.submit_edit(session).await;
 
         sender
             .send_message(TextComponent::translate(
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_data::game_rules::{GameRule, GameRuleRegistry, GameRuleValue};
 
@@ -13,6 +15,8 @@ use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, literal};
 use crate::command::{CommandExecutor, CommandSender};
 use crate::server::Server;
+use pumpkin_world::world_info::LevelData;
+use tokio::sync::RwLock;
 
 const NAMES: [&str; 1] = ["gamerule"];
 
@@ -20,6 +24,14 @@ const DESCRIPTION: &str = "Sets or queries a game rule value.";
 
 const ARG_NAME: &str = "value";
 
+/// The game rules of the sender's world, as additional worlds have their own.
+fn level_info(sender: &CommandSender, server: &Server) -> Arc<RwLock<LevelData>> {
+    sender.world().map_or_else(
+        || server.level_info.clone(),
+        |world| world.level_info.clone(),
+    )
+}
+
 struct QueryExecutor(GameRule);
 
 #[async_trait]
@@ -31,7 +43,8 @@ impl CommandExecutor for QueryExecutor {
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let key = TextComponent::text(self.0.to_string());
-        let level_info = server.level_info.read().await;
+        let level_info = level_info(sender, server);
+        let level_info = level_info.read().await;
         let value = TextComponent::text(level_info.game_rules.get(&self.0).to_string());
         drop(level_info);
 
@@ -56,7 +69,8 @@ impl CommandExecutor for SetExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let key = TextComponent::text(self.0.to_string());
-        let mut level_info = server.level_info.write().await;
+        let level_info = level_info(sender, server);
+        let mut level_info = level_info.write().await;
         let raw_value = level_info.game_rules.get_mut(&self.0);
 
         let value = TextComponent::text(match raw_value {
@@ -78,7 +78,10 @@ impl CommandExecutor for Executor {
                 let mut stack = ItemStack::new(take as u8, item);
                 target.inventory().insert_stack_anywhere(&mut stack).await;
                 if !stack.is_empty() {
-                    target.drop_item(stack).await;
+                    // What does not fit is dropped for the target alone to pick up right away
+                    let item_entity = target.drop_item(stack, false).await;
+                    item_entity.set_pickup_delay(0).await;
+                    item_entity.set_owner(Some(target.gameprofile.id));
                 }
                 remaining -= take;
             }
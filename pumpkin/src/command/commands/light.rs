@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use crate::command::dispatcher::CommandError::InvalidConsumption;
+use crate::command::{
+    CommandExecutor, CommandSender,
+    args::{Arg, ConsumedArgs, position_block::BlockPosArgumentConsumer},
+    dispatcher::CommandError,
+    tree::{CommandTree, builder::argument},
+};
+use crate::server::Server;
+use crate::world::World;
+use crate::world::natural_spawner::is_spawn_position_ok;
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use pumpkin_world::chunk::LightType;
+
+const NAMES: [&str; 1] = ["light"];
+
+const DESCRIPTION: &str = "Shows the light level at a position and whether mobs can spawn there.";
+
+const ARG_POSITION: &str = "position";
+
+struct HereExecutor;
+
+#[async_trait]
+impl CommandExecutor for HereExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(position) = sender.position() else {
+            return Err(CommandError::CommandFailed(Box::new(TextComponent::text(
+                "A position is required when not run by a player",
+            ))));
+        };
+        report(sender, position.to_block_pos()).await
+    }
+}
+
+struct PositionExecutor;
+
+#[async_trait]
+impl CommandExecutor for PositionExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::BlockPos(position)) = args.get(ARG_POSITION) else {
+            return Err(InvalidConsumption(Some(ARG_POSITION.into())));
+        };
+        report(sender, *position).await
+    }
+}
+
+async fn report(sender: &CommandSender, position: BlockPos) -> Result<(), CommandError> {
+    let Some(world) = sender.world() else {
+        return Err(CommandError::CommandFailed(Box::new(TextComponent::text(
+            "Failed to get world.",
+        ))));
+    };
+
+    let coordinates = format!("{} {} {}", position.0.x, position.0.y, position.0.z);
+    let (Some(sky), Some(block)) = (
+        world.get_light(LightType::Sky, &position).await,
+        world.get_light(LightType::Block, &position).await,
+    ) else {
+        sender
+            .send_message(
+                TextComponent::text(format!(
+                    "The chunk at {coordinates} isn't lit, so light doesn't stop mobs from spawning"
+                ))
+                .color_named(NamedColor::Yellow),
+            )
+            .await;
+        return Ok(());
+    };
+
+    let sky_darken = world.sky_darken().await;
+    let brightness = block.max(sky.saturating_sub(sky_darken));
+    sender
+        .send_message(TextComponent::text(format!(
+            "Light at {coordinates}: block {block}, sky {sky} (darkened by {sky_darken} now), brightness {brightness}"
+        )))
+        .await;
+    sender
+        .send_message(spawn_line(
+            "Monsters",
+            monsters_can_spawn(&world, &position).await,
+        ))
+        .await;
+    sender
+        .send_message(spawn_line(
+            "Animals",
+            world.is_bright_enough_to_spawn(&position).await,
+        ))
+        .await;
+    Ok(())
+}
+
+/// Whether a monster standing on the ground, like a zombie, could spawn at `position` now.
+async fn monsters_can_spawn(world: &Arc<World>, position: &BlockPos) -> bool {
+    world.can_monsters_spawn_in_light(position).await != Some(false)
+        && is_spawn_position_ok(world, position, &EntityType::ZOMBIE).await
+}
+
+fn spawn_line(mobs: &str, can_spawn: bool) -> TextComponent {
+    if can_spawn {
+        TextComponent::text(format!("{mobs} can spawn here"))
+    } else {
+        TextComponent::text(format!("{mobs} can't spawn here")).color_named(NamedColor::Gray)
+    }
+}
+
+#[must_use]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(HereExecutor)
+        .then(argument(ARG_POSITION, BlockPosArgumentConsumer).execute(PositionExecutor))
+}
@@ -12,6 +12,7 @@ mod banip;
 mod banlist;
 mod bossbar;
 mod clear;
+mod clone;
 mod damage;
 mod data;
 pub mod defaultgamemode;
@@ -27,10 +28,12 @@ mod give;
 mod help;
 mod kick;
 mod kill;
+mod light;
 mod list;
 mod me;
 mod msg;
 mod op;
+mod packetreplay;
 mod pardon;
 mod pardonip;
 mod particle;
@@ -39,6 +42,7 @@ mod plugin;
 mod plugins;
 mod pumpkin;
 mod say;
+mod schem;
 mod seed;
 mod setblock;
 mod setworldspawn;
@@ -53,6 +57,7 @@ mod title;
 mod transfer;
 mod weather;
 mod whitelist;
+mod world;
 mod worldborder;
 
 #[cfg(feature = "dhat-heap")]
@@ -86,6 +91,10 @@ pub async fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(setblock::init_command_tree(), "minecraft:command.setblock");
     dispatcher.register(seed::init_command_tree(), "minecraft:command.seed");
     dispatcher.register(fill::init_command_tree(), "minecraft:command.fill");
+    dispatcher.register(clone::init_command_tree(), "minecraft:command.clone");
+    dispatcher.register(schem::init_command_tree(), "pumpkin:command.schem");
+    dispatcher.register(light::init_command_tree(), "pumpkin:command.light");
+    dispatcher.register(world::init_command_tree(), "pumpkin:command.world");
     dispatcher.register(
         playsound::init_command_tree(),
         "minecraft:command.playsound",
@@ -139,6 +148,10 @@ pub async fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(transfer::init_command_tree(), "minecraft:command.transfer");
     // Four
     dispatcher.register(stop::init_command_tree(), "minecraft:command.stop");
+    dispatcher.register(
+        packetreplay::init_command_tree(),
+        "pumpkin:command.packetreplay",
+    );
 
     #[cfg(feature = "dhat-heap")]
     dispatcher.register(profile::init_command_tree(), "pumpkin:command.profile");
@@ -160,6 +173,15 @@ async fn register_permissions() {
 
     // Register level 4 permissions (OP level 4)
     register_level_4_permissions(&mut registry);
+
+    // Permissions that are not tied to a command and only granted explicitly
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:idle_timeout.exempt",
+            "Keeps the player from being kicked for idling",
+            PermissionDefault::Deny,
+        ))
+        .unwrap();
 }
 
 fn register_level_0_permissions(registry: &mut PermissionRegistry) {
@@ -274,6 +296,34 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.clone",
+            "Copies blocks from one region to another",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.schem",
+            "Saves regions to structure files and pastes them",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.light",
+            "Shows light levels and whether mobs can spawn",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.world",
+            "Lists the worlds and moves players between them",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.playsound",
@@ -506,6 +556,13 @@ fn register_level_4_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Four),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.packetreplay",
+            "Replays captured packets as if players sent them",
+            PermissionDefault::Op(PermissionLvl::Four),
+        ))
+        .unwrap();
 
     #[cfg(feature = "dhat-heap")]
     registry
@@ -0,0 +1,81 @@
+use std::fs::File;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::net::ClientPlatform;
+use crate::net::packet_capture::{capture_path, read_capture, replay};
+
+const NAMES: [&str; 1] = ["packetreplay"];
+
+const DESCRIPTION: &str =
+    "Feeds the packets of a capture file back through the packet handlers as if players sent them.";
+
+const ARG_CAPTURE: &str = "capture";
+const ARG_TARGETS: &str = "targets";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_CAPTURE)?;
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(CommandError::CommandFailed(Box::new(TextComponent::text(
+                format!("Invalid capture name {name}"),
+            ))));
+        }
+        let packets = File::open(capture_path(name))
+            .and_then(read_capture)
+            .map_err(|err| {
+                CommandError::CommandFailed(Box::new(TextComponent::text(format!(
+                    "Failed to read capture {name}: {err}"
+                ))))
+            })?;
+
+        for target in targets {
+            let ClientPlatform::Java(client) = &target.client else {
+                continue;
+            };
+            // The packet handlers need to own a reference to the server
+            let Some(target_server) = target.world().server.upgrade() else {
+                continue;
+            };
+            let client = client.clone();
+            let target = target.clone();
+            let packets = packets.clone();
+            server.spawn_task(async move {
+                let replayed = replay(&client, &target, &target_server, &packets).await;
+                log::info!("Replayed {replayed} packets to {}", target.gameprofile.name);
+            });
+        }
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Replaying capture {name} to {} players",
+                targets.len()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_CAPTURE, SimpleArgConsumer)
+            .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(Executor)),
+    )
+}
@@ -0,0 +1,170 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::generation::structure::template::{
+    BlockMirror, BlockRotation, StructureTemplate, TemplatePlacement,
+};
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["schem"];
+
+const DESCRIPTION: &str = "Saves a region to a structure file or pastes one into the world.";
+
+const ARG_NAME: &str = "name";
+const ARG_FROM: &str = "from";
+const ARG_TO: &str = "to";
+const ARG_POS: &str = "pos";
+const ARG_ROTATION: &str = "rotation";
+const ARG_MIRROR: &str = "mirror";
+const ARG_IGNORE_AIR: &str = "ignore_air";
+
+/// Where templates are stored, next to the ones saved by structure blocks.
+fn template_path(name: &str) -> Result<PathBuf, CommandError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.'))
+        || name.starts_with('.')
+    {
+        return Err(CommandError::CommandFailed(Box::new(TextComponent::text(
+            format!("Invalid template name {name}"),
+        ))));
+    }
+    Ok(BASIC_CONFIG
+        .get_world_path()
+        .join("generated/minecraft/structures")
+        .join(format!("{name}.nbt")))
+}
+
+struct SaveExecutor;
+
+#[async_trait]
+impl CommandExecutor for SaveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
+        let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
+        let path = template_path(name)?;
+        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+
+        let max_volume = advanced_config().commands.max_schem_volume;
+        let Some(template) = world.export_region(from, to, max_volume).await else {
+            return Err(CommandError::CommandFailed(Box::new(TextComponent::text(
+                format!("The region is too large, at most {max_volume} blocks can be saved"),
+            ))));
+        };
+        template.save(&path).map_err(|err| {
+            CommandError::CommandFailed(Box::new(TextComponent::text(format!(
+                "Failed to save template {name}: {err}"
+            ))))
+        })?;
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Saved {} blocks to template {name}",
+                template.blocks.len()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct PasteExecutor;
+
+#[async_trait]
+impl CommandExecutor for PasteExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let pos = BlockPosArgumentConsumer::find_arg(args, ARG_POS)?;
+        let invalid = |what: &str, value: &str| {
+            CommandError::CommandFailed(Box::new(TextComponent::text(format!(
+                "Invalid {what} {value}"
+            ))))
+        };
+        let rotation = match SimpleArgConsumer::find_arg(args, ARG_ROTATION) {
+            Ok(rotation) => {
+                BlockRotation::from_name(rotation).ok_or_else(|| invalid("rotation", rotation))?
+            }
+            Err(_) => BlockRotation::None,
+        };
+        let mirror = match SimpleArgConsumer::find_arg(args, ARG_MIRROR) {
+            Ok(mirror) => {
+                BlockMirror::from_name(mirror).ok_or_else(|| invalid("mirror", mirror))?
+            }
+            Err(_) => BlockMirror::None,
+        };
+        let ignore_air = BoolArgConsumer::find_arg(args, ARG_IGNORE_AIR).unwrap_or(false);
+        let path = template_path(name)?;
+        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+
+        let template = StructureTemplate::load(&path).map_err(|err| {
+            CommandError::CommandFailed(Box::new(TextComponent::text(format!(
+                "Failed to load template {name}: {err}"
+            ))))
+        })?;
+        let placement = TemplatePlacement {
+            rotation,
+            mirror,
+            ignore_air,
+        };
+        let placed = world.place_template(&template, pos, &placement).await;
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Pasted {placed} blocks from template {name}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("save").then(
+                argument(ARG_NAME, SimpleArgConsumer).then(
+                    argument(ARG_FROM, BlockPosArgumentConsumer)
+                        .then(argument(ARG_TO, BlockPosArgumentConsumer).execute(SaveExecutor)),
+                ),
+            ),
+        )
+        .then(
+            literal("paste").then(
+                argument(ARG_NAME, SimpleArgConsumer).then(
+                    argument(ARG_POS, BlockPosArgumentConsumer)
+                        .then(
+                            argument(ARG_ROTATION, SimpleArgConsumer)
+                                .then(
+                                    argument(ARG_MIRROR, SimpleArgConsumer)
+                                        .then(
+                                            argument(ARG_IGNORE_AIR, BoolArgConsumer)
+                                                .execute(PasteExecutor),
+                                        )
+                                        .execute(PasteExecutor),
+                                )
+                                .execute(PasteExecutor),
+                        )
+                        .execute(PasteExecutor),
+                ),
+            ),
+        )
+}
@@ -26,6 +26,9 @@ enum Mode {
 
     /// default; without particles
     Replace,
+
+    /// without block callbacks or neighbor updates
+    Strict,
 }
 
 struct Executor(Mode);
@@ -78,6 +81,12 @@ impl CommandExecutor for Executor {
                     .await;
                 true
             }
+            Mode::Strict => {
+                world
+                    .set_block_state(&pos, block_state_id, BlockFlags::CLIENT_ONLY)
+                    .await;
+                true
+            }
             Mode::Keep => {
                 let old_state = world.get_block_state(&pos).await;
                 if old_state.is_air() {
@@ -121,6 +130,7 @@ pub fn init_command_tree() -> CommandTree {
                 .then(literal("replace").execute(Executor(Mode::Replace)))
                 .then(literal("destroy").execute(Executor(Mode::Destroy)))
                 .then(literal("keep").execute(Executor(Mode::Keep)))
+                .then(literal("strict").execute(Executor(Mode::Strict)))
                 .execute(Executor(Mode::Replace)),
         ),
     )
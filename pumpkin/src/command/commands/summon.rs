@@ -59,6 +59,12 @@ impl CommandExecutor for Executor {
             }
         };
         let mob = from_type(entity, pos, &world, Uuid::new_v4()).await;
+        let Some(pos) = world.find_spawn_position(mob.as_ref(), pos).await else {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.summon.invalidPosition", []),
+            )));
+        };
+        mob.get_entity().set_pos(pos);
         world.spawn_entity(mob).await;
 
         sender
@@ -49,6 +49,7 @@ enum SubCommand {
     SprintTimed,
     SprintLiteral(i32),
     SprintStop,
+    Chunks,
 }
 
 struct TickExecutor(SubCommand);
@@ -199,6 +200,53 @@ impl TickExecutor {
     }
 }
 
+impl TickExecutor {
+    /// Reports the chunks which processed the most scheduled ticks during the last game tick.
+    async fn handle_chunks(
+        &self,
+        sender: &CommandSender,
+        server: &crate::server::Server,
+    ) -> Result<(), CommandError> {
+        const SHOWN_CHUNKS: usize = 10;
+
+        let worlds = match sender.world() {
+            Some(world) => vec![world],
+            None => server.worlds.read().await.clone(),
+        };
+
+        for world in worlds {
+            let block_ticks = world.block_tick_budget.lock().await;
+            let fluid_ticks = world.fluid_tick_budget.lock().await;
+            sender
+                .send_message(TextComponent::text(format!(
+                    "{}: {} block ticks and {} fluid ticks deferred",
+                    world.dimension_type.resource_location(),
+                    block_ticks.deferred_count(),
+                    fluid_ticks.deferred_count(),
+                )))
+                .await;
+
+            for (chunk, count) in block_ticks.busiest_chunks(SHOWN_CHUNKS) {
+                let fluid_count = fluid_ticks
+                    .chunk_tick_counts()
+                    .get(&chunk)
+                    .copied()
+                    .unwrap_or(0);
+                sender
+                    .send_message(
+                        TextComponent::text(format!(
+                            "  chunk [{}, {}]: {count} block ticks, {fluid_count} fluid ticks",
+                            chunk.x, chunk.y
+                        ))
+                        .color_named(NamedColor::Gray),
+                    )
+                    .await;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl CommandExecutor for TickExecutor {
     async fn execute<'a>(
@@ -211,6 +259,7 @@ impl CommandExecutor for TickExecutor {
 
         match self.0 {
             SubCommand::Query => self.handle_query(sender, server, manager).await,
+            SubCommand::Chunks => self.handle_chunks(sender, server).await,
             SubCommand::Rate => {
                 let rate = BoundedNumArgumentConsumer::<f32>::find_arg(args, "rate")??;
                 manager.set_tick_rate(server, rate).await;
@@ -233,6 +282,15 @@ impl CommandExecutor for TickExecutor {
                 Ok(())
             }
             SubCommand::Freeze(freeze) => {
+                if freeze {
+                    // Freezing ends whatever the game was running ahead for
+                    if manager.is_sprinting() {
+                        manager.stop_sprinting(server).await;
+                    }
+                    if manager.is_stepping_forward() {
+                        manager.stop_stepping(server).await;
+                    }
+                }
                 manager.set_frozen(server, freeze).await;
                 let message_key = if freeze {
                     "commands.tick.status.frozen"
@@ -246,7 +304,7 @@ impl CommandExecutor for TickExecutor {
             }
             SubCommand::StepDefault => self.handle_step_command(sender, server, manager, 1).await,
             SubCommand::StepTimed => {
-                let ticks = TimeArgumentConsumer::find_arg(args, "time")?;
+                let ticks = TimeArgumentConsumer::find_arg(args, "time")?.max(1);
                 self.handle_step_command(sender, server, manager, ticks)
                     .await
             }
@@ -270,7 +328,8 @@ impl CommandExecutor for TickExecutor {
                 Ok(())
             }
             SubCommand::SprintTimed => {
-                let ticks = TimeArgumentConsumer::find_arg(args, "time")?;
+                // A sprint of no ticks would never end and restore the frozen state
+                let ticks = TimeArgumentConsumer::find_arg(args, "time")?.max(1);
                 self.handle_sprint_command(sender, server, manager, ticks)
                     .await
             }
@@ -303,6 +362,7 @@ impl CommandExecutor for TickExecutor {
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
         .then(literal("query").execute(TickExecutor(SubCommand::Query)))
+        .then(literal("chunks").execute(TickExecutor(SubCommand::Chunks)))
         .then(
             literal("rate")
                 .then(
@@ -1,6 +1,4 @@
 use async_trait::async_trait;
-use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::java::client::play::CTransfer;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::{Color, NamedColor};
 
@@ -60,10 +58,7 @@ impl CommandExecutor for TargetSelfExecutor {
         if let CommandSender::Player(player) = sender {
             let name = &player.gameprofile.name;
             log::info!("[{name}: Transferring {name} to {hostname}:{port}]");
-            player
-                .client
-                .enqueue_packet(&CTransfer::new(hostname, VarInt(port)))
-                .await;
+            player.transfer(hostname, port as u16).await;
             Ok(())
         } else {
             Err(InvalidRequirement)
@@ -104,9 +99,7 @@ impl CommandExecutor for TargetPlayerExecutor {
         };
 
         for p in players {
-            p.client
-                .enqueue_packet(&CTransfer::new(hostname, VarInt(port)))
-                .await;
+            p.transfer(hostname, port as u16).await;
             log::info!(
                 "[{sender}: Transferring {} to {hostname}:{port}]",
                 p.gameprofile.name
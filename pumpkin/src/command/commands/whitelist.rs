@@ -4,11 +4,10 @@ use async_trait::async_trait;
 use pumpkin_config::{BASIC_CONFIG, whitelist::WhitelistEntry};
 use pumpkin_util::text::TextComponent;
 
-use crate::entity::EntityBase;
 use crate::{
     command::{
         CommandExecutor, CommandSender,
-        args::{Arg, ConsumedArgs, players::PlayersArgumentConsumer},
+        args::{ConsumedArgs, FindArg, gameprofiles::GameProfilesArgumentConsumer},
         dispatcher::CommandError,
         tree::{
             CommandTree,
@@ -166,13 +165,10 @@ impl CommandExecutor for AddExecutor {
         _server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
-            return Err(CommandError::InvalidConsumption(Some(ARG_TARGETS.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
 
         let mut whitelist = WHITELIST_CONFIG.write().await;
-        for player in targets {
-            let profile = &player.gameprofile;
+        for profile in targets {
             if whitelist.is_whitelisted(profile) {
                 sender
                     .send_message(TextComponent::translate(
@@ -208,16 +204,14 @@ impl CommandExecutor for RemoveExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
-            return Err(CommandError::InvalidConsumption(Some(ARG_TARGETS.into())));
-        };
+        let targets = GameProfilesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
 
         let mut whitelist = WHITELIST_CONFIG.write().await;
-        for player in targets {
+        for profile in targets {
             let i = whitelist
                 .whitelist
                 .iter()
-                .position(|entry| entry.uuid == player.gameprofile.id);
+                .position(|entry| entry.uuid == profile.id);
 
             match i {
                 Some(i) => {
@@ -225,7 +219,7 @@ impl CommandExecutor for RemoveExecutor {
                     sender
                         .send_message(TextComponent::translate(
                             "commands.whitelist.remove.success",
-                            [player.get_display_name().await],
+                            [TextComponent::text(profile.name.clone())],
                         ))
                         .await;
                 }
@@ -256,10 +250,10 @@ pub fn init_command_tree() -> CommandTree {
         .then(literal("reload").execute(ReloadExecutor))
         .then(
             literal("add")
-                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(AddExecutor)),
+                .then(argument(ARG_TARGETS, GameProfilesArgumentConsumer).execute(AddExecutor)),
         )
         .then(
             literal("remove")
-                .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(RemoveExecutor)),
+                .then(argument(ARG_TARGETS, GameProfilesArgumentConsumer).execute(RemoveExecutor)),
         )
 }
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::CommandSender::Player as PlayerSender;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::world::WorldArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs, FindArg};
+use crate::command::dispatcher::CommandError;
+use crate::command::dispatcher::CommandError::{InvalidConsumption, InvalidRequirement};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal, require};
+use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["world"];
+
+const DESCRIPTION: &str = "Lists the worlds and moves players between them.";
+
+const ARG_WORLD: &str = "world";
+const ARG_TARGETS: &str = "targets";
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let worlds = server.worlds.read().await.clone();
+        sender
+            .send_message(TextComponent::text(format!(
+                "There are {} worlds:",
+                worlds.len()
+            )))
+            .await;
+        for world in worlds {
+            let players = world.players.read().await.len();
+            sender
+                .send_message(
+                    TextComponent::text(format!("- {}", world.name)).add_child(
+                        TextComponent::text(format!(
+                            " ({}, {players} players)",
+                            world.dimension_type.resource_location()
+                        ))
+                        .color_named(NamedColor::Gray),
+                    ),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct TeleportSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for TeleportSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = WorldArgumentConsumer::find_arg(args, ARG_WORLD)?;
+        let PlayerSender(target) = sender else {
+            return Err(InvalidRequirement);
+        };
+
+        teleport_to_world(target, world.clone()).await;
+        sender
+            .send_message(TextComponent::text(format!("Teleported to {}", world.name)))
+            .await;
+        Ok(())
+    }
+}
+
+struct TeleportPlayersExecutor;
+
+#[async_trait]
+impl CommandExecutor for TeleportPlayersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = WorldArgumentConsumer::find_arg(args, ARG_WORLD)?;
+        let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+
+        for target in targets {
+            teleport_to_world(target, world.clone()).await;
+        }
+        let message = if let [target] = targets.as_slice() {
+            TextComponent::text(format!(
+                "Teleported {} to {}",
+                target.gameprofile.name, world.name
+            ))
+        } else {
+            TextComponent::text(format!(
+                "Teleported {} players to {}",
+                targets.len(),
+                world.name
+            ))
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+/// Moves `player` to where they were when they last left `world`, or to its spawn if they have
+/// never been there.
+async fn teleport_to_world(player: &Arc<Player>, world: Arc<World>) {
+    if Arc::ptr_eq(player.world(), &world) {
+        return;
+    }
+
+    let saved = player
+        .world_positions
+        .lock()
+        .await
+        .get(&world.name)
+        .copied();
+    if let Some(saved) = saved {
+        player
+            .teleport_world(world, saved.position, Some(saved.yaw), Some(saved.pitch))
+            .await;
+    } else {
+        let (position, yaw) = world.get_spawn_position().await;
+        player
+            .teleport_world(world, position, Some(yaw), Some(0.0))
+            .await;
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("list").execute(ListExecutor))
+        .then(
+            literal("tp").then(
+                argument(ARG_WORLD, WorldArgumentConsumer)
+                    .then(require(|sender| sender.is_player()).execute(TeleportSelfExecutor))
+                    .then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer)
+                            .execute(TeleportPlayersExecutor),
+                    ),
+            ),
+        )
+}
@@ -1,5 +1,6 @@
 use pumpkin_protocol::java::client::play::CommandSuggestion;
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::translation::Locale;
 
 use super::args::ConsumedArgs;
 
@@ -35,18 +36,21 @@ impl CommandError {
                 log::error!(
                     "Error while parsing command \"{cmd}\": {s:?} was consumed, but couldn't be parsed"
                 );
-                TextComponent::text("Internal error (See logs for details)")
+                TextComponent::custom("pumpkin", "commands.error.internal", Locale::EnUs, [])
             }
             InvalidRequirement => {
                 log::error!(
                     "Error while parsing command \"{cmd}\": a requirement that was expected was not met."
                 );
-                TextComponent::text("Internal error (See logs for details)")
+                TextComponent::custom("pumpkin", "commands.error.internal", Locale::EnUs, [])
             }
             PermissionDenied => {
                 log::warn!("Permission denied for command \"{cmd}\"");
-                TextComponent::text(
-                    "I'm sorry, but you do not have permission to perform this command. Please contact the server administrator if you believe this is an error.",
+                TextComponent::custom(
+                    "pumpkin",
+                    "commands.error.permission_denied",
+                    Locale::EnUs,
+                    [],
                 )
             }
             CommandFailed(s) => *s,
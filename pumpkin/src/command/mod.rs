@@ -1,5 +1,4 @@
 use std::fmt;
-use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::entity::player::Player;
@@ -111,9 +110,7 @@ impl CommandSender {
     pub async fn get_locale(&self) -> Locale {
         match self {
             Self::Console | Self::Rcon(..) => Locale::EnUs, // Default locale for console and RCON
-            Self::Player(player) => {
-                Locale::from_str(&player.config.read().await.locale).unwrap_or(Locale::EnUs)
-            }
+            Self::Player(player) => player.locale().await,
         }
     }
 }
@@ -10,6 +10,7 @@ pub mod banlist_serializer;
 pub mod banned_ip_data;
 pub mod banned_player_data;
 pub mod player_server_data;
+pub mod profile_cache;
 pub mod whitelist_data;
 
 pub trait LoadJSONConfiguration {
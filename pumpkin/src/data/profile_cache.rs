@@ -0,0 +1,224 @@
+use std::{
+    path::Path,
+    sync::LazyLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_protocol::Property;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::{GameProfile, authentication, offline_uuid};
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static PROFILE_CACHE: LazyLock<tokio::sync::RwLock<ProfileCache>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(ProfileCache::load()));
+
+/// Held while [`PROFILE_CACHE`] is saved, so an older copy can't overwrite a newer one.
+static SAVE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// The most profiles kept, the ones that expire first are dropped beyond this.
+const MAX_ENTRIES: usize = 1000;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CachedProfile {
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Vec<Property>,
+    /// Unix timestamp in seconds after which the profile is looked up again
+    pub expires_at: u64,
+}
+
+impl CachedProfile {
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        now() >= self.expires_at
+    }
+
+    #[must_use]
+    pub fn to_profile(&self) -> GameProfile {
+        GameProfile {
+            id: self.uuid,
+            name: self.name.clone(),
+            properties: self.properties.clone(),
+            profile_actions: None,
+        }
+    }
+}
+
+/// Remembers the UUID and skin of player names, so they do not have to be looked up every time.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct ProfileCache {
+    pub profiles: Vec<CachedProfile>,
+}
+
+impl ProfileCache {
+    /// The cached profile of `name` (ignoring case), even if it expired.
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&CachedProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The cached profile named exactly `name`, even if it expired.
+    #[must_use]
+    pub fn get_by_exact_name(&self, name: &str) -> Option<&CachedProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// The cached profile with `uuid`, even if it expired.
+    #[must_use]
+    pub fn get_by_uuid(&self, uuid: &Uuid) -> Option<&CachedProfile> {
+        self.profiles.iter().find(|profile| profile.uuid == *uuid)
+    }
+
+    /// Adds or refreshes `profile`, replacing any profile with the same UUID or name.
+    pub fn insert(&mut self, profile: &GameProfile) {
+        self.profiles.retain(|cached| {
+            cached.uuid != profile.id && !cached.name.eq_ignore_ascii_case(&profile.name)
+        });
+        let ttl = u64::from(
+            advanced_config()
+                .networking
+                .authentication
+                .profile_cache_days,
+        );
+        self.profiles.push(CachedProfile {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            properties: profile.properties.clone(),
+            expires_at: now() + ttl * 24 * 60 * 60,
+        });
+
+        if self.profiles.len() > MAX_ENTRIES {
+            self.profiles.sort_by_key(|cached| cached.expires_at);
+            let excess = self.profiles.len() - MAX_ENTRIES;
+            self.profiles.drain(..excess);
+        }
+    }
+}
+
+impl LoadJSONConfiguration for ProfileCache {
+    fn get_path() -> &'static Path {
+        Path::new("profile_cache.json")
+    }
+    fn validate(&self) {}
+}
+
+impl SaveJSONConfiguration for ProfileCache {}
+
+/// Remembers the profile a player logged in with.
+pub async fn cache_profile(profile: &GameProfile) {
+    PROFILE_CACHE.write().await.insert(profile);
+
+    // Writing the file blocks, so it happens on a blocking thread without holding the cache
+    let _save = SAVE_LOCK.lock().await;
+    let cache = ProfileCache {
+        profiles: PROFILE_CACHE.read().await.profiles.clone(),
+    };
+    if let Err(err) = tokio::task::spawn_blocking(move || cache.save()).await {
+        log::warn!("Failed to save the profile cache: {err}");
+    }
+}
+
+/// The UUID of `name` in offline mode.
+///
+/// Names keep the UUID they were cached with, so players keep their data when a server switches
+/// from online to offline mode. Offline UUIDs are derived from the exact name, so only a profile
+/// with exactly the same name counts; `steve` doesn't get the UUID of `Steve`.
+pub async fn offline_profile_uuid(name: &str) -> Result<Uuid, uuid::Error> {
+    match PROFILE_CACHE.read().await.get_by_exact_name(name) {
+        Some(cached) => Ok(cached.uuid),
+        None => offline_uuid(name),
+    }
+}
+
+/// Resolves the profile of the player called `name`, or `None` if there is no such player.
+///
+/// Cached profiles are used until they expire. After that, and for unknown names, Mojang is asked
+/// in online mode, while in offline mode the name gets an offline UUID.
+pub async fn resolve_profile(name: &str) -> Option<GameProfile> {
+    let cached = PROFILE_CACHE.read().await.get_by_name(name).cloned();
+    if let Some(cached) = &cached
+        && (!cached.is_expired() || !BASIC_CONFIG.online_mode)
+    {
+        return Some(cached.to_profile());
+    }
+
+    let profile = if BASIC_CONFIG.online_mode {
+        let username = name.to_string();
+        let lookup = tokio::task::spawn_blocking(move || authentication::lookup_profile(&username))
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|result| result.map_err(|err| err.to_string()));
+        match lookup {
+            Ok(profile) => profile?,
+            Err(err) => {
+                // Better an outdated profile than none at all
+                log::warn!("Failed to look up the profile of {name}: {err}");
+                return cached.map(|cached| cached.to_profile());
+            }
+        }
+    } else {
+        GameProfile {
+            id: offline_uuid(name).ok()?,
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    };
+    cache_profile(&profile).await;
+    Some(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::ProfileCache;
+    use crate::net::GameProfile;
+
+    fn profile(id: u128, name: &str) -> GameProfile {
+        GameProfile {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }
+    }
+
+    #[test]
+    fn insert_replaces_same_uuid_or_name() {
+        let mut cache = ProfileCache::default();
+        cache.insert(&profile(1, "Alex"));
+        cache.insert(&profile(2, "Steve"));
+        // Alex changed their name
+        cache.insert(&profile(1, "Alexa"));
+        // Someone else took the name Steve
+        cache.insert(&profile(3, "steve"));
+
+        assert_eq!(cache.profiles.len(), 2);
+        assert!(cache.get_by_name("alex").is_none());
+        assert_eq!(cache.get_by_name("ALEXA").unwrap().uuid, Uuid::from_u128(1));
+        assert_eq!(cache.get_by_name("Steve").unwrap().uuid, Uuid::from_u128(3));
+        assert!(cache.get_by_exact_name("Steve").is_none());
+        assert_eq!(
+            cache.get_by_exact_name("steve").unwrap().uuid,
+            Uuid::from_u128(3)
+        );
+        assert!(cache.get_by_uuid(&Uuid::from_u128(2)).is_none());
+        assert!(!cache.get_by_uuid(&Uuid::from_u128(3)).unwrap().is_expired());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::entity::mob::Mob;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_util::math::clamp_angle;
+use pumpkin_util::math::vector3::Vector3;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering::Relaxed;
+
+/// How far the head may turn away from where the body last settled before the body follows.
+const HEAD_TURN_THRESHOLD: f32 = 15.0;
+/// Ticks a standing mob waits before its body starts turning towards the head.
+const BODY_ADJUST_DELAY: i32 = 10;
+/// Ticks the body takes to fully catch up with the head once it started turning.
+const BODY_ADJUST_DURATION: f32 = 10.0;
+
+/// Keeps the body yaw of a mob lagging behind its head.
+///
+/// While moving, the body faces the walking direction and the head may only turn so far from it.
+/// Standing still, the body stays put until the head looks away far enough or long enough.
+#[derive(Default)]
+pub struct BodyControl {
+    last_head_yaw: AtomicCell<f32>,
+    body_adjust_ticks: AtomicI32,
+    last_pos: AtomicCell<Option<Vector3<f64>>>,
+}
+
+impl BodyControl {
+    pub fn tick(&self, mob: &dyn Mob) {
+        let entity = mob.get_entity();
+        let max_head_rotation = mob.get_max_head_rotation() as f32;
+        let head_yaw = entity.head_yaw.load();
+
+        if self.is_moving(entity.pos.load()) {
+            entity.body_yaw.store(entity.yaw.load());
+            Self::keep_head_in_range(mob, max_head_rotation);
+            self.last_head_yaw.store(entity.head_yaw.load());
+            self.body_adjust_ticks.store(0, Relaxed);
+            return;
+        }
+
+        // TODO: Mobs with passengers should turn their body with the controlling passenger
+        if (head_yaw - self.last_head_yaw.load()).abs() > HEAD_TURN_THRESHOLD {
+            self.body_adjust_ticks.store(0, Relaxed);
+            self.last_head_yaw.store(head_yaw);
+            Self::keep_body_in_range(mob, max_head_rotation);
+        } else {
+            let ticks = self.body_adjust_ticks.fetch_add(1, Relaxed) + 1;
+            if ticks > BODY_ADJUST_DELAY {
+                let progress =
+                    ((ticks - BODY_ADJUST_DELAY) as f32 / BODY_ADJUST_DURATION).clamp(0.0, 1.0);
+                Self::keep_body_in_range(mob, max_head_rotation * (1.0 - progress));
+            }
+        }
+    }
+
+    fn is_moving(&self, pos: Vector3<f64>) -> bool {
+        let Some(last_pos) = self.last_pos.swap(Some(pos)) else {
+            return false;
+        };
+        let dx = pos.x - last_pos.x;
+        let dz = pos.z - last_pos.z;
+        dx * dx + dz * dz > 2.5E-7
+    }
+
+    fn keep_head_in_range(mob: &dyn Mob, max_head_rotation: f32) {
+        let entity = mob.get_entity();
+        entity.head_yaw.store(clamp_angle(
+            entity.head_yaw.load(),
+            entity.body_yaw.load(),
+            max_head_rotation,
+        ));
+    }
+
+    fn keep_body_in_range(mob: &dyn Mob, max_rotation: f32) {
+        let entity = mob.get_entity();
+        entity.body_yaw.store(clamp_angle(
+            entity.body_yaw.load(),
+            entity.head_yaw.load(),
+            max_rotation,
+        ));
+    }
+}
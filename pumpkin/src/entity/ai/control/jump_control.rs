@@ -0,0 +1,22 @@
+use crate::entity::mob::Mob;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::Relaxed;
+
+/// Lets goals ask the mob to jump during the next tick.
+#[derive(Default)]
+pub struct JumpControl {
+    active: AtomicBool,
+}
+
+impl JumpControl {
+    pub fn set_active(&self) {
+        self.active.store(true, Relaxed);
+    }
+
+    pub fn tick(&self, mob: &dyn Mob) {
+        mob.get_mob_entity()
+            .living_entity
+            .jumping
+            .store(self.active.swap(false, Relaxed), Relaxed);
+    }
+}
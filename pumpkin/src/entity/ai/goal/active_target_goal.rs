@@ -25,7 +25,7 @@ pub struct ActiveTargetGoal {
 
 impl ActiveTargetGoal {
     pub async fn new<F, Fut>(
-        mob: &MobEntity,
+        mob: &dyn Mob,
         target_type: &'static EntityType,
         reciprocal_chance: i32,
         check_visibility: bool,
@@ -38,8 +38,7 @@ impl ActiveTargetGoal {
     {
         let track_target_goal = TrackTargetGoal::new(check_visibility, check_can_navigate);
         track_target_goal.set_controls(&[Control::Target]).await;
-        let mut target_predicate = TargetPredicate::attackable();
-        target_predicate.base_max_distance = TrackTargetGoal::get_follow_range(mob);
+        let mut target_predicate = Self::create_target_predicate(mob, check_visibility);
         if let Some(predicate) = predicate {
             target_predicate.set_predicate(predicate);
         }
@@ -54,14 +53,13 @@ impl ActiveTargetGoal {
 
     #[must_use]
     pub async fn with_default(
-        mob: &MobEntity,
+        mob: &dyn Mob,
         target_type: &'static EntityType,
         check_visibility: bool,
     ) -> Self {
         let track_target_goal = TrackTargetGoal::with_default(check_visibility);
         track_target_goal.set_controls(&[Control::Target]).await;
-        let mut target_predicate = TargetPredicate::attackable();
-        target_predicate.base_max_distance = TrackTargetGoal::get_follow_range(mob);
+        let target_predicate = Self::create_target_predicate(mob, check_visibility);
         Self {
             track_target_goal,
             target: Mutex::new(None),
@@ -71,26 +69,45 @@ impl ActiveTargetGoal {
         }
     }
 
+    fn create_target_predicate(mob: &dyn Mob, check_visibility: bool) -> TargetPredicate {
+        let mut target_predicate = TargetPredicate::attackable();
+        target_predicate.base_max_distance = mob.get_follow_range();
+        if !check_visibility {
+            target_predicate.ignore_visibility();
+        }
+        target_predicate
+    }
+
+    /// Picks the closest entity of the target type the mob has noticed.
     async fn find_closest_target(&self, mob: &MobEntity) {
-        let mut target = self.target.lock().await;
-        let world = &mob.living_entity.entity.world;
-        if self.target_type == &EntityType::PLAYER {
-            *target = world
-                .get_closest_player(
-                    mob.living_entity.entity.pos.load(),
-                    TrackTargetGoal::get_follow_range(mob),
-                )
+        let candidates: Vec<Arc<dyn EntityBase>> = if self.target_type == &EntityType::PLAYER {
+            mob.sensing
+                .nearest_visible_players()
                 .await
-                .map(|p: Arc<Player>| p as Arc<dyn EntityBase>);
+                .into_iter()
+                .map(|player: Arc<Player>| player as Arc<dyn EntityBase>)
+                .collect()
         } else {
-            *target = world
-                .get_closest_entity(
-                    mob.living_entity.entity.pos.load(),
-                    TrackTargetGoal::get_follow_range(mob),
-                    Some(&[self.target_type]),
-                )
-                .await;
+            mob.sensing
+                .nearest_visible_entities()
+                .await
+                .into_iter()
+                .filter(|entity| entity.get_entity().entity_type == self.target_type)
+                .collect()
+        };
+
+        let mut closest = None;
+        for candidate in candidates {
+            if self
+                .target_predicate
+                .test(Some(mob), candidate.as_ref())
+                .await
+            {
+                closest = Some(candidate);
+                break;
+            }
         }
+        *self.target.lock().await = closest;
     }
 }
 
@@ -110,10 +127,8 @@ impl Goal for ActiveTargetGoal {
     }
 
     async fn start(&self, mob: &dyn Mob) {
-        let mob_entity = mob.get_mob_entity();
-        let mut mob_target = mob_entity.target.lock().await;
         let target = self.target.lock().await.clone();
-        (*mob_target).clone_from(&target);
+        (*mob.get_mob_entity().target.lock().await).clone_from(&target);
 
         self.track_target_goal.start(mob).await;
     }
@@ -0,0 +1,123 @@
+use super::{Control, Goal, GoalControl};
+use crate::entity::mob::Mob;
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::entity::EntityStatus;
+use pumpkin_data::world::WorldEvent;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::world::BlockFlags;
+use rand::Rng;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering::Relaxed;
+
+use crate::world::World;
+
+/// How long eating takes, in ticks.
+const EAT_TICKS: i32 = 40;
+/// How many ticks before the end the grass is actually eaten.
+const EATEN_AT_TICKS: i32 = 4;
+
+/// Lets the mob now and then eat the grass it stands in or on, calling [`Mob::ate`] once it did.
+pub struct EatGrassGoal {
+    goal_control: GoalControl,
+    timer: AtomicI32,
+}
+
+impl Default for EatGrassGoal {
+    fn default() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move, Control::Look, Control::Jump]),
+            timer: AtomicI32::new(0),
+        }
+    }
+}
+
+impl EatGrassGoal {
+    async fn can_eat_at(world: &World, position: &BlockPos) -> bool {
+        world.get_block(position).await == &Block::SHORT_GRASS
+            || world.get_block(&position.down()).await == &Block::GRASS_BLOCK
+    }
+}
+
+#[async_trait]
+impl Goal for EatGrassGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        if mob.get_random().random_range(0..1000) != 0 {
+            return false;
+        }
+        let entity = &mob.get_mob_entity().living_entity.entity;
+        Self::can_eat_at(&entity.world, &entity.block_pos.load()).await
+    }
+
+    async fn should_continue(&self, _mob: &dyn Mob) -> bool {
+        self.timer.load(Relaxed) > 0
+    }
+
+    async fn start(&self, mob: &dyn Mob) {
+        self.timer.store(self.get_tick_count(EAT_TICKS), Relaxed);
+        let mob_entity = mob.get_mob_entity();
+        mob_entity.navigator.lock().await.cancel();
+        // Lets the client play the eating animation
+        let entity = &mob_entity.living_entity.entity;
+        entity
+            .world
+            .send_entity_status(
+                entity,
+                EntityStatus::SetSheepEatGrassTimerOrPrimeTntMinecart,
+            )
+            .await;
+    }
+
+    async fn stop(&self, _mob: &dyn Mob) {
+        self.timer.store(0, Relaxed);
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let timer = (self.timer.load(Relaxed) - 1).max(0);
+        self.timer.store(timer, Relaxed);
+        if timer != self.get_tick_count(EATEN_AT_TICKS) {
+            return;
+        }
+
+        let entity = &mob.get_mob_entity().living_entity.entity;
+        let world = &entity.world;
+        let position = entity.block_pos.load();
+        let mob_griefing = world.level_info.read().await.game_rules.mob_griefing;
+        if world.get_block(&position).await == &Block::SHORT_GRASS {
+            if mob_griefing {
+                world
+                    .break_block(
+                        &position,
+                        None,
+                        BlockFlags::NOTIFY_ALL | BlockFlags::SKIP_DROPS,
+                    )
+                    .await;
+            }
+        } else if world.get_block(&position.down()).await == &Block::GRASS_BLOCK {
+            if mob_griefing {
+                let below = position.down();
+                world
+                    .sync_world_event(
+                        WorldEvent::BlockBroken,
+                        below,
+                        i32::from(Block::GRASS_BLOCK.default_state.id),
+                    )
+                    .await;
+                world
+                    .set_block_state(
+                        &below,
+                        Block::DIRT.default_state.id,
+                        BlockFlags::NOTIFY_LISTENERS,
+                    )
+                    .await;
+            }
+        } else {
+            return;
+        }
+        mob.ate().await;
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
@@ -8,11 +8,13 @@ use tokio::sync::RwLock;
 
 pub mod active_target_goal;
 pub mod ambient_stand_goal;
+pub mod eat_grass_goal;
 pub mod goal_selector;
 pub mod look_around_goal;
 pub mod look_at_entity;
-mod melee_attack_goal;
+pub mod melee_attack_goal;
 pub mod move_to_target_pos_goal;
+pub mod revenge_goal;
 pub mod step_and_destroy_block_goal;
 mod track_target_goal;
 pub mod zombie_attack_goal;
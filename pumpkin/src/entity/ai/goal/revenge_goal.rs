@@ -0,0 +1,142 @@
+use super::{Control, Goal, GoalControl};
+use crate::entity::EntityBase;
+use crate::entity::ai::goal::track_target_goal::TrackTargetGoal;
+use crate::entity::ai::target_predicate::TargetPredicate;
+use crate::entity::mob::Mob;
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::boundingbox::BoundingBox;
+use pumpkin_util::math::vector3::Vector3;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering::Relaxed;
+use tokio::sync::Mutex;
+
+/// How long the mob keeps chasing its attacker without seeing it.
+const MAX_TIME_WITHOUT_VISIBILITY: i32 = 300;
+
+/// Targets whoever hurt the mob last. With group revenge, nearby mobs of the same type join in,
+/// like zombified piglins do.
+pub struct RevengeGoal {
+    track_target_goal: TrackTargetGoal,
+    target_predicate: TargetPredicate,
+    /// Attackers of these types are never targeted.
+    no_revenge_types: Vec<&'static EntityType>,
+    group_revenge: bool,
+    /// The age of the mob when it was last hurt by the attacker it took revenge on.
+    last_attacked_time: AtomicI32,
+    attacker: Mutex<Option<Arc<dyn EntityBase>>>,
+}
+
+impl RevengeGoal {
+    pub async fn new(no_revenge_types: &[&'static EntityType]) -> Self {
+        let track_target_goal = TrackTargetGoal::with_default(true);
+        track_target_goal.set_controls(&[Control::Target]).await;
+        let mut target_predicate = TargetPredicate::attackable();
+        target_predicate.ignore_visibility();
+        target_predicate.ignore_distance_scaling();
+        Self {
+            track_target_goal,
+            target_predicate,
+            no_revenge_types: no_revenge_types.to_vec(),
+            group_revenge: false,
+            last_attacked_time: AtomicI32::new(-1),
+            attacker: Mutex::new(None),
+        }
+    }
+
+    /// Makes other mobs of the same type without a target go after the attacker as well.
+    #[must_use]
+    pub fn with_group_revenge(mut self) -> Self {
+        self.group_revenge = true;
+        self
+    }
+
+    async fn call_same_type_for_revenge(&self, mob: &dyn Mob, attacker: &Arc<dyn EntityBase>) {
+        let entity = mob.get_entity();
+        let range = mob.get_follow_range();
+        let pos = entity.pos.load();
+        let area = BoundingBox::new(
+            Vector3::new(pos.x - range, pos.y - 10.0, pos.z - range),
+            Vector3::new(pos.x + range, pos.y + 10.0, pos.z + range),
+        );
+        for other in entity.world.get_entities_at_box(&area).await {
+            let other_entity = other.get_entity();
+            if other_entity.entity_id == entity.entity_id
+                || other_entity.entity_type != entity.entity_type
+            {
+                continue;
+            }
+            let Some(other_mob) = other.as_mob() else {
+                continue;
+            };
+            // TODO: Skip teammates of the attacker
+            let mut target = other_mob.get_mob_entity().target.lock().await;
+            if target.is_none() {
+                *target = Some(attacker.clone());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for RevengeGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let living = &mob.get_mob_entity().living_entity;
+        let Some((attacker_id, attacked_time)) = living.last_attacker.load() else {
+            return false;
+        };
+        if attacked_time == self.last_attacked_time.load(Relaxed) {
+            return false;
+        }
+        let Some(attacker) = living.entity.world.get_entity_by_id(attacker_id).await else {
+            return false;
+        };
+        if self
+            .no_revenge_types
+            .contains(&attacker.get_entity().entity_type)
+            || !self
+                .track_target_goal
+                .can_track(mob, Some(attacker.as_ref()), &self.target_predicate)
+                .await
+        {
+            return false;
+        }
+        *self.attacker.lock().await = Some(attacker);
+        true
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        self.track_target_goal.should_continue(mob).await
+    }
+
+    async fn start(&self, mob: &dyn Mob) {
+        let mob_entity = mob.get_mob_entity();
+        let attacker = self.attacker.lock().await.take();
+        (*mob_entity.target.lock().await).clone_from(&attacker);
+        self.track_target_goal.set_target(attacker.clone()).await;
+        if let Some((_, attacked_time)) = mob_entity.living_entity.last_attacker.load() {
+            self.last_attacked_time.store(attacked_time, Relaxed);
+        }
+        self.track_target_goal
+            .set_max_time_without_visibility(MAX_TIME_WITHOUT_VISIBILITY);
+
+        if self.group_revenge
+            && let Some(attacker) = &attacker
+        {
+            self.call_same_type_for_revenge(mob, attacker).await;
+        }
+
+        self.track_target_goal.start(mob).await;
+    }
+
+    async fn stop(&self, mob: &dyn Mob) {
+        self.track_target_goal.stop(mob).await;
+    }
+
+    async fn tick(&self, _mob: &dyn Mob) {}
+
+    fn get_goal_control(&self) -> &GoalControl {
+        self.track_target_goal.get_goal_control()
+    }
+}
@@ -1,8 +1,8 @@
 use super::{Goal, GoalControl, to_goal_ticks};
+use crate::entity::EntityBase;
 use crate::entity::ai::target_predicate::TargetPredicate;
 use crate::entity::living::LivingEntity;
 use crate::entity::mob::Mob;
-use crate::entity::{EntityBase, mob::MobEntity};
 use async_trait::async_trait;
 use rand::Rng;
 use std::sync::Arc;
@@ -48,11 +48,6 @@ impl TrackTargetGoal {
         Self::new(check_visibility, false)
     }
 
-    // TODO: get from entity attribute
-    pub fn get_follow_range(_mob: &MobEntity) -> f64 {
-        1.0
-    }
-
     pub fn set_max_time_without_visibility(&self, time: i32) {
         self.max_time_without_visibility.store(time, Relaxed);
     }
@@ -66,24 +61,24 @@ impl TrackTargetGoal {
         false
     }
 
-    pub fn can_track(
+    pub async fn can_track(
         &self,
         mob: &dyn Mob,
-        target: Option<&LivingEntity>,
+        target: Option<&dyn EntityBase>,
         target_predicate: &TargetPredicate,
     ) -> bool {
-        if target.is_none() {
+        let Some(target) = target else {
             return false;
-        }
+        };
         let mob_entity = mob.get_mob_entity();
-        let target = target.unwrap();
-        let world = &mob_entity.living_entity.entity.world;
-        if !target_predicate.test(world.clone(), Some(&mob_entity.living_entity), target) {
+        if !target_predicate.test(Some(mob_entity), target).await
+            || !mob_entity.is_in_position_target_range_pos(target.get_entity().block_pos.load())
+        {
+            return false;
+        }
+        let Some(target) = target.get_living_entity() else {
             return false;
-        } /*else if (!this.mob.isInPositionTargetRange(target.getBlockPos())) {
-        return false;
-        }*/
-        // TODO: implement this
+        };
 
         if self.check_can_navigate {
             if self.check_can_navigate_cooldown.fetch_sub(1, Relaxed) - 1 <= 0 {
@@ -106,6 +101,11 @@ impl TrackTargetGoal {
 
         true
     }
+
+    /// Remembers `target` so the goal keeps following it even if the mob's target got cleared.
+    pub async fn set_target(&self, target: Option<Arc<dyn EntityBase>>) {
+        *self.target.lock().await = target;
+    }
 }
 
 #[async_trait]
@@ -115,19 +115,49 @@ impl Goal for TrackTargetGoal {
     }
 
     async fn should_continue(&self, mob: &dyn Mob) -> bool {
-        let mob = mob.get_mob_entity();
-        let mob_target = mob.target.lock().await;
-        let target = if mob_target.is_some() {
-            mob_target.clone()
-        } else {
-            let lock = self.target.lock().await;
-            lock.clone()
+        let mob_entity = mob.get_mob_entity();
+        let mob_target = mob_entity.target.lock().await.clone();
+        let target = match mob_target {
+            Some(target) => target,
+            None => match self.target.lock().await.clone() {
+                Some(target) => target,
+                None => return false,
+            },
         };
-        drop(mob_target);
 
-        if target.is_none() {
+        // TODO: continue when scoreboard team are implemented
+        if !TargetPredicate::attackable()
+            .test(None, target.as_ref())
+            .await
+        {
+            return false;
+        }
+        let follow_range = mob.get_follow_range();
+        let distance = mob_entity
+            .living_entity
+            .entity
+            .pos
+            .load()
+            .squared_distance_to_vec(target.get_entity().pos.load());
+        if distance > follow_range * follow_range {
             return false;
-        } // TODO: continue when scoreboard team are implemented
+        }
+
+        if self.check_visibility {
+            if mob_entity
+                .sensing
+                .can_see(&mob_entity.living_entity.entity, target.get_entity())
+                .await
+            {
+                self.time_without_visibility.store(0, Relaxed);
+            } else if self.time_without_visibility.fetch_add(1, Relaxed) + 1
+                > to_goal_ticks(self.max_time_without_visibility.load(Relaxed))
+            {
+                return false;
+            }
+        }
+
+        *mob_entity.target.lock().await = Some(target);
         true
     }
 
@@ -1,7 +1,10 @@
-use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_util::math::{position::BlockPos, subtract_angles, vector3::Vector3, wrap_degrees};
 
 use crate::entity::living::LivingEntity;
 
+/// How many degrees a walking mob turns towards its next step per tick.
+const MAX_TURN_PER_TICK: f32 = 90.0;
+
 #[derive(Default)]
 pub struct Navigator {
     current_goal: Option<NavigatorGoal>,
@@ -71,11 +74,23 @@ impl Navigator {
             }
 
             // Update current progress based on the best move
-            goal.current_progress += best_move.normalize() * goal.speed;
+            let direction = best_move.normalize();
+            goal.current_progress += direction * goal.speed;
+
+            // Face the way we are walking, the body and head follow through their controls
+            let target_yaw =
+                direction.z.atan2(direction.x) as f32 * 180.0 / std::f32::consts::PI - 90.0;
+            let yaw = entity.entity.yaw.load();
+            entity.entity.yaw.store(wrap_degrees(
+                yaw + subtract_angles(yaw, target_yaw).clamp(-MAX_TURN_PER_TICK, MAX_TURN_PER_TICK),
+            ));
 
             // Now let's move
             entity.entity.set_pos(goal.current_progress);
             entity.entity.send_pos().await;
+            world
+                .send_debug_path_step(BlockPos::floored_v(goal.current_progress))
+                .await;
         }
     }
 
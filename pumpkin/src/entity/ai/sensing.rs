@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering::Relaxed;
+
+use tokio::sync::Mutex;
+
+use crate::entity::{Entity, EntityBase, player::Player};
+
+/// Ticks between two scans for nearby entities.
+const SCAN_RATE: i32 = 20;
+
+/// What a mob currently perceives of its surroundings.
+///
+/// Line of sight checks are cached until the next tick, so several goals asking whether the same
+/// target is visible only cast one ray. The nearest visible players and living entities are
+/// refreshed every `SCAN_RATE` ticks, sorted by distance.
+#[derive(Default)]
+pub struct Sensing {
+    seen: Mutex<HashSet<i32>>,
+    unseen: Mutex<HashSet<i32>>,
+    nearest_visible_players: Mutex<Vec<Arc<Player>>>,
+    nearest_visible_entities: Mutex<Vec<Arc<dyn EntityBase>>>,
+    scan_cooldown: AtomicI32,
+}
+
+impl Sensing {
+    /// Forgets last tick's line of sight results and rescans the surroundings within `range` when
+    /// it is time to.
+    pub async fn tick(&self, entity: &Entity, range: f64) {
+        self.seen.lock().await.clear();
+        self.unseen.lock().await.clear();
+
+        if self.scan_cooldown.fetch_sub(1, Relaxed) > 0 {
+            return;
+        }
+        self.scan_cooldown.store(SCAN_RATE - 1, Relaxed);
+        self.scan(entity, range).await;
+    }
+
+    async fn scan(&self, entity: &Entity, range: f64) {
+        let world = &entity.world;
+        let pos = entity.pos.load();
+        let distance = |other: &Entity| other.pos.load().squared_distance_to_vec(pos);
+
+        let mut players = Vec::new();
+        for player in world.get_nearby_players(pos, range).await.into_values() {
+            if !player.is_spectator()
+                && player.living_entity.entity.is_alive()
+                && self.can_see(entity, &player.living_entity.entity).await
+            {
+                players.push(player);
+            }
+        }
+        players.sort_by(|a, b| {
+            distance(&a.living_entity.entity).total_cmp(&distance(&b.living_entity.entity))
+        });
+
+        let mut entities = Vec::new();
+        for other in world.get_nearby_entities(pos, range).await.into_values() {
+            let other_entity = other.get_entity();
+            if other_entity.entity_id != entity.entity_id
+                && other.get_living_entity().is_some()
+                && other_entity.is_alive()
+                && self.can_see(entity, other_entity).await
+            {
+                entities.push(other);
+            }
+        }
+        entities.sort_by(|a, b| distance(a.get_entity()).total_cmp(&distance(b.get_entity())));
+
+        *self.nearest_visible_players.lock().await = players;
+        *self.nearest_visible_entities.lock().await = entities;
+    }
+
+    /// Whether `entity` can see `target`, cached until the next tick.
+    pub async fn can_see(&self, entity: &Entity, target: &Entity) -> bool {
+        let id = target.entity_id;
+        if self.seen.lock().await.contains(&id) {
+            return true;
+        }
+        if self.unseen.lock().await.contains(&id) {
+            return false;
+        }
+        let visible = entity.can_see(target).await;
+        if visible {
+            self.seen.lock().await.insert(id);
+        } else {
+            self.unseen.lock().await.insert(id);
+        }
+        visible
+    }
+
+    /// The players seen during the last scan, closest first.
+    pub async fn nearest_visible_players(&self) -> Vec<Arc<Player>> {
+        self.nearest_visible_players.lock().await.clone()
+    }
+
+    /// The living entities other than players seen during the last scan, closest first.
+    pub async fn nearest_visible_entities(&self) -> Vec<Arc<dyn EntityBase>> {
+        self.nearest_visible_entities.lock().await.clone()
+    }
+}
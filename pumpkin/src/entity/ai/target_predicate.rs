@@ -1,9 +1,11 @@
 use crate::entity::living::LivingEntity;
+use crate::entity::mob::MobEntity;
+use crate::entity::{EntityBase, player::Player};
 use crate::world::World;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::Ordering::Relaxed;
 
-#[allow(dead_code)]
 const MIN_DISTANCE: f64 = 2.0;
 
 pub type PredicateFn = dyn Fn(Arc<LivingEntity>, Arc<World>) -> Pin<Box<dyn Future<Output = bool> + Send>>
@@ -71,18 +73,58 @@ impl TargetPredicate {
         self.use_distance_scaling_factor = false;
     }
 
-    pub fn test(
-        &self,
-        _world: Arc<World>,
-        tester: Option<&LivingEntity>,
-        target: &LivingEntity,
-    ) -> bool {
-        if (tester.is_some() && std::ptr::eq(tester.unwrap(), target)) || !target.is_part_of_game()
+    /// Whether `tester` may target `target`. Visibility is checked using the sensing of the tester.
+    pub async fn test(&self, tester: Option<&MobEntity>, target: &dyn EntityBase) -> bool {
+        let target_entity = target.get_entity();
+        if tester
+            .is_some_and(|tester| tester.living_entity.entity.entity_id == target_entity.entity_id)
+            || target.get_living_entity().is_none()
+            || !target_entity.is_alive()
+            || target.is_spectator()
         {
             return false;
         }
-        //TODO: continue
-        true
+        // TODO: Check teams and invulnerable entities
+        if self.attackable && target.get_player().is_some_and(Player::is_creative) {
+            return false;
+        }
+        //TODO: Call the custom predicate
+
+        let Some(tester) = tester else {
+            return true;
+        };
+        if self.base_max_distance > 0.0 {
+            let scaling_factor = if self.use_distance_scaling_factor {
+                Self::attack_distance_scaling_factor(target)
+            } else {
+                1.0
+            };
+            let max_distance = (self.base_max_distance * scaling_factor).max(MIN_DISTANCE);
+            let distance = tester
+                .living_entity
+                .entity
+                .pos
+                .load()
+                .squared_distance_to_vec(target_entity.pos.load());
+            if distance > max_distance * max_distance {
+                return false;
+            }
+        }
+        !self.respects_visibility
+            || tester
+                .sensing
+                .can_see(&tester.living_entity.entity, target_entity)
+                .await
+    }
+
+    /// Sneaking makes it harder to be noticed.
+    fn attack_distance_scaling_factor(target: &dyn EntityBase) -> f64 {
+        // TODO: Invisibility and mob heads
+        if target.get_entity().sneaking.load(Relaxed) {
+            0.8
+        } else {
+            1.0
+        }
     }
 
     pub fn set_predicate<F, Fut>(&mut self, predicate: F)
@@ -1,10 +1,10 @@
 use std::sync::atomic::Ordering;
 
 use pumpkin_data::{
+    entity::EntityType,
     particle::Particle,
     sound::{Sound, SoundCategory},
 };
-use pumpkin_protocol::{codec::var_int::VarInt, java::client::play::CEntityVelocity};
 use pumpkin_util::math::vector3::Vector3;
 
 use crate::{
@@ -51,7 +51,7 @@ impl AttackType {
     }
 }
 
-pub async fn handle_knockback(attacker: &Entity, world: &World, victim: &Entity, strength: f64) {
+pub async fn handle_knockback(attacker: &Entity, victim: &Entity, strength: f64) {
     let yaw = attacker.yaw.load();
 
     let saved_velo = victim.velocity.load();
@@ -60,16 +60,14 @@ pub async fn handle_knockback(attacker: &Entity, world: &World, victim: &Entity,
         f64::from((yaw.to_radians()).sin()),
         f64::from(-(yaw.to_radians()).cos()),
     );
+    victim.send_velocity().await;
+    // Players move themselves, their client applies the knockback it was just sent
+    if victim.entity_type == &EntityType::PLAYER {
+        victim.velocity.store(saved_velo);
+    }
 
-    let entity_id = VarInt(victim.entity_id);
-    let victim_velocity = victim.velocity.load();
-
-    let packet = CEntityVelocity::new(entity_id, victim_velocity);
     let velocity = attacker.velocity.load();
     attacker.velocity.store(velocity.multiply(0.6, 1.0, 0.6));
-
-    victim.velocity.store(saved_velo);
-    world.broadcast_packet_all(&packet).await;
 }
 
 pub async fn spawn_sweep_particle(attacker_entity: &Entity, world: &World, pos: &Vector3<f64>) {
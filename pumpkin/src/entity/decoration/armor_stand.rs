@@ -0,0 +1,359 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicI32, AtomicI64, AtomicU8, Ordering},
+};
+
+use crate::entity::{Entity, EntityBase, NBTStorage, living::LivingEntity, player::Player};
+use async_trait::async_trait;
+use pumpkin_data::{damage::DamageType, entity::EntityStatus, item::Item};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::math::{boundingbox::EntityDimensions, vector3::Vector3};
+use pumpkin_world::{
+    entity::entity_data_flags::{
+        DATA_BODY_POSE, DATA_CLIENT_FLAGS, DATA_HEAD_POSE, DATA_LEFT_ARM_POSE, DATA_LEFT_LEG_POSE,
+        DATA_NO_GRAVITY, DATA_RIGHT_ARM_POSE, DATA_RIGHT_LEG_POSE,
+    },
+    item::ItemStack,
+};
+use tokio::sync::Mutex;
+
+/// A second hit within this many ticks breaks an armor stand.
+const BREAK_HIT_TICKS: i64 = 5;
+
+/// Bits of the armor stand client flags.
+pub const FLAG_SMALL: u8 = 0x01;
+pub const FLAG_SHOW_ARMS: u8 = 0x04;
+pub const FLAG_NO_BASE_PLATE: u8 = 0x08;
+pub const FLAG_MARKER: u8 = 0x10;
+
+/// The rotation of each body part in degrees, as pitch, yaw and roll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArmorStandPose {
+    pub head: Vector3<f32>,
+    pub body: Vector3<f32>,
+    pub left_arm: Vector3<f32>,
+    pub right_arm: Vector3<f32>,
+    pub left_leg: Vector3<f32>,
+    pub right_leg: Vector3<f32>,
+}
+
+impl Default for ArmorStandPose {
+    fn default() -> Self {
+        Self {
+            head: Vector3::new(0.0, 0.0, 0.0),
+            body: Vector3::new(0.0, 0.0, 0.0),
+            left_arm: Vector3::new(-10.0, 0.0, -10.0),
+            right_arm: Vector3::new(-15.0, 0.0, 10.0),
+            left_leg: Vector3::new(-1.0, 0.0, -1.0),
+            right_leg: Vector3::new(1.0, 0.0, 1.0),
+        }
+    }
+}
+
+impl ArmorStandPose {
+    fn parts(&self) -> [(&'static str, u8, Vector3<f32>); 6] {
+        [
+            ("Head", DATA_HEAD_POSE, self.head),
+            ("Body", DATA_BODY_POSE, self.body),
+            ("LeftArm", DATA_LEFT_ARM_POSE, self.left_arm),
+            ("RightArm", DATA_RIGHT_ARM_POSE, self.right_arm),
+            ("LeftLeg", DATA_LEFT_LEG_POSE, self.left_leg),
+            ("RightLeg", DATA_RIGHT_LEG_POSE, self.right_leg),
+        ]
+    }
+
+    fn write_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        for (name, _, rotation) in self.parts() {
+            nbt.put_list(
+                name,
+                vec![rotation.x.into(), rotation.y.into(), rotation.z.into()],
+            );
+        }
+        nbt
+    }
+
+    /// Reads the pose, keeping the default rotation of missing body parts.
+    fn read_nbt(nbt: &NbtCompound) -> Self {
+        let read = |name: &str, default: Vector3<f32>| {
+            let Some([x, y, z]) = nbt.get_list(name) else {
+                return default;
+            };
+            Vector3::new(
+                x.extract_float().unwrap_or(default.x),
+                y.extract_float().unwrap_or(default.y),
+                z.extract_float().unwrap_or(default.z),
+            )
+        };
+        let default = Self::default();
+        Self {
+            head: read("Head", default.head),
+            body: read("Body", default.body),
+            left_arm: read("LeftArm", default.left_arm),
+            right_arm: read("RightArm", default.right_arm),
+            left_leg: read("LeftLeg", default.left_leg),
+            right_leg: read("RightLeg", default.right_leg),
+        }
+    }
+}
+
+pub struct ArmorStandEntity {
+    living_entity: LivingEntity,
+    flags: AtomicU8,
+    pose: Mutex<ArmorStandPose>,
+    /// Bitmask of equipment slots players can not change
+    disabled_slots: AtomicI32,
+    /// World age of the last hit, armor stands break when hit twice in quick succession
+    last_hit: AtomicI64,
+}
+
+impl ArmorStandEntity {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            living_entity: LivingEntity::new(entity),
+            flags: AtomicU8::new(0),
+            pose: Mutex::new(ArmorStandPose::default()),
+            disabled_slots: AtomicI32::new(0),
+            last_hit: AtomicI64::new(i64::MIN),
+        }
+    }
+
+    fn has_flag(&self, flag: u8) -> bool {
+        self.flags.load(Ordering::Relaxed) & flag != 0
+    }
+
+    pub fn is_small(&self) -> bool {
+        self.has_flag(FLAG_SMALL)
+    }
+
+    pub fn shows_arms(&self) -> bool {
+        self.has_flag(FLAG_SHOW_ARMS)
+    }
+
+    pub fn has_base_plate(&self) -> bool {
+        !self.has_flag(FLAG_NO_BASE_PLATE)
+    }
+
+    /// Markers have no hitbox, so players can neither hit nor click them.
+    pub fn is_marker(&self) -> bool {
+        self.has_flag(FLAG_MARKER)
+    }
+
+    /// Sets or clears `flag`, one of the `FLAG_*` constants.
+    pub async fn set_flag(&self, flag: u8, value: bool) {
+        if value {
+            self.flags.fetch_or(flag, Ordering::Relaxed);
+        } else {
+            self.flags.fetch_and(!flag, Ordering::Relaxed);
+        }
+        self.update_dimensions();
+        self.send_flags().await;
+    }
+
+    pub async fn pose(&self) -> ArmorStandPose {
+        *self.pose.lock().await
+    }
+
+    pub async fn set_pose(&self, pose: ArmorStandPose) {
+        *self.pose.lock().await = pose;
+        self.send_pose(&pose).await;
+    }
+
+    fn update_dimensions(&self) {
+        let entity_type = self.living_entity.entity.entity_type;
+        let dimensions = if self.is_marker() {
+            EntityDimensions {
+                width: 0.0,
+                height: 0.0,
+            }
+        } else if self.is_small() {
+            EntityDimensions {
+                width: entity_type.dimension[0] * 0.5,
+                height: entity_type.dimension[1] * 0.5,
+            }
+        } else {
+            EntityDimensions {
+                width: entity_type.dimension[0],
+                height: entity_type.dimension[1],
+            }
+        };
+        self.living_entity.entity.set_dimensions(dimensions);
+    }
+
+    async fn send_flags(&self) {
+        self.living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                DATA_CLIENT_FLAGS,
+                MetaDataType::Byte,
+                self.flags.load(Ordering::Relaxed) as i8,
+            )])
+            .await;
+    }
+
+    async fn send_pose(&self, pose: &ArmorStandPose) {
+        let meta = pose
+            .parts()
+            .map(|(_, index, rotation)| Metadata::new(index, MetaDataType::Rotation, rotation));
+        self.living_entity.entity.send_meta_data(&meta).await;
+    }
+
+    async fn break_stand(&self, drop: bool) {
+        let entity = &self.living_entity.entity;
+        if drop {
+            entity
+                .world
+                .drop_stack(
+                    &entity.block_pos.load(),
+                    ItemStack::new(1, &Item::ARMOR_STAND),
+                )
+                .await;
+        }
+        entity.remove().await;
+    }
+}
+
+#[async_trait]
+impl NBTStorage for ArmorStandEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        let entity = &self.living_entity.entity;
+        nbt.put_bool("Invisible", entity.is_invisible());
+        nbt.put_bool("Small", self.is_small());
+        nbt.put_bool("ShowArms", self.shows_arms());
+        nbt.put_bool("NoBasePlate", !self.has_base_plate());
+        nbt.put_bool("Marker", self.is_marker());
+        nbt.put_int("DisabledSlots", self.disabled_slots.load(Ordering::Relaxed));
+        nbt.put("Pose", NbtTag::Compound(self.pose().await.write_nbt()));
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.living_entity.read_nbt_non_mut(nbt).await;
+        let mut flags = 0;
+        for (name, flag) in [
+            ("Small", FLAG_SMALL),
+            ("ShowArms", FLAG_SHOW_ARMS),
+            ("NoBasePlate", FLAG_NO_BASE_PLATE),
+            ("Marker", FLAG_MARKER),
+        ] {
+            if nbt.get_bool(name).unwrap_or(false) {
+                flags |= flag;
+            }
+        }
+        self.flags.store(flags, Ordering::Relaxed);
+        self.update_dimensions();
+        self.disabled_slots
+            .store(nbt.get_int("DisabledSlots").unwrap_or(0), Ordering::Relaxed);
+        *self.pose.lock().await = nbt
+            .get_compound("Pose")
+            .map(ArmorStandPose::read_nbt)
+            .unwrap_or_default();
+        if nbt.get_bool("Invisible").unwrap_or(false) {
+            self.living_entity.entity.set_invisible(true).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for ArmorStandEntity {
+    async fn init_data_tracker(&self) {
+        let entity = &self.living_entity.entity;
+        if entity.no_gravity.load(Ordering::Relaxed) {
+            entity
+                .send_meta_data(&[Metadata::new(DATA_NO_GRAVITY, MetaDataType::Boolean, true)])
+                .await;
+        }
+        if entity.is_invisible() {
+            entity.set_invisible(true).await;
+        }
+        self.send_flags().await;
+        self.send_pose(&self.pose().await).await;
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        Some(&self.living_entity)
+    }
+
+    fn get_gravity(&self) -> f64 {
+        if self.living_entity.entity.no_gravity.load(Ordering::Relaxed) {
+            0.0
+        } else {
+            self.living_entity.get_gravity()
+        }
+    }
+
+    fn is_pushed_by_fluids(&self) -> bool {
+        !self.is_marker()
+    }
+
+    fn can_hit(&self) -> bool {
+        !self.is_marker()
+    }
+
+    async fn skip_attack_interaction(&self, player: &Player) -> bool {
+        // Players in adventure mode can not break armor stands
+        self.is_marker() || !player.gamemode.load().can_modify_world()
+    }
+
+    async fn damage_with_context(
+        &self,
+        caller: Arc<dyn EntityBase>,
+        amount: f32,
+        damage_type: DamageType,
+        position: Option<Vector3<f64>>,
+        source: Option<&dyn EntityBase>,
+        cause: Option<&dyn EntityBase>,
+    ) -> bool {
+        let entity = &self.living_entity.entity;
+        if (self.is_marker() && damage_type != DamageType::GENERIC_KILL)
+            || entity.is_invulnerable_to(&damage_type)
+        {
+            return false;
+        }
+        if damage_type == DamageType::GENERIC_KILL {
+            return self
+                .living_entity
+                .damage_with_context(caller, amount, damage_type, position, source, cause)
+                .await;
+        }
+        if damage_type == DamageType::EXPLOSION || damage_type == DamageType::PLAYER_EXPLOSION {
+            self.break_stand(true).await;
+            return true;
+        }
+
+        let Some(source) = source else {
+            return false;
+        };
+        let Some(player) = entity
+            .world
+            .get_player_by_id(source.get_entity().entity_id)
+            .await
+        else {
+            return false;
+        };
+        if player.gamemode.load().breaks_blocks_instantly() {
+            self.break_stand(false).await;
+            return true;
+        }
+        let now = entity.world.level_time.lock().await.world_age;
+        let last_hit = self.last_hit.swap(now, Ordering::Relaxed);
+        if now.saturating_sub(last_hit) <= BREAK_HIT_TICKS {
+            self.break_stand(true).await;
+        } else {
+            entity
+                .world
+                .send_entity_status(entity, EntityStatus::HitArmorStand)
+                .await;
+        }
+        true
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+}
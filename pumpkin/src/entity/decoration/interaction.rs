@@ -0,0 +1,210 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::entity::{Entity, EntityBase, NBTStorage, living::LivingEntity, player::Player};
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::math::boundingbox::EntityDimensions;
+use pumpkin_world::entity::entity_data_flags::{
+    DATA_HEIGHT_ID_INTERACTION, DATA_RESPONSE_ID, DATA_WIDTH_ID_INTERACTION,
+};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// The last time a player attacked or interacted with an interaction entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayerAction {
+    pub player: Uuid,
+    /// The world age at the time of the click
+    pub timestamp: i64,
+}
+
+impl PlayerAction {
+    fn write_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        let uuid = self.player.as_u128();
+        nbt.put(
+            "player",
+            NbtTag::IntArray(vec![
+                (uuid >> 96) as i32,
+                ((uuid >> 64) & 0xFFFF_FFFF) as i32,
+                ((uuid >> 32) & 0xFFFF_FFFF) as i32,
+                (uuid & 0xFFFF_FFFF) as i32,
+            ]),
+        );
+        nbt.put_long("timestamp", self.timestamp);
+        nbt
+    }
+
+    fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let [a, b, c, d] = nbt.get_int_array("player")? else {
+            return None;
+        };
+        let uuid = (u128::from(*a as u32) << 96)
+            | (u128::from(*b as u32) << 64)
+            | (u128::from(*c as u32) << 32)
+            | u128::from(*d as u32);
+        Some(Self {
+            player: Uuid::from_u128(uuid),
+            timestamp: nbt.get_long("timestamp")?,
+        })
+    }
+}
+
+/// An invisible hitbox that records who clicked it, used by map makers to build interactive
+/// displays.
+pub struct InteractionEntity {
+    entity: Entity,
+    width: AtomicCell<f32>,
+    height: AtomicCell<f32>,
+    /// Whether clicking the entity makes the player swing their arm
+    response: AtomicBool,
+    attack: Mutex<Option<PlayerAction>>,
+    interaction: Mutex<Option<PlayerAction>>,
+}
+
+impl InteractionEntity {
+    pub fn new(entity: Entity) -> Self {
+        let size = entity.bounding_box_size.load();
+        Self {
+            entity,
+            width: AtomicCell::new(size.width),
+            height: AtomicCell::new(size.height),
+            response: AtomicBool::new(false),
+            attack: Mutex::new(None),
+            interaction: Mutex::new(None),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width.load()
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height.load()
+    }
+
+    pub fn response(&self) -> bool {
+        self.response.load(Ordering::Relaxed)
+    }
+
+    /// The player that last attacked this entity.
+    pub async fn last_attack(&self) -> Option<PlayerAction> {
+        *self.attack.lock().await
+    }
+
+    /// The player that last right clicked this entity.
+    pub async fn last_interaction(&self) -> Option<PlayerAction> {
+        *self.interaction.lock().await
+    }
+
+    pub async fn set_size(&self, width: f32, height: f32) {
+        self.width.store(width);
+        self.height.store(height);
+        self.entity
+            .set_dimensions(EntityDimensions { width, height });
+        self.send_size().await;
+    }
+
+    pub async fn set_response(&self, response: bool) {
+        self.response.store(response, Ordering::Relaxed);
+        self.entity
+            .send_meta_data(&[Metadata::new(
+                DATA_RESPONSE_ID,
+                MetaDataType::Boolean,
+                response,
+            )])
+            .await;
+    }
+
+    async fn send_size(&self) {
+        self.entity
+            .send_meta_data(&[
+                Metadata::new(DATA_WIDTH_ID_INTERACTION, MetaDataType::Float, self.width()),
+                Metadata::new(
+                    DATA_HEIGHT_ID_INTERACTION,
+                    MetaDataType::Float,
+                    self.height(),
+                ),
+            ])
+            .await;
+    }
+
+    async fn action(&self, player: &Player) -> PlayerAction {
+        PlayerAction {
+            player: player.gameprofile.id,
+            timestamp: self.entity.world.level_time.lock().await.world_age,
+        }
+    }
+}
+
+#[async_trait]
+impl NBTStorage for InteractionEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.entity.write_nbt(nbt).await;
+        nbt.put_float("width", self.width());
+        nbt.put_float("height", self.height());
+        nbt.put_bool("response", self.response());
+        if let Some(attack) = self.last_attack().await {
+            nbt.put_component("attack", attack.write_nbt());
+        }
+        if let Some(interaction) = self.last_interaction().await {
+            nbt.put_component("interaction", interaction.write_nbt());
+        }
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.entity.read_nbt_non_mut(nbt).await;
+        let width = nbt.get_float("width").unwrap_or(1.0);
+        let height = nbt.get_float("height").unwrap_or(1.0);
+        self.width.store(width);
+        self.height.store(height);
+        self.entity
+            .set_dimensions(EntityDimensions { width, height });
+        self.response
+            .store(nbt.get_bool("response").unwrap_or(false), Ordering::Relaxed);
+        *self.attack.lock().await = nbt.get_compound("attack").and_then(PlayerAction::read_nbt);
+        *self.interaction.lock().await = nbt
+            .get_compound("interaction")
+            .and_then(PlayerAction::read_nbt);
+    }
+}
+
+#[async_trait]
+impl EntityBase for InteractionEntity {
+    async fn init_data_tracker(&self) {
+        self.send_size().await;
+        if self.response() {
+            self.set_response(true).await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn can_hit(&self) -> bool {
+        true
+    }
+
+    async fn skip_attack_interaction(&self, player: &Player) -> bool {
+        let action = self.action(player).await;
+        *self.attack.lock().await = Some(action);
+        // Interaction entities can never be damaged
+        true
+    }
+
+    async fn interact(&self, player: &Player) {
+        let action = self.action(player).await;
+        *self.interaction.lock().await = Some(action);
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+}
@@ -1,2 +1,4 @@
+pub mod armor_stand;
 pub mod end_crystal;
+pub mod interaction;
 pub mod painting;
@@ -5,17 +5,23 @@ use std::sync::{
 };
 
 use async_trait::async_trait;
-use pumpkin_data::entity::EntityType;
-use pumpkin_util::math::vector3::Vector3;
+use pumpkin_data::{Enchantment, data_component_impl::EquipmentSlot, entity::EntityType};
+use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+use rand::{Rng, seq::IndexedRandom};
 use uuid::Uuid;
 
 use crate::{server::Server, world::World};
 
 use super::{Entity, EntityBase, NBTStorage, living::LivingEntity, player::Player};
 
+/// Orbs only merge if their entity IDs differ by a multiple of this, like in vanilla.
+const MERGE_ID_MODULUS: i32 = 40;
+
 pub struct ExperienceOrbEntity {
     entity: Entity,
     amount: u32,
+    /// How many orbs of `amount` this orb represents.
+    picking_count: AtomicU32,
     orb_age: AtomicU32,
 }
 
@@ -25,6 +31,7 @@ impl ExperienceOrbEntity {
         Self {
             entity,
             amount,
+            picking_count: AtomicU32::new(1),
             orb_age: AtomicU32::new(0),
         }
     }
@@ -34,6 +41,9 @@ impl ExperienceOrbEntity {
         while amount > 0 {
             let i = Self::round_to_orb_size(amount);
             amount -= i;
+            if Self::merge_into_existing(world, position, i).await {
+                continue;
+            }
             let entity = Entity::new(
                 Uuid::new_v4(),
                 world.clone(),
@@ -46,6 +56,96 @@ impl ExperienceOrbEntity {
         }
     }
 
+    /// Adds an orb of `amount` to a matching orb near `position` instead of spawning a new one.
+    async fn merge_into_existing(world: &World, position: Vector3<f64>, amount: u32) -> bool {
+        let bounding_box = BoundingBox::new(
+            position.sub_raw(0.5, 0.5, 0.5),
+            position.add_raw(0.5, 0.5, 0.5),
+        );
+        let seed = rand::rng().random_range(0..MERGE_ID_MODULUS);
+        for entity in world.get_entities_at_box(&bounding_box).await {
+            if let Some(orb) = entity.get_experience_orb()
+                && orb.is_mergeable(seed, amount)
+            {
+                orb.picking_count.fetch_add(1, Ordering::Relaxed);
+                orb.orb_age.store(0, Ordering::Relaxed);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_mergeable(&self, seed: i32, amount: u32) -> bool {
+        !self.entity.removed.load(Ordering::Relaxed)
+            && (self.entity.entity_id - seed) % MERGE_ID_MODULUS == 0
+            && self.amount == amount
+    }
+
+    /// Absorbs nearby orbs of the same size into this one.
+    async fn merge_nearby(&self) {
+        let bounding_box = self.entity.bounding_box.load().expand(0.5, 0.5, 0.5);
+        for entity in self.entity.world.get_entities_at_box(&bounding_box).await {
+            let Some(other) = entity.get_experience_orb() else {
+                continue;
+            };
+            if other.entity.entity_id == self.entity.entity_id
+                || !other.is_mergeable(self.entity.entity_id, self.amount)
+            {
+                continue;
+            }
+            self.picking_count.fetch_add(
+                other.picking_count.swap(0, Ordering::Relaxed),
+                Ordering::Relaxed,
+            );
+            self.orb_age
+                .fetch_min(other.orb_age.load(Ordering::Relaxed), Ordering::Relaxed);
+            other.entity.remove().await;
+        }
+    }
+
+    /// Repairs random damaged mending gear of the player, returning the experience left over.
+    async fn repair_player_gear(player: &Player, mut amount: u32) -> u32 {
+        let mut gear = vec![
+            player.inventory.held_item(),
+            player.living_entity.off_hand_item().await,
+        ];
+        {
+            let equipment = player.living_entity.entity_equipment.lock().await;
+            for slot in [
+                EquipmentSlot::HEAD,
+                EquipmentSlot::CHEST,
+                EquipmentSlot::LEGS,
+                EquipmentSlot::FEET,
+            ] {
+                gear.push(equipment.get(&slot));
+            }
+        }
+
+        while amount > 0 {
+            let mut candidates = Vec::new();
+            for stack in &gear {
+                let stack_guard = stack.lock().await;
+                if stack_guard.get_damage() > 0
+                    && stack_guard.get_enchantment_level(&Enchantment::MENDING) > 0
+                {
+                    candidates.push(stack.clone());
+                }
+            }
+            let Some(stack) = candidates.choose(&mut rand::rng()) else {
+                break;
+            };
+
+            // Mending repairs 2 durability per experience point
+            let repair = amount * 2;
+            let mut stack = stack.lock().await;
+            let damage = stack.get_damage();
+            let repaired = repair.min(damage as u32);
+            stack.set_damage(damage - repaired as i32);
+            amount -= repaired * amount / repair;
+        }
+        amount
+    }
+
     fn round_to_orb_size(value: u32) -> u32 {
         if value >= 2477 {
             2477
@@ -102,6 +202,10 @@ impl EntityBase for ExperienceOrbEntity {
 
         entity.tick_block_collisions(&caller, server).await;
 
+        if entity.age.load(Ordering::Relaxed) % 20 == 1 {
+            self.merge_nearby().await;
+        }
+
         let age = self.orb_age.fetch_add(1, Ordering::Relaxed);
         if age >= 6000 {
             self.entity.remove().await;
@@ -118,9 +222,17 @@ impl EntityBase for ExperienceOrbEntity {
             if *delay == 0 {
                 *delay = 2;
                 player.living_entity.pickup(&self.entity, 1).await;
-                player.add_experience_points(self.amount as i32).await;
-                // TODO: pickingCount for merging
-                self.entity.remove().await;
+                let amount = Self::repair_player_gear(player, self.amount).await;
+                if amount > 0 {
+                    player.add_experience_points(amount as i32).await;
+                }
+                let remaining = self
+                    .picking_count
+                    .fetch_sub(1, Ordering::Relaxed)
+                    .saturating_sub(1);
+                if remaining == 0 {
+                    self.entity.remove().await;
+                }
             }
         }
     }
@@ -129,6 +241,10 @@ impl EntityBase for ExperienceOrbEntity {
         None
     }
 
+    fn get_experience_orb(self: Arc<Self>) -> Option<Arc<ExperienceOrbEntity>> {
+        Some(self)
+    }
+
     fn as_nbt_storage(&self) -> &dyn NBTStorage {
         self
     }
@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use pumpkin_data::Block;
+use pumpkin_data::damage::DamageType;
 use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
 use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::item::ItemStack;
 use pumpkin_world::{BlockStateId, world::BlockFlags};
 use std::sync::{Arc, atomic::Ordering};
 use uuid::Uuid;
@@ -13,21 +16,51 @@ use crate::{
     world::World,
 };
 
+/// How a falling block hurts the entities it lands on.
+#[derive(Clone, Copy)]
+pub struct FallingBlockDamage {
+    /// Damage dealt for every block fallen
+    pub per_block: f32,
+    /// The most damage the block can deal
+    pub max: f32,
+    pub damage_type: DamageType,
+}
+
 pub struct FallingEntity {
     entity: Entity,
     block_state_id: BlockStateId,
+    damage: Option<FallingBlockDamage>,
+    /// Whether the block breaks into its item when it lands instead of being placed again
+    break_on_landing: bool,
+    fall_start_y: f64,
 }
 
 impl FallingEntity {
     pub fn new(entity: Entity, block_state_id: BlockStateId) -> Self {
+        let fall_start_y = entity.pos.load().y;
         Self {
             entity,
             block_state_id,
+            damage: None,
+            break_on_landing: false,
+            fall_start_y,
         }
     }
 
     /// Replaced the current Block and Spawns a new Falling one
     pub async fn replace_spawn(world: &Arc<World>, position: BlockPos, block_state: BlockStateId) {
+        Self::replace_spawn_with(world, position, block_state, None, false).await;
+    }
+
+    /// Like [`Self::replace_spawn`], but the falling block may hurt what it lands on and break
+    /// into its item instead of being placed again.
+    pub async fn replace_spawn_with(
+        world: &Arc<World>,
+        position: BlockPos,
+        block_state: BlockStateId,
+        damage: Option<FallingBlockDamage>,
+        break_on_landing: bool,
+    ) {
         // Replace the original block, TODO: use fluid state
         world
             .set_block_state(
@@ -46,8 +79,41 @@ impl FallingEntity {
             false,
         );
         entity.data.store(i32::from(block_state), Ordering::Relaxed);
-        let entity = Arc::new(Self::new(entity, block_state));
-        world.spawn_entity(entity).await;
+        let mut falling = Self::new(entity, block_state);
+        falling.damage = damage;
+        falling.break_on_landing = break_on_landing;
+        world.spawn_entity(Arc::new(falling)).await;
+    }
+
+    /// Hurts every living entity the block landed on, the further it fell the more it hurts.
+    async fn hurt_entities(&self, damage: FallingBlockDamage) {
+        let entity = &self.entity;
+        let fall_distance = (self.fall_start_y - entity.pos.load().y) as f32;
+        let blocks_fallen = (fall_distance - 1.0).ceil();
+        if blocks_fallen < 0.0 {
+            return;
+        }
+        let amount = (blocks_fallen * damage.per_block).floor().min(damage.max);
+        if amount <= 0.0 {
+            return;
+        }
+
+        let bounding_box = entity.bounding_box.load();
+        for player in entity.world.get_players_at_box(&bounding_box).await {
+            if !player.gamemode.load().takes_damage() {
+                continue;
+            }
+            player
+                .damage(player.clone(), amount, damage.damage_type)
+                .await;
+        }
+        for other in entity.world.get_entities_at_box(&bounding_box).await {
+            if other.get_living_entity().is_some() {
+                other
+                    .damage(other.clone(), amount, damage.damage_type)
+                    .await;
+            }
+        }
     }
 }
 
@@ -69,14 +135,24 @@ impl EntityBase for FallingEntity {
         entity.tick_block_collisions(&caller, server).await;
         if entity.on_ground.load(Ordering::Relaxed) {
             entity.velocity.store(velo.multiply(0.7, -0.5, 0.7));
-            entity
-                .world
-                .set_block_state(
-                    &self.entity.block_pos.load(),
-                    self.block_state_id,
-                    BlockFlags::NOTIFY_ALL,
-                )
-                .await;
+            if let Some(damage) = self.damage {
+                self.hurt_entities(damage).await;
+            }
+            let block_pos = self.entity.block_pos.load();
+            if self.break_on_landing {
+                let block = Block::from_state_id(self.block_state_id);
+                if let Some(item) = Item::from_id(block.item_id) {
+                    entity
+                        .world
+                        .drop_stack(&block_pos, ItemStack::new(1, item))
+                        .await;
+                }
+            } else {
+                entity
+                    .world
+                    .set_block_state(&block_pos, self.block_state_id, BlockFlags::NOTIFY_ALL)
+                    .await;
+            }
             entity.remove().await;
         }
 
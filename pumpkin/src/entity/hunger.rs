@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{Arc, atomic::Ordering};
 
 use super::{EntityBase, NBTStorage, NBTStorageInit, player::Player};
 use async_trait::async_trait;
@@ -30,37 +30,48 @@ impl Default for HungerManager {
 
 impl HungerManager {
     pub async fn tick(&self, player: &Arc<Player>) {
-        let saturation = self.saturation.load();
-        let level = self.level.load();
-        let exhaustion = self.exhaustion.load();
-        let health = player.living_entity.health.load();
-        let difficulty = player.world().level_info.read().await.difficulty;
+        let (difficulty, natural_regen) = {
+            let level_info = player.world().level_info.read().await;
+            (
+                level_info.difficulty,
+                level_info.game_rules.natural_regeneration,
+            )
+        };
+        if difficulty == Difficulty::Peaceful && natural_regen {
+            self.tick_peaceful(player).await;
+        }
+
         // Decrease hunger level on exhaustion
-        if level != 0 && exhaustion > 4.0 {
+        let exhaustion = self.exhaustion.load();
+        if exhaustion > 4.0 {
             self.exhaustion.store(exhaustion - 4.0);
+            let saturation = self.saturation.load();
+            let level = self.level.load();
             if saturation > 0.0 {
                 self.saturation.store((saturation - 1.0).max(0.0));
-            } else if difficulty != Difficulty::Peaceful {
+            } else if difficulty != Difficulty::Peaceful && level > 0 {
                 self.level.store(level - 1);
                 player.send_health().await;
             }
         }
 
-        // Heal when hunger is full
-        let natural_regen = true; // TODO: Get the actual value when this will be implemented.
+        let saturation = self.saturation.load();
+        let level = self.level.load();
+        let health = player.living_entity.health.load();
+        // Heal quickly while there is saturation left on a full hunger bar, and slowly otherwise
         if natural_regen && saturation > 0.0 && player.can_food_heal() && level >= 20 {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 10 {
                 let saturation = saturation.min(6.0);
                 player.heal(saturation / 6.0).await;
-                self.add_exhaustion(saturation);
+                player.add_exhaustion(saturation).await;
                 self.tick_timer.store(0);
             }
         } else if natural_regen && level >= 18 && player.can_food_heal() {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 80 {
                 player.heal(1.0).await;
-                self.add_exhaustion(6.0);
+                player.add_exhaustion(6.0).await;
                 self.tick_timer.store(0);
             }
         } else if level == 0 {
@@ -79,6 +90,20 @@ impl HungerManager {
         }
     }
 
+    /// Players on peaceful regain health and hunger on their own, unless natural regeneration is
+    /// turned off.
+    async fn tick_peaceful(&self, player: &Player) {
+        let age = player.living_entity.entity.age.load(Ordering::Relaxed);
+        if player.can_food_heal() && age % 20 == 0 {
+            player.heal(1.0).await;
+        }
+        let level = self.level.load();
+        if level < 20 && age % 10 == 0 {
+            self.level.store(level + 1);
+            player.send_health().await;
+        }
+    }
+
     pub async fn add_modifier(&self, player: &Player, food: u8, saturation_modifier: f32) {
         let saturation = f32::from(food) * saturation_modifier * 2.0;
         self.level.store(food + self.level.load());
@@ -9,7 +9,9 @@ use std::sync::{
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
-use pumpkin_data::damage::DamageType;
+use pumpkin_config::advanced_config;
+use pumpkin_data::{damage::DamageType, tag, tag::Taggable};
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::{
     codec::item_stack_seralizer::ItemStackSerializer,
     java::client::play::{CTakeItemEntity, MetaDataType, Metadata},
@@ -17,10 +19,21 @@ use pumpkin_protocol::{
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::item::ItemStack;
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 use crate::server::Server;
 
-use super::{Entity, EntityBase, NBTStorage, living::LivingEntity, player::Player};
+use super::{
+    Entity, EntityBase, Flag, NBTStorage, living::LivingEntity, player::Player, uuid_from_nbt,
+    uuid_to_nbt,
+};
+
+/// Items this old despawn.
+const DESPAWN_AGE: u32 = 6000;
+/// Stored as the age of items that never despawn.
+const NEVER_DESPAWN_AGE: i16 = i16::MIN;
+/// Stored as the pickup delay of items that can never be picked up.
+const NEVER_PICKUP_DELAY: i16 = i16::MAX;
 
 pub struct ItemEntity {
     entity: Entity,
@@ -32,6 +45,14 @@ pub struct ItemEntity {
     health: AtomicCell<f32>,
     never_despawn: AtomicBool,
     never_pickup: AtomicBool,
+    /// The only player who can pick the item up, anyone can if this is `None`
+    owner: AtomicCell<Option<Uuid>>,
+    /// Ticks until the item is no longer reserved for its owner, it stays reserved if `None`
+    owner_ticks: AtomicCell<Option<u32>>,
+    /// Whether the item glows, e.g. because it was dropped on death
+    glowing: AtomicBool,
+    /// The entity that dropped or threw the item
+    thrower: AtomicCell<Option<Uuid>>,
 }
 
 impl ItemEntity {
@@ -52,6 +73,10 @@ impl ItemEntity {
             health: AtomicCell::new(5.0),
             never_despawn: AtomicBool::new(false),
             never_pickup: AtomicBool::new(false),
+            owner: AtomicCell::new(None),
+            owner_ticks: AtomicCell::new(None),
+            glowing: AtomicBool::new(false),
+            thrower: AtomicCell::new(None),
         }
     }
 
@@ -71,9 +96,131 @@ impl ItemEntity {
             health: AtomicCell::new(5.0),
             never_despawn: AtomicBool::new(false),
             never_pickup: AtomicBool::new(false),
+            owner: AtomicCell::new(None),
+            owner_ticks: AtomicCell::new(None),
+            glowing: AtomicBool::new(false),
+            thrower: AtomicCell::new(None),
+        }
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner.load()
+    }
+
+    /// Only lets the player with the UUID `owner` pick the item up, or anyone if it is `None`.
+    pub fn set_owner(&self, owner: Option<Uuid>) {
+        self.owner.store(owner);
+        self.owner_ticks.store(None);
+    }
+
+    /// Marks the item as dropped by `owner` on death: only they can pick it up during the
+    /// configured grace period, and it glows if configured. Call this before spawning the item.
+    pub fn set_death_drop(&self, owner: Uuid) {
+        let config = &advanced_config().item_entity;
+        if config.death_drop_protection_ticks > 0 {
+            self.owner.store(Some(owner));
+            self.owner_ticks
+                .store(Some(config.death_drop_protection_ticks));
+        }
+        if config.glow_death_drops {
+            self.glowing.store(true, Ordering::Relaxed);
         }
     }
 
+    pub fn thrower(&self) -> Option<Uuid> {
+        self.thrower.load()
+    }
+
+    pub fn set_thrower(&self, thrower: Option<Uuid>) {
+        self.thrower.store(thrower);
+    }
+
+    /// Sets the ticks until the item can be picked up, 0 lets it be picked up right away.
+    pub async fn set_pickup_delay(&self, ticks: u8) {
+        *self.pickup_delay.lock().await = ticks;
+        self.never_pickup.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the entity with the UUID `collector` may pick the item up right now.
+    async fn can_be_picked_up_by(&self, collector: Uuid) -> bool {
+        *self.pickup_delay.lock().await == 0
+            && !self.never_pickup.load(Ordering::Relaxed)
+            && self.owner.load().is_none_or(|owner| owner == collector)
+    }
+
+    /// Whether the item could be stacked onto `stack`, an empty stack takes anything.
+    pub async fn fits_onto(&self, stack: &ItemStack) -> bool {
+        stack.is_empty()
+            || (stack.item_count < stack.get_max_stack_size()
+                && stack.are_items_and_components_equal(&*self.item_stack.lock().await))
+    }
+
+    /// Whether the item is the same as `stack`'s, ignoring their components.
+    pub async fn is_same_item(&self, stack: &ItemStack) -> bool {
+        self.item_stack.lock().await.item == stack.item
+    }
+
+    /// Lets `collector`, which is not a player, pick up as much of the item as fits onto `stack`.
+    /// Returns whether it picked up anything.
+    pub async fn pick_up_into(&self, collector: &LivingEntity, stack: &mut ItemStack) -> bool {
+        if self.entity.removed.load(Ordering::Relaxed)
+            || !self.can_be_picked_up_by(collector.entity.entity_uuid).await
+            || !self.fits_onto(stack).await
+        {
+            return false;
+        }
+
+        let mut item_stack = self.item_stack.lock().await;
+        let room = item_stack.get_max_stack_size() - stack.item_count;
+        let picked_up = item_stack.split(room);
+        let empty = item_stack.is_empty();
+        drop(item_stack);
+
+        collector
+            .pickup(&self.entity, u32::from(picked_up.item_count))
+            .await;
+        if stack.is_empty() {
+            *stack = picked_up;
+        } else {
+            stack.increment(picked_up.item_count);
+        }
+        if empty {
+            self.entity.remove().await;
+        } else {
+            self.init_data_tracker().await;
+        }
+        true
+    }
+
+    /// Whether the configuration protects the item against `damage_type`.
+    fn is_immune_to(stack: &ItemStack, damage_type: DamageType) -> bool {
+        let config = &advanced_config().item_entity;
+        let listed = |items: &[String]| {
+            items
+                .iter()
+                .any(|key| key.strip_prefix("minecraft:").unwrap_or(key) == stack.item.registry_key)
+        };
+        (damage_type.is_tagged_with_by_tag(&tag::DamageType::MINECRAFT_IS_EXPLOSION)
+            && listed(&config.explosion_immune))
+            || (damage_type.is_tagged_with_by_tag(&tag::DamageType::MINECRAFT_IS_FIRE)
+                && listed(&config.fire_immune))
+            || (damage_type == DamageType::CACTUS && listed(&config.cactus_immune))
+    }
+
+    /// Whether the configuration makes the item glow because it is immune to some damage.
+    fn glows_as_immune(stack: &ItemStack) -> bool {
+        let config = &advanced_config().item_entity;
+        config.glow_immune_items
+            && [
+                &config.explosion_immune,
+                &config.fire_immune,
+                &config.cactus_immune,
+            ]
+            .into_iter()
+            .flatten()
+            .any(|key| key.strip_prefix("minecraft:").unwrap_or(key) == stack.item.registry_key)
+    }
+
     async fn can_merge(&self) -> bool {
         if self.never_pickup.load(Ordering::Relaxed) || self.entity.removed.load(Ordering::Relaxed)
         {
@@ -118,6 +265,11 @@ impl ItemEntity {
     async fn try_merge_with(&self, other: &Self) {
         // Check if merge is possible
 
+        // Merging would hand items reserved for one player to someone else
+        if self.owner.load() != other.owner.load() {
+            return;
+        }
+
         let self_stack = self.item_stack.lock().await;
 
         let other_stack = other.item_stack.lock().await;
@@ -192,17 +344,96 @@ impl ItemEntity {
     }
 }
 
-impl NBTStorage for ItemEntity {}
+#[async_trait]
+impl NBTStorage for ItemEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.entity.write_nbt(nbt).await;
+        nbt.put_short("Health", self.health.load() as i16);
+        let age = if self.never_despawn.load(Ordering::Relaxed) {
+            NEVER_DESPAWN_AGE
+        } else {
+            self.item_age.load(Ordering::Relaxed).min(DESPAWN_AGE) as i16
+        };
+        nbt.put_short("Age", age);
+        let pickup_delay = if self.never_pickup.load(Ordering::Relaxed) {
+            NEVER_PICKUP_DELAY
+        } else {
+            i16::from(*self.pickup_delay.lock().await)
+        };
+        nbt.put_short("PickupDelay", pickup_delay);
+        if let Some(owner) = self.owner.load() {
+            nbt.put("Owner", uuid_to_nbt(owner));
+            if let Some(owner_ticks) = self.owner_ticks.load() {
+                nbt.put_int("OwnerTicks", owner_ticks as i32);
+            }
+        }
+        if self.glowing.load(Ordering::Relaxed) {
+            nbt.put_bool("Glowing", true);
+        }
+        if let Some(thrower) = self.thrower.load() {
+            nbt.put("Thrower", uuid_to_nbt(thrower));
+        }
+        let item_stack = self.item_stack.lock().await;
+        if !item_stack.is_empty() {
+            let mut item = NbtCompound::new();
+            item_stack.write_item_stack(&mut item);
+            nbt.put_component("Item", item);
+        }
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.entity.read_nbt_non_mut(nbt).await;
+        if let Some(health) = nbt.get_short("Health") {
+            self.health.store(f32::from(health));
+        }
+        let age = nbt.get_short("Age").unwrap_or(0);
+        self.never_despawn
+            .store(age == NEVER_DESPAWN_AGE, Ordering::Relaxed);
+        self.item_age.store(age.max(0) as u32, Ordering::Relaxed);
+        if let Some(pickup_delay) = nbt.get_short("PickupDelay") {
+            self.never_pickup
+                .store(pickup_delay == NEVER_PICKUP_DELAY, Ordering::Relaxed);
+            *self.pickup_delay.lock().await = pickup_delay.clamp(0, i16::from(u8::MAX)) as u8;
+        }
+        self.owner
+            .store(nbt.get_int_array("Owner").and_then(uuid_from_nbt));
+        self.owner_ticks.store(
+            nbt.get_int("OwnerTicks")
+                .map(|owner_ticks| owner_ticks.max(0) as u32),
+        );
+        self.glowing
+            .store(nbt.get_bool("Glowing").unwrap_or(false), Ordering::Relaxed);
+        self.thrower
+            .store(nbt.get_int_array("Thrower").and_then(uuid_from_nbt));
+        if let Some(item) = nbt
+            .get_compound("Item")
+            .and_then(ItemStack::read_item_stack)
+        {
+            *self.item_stack.lock().await = item;
+        }
+    }
+}
 
 #[async_trait]
 impl EntityBase for ItemEntity {
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         let entity = &self.entity;
+        if self.item_stack.lock().await.is_empty() {
+            entity.remove().await;
+            return;
+        }
         entity.tick(caller.clone(), server).await;
         {
             let mut delay = self.pickup_delay.lock().await;
             *delay = delay.saturating_sub(1);
         };
+        if let Some(owner_ticks) = self.owner_ticks.load() {
+            if owner_ticks <= 1 {
+                self.set_owner(None);
+            } else {
+                self.owner_ticks.store(Some(owner_ticks - 1));
+            }
+        }
 
         let original_velo = entity.velocity.load();
 
@@ -294,7 +525,7 @@ impl EntityBase for ItemEntity {
         if !self.never_despawn.load(Ordering::Relaxed) {
             let age = self.item_age.fetch_add(1, Ordering::Relaxed) + 1;
 
-            if age >= 6000 {
+            if age >= DESPAWN_AGE {
                 entity.remove().await;
 
                 return;
@@ -341,6 +572,11 @@ impl EntityBase for ItemEntity {
     }
 
     async fn init_data_tracker(&self) {
+        if self.glowing.load(Ordering::Relaxed)
+            || Self::glows_as_immune(&*self.item_stack.lock().await)
+        {
+            self.entity.set_flag(Flag::Glowing, true).await;
+        }
         self.entity
             .send_meta_data(&[Metadata::new(
                 8,
@@ -354,12 +590,14 @@ impl EntityBase for ItemEntity {
         &self,
         _caller: Arc<dyn EntityBase>,
         amount: f32,
-        _damage_type: DamageType,
+        damage_type: DamageType,
         _position: Option<Vector3<f64>>,
         _source: Option<&dyn EntityBase>,
         _cause: Option<&dyn EntityBase>,
     ) -> bool {
-        //TODO: invulnerability, e.g. ancient debris
+        if Self::is_immune_to(&*self.item_stack.lock().await, damage_type) {
+            return false;
+        }
         self.health.store(self.health.load() - amount);
         if self.health.load() <= 0.0 {
             self.entity.remove().await;
@@ -367,20 +605,8 @@ impl EntityBase for ItemEntity {
         true
     }
 
-    async fn damage(
-        &self,
-        _caller: Arc<dyn EntityBase>,
-        _amount: f32,
-        _damage_type: DamageType,
-    ) -> bool {
-        false
-    }
-
     async fn on_player_collision(&self, player: &Arc<Player>) {
-        let can_pickup = {
-            let delay = self.pickup_delay.lock().await;
-            *delay == 0
-        };
+        let can_pickup = self.can_be_picked_up_by(player.gameprofile.id).await;
 
         if can_pickup
             && player.living_entity.health.load() > 0.0
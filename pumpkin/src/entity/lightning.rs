@@ -0,0 +1,235 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Arc,
+        atomic::{AtomicI32, Ordering::Relaxed},
+    },
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::{
+    Difficulty,
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+};
+use pumpkin_world::world::BlockFlags;
+use rand::{Rng, rng};
+use tokio::sync::Mutex;
+
+use super::{Entity, EntityBase, NBTStorage, living::LivingEntity, mob::convert_to};
+use crate::{
+    block::blocks::fire::{FireBlockBase, fire::FireBlock},
+    server::Server,
+    world::World,
+};
+
+/// Index of the creeper's charged flag in its metadata.
+const CREEPER_CHARGED_ID: u8 = 17;
+
+pub struct LightningEntity {
+    entity: Entity,
+    /// Counts down while the bolt is visible. Each flash starts again at 1.
+    ambient_tick: AtomicI32,
+    /// How many more times the bolt flashes.
+    remaining_actions: AtomicI32,
+    /// Cosmetic bolts don't start fires or hurt entities, like the one of a skeleton horse trap.
+    cosmetic: bool,
+    struck_entities: Mutex<HashSet<i32>>,
+}
+
+impl LightningEntity {
+    pub fn new(entity: Entity, cosmetic: bool) -> Self {
+        Self {
+            entity,
+            ambient_tick: AtomicI32::new(2),
+            remaining_actions: AtomicI32::new(rng().random_range(1..=3)),
+            cosmetic,
+            struck_entities: Mutex::new(HashSet::new()),
+        }
+    }
+
+    async fn spawn_fire(&self, spread_attempts: u32) {
+        let world = &self.entity.world;
+        if self.cosmetic || !world.level_info.read().await.game_rules.do_fire_tick {
+            return;
+        }
+        let pos = self.entity.block_pos.load();
+        Self::place_fire(world, pos).await;
+        for _ in 0..spread_attempts {
+            let offset = Vector3::new(
+                rng().random_range(-1..=1),
+                rng().random_range(-1..=1),
+                rng().random_range(-1..=1),
+            );
+            Self::place_fire(world, pos.offset(offset)).await;
+        }
+    }
+
+    async fn place_fire(world: &Arc<World>, pos: BlockPos) {
+        if !FireBlockBase::can_place_at(world, &pos).await {
+            return;
+        }
+        let fire = FireBlockBase::get_fire_type(world, &pos).await;
+        let state_id = FireBlock.get_state_for_position(world, &fire, &pos).await;
+        world
+            .set_block_state(&pos, state_id, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+
+    async fn strike_entities(&self) {
+        let world = &self.entity.world;
+        let pos = self.entity.pos.load();
+        let area = BoundingBox::new(
+            Vector3::new(pos.x - 3.0, pos.y - 3.0, pos.z - 3.0),
+            Vector3::new(pos.x + 3.0, pos.y + 9.0, pos.z + 3.0),
+        );
+        let mut targets = world.get_entities_at_box(&area).await;
+        targets.extend(
+            world
+                .get_players_at_box(&area)
+                .await
+                .into_iter()
+                .map(|player| player as Arc<dyn EntityBase>),
+        );
+
+        for target in targets {
+            let target_entity = target.get_entity();
+            if target_entity.entity_id == self.entity.entity_id
+                || !target_entity.is_alive()
+                || !self
+                    .struck_entities
+                    .lock()
+                    .await
+                    .insert(target_entity.entity_id)
+            {
+                continue;
+            }
+            self.strike(target).await;
+        }
+    }
+
+    /// What happens to an entity struck by this bolt.
+    async fn strike(&self, target: Arc<dyn EntityBase>) {
+        let target_entity = target.get_entity();
+        let entity_type = target_entity.entity_type;
+        let difficulty = self.entity.world.level_info.read().await.difficulty;
+
+        if difficulty != Difficulty::Peaceful {
+            let converted = if entity_type == &EntityType::PIG {
+                Some(&EntityType::ZOMBIFIED_PIGLIN)
+            } else if entity_type == &EntityType::VILLAGER {
+                Some(&EntityType::WITCH)
+            } else {
+                None
+            };
+            if let Some(converted) = converted {
+                let converted = convert_to(target_entity, converted).await;
+                self.struck_entities
+                    .lock()
+                    .await
+                    .insert(converted.get_entity().entity_id);
+                return;
+            }
+        }
+        if entity_type == &EntityType::CREEPER {
+            target_entity
+                .send_meta_data(&[Metadata::new(
+                    CREEPER_CHARGED_ID,
+                    MetaDataType::Boolean,
+                    true,
+                )])
+                .await;
+        }
+
+        let fire_ticks = target_entity.fire_ticks.fetch_add(1, Relaxed) + 1;
+        if fire_ticks == 0 {
+            target_entity.set_on_fire_for(8.0);
+        }
+        target
+            .damage_with_context(
+                target.clone(),
+                5.0,
+                DamageType::LIGHTNING_BOLT,
+                None,
+                Some(self),
+                None,
+            )
+            .await;
+    }
+}
+
+impl NBTStorage for LightningEntity {}
+
+#[async_trait]
+impl EntityBase for LightningEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        self.entity.tick(caller, server).await;
+        let world = &self.entity.world;
+
+        let mut ambient_tick = self.ambient_tick.load(Relaxed);
+        if ambient_tick == 2 {
+            let pos = self.entity.pos.load();
+            let thunder_pitch = 0.8 + rng().random::<f32>() * 0.2;
+            world
+                .play_sound_raw(
+                    Sound::EntityLightningBoltThunder as u16,
+                    SoundCategory::Weather,
+                    &pos,
+                    10000.0,
+                    thunder_pitch,
+                )
+                .await;
+            let impact_pitch = 0.5 + rng().random::<f32>() * 0.2;
+            world
+                .play_sound_raw(
+                    Sound::EntityLightningBoltImpact as u16,
+                    SoundCategory::Weather,
+                    &pos,
+                    2.0,
+                    impact_pitch,
+                )
+                .await;
+
+            let difficulty = world.level_info.read().await.difficulty;
+            if matches!(difficulty, Difficulty::Normal | Difficulty::Hard) {
+                self.spawn_fire(4).await;
+            }
+        }
+
+        ambient_tick -= 1;
+        if ambient_tick < 0 {
+            let remaining_actions = self.remaining_actions.load(Relaxed);
+            let flash_delay = rng().random_range(0..10);
+            if remaining_actions == 0 {
+                self.entity.remove().await;
+                return;
+            } else if ambient_tick < -flash_delay {
+                self.remaining_actions.store(remaining_actions - 1, Relaxed);
+                ambient_tick = 1;
+                self.spawn_fire(0).await;
+            }
+        }
+        self.ambient_tick.store(ambient_tick, Relaxed);
+
+        if ambient_tick >= 0 && !self.cosmetic {
+            self.strike_entities().await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+}
@@ -13,17 +13,24 @@ use std::{collections::HashMap, sync::atomic::AtomicI32};
 
 use super::{Entity, NBTStorage};
 use super::{EntityBase, NBTStorageInit};
+use crate::PLUGIN_MANAGER;
+use crate::plugin::entity::entity_damage::EntityDamageEvent;
 use crate::server::Server;
 use crate::world::loot::{LootContextParameters, LootTableExt};
+use crate::world::raid;
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::advanced_config;
-use pumpkin_data::Block;
 use pumpkin_data::damage::DeathMessageType;
-use pumpkin_data::data_component_impl::{DeathProtectionImpl, EquipmentSlot, FoodImpl};
+use pumpkin_data::data_component_impl::{
+    CustomNameImpl, DeathProtectionImpl, EquipmentSlot, FoodImpl,
+};
 use pumpkin_data::effect::StatusEffect;
 use pumpkin_data::entity::{EntityPose, EntityStatus, EntityType};
+use pumpkin_data::fluid::Fluid;
 use pumpkin_data::sound::SoundCategory;
+use pumpkin_data::tag::EntityType::MINECRAFT_CAN_BREATHE_UNDER_WATER;
+use pumpkin_data::{Block, Enchantment};
 use pumpkin_data::{damage::DamageType, sound::Sound};
 use pumpkin_inventory::entity_equipment::EntityEquipment;
 use pumpkin_nbt::compound::NbtCompound;
@@ -34,9 +41,14 @@ use pumpkin_protocol::{
     codec::item_stack_seralizer::ItemStackSerializer,
     java::client::play::{CDamageEvent, CSetEquipment, MetaDataType, Metadata},
 };
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::click::ClickEvent;
+use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_world::item::ItemStack;
+use rand::Rng;
+use std::borrow::Cow;
 use tokio::sync::Mutex;
 
 /// Represents a living entity within the game world.
@@ -74,6 +86,9 @@ pub struct LivingEntity {
     /// The position where the entity was last climbing, used for death messages
     pub climbing_pos: AtomicCell<Option<BlockPos>>,
 
+    /// The id of the entity that last attacked this entity and the age at which it did, used for death messages
+    pub last_attacker: AtomicCell<Option<(i32, i32)>>,
+
     water_movement_speed_multiplier: f32,
     livings_flags: AtomicU8,
 }
@@ -90,6 +105,8 @@ impl LivingEntity {
     const OFF_HAND_ACTIVE_FLAG: i32 = 2;
     #[allow(dead_code)]
     const USING_RIPTIDE_FLAG: i32 = 4;
+    /// How long an attacker is remembered for death messages
+    const ATTACKER_MEMORY_TICKS: i32 = 100;
 
     pub fn new(entity: Entity) -> Self {
         let water_movement_speed_multiplier = if entity.entity_type == &EntityType::POLAR_BEAR {
@@ -120,6 +137,7 @@ impl LivingEntity {
             jumping_cooldown: AtomicU8::new(0),
             climbing: AtomicBool::new(false),
             climbing_pos: AtomicCell::new(None),
+            last_attacker: AtomicCell::new(None),
             movement_input: AtomicCell::new(Vector3::default()),
             movement_speed: AtomicCell::new(default_movement_speed),
             water_movement_speed_multiplier,
@@ -145,6 +163,16 @@ impl LivingEntity {
             .await;
     }
 
+    /// Puts `stack` into `slot` and shows the change to everyone else.
+    pub async fn equip(&self, slot: EquipmentSlot, stack: ItemStack) {
+        self.entity_equipment
+            .lock()
+            .await
+            .put(&slot, stack.clone())
+            .await;
+        self.send_equipment_changes(&[(slot, stack)]).await;
+    }
+
     /// Picks up and Item entity or XP Orb
     pub async fn pickup(&self, item: &Entity, stack_amount: u32) {
         // TODO: Only nearby
@@ -245,6 +273,68 @@ impl LivingEntity {
         self.entity.world.get_block(&block_pos).await == &Block::POWDER_SNOW
     }
 
+    /// The level of `enchantment` on the item worn in `slot`.
+    pub async fn get_equipment_enchantment_level(
+        &self,
+        slot: &EquipmentSlot,
+        enchantment: &Enchantment,
+    ) -> i32 {
+        let stack = self.entity_equipment.lock().await.get(slot);
+        stack.lock().await.get_enchantment_level(enchantment)
+    }
+
+    /// Whether the entity can stay under water without running out of air.
+    async fn can_breathe_in_water(&self, caller: &Arc<dyn EntityBase>) -> bool {
+        if MINECRAFT_CAN_BREATHE_UNDER_WATER
+            .1
+            .contains(&self.entity.entity_type.id)
+            || self.has_effect(&StatusEffect::WATER_BREATHING).await
+            || self.has_effect(&StatusEffect::CONDUIT_POWER).await
+        {
+            return true;
+        }
+        if let Some(player) = caller.get_player() {
+            return player.abilities.lock().await.invulnerable;
+        }
+        false
+    }
+
+    /// Uses up air while under water and drowns once there is none left, refills it otherwise.
+    async fn tick_air(&self, caller: &Arc<dyn EntityBase>) {
+        if !self.entity.is_alive() {
+            return;
+        }
+        let air = self.entity.air.load(Relaxed);
+        // TODO: Bubble columns refill air
+        if self.entity.submerged_in_water.load(Relaxed) {
+            if self.can_breathe_in_water(caller).await {
+                return;
+            }
+            let respiration = self
+                .get_equipment_enchantment_level(&EquipmentSlot::HEAD, &Enchantment::RESPIRATION)
+                .await;
+            // Each level of respiration gives a chance to not use up air this tick
+            let keep_air = respiration > 0 && rand::rng().random_range(0..=respiration) > 0;
+            let air = if keep_air { air } else { air - 1 };
+            if air == -20 {
+                self.entity.set_air(0).await;
+                self.damage(caller.clone(), 2.0, DamageType::DROWN).await;
+            } else {
+                self.entity.set_air(air).await;
+            }
+        } else if air < Entity::MAX_AIR {
+            self.entity.set_air((air + 4).min(Entity::MAX_AIR)).await;
+        }
+    }
+
+    /// How much depth strider boots reduce the drag of water, from 0 to 1.
+    async fn get_water_movement_efficiency(&self) -> f64 {
+        let level = self
+            .get_equipment_enchantment_level(&EquipmentSlot::FEET, &Enchantment::DEPTH_STRIDER)
+            .await;
+        (f64::from(level) / 3.0).min(1.0)
+    }
+
     async fn get_effective_gravity(&self, caller: &Arc<dyn EntityBase>) -> f64 {
         let final_gravity = caller.get_gravity();
 
@@ -320,6 +410,10 @@ impl LivingEntity {
             self.fall_distance.store(0.0);
         }
 
+        if self.entity.swimming.load(Relaxed) && caller.get_player().is_some() {
+            self.swim_towards_look_direction().await;
+        }
+
         let touching_water = self.entity.touching_water.load(SeqCst);
 
         // Strider is the only entity that has canWalkOnFluid = false
@@ -335,9 +429,9 @@ impl LivingEntity {
             self.travel_in_air(caller.clone()).await;
         }
 
-        //self.entity.tick_block_underneath(&caller);
-
-        let suffocating = self.entity.tick_block_collisions(&caller, server).await;
+        // Spectators pass through blocks without triggering them
+        let suffocating = !self.entity.no_clip.load(Relaxed)
+            && self.entity.tick_block_collisions(&caller, server).await;
 
         if suffocating {
             self.damage(caller, 1.0, DamageType::IN_WALL).await;
@@ -436,7 +530,7 @@ impl LivingEntity {
 
             let mut speed = 0.02;
 
-            let mut water_movement_efficiency = 0.0; // TODO: Entity attribute
+            let mut water_movement_efficiency = self.get_water_movement_efficiency().await;
 
             if water_movement_efficiency > 0.0 {
                 if !self.entity.on_ground.load(SeqCst) {
@@ -511,6 +605,27 @@ impl LivingEntity {
         }
     }
 
+    /// Swimming players rise and dive in the direction they are looking.
+    async fn swim_towards_look_direction(&self) {
+        // TODO: Not while riding
+        let look_y = -f64::from(self.entity.pitch.load().to_radians().sin());
+        let acceleration = if look_y < -0.2 { 0.085 } else { 0.06 };
+        let pos = self.entity.pos.load();
+        let above = BlockPos::new(
+            pos.x.floor() as i32,
+            (pos.y + 0.9).floor() as i32,
+            pos.z.floor() as i32,
+        );
+        if look_y <= 0.0
+            || self.jumping.load(SeqCst)
+            || self.entity.world.get_fluid(&above).await.id != Fluid::EMPTY.id
+        {
+            let mut velo = self.entity.velocity.load();
+            velo.y += (look_y - velo.y) * acceleration;
+            self.entity.velocity.store(velo);
+        }
+    }
+
     fn apply_fluid_moving_speed(&self, dy: &mut f64, gravity: f64, falling: bool) {
         if gravity != 0.0 && !self.entity.sprinting.load(Relaxed) {
             if falling && (*dy - 0.005).abs() >= 0.003 && (*dy - gravity / 16.0).abs() < 0.003 {
@@ -699,13 +814,27 @@ impl LivingEntity {
                 return;
             }
 
+            let (pos, block, _state) = self.entity.get_block_with_y_offset(0.2).await;
+            let fall_damage = self
+                .entity
+                .world
+                .block_registry
+                .on_landed_upon(
+                    block,
+                    &self.entity.world,
+                    caller.as_ref(),
+                    &pos,
+                    fall_distance,
+                )
+                .await;
+
             let safe_fall_distance = 3.0;
-            let mut damage = fall_distance - safe_fall_distance;
-            damage = damage.ceil();
+            let damage =
+                ((fall_damage.fall_distance - safe_fall_distance) * fall_damage.multiplier).ceil();
 
             // TODO: Play block fall sound
             if damage > 0.0 {
-                let check_damage = self.damage(caller, damage, DamageType::FALL).await; // Fall
+                let check_damage = self.damage(caller, damage, fall_damage.damage_type).await;
                 if check_damage {
                     self.entity
                         .play_sound(Self::get_fall_sound(fall_distance as i32))
@@ -734,45 +863,156 @@ impl LivingEntity {
         }
     }
 
+    /// Builds the translatable death message for `dyn_self`.
+    ///
+    /// `source` is the entity that directly dealt the damage (e.g. an arrow) and `cause` the entity
+    /// responsible for it (e.g. the skeleton that shot the arrow).
     pub async fn get_death_message(
         dyn_self: &dyn EntityBase,
         damage_type: DamageType,
         source: Option<&dyn EntityBase>,
         cause: Option<&dyn EntityBase>,
     ) -> TextComponent {
+        let victim = dyn_self.get_display_name().await;
         match damage_type.death_message_type {
             DeathMessageType::Default => {
-                if let Some(cause) = cause
-                    && source.is_some()
-                {
+                let key = format!("death.attack.{}", damage_type.message_id);
+                if let Some(attacker) = cause.or(source) {
+                    Self::attacker_death_message(key, victim, attacker).await
+                } else if let Some(adversary) = Self::get_prime_adversary(dyn_self).await {
                     TextComponent::translate(
-                        format!("death.attack.{}.player", damage_type.message_id),
-                        [
-                            dyn_self.get_display_name().await,
-                            cause.get_display_name().await,
-                        ],
+                        format!("{key}.player"),
+                        [victim, adversary.get_display_name().await],
                     )
                 } else {
-                    TextComponent::translate(
-                        format!("death.attack.{}", damage_type.message_id),
-                        [dyn_self.get_display_name().await],
-                    )
+                    TextComponent::translate(key, [victim])
                 }
             }
             DeathMessageType::FallVariants => {
-                //TODO
-                TextComponent::translate(
-                    "death.fell.accident.generic",
-                    [dyn_self.get_display_name().await],
-                )
+                let key = String::from("death.fell.assist");
+                if let Some(attacker) = cause.or(source) {
+                    Self::attacker_death_message(key, victim, attacker).await
+                } else if let Some(adversary) = Self::get_prime_adversary(dyn_self).await {
+                    Self::attacker_death_message(key, victim, &*adversary).await
+                } else {
+                    let location = match dyn_self.get_living_entity() {
+                        Some(living) => living.get_fall_location().await,
+                        None => "generic",
+                    };
+                    TextComponent::translate(format!("death.fell.accident.{location}"), [victim])
+                }
+            }
+            DeathMessageType::IntentionalGameDesign => TextComponent::translate(
+                format!("death.attack.{}.message", damage_type.message_id),
+                [
+                    victim,
+                    TextComponent::text("[")
+                        .add_child(TextComponent::translate(
+                            "death.attack.badRespawnPoint.link",
+                            [],
+                        ))
+                        .add_child(TextComponent::text("]"))
+                        .click_event(ClickEvent::OpenUrl {
+                            url: Cow::Borrowed("https://bugs.mojang.com/browse/MCPE-28723"),
+                        })
+                        .hover_event(HoverEvent::show_text(TextComponent::text("MCPE-28723"))),
+                ],
+            ),
+        }
+    }
+
+    /// Death message naming the attacker, and the weapon if the attacker holds a renamed item.
+    async fn attacker_death_message(
+        key: String,
+        victim: TextComponent,
+        attacker: &dyn EntityBase,
+    ) -> TextComponent {
+        let attacker_name = attacker.get_display_name().await;
+        if let Some(player) = attacker.get_player() {
+            let held_item = player.inventory.held_item();
+            let held_item = held_item.lock().await;
+            if !held_item.is_empty()
+                && let Some(custom_name) = held_item.get_data_component::<CustomNameImpl>()
+            {
+                let item = TextComponent::text("[")
+                    .add_child(custom_name.name.clone().italic())
+                    .add_child(TextComponent::text("]"))
+                    .hover_event(HoverEvent::ShowItem {
+                        id: Cow::Owned(format!("minecraft:{}", held_item.item.registry_key)),
+                        count: Some(i32::from(held_item.item_count)),
+                    });
+                return TextComponent::translate(
+                    format!("{key}.item"),
+                    [victim, attacker_name, item],
+                );
             }
-            DeathMessageType::IntentionalGameDesign => TextComponent::text("[")
-                .add_child(TextComponent::translate(
-                    format!("death.attack.{}.message", damage_type.message_id),
-                    [dyn_self.get_display_name().await],
-                ))
-                .add_child(TextComponent::text("]")),
         }
+        TextComponent::translate(key, [victim, attacker_name])
+    }
+
+    /// The entity that recently attacked `dyn_self`, used to credit deaths without a direct attacker.
+    async fn get_prime_adversary(dyn_self: &dyn EntityBase) -> Option<Arc<dyn EntityBase>> {
+        let living = dyn_self.get_living_entity()?;
+        let (attacker_id, attacked_age) = living.last_attacker.load()?;
+        if living.entity.age.load(Relaxed) - attacked_age > Self::ATTACKER_MEMORY_TICKS {
+            return None;
+        }
+        living.entity.world.get_entity_by_id(attacker_id).await
+    }
+
+    /// Where the entity was when it started falling, as used in `death.fell.accident.*`.
+    async fn get_fall_location(&self) -> &'static str {
+        let Some(pos) = self.climbing_pos.load() else {
+            return "generic";
+        };
+        let block = self.entity.world.get_block(&pos).await;
+        if block == &Block::LADDER {
+            "ladder"
+        } else if block == &Block::VINE {
+            "vines"
+        } else if block == &Block::WEEPING_VINES || block == &Block::WEEPING_VINES_PLANT {
+            "weeping_vines"
+        } else if block == &Block::TWISTING_VINES || block == &Block::TWISTING_VINES_PLANT {
+            "twisting_vines"
+        } else if block == &Block::SCAFFOLDING {
+            "scaffolding"
+        } else {
+            "other_climbable"
+        }
+    }
+
+    /// Lets plugins change or cancel damage about to be dealt to `caller`. Returns the damage to
+    /// deal, or `None` if it was cancelled.
+    async fn fire_damage_event(
+        &self,
+        caller: Arc<dyn EntityBase>,
+        amount: f32,
+        damage_type: DamageType,
+        source: Option<&dyn EntityBase>,
+        cause: Option<&dyn EntityBase>,
+    ) -> Option<f32> {
+        if !PLUGIN_MANAGER.has_listeners::<EntityDamageEvent>().await {
+            return Some(amount);
+        }
+        let world = &self.entity.world;
+        let source = match source {
+            Some(source) => world.get_entity_by_id(source.get_entity().entity_id).await,
+            None => None,
+        };
+        let cause = match cause {
+            Some(cause) => world.get_entity_by_id(cause.get_entity().entity_id).await,
+            None => None,
+        };
+        let event = PLUGIN_MANAGER
+            .fire(EntityDamageEvent::new(
+                caller,
+                amount,
+                damage_type,
+                source,
+                cause,
+            ))
+            .await;
+        (!event.cancelled && event.damage >= 0.0).then_some(event.damage)
     }
 
     pub async fn on_death(
@@ -806,6 +1046,12 @@ impl LivingEntity {
             self.drop_loot(params).await;
             self.entity.pose.store(EntityPose::Dying);
 
+            if let Some(player) = cause.or(source).and_then(|attacker| attacker.get_player())
+                && raid::is_raid_captain(self).await
+            {
+                raid::on_raid_captain_killed(player).await;
+            }
+
             let level_info = world.level_info.read().await;
             let game_rules = &level_info.game_rules;
             if self.entity.entity_type == &EntityType::PLAYER && game_rules.show_death_messages {
@@ -867,7 +1113,7 @@ impl LivingEntity {
         false
     }
 
-    pub async fn held_item(&self, caller: &Arc<dyn EntityBase>) -> Arc<Mutex<ItemStack>> {
+    pub async fn held_item(&self, caller: &Arc<dyn EntityBase>) -> Arc<OrderedMutex<ItemStack>> {
         if let Some(player) = caller.get_player() {
             return player.inventory.held_item();
         }
@@ -883,7 +1129,7 @@ impl LivingEntity {
         &self,
         caller: &Arc<dyn EntityBase>,
         hand: Hand,
-    ) -> Arc<Mutex<ItemStack>> {
+    ) -> Arc<OrderedMutex<ItemStack>> {
         match hand {
             Hand::Left => self.off_hand_item().await,
             Hand::Right => self.held_item(caller).await,
@@ -891,7 +1137,7 @@ impl LivingEntity {
     }
 
     /// getOffHandStack in source
-    pub async fn off_hand_item(&self) -> Arc<Mutex<ItemStack>> {
+    pub async fn off_hand_item(&self) -> Arc<OrderedMutex<ItemStack>> {
         let slot = self
             .equipment_slots
             .get(&PlayerInventory::OFF_HAND_SLOT)
@@ -900,7 +1146,7 @@ impl LivingEntity {
     }
 
     pub fn is_part_of_game(&self) -> bool {
-        self.is_spectator() && self.entity.is_alive()
+        !self.is_spectator() && self.entity.is_alive()
     }
 
     pub async fn reset_state(&self) {
@@ -990,6 +1236,13 @@ impl EntityBase for LivingEntity {
             return false;
         }
 
+        let Some(amount) = self
+            .fire_damage_event(caller.clone(), amount, damage_type, source, cause)
+            .await
+        else {
+            return false;
+        };
+
         if (damage_type == DamageType::IN_FIRE || damage_type == DamageType::ON_FIRE)
             && self.has_effect(&StatusEffect::FIRE_RESISTANCE).await
         {
@@ -1028,12 +1281,21 @@ impl EntityBase for LivingEntity {
             .broadcast_packet_all(&CDamageEvent::new(
                 self.entity.entity_id.into(),
                 damage_type.id.into(),
+                cause.or(source).map(|e| e.get_entity().entity_id.into()),
                 source.map(|e| e.get_entity().entity_id.into()),
-                cause.map(|e| e.get_entity().entity_id.into()),
                 position,
             ))
             .await;
 
+        if let Some(attacker) = cause.or(source)
+            && attacker.get_living_entity().is_some()
+        {
+            self.last_attacker.store(Some((
+                attacker.get_entity().entity_id,
+                self.entity.age.load(Relaxed),
+            )));
+        }
+
         if play_sound {
             self.entity
                 .world
@@ -1065,11 +1327,27 @@ impl EntityBase for LivingEntity {
         GRAVITY
     }
 
+    // TODO: get from entity attribute
+    fn get_step_height(&self) -> f64 {
+        let entity_type = self.entity.entity_type;
+        if entity_type == &EntityType::HORSE
+            || entity_type == &EntityType::DONKEY
+            || entity_type == &EntityType::MULE
+            || entity_type == &EntityType::SKELETON_HORSE
+            || entity_type == &EntityType::ZOMBIE_HORSE
+        {
+            1.0
+        } else {
+            0.6
+        }
+    }
+
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         self.entity.tick(caller.clone(), server).await;
+        self.tick_air(&caller).await;
         self.tick_movement(server, caller.clone()).await;
         // TODO
-        if caller.get_player().is_none() {
+        if caller.get_player().is_none() && self.entity.is_movement_update_tick() {
             self.entity.send_pos_rot().await;
             self.entity.send_velocity().await;
         }
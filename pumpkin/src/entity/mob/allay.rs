@@ -0,0 +1,695 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicI32, AtomicI64, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::{
+    Block,
+    block_properties::{BlockProperties, JukeboxLikeProperties},
+    data_component_impl::EquipmentSlot,
+    entity::{EntityStatus, EntityType},
+    game_event::GameEvent,
+    sound::{Sound, SoundCategory},
+    tag::{self, Taggable},
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::{Mob, MobEntity};
+use crate::entity::ai::goal::{Control, Goal, GoalControl};
+use crate::entity::ai::path::NavigatorGoal;
+use crate::entity::item::ItemEntity;
+use crate::entity::player::Player;
+use crate::entity::r#type::from_type;
+use crate::entity::{Entity, EntityBase, NBTStorage, uuid_from_nbt, uuid_to_nbt};
+
+const DATA_DANCING: u8 = 16;
+const DATA_CAN_DUPLICATE: u8 = 17;
+
+const EQUIPMENT: &str = "equipment";
+const MAINHAND: &str = "mainhand";
+const INVENTORY: &str = "Inventory";
+const LIKED_PLAYER: &str = "LikedPlayer";
+const DUPLICATION_COOLDOWN: &str = "DuplicationCooldown";
+
+/// How long an allay has to wait after duplicating before it can again, in ticks.
+const DUPLICATION_COOLDOWN_TICKS: i64 = 6000;
+/// How long an allay brings its items to a note block after hearing it, in ticks.
+const NOTE_BLOCK_COOLDOWN_TICKS: i32 = 600;
+/// How long an allay waits after throwing its items before it picks up any again, in ticks.
+const PICKUP_COOLDOWN_TICKS: i32 = 60;
+/// How far around itself an allay looks for items it wants.
+const ITEM_SEARCH_RANGE: f64 = 32.0;
+/// How close an allay has to get to an item to pick it up.
+const ITEM_REACH_DISTANCE: f64 = 1.5;
+/// How close an allay gets to whoever it brings its items to before throwing them.
+const DELIVERY_DISTANCE: f64 = 3.0;
+/// How close an allay with nothing to do stays to its player or note block.
+const FOLLOW_DISTANCE: f64 = 4.0;
+/// How far away an allay still dances to a jukebox.
+const JUKEBOX_RANGE: f64 = 10.0;
+/// How fast the items an allay throws fly.
+const THROW_SPEED: f64 = 0.3;
+/// Blocks per tick an allay flies when it is going somewhere.
+const FLYING_SPEED: f64 = 0.2;
+
+/// Collects items like the one a player gave it and brings them back to the player, or to the
+/// note block it likes while that is being played. Dancing allays duplicate when given an
+/// amethyst shard.
+pub struct Allay {
+    mob_entity: MobEntity,
+    /// The item the allay was given, it collects more of the same
+    held_item: Mutex<ItemStack>,
+    /// The items the allay collected and has yet to bring back
+    inventory: Mutex<ItemStack>,
+    /// The player who gave the allay its item
+    liked_player: AtomicCell<Option<Uuid>>,
+    /// The first note block the allay heard, it only listens to that one
+    liked_note_block: AtomicCell<Option<BlockPos>>,
+    /// Ticks the allay keeps bringing its items to the note block
+    note_block_ticks: AtomicI32,
+    /// The jukebox the allay is dancing to
+    jukebox: AtomicCell<Option<BlockPos>>,
+    duplication_cooldown: AtomicI64,
+    pickup_cooldown: AtomicI32,
+}
+
+impl Allay {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let allay = Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+            held_item: Mutex::new(ItemStack::EMPTY.clone()),
+            inventory: Mutex::new(ItemStack::EMPTY.clone()),
+            liked_player: AtomicCell::new(None),
+            liked_note_block: AtomicCell::new(None),
+            note_block_ticks: AtomicI32::new(0),
+            jukebox: AtomicCell::new(None),
+            duplication_cooldown: AtomicI64::new(0),
+            pickup_cooldown: AtomicI32::new(0),
+        });
+
+        let goal_selector = &allay.mob_entity.goals_selector;
+        goal_selector
+            .add_goal(0, Arc::new(CollectItemGoal::new()))
+            .await;
+        goal_selector
+            .add_goal(1, Arc::new(DeliverItemsGoal::new()))
+            .await;
+        goal_selector
+            .add_goal(2, Arc::new(StayCloseGoal::new()))
+            .await;
+
+        allay
+    }
+
+    pub fn is_dancing(&self) -> bool {
+        self.jukebox.load().is_some()
+    }
+
+    async fn set_jukebox(&self, jukebox: Option<BlockPos>) {
+        self.jukebox.store(jukebox);
+        self.mob_entity
+            .living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                DATA_DANCING,
+                MetaDataType::Boolean,
+                jukebox.is_some(),
+            )])
+            .await;
+    }
+
+    pub fn can_duplicate(&self) -> bool {
+        self.duplication_cooldown.load(Ordering::Relaxed) <= 0
+    }
+
+    async fn set_duplication_cooldown(&self, ticks: i64) {
+        self.duplication_cooldown.store(ticks, Ordering::Relaxed);
+        self.send_can_duplicate().await;
+    }
+
+    async fn send_can_duplicate(&self) {
+        self.mob_entity
+            .living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                DATA_CAN_DUPLICATE,
+                MetaDataType::Boolean,
+                self.can_duplicate(),
+            )])
+            .await;
+    }
+
+    async fn set_held_item(&self, stack: ItemStack) {
+        let mut held_item = self.held_item.lock().await;
+        *held_item = stack.clone();
+        self.show_held_item(stack).await;
+    }
+
+    /// Shows `stack` in the allay's hand. Called with `held_item` locked, so concurrent changes
+    /// can't show another item than the one held.
+    async fn show_held_item(&self, stack: ItemStack) {
+        self.mob_entity
+            .living_entity
+            .equip(EquipmentSlot::MAIN_HAND, stack)
+            .await;
+    }
+
+    /// Makes the note block at `position` the one the allay brings its items to, unless it
+    /// already likes another one.
+    fn hear_note_block(&self, position: BlockPos) {
+        match self.liked_note_block.load() {
+            None => self.liked_note_block.store(Some(position)),
+            Some(liked) if liked == position => {}
+            Some(_) => return,
+        }
+        self.note_block_ticks
+            .store(NOTE_BLOCK_COOLDOWN_TICKS, Ordering::Relaxed);
+    }
+
+    /// Where the allay brings its items: to the note block it likes while that is being played,
+    /// otherwise to the player who gave it its item.
+    async fn deposit_target(&self) -> Option<Vector3<f64>> {
+        let world = &self.mob_entity.living_entity.entity.world;
+        if let Some(note_block) = self.liked_note_block.load() {
+            if world.get_block(&note_block).await != &Block::NOTE_BLOCK {
+                self.liked_note_block.store(None);
+            } else if self.note_block_ticks.load(Ordering::Relaxed) > 0 {
+                return Some(note_block.to_centered_f64());
+            }
+        }
+        let player = world.get_player_by_uuid(self.liked_player.load()?).await?;
+        Some(player.living_entity.entity.pos.load())
+    }
+
+    /// Whether the allay would pick up `item`.
+    async fn wants(&self, item: &ItemEntity) -> bool {
+        if self.pickup_cooldown.load(Ordering::Relaxed) > 0 {
+            return false;
+        }
+        let held_item = self.held_item.lock().await;
+        !held_item.is_empty()
+            && item.is_same_item(&held_item).await
+            && item.fits_onto(&*self.inventory.lock().await).await
+    }
+
+    /// The closest item the allay wants.
+    async fn find_wanted_item(&self) -> Option<Arc<ItemEntity>> {
+        let entity = &self.mob_entity.living_entity.entity;
+        let position = entity.pos.load();
+        let mut closest: Option<(f64, Arc<ItemEntity>)> = None;
+        for other in entity
+            .world
+            .get_nearby_entities(position, ITEM_SEARCH_RANGE)
+            .await
+            .into_values()
+        {
+            let Some(item) = other.get_item_entity() else {
+                continue;
+            };
+            let distance = item
+                .get_entity()
+                .pos
+                .load()
+                .squared_distance_to_vec(position);
+            if closest
+                .as_ref()
+                .is_some_and(|(closest, _)| *closest <= distance)
+            {
+                continue;
+            }
+            if self.wants(&item).await {
+                closest = Some((distance, item));
+            }
+        }
+        closest.map(|(_, item)| item)
+    }
+
+    /// Throws everything the allay collected towards `target`.
+    async fn throw_items(&self, target: Vector3<f64>) {
+        let stack = std::mem::replace(&mut *self.inventory.lock().await, ItemStack::EMPTY.clone());
+        if stack.is_empty() {
+            return;
+        }
+        let entity = &self.mob_entity.living_entity.entity;
+        let position = entity.pos.load();
+        let origin = Vector3::new(position.x, entity.get_eye_y() - 0.3, position.z);
+        throw_item(entity, stack, origin, target).await;
+        self.pickup_cooldown
+            .store(PICKUP_COOLDOWN_TICKS, Ordering::Relaxed);
+    }
+
+    /// Spawns a copy of the allay next to it, both have to wait before they can duplicate again.
+    async fn duplicate(&self) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        let copy = from_type(&EntityType::ALLAY, entity.pos.load(), world, Uuid::new_v4()).await;
+        if let Some(allay) = copy.as_mob().and_then(Mob::get_allay) {
+            allay
+                .duplication_cooldown
+                .store(DUPLICATION_COOLDOWN_TICKS, Ordering::Relaxed);
+        }
+        world.spawn_entity(copy).await;
+        self.set_duplication_cooldown(DUPLICATION_COOLDOWN_TICKS)
+            .await;
+        world
+            .send_entity_status(entity, EntityStatus::AddBreedingParticles)
+            .await;
+        world
+            .play_sound_fine(
+                Sound::BlockAmethystBlockChime,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+                2.0,
+                1.0,
+            )
+            .await;
+    }
+
+    async fn fly_to(&self, destination: Vector3<f64>) {
+        let entity = &self.mob_entity.living_entity.entity;
+        self.mob_entity
+            .navigator
+            .lock()
+            .await
+            .set_progress(NavigatorGoal {
+                current_progress: entity.pos.load(),
+                destination,
+                speed: FLYING_SPEED,
+            });
+    }
+
+    fn distance_to(&self, position: Vector3<f64>) -> f64 {
+        self.mob_entity
+            .living_entity
+            .entity
+            .pos
+            .load()
+            .squared_distance_to_vec(position)
+            .sqrt()
+    }
+}
+
+/// Spawns `stack` at `origin` flying towards `target`, thrown by `thrower`.
+async fn throw_item(
+    thrower: &Entity,
+    stack: ItemStack,
+    origin: Vector3<f64>,
+    target: Vector3<f64>,
+) {
+    let direction = target.sub(&origin);
+    let velocity = if direction.length_squared() < 1.0e-7 {
+        Vector3::default()
+    } else {
+        direction.normalize() * THROW_SPEED
+    };
+    let item = ItemEntity::new_with_velocity(
+        Entity::new(
+            Uuid::new_v4(),
+            thrower.world.clone(),
+            origin,
+            &EntityType::ITEM,
+            false,
+        ),
+        stack,
+        velocity,
+        10,
+    )
+    .await;
+    item.set_thrower(Some(thrower.entity_uuid));
+    thrower.world.spawn_entity(Arc::new(item)).await;
+    thrower
+        .world
+        .play_sound(
+            Sound::EntityAllayItemThrown,
+            SoundCategory::Neutral,
+            &origin,
+        )
+        .await;
+}
+
+fn write_stack(stack: &ItemStack) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    stack.write_item_stack(&mut nbt);
+    nbt
+}
+
+#[async_trait]
+impl NBTStorage for Allay {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        let held_item = self.held_item.lock().await;
+        if !held_item.is_empty() {
+            // Living entities don't save their equipment yet
+            let mut equipment = NbtCompound::new();
+            equipment.put_component(MAINHAND, write_stack(&held_item));
+            nbt.put_component(EQUIPMENT, equipment);
+        }
+        drop(held_item);
+        let inventory = self.inventory.lock().await;
+        let items = if inventory.is_empty() {
+            Vec::new()
+        } else {
+            vec![NbtTag::Compound(write_stack(&inventory))]
+        };
+        nbt.put_list(INVENTORY, items);
+        drop(inventory);
+        if let Some(liked_player) = self.liked_player.load() {
+            nbt.put(LIKED_PLAYER, uuid_to_nbt(liked_player));
+        }
+        nbt.put_long(
+            DUPLICATION_COOLDOWN,
+            self.duplication_cooldown.load(Ordering::Relaxed),
+        );
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        if let Some(held_item) = nbt
+            .get_compound(EQUIPMENT)
+            .and_then(|equipment| equipment.get_compound(MAINHAND))
+            .and_then(ItemStack::read_item_stack)
+        {
+            self.set_held_item(held_item).await;
+        }
+        if let Some(stack) = nbt
+            .get_list(INVENTORY)
+            .and_then(<[NbtTag]>::first)
+            .and_then(|item| match item {
+                NbtTag::Compound(item) => ItemStack::read_item_stack(item),
+                _ => None,
+            })
+        {
+            *self.inventory.lock().await = stack;
+        }
+        self.liked_player
+            .store(nbt.get_int_array(LIKED_PLAYER).and_then(uuid_from_nbt));
+        self.duplication_cooldown.store(
+            nbt.get_long(DUPLICATION_COOLDOWN).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[async_trait]
+impl Mob for Allay {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    async fn mob_tick(&self) {
+        for ticks in [&self.note_block_ticks, &self.pickup_cooldown] {
+            if ticks.load(Ordering::Relaxed) > 0 {
+                ticks.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+        if self.duplication_cooldown.load(Ordering::Relaxed) > 0
+            && self.duplication_cooldown.fetch_sub(1, Ordering::Relaxed) == 1
+        {
+            self.send_can_duplicate().await;
+        }
+
+        // Stop dancing once the jukebox is out of earshot or no longer playing
+        if let Some(jukebox) = self.jukebox.load() {
+            let world = &self.mob_entity.living_entity.entity.world;
+            let (block, state_id) = world.get_block_and_state_id(&jukebox).await;
+            let playing = block == &Block::JUKEBOX
+                && JukeboxLikeProperties::from_state_id(state_id, block).has_record;
+            if !playing || self.distance_to(jukebox.to_centered_f64()) > JUKEBOX_RANGE {
+                self.set_jukebox(None).await;
+            }
+        }
+    }
+
+    async fn mob_init_data_tracker(&self) {
+        if self.is_dancing() {
+            self.set_jukebox(self.jukebox.load()).await;
+        }
+        self.send_can_duplicate().await;
+        let held_item = self.held_item.lock().await.clone();
+        if !held_item.is_empty() {
+            self.mob_entity
+                .living_entity
+                .send_equipment_changes(&[(EquipmentSlot::MAIN_HAND, held_item)])
+                .await;
+        }
+    }
+
+    async fn mob_interact(&self, player: &Player) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let held = player.inventory.held_item();
+        let mut player_stack = held.lock().await;
+        // Held until the item changed hands, so two players can't both take or give it
+        let mut allay_item = self.held_item.lock().await;
+
+        if self.is_dancing()
+            && self.can_duplicate()
+            && player_stack
+                .item
+                .is_tagged_with_by_tag(&tag::Item::MINECRAFT_DUPLICATES_ALLAYS)
+        {
+            player_stack.decrement_unless_creative(player.gamemode.load(), 1);
+            drop(player_stack);
+            drop(allay_item);
+            self.duplicate().await;
+        } else if allay_item.is_empty() && !player_stack.is_empty() {
+            let given = player_stack.copy_with_count(1);
+            player_stack.decrement_unless_creative(player.gamemode.load(), 1);
+            drop(player_stack);
+            *allay_item = given.clone();
+            self.show_held_item(given).await;
+            drop(allay_item);
+            self.liked_player.store(Some(player.gameprofile.id));
+            entity
+                .world
+                .play_sound_fine(
+                    Sound::EntityAllayItemGiven,
+                    SoundCategory::Neutral,
+                    &entity.pos.load(),
+                    2.0,
+                    1.0,
+                )
+                .await;
+        } else if !allay_item.is_empty() && player_stack.is_empty() {
+            *player_stack = std::mem::replace(&mut *allay_item, ItemStack::EMPTY.clone());
+            drop(player_stack);
+            self.show_held_item(ItemStack::EMPTY.clone()).await;
+            drop(allay_item);
+            self.liked_player.store(None);
+            // Whatever the allay collected falls to the ground where it is
+            let position = entity.pos.load();
+            let inventory =
+                std::mem::replace(&mut *self.inventory.lock().await, ItemStack::EMPTY.clone());
+            if !inventory.is_empty() {
+                throw_item(entity, inventory, position, position).await;
+            }
+            entity
+                .world
+                .play_sound_fine(
+                    Sound::EntityAllayItemTaken,
+                    SoundCategory::Neutral,
+                    &position,
+                    2.0,
+                    1.0,
+                )
+                .await;
+        }
+    }
+
+    async fn on_game_event(&self, event: &GameEvent, position: &BlockPos) {
+        match event {
+            GameEvent::NoteBlockPlay => self.hear_note_block(*position),
+            GameEvent::JukeboxPlay => self.set_jukebox(Some(*position)).await,
+            _ => {}
+        }
+    }
+
+    fn get_allay(&self) -> Option<&Allay> {
+        Some(self)
+    }
+}
+
+/// Flies to the closest item like the one the allay holds and picks it up.
+struct CollectItemGoal {
+    goal_control: GoalControl,
+    item: Mutex<Option<Arc<ItemEntity>>>,
+}
+
+impl CollectItemGoal {
+    fn new() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move]),
+            item: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for CollectItemGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(allay) = mob.get_allay() else {
+            return false;
+        };
+        let item = allay.find_wanted_item().await;
+        let found = item.is_some();
+        *self.item.lock().await = item;
+        found
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        let Some(allay) = mob.get_allay() else {
+            return false;
+        };
+        let item = self.item.lock().await;
+        match item.as_ref() {
+            Some(item) => {
+                !item.get_entity().removed.load(Ordering::Relaxed) && allay.wants(item).await
+            }
+            None => false,
+        }
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {}
+
+    async fn stop(&self, mob: &dyn Mob) {
+        mob.get_mob_entity().navigator.lock().await.cancel();
+        *self.item.lock().await = None;
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(allay) = mob.get_allay() else {
+            return;
+        };
+        let Some(item) = self.item.lock().await.clone() else {
+            return;
+        };
+        let position = item.get_entity().pos.load();
+        if allay.distance_to(position) > ITEM_REACH_DISTANCE {
+            allay.fly_to(position).await;
+            return;
+        }
+        item.pick_up_into(
+            &allay.mob_entity.living_entity,
+            &mut *allay.inventory.lock().await,
+        )
+        .await;
+    }
+
+    fn should_run_every_tick(&self) -> bool {
+        true
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
+
+/// Brings the collected items to the player or note block and throws them there.
+struct DeliverItemsGoal {
+    goal_control: GoalControl,
+}
+
+impl DeliverItemsGoal {
+    fn new() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move]),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for DeliverItemsGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(allay) = mob.get_allay() else {
+            return false;
+        };
+        !allay.inventory.lock().await.is_empty() && allay.deposit_target().await.is_some()
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        self.can_start(mob).await
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {}
+
+    async fn stop(&self, mob: &dyn Mob) {
+        mob.get_mob_entity().navigator.lock().await.cancel();
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(allay) = mob.get_allay() else {
+            return;
+        };
+        let Some(target) = allay.deposit_target().await else {
+            return;
+        };
+        if allay.distance_to(target) > DELIVERY_DISTANCE {
+            allay.fly_to(target).await;
+        } else {
+            allay.throw_items(target).await;
+        }
+    }
+
+    fn should_run_every_tick(&self) -> bool {
+        true
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
+
+/// Keeps the allay close to the player or note block while it has nothing else to do.
+struct StayCloseGoal {
+    goal_control: GoalControl,
+}
+
+impl StayCloseGoal {
+    fn new() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move]),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for StayCloseGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(allay) = mob.get_allay() else {
+            return false;
+        };
+        allay
+            .deposit_target()
+            .await
+            .is_some_and(|target| allay.distance_to(target) > FOLLOW_DISTANCE)
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        self.can_start(mob).await
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {}
+
+    async fn stop(&self, mob: &dyn Mob) {
+        mob.get_mob_entity().navigator.lock().await.cancel();
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(allay) = mob.get_allay() else {
+            return;
+        };
+        if let Some(target) = allay.deposit_target().await {
+            allay.fly_to(target).await;
+        }
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
@@ -0,0 +1,626 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicI32, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::{
+    Block,
+    sound::{Sound, SoundCategory},
+    tag::{self, Taggable},
+    world::WorldEvent,
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::entities::{BlockEntity, beehive::BeehiveBlockEntity};
+use pumpkin_world::poi::PoiType;
+use pumpkin_world::world::BlockFlags;
+use rand::{Rng, rng};
+
+use super::{Mob, MobEntity};
+use crate::entity::ai::goal::melee_attack_goal::MeleeAttackGoal;
+use crate::entity::ai::goal::{Control, Goal, GoalControl};
+use crate::entity::ai::path::NavigatorGoal;
+use crate::entity::{Entity, NBTStorage};
+use crate::world::World;
+
+/// The bee's flags in its metadata, only the nectar flag is used for now.
+const DATA_FLAGS: u8 = 17;
+const HAS_NECTAR_FLAG: i8 = 0x08;
+
+const HAS_NECTAR: &str = "HasNectar";
+const HIVE_POS: &str = "hive_pos";
+const FLOWER_POS: &str = "flower_pos";
+const CANNOT_ENTER_HIVE_TICKS: &str = "CannotEnterHiveTicks";
+const TICKS_SINCE_POLLINATION: &str = "TicksSincePollination";
+const CROPS_GROWN_SINCE_POLLINATION: &str = "CropsGrownSincePollination";
+
+/// How far around itself a bee without a hive looks for one.
+const HIVE_SEARCH_RANGE: i32 = 20;
+/// How often a bee without a hive looks for one, in ticks.
+const HIVE_SEARCH_INTERVAL: i32 = 200;
+/// How close a bee has to get to its hive to go in.
+const HIVE_ENTER_DISTANCE: f64 = 2.0;
+/// How far around itself a bee looks for flowers, horizontally and vertically.
+const FLOWER_SEARCH_RANGE: (i32, i32) = (5, 2);
+/// How often a bee that found no flower looks again, in ticks.
+const FLOWER_SEARCH_INTERVAL: i32 = 20;
+/// How close a bee has to get to a flower to collect its nectar.
+const FLOWER_REACH_DISTANCE: f64 = 1.5;
+/// How long a bee stays at a flower to collect its nectar.
+const POLLINATION_TICKS: i32 = 400;
+/// A bee that didn't reach its flower in this many ticks looks for another one.
+const MAX_POLLINATION_TRYING_TICKS: i32 = 600;
+/// How long a bee waits after pollinating before it looks for flowers again.
+const POLLINATION_COOLDOWN: i32 = 600;
+/// A bee grows at most this many crops with the nectar of one flower.
+const MAX_CROPS_GROWN: i32 = 10;
+/// How many blocks below itself a bee grows crops.
+const CROP_GROWTH_RANGE: i32 = 2;
+/// Blocks per tick a bee flies when it is going somewhere.
+const FLYING_SPEED: f64 = 0.15;
+
+pub struct Bee {
+    mob_entity: MobEntity,
+    has_nectar: AtomicBool,
+    /// The hive the bee goes back to, it looks for one while it has none
+    pub hive_pos: AtomicCell<Option<BlockPos>>,
+    /// The flower the bee pollinated last
+    pub flower_pos: AtomicCell<Option<BlockPos>>,
+    /// Ticks until the bee may go back into a hive
+    pub cannot_enter_hive_ticks: AtomicI32,
+    ticks_since_pollination: AtomicI32,
+    ticks_until_can_pollinate: AtomicI32,
+    crops_grown_since_pollination: AtomicI32,
+}
+
+impl Bee {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let bee = Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+            has_nectar: AtomicBool::new(false),
+            hive_pos: AtomicCell::new(None),
+            flower_pos: AtomicCell::new(None),
+            cannot_enter_hive_ticks: AtomicI32::new(0),
+            ticks_since_pollination: AtomicI32::new(0),
+            ticks_until_can_pollinate: AtomicI32::new(0),
+            crops_grown_since_pollination: AtomicI32::new(0),
+        });
+
+        let goal_selector = &bee.mob_entity.goals_selector;
+        goal_selector
+            .add_goal(0, Arc::new(MeleeAttackGoal::new(FLYING_SPEED, false)))
+            .await;
+        goal_selector
+            .add_goal(1, Arc::new(EnterHiveGoal::new()))
+            .await;
+        goal_selector
+            .add_goal(4, Arc::new(GrowCropGoal::default()))
+            .await;
+        goal_selector
+            .add_goal(5, Arc::new(PollinateGoal::new()))
+            .await;
+
+        bee
+    }
+
+    pub fn has_nectar(&self) -> bool {
+        self.has_nectar.load(Ordering::Relaxed)
+    }
+
+    pub async fn set_has_nectar(&self, has_nectar: bool) {
+        self.has_nectar.store(has_nectar, Ordering::Relaxed);
+        if has_nectar {
+            self.ticks_since_pollination.store(0, Ordering::Relaxed);
+            self.crops_grown_since_pollination
+                .store(0, Ordering::Relaxed);
+        }
+        self.send_flags().await;
+    }
+
+    async fn send_flags(&self) {
+        let flags = if self.has_nectar() {
+            HAS_NECTAR_FLAG
+        } else {
+            0
+        };
+        self.mob_entity
+            .living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(DATA_FLAGS, MetaDataType::Byte, flags)])
+            .await;
+    }
+
+    /// The hive the bee belongs to with its block entity, `None` if it has none or its hive is
+    /// gone.
+    async fn get_hive(&self) -> Option<(BlockPos, Arc<dyn BlockEntity>)> {
+        let hive_pos = self.hive_pos.load()?;
+        let world = &self.mob_entity.living_entity.entity.world;
+        let hive = get_beehive(world, &hive_pos).await;
+        if hive.is_none() {
+            self.hive_pos.store(None);
+        }
+        hive.map(|hive| (hive_pos, hive))
+    }
+
+    /// Whether the bee would rather be inside its hive right now.
+    async fn wants_to_enter_hive(&self) -> bool {
+        if self.cannot_enter_hive_ticks.load(Ordering::Relaxed) > 0
+            || self.mob_entity.target.lock().await.is_some()
+        {
+            return false;
+        }
+        if self.has_nectar() {
+            return true;
+        }
+        let world = &self.mob_entity.living_entity.entity.world;
+        world.level_time.lock().await.is_night() || world.weather.lock().await.raining
+    }
+
+    /// Looks for a hive with room to move into.
+    async fn find_hive(&self) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        let candidates = world
+            .get_loaded_pois_in_range(&entity.block_pos.load(), HIVE_SEARCH_RANGE, |record| {
+                matches!(record.poi_type, PoiType::Beehive | PoiType::BeeNest)
+            })
+            .await;
+        let mut candidates: Vec<_> = candidates.into_iter().map(|record| record.pos).collect();
+        let position = entity.block_pos.load();
+        candidates.sort_unstable_by_key(|pos| pos.squared_distance(position));
+        for candidate in candidates {
+            if let Some(block_entity) = get_beehive(world, &candidate).await
+                && let Some(hive) = block_entity.as_any().downcast_ref::<BeehiveBlockEntity>()
+                && !hive.is_full().await
+            {
+                self.hive_pos.store(Some(candidate));
+                return;
+            }
+        }
+    }
+
+    async fn fly_to(&self, destination: &BlockPos) {
+        let entity = &self.mob_entity.living_entity.entity;
+        self.mob_entity
+            .navigator
+            .lock()
+            .await
+            .set_progress(NavigatorGoal {
+                current_progress: entity.pos.load(),
+                destination: destination.to_centered_f64(),
+                speed: FLYING_SPEED,
+            });
+    }
+
+    fn distance_to(&self, position: &BlockPos) -> f64 {
+        self.mob_entity
+            .living_entity
+            .entity
+            .pos
+            .load()
+            .squared_distance_to_vec(position.to_centered_f64())
+            .sqrt()
+    }
+}
+
+/// The block entity at `position` if it is a beehive's.
+async fn get_beehive(world: &World, position: &BlockPos) -> Option<Arc<dyn BlockEntity>> {
+    world
+        .get_block_entity(position)
+        .await
+        .filter(|block_entity| block_entity.as_any().is::<BeehiveBlockEntity>())
+}
+
+fn read_block_pos(nbt: &NbtCompound, key: &str) -> Option<BlockPos> {
+    match nbt.get_int_array(key)? {
+        [x, y, z] => Some(BlockPos::new(*x, *y, *z)),
+        _ => None,
+    }
+}
+
+fn write_block_pos(nbt: &mut NbtCompound, key: &str, pos: Option<BlockPos>) {
+    if let Some(pos) = pos {
+        nbt.put(key, NbtTag::IntArray(vec![pos.0.x, pos.0.y, pos.0.z]));
+    }
+}
+
+#[async_trait]
+impl NBTStorage for Bee {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        nbt.put_bool(HAS_NECTAR, self.has_nectar());
+        write_block_pos(nbt, HIVE_POS, self.hive_pos.load());
+        write_block_pos(nbt, FLOWER_POS, self.flower_pos.load());
+        nbt.put_int(
+            CANNOT_ENTER_HIVE_TICKS,
+            self.cannot_enter_hive_ticks.load(Ordering::Relaxed),
+        );
+        nbt.put_int(
+            TICKS_SINCE_POLLINATION,
+            self.ticks_since_pollination.load(Ordering::Relaxed),
+        );
+        nbt.put_int(
+            CROPS_GROWN_SINCE_POLLINATION,
+            self.crops_grown_since_pollination.load(Ordering::Relaxed),
+        );
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        self.has_nectar
+            .store(nbt.get_bool(HAS_NECTAR).unwrap_or(false), Ordering::Relaxed);
+        self.hive_pos.store(read_block_pos(nbt, HIVE_POS));
+        self.flower_pos.store(read_block_pos(nbt, FLOWER_POS));
+        self.cannot_enter_hive_ticks.store(
+            nbt.get_int(CANNOT_ENTER_HIVE_TICKS).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+        self.ticks_since_pollination.store(
+            nbt.get_int(TICKS_SINCE_POLLINATION).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+        self.crops_grown_since_pollination.store(
+            nbt.get_int(CROPS_GROWN_SINCE_POLLINATION).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[async_trait]
+impl Mob for Bee {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    async fn mob_tick(&self) {
+        for ticks in [
+            &self.cannot_enter_hive_ticks,
+            &self.ticks_until_can_pollinate,
+        ] {
+            if ticks.load(Ordering::Relaxed) > 0 {
+                ticks.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+        if self.has_nectar() {
+            self.ticks_since_pollination.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let age = self
+            .mob_entity
+            .living_entity
+            .entity
+            .age
+            .load(Ordering::Relaxed);
+        if age % HIVE_SEARCH_INTERVAL == 0 && self.get_hive().await.is_none() {
+            self.find_hive().await;
+        }
+    }
+
+    async fn mob_init_data_tracker(&self) {
+        self.send_flags().await;
+    }
+
+    fn get_bee(&self) -> Option<&Bee> {
+        Some(self)
+    }
+}
+
+/// Flies back to the hive and goes in, with nectar to make honey or to sit out the night and
+/// rain.
+struct EnterHiveGoal {
+    goal_control: GoalControl,
+}
+
+impl EnterHiveGoal {
+    fn new() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move]),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for EnterHiveGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(bee) = mob.get_bee() else {
+            return false;
+        };
+        bee.wants_to_enter_hive().await && bee.get_hive().await.is_some()
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        self.can_start(mob).await
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {}
+
+    async fn stop(&self, mob: &dyn Mob) {
+        mob.get_mob_entity().navigator.lock().await.cancel();
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(bee) = mob.get_bee() else {
+            return;
+        };
+        let Some((hive_pos, block_entity)) = bee.get_hive().await else {
+            return;
+        };
+        if bee.distance_to(&hive_pos) > HIVE_ENTER_DISTANCE {
+            bee.fly_to(&hive_pos).await;
+            return;
+        }
+
+        let entity = &bee.mob_entity.living_entity.entity;
+        let Some(hive) = block_entity.as_any().downcast_ref::<BeehiveBlockEntity>() else {
+            return;
+        };
+        let mut nbt = NbtCompound::new();
+        bee.write_nbt(&mut nbt).await;
+        if hive.add_bee(nbt, bee.flower_pos.load()).await {
+            entity
+                .world
+                .play_sound(
+                    Sound::BlockBeehiveEnter,
+                    SoundCategory::Blocks,
+                    &hive_pos.to_centered_f64(),
+                )
+                .await;
+            entity.remove().await;
+        } else {
+            // Someone else took the last spot, look for another hive
+            bee.hive_pos.store(None);
+        }
+    }
+
+    fn should_run_every_tick(&self) -> bool {
+        true
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
+
+/// Flies to a flower nearby and stays there until it collected the nectar.
+struct PollinateGoal {
+    goal_control: GoalControl,
+    flower: AtomicCell<Option<BlockPos>>,
+    /// Ticks spent at the flower
+    pollinating_ticks: AtomicI32,
+    /// Ticks spent since the goal started
+    trying_ticks: AtomicI32,
+}
+
+impl PollinateGoal {
+    fn new() -> Self {
+        Self {
+            goal_control: GoalControl::from_array(&[Control::Move]),
+            flower: AtomicCell::new(None),
+            pollinating_ticks: AtomicI32::new(0),
+            trying_ticks: AtomicI32::new(0),
+        }
+    }
+
+    async fn find_flower(bee: &Bee) -> Option<BlockPos> {
+        let entity = &bee.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        if let Some(flower) = bee.flower_pos.load()
+            && bee.distance_to(&flower) <= f64::from(FLOWER_SEARCH_RANGE.0)
+            && is_flower(world, &flower).await
+        {
+            return Some(flower);
+        }
+
+        let center = entity.block_pos.load();
+        let (horizontal, vertical) = FLOWER_SEARCH_RANGE;
+        let mut closest: Option<BlockPos> = None;
+        for y in -vertical..=vertical {
+            for x in -horizontal..=horizontal {
+                for z in -horizontal..=horizontal {
+                    let pos = center.offset(Vector3::new(x, y, z));
+                    if closest.is_some_and(|closest| {
+                        closest.squared_distance(center) <= pos.squared_distance(center)
+                    }) {
+                        continue;
+                    }
+                    if is_flower(world, &pos).await {
+                        closest = Some(pos);
+                    }
+                }
+            }
+        }
+        closest
+    }
+}
+
+async fn is_flower(world: &World, pos: &BlockPos) -> bool {
+    world
+        .get_block(pos)
+        .await
+        .is_tagged_with_by_tag(&tag::Block::MINECRAFT_FLOWERS)
+}
+
+#[async_trait]
+impl Goal for PollinateGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(bee) = mob.get_bee() else {
+            return false;
+        };
+        if bee.has_nectar()
+            || bee.ticks_until_can_pollinate.load(Ordering::Relaxed) > 0
+            || bee
+                .mob_entity
+                .living_entity
+                .entity
+                .world
+                .weather
+                .lock()
+                .await
+                .raining
+        {
+            return false;
+        }
+        let flower = Self::find_flower(bee).await;
+        if flower.is_none() {
+            bee.ticks_until_can_pollinate
+                .store(FLOWER_SEARCH_INTERVAL, Ordering::Relaxed);
+        }
+        self.flower.store(flower);
+        flower.is_some()
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        let Some(bee) = mob.get_bee() else {
+            return false;
+        };
+        let Some(flower) = self.flower.load() else {
+            return false;
+        };
+        !bee.has_nectar()
+            && self.trying_ticks.load(Ordering::Relaxed) < MAX_POLLINATION_TRYING_TICKS
+            && is_flower(&bee.mob_entity.living_entity.entity.world, &flower).await
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {
+        self.pollinating_ticks.store(0, Ordering::Relaxed);
+        self.trying_ticks.store(0, Ordering::Relaxed);
+    }
+
+    async fn stop(&self, mob: &dyn Mob) {
+        let Some(bee) = mob.get_bee() else {
+            return;
+        };
+        bee.ticks_until_can_pollinate
+            .store(POLLINATION_COOLDOWN, Ordering::Relaxed);
+        bee.mob_entity.navigator.lock().await.cancel();
+        self.flower.store(None);
+    }
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(bee) = mob.get_bee() else {
+            return;
+        };
+        let Some(flower) = self.flower.load() else {
+            return;
+        };
+        self.trying_ticks.fetch_add(1, Ordering::Relaxed);
+        if bee.distance_to(&flower) > FLOWER_REACH_DISTANCE {
+            bee.fly_to(&flower).await;
+            return;
+        }
+
+        let pollinating_ticks = self.pollinating_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        if pollinating_ticks == 1 {
+            let entity = &bee.mob_entity.living_entity.entity;
+            entity
+                .world
+                .play_sound(
+                    Sound::EntityBeePollinate,
+                    SoundCategory::Neutral,
+                    &entity.pos.load(),
+                )
+                .await;
+        }
+        if pollinating_ticks >= POLLINATION_TICKS {
+            bee.flower_pos.store(Some(flower));
+            bee.set_has_nectar(true).await;
+        }
+    }
+
+    fn should_run_every_tick(&self) -> bool {
+        true
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
+
+/// Lets a bee carrying nectar grow the crops it flies over.
+#[derive(Default)]
+struct GrowCropGoal {
+    goal_control: GoalControl,
+}
+
+impl GrowCropGoal {
+    /// Grows the crop at `pos` by one stage, returns false if it is no crop or fully grown.
+    async fn grow(world: &Arc<World>, pos: &BlockPos) -> bool {
+        let (block, state_id) = world.get_block_and_state_id(pos).await;
+        if !block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_BEE_GROWABLES) {
+            return false;
+        }
+        let Some(props) = block.properties(state_id).map(|props| props.to_props()) else {
+            return false;
+        };
+
+        let grown = if block == &Block::CAVE_VINES || block == &Block::CAVE_VINES_PLANT {
+            block.with_property(state_id, "berries", "true")
+        } else {
+            props
+                .iter()
+                .find(|(key, _)| key == "age")
+                .and_then(|(_, age)| age.parse::<u8>().ok())
+                .and_then(|age| block.with_property(state_id, "age", &(age + 1).to_string()))
+        };
+        let Some(grown) = grown.filter(|grown| *grown != state_id) else {
+            return false;
+        };
+        world
+            .set_block_state(pos, grown, BlockFlags::NOTIFY_ALL)
+            .await;
+        world
+            .sync_world_event(WorldEvent::BeeFertilizesPlant, *pos, 15)
+            .await;
+        true
+    }
+}
+
+#[async_trait]
+impl Goal for GrowCropGoal {
+    async fn can_start(&self, mob: &dyn Mob) -> bool {
+        let Some(bee) = mob.get_bee() else {
+            return false;
+        };
+        bee.has_nectar()
+            && bee.crops_grown_since_pollination.load(Ordering::Relaxed) < MAX_CROPS_GROWN
+            && rng().random::<f32>() >= 0.3
+    }
+
+    async fn should_continue(&self, mob: &dyn Mob) -> bool {
+        let Some(bee) = mob.get_bee() else {
+            return false;
+        };
+        bee.has_nectar()
+            && bee.crops_grown_since_pollination.load(Ordering::Relaxed) < MAX_CROPS_GROWN
+    }
+
+    async fn start(&self, _mob: &dyn Mob) {}
+
+    async fn stop(&self, _mob: &dyn Mob) {}
+
+    async fn tick(&self, mob: &dyn Mob) {
+        let Some(bee) = mob.get_bee() else {
+            return;
+        };
+        if rng().random_range(0..self.get_tick_count(30)) != 0 {
+            return;
+        }
+
+        let entity = &bee.mob_entity.living_entity.entity;
+        let position = entity.block_pos.load();
+        for depth in 1..=CROP_GROWTH_RANGE {
+            if Self::grow(&entity.world, &position.down_height(depth)).await {
+                bee.crops_grown_since_pollination
+                    .fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    fn get_goal_control(&self) -> &GoalControl {
+        &self.goal_control
+    }
+}
@@ -1,19 +1,40 @@
-use super::{Entity, EntityBase, NBTStorage, ai::path::Navigator, living::LivingEntity};
+use super::{
+    Entity, EntityBase, NBTStorage, ai::path::Navigator, living::LivingEntity, player::Player,
+    r#type::from_type,
+};
+use crate::entity::ai::control::body_control::BodyControl;
+use crate::entity::ai::control::jump_control::JumpControl;
 use crate::entity::ai::control::look_control::LookControl;
 use crate::entity::ai::goal::goal_selector::GoalSelector;
+use crate::entity::ai::sensing::Sensing;
 use crate::server::Server;
 use crate::world::World;
+use allay::Allay;
 use async_trait::async_trait;
+use bee::Bee;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::game_event::GameEvent;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
+use sheep::Sheep;
 use std::sync::Arc;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::Ordering::Relaxed;
 use tokio::sync::Mutex;
+use trader_llama::TraderLlama;
+use uuid::Uuid;
 
+pub mod allay;
+pub mod bee;
+pub mod sheep;
+pub mod skeleton_horse;
+pub mod trade;
+pub mod trader_llama;
+pub mod wandering_trader;
 pub mod zombie;
+pub mod zombified_piglin;
 
 pub struct MobEntity {
     pub living_entity: LivingEntity,
@@ -22,8 +43,11 @@ pub struct MobEntity {
     pub navigator: Mutex<Navigator>,
     pub target: Mutex<Option<Arc<dyn EntityBase>>>,
     pub look_control: Mutex<LookControl>,
+    pub body_control: BodyControl,
+    pub jump_control: JumpControl,
     pub position_target: AtomicCell<BlockPos>,
     pub position_target_range: AtomicI32,
+    pub sensing: Sensing,
 }
 
 impl MobEntity {
@@ -36,8 +60,11 @@ impl MobEntity {
             navigator: Mutex::new(Navigator::default()),
             target: Mutex::new(None),
             look_control: Mutex::new(LookControl::default()),
+            body_control: BodyControl::default(),
+            jump_control: JumpControl::default(),
             position_target: AtomicCell::new(BlockPos::ZERO),
             position_target_range: AtomicI32::new(-1),
+            sensing: Sensing::default(),
         }
     }
     pub fn is_in_position_target_range(&self) -> bool {
@@ -59,8 +86,38 @@ impl MobEntity {
     }
 }
 
+/// Replaces `entity` with a new entity of `entity_type` at the same place, e.g. a pig turning into a
+/// zombified piglin. Returns the new entity.
+pub async fn convert_to(entity: &Entity, entity_type: &'static EntityType) -> Arc<dyn EntityBase> {
+    let world = entity.world.clone();
+    let converted = from_type(entity_type, entity.pos.load(), &world, Uuid::new_v4()).await;
+    let converted_entity = converted.get_entity();
+    converted_entity.set_rotation(entity.yaw.load(), entity.pitch.load());
+    converted_entity.head_yaw.store(entity.head_yaw.load());
+    entity.remove().await;
+    world.spawn_entity(converted.clone()).await;
+    converted
+}
+
 // This trait contains all overridable functions
+#[async_trait]
 pub trait Mob: EntityBase + Send + Sync {
+    /// Called every tick after the living entity has been ticked, even outside of the
+    /// activation range.
+    async fn mob_tick(&self) {}
+
+    /// Sends the mob's own metadata once it has been spawned.
+    async fn mob_init_data_tracker(&self) {}
+
+    /// Called when the mob ate something, e.g. a sheep grass to regrow its wool.
+    async fn ate(&self) {}
+
+    /// Called when a player right clicks the mob.
+    async fn mob_interact(&self, _player: &Player) {}
+
+    /// Called when `event` happens at `position` close enough for the mob to notice.
+    async fn on_game_event(&self, _event: &GameEvent, _position: &BlockPos) {}
+
     fn get_random(&self) -> rand::rngs::ThreadRng {
         rand::rng()
     }
@@ -77,11 +134,33 @@ pub trait Mob: EntityBase + Send + Sync {
         75
     }
 
+    /// How far away the mob notices and keeps track of targets.
+    // TODO: get from entity attribute
+    fn get_follow_range(&self) -> f64 {
+        16.0
+    }
+
     fn get_mob_entity(&self) -> &MobEntity;
 
     fn get_path_aware_entity(&self) -> Option<&dyn PathAwareEntity> {
         None
     }
+
+    fn get_allay(&self) -> Option<&Allay> {
+        None
+    }
+
+    fn get_bee(&self) -> Option<&Bee> {
+        None
+    }
+
+    fn get_sheep(&self) -> Option<&Sheep> {
+        None
+    }
+
+    fn get_trader_llama(&self) -> Option<&TraderLlama> {
+        None
+    }
 }
 
 #[async_trait]
@@ -92,6 +171,17 @@ where
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         let mob_entity = self.get_mob_entity();
         mob_entity.living_entity.tick(caller, server).await;
+        self.mob_tick().await;
+
+        // Entities outside of their activation range keep moving, but skip most of their AI
+        if !mob_entity.living_entity.entity.should_tick_ai() {
+            return;
+        }
+
+        mob_entity
+            .sensing
+            .tick(&mob_entity.living_entity.entity, self.get_follow_range())
+            .await;
 
         let age = mob_entity.living_entity.entity.age.load(Relaxed);
         if (age + mob_entity.living_entity.entity.entity_id) % 2 != 0 && age > 1 {
@@ -109,6 +199,10 @@ where
         let look_control = mob_entity.look_control.lock().await;
         look_control.tick(self).await;
         drop(look_control);
+        mob_entity.jump_control.tick(self);
+        mob_entity.body_control.tick(self);
+
+        mob_entity.living_entity.entity.send_head_yaw().await;
     }
 
     async fn damage_with_context(
@@ -126,6 +220,14 @@ where
             .await
     }
 
+    async fn init_data_tracker(&self) {
+        self.mob_init_data_tracker().await;
+    }
+
+    async fn interact(&self, player: &Player) {
+        self.mob_interact(player).await;
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.get_mob_entity().living_entity.entity
     }
@@ -134,6 +236,10 @@ where
         Some(&self.get_mob_entity().living_entity)
     }
 
+    fn as_mob(&self) -> Option<&dyn Mob> {
+        Some(self)
+    }
+
     fn as_nbt_storage(&self) -> &dyn NBTStorage {
         self
     }
@@ -141,6 +247,10 @@ where
     fn get_gravity(&self) -> f64 {
         self.get_mob_entity().living_entity.get_gravity()
     }
+
+    fn get_step_height(&self) -> f64 {
+        self.get_mob_entity().living_entity.get_step_height()
+    }
 }
 
 #[allow(dead_code)]
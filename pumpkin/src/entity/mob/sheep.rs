@@ -0,0 +1,178 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicI8, Ordering},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_world::block::entities::sign::DyeColor;
+use pumpkin_world::item::ItemStack;
+use rand::{Rng, rng};
+use uuid::Uuid;
+
+use super::{Mob, MobEntity};
+use crate::entity::ai::goal::eat_grass_goal::EatGrassGoal;
+use crate::entity::item::ItemEntity;
+use crate::entity::{Entity, NBTStorage};
+
+/// The wool color in the low four bits, the sheared flag in the fifth.
+const DATA_WOOL: u8 = 17;
+const SHEARED_FLAG: i8 = 0x10;
+
+pub struct Sheep {
+    mob_entity: MobEntity,
+    color: AtomicI8,
+    sheared: AtomicBool,
+}
+
+impl Sheep {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let sheep = Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+            color: AtomicI8::new(Self::random_color() as i8),
+            sheared: AtomicBool::new(false),
+        });
+        sheep
+            .mob_entity
+            .goals_selector
+            .add_goal(5, Arc::new(EatGrassGoal::default()))
+            .await;
+        sheep
+    }
+
+    /// The color naturally spawned sheep have, mostly white.
+    fn random_color() -> DyeColor {
+        match rng().random_range(0..100_000) {
+            0..5_000 => DyeColor::Black,
+            5_000..10_000 => DyeColor::Gray,
+            10_000..15_000 => DyeColor::LightGray,
+            15_000..18_000 => DyeColor::Brown,
+            18_000..18_164 => DyeColor::Pink,
+            _ => DyeColor::White,
+        }
+    }
+
+    pub fn get_color(&self) -> DyeColor {
+        DyeColor::from(self.color.load(Ordering::Relaxed))
+    }
+
+    pub async fn set_color(&self, color: DyeColor) {
+        self.color.store(color as i8, Ordering::Relaxed);
+        self.send_wool().await;
+    }
+
+    pub fn is_sheared(&self) -> bool {
+        self.sheared.load(Ordering::Relaxed)
+    }
+
+    pub async fn set_sheared(&self, sheared: bool) {
+        self.sheared.store(sheared, Ordering::Relaxed);
+        self.send_wool().await;
+    }
+
+    /// The wool block of `color`.
+    pub fn wool_item(color: DyeColor) -> &'static Item {
+        Item::from_registry_key(&format!("{}_wool", String::from(color)))
+            .unwrap_or(&Item::WHITE_WOOL)
+    }
+
+    pub fn can_be_sheared(&self) -> bool {
+        !self.is_sheared() && self.mob_entity.living_entity.entity.is_alive()
+    }
+
+    /// Shears the sheep, which drops one to three of its wool. The wool grows back once it ate
+    /// grass.
+    pub async fn shear(&self, category: SoundCategory) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        world
+            .play_sound(Sound::EntitySheepShear, category, &entity.pos.load())
+            .await;
+        self.set_sheared(true).await;
+
+        let wool = Self::wool_item(self.get_color());
+        let position = entity.pos.load().add_raw(0.0, 1.0, 0.0);
+        for _ in 0..rng().random_range(1..=3) {
+            let item = ItemEntity::new(
+                Entity::new(
+                    Uuid::new_v4(),
+                    world.clone(),
+                    position,
+                    &EntityType::ITEM,
+                    false,
+                ),
+                ItemStack::new(1, wool),
+            )
+            .await;
+            world.spawn_entity(Arc::new(item)).await;
+        }
+    }
+
+    async fn send_wool(&self) {
+        let mut wool = self.color.load(Ordering::Relaxed) & 0x0F;
+        if self.is_sheared() {
+            wool |= SHEARED_FLAG;
+        }
+        self.mob_entity
+            .living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(DATA_WOOL, MetaDataType::Byte, wool)])
+            .await;
+    }
+}
+
+#[async_trait]
+impl NBTStorage for Sheep {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        nbt.put_byte("Color", self.color.load(Ordering::Relaxed));
+        nbt.put_bool("Sheared", self.is_sheared());
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        self.color
+            .store(nbt.get_byte("Color").unwrap_or(0), Ordering::Relaxed);
+        self.sheared
+            .store(nbt.get_bool("Sheared").unwrap_or(false), Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl Mob for Sheep {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    async fn mob_init_data_tracker(&self) {
+        self.send_wool().await;
+    }
+
+    async fn ate(&self) {
+        self.set_sheared(false).await;
+    }
+
+    fn get_sheep(&self) -> Option<&Sheep> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_color_has_its_own_wool() {
+        for color in 0..16i8 {
+            let color = DyeColor::from(color);
+            assert_eq!(
+                Sheep::wool_item(color).registry_key,
+                format!("{}_wool", String::from(color))
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicI32, Ordering},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{data_component_impl::EquipmentSlot, entity::EntityType, item::Item};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
+use rand::{Rng, rng};
+use uuid::Uuid;
+
+use super::{Mob, MobEntity};
+use crate::entity::{
+    Entity, EntityBase, NBTStorage, lightning::LightningEntity, r#type::from_type,
+};
+
+/// A trap horse disappears after this many ticks if nobody came close.
+const TRAP_DESPAWN_TICKS: i32 = 18_000;
+/// Players this close to a trap horse trigger it.
+const TRAP_TRIGGER_RANGE: f64 = 10.0;
+/// Horsemen spawned in addition to the trap horse itself.
+const EXTRA_HORSEMEN: usize = 3;
+
+pub struct SkeletonHorse {
+    mob_entity: MobEntity,
+    /// Trap horses spawn with lightning and turn into skeleton horsemen once a player comes close.
+    pub trap: AtomicBool,
+    trap_time: AtomicI32,
+}
+
+impl SkeletonHorse {
+    pub fn make(entity: Entity) -> Arc<Self> {
+        Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+            trap: AtomicBool::new(false),
+            trap_time: AtomicI32::new(0),
+        })
+    }
+
+    async fn trigger_trap(&self) {
+        self.trap.store(false, Ordering::Relaxed);
+        let entity = &self.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        let pos = entity.pos.load();
+
+        let lightning = LightningEntity::new(
+            Entity::new(
+                Uuid::new_v4(),
+                world.clone(),
+                pos,
+                &EntityType::LIGHTNING_BOLT,
+                false,
+            ),
+            true,
+        );
+        world.spawn_entity(Arc::new(lightning)).await;
+
+        // TODO: Make the skeletons ride the horses once riding is supported
+        Self::spawn_skeleton(entity, pos).await;
+        for _ in 0..EXTRA_HORSEMEN {
+            let horse = from_type(&EntityType::SKELETON_HORSE, pos, world, Uuid::new_v4()).await;
+            let velocity = Vector3::new(
+                rng().random_range(-1.1485..1.1485),
+                0.0,
+                rng().random_range(-1.1485..1.1485),
+            );
+            horse.get_entity().set_velocity(velocity).await;
+            world.spawn_entity(horse).await;
+            Self::spawn_skeleton(entity, pos).await;
+        }
+    }
+
+    async fn spawn_skeleton(horse: &Entity, pos: Vector3<f64>) {
+        let world = &horse.world;
+        let skeleton = from_type(&EntityType::SKELETON, pos, world, Uuid::new_v4()).await;
+        skeleton
+            .get_entity()
+            .set_rotation(horse.yaw.load(), horse.pitch.load());
+        world.spawn_entity(skeleton.clone()).await;
+
+        if let Some(living) = skeleton.get_living_entity() {
+            living
+                .equip(EquipmentSlot::HEAD, ItemStack::new(1, &Item::IRON_HELMET))
+                .await;
+            living
+                .equip(EquipmentSlot::MAIN_HAND, ItemStack::new(1, &Item::BOW))
+                .await;
+        }
+    }
+}
+
+#[async_trait]
+impl NBTStorage for SkeletonHorse {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        nbt.put_bool("SkeletonTrap", self.trap.load(Ordering::Relaxed));
+        nbt.put_int("SkeletonTrapTime", self.trap_time.load(Ordering::Relaxed));
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        self.trap.store(
+            nbt.get_bool("SkeletonTrap").unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        self.trap_time.store(
+            nbt.get_int("SkeletonTrapTime").unwrap_or(0),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[async_trait]
+impl Mob for SkeletonHorse {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    async fn mob_tick(&self) {
+        if !self.trap.load(Ordering::Relaxed) {
+            return;
+        }
+        let entity = &self.mob_entity.living_entity.entity;
+        if self.trap_time.fetch_add(1, Ordering::Relaxed) >= TRAP_DESPAWN_TICKS {
+            entity.remove().await;
+            return;
+        }
+
+        let pos = entity.pos.load();
+        let player_nearby = entity.world.players.read().await.values().any(|player| {
+            !player.is_spectator()
+                && player
+                    .living_entity
+                    .entity
+                    .pos
+                    .load()
+                    .squared_distance_to_vec(pos)
+                    < TRAP_TRIGGER_RANGE * TRAP_TRIGGER_RANGE
+        });
+        if player_nearby {
+            self.trigger_trap().await;
+        }
+    }
+}
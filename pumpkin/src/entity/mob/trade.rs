@@ -0,0 +1,199 @@
+use pumpkin_data::item::Item;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::item::ItemStack;
+use rand::seq::IndexedRandom;
+
+/// A single trade a merchant offers.
+#[derive(Clone, Debug)]
+pub struct TradeOffer {
+    pub buy: ItemStack,
+    pub sell: ItemStack,
+    pub uses: i32,
+    pub max_uses: i32,
+    pub experience: i32,
+}
+
+impl TradeOffer {
+    #[must_use]
+    pub fn is_disabled(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_string("buy", self.buy.item.registry_key.to_string());
+        nbt.put_byte("buyCount", self.buy.item_count as i8);
+        nbt.put_string("sell", self.sell.item.registry_key.to_string());
+        nbt.put_byte("sellCount", self.sell.item_count as i8);
+        nbt.put_int("uses", self.uses);
+        nbt.put_int("maxUses", self.max_uses);
+        nbt.put_int("xp", self.experience);
+    }
+
+    #[must_use]
+    pub fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let buy = Item::from_registry_key(nbt.get_string("buy")?)?;
+        let sell = Item::from_registry_key(nbt.get_string("sell")?)?;
+        Some(Self {
+            buy: ItemStack::new(nbt.get_byte("buyCount")? as u8, buy),
+            sell: ItemStack::new(nbt.get_byte("sellCount")? as u8, sell),
+            uses: nbt.get_int("uses").unwrap_or(0),
+            max_uses: nbt.get_int("maxUses")?,
+            experience: nbt.get_int("xp").unwrap_or(1),
+        })
+    }
+}
+
+/// Writes the offers of a merchant as an NBT list.
+#[must_use]
+pub fn offers_to_nbt(offers: &[TradeOffer]) -> NbtTag {
+    NbtTag::List(
+        offers
+            .iter()
+            .map(|offer| {
+                let mut nbt = NbtCompound::new();
+                offer.write_nbt(&mut nbt);
+                NbtTag::Compound(nbt)
+            })
+            .collect(),
+    )
+}
+
+/// Reads the offers written with `offers_to_nbt`, skipping invalid ones.
+#[must_use]
+pub fn offers_from_nbt(list: &[NbtTag]) -> Vec<TradeOffer> {
+    list.iter()
+        .filter_map(|tag| match tag {
+            NbtTag::Compound(nbt) => TradeOffer::read_nbt(nbt),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sells `count` of `item` for `price` emeralds.
+#[derive(Clone, Copy)]
+pub struct SellItemFactory {
+    pub item: &'static Item,
+    pub price: u8,
+    pub count: u8,
+    pub max_uses: i32,
+}
+
+impl SellItemFactory {
+    const fn new(item: &'static Item, price: u8, count: u8, max_uses: i32) -> Self {
+        Self {
+            item,
+            price,
+            count,
+            max_uses,
+        }
+    }
+
+    #[must_use]
+    pub fn create(&self) -> TradeOffer {
+        TradeOffer {
+            buy: ItemStack::new(self.price, &Item::EMERALD),
+            sell: ItemStack::new(self.count, self.item),
+            uses: 0,
+            max_uses: self.max_uses,
+            experience: 1,
+        }
+    }
+}
+
+const WANDERING_TRADER_COMMON_TRADES: &[SellItemFactory] = &[
+    SellItemFactory::new(&Item::SEA_PICKLE, 2, 1, 5),
+    SellItemFactory::new(&Item::SLIME_BALL, 4, 1, 5),
+    SellItemFactory::new(&Item::GLOWSTONE, 2, 1, 5),
+    SellItemFactory::new(&Item::NAUTILUS_SHELL, 5, 1, 5),
+    SellItemFactory::new(&Item::FERN, 1, 1, 12),
+    SellItemFactory::new(&Item::SUGAR_CANE, 1, 1, 8),
+    SellItemFactory::new(&Item::PUMPKIN, 1, 1, 4),
+    SellItemFactory::new(&Item::KELP, 3, 1, 12),
+    SellItemFactory::new(&Item::CACTUS, 3, 1, 8),
+    SellItemFactory::new(&Item::DANDELION, 1, 1, 12),
+    SellItemFactory::new(&Item::POPPY, 1, 1, 12),
+    SellItemFactory::new(&Item::BLUE_ORCHID, 1, 1, 8),
+    SellItemFactory::new(&Item::ALLIUM, 1, 1, 12),
+    SellItemFactory::new(&Item::AZURE_BLUET, 1, 1, 12),
+    SellItemFactory::new(&Item::RED_TULIP, 1, 1, 12),
+    SellItemFactory::new(&Item::ORANGE_TULIP, 1, 1, 12),
+    SellItemFactory::new(&Item::WHITE_TULIP, 1, 1, 12),
+    SellItemFactory::new(&Item::PINK_TULIP, 1, 1, 12),
+    SellItemFactory::new(&Item::OXEYE_DAISY, 1, 1, 12),
+    SellItemFactory::new(&Item::CORNFLOWER, 1, 1, 12),
+    SellItemFactory::new(&Item::LILY_OF_THE_VALLEY, 1, 1, 7),
+    SellItemFactory::new(&Item::WHEAT_SEEDS, 1, 1, 12),
+    SellItemFactory::new(&Item::BEETROOT_SEEDS, 1, 1, 12),
+    SellItemFactory::new(&Item::PUMPKIN_SEEDS, 1, 1, 12),
+    SellItemFactory::new(&Item::MELON_SEEDS, 1, 1, 12),
+    SellItemFactory::new(&Item::ACACIA_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::BIRCH_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::DARK_OAK_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::JUNGLE_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::OAK_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::SPRUCE_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::CHERRY_SAPLING, 5, 1, 8),
+    SellItemFactory::new(&Item::MANGROVE_PROPAGULE, 5, 1, 8),
+    SellItemFactory::new(&Item::RED_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::WHITE_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::BLUE_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::PINK_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::BLACK_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::GREEN_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::LIGHT_GRAY_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::MAGENTA_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::YELLOW_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::GRAY_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::PURPLE_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::LIGHT_BLUE_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::LIME_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::ORANGE_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::BROWN_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::CYAN_DYE, 1, 3, 12),
+    SellItemFactory::new(&Item::BRAIN_CORAL_BLOCK, 3, 1, 8),
+    SellItemFactory::new(&Item::BUBBLE_CORAL_BLOCK, 3, 1, 8),
+    SellItemFactory::new(&Item::FIRE_CORAL_BLOCK, 3, 1, 8),
+    SellItemFactory::new(&Item::HORN_CORAL_BLOCK, 3, 1, 8),
+    SellItemFactory::new(&Item::TUBE_CORAL_BLOCK, 3, 1, 8),
+    SellItemFactory::new(&Item::VINE, 1, 1, 12),
+    SellItemFactory::new(&Item::BROWN_MUSHROOM, 1, 1, 12),
+    SellItemFactory::new(&Item::RED_MUSHROOM, 1, 1, 12),
+    SellItemFactory::new(&Item::LILY_PAD, 1, 2, 5),
+    SellItemFactory::new(&Item::SMALL_DRIPLEAF, 1, 2, 5),
+    SellItemFactory::new(&Item::SAND, 1, 8, 8),
+    SellItemFactory::new(&Item::RED_SAND, 1, 4, 6),
+    SellItemFactory::new(&Item::POINTED_DRIPSTONE, 1, 2, 5),
+    SellItemFactory::new(&Item::ROOTED_DIRT, 1, 2, 5),
+    SellItemFactory::new(&Item::MOSS_BLOCK, 1, 2, 5),
+];
+
+const WANDERING_TRADER_RARE_TRADES: &[SellItemFactory] = &[
+    SellItemFactory::new(&Item::TROPICAL_FISH_BUCKET, 5, 1, 4),
+    SellItemFactory::new(&Item::PUFFERFISH_BUCKET, 5, 1, 4),
+    SellItemFactory::new(&Item::PACKED_ICE, 3, 1, 6),
+    SellItemFactory::new(&Item::BLUE_ICE, 6, 1, 6),
+    SellItemFactory::new(&Item::GUNPOWDER, 1, 1, 8),
+    SellItemFactory::new(&Item::PODZOL, 3, 3, 6),
+    SellItemFactory::new(&Item::ACACIA_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::BIRCH_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::DARK_OAK_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::JUNGLE_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::OAK_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::SPRUCE_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::CHERRY_LOG, 1, 8, 4),
+    SellItemFactory::new(&Item::MANGROVE_LOG, 1, 8, 4),
+];
+
+/// Picks 5 distinct common trades and a rare one, like a freshly spawned wandering trader.
+#[must_use]
+pub fn wandering_trader_offers() -> Vec<TradeOffer> {
+    let mut rng = rand::rng();
+    let mut offers: Vec<_> = WANDERING_TRADER_COMMON_TRADES
+        .choose_multiple(&mut rng, 5)
+        .map(SellItemFactory::create)
+        .collect();
+    if let Some(rare) = WANDERING_TRADER_RARE_TRADES.choose(&mut rng) {
+        offers.push(rare.create());
+    }
+    offers
+}
@@ -0,0 +1,224 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicI32, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::java::client::play::CSetEntityLink;
+use pumpkin_util::math::boundingbox::BoundingBox;
+
+use super::{Mob, MobEntity};
+use crate::entity::{Entity, EntityBase, NBTStorage, ai::path::NavigatorGoal};
+use crate::world::World;
+
+/// Ticks an unleashed trader llama stays around.
+const DESPAWN_DELAY: i32 = 47_999;
+/// How far around itself a llama looks for a caravan to join, horizontally and vertically.
+const CARAVAN_SEARCH_RANGE: (f64, f64) = (9.0, 4.0);
+/// How often a llama outside of a caravan looks for one to join, in ticks.
+const CARAVAN_SEARCH_INTERVAL: i32 = 20;
+/// The most llamas a caravan has, counting the leashed one in front.
+const MAX_CARAVAN_LENGTH: usize = 8;
+/// How close a llama walks up to the one in front of it.
+const CARAVAN_SPACING: f64 = 2.0;
+/// A llama this far from the one in front of it leaves the caravan.
+const CARAVAN_BREAK_DISTANCE: f64 = 26.0;
+/// Blocks per tick a llama walks to keep up with its caravan.
+const CARAVAN_SPEED: f64 = 0.2;
+
+pub struct TraderLlama {
+    mob_entity: MobEntity,
+    /// Ticks until the llama leaves once it is no longer leashed to its trader.
+    pub despawn_delay: AtomicI32,
+    /// The entity id of the wandering trader holding the leash.
+    pub leash_holder: AtomicCell<Option<i32>>,
+    /// The entity id of the llama this one follows in a caravan.
+    pub caravan_head: AtomicCell<Option<i32>>,
+    /// The entity id of the llama following this one in a caravan.
+    pub caravan_tail: AtomicCell<Option<i32>>,
+}
+
+impl TraderLlama {
+    pub fn make(entity: Entity) -> Arc<Self> {
+        Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+            despawn_delay: AtomicI32::new(DESPAWN_DELAY),
+            leash_holder: AtomicCell::new(None),
+            caravan_head: AtomicCell::new(None),
+            caravan_tail: AtomicCell::new(None),
+        })
+    }
+
+    /// Attaches the leash to `holder`, or detaches it when `None`.
+    pub async fn set_leash_holder(&self, holder: Option<i32>) {
+        self.leash_holder.store(holder);
+        let entity = &self.mob_entity.living_entity.entity;
+        entity
+            .world
+            .broadcast_packet_all(&CSetEntityLink::new(entity.entity_id, holder.unwrap_or(-1)))
+            .await;
+    }
+
+    /// Follows the llama in front, or looks for a leashed llama or the end of a caravan nearby
+    /// to fall in behind.
+    async fn tick_caravan(&self) {
+        let entity = &self.mob_entity.living_entity.entity;
+        let world = &entity.world;
+        if let Some(head_id) = self.caravan_head.load() {
+            let head = world.get_entity_by_id(head_id).await;
+            match head {
+                Some(head)
+                    if self.leash_holder.load().is_none()
+                        && head
+                            .get_entity()
+                            .pos
+                            .load()
+                            .squared_distance_to_vec(entity.pos.load())
+                            <= CARAVAN_BREAK_DISTANCE * CARAVAN_BREAK_DISTANCE =>
+                {
+                    self.follow(head.get_entity()).await;
+                }
+                head => self.leave_caravan(head.as_deref()),
+            }
+            return;
+        }
+
+        if self.leash_holder.load().is_some()
+            || entity.age.load(Ordering::Relaxed) % CARAVAN_SEARCH_INTERVAL != 0
+        {
+            return;
+        }
+        let (range, height) = CARAVAN_SEARCH_RANGE;
+        let position = entity.pos.load();
+        let search = BoundingBox::new(
+            position.sub_raw(range, height, range),
+            position.add_raw(range, height, range),
+        );
+        let mut closest: Option<(f64, Arc<dyn EntityBase>)> = None;
+        for other in world.get_entities_at_box(&search).await {
+            let other_entity = other.get_entity();
+            let Some(llama) = other.as_mob().and_then(Mob::get_trader_llama) else {
+                continue;
+            };
+            if other_entity.entity_id == entity.entity_id || llama.caravan_tail.load().is_some() {
+                continue;
+            }
+            let length = Self::leashed_caravan_length(world, llama).await;
+            if !length.is_some_and(|length| length < MAX_CARAVAN_LENGTH) {
+                continue;
+            }
+            let distance = other_entity.pos.load().squared_distance_to_vec(position);
+            if closest
+                .as_ref()
+                .is_none_or(|(closest, _)| distance < *closest)
+            {
+                closest = Some((distance, other.clone()));
+            }
+        }
+
+        if let Some((_, head)) = closest
+            && let Some(llama) = head.as_mob().and_then(Mob::get_trader_llama)
+        {
+            llama.caravan_tail.store(Some(entity.entity_id));
+            self.caravan_head.store(Some(head.get_entity().entity_id));
+        }
+    }
+
+    /// How many llamas the caravan `llama` belongs to has up to and including `llama`, `None` if
+    /// nobody in front is leashed.
+    async fn leashed_caravan_length(world: &World, llama: &Self) -> Option<usize> {
+        let mut length = 1;
+        let mut leashed = llama.leash_holder.load().is_some();
+        let mut head = llama.caravan_head.load();
+        while !leashed {
+            let entity = world.get_entity_by_id(head?).await?;
+            let llama = entity.as_mob()?.get_trader_llama()?;
+            length += 1;
+            if length > MAX_CARAVAN_LENGTH {
+                return None;
+            }
+            leashed = llama.leash_holder.load().is_some();
+            head = llama.caravan_head.load();
+        }
+        Some(length)
+    }
+
+    /// Walks up to `head`, stopping a little behind it.
+    async fn follow(&self, head: &Entity) {
+        let position = self.mob_entity.living_entity.entity.pos.load();
+        let offset = head.pos.load().sub(&position);
+        let distance = offset.length();
+        if distance <= CARAVAN_SPACING {
+            return;
+        }
+        let destination = position.add(&(offset.normalize() * (distance - CARAVAN_SPACING)));
+        self.mob_entity
+            .navigator
+            .lock()
+            .await
+            .set_progress(NavigatorGoal {
+                current_progress: position,
+                destination,
+                speed: CARAVAN_SPEED,
+            });
+    }
+
+    fn leave_caravan(&self, head: Option<&dyn EntityBase>) {
+        let id = self.mob_entity.living_entity.entity.entity_id;
+        if let Some(llama) = head
+            .and_then(EntityBase::as_mob)
+            .and_then(Mob::get_trader_llama)
+            && llama.caravan_tail.load() == Some(id)
+        {
+            llama.caravan_tail.store(None);
+        }
+        self.caravan_head.store(None);
+    }
+}
+
+#[async_trait]
+impl NBTStorage for TraderLlama {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        nbt.put_int("DespawnDelay", self.despawn_delay.load(Ordering::Relaxed));
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        self.despawn_delay.store(
+            nbt.get_int("DespawnDelay").unwrap_or(DESPAWN_DELAY),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+#[async_trait]
+impl Mob for TraderLlama {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    fn get_trader_llama(&self) -> Option<&TraderLlama> {
+        Some(self)
+    }
+
+    async fn mob_tick(&self) {
+        self.tick_caravan().await;
+        let entity = &self.mob_entity.living_entity.entity;
+        if let Some(holder) = self.leash_holder.load() {
+            // The llama only starts counting down once its trader is gone
+            if entity.world.get_entity_by_id(holder).await.is_some() {
+                return;
+            }
+            self.set_leash_holder(None).await;
+        }
+
+        let despawn_delay = self.despawn_delay.load(Ordering::Relaxed) - 1;
+        self.despawn_delay.store(despawn_delay, Ordering::Relaxed);
+        if despawn_delay <= 0 {
+            entity.remove().await;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::{
+    Arc, Weak,
+    atomic::{AtomicI32, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_data::entity::EntityType;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+use tokio::sync::Mutex;
+
+use super::{
+    Mob, MobEntity,
+    trade::{TradeOffer, offers_from_nbt, offers_to_nbt, wandering_trader_offers},
+};
+use crate::entity::{
+    Entity, NBTStorage,
+    ai::goal::{look_around_goal::LookAroundGoal, look_at_entity::LookAtEntityGoal},
+};
+
+pub struct WanderingTrader {
+    mob_entity: MobEntity,
+    /// Ticks until the trader leaves, `0` if it stays forever.
+    pub despawn_delay: AtomicI32,
+    /// The position the trader tries to stay close to, usually where it spawned.
+    pub wander_target: AtomicCell<Option<BlockPos>>,
+    pub offers: Mutex<Vec<TradeOffer>>,
+}
+
+impl WanderingTrader {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let trader = Self {
+            mob_entity: MobEntity::new(entity),
+            despawn_delay: AtomicI32::new(0),
+            wander_target: AtomicCell::new(None),
+            offers: Mutex::new(wandering_trader_offers()),
+        };
+        let mob_arc = Arc::new(trader);
+        let mob_weak: Weak<dyn Mob> = {
+            let mob_arc: Arc<dyn Mob> = mob_arc.clone();
+            Arc::downgrade(&mob_arc)
+        };
+
+        let goal_selector = &mob_arc.mob_entity.goals_selector;
+        goal_selector
+            .add_goal(
+                9,
+                Arc::new(LookAtEntityGoal::with_default(
+                    mob_weak,
+                    &EntityType::PLAYER,
+                    8.0,
+                )),
+            )
+            .await;
+        goal_selector
+            .add_goal(10, Arc::new(LookAroundGoal::default()))
+            .await;
+
+        mob_arc
+    }
+}
+
+#[async_trait]
+impl NBTStorage for WanderingTrader {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.mob_entity.living_entity.write_nbt(nbt).await;
+        nbt.put_int("DespawnDelay", self.despawn_delay.load(Ordering::Relaxed));
+        if let Some(target) = self.wander_target.load() {
+            nbt.put(
+                "wander_target",
+                NbtTag::IntArray(vec![target.0.x, target.0.y, target.0.z]),
+            );
+        }
+        nbt.put("Offers", offers_to_nbt(&self.offers.lock().await));
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.mob_entity.living_entity.read_nbt_non_mut(nbt).await;
+        self.despawn_delay
+            .store(nbt.get_int("DespawnDelay").unwrap_or(0), Ordering::Relaxed);
+        if let Some([x, y, z]) = nbt.get_int_array("wander_target") {
+            self.wander_target.store(Some(BlockPos::new(*x, *y, *z)));
+        }
+        if let Some(offers) = nbt.get_list("Offers") {
+            *self.offers.lock().await = offers_from_nbt(offers);
+        }
+    }
+}
+
+#[async_trait]
+impl Mob for WanderingTrader {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    async fn mob_tick(&self) {
+        // TODO: Don't leave while a player is trading
+        let despawn_delay = self.despawn_delay.load(Ordering::Relaxed);
+        if despawn_delay > 0 {
+            self.despawn_delay
+                .store(despawn_delay - 1, Ordering::Relaxed);
+            if despawn_delay == 1 {
+                self.mob_entity.living_entity.entity.remove().await;
+            }
+        }
+    }
+}
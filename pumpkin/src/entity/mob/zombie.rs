@@ -1,6 +1,7 @@
 use super::{Mob, MobEntity};
 use crate::entity::ai::goal::look_around_goal::LookAroundGoal;
 use crate::entity::ai::goal::move_to_target_pos_goal::MoveToTargetPos;
+use crate::entity::ai::goal::revenge_goal::RevengeGoal;
 use crate::entity::ai::goal::step_and_destroy_block_goal::{StepAndDestroyBlockGoal, Stepping};
 use crate::entity::ai::goal::zombie_attack_goal::ZombieAttackGoal;
 use crate::entity::ai::goal::{Goal, GoalControl};
@@ -17,6 +18,9 @@ use pumpkin_util::math::position::BlockPos;
 use rand::{Rng, rng};
 use std::sync::{Arc, Weak};
 
+/// Zombies and their variants notice targets from further away than most mobs.
+pub const ZOMBIE_FOLLOW_RANGE: f64 = 35.0;
+
 pub struct Zombie {
     mob_entity: MobEntity,
 }
@@ -54,11 +58,17 @@ impl Zombie {
             .add_goal(2, Arc::new(ZombieAttackGoal::new(0.1, false)))
             .await;
 
+        target_selector
+            .add_goal(
+                1,
+                Arc::new(RevengeGoal::new(&[]).await.with_group_revenge()),
+            )
+            .await;
         target_selector
             .add_goal(
                 2,
                 Arc::new(
-                    ActiveTargetGoal::with_default(&mob_arc.mob_entity, &EntityType::PLAYER, true)
+                    ActiveTargetGoal::with_default(mob_arc.as_ref(), &EntityType::PLAYER, true)
                         .await,
                 ),
             )
@@ -74,6 +84,10 @@ impl Mob for Zombie {
     fn get_mob_entity(&self) -> &MobEntity {
         &self.mob_entity
     }
+
+    fn get_follow_range(&self) -> f64 {
+        ZOMBIE_FOLLOW_RANGE
+    }
 }
 
 pub struct DestroyEggGoal {
@@ -0,0 +1,66 @@
+use super::{Mob, MobEntity, zombie::ZOMBIE_FOLLOW_RANGE};
+use crate::entity::ai::goal::look_around_goal::LookAroundGoal;
+use crate::entity::ai::goal::revenge_goal::RevengeGoal;
+use crate::entity::ai::goal::zombie_attack_goal::ZombieAttackGoal;
+use crate::entity::{Entity, NBTStorage, ai::goal::look_at_entity::LookAtEntityGoal};
+use pumpkin_data::entity::EntityType;
+use std::sync::{Arc, Weak};
+
+/// Neutral until hurt, then the whole group nearby goes after the attacker.
+pub struct ZombifiedPiglin {
+    mob_entity: MobEntity,
+}
+
+impl ZombifiedPiglin {
+    pub async fn make(entity: Entity) -> Arc<Self> {
+        let mob_arc = Arc::new(Self {
+            mob_entity: MobEntity::new(entity),
+        });
+        let mob_weak: Weak<dyn Mob> = {
+            let mob_arc: Arc<dyn Mob> = mob_arc.clone();
+            Arc::downgrade(&mob_arc)
+        };
+
+        let goal_selector = &mob_arc.mob_entity.goals_selector;
+        let target_selector = &mob_arc.mob_entity.target_selector;
+
+        goal_selector
+            .add_goal(2, Arc::new(ZombieAttackGoal::new(0.1, false)))
+            .await;
+        goal_selector
+            .add_goal(
+                8,
+                Arc::new(LookAtEntityGoal::with_default(
+                    mob_weak,
+                    &EntityType::PLAYER,
+                    8.0,
+                )),
+            )
+            .await;
+        goal_selector
+            .add_goal(8, Arc::new(LookAroundGoal::default()))
+            .await;
+
+        // TODO: Stay angry at players for a while and spread anger to players nearby
+        target_selector
+            .add_goal(
+                1,
+                Arc::new(RevengeGoal::new(&[]).await.with_group_revenge()),
+            )
+            .await;
+
+        mob_arc
+    }
+}
+
+impl NBTStorage for ZombifiedPiglin {}
+
+impl Mob for ZombifiedPiglin {
+    fn get_mob_entity(&self) -> &MobEntity {
+        &self.mob_entity
+    }
+
+    fn get_follow_range(&self) -> f64 {
+        ZOMBIE_FOLLOW_RANGE
+    }
+}
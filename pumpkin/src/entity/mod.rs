@@ -1,11 +1,17 @@
+use crate::entity::experience_orb::ExperienceOrbEntity;
 use crate::entity::item::ItemEntity;
 use crate::world::World;
-use crate::{server::Server, world::portal::PortalManager};
+use crate::{
+    server::Server,
+    world::portal::{PortalManager, PortalTeleport, nether::NetherPortal},
+};
 use async_trait::async_trait;
 use bytes::BufMut;
 use crossbeam::atomic::AtomicCell;
 use living::LivingEntity;
+use mob::Mob;
 use player::Player;
+use pumpkin_config::{ActivationRangeConfig, BASIC_CONFIG, advanced_config};
 use pumpkin_data::BlockState;
 use pumpkin_data::block_properties::{EnumVariants, Integer0To15};
 use pumpkin_data::fluid::Fluid;
@@ -13,12 +19,14 @@ use pumpkin_data::{Block, BlockDirection};
 use pumpkin_data::{
     block_properties::{Facing, HorizontalFacing},
     damage::DamageType,
-    entity::{EntityPose, EntityType},
+    entity::{EntityPose, EntityType, MobCategory},
     sound::{Sound, SoundCategory},
+    tag::{self, Taggable},
 };
 use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::java::client::play::{CUpdateEntityPos, CUpdateEntityPosRot};
 use pumpkin_protocol::{
+    ClientPacket,
     codec::var_int::VarInt,
     java::client::play::{
         CEntityPositionSync, CEntityVelocity, CHeadRot, CSetEntityMetadata, CSpawnEntity,
@@ -43,7 +51,7 @@ use std::collections::BTreeMap;
 use std::sync::{
     Arc,
     atomic::{
-        AtomicBool, AtomicI32, AtomicU32,
+        AtomicBool, AtomicI32, AtomicI64, AtomicU8, AtomicU32,
         Ordering::{self, Relaxed},
     },
 };
@@ -56,6 +64,7 @@ pub mod experience_orb;
 pub mod falling;
 pub mod hunger;
 pub mod item;
+pub mod lightning;
 pub mod living;
 pub mod mob;
 pub mod player;
@@ -65,6 +74,7 @@ pub mod r#type;
 
 mod combat;
 pub mod predicate;
+pub mod riding;
 
 #[async_trait]
 pub trait EntityBase: Send + Sync + NBTStorage {
@@ -105,6 +115,11 @@ pub trait EntityBase: Send + Sync + NBTStorage {
         0.0
     }
 
+    /// How high of a block the entity walks up without jumping.
+    fn get_step_height(&self) -> f64 {
+        0.0
+    }
+
     /// Returns if damage was successful or not
     async fn damage(
         &self,
@@ -147,6 +162,15 @@ pub trait EntityBase: Send + Sync + NBTStorage {
 
     /// Called when a player collides with a entity
     async fn on_player_collision(&self, _player: &Arc<Player>) {}
+
+    /// Called when a player attacks this entity, returns `true` if the attack should not deal any
+    /// damage.
+    async fn skip_attack_interaction(&self, _player: &Player) -> bool {
+        false
+    }
+
+    /// Called when a player right clicks this entity.
+    async fn interact(&self, _player: &Player) {}
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
 
@@ -154,6 +178,10 @@ pub trait EntityBase: Send + Sync + NBTStorage {
         None
     }
 
+    fn get_experience_orb(self: Arc<Self>) -> Option<Arc<ExperienceOrbEntity>> {
+        None
+    }
+
     fn get_player(&self) -> Option<&Player> {
         None
     }
@@ -191,9 +219,10 @@ pub trait EntityBase: Send + Sync + NBTStorage {
 
     /// Kills the Entity.
     async fn kill(&self, caller: Arc<dyn EntityBase>) {
-        if let Some(living) = self.get_living_entity() {
-            living
-                .damage(caller, f32::MAX, DamageType::GENERIC_KILL)
+        if self.get_living_entity().is_some() {
+            // Go through the damage of the entity itself, players and armor stands handle it
+            // differently
+            self.damage(caller, f32::MAX, DamageType::GENERIC_KILL)
                 .await;
         } else {
             // TODO this should be removed once all entities are implemented
@@ -201,6 +230,11 @@ pub trait EntityBase: Send + Sync + NBTStorage {
         }
     }
 
+    /// Returns itself as a mob, if it is one.
+    fn as_mob(&self) -> Option<&dyn Mob> {
+        None
+    }
+
     /// Returns itself as the nbt storage for saving and loading data.
     fn as_nbt_storage(&self) -> &dyn NBTStorage;
 }
@@ -236,6 +270,26 @@ impl RemovalReason {
 
 static CURRENT_ID: AtomicI32 = AtomicI32::new(0);
 
+fn is_water(fluid: &Fluid) -> bool {
+    fluid.id == Fluid::WATER.id || fluid.id == Fluid::FLOWING_WATER.id
+}
+
+/// Converts degrees to the 1/256 of a turn used for angles in packets.
+fn to_protocol_angle(degrees: f32) -> u8 {
+    (degrees * 256.0 / 360.0).rem_euclid(256.0) as u8
+}
+
+/// The fastest an entity can move on each axis in blocks per tick, as far as clients are told.
+pub const MAX_VELOCITY: f64 = 3.9;
+
+fn clamp_velocity(velocity: Vector3<f64>) -> Vector3<f64> {
+    Vector3::new(
+        velocity.x.clamp(-MAX_VELOCITY, MAX_VELOCITY),
+        velocity.y.clamp(-MAX_VELOCITY, MAX_VELOCITY),
+        velocity.z.clamp(-MAX_VELOCITY, MAX_VELOCITY),
+    )
+}
+
 /// Represents a non-living Entity (e.g. Item, Egg, Snowball...)
 pub struct Entity {
     /// A unique identifier for the entity
@@ -262,6 +316,8 @@ pub struct Entity {
     pub sprinting: AtomicBool,
     /// Indicates whether the entity is flying due to a fall
     pub fall_flying: AtomicBool,
+    /// Indicates whether the entity is swimming
+    pub swimming: AtomicBool,
     /// The entity's current velocity vector, aka knockback
     pub velocity: AtomicCell<Vector3<f64>>,
     /// Tracks a horizontal collision
@@ -270,6 +326,8 @@ pub struct Entity {
     pub on_ground: AtomicBool,
     /// Indicates whether the entity is touching water
     pub touching_water: AtomicBool,
+    /// Indicates whether the eyes of the entity are below the water surface
+    pub submerged_in_water: AtomicBool,
     /// Indicates the fluid height
     pub water_height: AtomicCell<f64>,
     /// Indicates whether the entity is touching lava
@@ -304,6 +362,10 @@ pub struct Entity {
     /// The vehicle that entity is in
     pub vehicle: Mutex<Option<Arc<dyn EntityBase>>>,
     pub age: AtomicI32,
+    /// The world age at which the entity last made a crystal block chime
+    pub last_chime_age: AtomicI64,
+    /// How loud the last chime was, it fades out over time
+    pub last_chime_intensity: AtomicCell<f32>,
 
     pub first_loaded_chunk_position: AtomicCell<Option<Vector3<i32>>>,
 
@@ -316,17 +378,38 @@ pub struct Entity {
     pub custom_name_visible: bool,
     /// The data send in the Entity Spawn packet
     pub data: AtomicI32,
+    /// Ticks of air left before drowning, below zero while drowning
+    pub air: AtomicI32,
     /// If true, the entity cannot collide with anything (e.g. spectator)
     pub no_clip: AtomicBool,
+    /// If true, the entity is not pulled down by gravity
+    pub no_gravity: AtomicBool,
     /// Multiplies movement for one tick before being reset
     pub movement_multiplier: AtomicCell<Vector3<f64>>,
     /// Determines whether the entity's velocity needs to be sent
     pub velocity_dirty: AtomicBool,
     /// Set when an Entity is to be removed but could still be referenced
     pub removed: AtomicBool,
+    /// If true, the entity is never slowed down by activation ranges
+    pub always_active: AtomicBool,
+    /// Set when the entity is outside of its activation range
+    pub inactive: AtomicBool,
+    /// Players that received the last movement update of this entity
+    tracked_by: Mutex<Vec<uuid::Uuid>>,
+    /// The entity flags last sent to clients, see [`Flag`]
+    flags: AtomicU8,
+    /// The yaw, pitch and head yaw last sent to clients, as protocol angles
+    sent_yaw: AtomicU8,
+    sent_pitch: AtomicU8,
+    sent_head_yaw: AtomicU8,
+    /// How many movement updates were sent, used to skip updates for far away players
+    movement_updates: AtomicU32,
 }
 
 impl Entity {
+    /// Ticks of air an entity has when it is out of water.
+    pub const MAX_AIR: i32 = 300;
+
     pub fn new(
         entity_uuid: uuid::Uuid,
         world: Arc<World>,
@@ -349,6 +432,7 @@ impl Entity {
             entity_type,
             on_ground: AtomicBool::new(false),
             touching_water: AtomicBool::new(false),
+            submerged_in_water: AtomicBool::new(false),
             water_height: AtomicCell::new(0.0),
             touching_lava: AtomicBool::new(false),
             lava_height: AtomicCell::new(0.0),
@@ -365,6 +449,7 @@ impl Entity {
             world,
             sprinting: AtomicBool::new(false),
             fall_flying: AtomicBool::new(false),
+            swimming: AtomicBool::new(false),
             yaw: AtomicCell::new(0.0),
             head_yaw: AtomicCell::new(0.0),
             body_yaw: AtomicCell::new(0.0),
@@ -383,28 +468,47 @@ impl Entity {
             invulnerable: AtomicBool::new(invulnerable),
             damage_immunities: Vec::new(),
             data: AtomicI32::new(0),
+            air: AtomicI32::new(Self::MAX_AIR),
             fire_ticks: AtomicI32::new(-1),
             has_visual_fire: AtomicBool::new(false),
             removal_reason: AtomicCell::new(None),
             passengers: Mutex::new(Vec::new()),
             vehicle: Mutex::new(None),
             age: AtomicI32::new(0),
+            last_chime_age: AtomicI64::new(0),
+            last_chime_intensity: AtomicCell::new(0.0),
             portal_cooldown: AtomicU32::new(0),
             portal_manager: Mutex::new(None),
             custom_name: None,
             custom_name_visible: false,
             no_clip: AtomicBool::new(false),
+            no_gravity: AtomicBool::new(false),
             movement_multiplier: AtomicCell::new(Vector3::default()),
             velocity_dirty: AtomicBool::new(true),
             removed: AtomicBool::new(false),
+            always_active: AtomicBool::new(false),
+            inactive: AtomicBool::new(false),
+            tracked_by: Mutex::new(Vec::new()),
+            flags: AtomicU8::new(0),
+            sent_yaw: AtomicU8::new(0),
+            sent_pitch: AtomicU8::new(0),
+            sent_head_yaw: AtomicU8::new(0),
+            movement_updates: AtomicU32::new(0),
         }
     }
 
+    /// Sets the velocity and sends it to every player. Every axis is clamped to what clients can
+    /// receive, so the server never moves the entity differently than they do.
     pub async fn set_velocity(&self, velocity: Vector3<f64>) {
-        self.velocity.store(velocity);
+        self.velocity.store(clamp_velocity(velocity));
         self.send_velocity().await;
     }
 
+    /// Adds to the current velocity, e.g. for pushes from explosions, and sends the result.
+    pub async fn add_velocity(&self, velocity: Vector3<f64>) {
+        self.set_velocity(self.velocity.load() + velocity).await;
+    }
+
     /// Sets a custom name for the entity, typically used with nametags
     pub async fn set_custom_name(&self, name: TextComponent) {
         self.send_meta_data(&[Metadata::new(
@@ -490,39 +594,43 @@ impl Entity {
         self.send_rotation().await;
     }
 
+    /// Sends the rotation to clients if it changed enough to be visible since the last update.
     pub async fn send_rotation(&self) {
-        let yaw = self.yaw.load();
-        let pitch = self.pitch.load();
-
-        // Broadcast the update packet.
-
-        // TODO: Do caching to only send the packet when needed.
-
-        let yaw = (yaw * 256.0 / 360.0).rem_euclid(256.0);
-
-        let yaw = (yaw * 256.0 / 360.0).rem_euclid(256.0) as u8;
-
-        let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
-
-        self.world
-            .broadcast_packet_all(&CUpdateEntityRot::new(
-                self.entity_id.into(),
-                yaw,
-                pitch as u8,
-                self.on_ground.load(Relaxed),
-            ))
-            .await;
-
-        self.send_head_rot(yaw).await;
+        if let Some((yaw, pitch)) = self.take_rotation_change() {
+            self.world
+                .broadcast_packet_all(&CUpdateEntityRot::new(
+                    self.entity_id.into(),
+                    yaw,
+                    pitch,
+                    self.on_ground.load(Relaxed),
+                ))
+                .await;
+        }
+        self.send_head_yaw().await;
     }
 
-    pub async fn send_head_rot(&self, head_yaw: u8) {
+    /// Sends the head yaw to clients if it changed enough to be visible since the last update.
+    pub async fn send_head_yaw(&self) {
+        let head_yaw = to_protocol_angle(self.head_yaw.load());
+        if self.sent_head_yaw.swap(head_yaw, Relaxed) == head_yaw {
+            return;
+        }
         self.world
             .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), head_yaw))
             .await;
     }
 
-    fn default_portal_cooldown(&self) -> u32 {
+    /// Returns the yaw and pitch as protocol angles if either differs from what was sent last,
+    /// remembering them as sent.
+    fn take_rotation_change(&self) -> Option<(u8, u8)> {
+        let yaw = to_protocol_angle(self.yaw.load());
+        let pitch = to_protocol_angle(self.pitch.load());
+        let old_yaw = self.sent_yaw.swap(yaw, Relaxed);
+        let old_pitch = self.sent_pitch.swap(pitch, Relaxed);
+        (old_yaw != yaw || old_pitch != pitch).then_some((yaw, pitch))
+    }
+
+    pub fn default_portal_cooldown(&self) -> u32 {
         if self.entity_type == &EntityType::PLAYER {
             10
         } else {
@@ -530,9 +638,16 @@ impl Entity {
         }
     }
 
+    /// Moves the bounding box along `movement` as far as the block collision shapes allow. Like
+    /// in vanilla, entities step up blocks up to `step_height` high when walking into them, so
+    /// slabs and stairs don't stop them.
     #[allow(clippy::float_cmp)]
-    async fn adjust_movement_for_collisions(&self, movement: Vector3<f64>) -> Vector3<f64> {
-        self.on_ground.store(false, Ordering::SeqCst);
+    async fn adjust_movement_for_collisions(
+        &self,
+        movement: Vector3<f64>,
+        step_height: f64,
+    ) -> Vector3<f64> {
+        let was_on_ground = self.on_ground.swap(false, Ordering::SeqCst);
 
         self.supporting_block_pos.store(None);
 
@@ -544,125 +659,144 @@ impl Entity {
 
         let bounding_box = self.bounding_box.load();
 
-        let (collisions, block_positions) = self
-            .world
-            .get_block_collisions(bounding_box.stretch(movement))
-            .await;
+        let mut area = bounding_box.stretch(movement);
+        if step_height > 0.0 {
+            area = area.stretch(Vector3::new(0.0, step_height, 0.0));
+        }
+        let (collisions, block_positions) = self.world.get_block_collisions(area).await;
 
         if collisions.is_empty() {
             return movement;
         }
 
-        let mut adjusted_movement = movement;
-
-        // Y-Axis adjustment
-
-        if movement.get_axis(Axis::Y) != 0.0 {
-            let mut max_time = 1.0;
-
-            let mut positions = block_positions.into_iter();
-
-            let (mut collisions_len, mut position) = positions.next().unwrap();
+        let mut adjusted_movement = Self::collide_with_shapes(bounding_box, movement, &collisions);
 
-            let mut supporting_block_pos = None;
+        let landed = movement.y < 0.0 && adjusted_movement.y != movement.y;
+        let hit_wall = adjusted_movement.x != movement.x || adjusted_movement.z != movement.z;
 
-            for (i, inert_box) in collisions.iter().enumerate() {
-                if i == collisions_len {
-                    (collisions_len, position) = positions.next().unwrap();
-                }
+        if step_height > 0.0 && (landed || was_on_ground) && hit_wall {
+            let horizontal = Vector3::new(movement.x, 0.0, movement.z);
 
-                if let Some(collision_time) = bounding_box.calculate_collision_time(
-                    inert_box,
-                    adjusted_movement,
-                    Axis::Y,
-                    max_time,
-                ) {
-                    max_time = collision_time;
+            let mut stepped = Self::collide_with_shapes(
+                bounding_box,
+                Vector3::new(movement.x, step_height, movement.z),
+                &collisions,
+            );
 
-                    supporting_block_pos = Some(position);
+            // A ceiling might be in the way of the diagonal move, so also try going straight up
+            // first and then across
+            let up = Self::collide_with_shapes(
+                bounding_box.stretch(horizontal),
+                Vector3::new(0.0, step_height, 0.0),
+                &collisions,
+            );
+            if up.y < step_height {
+                let across =
+                    Self::collide_with_shapes(bounding_box.shift(up), horizontal, &collisions) + up;
+                if across.horizontal_length_squared() > stepped.horizontal_length_squared() {
+                    stepped = across;
                 }
             }
 
-            if max_time != 1.0 {
-                let changed_component = adjusted_movement.get_axis(Axis::Y) * max_time;
-
-                adjusted_movement.set_axis(Axis::Y, changed_component);
+            if stepped.horizontal_length_squared() > adjusted_movement.horizontal_length_squared() {
+                let down = Self::collide_with_shapes(
+                    bounding_box.shift(stepped),
+                    Vector3::new(0.0, movement.y - stepped.y, 0.0),
+                    &collisions,
+                );
+                adjusted_movement = stepped + down;
             }
+        }
 
-            self.on_ground
-                .store(supporting_block_pos.is_some(), Ordering::SeqCst);
+        if movement.y < 0.0 && adjusted_movement.y != movement.y {
+            self.on_ground.store(true, Ordering::SeqCst);
 
-            self.supporting_block_pos.store(supporting_block_pos);
+            self.supporting_block_pos.store(Self::find_supporting_block(
+                &bounding_box.shift(adjusted_movement),
+                &collisions,
+                &block_positions,
+            ));
         }
 
-        let mut horizontal_collision = false;
+        self.horizontal_collision.store(
+            adjusted_movement.x != movement.x || adjusted_movement.z != movement.z,
+            Ordering::SeqCst,
+        );
 
-        for axis in Axis::horizontal() {
-            if movement.get_axis(axis) == 0.0 {
-                continue;
-            }
+        adjusted_movement
+    }
 
-            let mut max_time = 1.0;
+    /// Moves `bounding_box` along `movement` one axis at a time, stopping at the first shape in the
+    /// way. Y goes first, then the larger of the horizontal components.
+    fn collide_with_shapes(
+        mut bounding_box: BoundingBox,
+        movement: Vector3<f64>,
+        shapes: &[BoundingBox],
+    ) -> Vector3<f64> {
+        let axes = if movement.x.abs() < movement.z.abs() {
+            [Axis::Y, Axis::Z, Axis::X]
+        } else {
+            [Axis::Y, Axis::X, Axis::Z]
+        };
 
-            for inert_box in &collisions {
-                if let Some(collision_time) = bounding_box.calculate_collision_time(
-                    inert_box,
-                    adjusted_movement,
-                    axis,
-                    max_time,
-                ) {
-                    max_time = collision_time;
-                }
-            }
+        let mut adjusted_movement = Vector3::default();
 
-            if max_time != 1.0 {
-                let changed_component = adjusted_movement.get_axis(axis) * max_time;
+        for axis in axes {
+            let mut offset = movement.get_axis(axis);
 
-                adjusted_movement.set_axis(axis, changed_component);
+            for shape in shapes {
+                if offset == 0.0 {
+                    break;
+                }
+                offset = bounding_box.calculate_max_offset(shape, axis, offset);
+            }
 
-                horizontal_collision = true;
+            if offset != 0.0 {
+                let mut delta = Vector3::default();
+                delta.set_axis(axis, offset);
+                bounding_box = bounding_box.shift(delta);
+                adjusted_movement.set_axis(axis, offset);
             }
         }
 
-        self.horizontal_collision
-            .store(horizontal_collision, Ordering::SeqCst);
-
         adjusted_movement
     }
 
-    /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
-    /// `LivingEntity.takeKnockback()`
-    /// This function calculates the entity's new velocity based on the specified knockback strength and direction.
-    pub fn apply_knockback(&self, strength: f64, mut x: f64, mut z: f64) {
-        // TODO: strength *= 1 - Entity attribute knockback resistance
-
-        if strength <= 0.0 {
-            return;
-        }
-
-        self.velocity_dirty.store(true, Ordering::SeqCst);
-
-        // This has some vanilla magic
-
-        while x.mul_add(x, z * z) < 1.0E-5 {
-            x = (rand::random::<f64>() - rand::random::<f64>()) * 0.01;
+    /// The block the entity stands on, preferring the one closest to the center of its feet when
+    /// it stands on several.
+    fn find_supporting_block(
+        bounding_box: &BoundingBox,
+        collisions: &[BoundingBox],
+        block_positions: &[(usize, BlockPos)],
+    ) -> Option<BlockPos> {
+        let below = BoundingBox::new(
+            bounding_box.min.add_raw(0.0, -1.0E-6, 0.0),
+            Vector3::new(bounding_box.max.x, bounding_box.min.y, bounding_box.max.z),
+        );
+        let center = Vector3::new(
+            f64::midpoint(bounding_box.min.x, bounding_box.max.x),
+            bounding_box.min.y,
+            f64::midpoint(bounding_box.min.z, bounding_box.max.z),
+        );
 
-            z = (rand::random::<f64>() - rand::random::<f64>()) * 0.01;
+        let mut start = 0;
+        let mut supporting_block = None;
+        let mut closest = f64::MAX;
+        // Each position is stored along with the end of its range of collision shapes
+        for &(end, pos) in block_positions {
+            if collisions[start..end]
+                .iter()
+                .any(|shape| shape.intersects(&below))
+            {
+                let distance = pos.to_centered_f64().squared_distance_to_vec(center);
+                if distance < closest {
+                    closest = distance;
+                    supporting_block = Some(pos);
+                }
+            }
+            start = end;
         }
-
-        let var8 = Vector3::new(x, 0.0, z).normalize() * strength;
-
-        let velocity = self.velocity.load();
-
-        self.velocity.store(Vector3::new(
-            velocity.x / 2.0 - var8.x,
-            if self.on_ground.load(Relaxed) {
-                (velocity.y / 2.0 + strength).min(0.4)
-            } else {
-                velocity.y
-            },
-            velocity.z / 2.0 - var8.z,
-        ));
+        supporting_block
     }
 
     // Part of LivingEntity.tickMovement() in yarn
@@ -693,68 +827,64 @@ impl Entity {
         self.velocity.store(motion);
     }
 
-    #[allow(dead_code)]
-    fn tick_block_underneath(_caller: &Arc<dyn EntityBase>) {
-        // let world = self.world.read().await;
-
-        // let (pos, block, state) = self.get_block_with_y_offset(0.2).await;
-
-        // world
-        //     .block_registry
-        //     .on_stepped_on(&world, caller.as_ref(), pos, block, state)
-        //     .await;
-
-        // TODO: Add this to on_stepped_on
-
-        /*
-
-
-        if self.on_ground.load(Ordering::SeqCst) {
-
-
-            let (_pos, block, state) = self.get_block_with_y_offset(0.2).await;
-
-
-            if let Some(live) = living {
-
-
-                if block == Block::CAMPFIRE
-
-
-                    || block == Block::SOUL_CAMPFIRE
-
-
-                        && CampfireLikeProperties::from_state_id(state.id, &block).r#signal_fire
-
-
-                {
-
-
-                    let _ = live.damage(1.0, DamageType::CAMPFIRE).await;
-
-
-                }
-
-
-
-
-
-                if block == Block::MAGMA_BLOCK {
-
-
-                    let _ = live.damage(1.0, DamageType::HOT_FLOOR).await;
-
-
-                }
-
+    /// Lets the block the entity stands on act on it, e.g. magma blocks burning it.
+    async fn tick_stepped_on(&self, caller: &Arc<dyn EntityBase>) {
+        if !self.on_ground.load(Ordering::Relaxed) {
+            return;
+        }
 
-            }
+        let (pos, block, state) = self.get_block_with_y_offset(0.2).await;
 
+        self.world
+            .block_registry
+            .on_stepped_on(block, &self.world, caller, &pos, state)
+            .await;
+    }
 
+    /// Lets crystal blocks like amethyst chime when the entity walks over them, at most once a
+    /// second and louder the more often it happens.
+    pub async fn play_crystal_chime(&self, caller: &dyn EntityBase) {
+        let (_, block, _) = self.get_block_with_y_offset(0.2).await;
+        if !block.is_tagged_with_by_tag(&tag::Block::MINECRAFT_CRYSTAL_SOUND_BLOCKS) {
+            return;
         }
 
+        let age = self.world.level_time.lock().await.world_age;
+        let last_age = self.last_chime_age.load(Relaxed);
+        if age < last_age + 20 {
+            return;
+        }
+        let faded = self.last_chime_intensity.load() * 0.997_f32.powf((age - last_age) as f32);
+        let intensity = (faded + 0.07).min(1.0);
+        self.last_chime_age.store(age, Relaxed);
+        self.last_chime_intensity.store(intensity);
 
-        */
+        let volume = 0.1 + intensity * 1.2;
+        let pitch = 0.5 + intensity * rand::random::<f32>() * 1.2;
+        let position = self.pos.load();
+        if let Some(player) = caller.get_player() {
+            // The walking player's client plays the chime on its own
+            self.world
+                .play_sound_raw_expect(
+                    player,
+                    Sound::BlockAmethystBlockChime as u16,
+                    SoundCategory::Players,
+                    &position,
+                    volume,
+                    pitch,
+                )
+                .await;
+        } else {
+            self.world
+                .play_sound_fine(
+                    Sound::BlockAmethystBlockChime,
+                    SoundCategory::Neutral,
+                    &position,
+                    volume,
+                    pitch,
+                )
+                .await;
+        }
     }
 
     // Returns whether the entity's eye level is in a wall
@@ -816,6 +946,11 @@ impl Entity {
     }
 
     pub async fn send_pos_rot(&self) {
+        let Some((yaw, pitch)) = self.take_rotation_change() else {
+            self.send_pos().await;
+            self.send_head_yaw().await;
+            return;
+        };
         let old = self.update_last_pos();
 
         let new = self.pos.load();
@@ -826,28 +961,18 @@ impl Entity {
             new.z.mul_add(4096.0, -(old.z * 4096.0)) as i16,
         );
 
-        let yaw = self.yaw.load();
-
-        let pitch = self.pitch.load();
-
-        // Broadcast the update packet.
-
-        // TODO: Do caching to only send the packet when needed.
-
-        let yaw = (yaw * 256.0 / 360.0).rem_euclid(256.0) as u8;
-
-        let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
-
-        self.world
-            .broadcast_packet_all(&CUpdateEntityPosRot::new(
+        self.send_movement(
+            &CUpdateEntityPosRot::new(
                 self.entity_id.into(),
                 Vector3::new(converted.x, converted.y, converted.z),
                 yaw,
-                pitch as u8,
+                pitch,
                 self.on_ground.load(Relaxed),
-            ))
-            .await;
-        self.send_head_rot(yaw).await;
+            ),
+            new.sub(&old),
+        )
+        .await;
+        self.send_head_yaw().await;
     }
 
     pub fn update_last_pos(&self) -> Vector3<f64> {
@@ -868,13 +993,74 @@ impl Entity {
             new.z.mul_add(4096.0, -(old.z * 4096.0)) as i16,
         );
 
-        self.world
-            .broadcast_packet_all(&CUpdateEntityPos::new(
+        self.send_movement(
+            &CUpdateEntityPos::new(
                 self.entity_id.into(),
                 Vector3::new(converted.x, converted.y, converted.z),
                 self.on_ground.load(Relaxed),
-            ))
-            .await;
+            ),
+            new.sub(&old),
+        )
+        .await;
+    }
+
+    /// How far away players can see this entity, in blocks.
+    pub fn tracking_range(&self) -> f64 {
+        let view_distance = i32::from(BASIC_CONFIG.view_distance.get());
+        f64::from(self.entity_type.client_tracking_range.min(view_distance) * 16)
+    }
+
+    /// Returns whether movement updates should be sent this tick, based on the update interval of
+    /// the entity type.
+    pub fn is_movement_update_tick(&self) -> bool {
+        self.age.load(Relaxed) % self.entity_type.update_interval.max(1) == 0
+    }
+
+    /// Sends a relative movement packet to all players within tracking range.
+    ///
+    /// Players that just came into range missed the previous relative moves, so they get the
+    /// absolute position instead. The same goes for moves too large for a relative packet.
+    /// With entity tracking rings enabled, far away players skip updates and likewise get the
+    /// absolute position on the next update they receive.
+    async fn send_movement<P: ClientPacket>(&self, packet: &P, delta: Vector3<f64>) {
+        let pos = self.pos.load();
+        let range = self.tracking_range();
+        let interest = &advanced_config().entity_tracking;
+        let update = self.movement_updates.fetch_add(1, Relaxed);
+        // Relative moves are limited to 8 blocks (checking 8²)
+        let teleport = delta.length_squared() >= 64.0;
+        let sync = CEntityPositionSync::new(
+            self.entity_id.into(),
+            pos,
+            self.velocity.load(),
+            self.yaw.load(),
+            self.pitch.load(),
+            self.on_ground.load(Relaxed),
+        );
+
+        let players = self.world.players.read().await;
+        let mut tracked_by = self.tracked_by.lock().await;
+        let mut now_tracked_by = Vec::with_capacity(tracked_by.len());
+        for player in players.values() {
+            let player_pos = player.living_entity.entity.pos.load();
+            let distance = (player_pos.x - pos.x)
+                .abs()
+                .max((player_pos.z - pos.z).abs());
+            if distance > range {
+                continue;
+            }
+            if interest.enabled && update % interest.update_interval(distance) != 0 {
+                continue;
+            }
+            let uuid = player.gameprofile.id;
+            if teleport || !tracked_by.contains(&uuid) {
+                player.client.enqueue_packet(&sync).await;
+            } else {
+                player.client.enqueue_packet(packet).await;
+            }
+            now_tracked_by.push(uuid);
+        }
+        *tracked_by = now_tracked_by;
     }
 
     // updateWaterState() in yarn
@@ -1005,6 +1191,23 @@ impl Entity {
         self.lava_height.store(lava_height);
 
         self.touching_lava.store(in_lava, Ordering::SeqCst);
+
+        self.update_submerged_in_water().await;
+    }
+
+    async fn update_submerged_in_water(&self) {
+        // TODO: Boats keep their passengers dry
+        let eye_y = self.get_eye_y() - 0.111_111_11;
+        let pos = self.pos.load();
+        let block_pos = BlockPos::new(
+            pos.x.floor() as i32,
+            eye_y.floor() as i32,
+            pos.z.floor() as i32,
+        );
+        let (fluid, state) = self.world.get_fluid_and_fluid_state(&block_pos).await;
+        let submerged =
+            is_water(&fluid) && f64::from(block_pos.0.y) + f64::from(state.height) > eye_y;
+        self.submerged_in_water.store(submerged, Relaxed);
     }
 
     fn push_by_fluid(&self, speed: f64, mut push: Vector3<f64>, n: usize) {
@@ -1162,8 +1365,13 @@ impl Entity {
     // Move by a delta, adjust for collisions, and send
 
     // Does not send movement. That must be done separately
+    #[allow(clippy::float_cmp)]
     async fn move_entity(&self, caller: Arc<dyn EntityBase>, mut motion: Vector3<f64>) {
         if caller.get_player().is_some() {
+            if !self.no_clip.load(Ordering::Relaxed) {
+                self.tick_stepped_on(&caller).await;
+            }
+
             return;
         }
 
@@ -1185,14 +1393,12 @@ impl Entity {
             self.velocity.store(Vector3::default());
         }
 
-        let final_move = self.adjust_movement_for_collisions(motion).await;
+        let final_move = self
+            .adjust_movement_for_collisions(motion, caller.get_step_height())
+            .await;
 
         self.move_pos(final_move);
 
-        let velocity_multiplier = f64::from(self.get_velocity_multiplier().await);
-
-        self.velocity.store(final_move * velocity_multiplier);
-
         if let Some(living) = caller.get_living_entity() {
             living
                 .update_fall_distance(
@@ -1203,6 +1409,41 @@ impl Entity {
                 )
                 .await;
         }
+
+        let mut velocity = self.velocity.load();
+
+        if motion.x != final_move.x {
+            velocity.x = 0.0;
+        }
+
+        if motion.z != final_move.z {
+            velocity.z = 0.0;
+        }
+
+        self.velocity.store(velocity);
+
+        if motion.y != final_move.y {
+            let (pos, block, _state) = self.get_block_with_y_offset(0.2).await;
+
+            self.world
+                .block_registry
+                .on_entity_land(block, &self.world, caller.as_ref(), &pos)
+                .await;
+        }
+
+        self.tick_stepped_on(&caller).await;
+        if self.on_ground.load(Ordering::Relaxed) && final_move.horizontal_length_squared() > 1.0e-7
+        {
+            self.play_crystal_chime(caller.as_ref()).await;
+        }
+
+        let velocity_multiplier = f64::from(self.get_velocity_multiplier().await);
+
+        self.velocity.store(self.velocity.load().multiply(
+            velocity_multiplier,
+            1.0,
+            velocity_multiplier,
+        ));
     }
 
     pub async fn push_out_of_blocks(&self, center_pos: Vector3<f64>) {
@@ -1273,33 +1514,14 @@ impl Entity {
                 // reset cooldown
                 self.portal_cooldown
                     .store(self.default_portal_cooldown(), Ordering::Relaxed);
-                let pos = self.pos.load();
-                // TODO: this is bad
-                let scale_factor_new = if portal_manager.portal_world.dimension_type
-                    == VanillaDimensionType::TheNether
-                {
-                    8.0
-                } else {
-                    1.0
-                };
-                // TODO: this is bad
-                let scale_factor_current =
-                    if self.world.dimension_type == VanillaDimensionType::TheNether {
-                        8.0
-                    } else {
-                        1.0
-                    };
-                let scale_factor = scale_factor_current / scale_factor_new;
-                // TODO
-                let pos = BlockPos::floored(pos.x * scale_factor, pos.y, pos.z * scale_factor);
-                caller
-                    .clone()
-                    .teleport(
-                        pos.0.to_f64(),
-                        None,
-                        None,
-                        portal_manager.portal_world.clone(),
-                    )
+                let portal_world = portal_manager.portal_world.clone();
+                let position = self.portal_target(&portal_world).await;
+                self.world
+                    .queue_portal_teleport(PortalTeleport {
+                        entity: caller.clone(),
+                        world: portal_world,
+                        position,
+                    })
                     .await;
                 drop(portal_manager);
             } else if portal_manager.ticks_in_portal == 0 {
@@ -1311,6 +1533,43 @@ impl Entity {
         }
     }
 
+    /// Where the entity arrives in `portal_world` when going through a portal.
+    async fn portal_target(&self, portal_world: &Arc<World>) -> Vector3<f64> {
+        if portal_world.dimension_type == VanillaDimensionType::TheEnd {
+            // Everything entering the end arrives at the obsidian platform
+            return Vector3::new(100.5, 49.0, 0.5);
+        }
+        if self.world.dimension_type == VanillaDimensionType::TheEnd {
+            let info = portal_world.level_info.read().await;
+            return Vector3::new(
+                f64::from(info.spawn_x) + 0.5,
+                f64::from(info.spawn_y),
+                f64::from(info.spawn_z) + 0.5,
+            );
+        }
+
+        // Every block in the nether is worth eight in the overworld
+        let scale = |world: &World| {
+            if world.dimension_type == VanillaDimensionType::TheNether {
+                8.0
+            } else {
+                1.0
+            }
+        };
+        let scale_factor = scale(self.world.as_ref()) / scale(portal_world.as_ref());
+        let pos = self.pos.load();
+        let pos = BlockPos::floored(pos.x * scale_factor, pos.y, pos.z * scale_factor);
+        NetherPortal::find_linked_portal(portal_world, &pos)
+            .await
+            .map_or_else(
+                || pos.0.to_f64(),
+                |portal| {
+                    let portal = portal.0.to_f64();
+                    Vector3::new(portal.x + 0.5, portal.y, portal.z + 0.5)
+                },
+            )
+    }
+
     pub async fn try_use_portal(&self, portal_delay: u32, portal_world: Arc<World>, pos: BlockPos) {
         if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
             self.portal_cooldown
@@ -1360,6 +1619,8 @@ impl Entity {
 
     /// Removes the `Entity` from their current `World`
     pub async fn remove(&self) {
+        self.eject_passengers().await;
+        self.detach_from_vehicle().await;
         self.world.remove_entity(self).await;
     }
 
@@ -1387,9 +1648,17 @@ impl Entity {
     }
 
     /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
+    /// `LivingEntity.takeKnockback()`
     ///
     /// This function calculates the entity's new velocity based on the specified knockback strength and direction.
+    /// The velocity is not sent, see [`Self::send_velocity`].
     pub fn knockback(&self, strength: f64, x: f64, z: f64) {
+        // TODO: strength *= 1 - Entity attribute knockback resistance
+        if strength <= 0.0 {
+            return;
+        }
+        self.velocity_dirty.store(true, Ordering::SeqCst);
+
         // This has some vanilla magic
         let mut x = x;
         let mut z = z;
@@ -1570,17 +1839,73 @@ impl Entity {
     }
 
     async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
-        let mut b = 0i8;
-        if value {
-            b |= 1 << index;
+        let bit = 1 << flag as u8;
+        let flags = if value {
+            self.flags.fetch_or(bit, Relaxed) | bit
         } else {
-            b &= !(1 << index);
-        }
-        self.send_meta_data(&[Metadata::new(0, MetaDataType::Byte, b)])
+            self.flags.fetch_and(!bit, Relaxed) & !bit
+        };
+        self.send_meta_data(&[Metadata::new(0, MetaDataType::Byte, flags as i8)])
             .await;
     }
 
+    pub async fn set_invisible(&self, invisible: bool) {
+        self.set_flag(Flag::Invisible, invisible).await;
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.flags.load(Relaxed) & (1 << Flag::Invisible as u8) != 0
+    }
+
+    pub async fn set_no_gravity(&self, no_gravity: bool) {
+        if self.no_gravity.swap(no_gravity, Relaxed) != no_gravity {
+            self.send_meta_data(&[Metadata::new(5, MetaDataType::Boolean, no_gravity)])
+                .await;
+        }
+    }
+
+    /// Changes the size of the bounding box, keeping it at the current position.
+    pub fn set_dimensions(&self, dimensions: EntityDimensions) {
+        let pos = self.pos.load();
+        self.bounding_box_size.store(dimensions);
+        self.bounding_box
+            .store(BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &dimensions));
+    }
+
+    pub async fn set_swimming(&self, swimming: bool) {
+        self.swimming.store(swimming, Relaxed);
+        self.set_flag(Flag::Swimming, swimming).await;
+        if swimming {
+            self.set_pose(EntityPose::Swimming).await;
+        } else if self.pose.load() == EntityPose::Swimming {
+            self.set_pose(EntityPose::Standing).await;
+        }
+    }
+
+    /// Starts swimming when sprinting under water and stops once no longer sprinting or out of the
+    /// water.
+    pub async fn update_swimming(&self) {
+        let swimming = self.swimming.load(Relaxed);
+        let in_water = if swimming {
+            self.touching_water.load(Ordering::SeqCst)
+        } else {
+            self.submerged_in_water.load(Relaxed)
+                && is_water(self.world.get_fluid(&self.block_pos.load()).await)
+        };
+        let can_swim =
+            in_water && self.sprinting.load(Relaxed) && self.vehicle.lock().await.is_none();
+        if can_swim != swimming {
+            self.set_swimming(can_swim).await;
+        }
+    }
+
+    pub async fn set_air(&self, air: i32) {
+        if self.air.swap(air, Relaxed) != air {
+            self.send_meta_data(&[Metadata::new(1, MetaDataType::Integer, VarInt(air))])
+                .await;
+        }
+    }
+
     /// Plays sound at this entity's position with the entity's sound category
     pub async fn play_sound(&self, sound: Sound) {
         self.world
@@ -1610,7 +1935,7 @@ impl Entity {
     }
 
     pub fn is_invulnerable_to(&self, damage_type: &DamageType) -> bool {
-        *damage_type != DamageType::GENERIC_KILL
+        !damage_type.is_tagged_with_by_tag(&tag::DamageType::MINECRAFT_BYPASSES_INVULNERABILITY)
             && (self.invulnerable.load(Relaxed) || self.damage_immunities.contains(damage_type))
     }
 
@@ -1686,14 +2011,18 @@ impl Entity {
         pitch: Option<f32>,
         _world: Arc<World>,
     ) {
-        // TODO: handle world change
+        // Changing worlds is done by `World::transfer_entity`
+        let yaw = yaw.unwrap_or_else(|| self.yaw.load());
+        let pitch = pitch.unwrap_or_else(|| self.pitch.load());
+        self.set_pos(position);
+        self.set_rotation(yaw, pitch);
         self.world
             .broadcast_packet_all(&CEntityPositionSync::new(
                 self.entity_id.into(),
                 position,
                 Vector3::new(0.0, 0.0, 0.0),
-                yaw.unwrap_or(0.0),
-                pitch.unwrap_or(0.0),
+                yaw,
+                pitch,
                 self.on_ground.load(Ordering::SeqCst),
             ))
             .await;
@@ -1703,6 +2032,29 @@ impl Entity {
         self.pos.load().y + f64::from(self.standing_eye_height)
     }
 
+    /// Whether no block with a collision shape is between the eyes of this entity and the eyes of
+    /// `target`.
+    pub async fn can_see(&self, target: &Self) -> bool {
+        if !Arc::ptr_eq(&self.world, &target.world) {
+            return false;
+        }
+        let pos = self.pos.load();
+        let target_pos = target.pos.load();
+        let from = Vector3::new(pos.x, self.get_eye_y(), pos.z);
+        let to = Vector3::new(target_pos.x, target.get_eye_y(), target_pos.z);
+        if from.squared_distance_to_vec(to) > 128.0 * 128.0 {
+            return false;
+        }
+        let hit_check = async |pos: &BlockPos, world: &Arc<World>| {
+            !world
+                .get_block_state(pos)
+                .await
+                .get_block_collision_shapes()
+                .is_empty()
+        };
+        self.world.raycast(from, to, hit_check).await.is_none()
+    }
+
     pub fn is_removed(&self) -> bool {
         self.removal_reason.load().is_some()
     }
@@ -1720,12 +2072,74 @@ impl Entity {
         vehicle.is_some()
     }
 
+    /// Exempts this entity from activation range checks, so it always runs its full tick.
+    pub fn set_always_active(&self, always_active: bool) {
+        self.always_active.store(always_active, Relaxed);
+        if always_active {
+            self.inactive.store(false, Relaxed);
+        }
+    }
+
+    fn activation_range(&self, config: &ActivationRangeConfig) -> u32 {
+        let category = self.entity_type.category;
+        if *category == MobCategory::MONSTER {
+            config.monsters
+        } else if *category == MobCategory::CREATURE {
+            config.animals
+        } else if *category == MobCategory::WATER_CREATURE
+            || *category == MobCategory::WATER_AMBIENT
+            || *category == MobCategory::UNDERGROUND_WATER_CREATURE
+            || *category == MobCategory::AXOLOTLS
+        {
+            config.water
+        } else {
+            config.misc
+        }
+    }
+
+    /// Marks the entity as inactive if no player is within its activation range.
+    pub fn update_activation(
+        &self,
+        player_positions: &[Vector3<f64>],
+        config: &ActivationRangeConfig,
+    ) {
+        if self.always_active.load(Relaxed) {
+            return;
+        }
+        let range = f64::from(self.activation_range(config));
+        let pos = self.pos.load();
+        let active = player_positions
+            .iter()
+            .any(|player| (player.x - pos.x).abs() <= range && (player.z - pos.z).abs() <= range);
+        self.inactive.store(!active, Relaxed);
+    }
+
+    /// Returns whether the entity should run its AI this tick.
+    ///
+    /// Inactive entities only do so every `inactive_tick_interval` ticks.
+    pub fn should_tick_ai(&self) -> bool {
+        if !self.inactive.load(Relaxed) {
+            return true;
+        }
+        let interval = advanced_config()
+            .activation_range
+            .inactive_tick_interval
+            .max(1);
+        self.age.load(Relaxed).wrapping_add(self.entity_id) as u32 % interval == 0
+    }
+
     pub async fn check_out_of_world(&self, dyn_self: Arc<dyn EntityBase>) {
-        if self.pos.load().y < f64::from(self.world.generation_settings().shape.min_y) - 64.0 {
+        if self.pos.load().y >= f64::from(self.world.min_y - 64) {
+            return;
+        }
+        if dyn_self.get_living_entity().is_some() {
             // Tick out of world damage
             dyn_self
                 .damage(dyn_self.clone(), 4.0, DamageType::OUT_OF_WORLD)
                 .await;
+        } else {
+            // Non-living entities (items, projectiles, ...) just fall out of the world
+            self.remove().await;
         }
     }
 
@@ -1736,6 +2150,31 @@ impl Entity {
     }
 }
 
+/// Vanilla stores UUIDs in NBT as four ints, most significant first.
+#[must_use]
+pub fn uuid_to_nbt(uuid: uuid::Uuid) -> NbtTag {
+    let uuid = uuid.as_u128();
+    NbtTag::IntArray(vec![
+        (uuid >> 96) as i32,
+        ((uuid >> 64) & 0xFFFF_FFFF) as i32,
+        ((uuid >> 32) & 0xFFFF_FFFF) as i32,
+        (uuid & 0xFFFF_FFFF) as i32,
+    ])
+}
+
+#[must_use]
+pub fn uuid_from_nbt(ints: &[i32]) -> Option<uuid::Uuid> {
+    let [a, b, c, d] = ints else {
+        return None;
+    };
+    Some(uuid::Uuid::from_u128(
+        (u128::from(*a as u32) << 96)
+            | (u128::from(*b as u32) << 64)
+            | (u128::from(*c as u32) << 32)
+            | u128::from(*d as u32),
+    ))
+}
+
 #[async_trait]
 impl NBTStorage for Entity {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
@@ -1744,16 +2183,7 @@ impl NBTStorage for Entity {
             "id",
             format!("minecraft:{}", self.entity_type.resource_name),
         );
-        let uuid = self.entity_uuid.as_u128();
-        nbt.put(
-            "UUID",
-            NbtTag::IntArray(vec![
-                (uuid >> 96) as i32,
-                ((uuid >> 64) & 0xFFFF_FFFF) as i32,
-                ((uuid >> 32) & 0xFFFF_FFFF) as i32,
-                (uuid & 0xFFFF_FFFF) as i32,
-            ]),
-        );
+        nbt.put("UUID", uuid_to_nbt(self.entity_uuid));
         nbt.put(
             "Pos",
             NbtTag::List(vec![
@@ -1776,12 +2206,16 @@ impl NBTStorage for Entity {
             NbtTag::List(vec![self.yaw.load().into(), self.pitch.load().into()]),
         );
         nbt.put_short("Fire", self.fire_ticks.load(Relaxed) as i16);
+        nbt.put_short("Air", self.air.load(Relaxed) as i16);
         nbt.put_bool("OnGround", self.on_ground.load(Relaxed));
         nbt.put_bool("Invulnerable", self.invulnerable.load(Relaxed));
         nbt.put_int("PortalCooldown", self.portal_cooldown.load(Relaxed) as i32);
         if self.has_visual_fire.load(Relaxed) {
             nbt.put_bool("HasVisualFire", true);
         }
+        if self.no_gravity.load(Relaxed) {
+            nbt.put_bool("NoGravity", true);
+        }
 
         // todo more...
     }
@@ -1806,6 +2240,10 @@ impl NBTStorage for Entity {
         self.head_yaw.store(yaw);
         self.fire_ticks
             .store(i32::from(nbt.get_short("Fire").unwrap_or(0)), Relaxed);
+        self.air.store(
+            nbt.get_short("Air").map_or(Self::MAX_AIR, i32::from),
+            Relaxed,
+        );
         self.on_ground
             .store(nbt.get_bool("OnGround").unwrap_or(false), Relaxed);
         self.invulnerable
@@ -1814,6 +2252,8 @@ impl NBTStorage for Entity {
             .store(nbt.get_int("PortalCooldown").unwrap_or(0) as u32, Relaxed);
         self.has_visual_fire
             .store(nbt.get_bool("HasVisualFire").unwrap_or(false), Relaxed);
+        self.no_gravity
+            .store(nbt.get_bool("NoGravity").unwrap_or(false), Relaxed);
         // todo more...
     }
 }
@@ -1843,6 +2283,7 @@ impl EntityBase for Entity {
         }
         self.set_on_fire(self.fire_ticks.load(Ordering::Relaxed) > 0)
             .await;
+        self.position_passengers().await;
         // TODO: Tick
     }
 
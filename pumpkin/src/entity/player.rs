@@ -1,10 +1,11 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::f64::consts::TAU;
 use std::num::NonZeroU8;
 use std::ops::AddAssign;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU8, AtomicU32, Ordering};
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
@@ -15,7 +16,6 @@ use pumpkin_protocol::bedrock::client::update_abilities::{
     Ability, AbilityLayer, CUpdateAbilities,
 };
 use pumpkin_protocol::bedrock::server::text::SText;
-use pumpkin_world::chunk::{ChunkData, ChunkEntityData};
 use pumpkin_world::inventory::Inventory;
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
@@ -30,32 +30,35 @@ use pumpkin_data::entity::{EntityPose, EntityStatus, EntityType};
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::tag::Taggable;
-use pumpkin_data::{Block, BlockState, tag};
+use pumpkin_data::{Block, BlockState, Enchantment, tag};
+use pumpkin_inventory::crafting::recipes::RecipeBook;
 use pumpkin_inventory::player::{
     player_inventory::PlayerInventory, player_screen_handler::PlayerScreenHandler,
 };
 use pumpkin_inventory::screen_handler::{
     InventoryPlayer, ScreenHandler, ScreenHandlerBehaviour, ScreenHandlerFactory,
-    ScreenHandlerListener,
+    ScreenHandlerListener, listen_to_inventories,
 };
 use pumpkin_inventory::sync_handler::SyncHandler;
 use pumpkin_macros::send_cancellable;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::tag::NbtTag;
-use pumpkin_protocol::IdOr;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::play::{
     Animation, CAcknowledgeBlockChange, CActionBar, CChangeDifficulty, CChunkBatchEnd,
-    CChunkBatchStart, CChunkData, CCloseContainer, CCombatDeath, CDisguisedChatMessage,
-    CEntityAnimation, CEntityPositionSync, CGameEvent, CKeepAlive, COpenScreen, CParticle,
-    CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CPlayerSpawnPosition, CRespawn,
+    CChunkBatchStart, CChunkData, CCloseContainer, CCombatDeath, CCustomPayload,
+    CDisguisedChatMessage, CEntityAnimation, CEntityPositionSync, CGameEvent, CKeepAlive,
+    COpenScreen, CParticle, CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition,
+    CPlayerSpawnPosition, CRecipeBookAdd, CRecipeBookRemove, CRespawn, CSetCamera,
     CSetContainerContent, CSetContainerProperty, CSetContainerSlot, CSetCursorItem, CSetExperience,
     CSetHealth, CSetPlayerInventory, CSetSelectedSlot, CSoundEffect, CStopSound, CSubtitle,
     CSystemChatMessage, CTitleText, CUnloadChunk, CUpdateMobEffect, CUpdateTime, GameEvent,
-    MetaDataType, Metadata, PlayerAction, PlayerInfoFlags, PreviousMessage,
+    MetaDataType, Metadata, PlayerAction, PlayerInfoFlags, PreviousMessage, RecipeBookEntry,
 };
 use pumpkin_protocol::java::server::play::SClickSlot;
+use pumpkin_protocol::{IdOr, Property};
 use pumpkin_registry::VanillaDimensionType;
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::{
     boundingbox::BoundingBox, experience, position::BlockPos, vector2::Vector2, vector3::Vector3,
 };
@@ -63,7 +66,9 @@ use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::click::ClickEvent;
+use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::text::hover::HoverEvent;
+use pumpkin_util::translation::Locale;
 use pumpkin_util::{GameMode, Hand};
 use pumpkin_world::biome;
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
@@ -77,20 +82,23 @@ use crate::block::blocks::bed::BedBlock;
 use crate::command::client_suggestions;
 use crate::command::dispatcher::CommandDispatcher;
 use crate::data::op_data::OPERATOR_CONFIG;
-use crate::net::{ClientPlatform, GameProfile};
-use crate::net::{DisconnectReason, PlayerConfig};
+use crate::net::{ClientPlatform, GameProfile, TEXTURES_PROPERTY};
+use crate::net::{DisconnectReason, PlayerConfig, java::KEEP_ALIVE_INTERVAL};
+use crate::plugin::player::player_afk_change::PlayerAfkChangeEvent;
 use crate::plugin::player::player_change_world::PlayerChangeWorldEvent;
 use crate::plugin::player::player_gamemode_change::PlayerGamemodeChangeEvent;
 use crate::plugin::player::player_teleport::PlayerTeleportEvent;
 use crate::server::Server;
-use crate::world::World;
-use crate::{PERMISSION_MANAGER, block};
+use crate::world::{World, chunker, raid};
+use crate::{PERMISSION_MANAGER, PLUGIN_MANAGER, block};
 
 use super::combat::{self, AttackType, player_attack_sound};
+use super::experience_orb::ExperienceOrbEntity;
 use super::hunger::HungerManager;
 use super::item::ItemEntity;
 use super::living::LivingEntity;
-use super::{Entity, EntityBase, NBTStorage, NBTStorageInit};
+use super::riding::{spawn_with_passengers, write_nbt_with_passengers};
+use super::{Entity, EntityBase, NBTStorage, NBTStorageInit, uuid_from_nbt, uuid_to_nbt};
 use pumpkin_data::potion::Effect;
 
 const MAX_CACHED_SIGNATURES: u8 = 128; // Vanilla: 128
@@ -104,33 +112,118 @@ enum BatchState {
     Count(u8),
 }
 
+/// A chunk waiting to be sent, with the tick of the chunk manager it was queued in.
+struct QueuedChunk {
+    position: Vector2<i32>,
+    chunk: SyncChunk,
+    queued_at: u64,
+}
+
+/// Counters about the chunks sent to a player.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChunkSendStats {
+    pub chunks_sent: u64,
+    pub batches_sent: u64,
+    pub batches_acknowledged: u64,
+    /// The most ticks a chunk waited in the queue before being sent.
+    pub max_wait_ticks: u64,
+    /// The estimated number of chunks per tick the connection can take, if measured yet.
+    pub estimated_chunks_per_tick: Option<f32>,
+}
+
 pub struct ChunkManager {
+    /// The chunks per tick the client asked for.
     chunks_per_tick: usize,
-    chunk_queue: VecDeque<(Vector2<i32>, SyncChunk)>,
+    chunk_queue: Vec<QueuedChunk>,
     entity_chunk_queue: VecDeque<(Vector2<i32>, SyncEntityChunk)>,
     batches_sent_since_ack: BatchState,
+    /// Counts the calls to [`ChunkManager::tick`], used to age queued chunks.
+    current_tick: u64,
+    /// When each batch not yet acknowledged was sent and how many chunks it had.
+    unacknowledged_batches: VecDeque<(Instant, usize)>,
+    last_acknowledge: Option<Instant>,
+    stats: ChunkSendStats,
 }
 
 impl ChunkManager {
     pub const NOTCHIAN_BATCHES_WITHOUT_ACK_UNTIL_PAUSE: u8 = 10;
+    /// A queued chunk is sent as if it were one chunk closer for every this many ticks it waited,
+    /// so far away chunks are not put off forever by a player moving around.
+    const AGING_TICKS_PER_CHUNK: u64 = 20;
+    /// How much faster than the measured rate chunks are sent, so the rate can grow again once
+    /// the connection allows it.
+    const BUDGET_HEADROOM: f32 = 2.0;
+    /// The weight of a new sample in the rolling estimate of the connection's chunk rate.
+    const ESTIMATE_WEIGHT: f32 = 0.25;
 
     #[must_use]
     pub fn new(chunks_per_tick: usize) -> Self {
         Self {
             chunks_per_tick,
-            chunk_queue: VecDeque::new(),
+            chunk_queue: Vec::new(),
             entity_chunk_queue: VecDeque::new(),
             batches_sent_since_ack: BatchState::Initial,
+            current_tick: 0,
+            unacknowledged_batches: VecDeque::new(),
+            last_acknowledge: None,
+            stats: ChunkSendStats::default(),
         }
     }
 
+    pub fn tick(&mut self) {
+        self.current_tick += 1;
+    }
+
     pub fn handle_acknowledge(&mut self, chunks_per_tick: f32) {
         self.batches_sent_since_ack = BatchState::Count(0);
         self.chunks_per_tick = chunks_per_tick.ceil() as usize;
+        self.stats.batches_acknowledged += 1;
+
+        let now = Instant::now();
+        if let Some((sent_at, chunk_count)) = self.unacknowledged_batches.pop_front() {
+            // Batches are sent back to back, so the time since the last acknowledgement is how
+            // long the connection took for this one
+            let since = self
+                .last_acknowledge
+                .map_or(sent_at, |last_acknowledge| last_acknowledge.max(sent_at));
+            let ticks = (now.duration_since(since).as_secs_f32() * 20.0).max(1.0);
+            let rate = chunk_count as f32 / ticks;
+            let estimate = self
+                .stats
+                .estimated_chunks_per_tick
+                .map_or(rate, |estimate| {
+                    estimate + (rate - estimate) * Self::ESTIMATE_WEIGHT
+                });
+            self.stats.estimated_chunks_per_tick = Some(estimate);
+        }
+        self.last_acknowledge = Some(now);
+    }
+
+    /// The chunks to send per tick, what the client asked for unless the connection seems too
+    /// slow for it.
+    #[must_use]
+    pub fn chunk_budget(&self) -> usize {
+        let budget =
+            self.stats
+                .estimated_chunks_per_tick
+                .map_or(self.chunks_per_tick, |estimate| {
+                    self.chunks_per_tick
+                        .min((estimate * Self::BUDGET_HEADROOM).ceil() as usize)
+                });
+        budget.max(1)
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> ChunkSendStats {
+        self.stats
     }
 
     pub fn push_chunk(&mut self, position: Vector2<i32>, chunk: SyncChunk) {
-        self.chunk_queue.push_back((position, chunk));
+        self.chunk_queue.push(QueuedChunk {
+            position,
+            chunk,
+            queued_at: self.current_tick,
+        });
     }
 
     pub fn push_entity(&mut self, position: Vector2<i32>, chunk: SyncEntityChunk) {
@@ -148,13 +241,32 @@ impl ChunkManager {
         state_available && !self.chunk_queue.is_empty()
     }
 
-    pub fn next_chunk(&mut self) -> Box<[SyncChunk]> {
-        let chunk_size = self.chunk_queue.len().min(self.chunks_per_tick);
-        let chunks: Vec<Arc<RwLock<ChunkData>>> = self
-            .chunk_queue
-            .drain(0..chunk_size)
-            .map(|(_, chunk)| chunk)
-            .collect();
+    /// Lower is sent first: the distance to `center` in chunks, minus the time the chunk waited.
+    fn priority(&self, queued: &QueuedChunk, center: Vector2<i32>) -> i64 {
+        let distance = (queued.position.x - center.x)
+            .abs()
+            .max((queued.position.y - center.y).abs());
+        let age = (self.current_tick - queued.queued_at) / Self::AGING_TICKS_PER_CHUNK;
+        i64::from(distance) - age as i64
+    }
+
+    /// Takes the next chunks to send to a player in the chunk at `center`, the closest and the
+    /// longest waiting ones first.
+    pub fn next_chunk(&mut self, center: Vector2<i32>) -> Box<[SyncChunk]> {
+        let chunk_size = self.chunk_queue.len().min(self.chunk_budget());
+        if chunk_size < self.chunk_queue.len() {
+            let mut queue = std::mem::take(&mut self.chunk_queue);
+            queue.select_nth_unstable_by_key(chunk_size, |queued| self.priority(queued, center));
+            self.chunk_queue = queue;
+        }
+        let mut chunks = Vec::with_capacity(chunk_size);
+        for queued in self.chunk_queue.drain(0..chunk_size) {
+            self.stats.max_wait_ticks = self
+                .stats
+                .max_wait_ticks
+                .max(self.current_tick - queued.queued_at);
+            chunks.push(queued.chunk);
+        }
 
         match &mut self.batches_sent_since_ack {
             BatchState::Count(count) => {
@@ -164,12 +276,25 @@ impl ChunkManager {
             BatchState::Waiting => (),
         }
 
+        if !chunks.is_empty() {
+            self.stats.chunks_sent += chunks.len() as u64;
+            self.stats.batches_sent += 1;
+            // Clients that never acknowledge batches must not grow this forever
+            if self.unacknowledged_batches.len()
+                >= usize::from(Self::NOTCHIAN_BATCHES_WITHOUT_ACK_UNTIL_PAUSE)
+            {
+                self.unacknowledged_batches.pop_front();
+            }
+            self.unacknowledged_batches
+                .push_back((Instant::now(), chunks.len()));
+        }
+
         chunks.into_boxed_slice()
     }
 
     pub fn next_entity(&mut self) -> Box<[SyncEntityChunk]> {
         let chunk_size = self.entity_chunk_queue.len().min(self.chunks_per_tick);
-        let chunks: Vec<Arc<RwLock<ChunkEntityData>>> = self
+        let chunks: Vec<SyncEntityChunk> = self
             .entity_chunk_queue
             .drain(0..chunk_size)
             .map(|(_, chunk)| chunk)
@@ -190,7 +315,9 @@ impl ChunkManager {
     pub fn is_chunk_pending(&self, pos: &Vector2<i32>) -> bool {
         // This is probably comparable to hashmap speed due to the relatively small count of chunks
         // (guestimated to be ~ 1024)
-        self.chunk_queue.iter().any(|(elem_pos, _)| elem_pos == pos)
+        self.chunk_queue
+            .iter()
+            .any(|queued| queued.position == *pos)
     }
 }
 
@@ -202,10 +329,15 @@ pub struct Player {
     pub living_entity: LivingEntity,
     /// The player's game profile information, including their username and UUID.
     pub gameprofile: GameProfile,
+    /// The profile properties shown to everyone, like the skin and cape textures. Starts out as
+    /// the properties of the game profile.
+    profile_properties: RwLock<Vec<Property>>,
     /// The client connection associated with the player.
     pub client: ClientPlatform,
     /// The player's inventory.
     pub inventory: Arc<PlayerInventory>,
+    /// The recipes the player has unlocked, which limit crafting with `doLimitedCrafting`.
+    pub recipe_book: Arc<RecipeBook>,
     /// The player's configuration settings. Changes when the player changes their settings.
     pub config: RwLock<PlayerConfig>,
     /// The player's current gamemode (e.g., Survival, Creative, Adventure).
@@ -216,6 +348,8 @@ pub struct Player {
     pub respawn_point: AtomicCell<Option<RespawnPoint>>,
     /// The player's sleep status
     pub sleeping_since: AtomicCell<Option<u8>>,
+    /// Where the player got Raid Omen, the raid starts there once the effect runs out
+    pub raid_omen_position: AtomicCell<Option<BlockPos>>,
     /// Manages the player's hunger level.
     pub hunger_manager: HungerManager,
     /// The ID of the currently open container (if any).
@@ -248,6 +382,12 @@ pub struct Player {
     pub keep_alive_id: AtomicI64,
     /// The last time we sent a keep alive packet.
     pub last_keep_alive_time: AtomicCell<Instant>,
+    /// The rolling average of the keep alive round trip time in milliseconds.
+    pub ping: AtomicU32,
+    /// When the player last did something themselves, like moving, chatting or using an item.
+    pub last_action_time: AtomicCell<Instant>,
+    /// Whether the player has been idle for long enough to count as away from keyboard.
+    afk: AtomicBool,
     /// The amount of ticks since the player's last attack.
     pub last_attacked_ticks: AtomicU32,
     /// The player's last known experience level.
@@ -268,12 +408,18 @@ pub struct Player {
     /// The player's total experience points.
     pub experience_points: AtomicI32,
     pub experience_pick_up_delay: Mutex<u32>,
-    pub chunk_manager: Mutex<ChunkManager>,
+    pub chunk_manager: OrderedMutex<ChunkManager>,
     pub has_played_before: AtomicBool,
+    /// The vehicle the player logged out in, spawned again once they are back in the world.
+    saved_vehicle: Mutex<Option<NbtCompound>>,
+    /// Where the player was when they last left each world, keyed by [`World::name`].
+    pub world_positions: Mutex<HashMap<String, WorldPosition>>,
+    /// The entity a spectator views the world from, `None` when it is the player itself.
+    camera: Mutex<Option<Arc<dyn EntityBase>>>,
     pub chat_session: Arc<Mutex<ChatSession>>,
     pub signature_cache: Mutex<MessageCache>,
     pub player_screen_handler: Arc<Mutex<PlayerScreenHandler>>,
-    pub current_screen_handler: Mutex<Arc<Mutex<dyn ScreenHandler>>>,
+    pub current_screen_handler: OrderedMutex<Arc<Mutex<dyn ScreenHandler>>>,
     pub screen_handler_sync_id: AtomicU8,
     pub screen_handler_listener: Arc<dyn ScreenHandlerListener>,
     pub screen_handler_sync_handler: Arc<SyncHandler>,
@@ -308,21 +454,27 @@ impl Player {
             world,
             Vector3::new(0.0, 100.0, 0.0),
             &EntityType::PLAYER,
-            matches!(gamemode, GameMode::Creative | GameMode::Spectator),
+            !gamemode.takes_damage(),
         ));
+        living_entity
+            .entity
+            .no_clip
+            .store(!gamemode.has_collision(), Ordering::Relaxed);
 
         let inventory = Arc::new(PlayerInventory::new(
             living_entity.entity_equipment.clone(),
             living_entity.equipment_slots.clone(),
         ));
 
+        let recipe_book = Arc::new(RecipeBook::default());
         let player_screen_handler = Arc::new(Mutex::new(
-            PlayerScreenHandler::new(&inventory, None, 0).await,
+            PlayerScreenHandler::new(&inventory, None, 0, Some(recipe_book.clone())).await,
         ));
 
         Self {
             living_entity,
             config: RwLock::new(config),
+            profile_properties: RwLock::new(gameprofile.properties.clone()),
             gameprofile,
             client,
             awaiting_teleport: Mutex::new(None),
@@ -344,6 +496,7 @@ impl Player {
             // TODO: Send the CPlayerSpawnPosition packet when the client connects with proper values
             respawn_point: AtomicCell::new(None),
             sleeping_since: AtomicCell::new(None),
+            raid_omen_position: AtomicCell::new(None),
             // We want this to be an impossible watched section so that `chunker::update_position`
             // will mark chunks as watched for a new join rather than a respawn.
             // (We left shift by one so we can search around that chunk)
@@ -355,6 +508,9 @@ impl Player {
             wait_for_keep_alive: AtomicBool::new(false),
             keep_alive_id: AtomicI64::new(0),
             last_keep_alive_time: AtomicCell::new(std::time::Instant::now()),
+            ping: AtomicU32::new(0),
+            last_action_time: AtomicCell::new(std::time::Instant::now()),
+            afk: AtomicBool::new(false),
             last_attacked_ticks: AtomicU32::new(0),
             client_loaded: AtomicBool::new(false),
             client_loaded_timeout: AtomicU32::new(60),
@@ -365,21 +521,28 @@ impl Player {
                 |op| AtomicCell::new(op.level),
             ),
             inventory,
+            recipe_book,
             // TODO: enderChestInventory
             experience_level: AtomicI32::new(0),
             experience_progress: AtomicCell::new(0.0),
             experience_points: AtomicI32::new(0),
             // Default to sending 16 chunks per tick.
-            chunk_manager: Mutex::new(ChunkManager::new(16)),
+            chunk_manager: OrderedMutex::new("player.chunk_manager", ChunkManager::new(16)),
             last_sent_xp: AtomicI32::new(-1),
             last_sent_health: AtomicI32::new(-1),
             last_sent_food: AtomicU8::new(0),
             last_food_saturation: AtomicBool::new(true),
             has_played_before: AtomicBool::new(false),
+            saved_vehicle: Mutex::new(None),
+            world_positions: Mutex::new(HashMap::new()),
+            camera: Mutex::new(None),
             chat_session: Arc::new(Mutex::new(ChatSession::default())), // Placeholder value until the player actually sets their session id
             signature_cache: Mutex::new(MessageCache::default()),
             player_screen_handler: player_screen_handler.clone(),
-            current_screen_handler: Mutex::new(player_screen_handler),
+            current_screen_handler: OrderedMutex::new(
+                "player.current_screen_handler",
+                player_screen_handler,
+            ),
             screen_handler_sync_id: AtomicU8::new(0),
             screen_handler_listener: Arc::new(ScreenListener {}),
             screen_handler_sync_handler: Arc::new(SyncHandler::new()),
@@ -403,9 +566,53 @@ impl Player {
         &self.inventory
     }
 
+    /// Takes the vehicle of the player out of the world when they leave, it is saved with them
+    /// instead. The vehicle stays if another player still rides it.
+    async fn remove_vehicle(&self) {
+        let entity = self.get_entity();
+        let Some(root_vehicle) = entity.root_vehicle().await else {
+            return;
+        };
+        entity.detach_from_vehicle().await;
+        if Entity::has_player_passenger(&root_vehicle).await {
+            return;
+        }
+        let world = self.world();
+        for vehicle in Entity::self_and_passengers(&root_vehicle).await {
+            world.remove_entity(vehicle.get_entity()).await;
+        }
+    }
+
+    /// Spawns the vehicle the player logged out in and seats them on it again.
+    pub async fn mount_saved_vehicle(self: &Arc<Self>) {
+        let Some(root_vehicle) = self.saved_vehicle.lock().await.take() else {
+            return;
+        };
+        let Some(entity_nbt) = root_vehicle.get_compound("Entity") else {
+            return;
+        };
+        let Some(root) = spawn_with_passengers(self.world(), entity_nbt).await else {
+            log::warn!(
+                "Could not spawn the saved vehicle of {}",
+                self.gameprofile.name
+            );
+            return;
+        };
+
+        let attach = root_vehicle.get_int_array("Attach").and_then(uuid_from_nbt);
+        let vehicle = Entity::self_and_passengers(&root)
+            .await
+            .into_iter()
+            .find(|vehicle| Some(vehicle.get_entity().entity_uuid) == attach)
+            .unwrap_or(root);
+        let player: Arc<dyn EntityBase> = self.clone();
+        Entity::start_riding(&player, &vehicle).await;
+    }
+
     /// Removes the [`Player`] out of the current [`World`].
     pub async fn remove(self: &Arc<Self>) {
         let world = self.world();
+        self.remove_vehicle().await;
         world.remove_player(self, true).await;
 
         let cylindrical = self.watched_section.load();
@@ -425,7 +632,7 @@ impl Player {
         // Decrement the value of watched chunks
         let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks).await;
         // Remove chunks with no watchers from the cache
-        level.clean_chunks(&chunks_to_clean).await;
+        world.clean_chunks(&chunks_to_clean).await;
         level.clean_entity_chunks(&chunks_to_clean).await;
         // Remove left over entries from all possiblily loaded chunks
         level.clean_memory();
@@ -529,8 +736,7 @@ impl Player {
                 _ => {}
             }
             if config.knockback {
-                combat::handle_knockback(attacker_entity, world, victim_entity, knockback_strength)
-                    .await;
+                combat::handle_knockback(attacker_entity, victim_entity, knockback_strength).await;
             }
         }
 
@@ -605,6 +811,7 @@ impl Player {
         self.get_entity().set_velocity(Vector3::default()).await;
 
         self.sleeping_since.store(Some(0));
+        self.world().update_sleeping_status().await;
     }
 
     pub async fn get_off_ground_speed(&self) -> f64 {
@@ -662,6 +869,7 @@ impl Player {
             .await;
 
         self.sleeping_since.store(None);
+        world.update_sleeping_status().await;
     }
 
     pub async fn show_title(&self, text: &TextComponent, mode: &TitleMode) {
@@ -739,7 +947,7 @@ impl Player {
             .await
             .lock()
             .await
-            .send_content_updates()
+            .send_polled_content_updates()
             .await;
 
         // if self.client.closed.load(Ordering::Relaxed) {
@@ -761,15 +969,17 @@ impl Player {
         }
 
         let chunk_of_chunks = {
+            let center = self.living_entity.entity.chunk_pos.load();
             let mut chunk_manager = self.chunk_manager.lock().await;
+            chunk_manager.tick();
             if let ClientPlatform::Java(_) = self.client {
                 // Java clients can only send a limited amount of chunks per tick.
                 // If we have sent too many chunks without receiving an ack, we stop sending chunks.
                 chunk_manager
                     .can_send_chunk()
-                    .then(|| chunk_manager.next_chunk())
+                    .then(|| chunk_manager.next_chunk(center))
             } else {
-                Some(chunk_manager.next_chunk())
+                Some(chunk_manager.next_chunk(center))
             }
         };
 
@@ -778,11 +988,21 @@ impl Player {
             match &self.client {
                 ClientPlatform::Java(java_client) => {
                     java_client.send_packet_now(&CChunkBatchStart).await;
+                    let world = self.world();
                     for chunk in chunk_of_chunks {
                         let chunk = chunk.read().await;
+                        let obfuscated = world
+                            .anti_xray
+                            .as_ref()
+                            .and_then(|anti_xray| anti_xray.obfuscate(&chunk.section));
                         // TODO: Can we check if we still need to send the chunk? Like if it's a fast moving
                         // player or something.
-                        java_client.send_packet_now(&CChunkData(&chunk)).await;
+                        java_client
+                            .send_packet_now(&CChunkData(
+                                &chunk,
+                                obfuscated.as_ref().unwrap_or(&chunk.section),
+                            ))
+                            .await;
                     }
                     java_client
                         .send_packet_now(&CChunkBatchEnd::new(chunk_count as u16))
@@ -804,7 +1024,9 @@ impl Player {
             }
         }
 
-        self.tick_counter.fetch_add(1, Ordering::Relaxed);
+        if self.tick_counter.fetch_add(1, Ordering::Relaxed) % 20 == 0 {
+            self.update_recipe_book().await;
+        }
         if let Some(sleeping_since) = self.sleeping_since.load()
             && sleeping_since < 101
         {
@@ -836,6 +1058,9 @@ impl Player {
 
         self.last_attacked_ticks.fetch_add(1, Ordering::Relaxed);
 
+        self.tick_camera().await;
+        raid::tick_player_omens(self).await;
+        self.living_entity.entity.update_swimming().await;
         self.living_entity.tick(self.clone(), server).await;
         self.hunger_manager.tick(self).await;
 
@@ -845,10 +1070,13 @@ impl Player {
 
         // Timeout/keep alive handling
         self.tick_client_load_timeout();
+        if self.tick_idle().await {
+            return;
+        }
 
         // TODO This should only be handled by the ClientPlatform
         let now = Instant::now();
-        if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
+        if now.duration_since(self.last_keep_alive_time.load()) >= KEEP_ALIVE_INTERVAL {
             if matches!(self.client, ClientPlatform::Bedrock(_)) {
                 return;
             }
@@ -863,7 +1091,7 @@ impl Player {
             }
             self.wait_for_keep_alive.store(true, Ordering::Relaxed);
             self.last_keep_alive_time.store(now);
-            let id = now.elapsed().as_millis() as i64;
+            let id = rand::random();
             self.keep_alive_id.store(id, Ordering::Relaxed);
             self.client.enqueue_packet(&CKeepAlive::new(id)).await;
         }
@@ -899,10 +1127,24 @@ impl Player {
 
     #[expect(clippy::cast_precision_loss)]
     pub async fn progress_motion(&self, delta_pos: Vector3<f64>) {
-        // TODO: Swimming, gliding...
-        if self.living_entity.entity.on_ground.load(Ordering::Relaxed) {
+        // TODO: Climbing, gliding...
+        let entity = &self.living_entity.entity;
+        if entity.swimming.load(Ordering::Relaxed)
+            || entity.submerged_in_water.load(Ordering::Relaxed)
+        {
+            let delta = (delta_pos.length() * 100.0).round() as i32;
+            if delta > 0 {
+                self.add_exhaustion(0.01 * delta as f32 * 0.01).await;
+            }
+        } else if entity.touching_water.load(Ordering::Relaxed) {
+            let delta = (delta_pos.horizontal_length() * 100.0).round() as i32;
+            if delta > 0 {
+                self.add_exhaustion(0.01 * delta as f32 * 0.01).await;
+            }
+        } else if entity.on_ground.load(Ordering::Relaxed) {
             let delta = (delta_pos.horizontal_length() * 100.0).round() as i32;
             if delta > 0 {
+                entity.play_crystal_chime(self).await;
                 if self.living_entity.entity.sprinting.load(Ordering::Relaxed) {
                     self.add_exhaustion(0.1 * delta as f32 * 0.01).await;
                 } else {
@@ -1099,11 +1341,11 @@ impl Player {
         }
     }
 
-    async fn unload_watched_chunks(&self, world: &World) {
+    async fn unload_watched_chunks(&self, world: &Arc<World>) {
         let radial_chunks = self.watched_section.load().all_chunks_within();
         let level = &world.level;
         let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks).await;
-        level.clean_chunks(&chunks_to_clean).await;
+        world.clean_chunks(&chunks_to_clean).await;
         for chunk in chunks_to_clean {
             self.client
                 .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
@@ -1148,15 +1390,30 @@ impl Player {
 
                 self.set_client_loaded(false);
                 let uuid = self.gameprofile.id;
+                self.world_positions.lock().await.insert(
+                    current_world.name.clone(),
+                    WorldPosition {
+                        position: self.position(),
+                        yaw: self.living_entity.entity.yaw.load(),
+                        pitch: self.living_entity.entity.pitch.load(),
+                    },
+                );
                 current_world.remove_player(self, false).await;
-                new_world.players.write().await.insert(uuid, Arc::new(Self::new(
-                            self.client.clone(),
-                            self.gameprofile.clone(),
-                            self.config.read().await.clone(),
-                            new_world.clone(),
-                            self.gamemode.load(),
-                        )
-                        .await));
+                let new_player = Self::new(
+                    self.client.clone(),
+                    self.gameprofile.clone(),
+                    self.config.read().await.clone(),
+                    new_world.clone(),
+                    self.gamemode.load(),
+                )
+                .await;
+                *new_player.profile_properties.write().await = self.profile_properties().await;
+                new_player
+                    .world_positions
+                    .lock()
+                    .await
+                    .clone_from(&*self.world_positions.lock().await);
+                new_world.players.write().await.insert(uuid, Arc::new(new_player));
                 self.unload_watched_chunks(&current_world).await;
 
                 let last_pos = self.living_entity.entity.last_pos.load();
@@ -1236,12 +1493,65 @@ impl Player {
         }}
     }
 
-    pub fn block_interaction_range(&self) -> f64 {
-        if self.gamemode.load() == GameMode::Creative {
-            5.0
-        } else {
-            4.5
+    /// The entity the player views the world from, `None` if it is the player itself.
+    pub async fn camera(&self) -> Option<Arc<dyn EntityBase>> {
+        self.camera.lock().await.clone()
+    }
+
+    /// Makes the player view the world from `camera`, or from the player itself again if it is
+    /// `None`. The player is moved to the camera so the chunks around it are sent.
+    pub async fn set_camera(self: &Arc<Self>, camera: Option<Arc<dyn EntityBase>>) {
+        let camera = camera.filter(|camera| camera.get_entity().entity_id != self.entity_id());
+        let camera_id = camera
+            .as_ref()
+            .map_or(self.entity_id(), |camera| camera.get_entity().entity_id);
+        {
+            let mut current = self.camera.lock().await;
+            let current_id = current
+                .as_ref()
+                .map_or(self.entity_id(), |camera| camera.get_entity().entity_id);
+            if current_id == camera_id {
+                return;
+            }
+            current.clone_from(&camera);
+        }
+
+        if let Some(camera) = &camera {
+            let entity = camera.get_entity();
+            self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+                .await;
+            chunker::update_position(self).await;
         }
+        self.client
+            .enqueue_packet(&CSetCamera::new(camera_id.into()))
+            .await;
+    }
+
+    /// Keeps a spectator at its camera, and gives it its own view back once it sneaks or the
+    /// camera is gone.
+    async fn tick_camera(self: &Arc<Self>) {
+        let Some(camera) = self.camera().await else {
+            return;
+        };
+        let entity = camera.get_entity();
+        let is_gone = entity.removed.load(Ordering::Relaxed)
+            || !Arc::ptr_eq(&entity.world, self.world())
+            || camera
+                .get_living_entity()
+                .is_some_and(|living| living.dead.load(Ordering::Relaxed));
+        if is_gone || self.living_entity.entity.sneaking.load(Ordering::Relaxed) {
+            self.set_camera(None).await;
+            return;
+        }
+
+        let player = &self.living_entity.entity;
+        player.set_pos(entity.pos.load());
+        player.set_rotation(entity.yaw.load(), entity.pitch.load());
+        chunker::update_position(self).await;
+    }
+
+    pub fn block_interaction_range(&self) -> f64 {
+        self.gamemode.load().block_interaction_range()
     }
 
     pub fn can_interact_with_block_at(&self, position: &BlockPos, additional_range: f64) -> bool {
@@ -1260,6 +1570,102 @@ impl Player {
         self.client.kick(reason, message).await;
     }
 
+    /// Marks that the player did something themselves, which ends being idle.
+    pub fn reset_last_action_time(&self) {
+        self.last_action_time.store(Instant::now());
+    }
+
+    /// Whether the player has been idle for long enough to count as away from keyboard.
+    pub fn is_afk(&self) -> bool {
+        self.afk.load(Ordering::Relaxed)
+    }
+
+    /// The name the tab list shows for the player, `None` for their own name.
+    pub fn tab_list_name(&self) -> Option<TextComponent> {
+        (self.is_afk() && advanced_config().afk.mark_in_tab_list).then(|| {
+            TextComponent::text(self.gameprofile.name.clone())
+                .add_child(TextComponent::text(" [AFK]").color_named(NamedColor::Gray))
+        })
+    }
+
+    /// Updates the AFK state and kicks players idle for longer than `player_idle_timeout`.
+    /// Returns whether the player was kicked.
+    async fn tick_idle(self: &Arc<Self>) -> bool {
+        let idle = self.last_action_time.load().elapsed();
+
+        let afk_after = u64::from(advanced_config().afk.after_seconds);
+        let afk = afk_after > 0 && idle.as_secs() >= afk_after;
+        if afk != self.afk.swap(afk, Ordering::Relaxed) {
+            PLUGIN_MANAGER
+                .fire(PlayerAfkChangeEvent::new(self.clone(), afk))
+                .await;
+            if advanced_config().afk.mark_in_tab_list {
+                self.world()
+                    .broadcast_packet_all(&CPlayerInfoUpdate::new(
+                        PlayerInfoFlags::UPDATE_DISPLAY_NAME.bits(),
+                        &[pumpkin_protocol::java::client::play::Player {
+                            uuid: self.gameprofile.id,
+                            actions: &[PlayerAction::UpdateDisplayName(self.tab_list_name())],
+                        }],
+                    ))
+                    .await;
+            }
+        }
+
+        let timeout = u64::from(BASIC_CONFIG.player_idle_timeout) * 60;
+        if timeout == 0
+            || idle.as_secs() < timeout
+            || self.has_permission("pumpkin:idle_timeout.exempt").await
+        {
+            return false;
+        }
+        self.kick(
+            DisconnectReason::KickedForIdle,
+            TextComponent::translate("multiplayer.disconnect.idling", []),
+        )
+        .await;
+        true
+    }
+
+    /// Makes the player join the server at `host` and `port` instead, which has to accept
+    /// transfers. Only Java players can be transferred.
+    pub async fn transfer(&self, host: &str, port: u16) {
+        if let ClientPlatform::Java(java) = &self.client {
+            java.transfer(host, port).await;
+        }
+    }
+
+    /// Stores a cookie on the player's client that servers it is transferred to can request.
+    /// Returns `false` if the cookie could not be stored, e.g. because it is too large.
+    pub async fn store_cookie(&self, key: &ResourceLocation, payload: &[u8]) -> bool {
+        match &self.client {
+            ClientPlatform::Java(java) => java.store_cookie(key, payload).await,
+            ClientPlatform::Bedrock(_) => false,
+        }
+    }
+
+    /// Requests the cookie stored under `key` from the player's client.
+    pub async fn request_cookie(&self, key: &ResourceLocation) -> Option<Box<[u8]>> {
+        match &self.client {
+            ClientPlatform::Java(java) => java.request_cookie(key).await,
+            ClientPlatform::Bedrock(_) => None,
+        }
+    }
+
+    /// The player's latency in milliseconds, as shown in the tab list.
+    pub fn ping(&self) -> u32 {
+        self.ping.load(Ordering::Relaxed)
+    }
+
+    /// Adds the round trip time of a keep alive to the rolling average of the latency.
+    pub fn update_ping(&self, round_trip_millis: u32) {
+        let ping = self.ping.load(Ordering::Relaxed);
+        self.ping.store(
+            ping.saturating_mul(3).saturating_add(round_trip_millis) / 4,
+            Ordering::Relaxed,
+        );
+    }
+
     pub fn can_food_heal(&self) -> bool {
         let health = self.living_entity.health.load();
         let max_health = 20.0; // TODO
@@ -1267,7 +1673,7 @@ impl Player {
     }
 
     pub async fn add_exhaustion(&self, exhaustion: f32) {
-        if self.abilities.lock().await.invulnerable {
+        if !self.gamemode.load().has_hunger() {
             return;
         }
         self.hunger_manager.add_exhaustion(exhaustion);
@@ -1319,8 +1725,99 @@ impl Player {
         }
     }
 
+    /// Unlocks the recipes for the items the player got since the last update and applies the
+    /// `doLimitedCrafting` game rule.
+    async fn update_recipe_book(&self) {
+        let limited_crafting = self
+            .world()
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .do_limited_crafting;
+        self.recipe_book.set_limited_crafting(limited_crafting);
+
+        let mut unlocked = Vec::new();
+        for slot in 0..self.inventory.size() {
+            let stack = self.inventory.get_stack(slot).await;
+            let stack = stack.lock().await;
+            if !stack.is_empty() {
+                unlocked.extend(self.recipe_book.unlock_recipes_using(stack.item));
+            }
+        }
+        self.send_unlocked_recipes(&unlocked).await;
+    }
+
+    /// Sends all unlocked recipes to the client, replacing its recipe book.
+    pub async fn send_recipe_book(&self) {
+        let entries = self
+            .recipe_book
+            .unlocked()
+            .iter()
+            .filter_map(|id| RecipeBook::display_entry(id, 0))
+            .collect();
+        self.client
+            .enqueue_packet(&CRecipeBookAdd::new(entries, true))
+            .await;
+    }
+
+    /// Unlocks the recipes with the given ids and tells the client about the ones that were
+    /// locked before.
+    pub async fn unlock_recipes(&self, ids: &[&str]) {
+        let unlocked: Vec<_> = ids
+            .iter()
+            .copied()
+            .filter(|id| self.recipe_book.unlock(id))
+            .collect();
+        self.send_unlocked_recipes(&unlocked).await;
+    }
+
+    /// Locks the recipes with the given ids again and removes the ones that were unlocked from the
+    /// client's recipe book.
+    pub async fn lock_recipes(&self, ids: &[&str]) {
+        let display_ids: Vec<_> = ids
+            .iter()
+            .filter(|id| self.recipe_book.lock(id))
+            .filter_map(|id| RecipeBook::display_id(id))
+            .collect();
+        if !display_ids.is_empty() {
+            self.client
+                .enqueue_packet(&CRecipeBookRemove::new(&display_ids))
+                .await;
+        }
+    }
+
+    /// Adds newly unlocked recipes to the client's recipe book, highlighted as new.
+    async fn send_unlocked_recipes(&self, ids: &[&str]) {
+        let entries: Vec<_> = ids
+            .iter()
+            .filter_map(|id| {
+                let mut flags = RecipeBookEntry::HIGHLIGHT;
+                if RecipeBook::shows_notification(id) {
+                    flags |= RecipeBookEntry::SHOW_NOTIFICATION;
+                }
+                RecipeBook::display_entry(id, flags)
+            })
+            .collect();
+        if !entries.is_empty() {
+            self.client
+                .enqueue_packet(&CRecipeBookAdd::new(entries, false))
+                .await;
+        }
+    }
+
     async fn handle_killed(&self, death_msg: TextComponent) {
         self.set_client_loaded(false);
+        let keep_inventory = self
+            .world()
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .keep_inventory;
+        if !keep_inventory && !self.is_spectator() {
+            self.drop_inventory().await;
+        }
         self.client
             .send_packet_now(&CCombatDeath::new(self.entity_id().into(), &death_msg))
             .await;
@@ -1344,6 +1841,9 @@ impl Player {
             'after: {
                 let gamemode = event.new_gamemode;
                 self.gamemode.store(gamemode);
+                if gamemode != GameMode::Spectator {
+                    self.set_camera(None).await;
+                }
                 // TODO: Fix this when mojang fixes it
                 // This is intentional to keep the pure vanilla mojang experience
                 // self.previous_gamemode.store(self.previous_gamemode.load());
@@ -1354,10 +1854,14 @@ impl Player {
                 };
                 self.send_abilities_update().await;
 
-                self.living_entity.entity.invulnerable.store(
-                    matches!(gamemode, GameMode::Creative | GameMode::Spectator),
-                    Ordering::Relaxed,
-                );
+                self.living_entity
+                    .entity
+                    .invulnerable
+                    .store(!gamemode.takes_damage(), Ordering::Relaxed);
+                self.living_entity
+                    .entity
+                    .no_clip
+                    .store(!gamemode.has_collision(), Ordering::Relaxed);
                 self.living_entity
                     .entity
                     .world
@@ -1399,6 +1903,96 @@ impl Player {
             .await;
     }
 
+    /// The profile properties everyone sees, like the skin and cape textures.
+    pub async fn profile_properties(&self) -> Vec<Property> {
+        self.profile_properties.read().await.clone()
+    }
+
+    /// Replaces the profile properties everyone sees and refreshes the player for all clients.
+    pub async fn set_profile_properties(self: &Arc<Self>, properties: Vec<Property>) {
+        *self.profile_properties.write().await = properties;
+        self.refresh_skin().await;
+    }
+
+    /// Shows the skin and cape described by a base64 encoded `textures` property instead of the
+    /// player's own.
+    ///
+    /// Clients only show textures signed by Mojang, or unsigned ones hosted on Mojang's texture
+    /// server.
+    pub async fn set_textures(self: &Arc<Self>, value: String, signature: Option<String>) {
+        let mut properties = self.profile_properties().await;
+        properties.retain(|property| property.name != TEXTURES_PROPERTY);
+        properties.push(Property {
+            name: TEXTURES_PROPERTY.to_string(),
+            value,
+            signature,
+        });
+        self.set_profile_properties(properties).await;
+    }
+
+    /// Shows the skin and cape of the player's game profile again.
+    pub async fn reset_textures(self: &Arc<Self>) {
+        self.set_profile_properties(self.gameprofile.properties.clone())
+            .await;
+    }
+
+    /// Re-sends the player's list entry and entity, so every client (including the player's own)
+    /// shows the current profile properties.
+    pub async fn refresh_skin(self: &Arc<Self>) {
+        let world = self.world();
+        world.refresh_player(self).await;
+
+        // Clients only load their own skin when their player entity is created, so respawn it in
+        // place. The dimension does not change, so the client keeps its chunks.
+        let entity = &self.living_entity.entity;
+        self.client
+            .enqueue_packet(&CRespawn::new(
+                (world.dimension_type as u8).into(),
+                world.dimension_type.resource_location(),
+                biome::hash_seed(world.level.seed.0), // seed
+                self.gamemode.load() as u8,
+                self.previous_gamemode
+                    .load()
+                    .map_or(-1, |gamemode| gamemode as i8),
+                false,
+                false,
+                None,
+                VarInt(entity.portal_cooldown.load(Ordering::Relaxed) as i32),
+                world.sea_level.into(),
+                // Keep attributes and metadata
+                0x03,
+            ))
+            .await;
+        self.send_permission_lvl_update().await;
+        self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+            .await;
+        self.send_abilities_update().await;
+        self.enqueue_set_held_item_packet(&CSetSelectedSlot::new(
+            self.get_inventory().get_selected_slot() as i8,
+        ))
+        .await;
+        self.on_screen_handler_opened(self.player_screen_handler.clone())
+            .await;
+        self.send_health().await;
+        self.last_sent_xp.store(-1, Ordering::Relaxed);
+        self.tick_experience().await;
+        self.send_active_effects().await;
+    }
+
+    /// Shows everything the player holds and wears to everyone else.
+    pub async fn send_equipment(&self) {
+        let inventory = &self.inventory;
+        let mut equipment = vec![(
+            EquipmentSlot::MAIN_HAND,
+            inventory.held_item().lock().await.clone(),
+        )];
+        for slot in inventory.equipment_slots.values() {
+            let stack = inventory.entity_equipment.lock().await.get(slot);
+            equipment.push((slot.clone(), stack.lock().await.clone()));
+        }
+        self.living_entity.send_equipment_changes(&equipment).await;
+    }
+
     pub async fn can_harvest(&self, state: &BlockState, block: &'static Block) -> bool {
         !state.tool_required()
             || self
@@ -1434,7 +2028,20 @@ impl Player {
             };
             speed *= fatigue_speed;
         }
-        // TODO: Handle when in water
+        // Aqua affinity lets players mine under water as fast as on land
+        if self
+            .living_entity
+            .entity
+            .submerged_in_water
+            .load(Ordering::Relaxed)
+            && self
+                .living_entity
+                .get_equipment_enchantment_level(&EquipmentSlot::HEAD, &Enchantment::AQUA_AFFINITY)
+                .await
+                == 0
+        {
+            speed /= 5.0;
+        }
         if !self.living_entity.entity.on_ground.load(Ordering::Relaxed) {
             speed /= 5.0;
         }
@@ -1474,17 +2081,13 @@ impl Player {
             .await;
     }
 
-    pub async fn drop_item(&self, item_stack: ItemStack) {
-        let item_pos = self.living_entity.entity.pos.load()
-            + Vector3::new(0.0, f64::from(EntityType::PLAYER.eye_height) - 0.3, 0.0);
-        let entity = Entity::new(
-            Uuid::new_v4(),
-            self.world().clone(),
-            item_pos,
-            &EntityType::ITEM,
-            false,
-        );
-
+    /// Throws `item_stack` in front of the player. With `retain_ownership` the player is recorded
+    /// as its thrower, as when the item was dropped on purpose.
+    pub async fn drop_item(
+        &self,
+        item_stack: ItemStack,
+        retain_ownership: bool,
+    ) -> Arc<ItemEntity> {
         let pitch = f64::from(self.living_entity.entity.pitch.load()).to_radians();
         let yaw = f64::from(self.living_entity.entity.yaw.load()).to_radians();
         let pitch_sin = pitch.sin();
@@ -1501,9 +2104,49 @@ impl Player {
         );
 
         // TODO: Merge stacks together
-        let item_entity =
-            Arc::new(ItemEntity::new_with_velocity(entity, item_stack, velocity, 40).await);
-        self.world().spawn_entity(item_entity).await;
+        let item_entity = self.new_dropped_item(item_stack, velocity).await;
+        if retain_ownership {
+            item_entity.set_thrower(Some(self.gameprofile.id));
+        }
+        self.world().spawn_entity(item_entity.clone()).await;
+        item_entity
+    }
+
+    /// Throws every item in the inventory in a random direction, as on death without
+    /// `keepInventory`. Items cursed with vanishing are destroyed.
+    pub async fn drop_inventory(&self) {
+        for slot in 0..self.inventory.size() {
+            let stack = self.inventory.get_stack(slot).await;
+            let stack = std::mem::replace(&mut *stack.lock().await, ItemStack::EMPTY.clone());
+            if stack.is_empty() || stack.get_enchantment_level(&Enchantment::VANISHING_CURSE) > 0 {
+                continue;
+            }
+
+            let strength = rand::random::<f64>() * 0.5;
+            let angle = rand::random::<f64>() * TAU;
+            let velocity = Vector3::new(-angle.sin() * strength, 0.2, angle.cos() * strength);
+            let item_entity = self.new_dropped_item(stack, velocity).await;
+            item_entity.set_death_drop(self.gameprofile.id);
+            self.world().spawn_entity(item_entity).await;
+        }
+    }
+
+    /// An item entity leaving the player at chest height, which still has to be spawned.
+    async fn new_dropped_item(
+        &self,
+        item_stack: ItemStack,
+        velocity: Vector3<f64>,
+    ) -> Arc<ItemEntity> {
+        let item_pos = self.living_entity.entity.pos.load()
+            + Vector3::new(0.0, f64::from(EntityType::PLAYER.eye_height) - 0.3, 0.0);
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            self.world().clone(),
+            item_pos,
+            &EntityType::ITEM,
+            false,
+        );
+        Arc::new(ItemEntity::new_with_velocity(entity, item_stack, velocity, 40).await)
     }
 
     pub async fn drop_held_item(&self, drop_stack: bool) {
@@ -1514,7 +2157,7 @@ impl Player {
 
         if !item_stack.is_empty() {
             let drop_amount = if drop_stack { item_stack.item_count } else { 1 };
-            self.drop_item(item_stack.copy_with_count(drop_amount))
+            self.drop_item(item_stack.copy_with_count(drop_amount), true)
                 .await;
             item_stack.decrement(drop_amount);
             let selected_slot = self.inventory.get_selected_slot();
@@ -1541,33 +2184,48 @@ impl Player {
         // todo this.player.stopUsingItem();
     }
 
+    /// The language the client declared in its settings.
+    pub async fn locale(&self) -> Locale {
+        Locale::from_str(&self.config.read().await.locale).unwrap_or(Locale::EnUs)
+    }
+
     pub async fn send_system_message(&self, text: &TextComponent) {
+        self.send_system_message_raw(text, false).await;
+    }
+
+    /// Sends `text` with Pumpkin's own translations resolved in the player's locale.
+    pub async fn send_system_message_raw(&self, text: &TextComponent, overlay: bool) {
+        let locale = self.locale().await;
+        let text = text.clone().localized(locale);
         match &self.client {
             ClientPlatform::Java(client) => {
                 client
-                    .enqueue_packet(&CSystemChatMessage::new(text, false))
+                    .enqueue_packet(&CSystemChatMessage::new(&text, overlay))
                     .await;
             }
             ClientPlatform::Bedrock(client) => {
                 client
-                    .send_game_packet(&SText::system_message(text.clone().get_text()))
+                    .send_game_packet(&SText::system_message(text.0.get_text(locale)))
                     .await;
             }
         }
     }
 
-    pub async fn send_system_message_raw(&self, text: &TextComponent, overlay: bool) {
+    /// Sends a custom payload to the client. Bedrock clients have no plugin channels, so nothing
+    /// is sent to them.
+    pub async fn send_custom_payload(&self, channel: &str, data: &[u8]) {
+        if let ClientPlatform::Java(client) = &self.client {
+            client
+                .enqueue_packet(&CCustomPayload::new(channel, data))
+                .await;
+        }
+    }
+
+    /// Whether the client announced it can receive payloads on `channel`.
+    pub async fn has_registered_channel(&self, channel: &str) -> bool {
         match &self.client {
-            ClientPlatform::Java(client) => {
-                client
-                    .enqueue_packet(&CSystemChatMessage::new(text, overlay))
-                    .await;
-            }
-            ClientPlatform::Bedrock(client) => {
-                client
-                    .send_game_packet(&SText::system_message(text.clone().get_text()))
-                    .await;
-            }
+            ClientPlatform::Java(client) => client.channels.lock().await.contains(channel),
+            ClientPlatform::Bedrock(_) => false,
         }
     }
 
@@ -1793,6 +2451,7 @@ impl Player {
     }
 
     pub async fn on_screen_handler_opened(&self, screen_handler: Arc<Mutex<dyn ScreenHandler>>) {
+        listen_to_inventories(&screen_handler).await;
         let mut screen_handler = screen_handler.lock().await;
 
         screen_handler
@@ -1862,7 +2521,7 @@ impl Player {
             return;
         }
 
-        if self.gamemode.load() == GameMode::Spectator {
+        if !self.gamemode.load().can_interact() {
             screen_handler.sync_state().await;
             return;
         }
@@ -1980,10 +2639,41 @@ impl NBTStorage for Player {
         // Store food level, saturation, exhaustion, and tick timer
         self.hunger_manager.write_nbt(nbt).await;
 
+        let mut recipe_book = NbtCompound::new();
+        recipe_book.put_list(
+            "recipes",
+            self.recipe_book
+                .unlocked()
+                .into_iter()
+                .map(NbtTag::String)
+                .collect(),
+        );
+        nbt.put_component("recipeBook", recipe_book);
+
         nbt.put_string(
             "Dimension",
             self.world().dimension_type.resource_location().to_string(),
         );
+        nbt.put_string("pumpkin:World", self.world().name.clone());
+
+        let mut world_positions = NbtCompound::new();
+        for (world, position) in self.world_positions.lock().await.iter() {
+            world_positions.put_component(world, position.to_nbt());
+        }
+        nbt.put_component("pumpkin:WorldPositions", world_positions);
+
+        let entity = self.get_entity();
+        let vehicle = entity.vehicle.lock().await.clone();
+        if let Some(vehicle) = vehicle
+            && let Some(root_vehicle) = entity.root_vehicle().await
+        {
+            let mut root_vehicle_nbt = NbtCompound::new();
+            root_vehicle_nbt.put("Attach", uuid_to_nbt(vehicle.get_entity().entity_uuid));
+            let mut entity_nbt = NbtCompound::new();
+            write_nbt_with_passengers(&root_vehicle, &mut entity_nbt).await;
+            root_vehicle_nbt.put_component("Entity", entity_nbt);
+            nbt.put_component("RootVehicle", root_vehicle_nbt);
+        }
     }
 
     async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
@@ -1995,6 +2685,10 @@ impl NBTStorage for Player {
             GameMode::try_from(nbt.get_byte("playerGameType").unwrap_or(0))
                 .unwrap_or(GameMode::Survival),
         );
+        self.living_entity
+            .entity
+            .no_clip
+            .store(!self.gamemode.load().has_collision(), Ordering::Relaxed);
 
         self.previous_gamemode.store(
             nbt.get_byte("previousPlayerGameType")
@@ -2009,6 +2703,30 @@ impl NBTStorage for Player {
         // Load food level, saturation, exhaustion, and tick timer
         self.hunger_manager.read_nbt(nbt).await;
 
+        if let Some(recipes) = nbt
+            .get_compound("recipeBook")
+            .and_then(|recipe_book| recipe_book.get_list("recipes"))
+        {
+            for recipe in recipes.iter().filter_map(NbtTag::extract_string) {
+                self.recipe_book.unlock(recipe);
+            }
+        }
+
+        *self.saved_vehicle.lock().await = nbt.get_compound("RootVehicle").cloned();
+
+        if let Some(world_positions) = nbt.get_compound("pumpkin:WorldPositions") {
+            *self.world_positions.lock().await = world_positions
+                .child_tags
+                .iter()
+                .filter_map(|(world, tag)| {
+                    Some((
+                        world.clone(),
+                        WorldPosition::from_nbt(tag.extract_compound()?)?,
+                    ))
+                })
+                .collect();
+        }
+
         // Load from total XP
         let total_exp = nbt.get_int("XpTotal").unwrap_or(0);
         let (level, points) = experience::total_to_level_and_points(total_exp);
@@ -2021,6 +2739,51 @@ impl NBTStorage for Player {
 
 impl NBTStorageInit for Player {}
 
+/// A position and rotation in a world the player left, see [`Player::world_positions`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldPosition {
+    pub position: Vector3<f64>,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl WorldPosition {
+    fn to_nbt(self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "Pos",
+            NbtTag::List(vec![
+                self.position.x.into(),
+                self.position.y.into(),
+                self.position.z.into(),
+            ]),
+        );
+        nbt.put(
+            "Rotation",
+            NbtTag::List(vec![self.yaw.into(), self.pitch.into()]),
+        );
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let [x, y, z] = nbt.get_list("Pos")? else {
+            return None;
+        };
+        let [yaw, pitch] = nbt.get_list("Rotation")? else {
+            return None;
+        };
+        Some(Self {
+            position: Vector3::new(
+                x.extract_double()?,
+                y.extract_double()?,
+                z.extract_double()?,
+            ),
+            yaw: yaw.extract_float()?,
+            pitch: pitch.extract_float()?,
+        })
+    }
+}
+
 #[async_trait]
 impl NBTStorage for PlayerInventory {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
@@ -2138,7 +2901,10 @@ impl EntityBase for Player {
         source: Option<&dyn EntityBase>,
         cause: Option<&dyn EntityBase>,
     ) -> bool {
-        if self.abilities.lock().await.invulnerable && damage_type != DamageType::GENERIC_KILL {
+        if self.abilities.lock().await.invulnerable
+            && !damage_type
+                .is_tagged_with_by_tag(&tag::DamageType::MINECRAFT_BYPASSES_INVULNERABILITY)
+        {
             return false;
         }
         let dyn_self = self
@@ -2215,6 +2981,10 @@ impl EntityBase for Player {
         Some(self)
     }
 
+    fn get_step_height(&self) -> f64 {
+        self.living_entity.get_step_height()
+    }
+
     fn is_spectator(&self) -> bool {
         self.gamemode.load() == GameMode::Spectator
     }
@@ -2315,25 +3085,15 @@ impl Default for Abilities {
 
 impl Abilities {
     pub fn set_for_gamemode(&mut self, gamemode: GameMode) {
-        match gamemode {
-            GameMode::Creative => {
-                // self.flying = false; // Start not flying
-                self.allow_flying = true;
-                self.creative = true;
-                self.invulnerable = true;
-            }
-            GameMode::Spectator => {
-                self.flying = true;
-                self.allow_flying = true;
-                self.creative = false;
-                self.invulnerable = true;
-            }
-            _ => {
-                self.flying = false;
-                self.allow_flying = false;
-                self.creative = false;
-                self.invulnerable = false;
-            }
+        self.allow_flying = gamemode.may_fly();
+        self.creative = gamemode.breaks_blocks_instantly();
+        self.invulnerable = !gamemode.takes_damage();
+        self.allow_modify_world = gamemode.can_modify_world();
+        // Creative players keep flying or walking, spectators always fly
+        if gamemode == GameMode::Spectator {
+            self.flying = true;
+        } else if !self.allow_flying {
+            self.flying = false;
         }
     }
 }
@@ -2504,18 +3264,36 @@ impl MessageCache {
 
 #[async_trait]
 impl InventoryPlayer for Player {
-    async fn drop_item(&self, item: ItemStack, _retain_ownership: bool) {
-        self.drop_item(item).await;
+    async fn drop_item(&self, item: ItemStack, retain_ownership: bool) {
+        self.drop_item(item, retain_ownership).await;
     }
 
     fn has_infinite_materials(&self) -> bool {
-        self.gamemode.load() == GameMode::Creative
+        self.gamemode.load().has_infinite_materials()
     }
 
     fn get_inventory(&self) -> Arc<PlayerInventory> {
         self.inventory.clone()
     }
 
+    async fn award_experience(&self, amount: u32) {
+        if amount > 0 {
+            ExperienceOrbEntity::spawn(self.world(), self.position(), amount).await;
+        }
+    }
+
+    fn get_recipe_book(&self) -> Option<Arc<RecipeBook>> {
+        Some(self.recipe_book.clone())
+    }
+
+    fn get_experience_level(&self) -> i32 {
+        self.experience_level.load(Ordering::Relaxed)
+    }
+
+    async fn add_experience_levels(&self, levels: i32) {
+        self.add_experience_levels(levels).await;
+    }
+
     async fn enqueue_inventory_packet(&self, packet: &CSetContainerContent) {
         self.client.enqueue_packet(packet).await;
     }
@@ -0,0 +1,135 @@
+use std::sync::{Arc, atomic::Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::{damage::DamageType, particle::Particle};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::{
+    entity::{Entity, EntityBase, NBTStorage, living::LivingEntity, player::Player},
+    server::Server,
+    world::portal::PortalTeleport,
+};
+
+use super::ThrownItemEntity;
+
+/// The damage the owner takes when teleported.
+const TELEPORT_DAMAGE: f32 = 5.0;
+
+/// A thrown ender pearl. Wherever it lands its owner is teleported to, even when the pearl went
+/// through a portal into another world since.
+pub struct EnderPearlEntity {
+    thrown: ThrownItemEntity,
+}
+
+impl EnderPearlEntity {
+    pub fn new(entity: Entity, owner: Option<&Entity>) -> Self {
+        let thrown = match owner {
+            Some(owner) => ThrownItemEntity::new(entity, owner),
+            None => ThrownItemEntity::without_owner(entity),
+        };
+        Self { thrown }
+    }
+
+    pub fn thrown(&self) -> &ThrownItemEntity {
+        &self.thrown
+    }
+
+    /// The player that threw the pearl, if they are online in any world.
+    async fn owner(&self, server: &Server) -> Option<Arc<Player>> {
+        server.get_player_by_uuid(self.thrown.owner()?).await
+    }
+
+    /// Removes the pearl and teleports its owner to where it landed.
+    async fn land(&self, server: &Server) {
+        let entity = &self.thrown.entity;
+        let position = entity.pos.load();
+        entity
+            .world
+            .spawn_particle(
+                position,
+                Vector3::new(0.0, 0.5, 0.0),
+                0.0,
+                32,
+                Particle::Portal,
+            )
+            .await;
+        entity.remove().await;
+
+        let Some(owner) = self.owner(server).await else {
+            return;
+        };
+        if owner.living_entity.dead.load(Ordering::Relaxed) {
+            return;
+        }
+        owner.living_entity.fall_distance.store(0.0);
+        // Queued like portal teleports, as the owner may be in another world that is ticking
+        entity
+            .world
+            .queue_portal_teleport(PortalTeleport {
+                entity: owner.clone(),
+                world: entity.world.clone(),
+                position,
+            })
+            .await;
+        owner
+            .damage(owner.clone(), TELEPORT_DAMAGE, DamageType::ENDER_PEARL)
+            .await;
+    }
+}
+
+#[async_trait]
+impl NBTStorage for EnderPearlEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.thrown.write_nbt(nbt).await;
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.thrown.read_nbt_non_mut(nbt).await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for EnderPearlEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        let entity = &self.thrown.entity;
+        entity.tick(caller.clone(), server).await;
+
+        let vanish_on_death = entity
+            .world
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .ender_pearls_vanish_on_death;
+        if vanish_on_death
+            && let Some(owner) = self.owner(server).await
+            && owner.living_entity.dead.load(Ordering::Relaxed)
+        {
+            entity.remove().await;
+            return;
+        }
+
+        if self.thrown.fly(&caller, server).await {
+            self.land(server).await;
+            return;
+        }
+        self.thrown.slow_down(self.get_gravity());
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.thrown.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+
+    fn get_gravity(&self) -> f64 {
+        0.03
+    }
+}
@@ -1,14 +1,27 @@
+pub mod ender_pearl;
+pub mod wind_charge;
+
 use std::{
     f32::{self},
-    sync::atomic::Ordering,
+    sync::{Arc, atomic::Ordering},
 };
 
-use super::{Entity, EntityBase, NBTStorage, living::LivingEntity};
+use super::{Entity, EntityBase, NBTStorage, living::LivingEntity, uuid_from_nbt, uuid_to_nbt};
+use crate::server::Server;
 use async_trait::async_trait;
-use pumpkin_util::math::vector3::Vector3;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::{GameMode, math::vector3::Vector3};
+use uuid::Uuid;
+
+/// How much of its velocity a projectile keeps every tick, in air and in water.
+const DRAG: f64 = 0.99;
+const WATER_DRAG: f64 = 0.8;
 
 pub struct ThrownItemEntity {
     entity: Entity,
+    /// Who threw it. Kept by UUID so it survives the projectile going through a portal.
+    owner: AtomicCell<Option<Uuid>>,
 }
 
 impl ThrownItemEntity {
@@ -16,7 +29,83 @@ impl ThrownItemEntity {
         let mut owner_pos = owner.pos.load();
         owner_pos.y = (owner_pos.y + f64::from(owner.standing_eye_height)) - 0.1;
         entity.pos.store(owner_pos);
-        Self { entity }
+        Self {
+            entity,
+            owner: AtomicCell::new(Some(owner.entity_uuid)),
+        }
+    }
+
+    /// A projectile nobody threw, or one whose owner is read from NBT.
+    pub fn without_owner(entity: Entity) -> Self {
+        Self {
+            entity,
+            owner: AtomicCell::new(None),
+        }
+    }
+
+    /// The UUID of the entity that threw this projectile, which may be in another world by now.
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner.load()
+    }
+
+    /// Whether the projectile runs into an entity other than its owner on its way along
+    /// `movement`.
+    pub async fn hits_entity(&self, movement: Vector3<f64>) -> bool {
+        let entity = &self.entity;
+        let path = entity.bounding_box.load().stretch(movement);
+        let owner = self.owner();
+        let is_target = |uuid: Uuid| uuid != entity.entity_uuid && Some(uuid) != owner;
+
+        let hits_player = entity
+            .world
+            .get_players_at_box(&path)
+            .await
+            .iter()
+            .any(|player| {
+                is_target(player.living_entity.entity.entity_uuid)
+                    && player.gamemode.load() != GameMode::Spectator
+            });
+        hits_player
+            || entity
+                .world
+                .get_entities_at_box(&path)
+                .await
+                .iter()
+                .any(|other| {
+                    is_target(other.get_entity().entity_uuid) && other.get_living_entity().is_some()
+                })
+    }
+
+    /// Moves the projectile along its velocity for one tick, returns whether it hit a block or an
+    /// entity on the way. Blocks it flies through, like portals, are collided with.
+    pub async fn fly(&self, caller: &Arc<dyn EntityBase>, server: &Server) -> bool {
+        let entity = &self.entity;
+        let velocity = entity.velocity.load();
+        if self.hits_entity(velocity).await {
+            return true;
+        }
+
+        let start = entity.pos.load();
+        entity.move_entity(caller.clone(), velocity).await;
+        let moved = entity.pos.load().sub(&start);
+        if moved.sub(&velocity).length_squared() > 1.0E-7 {
+            return true;
+        }
+        entity.tick_block_collisions(caller, server).await;
+        false
+    }
+
+    /// Slows the projectile down by drag and `gravity` after it moved.
+    pub fn slow_down(&self, gravity: f64) {
+        let entity = &self.entity;
+        let drag = if entity.touching_water.load(Ordering::SeqCst) {
+            WATER_DRAG
+        } else {
+            DRAG
+        };
+        let mut velocity = entity.velocity.load() * drag;
+        velocity.y -= gravity;
+        entity.velocity.store(velocity);
     }
     pub fn set_velocity_from(
         &self,
@@ -76,10 +165,35 @@ impl ThrownItemEntity {
     }
 }
 
-impl NBTStorage for ThrownItemEntity {}
+#[async_trait]
+impl NBTStorage for ThrownItemEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.entity.write_nbt(nbt).await;
+        if let Some(owner) = self.owner() {
+            nbt.put("Owner", uuid_to_nbt(owner));
+        }
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.entity.read_nbt_non_mut(nbt).await;
+        self.owner
+            .store(nbt.get_int_array("Owner").and_then(uuid_from_nbt));
+    }
+}
 
 #[async_trait]
 impl EntityBase for ThrownItemEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        let entity = &self.entity;
+        entity.tick(caller.clone(), server).await;
+        // Snowballs and eggs do nothing on impact yet
+        if self.fly(&caller, server).await {
+            entity.remove().await;
+            return;
+        }
+        self.slow_down(self.get_gravity());
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.entity
     }
@@ -0,0 +1,110 @@
+use std::sync::{Arc, atomic::Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::{entity::EntityType, particle::Particle, sound::Sound};
+use pumpkin_nbt::compound::NbtCompound;
+
+use crate::{
+    entity::{Entity, EntityBase, NBTStorage, living::LivingEntity},
+    server::Server,
+};
+
+use super::ThrownItemEntity;
+
+/// Wind charges burst on their own once they are this far above the top of the world.
+const MAX_HEIGHT_ABOVE_WORLD: f64 = 30.0;
+
+/// A charge of wind, thrown by players or shot by breezes, that bursts on impact and pushes
+/// away everything around it without dealing damage.
+pub struct WindChargeEntity {
+    thrown: ThrownItemEntity,
+}
+
+impl WindChargeEntity {
+    pub fn new(entity: Entity, owner: Option<&Entity>) -> Self {
+        let thrown = match owner {
+            Some(owner) => ThrownItemEntity::new(entity, owner),
+            None => ThrownItemEntity::without_owner(entity),
+        };
+        Self { thrown }
+    }
+
+    pub fn thrown(&self) -> &ThrownItemEntity {
+        &self.thrown
+    }
+
+    /// How far the burst reaches, breezes shoot larger charges than players throw.
+    fn burst_radius(&self) -> f32 {
+        if self.thrown.entity.entity_type == &EntityType::BREEZE_WIND_CHARGE {
+            3.0
+        } else {
+            1.2
+        }
+    }
+
+    /// Removes the charge and lets it burst where it is.
+    pub async fn burst(&self) {
+        let entity = &self.thrown.entity;
+        entity.remove().await;
+        let (particle, sound) = if entity.entity_type == &EntityType::BREEZE_WIND_CHARGE {
+            (Particle::GustEmitterLarge, Sound::EntityBreezeWindBurst)
+        } else {
+            (Particle::GustEmitterSmall, Sound::EntityWindChargeWindBurst)
+        };
+        entity
+            .world
+            .wind_burst(entity.pos.load(), self.burst_radius(), particle, sound)
+            .await;
+    }
+}
+
+#[async_trait]
+impl NBTStorage for WindChargeEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.thrown.write_nbt(nbt).await;
+    }
+
+    async fn read_nbt_non_mut(&self, nbt: &NbtCompound) {
+        self.thrown.read_nbt_non_mut(nbt).await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for WindChargeEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        let entity = &self.thrown.entity;
+        entity.tick(caller.clone(), server).await;
+
+        // Wind charges fly in a straight line until something stops them
+        if self.thrown.fly(&caller, server).await {
+            self.burst().await;
+            return;
+        }
+
+        if entity.pos.load().y > f64::from(entity.world.top_y()) + MAX_HEIGHT_ABOVE_WORLD {
+            self.burst().await;
+            return;
+        }
+
+        if entity.velocity_dirty.swap(false, Ordering::SeqCst) {
+            entity.send_pos_rot().await;
+            entity.send_velocity().await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.thrown.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+
+    fn as_nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+
+    fn get_gravity(&self) -> f64 {
+        0.0
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, atomic::Ordering},
+};
+
+use pumpkin_data::entity::EntityType;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{codec::var_int::VarInt, java::client::play::CSetPassengers};
+use pumpkin_util::math::{
+    boundingbox::{BoundingBox, EntityDimensions},
+    position::BlockPos,
+    vector3::Vector3,
+};
+use uuid::Uuid;
+
+use crate::{
+    entity::{Entity, EntityBase, player::Player, r#type::from_type, uuid_from_nbt},
+    world::World,
+};
+
+/// How far above the top of its vehicle a passenger may be placed when dismounting.
+const MAX_DISMOUNT_HEIGHT: f64 = 0.75;
+
+/// How many passengers an entity of `entity_type` carries at once. Boats, rafts and camels have a
+/// second seat behind the first, chest boats lose it to the chest.
+fn max_passengers(entity_type: &EntityType) -> usize {
+    let name = entity_type.resource_name;
+    if name.ends_with("_boat") || name.ends_with("_raft") {
+        if name.contains("chest") { 1 } else { 2 }
+    } else if entity_type == &EntityType::CAMEL {
+        2
+    } else {
+        1
+    }
+}
+
+/// Whether players get on an entity of `entity_type` by right clicking it.
+fn is_ridden_on_interact(entity_type: &EntityType) -> bool {
+    let name = entity_type.resource_name;
+    name.ends_with("_boat")
+        || name.ends_with("_raft")
+        || entity_type == &EntityType::MINECART
+        || entity_type == &EntityType::CAMEL
+}
+
+/// Where a passenger is attached to `vehicle`, relative to the position of the vehicle.
+///
+/// Like in vanilla, boats and camels seat two passengers behind each other and the rest uses the
+/// height of the vehicle type, so the rider ends up where the client expects it.
+fn passenger_attachment(vehicle: &Entity, index: usize, count: usize) -> Vector3<f64> {
+    let entity_type = vehicle.entity_type;
+    let height = f64::from(vehicle.height());
+    let name = entity_type.resource_name;
+
+    let (y, forward) = if name.ends_with("_boat") || name.ends_with("_raft") {
+        let y = if name.ends_with("_raft") {
+            height * 0.888_888_9
+        } else {
+            height / 3.0
+        };
+        let forward = match (count > 1, index) {
+            (false, _) if name.contains("chest") => 0.15,
+            (false, _) => 0.0,
+            (true, 0) => 0.2,
+            (true, _) => -0.6,
+        };
+        (y, forward)
+    } else if name.ends_with("minecart") {
+        (0.1875, 0.0)
+    } else if entity_type == &EntityType::HORSE {
+        (1.443_75, 0.0)
+    } else if entity_type == &EntityType::DONKEY {
+        (1.112_5, 0.0)
+    } else if entity_type == &EntityType::MULE {
+        (1.212_5, 0.0)
+    } else if entity_type == &EntityType::SKELETON_HORSE || entity_type == &EntityType::ZOMBIE_HORSE
+    {
+        (1.318_75, 0.0)
+    } else if entity_type == &EntityType::LLAMA || entity_type == &EntityType::TRADER_LLAMA {
+        (1.37, -0.3)
+    } else if entity_type == &EntityType::PIG {
+        (0.868_75, 0.0)
+    } else if entity_type == &EntityType::STRIDER {
+        (1.187_5, 0.0)
+    } else if entity_type == &EntityType::SPIDER {
+        (0.765, 0.0)
+    } else if entity_type == &EntityType::CAMEL {
+        let forward = if index > 0 { -0.7 } else { 0.5 };
+        (height - 0.375, forward)
+    } else {
+        (height, 0.0)
+    };
+
+    // Rotate the offset with the vehicle, the vehicle looks along positive z at a yaw of 0
+    let yaw = f64::from(vehicle.yaw.load()).to_radians();
+    Vector3::new(-forward * yaw.sin(), y, forward * yaw.cos())
+}
+
+/// How far above its feet `passenger` is attached to a vehicle.
+fn vehicle_attachment(passenger: &Entity) -> f64 {
+    let entity_type = passenger.entity_type;
+    if entity_type == &EntityType::PLAYER {
+        0.6
+    } else if entity_type == &EntityType::ZOMBIE
+        || entity_type == &EntityType::HUSK
+        || entity_type == &EntityType::DROWNED
+        || entity_type == &EntityType::ZOMBIE_VILLAGER
+        || entity_type == &EntityType::ZOMBIFIED_PIGLIN
+        || entity_type == &EntityType::SKELETON
+        || entity_type == &EntityType::STRAY
+        || entity_type == &EntityType::WITHER_SKELETON
+    {
+        0.7
+    } else {
+        0.0
+    }
+}
+
+/// The horizontal offset from the centre of a vehicle that just clears it, in the direction of
+/// `yaw`.
+fn escape_vector(vehicle_width: f64, passenger_width: f64, yaw: f32) -> Vector3<f64> {
+    let distance = (vehicle_width + passenger_width + 1.0E-5) / 2.0;
+    let yaw = f64::from(yaw).to_radians();
+    let x = -yaw.sin();
+    let z = yaw.cos();
+    let scale = x.abs().max(z.abs());
+    Vector3::new(x * distance / scale, 0.0, z * distance / scale)
+}
+
+impl Entity {
+    /// Makes `passenger` ride `vehicle`, dismounting it from its current vehicle first.
+    ///
+    /// Returns `false` if an entity tried to ride itself or one of its own passengers, or if all
+    /// seats of `vehicle` are taken.
+    pub async fn start_riding(
+        passenger: &Arc<dyn EntityBase>,
+        vehicle: &Arc<dyn EntityBase>,
+    ) -> bool {
+        if !vehicle.get_entity().has_free_seat().await {
+            return false;
+        }
+        let passenger_entity = passenger.get_entity();
+        let mut current = Some(vehicle.clone());
+        while let Some(entity) = current {
+            if entity.get_entity().entity_id == passenger_entity.entity_id {
+                return false;
+            }
+            current = entity.get_entity().vehicle.lock().await.clone();
+        }
+
+        if passenger_entity.has_vehicle().await {
+            passenger_entity.detach_from_vehicle().await;
+        }
+        *passenger_entity.vehicle.lock().await = Some(vehicle.clone());
+        let vehicle_entity = vehicle.get_entity();
+        vehicle_entity
+            .passengers
+            .lock()
+            .await
+            .push(passenger.clone());
+        vehicle_entity.position_passengers().await;
+        vehicle_entity.send_passengers().await;
+        true
+    }
+
+    /// Whether another passenger fits on this entity.
+    pub async fn has_free_seat(&self) -> bool {
+        self.passengers.lock().await.len() < max_passengers(self.entity_type)
+    }
+
+    /// Seats `player` on `vehicle` when they right click a boat, minecart or camel. Sneaking
+    /// players interact with the vehicle instead. Returns whether the player got on.
+    pub async fn ride_on_interact(player: &Arc<Player>, vehicle: &Arc<dyn EntityBase>) -> bool {
+        let vehicle_entity = vehicle.get_entity();
+        if !is_ridden_on_interact(vehicle_entity.entity_type)
+            || player.living_entity.entity.sneaking.load(Ordering::Relaxed)
+        {
+            return false;
+        }
+        let passenger: Arc<dyn EntityBase> = player.clone();
+        Self::start_riding(&passenger, vehicle).await
+    }
+
+    /// Dismounts `passenger` from its vehicle and moves it to a free spot next to the vehicle, so
+    /// it does not end up inside of blocks.
+    pub async fn stop_riding(passenger: Arc<dyn EntityBase>) {
+        let entity = passenger.get_entity();
+        let Some(vehicle) = entity.detach_from_vehicle().await else {
+            return;
+        };
+        let position = vehicle.get_entity().find_dismount_position(entity).await;
+        entity.set_pos(position);
+        let world = entity.world.clone();
+        passenger.teleport(position, None, None, world).await;
+    }
+
+    /// Removes this entity from the passengers of its vehicle, without moving it.
+    pub async fn detach_from_vehicle(&self) -> Option<Arc<dyn EntityBase>> {
+        let vehicle = self.vehicle.lock().await.take()?;
+        let vehicle_entity = vehicle.get_entity();
+        vehicle_entity
+            .passengers
+            .lock()
+            .await
+            .retain(|passenger| passenger.get_entity().entity_id != self.entity_id);
+        // Whoever sat behind moves up to the free seat
+        vehicle_entity.position_passengers().await;
+        vehicle_entity.send_passengers().await;
+        Some(vehicle)
+    }
+
+    /// The vehicle at the bottom of the stack this entity rides in, `None` if it rides nothing.
+    pub async fn root_vehicle(&self) -> Option<Arc<dyn EntityBase>> {
+        let mut root = self.vehicle.lock().await.clone()?;
+        loop {
+            let vehicle = root.get_entity().vehicle.lock().await.clone();
+            match vehicle {
+                Some(vehicle) => root = vehicle,
+                None => return Some(root),
+            }
+        }
+    }
+
+    /// This entity, its passengers, their passengers and so on.
+    pub async fn self_and_passengers(this: &Arc<dyn EntityBase>) -> Vec<Arc<dyn EntityBase>> {
+        let mut entities = vec![this.clone()];
+        let mut index = 0;
+        while let Some(entity) = entities.get(index).cloned() {
+            entities.extend(entity.get_entity().passengers.lock().await.iter().cloned());
+            index += 1;
+        }
+        entities
+    }
+
+    /// Whether a player rides this entity, directly or on top of another passenger.
+    pub async fn has_player_passenger(this: &Arc<dyn EntityBase>) -> bool {
+        Self::self_and_passengers(this)
+            .await
+            .iter()
+            .skip(1)
+            .any(|entity| entity.get_player().is_some())
+    }
+
+    /// Dismounts all passengers of this entity.
+    pub async fn eject_passengers(&self) {
+        let passengers = self.passengers.lock().await.clone();
+        for passenger in passengers {
+            Self::stop_riding(passenger).await;
+        }
+    }
+
+    /// Tells clients which entities ride this one.
+    pub async fn send_passengers(&self) {
+        let passenger_ids: Vec<VarInt> = self
+            .passengers
+            .lock()
+            .await
+            .iter()
+            .map(|passenger| passenger.get_entity().entity_id.into())
+            .collect();
+        self.world
+            .broadcast_packet_all(&CSetPassengers::new(self.entity_id.into(), &passenger_ids))
+            .await;
+    }
+
+    /// Moves the passengers of this entity along with it.
+    ///
+    /// Clients place passengers themselves, this keeps the server side position in line with
+    /// what they see.
+    pub async fn position_passengers(&self) {
+        let passengers = self.passengers.lock().await.clone();
+        let count = passengers.len();
+        let position = self.pos.load();
+        for (index, passenger) in passengers.iter().enumerate() {
+            let passenger = passenger.get_entity();
+            let attachment = passenger_attachment(self, index, count);
+            passenger.set_pos(position.add(&attachment).sub_raw(
+                0.0,
+                vehicle_attachment(passenger),
+                0.0,
+            ));
+        }
+    }
+
+    /// Finds a spot next to this vehicle where `passenger` can stand without colliding with
+    /// blocks, trying both sides of its seat first, then the front and back. Falls back to the top
+    /// of the vehicle if it is surrounded.
+    pub async fn find_dismount_position(&self, passenger: &Self) -> Vector3<f64> {
+        let yaw = self.yaw.load();
+        let vehicle_width = f64::from(self.width());
+        let passenger_width = f64::from(passenger.width());
+        for direction_yaw in [yaw + 90.0, yaw - 90.0, yaw, yaw + 180.0] {
+            let direction = escape_vector(vehicle_width, passenger_width, direction_yaw);
+            if let Some(position) = self
+                .dismount_position_in_direction(passenger, direction)
+                .await
+            {
+                return position;
+            }
+        }
+
+        let position = self.pos.load();
+        Vector3::new(position.x, self.bounding_box.load().max.y, position.z)
+    }
+
+    async fn dismount_position_in_direction(
+        &self,
+        passenger: &Self,
+        direction: Vector3<f64>,
+    ) -> Option<Vector3<f64>> {
+        // Passengers still sit in their seat, so riders in the back get off in the back
+        let position = passenger.pos.load();
+        let bounding_box = self.bounding_box.load();
+        let x = position.x + direction.x;
+        let z = position.z + direction.z;
+        let max_y = bounding_box.max.y + MAX_DISMOUNT_HEIGHT;
+        let dimensions = EntityDimensions {
+            width: passenger.entity_type.dimension[0],
+            height: passenger.entity_type.dimension[1],
+        };
+
+        let mut block_pos = BlockPos::floored(x, bounding_box.min.y, z);
+        while f64::from(block_pos.0.y) < max_y {
+            let floor_height = self.block_floor_height(&block_pos).await;
+            let floor = f64::from(block_pos.0.y) + floor_height;
+            if floor > max_y {
+                break;
+            }
+            // Blocks taller than one, like fences, can not be stood on from this position
+            if floor_height.is_finite() && floor_height < 1.0 {
+                let bounding_box = BoundingBox::new_from_pos(x, floor, z, &dimensions);
+                if self.world.is_space_empty(bounding_box).await {
+                    return Some(Vector3::new(x, floor, z));
+                }
+            }
+            block_pos = block_pos.up();
+        }
+        None
+    }
+
+    /// The height of the collision shape of the block at `pos`, relative to the bottom of the
+    /// block. Tall blocks below count with the part that reaches into `pos`.
+    async fn block_floor_height(&self, pos: &BlockPos) -> f64 {
+        let shape_height = async |pos: &BlockPos| {
+            let state = self.world.get_block_state(pos).await;
+            if state.is_air() || !state.is_solid() {
+                return None;
+            }
+            state
+                .get_block_collision_shapes()
+                .iter()
+                .map(|shape| shape.max.y)
+                .reduce(f64::max)
+        };
+
+        if let Some(height) = shape_height(pos).await {
+            return height;
+        }
+        match shape_height(&pos.down()).await {
+            Some(height) if height >= 1.0 => height - 1.0,
+            _ => f64::NEG_INFINITY,
+        }
+    }
+}
+
+/// Writes `entity` along with everything riding it except players, who are saved on their own.
+/// This is how the vehicle a player logs out in is stored with the player.
+pub fn write_nbt_with_passengers<'a>(
+    entity: &'a Arc<dyn EntityBase>,
+    nbt: &'a mut NbtCompound,
+) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+    Box::pin(async move {
+        entity.write_nbt(nbt).await;
+        let passengers = entity.get_entity().passengers.lock().await.clone();
+        let mut passengers_nbt = Vec::new();
+        for passenger in passengers
+            .iter()
+            .filter(|passenger| passenger.get_player().is_none())
+        {
+            let mut passenger_nbt = NbtCompound::new();
+            write_nbt_with_passengers(passenger, &mut passenger_nbt).await;
+            passengers_nbt.push(NbtTag::Compound(passenger_nbt));
+        }
+        if !passengers_nbt.is_empty() {
+            nbt.put("Passengers", NbtTag::List(passengers_nbt));
+        }
+    })
+}
+
+/// Spawns the entity written by [`write_nbt_with_passengers`] into `world` and seats its
+/// passengers on it again. Returns `None` if the entity type is unknown.
+pub fn spawn_with_passengers<'a>(
+    world: &'a Arc<World>,
+    nbt: &'a NbtCompound,
+) -> Pin<Box<dyn Future<Output = Option<Arc<dyn EntityBase>>> + Send + 'a>> {
+    Box::pin(async move {
+        let id = nbt.get_string("id")?;
+        let entity_type = EntityType::from_name(id.strip_prefix("minecraft:").unwrap_or(id))?;
+        let uuid = nbt
+            .get_int_array("UUID")
+            .and_then(uuid_from_nbt)
+            .unwrap_or_else(Uuid::new_v4);
+        // The position is read from the NBT
+        let entity = from_type(entity_type, Vector3::new(0.0, 0.0, 0.0), world, uuid).await;
+        entity.read_nbt_non_mut(nbt).await;
+        world.spawn_entity(entity.clone()).await;
+
+        for passenger_nbt in nbt
+            .get_list("Passengers")
+            .into_iter()
+            .flatten()
+            .filter_map(NbtTag::extract_compound)
+        {
+            if let Some(passenger) = spawn_with_passengers(world, passenger_nbt).await {
+                Entity::start_riding(&passenger, &entity).await;
+            }
+        }
+        Some(entity)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_vector, max_passengers};
+    use crate::entity::{uuid_from_nbt, uuid_to_nbt};
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_nbt::tag::NbtTag;
+    use uuid::Uuid;
+
+    #[test]
+    fn vehicle_uuids_round_trip() {
+        let uuid = Uuid::from_u128(0xF000_0001_8000_0002_0000_0003_FFFF_FFFF);
+        let NbtTag::IntArray(ints) = uuid_to_nbt(uuid) else {
+            panic!("UUIDs are stored as int arrays");
+        };
+        assert_eq!(uuid_from_nbt(&ints), Some(uuid));
+        assert_eq!(uuid_from_nbt(&ints[1..]), None);
+    }
+
+    #[test]
+    fn seats_per_vehicle() {
+        assert_eq!(max_passengers(&EntityType::OAK_BOAT), 2);
+        assert_eq!(max_passengers(&EntityType::BAMBOO_RAFT), 2);
+        assert_eq!(max_passengers(&EntityType::OAK_CHEST_BOAT), 1);
+        assert_eq!(max_passengers(&EntityType::CAMEL), 2);
+        assert_eq!(max_passengers(&EntityType::HORSE), 1);
+    }
+
+    #[test]
+    fn escape_vector_clears_both_boxes() {
+        // Looking south, the right hand side is to the west
+        let west = escape_vector(1.0, 0.6, 90.0);
+        assert!((west.x + 0.800_005).abs() < 1.0E-6);
+        assert!(west.z.abs() < 1.0E-6);
+
+        // Diagonals are pushed out to the corner of the box, not just the circle around it
+        let diagonal = escape_vector(1.0, 0.6, 45.0);
+        assert!((diagonal.x.abs() - 0.800_005).abs() < 1.0E-6);
+        assert!((diagonal.z.abs() - 0.800_005).abs() < 1.0E-6);
+    }
+}
@@ -2,13 +2,26 @@ use std::sync::Arc;
 
 use pumpkin_data::entity::EntityType;
 use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
 use uuid::Uuid;
 
 use crate::{
     entity::{
         Entity, EntityBase,
-        decoration::{end_crystal::EndCrystalEntity, painting::PaintingEntity},
-        mob::zombie::Zombie,
+        decoration::{
+            armor_stand::ArmorStandEntity, end_crystal::EndCrystalEntity,
+            interaction::InteractionEntity, painting::PaintingEntity,
+        },
+        item::ItemEntity,
+        lightning::LightningEntity,
+        mob::{
+            allay::Allay, bee::Bee, sheep::Sheep, skeleton_horse::SkeletonHorse,
+            trader_llama::TraderLlama, wandering_trader::WanderingTrader, zombie::Zombie,
+            zombified_piglin::ZombifiedPiglin,
+        },
+        projectile::{
+            ThrownItemEntity, ender_pearl::EnderPearlEntity, wind_charge::WindChargeEntity,
+        },
     },
     world::World,
 };
@@ -24,8 +37,30 @@ pub async fn from_type(
     #[allow(clippy::single_match)]
     let mob: Arc<dyn EntityBase> = match entity_type.id {
         id if id == EntityType::ZOMBIE.id => Zombie::make(entity).await,
+        id if id == EntityType::ZOMBIFIED_PIGLIN.id => ZombifiedPiglin::make(entity).await,
+        id if id == EntityType::WANDERING_TRADER.id => WanderingTrader::make(entity).await,
+        id if id == EntityType::TRADER_LLAMA.id => TraderLlama::make(entity),
+        id if id == EntityType::SKELETON_HORSE.id => SkeletonHorse::make(entity),
+        id if id == EntityType::SHEEP.id => Sheep::make(entity).await,
+        id if id == EntityType::BEE.id => Bee::make(entity).await,
+        id if id == EntityType::ALLAY.id => Allay::make(entity).await,
+        // The stack is read from NBT, the item despawns if there is none
+        id if id == EntityType::ITEM.id => {
+            Arc::new(ItemEntity::new(entity, ItemStack::EMPTY.clone()).await)
+        }
+        id if id == EntityType::LIGHTNING_BOLT.id => Arc::new(LightningEntity::new(entity, false)),
         id if id == EntityType::PAINTING.id => Arc::new(PaintingEntity::new(entity)),
         id if id == EntityType::END_CRYSTAL.id => Arc::new(EndCrystalEntity::new(entity)),
+        id if id == EntityType::ARMOR_STAND.id => Arc::new(ArmorStandEntity::new(entity)),
+        id if id == EntityType::INTERACTION.id => Arc::new(InteractionEntity::new(entity)),
+        id if id == EntityType::WIND_CHARGE.id || id == EntityType::BREEZE_WIND_CHARGE.id => {
+            Arc::new(WindChargeEntity::new(entity, None))
+        }
+        // Projectiles read their owner from NBT, like when they went through a portal
+        id if id == EntityType::ENDER_PEARL.id => Arc::new(EnderPearlEntity::new(entity, None)),
+        id if id == EntityType::SNOWBALL.id || id == EntityType::EGG.id => {
+            Arc::new(ThrownItemEntity::without_owner(entity))
+        }
         // TODO
         _ => Arc::new(entity), // Fallback Entity
     };
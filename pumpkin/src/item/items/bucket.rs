@@ -12,10 +12,7 @@ use pumpkin_data::{
     sound::{Sound, SoundCategory},
 };
 use pumpkin_registry::VanillaDimensionType;
-use pumpkin_util::{
-    GameMode,
-    math::{position::BlockPos, vector3::Vector3},
-};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 use pumpkin_world::{inventory::Inventory, item::ItemStack, tick::TickPriority, world::BlockFlags};
 
 use crate::world::World;
@@ -79,19 +76,9 @@ fn waterlogged_check(block: &Block, state: u16) -> Option<bool> {
 }
 
 fn set_waterlogged(block: &Block, state: u16, waterlogged: bool) -> u16 {
-    let original_props = &block.properties(state).unwrap().to_props();
-    let waterlogged = waterlogged.to_string();
-    let props: Vec<(&str, &str)> = original_props
-        .iter()
-        .map(|(key, value)| {
-            if key == "waterlogged" {
-                ("waterlogged", waterlogged.as_str())
-            } else {
-                (key.as_str(), value.as_str())
-            }
-        })
-        .collect();
-    block.from_properties(&props).to_state_id(block)
+    block
+        .with_property(state, "waterlogged", &waterlogged.to_string())
+        .unwrap_or(state)
 }
 
 #[async_trait]
@@ -181,7 +168,7 @@ impl ItemBehaviour for EmptyBucketItem {
             &Item::WATER_BUCKET
         };
 
-        if player.gamemode.load() == GameMode::Creative {
+        if player.gamemode.load().has_infinite_materials() {
             //Check if player already has the item in their inventory
             for i in 0..player.inventory.main_inventory.len() {
                 if player.inventory.main_inventory[i].lock().await.item.id == item.id {
@@ -292,7 +279,7 @@ impl ItemBehaviour for FilledBucketItem {
         }
 
         //TODO: Spawn entity if applicable
-        if player.gamemode.load() != GameMode::Creative {
+        if !player.gamemode.load().has_infinite_materials() {
             let item_stack = ItemStack::new(1, &Item::BUCKET);
             player
                 .inventory
@@ -1,16 +1,17 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::tag;
-use pumpkin_util::GameMode;
 use pumpkin_world::block::entities::{
     BlockEntity,
     sign::{DyeColor, Text},
 };
+use pumpkin_world::item::ItemStack;
 
 use crate::{
     block::{UseWithItemArgs, registry::BlockActionResult},
-    entity::player::Player,
+    entity::{EntityBase, mob::Mob, player::Player},
     item::{ItemBehaviour, ItemMetadata},
 };
 
@@ -24,8 +25,38 @@ impl ItemMetadata for DyeItem {
 
 #[async_trait]
 impl ItemBehaviour for DyeItem {
+    async fn use_on_entity(
+        &self,
+        item: &mut ItemStack,
+        player: &Player,
+        entity: Arc<dyn EntityBase>,
+    ) {
+        let Some(sheep) = entity.as_mob().and_then(Mob::get_sheep) else {
+            return;
+        };
+        let Some(color_name) = item.item.registry_key.strip_suffix("_dye") else {
+            return;
+        };
+        let dye_color = DyeColor::from(color_name);
+        if sheep.is_sheared() || sheep.get_color() == dye_color {
+            return;
+        }
+
+        sheep.set_color(dye_color).await;
+        let entity = entity.get_entity();
+        entity
+            .world
+            .play_sound(
+                Sound::ItemDyeUse,
+                SoundCategory::Players,
+                &entity.pos.load(),
+            )
+            .await;
+        item.decrement_unless_creative(player.gamemode.load(), 1);
+    }
+
     fn can_mine(&self, player: &Player) -> bool {
-        player.gamemode.load() != GameMode::Creative
+        !player.gamemode.load().breaks_blocks_instantly()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -42,16 +73,15 @@ impl DyeItem {
         color_name: &str,
     ) -> BlockActionResult {
         let dye_color = DyeColor::from(color_name);
+        if text.get_color() == dye_color {
+            return BlockActionResult::PassToDefaultBlockAction;
+        }
 
         text.set_color(dye_color);
 
         args.world.update_block_entity(block_entity).await;
         args.world
-            .play_block_sound(
-                pumpkin_data::sound::Sound::ItemDyeUse,
-                pumpkin_data::sound::SoundCategory::Blocks,
-                *args.position,
-            )
+            .play_block_sound(Sound::ItemDyeUse, SoundCategory::Blocks, *args.position)
             .await;
         BlockActionResult::Success
     }
@@ -35,20 +35,9 @@ impl ItemBehaviour for EnderEyeItem {
 
         let world = player.world();
         let state_id = world.get_block_state_id(&location).await;
-        let original_props = block.properties(state_id).unwrap().to_props();
-
-        let props: Vec<(&str, &str)> = original_props
-            .iter()
-            .map(|(key, value)| {
-                if key == "eye" {
-                    (key.as_str(), "true")
-                } else {
-                    (key.as_str(), value.as_str())
-                }
-            })
-            .collect();
-
-        let new_state_id = block.from_properties(&props).to_state_id(block);
+        let Some(new_state_id) = block.with_property(state_id, "eye", "true") else {
+            return;
+        };
         world
             .set_block_state(&location, new_state_id, BlockFlags::empty())
             .await;
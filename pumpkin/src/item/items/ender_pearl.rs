@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::entity::Entity;
+use crate::entity::player::Player;
+use crate::entity::projectile::ender_pearl::EnderPearlEntity;
+use crate::item::{ItemBehaviour, ItemMetadata};
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::Sound;
+use uuid::Uuid;
+
+pub struct EnderPearlItem;
+
+impl ItemMetadata for EnderPearlItem {
+    fn ids() -> Box<[u16]> {
+        [Item::ENDER_PEARL.id].into()
+    }
+}
+
+const POWER: f32 = 1.5;
+
+#[async_trait]
+impl ItemBehaviour for EnderPearlItem {
+    async fn normal_use(&self, _item: &Item, player: &Player) {
+        let position = player.position();
+        let world = player.world();
+        world
+            .play_sound(
+                Sound::EntityEnderPearlThrow,
+                pumpkin_data::sound::SoundCategory::Neutral,
+                &position,
+            )
+            .await;
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            world.clone(),
+            position,
+            &EntityType::ENDER_PEARL,
+            false,
+        );
+        let ender_pearl = EnderPearlEntity::new(entity, Some(&player.living_entity.entity));
+        let yaw = player.living_entity.entity.yaw.load();
+        let pitch = player.living_entity.entity.pitch.load();
+        ender_pearl.thrown().set_velocity_from(
+            &player.living_entity.entity,
+            pitch,
+            yaw,
+            0.0,
+            POWER,
+            1.0,
+        );
+        world.spawn_entity(Arc::new(ender_pearl)).await;
+
+        // The pearl may have been thrown from either hand
+        let inventory = player.inventory();
+        let main_hand = inventory.held_item();
+        let held = if main_hand.lock().await.item.id == Item::ENDER_PEARL.id {
+            main_hand
+        } else {
+            inventory.off_hand_item().await
+        };
+        held.lock()
+            .await
+            .decrement_unless_creative(player.gamemode.load(), 1);
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -47,25 +47,9 @@ impl Ignition {
 }
 
 fn can_be_lit(block: &Block, state_id: u16) -> Option<u16> {
-    let mut props = match &block.properties(state_id) {
-        Some(props) => props.to_props(),
-        None => return None,
-    };
-
-    if let Some((_, value)) = props.iter_mut().find(|(k, _)| k == "extinguished") {
-        *value = "false".into();
-    } else if let Some((_, value)) = props.iter_mut().find(|(k, _)| k == "lit") {
-        *value = "true".into();
-    } else {
-        return None;
-    }
-
-    let props: Vec<(&str, &str)> = props
-        .iter()
-        .map(|(k, v)| (k.as_str(), v.as_str()))
-        .collect();
-
-    let new_state_id = block.from_properties(&props).to_state_id(block);
+    let new_state_id = block
+        .with_property(state_id, "extinguished", "false")
+        .or_else(|| block.with_property(state_id, "lit", "true"))?;
 
     (new_state_id != state_id).then_some(new_state_id)
 }
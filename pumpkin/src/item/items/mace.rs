@@ -2,7 +2,6 @@ use crate::entity::player::Player;
 use crate::item::{ItemBehaviour, ItemMetadata};
 use async_trait::async_trait;
 use pumpkin_data::item::Item;
-use pumpkin_util::GameMode;
 
 pub struct MaceItem;
 
@@ -14,7 +13,7 @@ impl ItemMetadata for MaceItem {
 #[async_trait]
 impl ItemBehaviour for MaceItem {
     fn can_mine(&self, player: &Player) -> bool {
-        player.gamemode.load() != GameMode::Creative
+        !player.gamemode.load().breaks_blocks_instantly()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -4,6 +4,7 @@ pub mod dye;
 pub mod egg;
 pub mod end_crystal;
 pub mod ender_eye;
+pub mod ender_pearl;
 pub mod glowing_ink_sac;
 pub mod hoe;
 pub mod honeycomb;
@@ -12,11 +13,13 @@ pub mod ink_sac;
 pub mod mace;
 pub mod minecart;
 pub mod name_tag;
+pub mod shears;
 pub mod shovel;
 pub mod snowball;
 pub mod spawn_egg;
 pub mod swords;
 pub mod trident;
+pub mod wind_charge;
 
 use crate::item::items::end_crystal::EndCrystalItem;
 use crate::item::items::minecart::MinecartItem;
@@ -29,6 +32,7 @@ use bucket::{EmptyBucketItem, FilledBucketItem};
 use dye::DyeItem;
 use egg::EggItem;
 use ender_eye::EnderEyeItem;
+use ender_pearl::EnderPearlItem;
 use glowing_ink_sac::GlowingInkSacItem;
 use hoe::HoeItem;
 use honeycomb::HoneyCombItem;
@@ -36,11 +40,13 @@ use ignite::fire_charge::FireChargeItem;
 use ignite::flint_and_steel::FlintAndSteelItem;
 use ink_sac::InkSacItem;
 use mace::MaceItem;
+use shears::ShearsItem;
 use shovel::ShovelItem;
 use snowball::SnowBallItem;
 use std::sync::Arc;
 use swords::SwordItem;
 use trident::TridentItem;
+use wind_charge::WindChargeItem;
 
 #[must_use]
 pub fn default_registry() -> Arc<ItemRegistry> {
@@ -65,8 +71,11 @@ pub fn default_registry() -> Arc<ItemRegistry> {
     manager.register(EnderEyeItem);
     manager.register(FireChargeItem);
     manager.register(DyeItem);
+    manager.register(ShearsItem);
     manager.register(InkSacItem);
     manager.register(GlowingInkSacItem);
+    manager.register(WindChargeItem);
+    manager.register(EnderPearlItem);
 
     Arc::new(manager)
 }
@@ -6,7 +6,6 @@ use crate::item::{ItemBehaviour, ItemMetadata};
 use async_trait::async_trait;
 use pumpkin_data::data_component_impl::CustomNameImpl;
 use pumpkin_data::item::Item;
-use pumpkin_util::text::TextComponent;
 use pumpkin_world::item::ItemStack;
 
 pub struct NameTagItem;
@@ -29,8 +28,7 @@ impl ItemBehaviour for NameTagItem {
         if entity.entity_type.saveable
             && let Some(name) = item.get_data_component::<CustomNameImpl>()
         {
-            // TODO
-            entity.set_custom_name(TextComponent::text(name.name)).await;
+            entity.set_custom_name(name.name.clone()).await;
             item.decrement_unless_creative(player.gamemode.load(), 1);
         }
     }
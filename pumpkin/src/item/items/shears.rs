@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::SoundCategory;
+use pumpkin_world::item::ItemStack;
+
+use crate::entity::{EntityBase, mob::Mob, player::Player};
+use crate::item::{ItemBehaviour, ItemMetadata};
+
+/// Shears sheep. Carving pumpkins and harvesting honeycomb are up to the blocks themselves.
+pub struct ShearsItem;
+
+impl ItemMetadata for ShearsItem {
+    fn ids() -> Box<[u16]> {
+        [Item::SHEARS.id].into()
+    }
+}
+
+#[async_trait]
+impl ItemBehaviour for ShearsItem {
+    async fn use_on_entity(
+        &self,
+        _item: &mut ItemStack,
+        _player: &Player,
+        entity: Arc<dyn EntityBase>,
+    ) {
+        if let Some(sheep) = entity.as_mob().and_then(Mob::get_sheep)
+            && sheep.can_be_sheared()
+        {
+            sheep.shear(SoundCategory::Players).await;
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
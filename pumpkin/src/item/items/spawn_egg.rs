@@ -54,6 +54,10 @@ impl ItemBehaviour for SpawnEggItem {
             let yaw = wrap_degrees(rand::random::<f32>() * 360.0) % 360.0;
 
             let mob = from_type(entity_type, pos, world, Uuid::new_v4()).await;
+            let Some(pos) = world.find_spawn_position(mob.as_ref(), pos).await else {
+                return;
+            };
+            mob.get_entity().set_pos(pos);
 
             // Set the rotation
             mob.get_entity().set_rotation(yaw, 0.0);
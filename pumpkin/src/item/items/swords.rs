@@ -2,7 +2,6 @@ use crate::entity::player::Player;
 use crate::item::{ItemBehaviour, ItemMetadata};
 use async_trait::async_trait;
 use pumpkin_data::tag;
-use pumpkin_util::GameMode;
 
 pub struct SwordItem;
 
@@ -15,7 +14,7 @@ impl ItemMetadata for SwordItem {
 #[async_trait]
 impl ItemBehaviour for SwordItem {
     fn can_mine(&self, player: &Player) -> bool {
-        player.gamemode.load() != GameMode::Creative
+        !player.gamemode.load().breaks_blocks_instantly()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
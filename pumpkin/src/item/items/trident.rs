@@ -1,6 +1,5 @@
 use async_trait::async_trait;
 use pumpkin_data::item::Item;
-use pumpkin_util::GameMode;
 
 use crate::{
     entity::player::Player,
@@ -18,7 +17,7 @@ impl ItemMetadata for TridentItem {
 #[async_trait]
 impl ItemBehaviour for TridentItem {
     fn can_mine(&self, player: &Player) -> bool {
-        player.gamemode.load() != GameMode::Creative
+        !player.gamemode.load().breaks_blocks_instantly()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
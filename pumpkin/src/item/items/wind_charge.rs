@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::entity::Entity;
+use crate::entity::player::Player;
+use crate::entity::projectile::wind_charge::WindChargeEntity;
+use crate::item::{ItemBehaviour, ItemMetadata};
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::Sound;
+use uuid::Uuid;
+
+pub struct WindChargeItem;
+
+impl ItemMetadata for WindChargeItem {
+    fn ids() -> Box<[u16]> {
+        [Item::WIND_CHARGE.id].into()
+    }
+}
+
+const POWER: f32 = 1.5;
+
+#[async_trait]
+impl ItemBehaviour for WindChargeItem {
+    async fn normal_use(&self, _item: &Item, player: &Player) {
+        let position = player.position();
+        let world = player.world();
+        world
+            .play_sound(
+                Sound::EntityWindChargeThrow,
+                pumpkin_data::sound::SoundCategory::Neutral,
+                &position,
+            )
+            .await;
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            world.clone(),
+            position,
+            &EntityType::WIND_CHARGE,
+            false,
+        );
+        let wind_charge = WindChargeEntity::new(entity, Some(&player.living_entity.entity));
+        let yaw = player.living_entity.entity.yaw.load();
+        let pitch = player.living_entity.entity.pitch.load();
+        wind_charge.thrown().set_velocity_from(
+            &player.living_entity.entity,
+            pitch,
+            yaw,
+            0.0,
+            POWER,
+            1.0,
+        );
+        world.spawn_entity(Arc::new(wind_charge)).await;
+
+        // The charge may have been thrown from either hand
+        let inventory = player.inventory();
+        let main_hand = inventory.held_item();
+        let held = if main_hand.lock().await.item.id == Item::WIND_CHARGE.id {
+            main_hand
+        } else {
+            inventory.off_hand_item().await
+        };
+        held.lock()
+            .await
+            .decrement_unless_creative(player.gamemode.load(), 1);
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
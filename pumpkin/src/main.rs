@@ -113,6 +113,16 @@ async fn main() {
 
     init_log!();
 
+    let mut args = std::env::args().skip(1);
+    let success = match args.next().as_deref() {
+        Some(world::preview::SUBCOMMAND) => Some(world::preview::run(args).await),
+        Some(world::inspect::SUBCOMMAND) => Some(world::inspect::run(args)),
+        _ => None,
+    };
+    if let Some(success) = success {
+        std::process::exit(if success { 0 } else { 1 });
+    }
+
     let default_panic = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         if let Some((wrapper, _)) = LOGGER_IMPL.as_ref() {
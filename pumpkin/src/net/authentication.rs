@@ -46,6 +46,10 @@ pub struct MojangPublicKeys {
 const MOJANG_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
 const MOJANG_PREVENT_PROXY_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
 const MOJANG_SERVICES_URL: &str = "https://api.minecraftservices.com/";
+const MOJANG_PROFILE_BY_NAME_URL: &str =
+    "https://api.mojang.com/users/profiles/minecraft/{username}";
+const MOJANG_PROFILE_URL: &str =
+    "https://sessionserver.mojang.com/session/minecraft/profile/{uuid}?unsigned=false";
 
 /// Sends a GET request to Mojang's authentication servers to verify a client's Minecraft account.
 ///
@@ -109,6 +113,48 @@ pub fn authenticate(
     Ok(profile)
 }
 
+#[derive(Deserialize)]
+struct ProfileId {
+    id: Uuid,
+}
+
+/// Looks up the profile (including the skin) of the account called `username`, or `None` if
+/// there is no such account.
+///
+/// This blocks until Mojang responds, so call it from a blocking task.
+pub fn lookup_profile(username: &str) -> Result<Option<GameProfile>, AuthError> {
+    let mut response =
+        match ureq::get(MOJANG_PROFILE_BY_NAME_URL.replace("{username}", username)).call() {
+            Ok(response) => response,
+            // Unknown names are answered with "not found"
+            Err(ureq::Error::StatusCode(404)) => return Ok(None),
+            Err(_) => return Err(AuthError::FailedResponse),
+        };
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NO_CONTENT => return Ok(None),
+        other => Err(AuthError::UnknownStatusCode(other))?,
+    }
+    let ProfileId { id } = response
+        .body_mut()
+        .read_json()
+        .map_err(|_| AuthError::FailedParse)?;
+
+    let mut response = ureq::get(MOJANG_PROFILE_URL.replace("{uuid}", &id.simple().to_string()))
+        .call()
+        .map_err(|_| AuthError::FailedResponse)?;
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NO_CONTENT => return Ok(None),
+        other => Err(AuthError::UnknownStatusCode(other))?,
+    }
+    let profile: GameProfile = response
+        .body_mut()
+        .read_json()
+        .map_err(|_| AuthError::FailedParse)?;
+    Ok(Some(profile))
+}
+
 pub fn validate_textures(property: &Property, config: &TextureConfig) -> Result<(), TextureError> {
     let from64 = general_purpose::STANDARD
         .decode(&property.value)
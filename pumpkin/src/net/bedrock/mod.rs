@@ -544,7 +544,7 @@ impl BedrockClient {
                     .await;
             }
             SText::PACKET_ID => {
-                self.handle_chat_message(player, SText::read(reader).unwrap())
+                self.handle_chat_message(player, server, SText::read(reader).unwrap())
                     .await;
             }
             SCommandRequest::PACKET_ID => {
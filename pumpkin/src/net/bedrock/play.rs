@@ -22,19 +22,15 @@ use pumpkin_protocol::{
         },
     },
     codec::{bedrock_block_pos::NetworkPos, var_long::VarLong, var_ulong::VarULong},
-    java::client::play::CSystemChatMessage,
-};
-use pumpkin_util::{
-    math::{position::BlockPos, vector3::Vector3},
-    text::TextComponent,
 };
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
 use crate::{
     command::CommandSender,
     entity::{EntityBase, player::Player},
     net::{DisconnectReason, bedrock::BedrockClient},
     plugin::player::{player_chat::PlayerChatEvent, player_command_send::PlayerCommandSendEvent},
-    server::{Server, seasonal_events},
+    server::{Server, content_filter::FilterSource, seasonal_events},
     world::chunker,
 };
 
@@ -159,7 +155,7 @@ impl BedrockClient {
         }
     }
 
-    pub async fn handle_chat_message(&self, player: &Arc<Player>, packet: SText) {
+    pub async fn handle_chat_message(&self, player: &Arc<Player>, server: &Server, packet: SText) {
         let gameprofile = &player.gameprofile;
 
         send_cancellable! {{
@@ -168,34 +164,18 @@ impl BedrockClient {
             'after: {
                 log::info!("<chat> {}: {}", gameprofile.name, event.message);
 
-                let config = advanced_config();
-
                 let message = match seasonal_events::modify_chat_message(&event.message) {
                     Some(m) => m,
                     None => event.message.clone(),
                 };
 
-                let decorated_message = &TextComponent::chat_decorated(
-                    config.chat.format.clone(),
-                    gameprofile.name.clone(),
-                    message.clone(),
-                );
-
                 let entity = &player.living_entity.entity;
                 if BASIC_CONFIG.allow_chat_reports {
                     //TODO Alex help, what is this?
                     //world.broadcast_secure_player_chat(player, &message, decorated_message).await;
                 } else {
-                    let je_packet = CSystemChatMessage::new(
-                        decorated_message,
-                        false,
-                    );
-
-                    let be_packet = SText::new(
-                        message, gameprofile.name.clone()
-                    );
-
-                    entity.world.broadcast_editioned(&je_packet, &be_packet).await;
+                    let filtered = server.filter_text(player, FilterSource::Chat, message).await;
+                    entity.world.broadcast_unsigned_player_chat(player, &filtered).await;
                 }
             }
         }}
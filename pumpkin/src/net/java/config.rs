@@ -1,21 +1,32 @@
-use std::{num::NonZeroU8, sync::Arc};
+use std::{
+    num::NonZeroU8,
+    sync::{Arc, atomic::Ordering},
+};
 
 use crate::{
     entity::player::ChatMode,
-    net::{ClientPlatform, PlayerConfig, can_not_join, java::JavaClient},
+    net::{
+        ClientPlatform, PlayerConfig, can_not_join,
+        java::{JavaClient, KEEP_ALIVE_INTERVAL, login::CORE_PACK},
+        plugin_channel::{
+            BRAND_CHANNEL, MAX_CHANNELS, REGISTER_CHANNEL, UNREGISTER_CHANNEL, decode_channel_list,
+            register_channels,
+        },
+    },
     server::Server,
 };
-use core::str;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_protocol::{
     ConnectionState,
-    java::client::config::{CFinishConfig, CRegistryData},
+    java::client::config::{CConfigKeepAlive, CFinishConfig, CRegistryData, CUpdateTags},
     java::server::config::{
         ResourcePackResponseResult, SClientInformationConfig, SConfigCookieResponse,
-        SConfigResourcePack, SKnownPacks, SPluginMessage,
+        SConfigKeepAlive, SConfigResourcePack, SKnownPacks, SPluginMessage,
     },
+    ser::NetworkReadExt,
 };
 use pumpkin_util::{Hand, text::TextComponent};
+use tokio::time;
 
 impl JavaClient {
     pub async fn handle_client_information_config(
@@ -54,17 +65,47 @@ impl JavaClient {
 
     pub async fn handle_plugin_message(&self, plugin_message: SPluginMessage) {
         log::debug!("Handling plugin message");
-        if plugin_message
-            .channel
-            .to_string()
-            .starts_with("minecraft:brand")
+        let channel = plugin_message.channel.to_string();
+        if !self
+            .handle_common_payload(&channel, &plugin_message.data)
+            .await
         {
-            log::debug!("Got a client brand");
-            match str::from_utf8(&plugin_message.data) {
-                Ok(brand) => *self.brand.lock().await = Some(brand.to_string()),
-                Err(e) => self.kick(TextComponent::text(e.to_string())).await,
+            // Channel handlers receive the player, which does not exist yet
+            log::debug!("Ignoring plugin message on channel {channel} during configuration");
+        }
+    }
+
+    /// Handles payloads on the channels every connection understands, returning whether
+    /// `channel` is one of them.
+    pub(super) async fn handle_common_payload(&self, channel: &str, data: &[u8]) -> bool {
+        match channel {
+            BRAND_CHANNEL => {
+                log::debug!("Got a client brand");
+                let mut data = data;
+                match data.get_string() {
+                    Ok(brand) => *self.brand.lock().await = Some(brand),
+                    Err(e) => self.kick(TextComponent::text(e.to_string())).await,
+                }
             }
+            REGISTER_CHANNEL => {
+                let mut channels = self.channels.lock().await;
+                if !register_channels(&mut channels, data) {
+                    drop(channels);
+                    self.kick(TextComponent::text(format!(
+                        "Cannot register more than {MAX_CHANNELS} channels"
+                    )))
+                    .await;
+                }
+            }
+            UNREGISTER_CHANNEL => {
+                let mut channels = self.channels.lock().await;
+                for channel in decode_channel_list(data) {
+                    channels.remove(&channel);
+                }
+            }
+            _ => return false,
         }
+        true
     }
 
     pub async fn handle_resource_pack_response(&self, packet: SConfigResourcePack) {
@@ -134,19 +175,33 @@ impl JavaClient {
         self.send_known_packs().await;
     }
 
-    pub fn handle_config_cookie_response(&self, packet: &SConfigCookieResponse) {
-        // TODO: allow plugins to access this
+    pub async fn handle_config_cookie_response(&self, packet: SConfigCookieResponse) {
         log::debug!(
             "Received cookie_response[config]: key: \"{}\", has_payload: \"{}\", payload_length: \"{:?}\"",
             packet.key,
             packet.has_payload,
             packet.payload.as_ref().map(|p| p.len()),
         );
+        self.complete_cookie_request(packet.key, packet.payload)
+            .await;
     }
 
-    pub async fn handle_known_packs(&self, server: &Server, _config_acknowledged: SKnownPacks) {
+    pub async fn handle_known_packs(&self, server: &Server, packet: SKnownPacks) {
         log::debug!("Handling known packs");
+        let knows_core_pack = packet.known_packs.iter().any(|pack| {
+            pack.namespace == CORE_PACK.namespace
+                && pack.id == CORE_PACK.id
+                && pack.version == CORE_PACK.version
+        });
         for registry in &server.cached_registry {
+            // Without the core pack the client can not look up vanilla entries on its own
+            let full_registry;
+            let registry = if knows_core_pack {
+                registry
+            } else {
+                full_registry = registry.with_vanilla_data();
+                &full_registry
+            };
             self.send_packet_now(&CRegistryData::new(
                 &registry.registry_id,
                 &registry.registry_entries,
@@ -154,11 +209,64 @@ impl JavaClient {
             .await;
         }
 
+        // Tags refer to registry entries, so they come after the registries
+        self.send_packet_now(&CUpdateTags::new(&[
+            pumpkin_data::tag::RegistryKey::Block,
+            pumpkin_data::tag::RegistryKey::Fluid,
+            pumpkin_data::tag::RegistryKey::Enchantment,
+            pumpkin_data::tag::RegistryKey::WorldgenBiome,
+            pumpkin_data::tag::RegistryKey::Item,
+            pumpkin_data::tag::RegistryKey::EntityType,
+        ]))
+        .await;
+
         // We are done with configuring
         log::debug!("Finished config");
         self.send_packet_now(&CFinishConfig).await;
     }
 
+    /// Keeps the connection alive while the client is being configured, which can take a while
+    /// when it downloads a resource pack. The play state has its own keep alives in the player
+    /// tick.
+    pub fn start_config_keep_alive(self: &Arc<Self>) {
+        let client = self.clone();
+        self.spawn_task(async move {
+            let mut interval = time::interval(KEEP_ALIVE_INTERVAL);
+            // The first tick completes immediately
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    () = client.await_close_interrupt() => return,
+                    _ = interval.tick() => {}
+                }
+                if client.closed.load(Ordering::Relaxed)
+                    || client.connection_state.load() != ConnectionState::Config
+                {
+                    return;
+                }
+                // The client never answered the last keep alive
+                if client.config_keep_alive_id.load().is_some() {
+                    client
+                        .kick(TextComponent::translate("disconnect.timeout", []))
+                        .await;
+                    return;
+                }
+                let id = rand::random();
+                client.config_keep_alive_id.store(Some(id));
+                client.send_packet_now(&CConfigKeepAlive::new(id)).await;
+            }
+        });
+    }
+
+    pub async fn handle_config_keep_alive(&self, keep_alive: SConfigKeepAlive) {
+        if self.config_keep_alive_id.load() == Some(keep_alive.keep_alive_id) {
+            self.config_keep_alive_id.store(None);
+        } else {
+            self.kick(TextComponent::translate("disconnect.timeout", []))
+                .await;
+        }
+    }
+
     pub async fn handle_config_acknowledged(self: &Arc<Self>, server: &Server) {
         log::debug!("Handling config acknowledgement");
         self.connection_state.store(ConnectionState::Play);
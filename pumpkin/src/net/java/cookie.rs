@@ -0,0 +1,103 @@
+use pumpkin_protocol::{
+    ConnectionState,
+    codec::var_int::VarInt,
+    java::client::{
+        config::{
+            CCookieRequest as CConfigCookieRequest, CStoreCookie as CConfigStoreCookie,
+            CTransfer as CConfigTransfer,
+        },
+        login::CLoginCookieRequest,
+        play::{CPlayCookieRequest, CStoreCookie, CTransfer},
+    },
+};
+use pumpkin_util::resource_location::ResourceLocation;
+use tokio::sync::oneshot;
+
+use crate::net::java::JavaClient;
+
+/// The vanilla client only stores cookies of up to this many bytes.
+pub const MAX_COOKIE_SIZE: usize = 5120;
+
+impl JavaClient {
+    /// Asks the client for the cookie stored under `key`, e.g. by the server that transferred
+    /// it here. Returns `None` if the client has no such cookie or disconnects before answering.
+    ///
+    /// The answer is read by the packet loop of the client, so this must not be awaited while
+    /// handling one of its packets.
+    pub async fn request_cookie(&self, key: &ResourceLocation) -> Option<Box<[u8]>> {
+        let (sender, receiver) = oneshot::channel();
+        self.pending_cookie_requests
+            .lock()
+            .await
+            .entry(key.clone())
+            .or_default()
+            .push(sender);
+
+        match self.connection_state.load() {
+            ConnectionState::Login | ConnectionState::Transfer => {
+                self.send_packet_now(&CLoginCookieRequest::new(key)).await;
+            }
+            ConnectionState::Config => {
+                self.send_packet_now(&CConfigCookieRequest::new(key)).await;
+            }
+            ConnectionState::Play => self.enqueue_packet(&CPlayCookieRequest::new(key)).await,
+            ConnectionState::HandShake | ConnectionState::Status => return None,
+        }
+
+        tokio::select! {
+            () = self.await_close_interrupt() => None,
+            response = receiver => response.ok().flatten(),
+        }
+    }
+
+    /// Stores `payload` on the client under `key`. The client keeps it when it is transferred to
+    /// another server. Returns `false` without storing anything if the payload is too large or
+    /// the client is not being configured or playing.
+    pub async fn store_cookie(&self, key: &ResourceLocation, payload: &[u8]) -> bool {
+        if payload.len() > MAX_COOKIE_SIZE {
+            return false;
+        }
+        match self.connection_state.load() {
+            ConnectionState::Config => {
+                self.send_packet_now(&CConfigStoreCookie::new(key, payload))
+                    .await;
+            }
+            ConnectionState::Play => self.enqueue_packet(&CStoreCookie::new(key, payload)).await,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Makes the client disconnect and join the server at `host` and `port` instead. The other
+    /// server has to accept transfers.
+    pub async fn transfer(&self, host: &str, port: u16) {
+        let port = VarInt(i32::from(port));
+        match self.connection_state.load() {
+            ConnectionState::Config => {
+                self.send_packet_now(&CConfigTransfer::new(host, &port))
+                    .await;
+            }
+            ConnectionState::Play => self.enqueue_packet(&CTransfer::new(host, port)).await,
+            _ => {}
+        }
+    }
+
+    /// Hands the client's answer to everyone waiting for the cookie under `key`.
+    pub(super) async fn complete_cookie_request(
+        &self,
+        key: ResourceLocation,
+        payload: Option<Box<[u8]>>,
+    ) {
+        let Some(requests) = self.pending_cookie_requests.lock().await.remove(&key) else {
+            log::debug!(
+                "Client {} sent cookie {key} which was not requested",
+                self.id
+            );
+            return;
+        };
+        for request in requests {
+            // The requester may have given up waiting
+            let _ = request.send(payload.clone());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use pumpkin_config::BASIC_CONFIG;
 use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
 use pumpkin_protocol::{ConnectionState, java::server::handshake::SHandShake};
 use pumpkin_util::text::TextComponent;
@@ -22,6 +23,7 @@ impl JavaClient {
                         [TextComponent::text(CURRENT_MC_VERSION.to_string())],
                     ))
                     .await;
+                    return;
                 }
                 std::cmp::Ordering::Equal => {}
                 std::cmp::Ordering::Greater => {
@@ -30,8 +32,18 @@ impl JavaClient {
                         [TextComponent::text(CURRENT_MC_VERSION.to_string())],
                     ))
                     .await;
+                    return;
                 }
             }
         }
+        if self.connection_state.load() == ConnectionState::Transfer
+            && !BASIC_CONFIG.accept_transfers
+        {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.transfers_disabled",
+                [],
+            ))
+            .await;
+        }
     }
 }
@@ -1,10 +1,13 @@
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_protocol::{
     ConnectionState, KnownPack, Label, Link, LinkType,
     java::client::{
-        config::{CConfigAddResourcePack, CConfigServerLinks, CKnownPacks, CUpdateTags},
+        config::{
+            CConfigAddResourcePack, CConfigServerLinks, CKnownPacks, CPluginMessage,
+            CUpdateEnabledFeatures,
+        },
         login::{CLoginSuccess, CSetCompression},
     },
     java::server::login::{
@@ -15,17 +18,29 @@ use pumpkin_util::text::TextComponent;
 use uuid::Uuid;
 
 use crate::{
+    data::profile_cache::{cache_profile, offline_profile_uuid},
     net::{
         GameProfile,
         authentication::{self, AuthError},
         is_valid_player_name,
         java::JavaClient,
-        offline_uuid,
+        plugin_channel::{REGISTER_CHANNEL, encode_channel_list},
         proxy::{bungeecord, velocity},
     },
     server::Server,
 };
 
+/// The vanilla data pack the registries and tags come from. Clients that have it get only the
+/// names of registry entries and look up their contents themselves.
+pub const CORE_PACK: KnownPack<'static> = KnownPack {
+    namespace: "minecraft",
+    id: "core",
+    version: "1.21.8",
+};
+
+/// The feature flags the server runs with, experimental features are not implemented.
+const ENABLED_FEATURES: &[&str] = &["minecraft:vanilla"];
+
 static LINKS: LazyLock<Vec<Link>> = LazyLock::new(|| {
     let mut links: Vec<Link> = Vec::new();
 
@@ -135,7 +150,9 @@ impl JavaClient {
             let id = if BASIC_CONFIG.online_mode {
                 login_start.uuid
             } else {
-                offline_uuid(&login_start.name).expect("This is very not safe and bad")
+                offline_profile_uuid(&login_start.name)
+                    .await
+                    .expect("This is very not safe and bad")
             };
 
             let profile = GameProfile {
@@ -255,6 +272,7 @@ impl JavaClient {
     }
 
     async fn finish_login(&self, profile: &GameProfile) {
+        cache_profile(profile).await;
         let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
         self.send_packet_now(&packet).await;
     }
@@ -305,13 +323,14 @@ impl JavaClient {
         Ok(profile)
     }
 
-    pub fn handle_login_cookie_response(&self, packet: &SLoginCookieResponse) {
-        // TODO: allow plugins to access this
+    pub async fn handle_login_cookie_response(&self, packet: SLoginCookieResponse) {
         log::debug!(
             "Received cookie_response[login]: key: \"{}\", payload_length: \"{:?}\"",
             packet.key,
             packet.payload.as_ref().map(|p| p.len())
         );
+        self.complete_cookie_request(packet.key, packet.payload)
+            .await;
     }
     pub async fn handle_plugin_response(&self, plugin_response: SLoginPluginResponse) {
         log::debug!("Handling plugin");
@@ -334,27 +353,27 @@ impl JavaClient {
         }
     }
 
-    pub async fn handle_login_acknowledged(&self, server: &Server) {
+    pub async fn handle_login_acknowledged(self: &Arc<Self>, server: &Server) {
         log::debug!("Handling login acknowledgement");
         self.connection_state.store(ConnectionState::Config);
+        self.start_config_keep_alive();
         self.send_packet_now(&server.get_branding()).await;
 
+        let channels = server.plugin_channels.channel_names().await;
+        if !channels.is_empty() {
+            self.send_packet_now(&CPluginMessage::new(
+                REGISTER_CHANNEL,
+                &encode_channel_list(&channels),
+            ))
+            .await;
+        }
+
         if advanced_config().server_links.enabled {
             self.send_packet_now(&CConfigServerLinks::new(&LINKS)).await;
         }
 
-        // Send tags.
-        // TODO: Is this the right place to send them?
-
-        self.send_packet_now(&CUpdateTags::new(&[
-            pumpkin_data::tag::RegistryKey::Block,
-            pumpkin_data::tag::RegistryKey::Fluid,
-            pumpkin_data::tag::RegistryKey::Enchantment,
-            pumpkin_data::tag::RegistryKey::WorldgenBiome,
-            pumpkin_data::tag::RegistryKey::Item,
-            pumpkin_data::tag::RegistryKey::EntityType,
-        ]))
-        .await;
+        self.send_packet_now(&CUpdateEnabledFeatures::new(ENABLED_FEATURES))
+            .await;
 
         let resource_config = &advanced_config().resource_pack;
         if resource_config.enabled {
@@ -381,11 +400,6 @@ impl JavaClient {
 
     /// Send the known data packs to the client.
     pub async fn send_known_packs(&self) {
-        self.send_packet_now(&CKnownPacks::new(&[KnownPack {
-            namespace: "minecraft",
-            id: "core",
-            version: "1.21.8",
-        }]))
-        .await;
+        self.send_packet_now(&CKnownPacks::new(&[CORE_PACK])).await;
     }
 }
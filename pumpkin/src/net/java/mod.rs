@@ -1,5 +1,7 @@
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::time::{Duration, Instant};
 use std::{io::Write, sync::Arc};
 
 use bytes::Bytes;
@@ -8,10 +10,11 @@ use pumpkin_config::networking::compression::CompressionInfo;
 use pumpkin_protocol::java::server::play::{
     SChangeGameMode, SChatCommand, SChatMessage, SChunkBatch, SClickSlot, SClientCommand,
     SClientInformationPlay, SClientTickEnd, SCloseContainer, SCommandSuggestion, SConfirmTeleport,
-    SCookieResponse as SPCookieResponse, SCustomPayload, SInteract, SKeepAlive, SPickItemFromBlock,
-    SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerLoaded,
-    SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SPlayerSession, SSetCommandBlock,
-    SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUpdateSign, SUseItem, SUseItemOn,
+    SCookieResponse as SPCookieResponse, SCustomPayload, SDebugSampleSubscription, SEditBook,
+    SInteract, SKeepAlive, SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction,
+    SPlayerCommand, SPlayerInput, SPlayerLoaded, SPlayerPosition, SPlayerPositionRotation,
+    SPlayerRotation, SPlayerSession, SRenameItem, SSetCommandBlock, SSetCreativeSlot, SSetHeldItem,
+    SSetPlayerGround, SSpectatorTeleport, SSwingArm, SUpdateSign, SUseItem, SUseItemOn,
 };
 use pumpkin_protocol::{
     ClientPacket, ConnectionState, PacketDecodeError, RawPacket, ServerPacket,
@@ -23,7 +26,7 @@ use pumpkin_protocol::{
         server::{
             config::{
                 SAcknowledgeFinishConfig, SClientInformationConfig, SConfigCookieResponse,
-                SConfigResourcePack, SKnownPacks, SPluginMessage,
+                SConfigKeepAlive, SConfigResourcePack, SKnownPacks, SPluginMessage,
             },
             handshake::SHandShake,
             login::{
@@ -36,6 +39,7 @@ use pumpkin_protocol::{
     packet::Packet,
     ser::{NetworkWriteExt, ReadingError, WritingError},
 };
+use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_util::text::TextComponent;
 use tokio::sync::Notify;
 use tokio::{
@@ -48,20 +52,29 @@ use tokio::{
 };
 use tokio::{
     sync::mpsc::{Receiver, Sender},
+    sync::oneshot,
     task::JoinHandle,
 };
 use tokio_util::task::TaskTracker;
 
 pub mod config;
+mod cookie;
 pub mod handshake;
 pub mod login;
 pub mod play;
 pub mod status;
 
 use crate::entity::player::Player;
+use crate::net::packet_capture::{PacketCapture, PacketDirection};
 use crate::net::{GameProfile, PlayerConfig};
 use crate::{error::PumpkinError, net::EncryptionError, server::Server};
 
+/// How often keep alives are sent, a client that did not answer the last one by then times out.
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// Packets written before the writer stops draining the queue, so a client that is sent a
+/// constant stream of packets (e.g. while loading chunks) still gets them in bounded batches.
+const MAX_BATCHED_PACKETS: usize = 256;
+
 pub struct JavaClient {
     pub id: u64,
     /// The client's game profile information.
@@ -78,6 +91,18 @@ pub struct JavaClient {
     pub address: Mutex<SocketAddr>,
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
+    /// Custom payload channels the client announced it can receive.
+    pub channels: Mutex<HashSet<String>>,
+    /// The keep alive id we wait a response for while the client is being configured.
+    pub config_keep_alive_id: AtomicCell<Option<i64>>,
+    /// The id of the newest command suggestion request. Answers to older requests that are still
+    /// being worked on are dropped, the client only shows the newest.
+    latest_suggestion_id: Arc<AtomicI32>,
+    /// When the current second of command suggestion requests started and how many came in since.
+    suggestion_requests: AtomicCell<(Instant, u32)>,
+    /// Cookie requests waiting for the client to respond, by cookie key.
+    pending_cookie_requests:
+        Mutex<HashMap<ResourceLocation, Vec<oneshot::Sender<Option<Box<[u8]>>>>>>,
     pub player: Mutex<Option<Arc<Player>>>,
     /// A collection of tasks associated with this client. The tasks await completion when removing the client.
     tasks: TaskTracker,
@@ -87,10 +112,16 @@ pub struct JavaClient {
     outgoing_packet_queue_send: Sender<Bytes>,
     /// A queue of serialized packets to send to the network
     outgoing_packet_queue_recv: Option<Receiver<Bytes>>,
+    /// Whether queued packets are kept in the write buffer until the server tick is over.
+    flush_held: Arc<AtomicBool>,
+    /// Wakes the outgoing packet task to flush once packets are no longer held.
+    flush_requested: Arc<Notify>,
     /// The packet encoder for outgoing packets.
     network_writer: Arc<Mutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
     /// The packet decoder for incoming packets.
     network_reader: Mutex<TCPNetworkDecoder<BufReader<OwnedReadHalf>>>,
+    /// Logs and records packets, if enabled in the config.
+    capture: Option<PacketCapture>,
 }
 
 impl JavaClient {
@@ -110,11 +141,19 @@ impl JavaClient {
             tasks: TaskTracker::new(),
             outgoing_packet_queue_send: send,
             outgoing_packet_queue_recv: Some(recv),
+            flush_held: Arc::new(AtomicBool::new(false)),
+            flush_requested: Arc::new(Notify::new()),
 
             network_writer: Arc::new(Mutex::new(TCPNetworkEncoder::new(BufWriter::new(write)))),
             network_reader: Mutex::new(TCPNetworkDecoder::new(BufReader::new(read))),
             brand: Mutex::new(None),
+            channels: Mutex::new(HashSet::new()),
+            config_keep_alive_id: AtomicCell::new(None),
+            latest_suggestion_id: Arc::new(AtomicI32::new(0)),
+            suggestion_requests: AtomicCell::new((Instant::now(), 0)),
+            pending_cookie_requests: Mutex::new(HashMap::new()),
             player: Mutex::new(None),
+            capture: PacketCapture::new(id),
         }
     }
     pub async fn set_encryption(
@@ -197,6 +236,22 @@ impl JavaClient {
         }
     }
 
+    /// Counts a command suggestion request, returning false if the client sent more than
+    /// allowed in the last second.
+    fn allow_suggestion_request(&self, limit: u32) -> bool {
+        if limit == 0 {
+            return true;
+        }
+        let (start, count) = self.suggestion_requests.load();
+        let now = Instant::now();
+        if now.duration_since(start) >= Duration::from_secs(1) {
+            self.suggestion_requests.store((now, 1));
+            return true;
+        }
+        self.suggestion_requests.store((start, count + 1));
+        count < limit
+    }
+
     pub async fn enqueue_packet<P: ClientPacket>(&self, packet: &P) {
         let mut buf = Vec::new();
         let writer = &mut buf;
@@ -211,6 +266,9 @@ impl JavaClient {
     ///
     /// * `packet`: A reference to a packet object implementing the `ClientPacket` trait.
     pub async fn enqueue_packet_data(&self, packet_data: Bytes) {
+        if let Some(capture) = &self.capture {
+            capture.record_clientbound(self.connection_state.load(), &packet_data);
+        }
         if let Err(err) = self.outgoing_packet_queue_send.send(packet_data).await {
             // This is expected to fail if we are closed
             if !self.closed.load(Ordering::Relaxed) {
@@ -223,6 +281,19 @@ impl JavaClient {
         }
     }
 
+    /// Keeps packets queued from now on in the write buffer instead of flushing them, so
+    /// everything sent during a server tick leaves with as few writes to the socket as possible.
+    pub fn hold_flushes(&self) {
+        self.flush_held.store(true, Ordering::Relaxed);
+    }
+
+    /// Flushes the packets held since [`Self::hold_flushes`] and goes back to flushing as soon
+    /// as the queue runs empty.
+    pub fn release_flushes(&self) {
+        self.flush_held.store(false, Ordering::Relaxed);
+        self.flush_requested.notify_one();
+    }
+
     pub async fn await_close_interrupt(&self) {
         self.close_interrupt.notified().await;
     }
@@ -252,7 +323,7 @@ impl JavaClient {
 
     pub async fn kick(&self, reason: TextComponent) {
         match self.connection_state.load() {
-            ConnectionState::Login => {
+            ConnectionState::Login | ConnectionState::Transfer => {
                 // TextComponent implements Serialize and writes in bytes instead of String, that's the reasib we only use content
                 self.send_packet_now(&CLoginDisconnect::new(
                     serde_json::to_string(&reason.0).unwrap_or_else(|_| String::new()),
@@ -278,6 +349,9 @@ impl JavaClient {
     }
 
     pub async fn send_packet_now_data(&self, packet: Vec<u8>) {
+        if let Some(capture) = &self.capture {
+            capture.record_clientbound(self.connection_state.load(), &packet);
+        }
         if let Err(err) = self
             .network_writer
             .lock()
@@ -333,7 +407,16 @@ impl JavaClient {
         server: &Arc<Server>,
         packet: &RawPacket,
     ) -> Result<(), ReadingError> {
-        match self.connection_state.load() {
+        let state = self.connection_state.load();
+        if let Some(capture) = &self.capture {
+            capture.record(
+                PacketDirection::Serverbound,
+                state,
+                packet.id,
+                &packet.payload,
+            );
+        }
+        match state {
             ConnectionState::HandShake => self.handle_handshake_packet(packet).await,
             ConnectionState::Status => self.handle_status_packet(server, packet).await,
             // TODO: Check config if transfer is enabled
@@ -411,6 +494,8 @@ impl JavaClient {
             .expect("This was set in the new fn");
         let close_interrupt = self.close_interrupt.clone();
         let closed = self.closed.clone();
+        let flush_held = self.flush_held.clone();
+        let flush_requested = self.flush_requested.clone();
         let writer = self.network_writer.clone();
         let id = self.id;
         self.spawn_task(async move {
@@ -420,7 +505,10 @@ impl JavaClient {
                         None
                     },
                     recv_result = packet_receiver.recv() => {
-                        recv_result
+                        recv_result.map(Some)
+                    }
+                    () = flush_requested.notified() => {
+                        Some(None)
                     }
                 };
 
@@ -428,7 +516,27 @@ impl JavaClient {
                     break;
                 };
 
-                if let Err(err) = writer.lock().await.write_packet(packet_data).await {
+                let mut writer = writer.lock().await;
+                let mut result = Ok(());
+                if let Some(packet_data) = packet_data {
+                    result = writer.append_packet(packet_data).await;
+                    // Compress everything else that was queued meanwhile, usually the rest of
+                    // the tick's packets, into the same buffer
+                    let mut batched = 1;
+                    while result.is_ok() && batched < MAX_BATCHED_PACKETS {
+                        let Ok(packet_data) = packet_receiver.try_recv() else {
+                            break;
+                        };
+                        result = writer.append_packet(packet_data).await;
+                        batched += 1;
+                    }
+                }
+                if result.is_ok() && !flush_held.load(Ordering::Relaxed) {
+                    result = writer.flush().await;
+                }
+                drop(writer);
+
+                if let Err(err) = result {
                     // It is expected that the packet will fail if we are closed
                     if !closed.load(Ordering::Relaxed) {
                         log::warn!("Failed to send packet to client {id}: {err}",);
@@ -458,7 +566,7 @@ impl JavaClient {
     }
 
     async fn handle_login_packet(
-        &self,
+        self: &Arc<Self>,
         server: &Server,
         packet: &RawPacket,
     ) -> Result<(), ReadingError> {
@@ -481,7 +589,8 @@ impl JavaClient {
                 self.handle_login_acknowledged(server).await;
             }
             SLoginCookieResponse::PACKET_ID => {
-                self.handle_login_cookie_response(&SLoginCookieResponse::read(payload)?);
+                self.handle_login_cookie_response(SLoginCookieResponse::read(payload)?)
+                    .await;
             }
             _ => {
                 log::error!(
@@ -517,12 +626,17 @@ impl JavaClient {
                     .await;
             }
             SConfigCookieResponse::PACKET_ID => {
-                self.handle_config_cookie_response(&SConfigCookieResponse::read(payload)?);
+                self.handle_config_cookie_response(SConfigCookieResponse::read(payload)?)
+                    .await;
             }
             SConfigResourcePack::PACKET_ID => {
                 self.handle_resource_pack_response(SConfigResourcePack::read(payload)?)
                     .await;
             }
+            SConfigKeepAlive::PACKET_ID => {
+                self.handle_config_keep_alive(SConfigKeepAlive::read(payload)?)
+                    .await;
+            }
             _ => {
                 log::error!(
                     "Failed to handle java client packet id {} in Config State",
@@ -555,7 +669,7 @@ impl JavaClient {
                     .await;
             }
             SChatMessage::PACKET_ID => {
-                self.handle_chat_message(player, SChatMessage::read(payload)?)
+                self.handle_chat_message(player, server, SChatMessage::read(payload)?)
                     .await;
             }
             SClientInformationPlay::PACKET_ID => {
@@ -637,7 +751,15 @@ impl JavaClient {
                     .await;
             }
             SUpdateSign::PACKET_ID => {
-                self.handle_sign_update(player, SUpdateSign::read(payload)?)
+                self.handle_sign_update(player, server, SUpdateSign::read(payload)?)
+                    .await;
+            }
+            SEditBook::PACKET_ID => {
+                self.handle_edit_book(player, server, SEditBook::read(payload)?)
+                    .await;
+            }
+            SRenameItem::PACKET_ID => {
+                self.handle_rename_item(player, server, SRenameItem::read(payload)?)
                     .await;
             }
             SUseItemOn::PACKET_ID => {
@@ -653,7 +775,8 @@ impl JavaClient {
                     .await;
             }
             SPCookieResponse::PACKET_ID => {
-                self.handle_cookie_response(&SPCookieResponse::read(payload)?);
+                self.handle_cookie_response(SPCookieResponse::read(payload)?)
+                    .await;
             }
             SCloseContainer::PACKET_ID => {
                 self.handle_close_container(player, server, SCloseContainer::read(payload)?)
@@ -668,7 +791,20 @@ impl JavaClient {
                     .await;
             }
             SCustomPayload::PACKET_ID => {
-                // TODO: this fixes Failed to handle player packet id for now
+                self.handle_custom_payload(player, server, SCustomPayload::read(payload)?)
+                    .await;
+            }
+            SDebugSampleSubscription::PACKET_ID => {
+                self.handle_debug_sample_subscription(
+                    player,
+                    server,
+                    SDebugSampleSubscription::read(payload)?,
+                )
+                .await;
+            }
+            SSpectatorTeleport::PACKET_ID => {
+                self.handle_spectator_teleport(player, server, SSpectatorTeleport::read(payload)?)
+                    .await;
             }
             _ => {
                 log::warn!("Failed to handle player packet id {}", packet.id);
@@ -1,4 +1,3 @@
-use pumpkin_protocol::bedrock::server::text::SText;
 use pumpkin_util::{Hand, PermissionLvl};
 use rsa::pkcs1v15::{Signature as RsaPkcs1v15Signature, VerifyingKey};
 use rsa::signature::Verifier;
@@ -9,55 +8,67 @@ use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+use crate::PLUGIN_MANAGER;
 use crate::block::BlockHitResult;
 use crate::block::registry::BlockActionResult;
 use crate::block::{self, BlockIsReplacing};
 use crate::command::CommandSender;
-use crate::entity::EntityBase;
 use crate::entity::player::{ChatMode, ChatSession, Player};
+use crate::entity::{Entity, EntityBase};
 use crate::error::PumpkinError;
 use crate::net::PlayerConfig;
 use crate::net::java::JavaClient;
 use crate::plugin::player::player_chat::PlayerChatEvent;
 use crate::plugin::player::player_command_send::PlayerCommandSendEvent;
+use crate::plugin::player::player_interact_entity_event::{
+    EntityInteractAction, PlayerInteractEntityEvent,
+};
 use crate::plugin::player::player_interact_event::{InteractAction, PlayerInteractEvent};
 use crate::plugin::player::player_move::PlayerMoveEvent;
+use crate::server::content_filter::{FilterSource, FilteredText};
+use crate::server::debug_sample::DebugSampleType;
 use crate::server::{Server, seasonal_events};
 use crate::world::{World, chunker};
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_data::block_properties::{BlockProperties, WaterLikeProperties};
-use pumpkin_data::data_component_impl::{ConsumableImpl, EquipmentSlot, FoodImpl};
+use pumpkin_data::data_component_impl::{
+    ConsumableImpl, DataComponentImpl, EquipmentSlot, Filterable, FoodImpl,
+    WritableBookContentImpl, WrittenBookContentImpl,
+};
 use pumpkin_data::item::Item;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_inventory::InventoryError;
+use pumpkin_inventory::anvil::anvil_screen_handler::AnvilScreenHandler;
 use pumpkin_inventory::player::player_inventory::PlayerInventory;
 use pumpkin_inventory::screen_handler::ScreenHandler;
 use pumpkin_macros::send_cancellable;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::play::{
     CBlockUpdate, CCommandSuggestions, CEntityPositionSync, CHeadRot, COpenSignEditor,
-    CPingResponse, CPlayerInfoUpdate, CPlayerPosition, CSetSelectedSlot, CSystemChatMessage,
-    CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, InitChat, PlayerAction,
+    CPingResponse, CPlayerInfoUpdate, CPlayerPosition, CSetSelectedSlot, CUpdateEntityPos,
+    CUpdateEntityPosRot, CUpdateEntityRot, InitChat, PlayerAction,
 };
 use pumpkin_protocol::java::server::play::{
     Action, ActionType, CommandBlockMode, FLAG_ON_GROUND, SChangeGameMode, SChatCommand,
     SChatMessage, SChunkBatch, SClientCommand, SClientInformationPlay, SCloseContainer,
-    SCommandSuggestion, SConfirmTeleport, SCookieResponse as SPCookieResponse, SInteract,
-    SKeepAlive, SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction,
-    SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation, SPlayerRotation,
-    SPlayerSession, SSetCommandBlock, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm,
-    SUpdateSign, SUseItem, SUseItemOn, Status,
+    SCommandSuggestion, SConfirmTeleport, SCookieResponse as SPCookieResponse, SCustomPayload,
+    SDebugSampleSubscription, SEditBook, SInteract, SKeepAlive, SPickItemFromBlock,
+    SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput,
+    SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SPlayerSession, SRenameItem,
+    SSetCommandBlock, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSpectatorTeleport,
+    SSwingArm, SUpdateSign, SUseItem, SUseItemOn, Status,
 };
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::math::{polynomial_rolling_hash, position::BlockPos, wrap_degrees};
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::{GameMode, text::TextComponent};
 use pumpkin_world::block::entities::command_block::CommandBlockEntity;
 use pumpkin_world::block::entities::sign::SignBlockEntity;
+use pumpkin_world::inventory::Inventory;
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::world::BlockFlags;
-use tokio::sync::Mutex;
 
 /// In secure chat mode, Player will be kicked if they send a chat message with a timestamp that is older than this (in ms)
 /// Vanilla: 2 minutes
@@ -292,6 +303,9 @@ impl JavaClient {
                 let pos = event.to;
                 let entity = &player.living_entity.entity;
                 let last_pos = entity.pos.load();
+                if pos != last_pos {
+                    player.reset_last_action_time();
+                }
                 player.living_entity.entity.set_pos(pos);
 
                 let height_difference = pos.y - last_pos.y;
@@ -327,7 +341,7 @@ impl JavaClient {
                             player.clone(),
                             height_difference,
                             packet.collision & FLAG_ON_GROUND != 0,
-                            player.gamemode.load() == GameMode::Creative,
+                            !player.gamemode.load().takes_damage(),
                         )
                         .await;
                 }
@@ -394,6 +408,9 @@ impl JavaClient {
                 let pos = event.to;
                 let entity = &player.living_entity.entity;
                 let last_pos = entity.pos.load();
+                if pos != last_pos {
+                    player.reset_last_action_time();
+                }
                 player.living_entity.entity.set_pos(pos);
 
                 let height_difference = pos.y - last_pos.y;
@@ -452,7 +469,7 @@ impl JavaClient {
                             player.clone(),
                             height_difference,
                             (packet.collision & FLAG_ON_GROUND) != 0,
-                            player.gamemode.load() == GameMode::Creative,
+                            !player.gamemode.load().takes_damage(),
                         )
                         .await;
                 }
@@ -485,6 +502,7 @@ impl JavaClient {
         .await;
     }
 
+    #[expect(clippy::float_cmp)]
     pub async fn handle_rotation(&self, player: &Player, rotation: SPlayerRotation) {
         if !player.has_client_loaded() {
             return;
@@ -498,11 +516,15 @@ impl JavaClient {
             return;
         }
         let entity = &player.living_entity.entity;
-        entity.on_ground.store(rotation.ground, Ordering::Relaxed);
-        entity.set_rotation(
+        let (yaw, pitch) = (
             wrap_degrees(rotation.yaw) % 360.0,
             wrap_degrees(rotation.pitch),
         );
+        if entity.yaw.load() != yaw || entity.pitch.load() != pitch {
+            player.reset_last_action_time();
+        }
+        entity.on_ground.store(rotation.ground, Ordering::Relaxed);
+        entity.set_rotation(yaw, pitch);
         // Send the new position to all other players.
         let entity_id = entity.entity_id;
         let yaw = (entity.yaw.load() * 256.0 / 360.0).rem_euclid(256.0);
@@ -527,6 +549,7 @@ impl JavaClient {
         server: &Arc<Server>,
         command: &SChatCommand,
     ) {
+        player.reset_last_action_time();
         let player_clone = player.clone();
         let server_clone = server.clone();
         send_cancellable! {{
@@ -600,7 +623,7 @@ impl JavaClient {
                     .swap_slot_with_hotbar(slot_with_stack as usize)
                     .await;
             }
-        } else if player.gamemode.load() == GameMode::Creative {
+        } else if player.gamemode.load().has_infinite_materials() {
             player.inventory.swap_stack_with_hotbar(stack).await;
         }
 
@@ -654,6 +677,7 @@ impl JavaClient {
         if !player.has_client_loaded() {
             return;
         }
+        player.reset_last_action_time();
 
         if let Ok(action) = Action::try_from(command.action.0) {
             let entity = &player.living_entity.entity;
@@ -691,6 +715,9 @@ impl JavaClient {
         if player.get_entity().sneaking.load(Ordering::Relaxed) != sneak {
             player.get_entity().set_sneaking(sneak).await;
         }
+        if sneak && player.get_entity().has_vehicle().await {
+            Entity::stop_riding(player.clone()).await;
+        }
     }
 
     pub async fn handle_swing_arm(&self, player: &Arc<Player>, swing_arm: SSwingArm) {
@@ -698,6 +725,7 @@ impl JavaClient {
             self.kick(TextComponent::text("Invalid hand")).await;
             return;
         };
+        player.reset_last_action_time();
 
         let inventory = player.inventory();
         let item = inventory.held_item();
@@ -743,7 +771,13 @@ impl JavaClient {
         }}
     }
 
-    pub async fn handle_chat_message(&self, player: &Arc<Player>, chat_message: SChatMessage) {
+    pub async fn handle_chat_message(
+        &self,
+        player: &Arc<Player>,
+        server: &Server,
+        chat_message: SChatMessage,
+    ) {
+        player.reset_last_action_time();
         let gameprofile = &player.gameprofile;
 
         if let Err(err) = self.validate_chat_message(player, &chat_message).await {
@@ -775,26 +809,24 @@ impl JavaClient {
                     None => event.message.clone(),
                 };
 
-                let decorated_message = &TextComponent::chat_decorated(
-                    config.chat.format.clone(),
-                    gameprofile.name.clone(),
-                    message.clone(),
-                );
-
                 let entity = &player.living_entity.entity;
                 let world = &entity.world;
                 if BASIC_CONFIG.allow_chat_reports {
-                    world.broadcast_secure_player_chat(player, &chat_message, decorated_message).await;
-                } else {
-                    let je_packet = CSystemChatMessage::new(
-                        decorated_message,
-                        false,
-                    );
-                    let be_packet = SText::new(
-                        message, player.gameprofile.name.clone()
+                    let decorated_message = &TextComponent::chat_decorated(
+                        config.chat.format.clone(),
+                        gameprofile.name.clone(),
+                        message,
                     );
-
-                    world.broadcast_editioned(&je_packet, &be_packet).await;
+                    // The mask applies to the signed message, which is what filtering clients show
+                    let filtered = server
+                        .filter_text(player, FilterSource::Chat, chat_message.message.clone())
+                        .await;
+                    world
+                        .broadcast_secure_player_chat(player, &chat_message, decorated_message, &filtered)
+                        .await;
+                } else {
+                    let filtered = server.filter_text(player, FilterSource::Chat, message).await;
+                    world.broadcast_unsigned_player_chat(player, &filtered).await;
                 }
             }
         }}
@@ -1064,13 +1096,14 @@ impl JavaClient {
 
     pub async fn handle_interact(
         &self,
-        player: &Player,
+        player: &Arc<Player>,
         interact: SInteract,
         server: &Arc<Server>,
     ) {
         if !player.has_client_loaded() {
             return;
         }
+        player.reset_last_action_time();
         let entity_id = interact.entity_id;
 
         let sneaking = interact.sneaking;
@@ -1085,16 +1118,7 @@ impl JavaClient {
 
         match action {
             ActionType::Attack => {
-                let config = &advanced_config().pvp;
-                // TODO: do validation and stuff
-                if !config.enabled {
-                    return;
-                }
-
-                // TODO: set as camera entity when spectator
-
                 let world = &player_entity.world;
-                let player_victim = world.get_player_by_id(entity_id.0).await;
                 if entity_id.0 == player.entity_id() {
                     // This can't be triggered from a non-modded client.
                     self.kick(TextComponent::translate(
@@ -1104,7 +1128,44 @@ impl JavaClient {
                     .await;
                     return;
                 }
-                if let Some(player_victim) = player_victim {
+                let Some(victim) = world.get_entity_by_id(entity_id.0).await else {
+                    log::error!(
+                        "Player id {} interacted with entity id {}, which was not found.",
+                        player.entity_id(),
+                        entity_id.0
+                    );
+                    self.kick(TextComponent::translate(
+                        "multiplayer.disconnect.invalid_entity_attacked",
+                        [],
+                    ))
+                    .await;
+                    return;
+                };
+
+                // Spectators view the world from the entity they attack
+                if player.gamemode.load() == GameMode::Spectator {
+                    player.set_camera(Some(victim)).await;
+                    return;
+                }
+
+                let event = PLUGIN_MANAGER
+                    .fire(PlayerInteractEntityEvent::new(
+                        player,
+                        victim.clone(),
+                        EntityInteractAction::Attack,
+                        None,
+                    ))
+                    .await;
+                if event.cancelled || victim.skip_attack_interaction(player).await {
+                    return;
+                }
+
+                if let Some(player_victim) = world.get_player_by_id(entity_id.0).await {
+                    let config = &advanced_config().pvp;
+                    // TODO: do validation and stuff
+                    if !config.enabled {
+                        return;
+                    }
                     if player_victim.living_entity.health.load() <= 0.0 {
                         // You can trigger this from a non-modded / innocent client,
                         // so we shouldn't kick the player.
@@ -1123,25 +1184,36 @@ impl JavaClient {
                         return;
                     }
                     player.attack(player_victim).await;
-                } else if let Some(entity_victim) = world.get_entity_by_id(entity_id.0).await {
-                    player.attack(entity_victim).await;
                 } else {
-                    log::error!(
-                        "Player id {} interacted with entity id {}, which was not found.",
-                        player.entity_id(),
-                        entity_id.0
-                    );
-                    self.kick(TextComponent::translate(
-                        "multiplayer.disconnect.invalid_entity_attacked",
-                        [],
-                    ))
-                    .await;
+                    player.attack(victim).await;
                 }
             }
             ActionType::Interact | ActionType::InteractAt => {
-                // TODO: split this up
-                let entity = player.world().get_player_by_id(entity_id.0).await;
-                if let Some(entity) = entity {
+                let Some(entity) = player.world().get_entity_by_id(entity_id.0).await else {
+                    return;
+                };
+                let interact_action = if action == ActionType::Interact {
+                    EntityInteractAction::Interact
+                } else {
+                    EntityInteractAction::InteractAt
+                };
+                let event = PLUGIN_MANAGER
+                    .fire(PlayerInteractEntityEvent::new(
+                        player,
+                        entity.clone(),
+                        interact_action,
+                        interact.target_position,
+                    ))
+                    .await;
+                if event.cancelled {
+                    return;
+                }
+
+                if action == ActionType::Interact {
+                    entity.interact(player).await;
+                    if Entity::ride_on_interact(player, &entity).await {
+                        return;
+                    }
                     let held = player.inventory.held_item();
                     let mut stack = held.lock().await;
                     server
@@ -1163,6 +1235,7 @@ impl JavaClient {
         if !player.has_client_loaded() {
             return;
         }
+        player.reset_last_action_time();
         match Status::try_from(player_action.status.0) {
             Ok(status) => match status {
                 Status::StartedDigging => {
@@ -1177,13 +1250,18 @@ impl JavaClient {
                     let position = player_action.position;
                     let entity = &player.living_entity.entity;
                     let world = &entity.world;
+                    if world.is_out_of_build_height(&position) {
+                        self.update_sequence(player, player_action.sequence.0);
+                        return;
+                    }
                     let (block, state) = world.get_block_and_state(&position).await;
 
                     let inventory = player.inventory();
                     let held = inventory.held_item();
-                    if !server
-                        .item_registry
-                        .can_mine(held.lock().await.item, player)
+                    if !player.gamemode.load().can_modify_world()
+                        || !server
+                            .item_registry
+                            .can_mine(held.lock().await.item, player)
                     {
                         self.enqueue_packet(&CBlockUpdate::new(
                             position,
@@ -1196,7 +1274,7 @@ impl JavaClient {
 
                     // TODO: do validation
                     // TODO: Config
-                    if player.gamemode.load() == GameMode::Creative {
+                    if player.gamemode.load().breaks_blocks_instantly() {
                         // Block break & play sound
                         world
                             .break_block(
@@ -1276,12 +1354,18 @@ impl JavaClient {
                     // Block break & play sound
                     let entity = &player.living_entity.entity;
                     let world = &entity.world;
+                    if world.is_out_of_build_height(&location)
+                        || !player.gamemode.load().can_modify_world()
+                    {
+                        self.update_sequence(player, player_action.sequence.0);
+                        return;
+                    }
 
                     player.mining.store(false, Ordering::Relaxed);
                     world.set_block_breaking(entity, location, -1).await;
 
                     let (block, state) = world.get_block_and_state(&location).await;
-                    let block_drop = player.gamemode.load() != GameMode::Creative
+                    let block_drop = !player.gamemode.load().breaks_blocks_instantly()
                         && player.can_harvest(state, block).await;
 
                     world
@@ -1325,11 +1409,11 @@ impl JavaClient {
             && keep_alive.keep_alive_id == player.keep_alive_id.load(Ordering::Relaxed)
         {
             player.wait_for_keep_alive.store(false, Ordering::Relaxed);
+            let round_trip = player.last_keep_alive_time.load().elapsed().as_millis();
+            player.update_ping(u32::try_from(round_trip).unwrap_or(u32::MAX));
         } else {
-            self.kick(TextComponent::text(
-                "Timeout, The server probably has a deadlock!",
-            ))
-            .await;
+            self.kick(TextComponent::translate("disconnect.timeout", []))
+                .await;
         }
     }
 
@@ -1373,6 +1457,7 @@ impl JavaClient {
         if !player.has_client_loaded() {
             return Ok(());
         }
+        player.reset_last_action_time();
         self.update_sequence(player, use_item_on.sequence.0);
 
         let position = use_item_on.position;
@@ -1460,7 +1545,7 @@ impl JavaClient {
         if should_try_decrement {
             // TODO: Config
             // Decrease block count
-            if player.gamemode.load() != GameMode::Creative {
+            if !player.gamemode.load().has_infinite_materials() {
                 stack.decrement(1);
             }
         }
@@ -1475,7 +1560,7 @@ impl JavaClient {
         position: &BlockPos,
         cursor_pos: &Vector3<f32>,
         face: &BlockDirection,
-        held_item: &Arc<Mutex<ItemStack>>,
+        held_item: &Arc<OrderedMutex<ItemStack>>,
         world: &Arc<World>,
         block: &Block,
         server: &Arc<Server>,
@@ -1520,7 +1605,12 @@ impl JavaClient {
         BlockActionResult::Pass
     }
 
-    pub async fn handle_sign_update(&self, player: &Player, sign_data: SUpdateSign) {
+    pub async fn handle_sign_update(
+        &self,
+        player: &Player,
+        server: &Server,
+        sign_data: SUpdateSign,
+    ) {
         let world = &player.living_entity.entity.world;
         let Some(block_entity) = world.get_block_entity(&sign_data.location).await else {
             return;
@@ -1538,16 +1628,131 @@ impl JavaClient {
             &sign_entity.back_text
         };
 
-        *text.messages.lock().unwrap() = [
-            sign_data.line_1,
-            sign_data.line_2,
-            sign_data.line_3,
-            sign_data.line_4,
+        let lines = [
+            server
+                .filter_text(player, FilterSource::Sign, sign_data.line_1)
+                .await,
+            server
+                .filter_text(player, FilterSource::Sign, sign_data.line_2)
+                .await,
+            server
+                .filter_text(player, FilterSource::Sign, sign_data.line_3)
+                .await,
+            server
+                .filter_text(player, FilterSource::Sign, sign_data.line_4)
+                .await,
         ];
+        let filtered = lines.each_ref().map(FilteredText::filtered_or_empty);
+        // Players that filter text should not be able to write unfiltered text for others
+        let messages = if player.is_text_filtering_enabled().await {
+            filtered.clone()
+        } else {
+            lines.map(|line| line.raw)
+        };
+        text.set_messages(messages, filtered);
         *sign_entity.currently_editing_player.lock().await = None;
         world.update_block_entity(&block_entity).await;
     }
 
+    pub async fn handle_edit_book(&self, player: &Player, server: &Server, edit: SEditBook) {
+        // Hotbar slots and the off hand
+        if !(0..=8).contains(&edit.slot) && edit.slot != 40 {
+            return;
+        }
+        // Players that filter text should not be able to write unfiltered text for others
+        let filtering = player.is_text_filtering_enabled().await;
+        let filterable = |text: FilteredText| {
+            if filtering {
+                Filterable::passthrough(text.filtered_or_empty())
+            } else {
+                Filterable {
+                    filtered: text.is_filtered().then(|| text.filtered_or_empty()),
+                    raw: text.raw,
+                }
+            }
+        };
+        let mut pages = Vec::with_capacity(edit.pages.len());
+        for page in edit.pages {
+            pages.push(filterable(
+                server.filter_text(player, FilterSource::Book, page).await,
+            ));
+        }
+        let title = match edit.title {
+            Some(title) => Some(filterable(
+                server.filter_text(player, FilterSource::Book, title).await,
+            )),
+            None => None,
+        };
+
+        let stack = player.inventory.get_stack(edit.slot as usize).await;
+        let mut stack = stack.lock().await;
+        if stack.item != &Item::WRITABLE_BOOK {
+            return;
+        }
+        if let Some(title) = title {
+            stack.item = &Item::WRITTEN_BOOK;
+            stack.remove_data_component::<WritableBookContentImpl>();
+            stack.set_data_component(
+                WrittenBookContentImpl {
+                    title,
+                    author: player.gameprofile.name.clone(),
+                    generation: 0,
+                    pages: pages
+                        .into_iter()
+                        .map(|page| page.map(TextComponent::text))
+                        .collect(),
+                    resolved: true,
+                }
+                .to_dyn(),
+            );
+        } else {
+            stack.set_data_component(WritableBookContentImpl { pages }.to_dyn());
+        }
+        drop(stack);
+        player
+            .player_screen_handler
+            .lock()
+            .await
+            .send_content_updates()
+            .await;
+    }
+
+    pub async fn handle_rename_item(&self, player: &Player, server: &Server, rename: SRenameItem) {
+        let name = server
+            .filter_text(player, FilterSource::AnvilRename, rename.item_name)
+            .await;
+        // Everyone sees the item name, so it gets the filtered text
+        let name = name.filtered_or_empty();
+
+        let screen_handler = player.current_screen_handler.lock().await.clone();
+        let mut screen_handler = screen_handler.lock().await;
+        let Some(anvil) = screen_handler.as_any().downcast_ref::<AnvilScreenHandler>() else {
+            return;
+        };
+        anvil.set_new_item_name(&name).await;
+        screen_handler.send_content_updates().await;
+    }
+
+    pub async fn handle_custom_payload(
+        &self,
+        player: &Arc<Player>,
+        server: &Server,
+        payload: SCustomPayload,
+    ) {
+        let channel = payload.channel.to_string();
+        if self.handle_common_payload(&channel, &payload.data).await {
+            return;
+        }
+        if let Some(handler) = server.plugin_channels.handler(&channel).await {
+            handler.on_message(player, &channel, &payload.data).await;
+        } else {
+            log::debug!(
+                "{} sent a custom payload on unregistered channel {channel}",
+                player.gameprofile.name
+            );
+        }
+    }
+
     pub async fn handle_use_item(
         &self,
         player: &Arc<Player>,
@@ -1557,6 +1762,7 @@ impl JavaClient {
         if !player.has_client_loaded() {
             return;
         }
+        player.reset_last_action_time();
 
         let inventory = player.inventory();
         let Ok(hand) = Hand::try_from(use_item.hand.0) else {
@@ -1639,6 +1845,7 @@ impl JavaClient {
             self.kick(TextComponent::text("Invalid held slot")).await;
             return;
         }
+        player.reset_last_action_time();
         let inv = player.inventory();
         inv.set_selected_slot(slot as u8);
         let stack = inv.held_item().lock().await.clone();
@@ -1651,7 +1858,7 @@ impl JavaClient {
         player: &Player,
         packet: SSetCreativeSlot,
     ) -> Result<(), InventoryError> {
-        if player.gamemode.load() != GameMode::Creative {
+        if !player.gamemode.load().has_infinite_materials() {
             return Err(InventoryError::PermissionError);
         }
         let is_negative = packet.slot < 0;
@@ -1672,11 +1879,58 @@ impl JavaClient {
             drop(player_screen_handler);
         } else if is_negative && is_legal {
             // Item drop
-            player.drop_item(item_stack).await;
+            player.drop_item(item_stack, true).await;
         }
         Ok(())
     }
 
+    pub async fn handle_debug_sample_subscription(
+        &self,
+        player: &Player,
+        server: &Server,
+        packet: SDebugSampleSubscription,
+    ) {
+        let Some(sample_type) = DebugSampleType::from_id(packet.sample_type.0) else {
+            log::debug!(
+                "{} subscribed to unknown debug sample type {}",
+                player.gameprofile.name,
+                packet.sample_type.0
+            );
+            return;
+        };
+        server
+            .debug_sample_subscriptions
+            .subscribe(player, sample_type)
+            .await;
+    }
+
+    pub async fn handle_spectator_teleport(
+        &self,
+        player: &Arc<Player>,
+        server: &Server,
+        packet: SSpectatorTeleport,
+    ) {
+        if player.gamemode.load() != GameMode::Spectator {
+            return;
+        }
+        let worlds = server.worlds.read().await.clone();
+        for world in worlds {
+            if let Some(target) = world.get_entity_by_uuid(packet.target).await {
+                let target = target.get_entity();
+                player
+                    .clone()
+                    .teleport(
+                        target.pos.load(),
+                        Some(target.yaw.load()),
+                        Some(target.pitch.load()),
+                        world,
+                    )
+                    .await;
+                return;
+            }
+        }
+    }
+
     pub async fn handle_chunk_batch(&self, player: &Player, packet: SChunkBatch) {
         player
             .chunk_manager
@@ -1704,45 +1958,58 @@ impl JavaClient {
         packet: SCommandSuggestion,
         server: &Arc<Server>,
     ) {
-        let src = CommandSender::Player(player.clone());
-        let Some(cmd) = &packet.command.get(1..) else {
+        if !self.allow_suggestion_request(advanced_config().commands.suggestion_rate_limit) {
             return;
-        };
+        }
+        self.latest_suggestion_id
+            .store(packet.id.0, Ordering::Relaxed);
 
+        let Some(cmd) = packet.command.get(1..).map(str::to_string) else {
+            return;
+        };
         let Some((last_word_start, _)) = cmd.char_indices().rfind(|(_, c)| c.is_whitespace())
         else {
             return;
         };
 
-        let suggestions = server
-            .command_dispatcher
-            .read()
-            .await
-            .find_suggestions(&src, server, cmd)
-            .await;
-
-        let response = CCommandSuggestions::new(
-            packet.id,
-            (last_word_start + 2).try_into().unwrap(),
-            (cmd.len() - last_word_start - 1).try_into().unwrap(),
-            suggestions.into(),
-        );
+        // Argument consumers may take a while to come up with suggestions, e.g. when a plugin
+        // looks them up somewhere, so we don't block packet processing for them.
+        let player = player.clone();
+        let server = server.clone();
+        let latest_id = self.latest_suggestion_id.clone();
+        self.spawn_task(async move {
+            let src = CommandSender::Player(player.clone());
+            let suggestions = server
+                .command_dispatcher
+                .read()
+                .await
+                .find_suggestions(&src, &server, &cmd)
+                .await;
 
-        self.enqueue_packet(&response).await;
+            // The client has already asked again, this answer would be thrown away
+            if latest_id.load(Ordering::Relaxed) != packet.id.0 {
+                return;
+            }
+            let response = CCommandSuggestions::new(
+                packet.id,
+                (last_word_start + 2).try_into().unwrap(),
+                (cmd.len() - last_word_start - 1).try_into().unwrap(),
+                suggestions.into(),
+            );
+            player.client.enqueue_packet(&response).await;
+        });
     }
 
-    pub fn handle_cookie_response(&self, packet: &SPCookieResponse) {
-        // TODO: allow plugins to access this
+    pub async fn handle_cookie_response(&self, packet: SPCookieResponse) {
         log::debug!(
             "Received cookie_response[play]: key: \"{}\", payload_length: \"{:?}\"",
             packet.key,
             packet.payload.as_ref().map(|p| p.len())
         );
+        self.complete_cookie_request(packet.key, packet.payload)
+            .await;
     }
 
-    const WORLD_LOWEST_Y: i8 = -64;
-    const WORLD_MAX_Y: u16 = 320;
-
     #[allow(clippy::too_many_lines)]
     async fn run_is_block_place(
         &self,
@@ -1754,19 +2021,20 @@ impl JavaClient {
         face: BlockDirection,
     ) -> Result<bool, BlockPlacingError> {
         let entity = &player.living_entity.entity;
+        let placed_y = location.0.y + face.to_offset().y;
 
         // Check if the block is under the world
-        if location.0.y + face.to_offset().y < i32::from(Self::WORLD_LOWEST_Y) {
+        if placed_y < entity.world.min_y {
             return Err(BlockPlacingError::BlockOutOfWorld);
         }
 
         // Check the world's max build height
-        if location.0.y + face.to_offset().y >= i32::from(Self::WORLD_MAX_Y) {
+        if placed_y > entity.world.top_y() {
             player
                 .send_system_message_raw(
                     &TextComponent::translate(
                         "build.tooHigh",
-                        vec![TextComponent::text((Self::WORLD_MAX_Y - 1).to_string())],
+                        vec![TextComponent::text(entity.world.top_y().to_string())],
                     )
                     .color_named(NamedColor::Red),
                     true,
@@ -1775,11 +2043,8 @@ impl JavaClient {
             return Err(BlockPlacingError::BlockOutOfWorld);
         }
 
-        match player.gamemode.load() {
-            GameMode::Spectator | GameMode::Adventure => {
-                return Err(BlockPlacingError::InvalidGamemode);
-            }
-            _ => {}
+        if !player.gamemode.load().can_modify_world() {
+            return Err(BlockPlacingError::InvalidGamemode);
         }
 
         let clicked_block_pos = BlockPos(location.0);
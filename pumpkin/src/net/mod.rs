@@ -28,10 +28,15 @@ pub mod authentication;
 pub mod bedrock;
 pub mod java;
 pub mod lan_broadcast;
+pub mod packet_capture;
+pub mod plugin_channel;
 mod proxy;
 pub mod query;
 pub mod rcon;
 
+/// The profile property holding the skin and cape textures.
+pub const TEXTURES_PROPERTY: &str = "textures";
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct GameProfile {
     pub id: Uuid,
@@ -167,6 +172,20 @@ impl ClientPlatform {
         }
     }
 
+    pub fn hold_flushes(&self) {
+        match self {
+            Self::Java(java) => java.hold_flushes(),
+            Self::Bedrock(_) => (),
+        }
+    }
+
+    pub fn release_flushes(&self) {
+        match self {
+            Self::Java(java) => java.release_flushes(),
+            Self::Bedrock(_) => (),
+        }
+    }
+
     pub async fn kick(&self, reason: DisconnectReason, message: TextComponent) {
         match self {
             Self::Java(java) => java.kick(message).await,
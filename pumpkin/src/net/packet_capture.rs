@@ -0,0 +1,354 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use pumpkin_config::advanced_config;
+use pumpkin_config::networking::packet_capture::PacketCaptureConfig;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::{ConnectionState, MAX_PACKET_DATA_SIZE, RawPacket};
+use tokio::sync::mpsc;
+
+use crate::entity::player::Player;
+use crate::net::java::JavaClient;
+use crate::server::Server;
+
+/// Identifies capture files, followed by the format version.
+const MAGIC: &[u8; 4] = b"PKPC";
+const VERSION: u8 = 1;
+/// The extension of capture files.
+pub const CAPTURE_EXTENSION: &str = "pktcap";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketDirection {
+    Serverbound,
+    Clientbound,
+}
+
+impl PacketDirection {
+    const fn is_enabled(self, config: &PacketCaptureConfig) -> bool {
+        match self {
+            Self::Serverbound => config.serverbound,
+            Self::Clientbound => config.clientbound,
+        }
+    }
+}
+
+const fn state_to_byte(state: ConnectionState) -> u8 {
+    match state {
+        ConnectionState::HandShake => 0,
+        ConnectionState::Status => 1,
+        ConnectionState::Login => 2,
+        ConnectionState::Transfer => 3,
+        ConnectionState::Config => 4,
+        ConnectionState::Play => 5,
+    }
+}
+
+fn state_from_byte(byte: u8) -> io::Result<ConnectionState> {
+    Ok(match byte {
+        0 => ConnectionState::HandShake,
+        1 => ConnectionState::Status,
+        2 => ConnectionState::Login,
+        3 => ConnectionState::Transfer,
+        4 => ConnectionState::Config,
+        5 => ConnectionState::Play,
+        _ => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid connection state {byte}"),
+            ));
+        }
+    })
+}
+
+/// A packet as it was recorded in a capture file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedPacket {
+    /// Time since the connection was opened
+    pub elapsed: Duration,
+    pub direction: PacketDirection,
+    /// The connection state the packet was sent in
+    pub state: ConnectionState,
+    pub id: i32,
+    /// The uncompressed and decrypted packet data, without the id
+    pub payload: Bytes,
+}
+
+impl CapturedPacket {
+    fn write(&self, write: &mut impl Write) -> io::Result<()> {
+        // Captures are not meant to outlive a connection by half a billion years
+        let elapsed = u64::try_from(self.elapsed.as_micros()).unwrap_or(u64::MAX);
+        write.write_all(&elapsed.to_be_bytes())?;
+        write.write_all(&[
+            u8::from(self.direction == PacketDirection::Clientbound),
+            state_to_byte(self.state),
+        ])?;
+        write.write_all(&self.id.to_be_bytes())?;
+        let len = u32::try_from(self.payload.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "Packet too large"))?;
+        write.write_all(&len.to_be_bytes())?;
+        write.write_all(&self.payload)
+    }
+
+    /// Reads the next packet, or `None` at the end of the capture.
+    fn read(read: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut elapsed = [0; 8];
+        match read.read_exact(&mut elapsed) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let mut header = [0; 10];
+        read.read_exact(&mut header)?;
+        let direction = if header[0] == 0 {
+            PacketDirection::Serverbound
+        } else {
+            PacketDirection::Clientbound
+        };
+        let state = state_from_byte(header[1])?;
+        let id = i32::from_be_bytes([header[2], header[3], header[4], header[5]]);
+        let len = u32::from_be_bytes([header[6], header[7], header[8], header[9]]) as usize;
+        // Don't trust the length of a damaged or made up capture with the allocation
+        if len > MAX_PACKET_DATA_SIZE {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Packet of {len} bytes is larger than any valid packet"),
+            ));
+        }
+        let mut payload = vec![0; len];
+        read.read_exact(&mut payload)?;
+        Ok(Some(Self {
+            elapsed: Duration::from_micros(u64::from_be_bytes(elapsed)),
+            direction,
+            state,
+            id,
+            payload: payload.into(),
+        }))
+    }
+}
+
+/// Logs and records the packets of a single connection, as configured in
+/// [`PacketCaptureConfig`].
+pub struct PacketCapture {
+    client_id: u64,
+    opened: Instant,
+    /// Hands recorded packets to the writer, which owns the capture file. Dropping it ends the
+    /// capture.
+    writer: Option<mpsc::UnboundedSender<CapturedPacket>>,
+}
+
+impl PacketCapture {
+    /// Starts capturing the packets of a connection, or returns `None` if packet capture is
+    /// disabled.
+    pub fn new(client_id: u64) -> Option<Self> {
+        let config = &advanced_config().networking.packet_capture;
+        if !config.is_enabled() {
+            return None;
+        }
+
+        let writer = config.capture.then(|| {
+            let (send, recv) = mpsc::unbounded_channel();
+            let directory = PathBuf::from(&config.directory);
+            // File IO blocks, so the file is only ever touched by this writer
+            tokio::task::spawn_blocking(move || {
+                if let Err(err) = Self::write_file(&directory, client_id, recv) {
+                    log::error!("Failed to write packet capture for client {client_id}: {err}");
+                }
+            });
+            send
+        });
+        Some(Self {
+            client_id,
+            opened: Instant::now(),
+            writer,
+        })
+    }
+
+    /// Creates a capture file for the client in `directory` and writes the packets received from
+    /// `packets` to it until the capture ends.
+    fn write_file(
+        directory: &Path,
+        client_id: u64,
+        mut packets: mpsc::UnboundedReceiver<CapturedPacket>,
+    ) -> io::Result<()> {
+        fs::create_dir_all(directory)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let file =
+            File::create(directory.join(format!("{timestamp}-{client_id}.{CAPTURE_EXTENSION}")))?;
+        let mut file = BufWriter::new(file);
+        file.write_all(MAGIC)?;
+        file.write_all(&[VERSION])?;
+        while let Some(packet) = packets.blocking_recv() {
+            packet.write(&mut file)?;
+        }
+        file.flush()
+    }
+
+    /// Records a packet if it passes the configured filters.
+    pub fn record(
+        &self,
+        direction: PacketDirection,
+        state: ConnectionState,
+        id: i32,
+        payload: &[u8],
+    ) {
+        let config = &advanced_config().networking.packet_capture;
+        if !direction.is_enabled(config) || !config.matches_id(id) {
+            return;
+        }
+
+        if config.log {
+            log::debug!(
+                "[{}] {direction:?} {state:?} packet 0x{id:02X} ({} bytes)",
+                self.client_id,
+                payload.len()
+            );
+        }
+        if let Some(writer) = &self.writer {
+            // Fails once the writer gave up, which it already logged
+            let _ = writer.send(CapturedPacket {
+                elapsed: self.opened.elapsed(),
+                direction,
+                state,
+                id,
+                payload: Bytes::copy_from_slice(payload),
+            });
+        }
+    }
+
+    /// Records a clientbound packet, which still starts with its id.
+    pub fn record_clientbound(&self, state: ConnectionState, data: &[u8]) {
+        let mut payload = data;
+        if let Ok(id) = VarInt::decode(&mut payload) {
+            self.record(PacketDirection::Clientbound, state, id.0, payload);
+        }
+    }
+}
+
+/// The path of the capture file called `name` in the configured capture directory.
+#[must_use]
+pub fn capture_path(name: &str) -> PathBuf {
+    Path::new(&advanced_config().networking.packet_capture.directory)
+        .join(format!("{name}.{CAPTURE_EXTENSION}"))
+}
+
+/// Reads every packet of a capture file.
+pub fn read_capture(read: impl Read) -> io::Result<Vec<CapturedPacket>> {
+    let mut read = BufReader::new(read);
+    let mut header = [0; 5];
+    read.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "Not a packet capture",
+        ));
+    }
+    if header[4] != VERSION {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Unsupported packet capture version {}", header[4]),
+        ));
+    }
+
+    let mut packets = Vec::new();
+    while let Some(packet) = CapturedPacket::read(&mut read)? {
+        packets.push(packet);
+    }
+    Ok(packets)
+}
+
+/// Feeds the serverbound play packets of a capture through the packet handlers as if `player`
+/// sent them, keeping their original timing. Returns how many packets were replayed.
+pub async fn replay(
+    client: &JavaClient,
+    player: &Arc<Player>,
+    server: &Arc<Server>,
+    packets: &[CapturedPacket],
+) -> usize {
+    let packets: Vec<_> = packets
+        .iter()
+        .filter(|packet| {
+            packet.direction == PacketDirection::Serverbound
+                && packet.state == ConnectionState::Play
+        })
+        .collect();
+    let Some(first) = packets.first() else {
+        return 0;
+    };
+
+    let start = tokio::time::Instant::now();
+    for packet in &packets {
+        tokio::time::sleep_until(start + packet.elapsed.saturating_sub(first.elapsed)).await;
+        if player.client.closed() {
+            break;
+        }
+        let raw = RawPacket {
+            id: packet.id,
+            payload: packet.payload.clone(),
+        };
+        if let Err(err) = client.handle_play_packet(player, server, &raw).await {
+            err.log();
+        }
+    }
+    packets.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pumpkin_protocol::{ConnectionState, MAX_PACKET_DATA_SIZE};
+
+    use super::{CapturedPacket, MAGIC, PacketDirection, VERSION, read_capture};
+
+    #[test]
+    fn capture_round_trip() {
+        let packets = [
+            CapturedPacket {
+                elapsed: Duration::from_millis(5),
+                direction: PacketDirection::Serverbound,
+                state: ConnectionState::Play,
+                id: 0x1C,
+                payload: vec![1, 2, 3].into(),
+            },
+            CapturedPacket {
+                elapsed: Duration::from_millis(12),
+                direction: PacketDirection::Clientbound,
+                state: ConnectionState::Config,
+                id: 0x07,
+                payload: Vec::new().into(),
+            },
+        ];
+        let mut data = MAGIC.to_vec();
+        data.push(VERSION);
+        for packet in &packets {
+            packet.write(&mut data).unwrap();
+        }
+
+        assert_eq!(read_capture(data.as_slice()).unwrap(), packets);
+        assert!(read_capture(&b"nope!"[..]).is_err());
+    }
+
+    #[test]
+    fn oversized_packet_is_rejected() {
+        let mut data = MAGIC.to_vec();
+        data.push(VERSION);
+        data.extend_from_slice(&0u64.to_be_bytes());
+        data.extend_from_slice(&[0, 5]);
+        data.extend_from_slice(&0x1C_i32.to_be_bytes());
+        data.extend_from_slice(
+            &u32::try_from(MAX_PACKET_DATA_SIZE + 1)
+                .unwrap()
+                .to_be_bytes(),
+        );
+
+        let err = read_capture(data.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::entity::player::Player;
+
+/// Carries the name of the client or server software.
+pub const BRAND_CHANNEL: &str = "minecraft:brand";
+/// Announces channels the sender is able to receive.
+pub const REGISTER_CHANNEL: &str = "minecraft:register";
+/// Withdraws channels previously announced with [`REGISTER_CHANNEL`].
+pub const UNREGISTER_CHANNEL: &str = "minecraft:unregister";
+/// How many channels a client may register, like Bukkit.
+pub const MAX_CHANNELS: usize = 128;
+
+/// Receives custom payloads players send on a registered channel.
+#[async_trait]
+pub trait PluginChannelHandler: Send + Sync {
+    async fn on_message(&self, player: &Arc<Player>, channel: &str, data: &[u8]);
+}
+
+/// Custom payload channels the server listens on.
+#[derive(Default)]
+pub struct PluginChannels {
+    handlers: RwLock<HashMap<String, Arc<dyn PluginChannelHandler>>>,
+}
+
+impl PluginChannels {
+    /// Starts passing payloads sent on `channel` to `handler`.
+    ///
+    /// Fails if the channel is reserved by the game or already has a handler.
+    pub async fn register(
+        &self,
+        channel: &str,
+        handler: Arc<dyn PluginChannelHandler>,
+    ) -> Result<(), String> {
+        if !is_valid_channel(channel) {
+            return Err(format!("Invalid channel name {channel}"));
+        }
+        if channel.starts_with("minecraft:") {
+            return Err(format!("Channel {channel} is reserved by the game"));
+        }
+        let mut handlers = self.handlers.write().await;
+        if handlers.contains_key(channel) {
+            return Err(format!("Channel {channel} is already registered"));
+        }
+        handlers.insert(channel.to_string(), handler);
+        Ok(())
+    }
+
+    /// Stops listening on `channel`, returning whether it was registered.
+    pub async fn unregister(&self, channel: &str) -> bool {
+        self.handlers.write().await.remove(channel).is_some()
+    }
+
+    pub async fn handler(&self, channel: &str) -> Option<Arc<dyn PluginChannelHandler>> {
+        self.handlers.read().await.get(channel).cloned()
+    }
+
+    /// The names of all registered channels, as announced to clients.
+    pub async fn channel_names(&self) -> Vec<String> {
+        self.handlers.read().await.keys().cloned().collect()
+    }
+}
+
+/// Whether `channel` is a valid `namespace:path` identifier.
+#[must_use]
+pub fn is_valid_channel(channel: &str) -> bool {
+    let Some((namespace, path)) = channel.split_once(':') else {
+        return false;
+    };
+    !namespace.is_empty()
+        && !path.is_empty()
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.'))
+        && path.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.' | '/')
+        })
+}
+
+/// Encodes channel names for [`REGISTER_CHANNEL`] and [`UNREGISTER_CHANNEL`] payloads.
+#[must_use]
+pub fn encode_channel_list<S: AsRef<str>>(channels: &[S]) -> Vec<u8> {
+    channels
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join("\0")
+        .into_bytes()
+}
+
+/// Decodes a [`REGISTER_CHANNEL`] or [`UNREGISTER_CHANNEL`] payload, skipping invalid names.
+#[must_use]
+pub fn decode_channel_list(data: &[u8]) -> Vec<String> {
+    data.split(|byte| *byte == 0)
+        .filter_map(|name| std::str::from_utf8(name).ok())
+        .filter(|name| is_valid_channel(name))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Adds the valid channel names of a [`REGISTER_CHANNEL`] payload to `channels`.
+///
+/// Returns `false` if the client tried to register more than [`MAX_CHANNELS`] channels, in which
+/// case the rest of the payload is ignored.
+#[must_use]
+pub fn register_channels(channels: &mut HashSet<String>, data: &[u8]) -> bool {
+    for channel in decode_channel_list(data) {
+        if channels.len() >= MAX_CHANNELS && !channels.contains(&channel) {
+            return false;
+        }
+        channels.insert(channel);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{
+        MAX_CHANNELS, decode_channel_list, encode_channel_list, is_valid_channel, register_channels,
+    };
+
+    #[test]
+    fn channel_list_round_trip() {
+        let channels = ["bungeecord:main", "velocity:player_info"];
+        let data = encode_channel_list(&channels);
+        assert_eq!(data, b"bungeecord:main\0velocity:player_info");
+        assert_eq!(decode_channel_list(&data), channels);
+        assert_eq!(
+            decode_channel_list(b"fabric:a\0Not Valid\0\0"),
+            ["fabric:a"]
+        );
+    }
+
+    #[test]
+    fn validates_channel_names() {
+        assert!(is_valid_channel("my_plugin:data/sync"));
+        assert!(!is_valid_channel("no_namespace"));
+        assert!(!is_valid_channel("Upper:case"));
+        assert!(!is_valid_channel("empty:"));
+    }
+
+    #[test]
+    fn caps_registered_channels() {
+        let names: Vec<String> = (0..=MAX_CHANNELS).map(|i| format!("spam:{i}")).collect();
+        let mut channels = HashSet::new();
+        assert!(register_channels(
+            &mut channels,
+            &encode_channel_list(&names[..MAX_CHANNELS])
+        ));
+        // Registering a known channel again is fine
+        assert!(register_channels(&mut channels, b"spam:0\0Invalid"));
+        assert_eq!(channels.len(), MAX_CHANNELS);
+        assert!(!register_channels(
+            &mut channels,
+            names[MAX_CHANNELS].as_bytes()
+        ));
+        assert_eq!(channels.len(), MAX_CHANNELS);
+    }
+}
@@ -4,7 +4,8 @@ use pumpkin_protocol::Property;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::net::{GameProfile, offline_uuid};
+use crate::data::profile_cache::offline_profile_uuid;
+use crate::net::GameProfile;
 
 #[derive(Error, Debug)]
 pub enum BungeeCordError {
@@ -48,7 +49,9 @@ pub async fn bungeecord_login(
     // The UUID of the player; only given if `ip_forward` on bungee is true.
     let id = match data.get(2) {
         Some(uuid) => uuid.parse().map_err(|_| BungeeCordError::FailedParseUUID)?,
-        None => offline_uuid(name.as_str()).map_err(|_| BungeeCordError::FailedMakeOfflineUUID)?,
+        None => offline_profile_uuid(name.as_str())
+            .await
+            .map_err(|_| BungeeCordError::FailedMakeOfflineUUID)?,
     };
 
     // Read properties and get textures.
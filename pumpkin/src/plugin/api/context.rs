@@ -8,7 +8,12 @@ use pumpkin_util::{
 use tokio::sync::RwLock;
 
 use crate::{
+    data::profile_cache::resolve_profile,
     entity::player::Player,
+    net::GameProfile,
+    net::plugin_channel::{
+        PluginChannelHandler, REGISTER_CHANNEL, UNREGISTER_CHANNEL, encode_channel_list,
+    },
     plugin::{EventHandler, HandlerMap, PluginManager, TypedEventHandler},
     server::Server,
 };
@@ -80,6 +85,23 @@ impl Context {
         self.server.get_player_by_name(&player_name).await
     }
 
+    /// Asynchronously resolves the game profile of any player, online or not.
+    ///
+    /// Profiles are taken from the profile cache while it is fresh, and looked up at Mojang in
+    /// online mode otherwise.
+    ///
+    /// # Arguments
+    /// - `player_name`: The name of the player to resolve.
+    ///
+    /// # Returns
+    /// The profile, or `None` if there is no player with that name.
+    pub async fn resolve_profile(&self, player_name: &str) -> Option<GameProfile> {
+        if let Some(player) = self.server.get_player_by_name(player_name).await {
+            return Some(player.gameprofile.clone());
+        }
+        resolve_profile(player_name).await
+    }
+
     /// Registers a service with the plugin context.
     ///
     /// This method allows you to associate a service instance with a given name,
@@ -179,6 +201,43 @@ impl Context {
         }
     }
 
+    /// Registers a handler for custom payloads players send on `channel`, and announces the
+    /// channel to every online player.
+    ///
+    /// # Arguments
+    /// - `channel`: The `namespace:path` name of the channel.
+    /// - `handler`: The handler receiving the payloads.
+    pub async fn register_plugin_channel(
+        &self,
+        channel: &str,
+        handler: Arc<dyn PluginChannelHandler>,
+    ) -> Result<(), String> {
+        self.server
+            .plugin_channels
+            .register(channel, handler)
+            .await?;
+        let data = encode_channel_list(&[channel]);
+        for player in self.server.get_all_players().await {
+            player.send_custom_payload(REGISTER_CHANNEL, &data).await;
+        }
+        Ok(())
+    }
+
+    /// Unregisters the handler of `channel`, and tells every online player the server no longer
+    /// listens on it.
+    ///
+    /// # Arguments
+    /// - `channel`: The name of the channel to unregister.
+    pub async fn unregister_plugin_channel(&self, channel: &str) {
+        if !self.server.plugin_channels.unregister(channel).await {
+            return;
+        }
+        let data = encode_channel_list(&[channel]);
+        for player in self.server.get_all_players().await {
+            player.send_custom_payload(UNREGISTER_CHANNEL, &data).await;
+        }
+    }
+
     /// Register a permission for this plugin
     pub async fn register_permission(&self, permission: Permission) -> Result<(), String> {
         // Ensure the permission has the correct namespace
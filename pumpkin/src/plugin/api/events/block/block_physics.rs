@@ -0,0 +1,61 @@
+use pumpkin_data::Block;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use std::sync::Arc;
+
+use crate::world::World;
+
+use super::BlockEvent;
+
+/// An event that occurs when a block receives a neighbor update.
+///
+/// Cancelling this event prevents the block from reacting to the change of its neighbor.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct BlockPhysicsEvent {
+    /// The world in which the update happens.
+    pub world: Arc<World>,
+
+    /// The block that is being updated.
+    pub block: &'static Block,
+
+    /// The position of the block that is being updated.
+    pub block_position: BlockPos,
+
+    /// The block that caused the update.
+    pub source_block: &'static Block,
+}
+
+impl BlockPhysicsEvent {
+    /// Creates a new instance of `BlockPhysicsEvent`.
+    ///
+    /// # Arguments
+    /// - `world`: The world in which the update happens.
+    /// - `block`: The block that is being updated.
+    /// - `block_position`: The position of the block that is being updated.
+    /// - `source_block`: The block that caused the update.
+    ///
+    /// # Returns
+    /// A new instance of `BlockPhysicsEvent`.
+    #[must_use]
+    pub fn new(
+        world: Arc<World>,
+        block: &'static Block,
+        block_position: BlockPos,
+        source_block: &'static Block,
+    ) -> Self {
+        Self {
+            world,
+            block,
+            block_position,
+            source_block,
+            cancelled: false,
+        }
+    }
+}
+
+impl BlockEvent for BlockPhysicsEvent {
+    fn get_block(&self) -> &Block {
+        self.block
+    }
+}
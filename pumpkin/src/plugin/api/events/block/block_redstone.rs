@@ -0,0 +1,68 @@
+use pumpkin_data::Block;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::position::BlockPos;
+use std::sync::Arc;
+
+use crate::world::World;
+
+use super::BlockEvent;
+
+/// An event that occurs when the redstone power of a block changes.
+///
+/// Plugins may change `new_current` to alter the resulting power level. Cancelling this event
+/// keeps the old power level.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct RedstoneChangeEvent {
+    /// The world in which the change happens.
+    pub world: Arc<World>,
+
+    /// The block whose power is changing.
+    pub block: &'static Block,
+
+    /// The position of the block whose power is changing.
+    pub block_position: BlockPos,
+
+    /// The power level before the change.
+    pub old_current: u8,
+
+    /// The power level after the change.
+    pub new_current: u8,
+}
+
+impl RedstoneChangeEvent {
+    /// Creates a new instance of `RedstoneChangeEvent`.
+    ///
+    /// # Arguments
+    /// - `world`: The world in which the change happens.
+    /// - `block`: The block whose power is changing.
+    /// - `block_position`: The position of the block whose power is changing.
+    /// - `old_current`: The power level before the change.
+    /// - `new_current`: The power level after the change.
+    ///
+    /// # Returns
+    /// A new instance of `RedstoneChangeEvent`.
+    #[must_use]
+    pub fn new(
+        world: Arc<World>,
+        block: &'static Block,
+        block_position: BlockPos,
+        old_current: u8,
+        new_current: u8,
+    ) -> Self {
+        Self {
+            world,
+            block,
+            block_position,
+            old_current,
+            new_current,
+            cancelled: false,
+        }
+    }
+}
+
+impl BlockEvent for RedstoneChangeEvent {
+    fn get_block(&self) -> &Block {
+        self.block
+    }
+}
@@ -1,7 +1,9 @@
 pub mod block_break;
 pub mod block_burn;
 pub mod block_can_build;
+pub mod block_physics;
 pub mod block_place;
+pub mod block_redstone;
 
 use pumpkin_data::Block;
 
@@ -0,0 +1,66 @@
+use pumpkin_data::damage::DamageType;
+use pumpkin_macros::{Event, cancellable};
+use std::sync::Arc;
+
+use crate::entity::EntityBase;
+
+use super::EntityEvent;
+
+/// An event that occurs when a living entity is about to take damage, no matter if it comes from
+/// an attack, the environment or a command like `/damage` or `/kill`.
+///
+/// If the event is cancelled, the entity takes no damage.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct EntityDamageEvent {
+    /// The entity taking damage.
+    pub entity: Arc<dyn EntityBase>,
+
+    /// The amount of damage, before armor and effects reduce it.
+    pub damage: f32,
+
+    /// The type of the damage.
+    pub damage_type: DamageType,
+
+    /// The entity that directly dealt the damage, e.g. an arrow.
+    pub source: Option<Arc<dyn EntityBase>>,
+
+    /// The entity responsible for the damage, e.g. the player who shot the arrow.
+    pub cause: Option<Arc<dyn EntityBase>>,
+}
+
+impl EntityDamageEvent {
+    /// Creates a new instance of `EntityDamageEvent`.
+    ///
+    /// # Arguments
+    /// - `entity`: The entity taking damage.
+    /// - `damage`: The amount of damage.
+    /// - `damage_type`: The type of the damage.
+    /// - `source`: The entity that directly dealt the damage, if any.
+    /// - `cause`: The entity responsible for the damage, if any.
+    ///
+    /// # Returns
+    /// A new instance of `EntityDamageEvent`.
+    pub fn new(
+        entity: Arc<dyn EntityBase>,
+        damage: f32,
+        damage_type: DamageType,
+        source: Option<Arc<dyn EntityBase>>,
+        cause: Option<Arc<dyn EntityBase>>,
+    ) -> Self {
+        Self {
+            entity,
+            damage,
+            damage_type,
+            source,
+            cause,
+            cancelled: false,
+        }
+    }
+}
+
+impl EntityEvent for EntityDamageEvent {
+    fn get_entity(&self) -> &Arc<dyn EntityBase> {
+        &self.entity
+    }
+}
@@ -1,7 +1,9 @@
+pub mod player_afk_change;
 pub mod player_change_world;
 pub mod player_chat;
 pub mod player_command_send;
 pub mod player_gamemode_change;
+pub mod player_interact_entity_event;
 pub mod player_interact_event;
 pub mod player_join;
 pub mod player_leave;
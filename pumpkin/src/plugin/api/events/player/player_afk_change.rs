@@ -0,0 +1,38 @@
+use pumpkin_macros::Event;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// An event that occurs when a player becomes AFK after being idle, or comes back.
+///
+/// The idle time is measured from the player's last action, see `Player::reset_last_action_time`.
+#[derive(Event, Clone)]
+pub struct PlayerAfkChangeEvent {
+    /// The player whose AFK state changed.
+    pub player: Arc<Player>,
+
+    /// Whether the player is AFK now.
+    pub afk: bool,
+}
+
+impl PlayerAfkChangeEvent {
+    /// Creates a new instance of `PlayerAfkChangeEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player whose AFK state changed.
+    /// - `afk`: Whether the player is AFK now.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerAfkChangeEvent`.
+    pub fn new(player: Arc<Player>, afk: bool) -> Self {
+        Self { player, afk }
+    }
+}
+
+impl PlayerEvent for PlayerAfkChangeEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
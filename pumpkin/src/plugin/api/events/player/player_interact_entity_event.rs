@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::entity::{EntityBase, player::Player};
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::vector3::Vector3;
+
+use super::PlayerEvent;
+
+/// Event that is triggered when a player left or right clicks an entity.
+///
+/// This is also fired for entities that only exist to be clicked, like interaction entities, so
+/// plugins can react to clicks on them.
+/// It can be cancelled to prevent the default interaction behavior, like dealing damage.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerInteractEntityEvent {
+    /// The player who performed the interaction.
+    pub player: Arc<Player>,
+
+    /// The entity that was clicked.
+    pub entity: Arc<dyn EntityBase>,
+
+    /// The type of action the player performed.
+    pub action: EntityInteractAction,
+
+    /// Where the entity was clicked, relative to its position, if the client sent it.
+    pub position: Option<Vector3<f32>>,
+}
+
+impl PlayerInteractEntityEvent {
+    /// Creates a new instance of `PlayerInteractEntityEvent`.
+    ///
+    /// # Arguments
+    ///
+    /// - `player`: A reference-counted pointer to the player who triggered the event.
+    /// - `entity`: The entity that was clicked.
+    /// - `action`: The type of interaction performed.
+    /// - `position`: The optional position on the entity that was clicked.
+    ///
+    /// # Returns
+    ///
+    /// A new `PlayerInteractEntityEvent` instance with the specified data.
+    pub fn new(
+        player: &Arc<Player>,
+        entity: Arc<dyn EntityBase>,
+        action: EntityInteractAction,
+        position: Option<Vector3<f32>>,
+    ) -> Self {
+        Self {
+            player: Arc::clone(player),
+            entity,
+            action,
+            position,
+            cancelled: false,
+        }
+    }
+}
+
+/// Enum representing the ways a player can click an entity.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EntityInteractAction {
+    /// Left-clicking the entity
+    Attack,
+
+    /// Right-clicking the entity
+    Interact,
+
+    /// Right-clicking a specific point of the entity, sent before `Interact`
+    InteractAt,
+}
+
+impl PlayerEvent for PlayerInteractEntityEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
@@ -3,9 +3,9 @@ use std::sync::Arc;
 use crate::entity::player::Player;
 use pumpkin_data::Block;
 use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::lock_order::OrderedMutex;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::item::ItemStack;
-use tokio::sync::Mutex;
 
 use super::PlayerEvent;
 
@@ -27,7 +27,7 @@ pub struct PlayerInteractEvent {
     pub clicked_pos: Option<BlockPos>,
 
     /// The item in the player's hand at the time of interaction.
-    pub item: Arc<Mutex<ItemStack>>,
+    pub item: Arc<OrderedMutex<ItemStack>>,
 
     /// The block that was interacted with.
     pub block: &'static Block,
@@ -50,7 +50,7 @@ impl PlayerInteractEvent {
     pub fn new(
         player: &Arc<Player>,
         action: InteractAction,
-        item: &Arc<Mutex<ItemStack>>,
+        item: &Arc<OrderedMutex<ItemStack>>,
         block: &'static Block,
         clicked_pos: Option<BlockPos>,
     ) -> Self {
@@ -1,8 +1,7 @@
 use crate::world::World;
 use pumpkin_macros::{Event, cancellable};
-use pumpkin_world::chunk::ChunkData;
+use pumpkin_world::level::SyncChunk;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 /// An event that occurs when a chunk is saved in a world.
 ///
@@ -14,5 +13,5 @@ pub struct ChunkSave {
     pub world: Arc<World>,
 
     /// The chunk data being saved, wrapped in a read-write lock for safe concurrent access.
-    pub chunk: Arc<RwLock<ChunkData>>,
+    pub chunk: SyncChunk,
 }
@@ -0,0 +1,18 @@
+use crate::world::World;
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::math::vector2::Vector2;
+use std::sync::Arc;
+
+/// An event that occurs when a chunk is no longer watched by any player and is about to be unloaded.
+///
+/// Cancelling this event keeps the chunk in memory. The event is fired again for the chunk about
+/// every second until it is no longer cancelled or a player watches the chunk again.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct ChunkUnload {
+    /// The world in which the chunk is being unloaded.
+    pub world: Arc<World>,
+
+    /// The position of the chunk being unloaded.
+    pub chunk_position: Vector2<i32>,
+}
@@ -1,3 +1,4 @@
 pub mod chunk_load;
 pub mod chunk_save;
 pub mod chunk_send;
+pub mod chunk_unload;
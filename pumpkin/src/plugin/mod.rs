@@ -594,6 +594,17 @@ impl PluginManager {
             .push(Box::new(typed_handler));
     }
 
+    /// Checks whether any handler is registered for the event type `E`.
+    ///
+    /// Hot paths can use this to avoid constructing events nobody listens to.
+    pub async fn has_listeners<E: Payload + 'static>(&self) -> bool {
+        self.handlers
+            .read()
+            .await
+            .get(&E::get_name_static())
+            .is_some_and(|handlers| !handlers.is_empty())
+    }
+
     /// Fire an event to all registered handlers
     pub async fn fire<E: Payload + Send + Sync + 'static>(&self, mut event: E) -> E {
         if let Some(server) = self.server.read().await.as_ref() {
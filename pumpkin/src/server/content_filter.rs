@@ -0,0 +1,264 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_config::{ContentFilterConfig, advanced_config};
+use pumpkin_protocol::codec::bit_set::BitSet;
+use pumpkin_protocol::java::client::play::FilterType;
+use regex::{Regex, RegexBuilder};
+
+use super::Server;
+use crate::entity::player::Player;
+
+/// Replaces every filtered character, like the vanilla client does.
+const FILTER_CHARACTER: char = '#';
+
+/// Where a piece of player written text comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterSource {
+    Chat,
+    Sign,
+    Book,
+    AnvilRename,
+}
+
+impl FilterSource {
+    /// Whether the configuration asks for text from this source to be filtered.
+    #[must_use]
+    pub fn is_enabled(self, config: &ContentFilterConfig) -> bool {
+        match self {
+            Self::Chat => config.chat,
+            Self::Sign => config.signs,
+            Self::Book => config.books,
+            Self::AnvilRename => config.anvil_renames,
+        }
+    }
+}
+
+/// Which parts of a text are hidden from players that see filtered text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterMask {
+    PassThrough,
+    FullyFiltered,
+    /// Byte ranges of the raw text that are filtered, sorted and not overlapping
+    PartiallyFiltered(Vec<Range<usize>>),
+}
+
+/// Decides which parts of player written text should be filtered.
+///
+/// The default implementation matches the regular expressions from the configuration, plugins can
+/// replace it with [`Server::set_content_filter`].
+#[async_trait]
+pub trait ContentFilter: Send + Sync {
+    async fn filter(&self, player: &Player, source: FilterSource, text: &str) -> FilterMask;
+}
+
+/// Filters every match of a set of case insensitive regular expressions.
+pub struct RegexContentFilter {
+    patterns: Vec<Regex>,
+}
+
+impl RegexContentFilter {
+    /// Compiles the configured patterns, skipping (and logging) invalid ones.
+    #[must_use]
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .inspect_err(|err| {
+                        log::warn!("Ignoring invalid content filter pattern {pattern:?}: {err}");
+                    })
+                    .ok()
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    #[must_use]
+    pub fn mask(&self, text: &str) -> FilterMask {
+        let mut ranges: Vec<Range<usize>> = self
+            .patterns
+            .iter()
+            .flat_map(|pattern| pattern.find_iter(text).map(|found| found.range()))
+            .filter(|range| !range.is_empty())
+            .collect();
+        if ranges.is_empty() {
+            return FilterMask::PassThrough;
+        }
+
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            if let Some(last) = merged.last_mut()
+                && range.start <= last.end
+            {
+                last.end = last.end.max(range.end);
+            } else {
+                merged.push(range);
+            }
+        }
+
+        if merged.len() == 1 && merged[0] == (0..text.len()) {
+            FilterMask::FullyFiltered
+        } else {
+            FilterMask::PartiallyFiltered(merged)
+        }
+    }
+}
+
+#[async_trait]
+impl ContentFilter for RegexContentFilter {
+    async fn filter(&self, _player: &Player, _source: FilterSource, text: &str) -> FilterMask {
+        self.mask(text)
+    }
+}
+
+/// Player written text together with the parts that should be filtered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilteredText {
+    pub raw: String,
+    pub mask: FilterMask,
+}
+
+impl FilteredText {
+    #[must_use]
+    pub const fn passthrough(raw: String) -> Self {
+        Self {
+            raw,
+            mask: FilterMask::PassThrough,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_filtered(&self) -> bool {
+        !matches!(self.mask, FilterMask::PassThrough)
+    }
+
+    /// The text with every filtered character replaced, or `None` if all of it is filtered.
+    #[must_use]
+    pub fn filtered(&self) -> Option<String> {
+        match &self.mask {
+            FilterMask::PassThrough => Some(self.raw.clone()),
+            FilterMask::FullyFiltered => None,
+            FilterMask::PartiallyFiltered(ranges) => Some(
+                self.raw
+                    .char_indices()
+                    .map(|(index, c)| {
+                        if ranges.iter().any(|range| range.contains(&index)) {
+                            FILTER_CHARACTER
+                        } else {
+                            c
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn filtered_or_empty(&self) -> String {
+        self.filtered().unwrap_or_default()
+    }
+
+    /// The mask as sent in chat packets, where each bit stands for one UTF-16 code unit.
+    #[must_use]
+    pub fn filter_type(&self) -> FilterType {
+        match &self.mask {
+            FilterMask::PassThrough => FilterType::PassThrough,
+            FilterMask::FullyFiltered => FilterType::FullyFiltered,
+            FilterMask::PartiallyFiltered(ranges) => {
+                let mut words = Vec::new();
+                let mut unit = 0;
+                for (index, c) in self.raw.char_indices() {
+                    let units = c.len_utf16();
+                    if ranges.iter().any(|range| range.contains(&index)) {
+                        for bit in unit..unit + units {
+                            if words.len() <= bit / 64 {
+                                words.resize(bit / 64 + 1, 0i64);
+                            }
+                            words[bit / 64] |= 1 << (bit % 64);
+                        }
+                    }
+                    unit += units;
+                }
+                FilterType::PartiallyFiltered(BitSet(words.into_boxed_slice()))
+            }
+        }
+    }
+}
+
+impl Server {
+    /// Replaces the filter used for player written text.
+    pub async fn set_content_filter(&self, filter: Arc<dyn ContentFilter>) {
+        *self.content_filter.write().await = filter;
+    }
+
+    /// Runs `text` through the content filter, unless filtering is disabled for `source`.
+    pub async fn filter_text(
+        &self,
+        player: &Player,
+        source: FilterSource,
+        text: String,
+    ) -> FilteredText {
+        if !source.is_enabled(&advanced_config().content_filter) {
+            return FilteredText::passthrough(text);
+        }
+        let filter = self.content_filter.read().await.clone();
+        let mask = filter.filter(player, source, &text).await;
+        FilteredText { raw: text, mask }
+    }
+}
+
+impl Player {
+    /// Whether this player sees filtered text instead of what other players wrote.
+    pub async fn is_text_filtering_enabled(&self) -> bool {
+        advanced_config().content_filter.filter_for_everyone
+            || self.config.read().await.text_filtering
+    }
+
+    /// Whether text written by this player has to be filtered before `recipient` sees it.
+    ///
+    /// Players always see their own text unfiltered.
+    pub async fn should_filter_text_to(&self, recipient: &Self) -> bool {
+        self.gameprofile.id != recipient.gameprofile.id
+            && (self.is_text_filtering_enabled().await
+                || recipient.is_text_filtering_enabled().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_protocol::java::client::play::FilterType;
+
+    use super::{FilterMask, FilteredText, RegexContentFilter};
+
+    #[test]
+    fn masks_every_match() {
+        let filter = RegexContentFilter::new(&["bad".to_string(), "[".to_string()]);
+        let text = "Bad words are bad".to_string();
+        let mask = filter.mask(&text);
+        assert_eq!(mask, FilterMask::PartiallyFiltered(vec![0..3, 14..17]));
+
+        let filtered = FilteredText { raw: text, mask };
+        assert_eq!(filtered.filtered().as_deref(), Some("### words are ###"));
+        assert_eq!(filter.mask("BAD"), FilterMask::FullyFiltered);
+        assert_eq!(filter.mask("good"), FilterMask::PassThrough);
+    }
+
+    #[test]
+    fn partial_mask_counts_utf16_units() {
+        let filtered = FilteredText {
+            raw: "🎃 x".to_string(),
+            mask: FilterMask::PartiallyFiltered(vec![5..6]),
+        };
+        let FilterType::PartiallyFiltered(mask) = filtered.filter_type() else {
+            panic!("expected a partial mask");
+        };
+        // The pumpkin takes two UTF-16 code units and the space one
+        assert_eq!(*mask.0, [0b1000]);
+        assert_eq!(filtered.filtered().as_deref(), Some("🎃 #"));
+    }
+}
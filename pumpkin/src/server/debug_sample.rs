@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use pumpkin_protocol::{codec::var_int::VarInt, java::client::play::CDebugSample};
+use pumpkin_util::PermissionLvl;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+use crate::server::Server;
+
+/// Clients renew their subscription while they show the graph, so it runs out this long after
+/// they close it.
+const SUBSCRIPTION_DURATION: Duration = Duration::from_secs(10);
+
+/// The debug graphs a client can subscribe to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DebugSampleType {
+    /// The tick time graph of the F3 screen, see [`TickTimeSample`].
+    TickTime,
+}
+
+impl DebugSampleType {
+    #[must_use]
+    pub const fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::TickTime),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        match self {
+            Self::TickTime => 0,
+        }
+    }
+}
+
+/// How long the parts of one tick took, in nanoseconds.
+pub struct TickTimeSample {
+    /// Everything from the end of the previous tick to the end of this one.
+    pub full_tick: i64,
+    pub server_tick: i64,
+    pub scheduled_tasks: i64,
+    /// Waiting for the tick to be due.
+    pub idle: i64,
+}
+
+impl TickTimeSample {
+    const fn to_array(&self) -> [i64; 4] {
+        [
+            self.full_tick,
+            self.server_tick,
+            self.scheduled_tasks,
+            self.idle,
+        ]
+    }
+}
+
+/// The players that currently get the tick time graph, and when their subscription runs out.
+#[derive(Default)]
+pub struct DebugSampleSubscriptions {
+    tick_time: Mutex<HashMap<Uuid, Instant>>,
+}
+
+impl DebugSampleSubscriptions {
+    /// Starts or renews the subscription of `player`. Like in vanilla, only players who could
+    /// use the `/tick` command get the samples.
+    pub async fn subscribe(&self, player: &Player, sample_type: DebugSampleType) {
+        if player.permission_lvl.load() < PermissionLvl::Two {
+            return;
+        }
+        match sample_type {
+            DebugSampleType::TickTime => {
+                self.tick_time.lock().await.insert(
+                    player.gameprofile.id,
+                    Instant::now() + SUBSCRIPTION_DURATION,
+                );
+            }
+        }
+    }
+
+    /// Sends `sample` to everyone subscribed to the tick time graph.
+    pub async fn send_tick_time(&self, server: &Server, sample: &TickTimeSample) {
+        let subscribers = {
+            let mut subscribers = self.tick_time.lock().await;
+            if subscribers.is_empty() {
+                return;
+            }
+            let now = Instant::now();
+            subscribers.retain(|_, expiry| *expiry > now);
+            subscribers.keys().copied().collect::<Vec<_>>()
+        };
+
+        let sample = sample.to_array();
+        let packet = CDebugSample::new(&sample, VarInt(DebugSampleType::TickTime.id()));
+        for uuid in subscribers {
+            if let Some(player) = server.get_player_by_uuid(uuid).await {
+                player.client.enqueue_packet(&packet).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_types_round_trip() {
+        let sample_type = DebugSampleType::TickTime;
+        assert_eq!(
+            DebugSampleType::from_id(sample_type.id()),
+            Some(sample_type)
+        );
+        assert_eq!(DebugSampleType::from_id(1), None);
+    }
+}
@@ -4,26 +4,36 @@ use crate::command::commands::defaultgamemode::DefaultGamemode;
 use crate::data::player_server_data::ServerPlayerData;
 use crate::entity::{EntityBase, NBTStorage};
 use crate::item::registry::ItemRegistry;
+use crate::net::plugin_channel::PluginChannels;
 use crate::net::{ClientPlatform, DisconnectReason, EncryptionError, GameProfile, PlayerConfig};
 use crate::plugin::player::player_login::PlayerLoginEvent;
 use crate::plugin::server::server_broadcast::ServerBroadcastEvent;
+use crate::server::debug_sample::DebugSampleSubscriptions;
 use crate::server::tick_rate_manager::ServerTickRateManager;
 use crate::world::custom_bossbar::CustomBossbars;
 use crate::{command::dispatcher::CommandDispatcher, entity::player::Player, world::World};
 use connection_cache::{CachedBranding, CachedStatus};
+use content_filter::{ContentFilter, RegexContentFilter};
 use key_store::KeyStore;
-use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_config::{BASIC_CONFIG, GameRuleSetting, WorldConfig, WorldDimension, advanced_config};
 
 use crate::command::CommandSender;
+use pumpkin_data::game_rules::{GameRule, GameRuleValue};
 use pumpkin_macros::send_cancellable;
+use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::login::CEncryptionRequest;
-use pumpkin_protocol::java::client::play::CChangeDifficulty;
+use pumpkin_protocol::java::client::play::{
+    CChangeDifficulty, CPlayerInfoUpdate, PlayerAction, PlayerInfoFlags,
+};
 use pumpkin_protocol::{ClientPacket, java::client::config::CPluginMessage};
+use pumpkin_registry::biome::load_datapack_biomes;
 use pumpkin_registry::{Registry, VanillaDimensionType};
 use pumpkin_util::Difficulty;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::dimension::Dimension;
+use pumpkin_world::generation::Seed;
+use pumpkin_world::level::Level;
 use pumpkin_world::lock::LevelLocker;
 use pumpkin_world::lock::anvil::AnvilLevelLocker;
 use pumpkin_world::world_info::anvil::{
@@ -35,6 +45,7 @@ use rsa::RsaPublicKey;
 use std::collections::HashSet;
 use std::fs;
 use std::net::IpAddr;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32};
 use std::{future::Future, sync::atomic::Ordering, time::Duration};
@@ -43,6 +54,8 @@ use tokio::task::JoinHandle;
 use tokio_util::task::TaskTracker;
 
 mod connection_cache;
+pub mod content_filter;
+pub mod debug_sample;
 mod key_store;
 pub mod seasonal_events;
 pub mod tick_rate_manager;
@@ -52,6 +65,9 @@ use super::command::args::entities::{
     EntityFilter, EntityFilterSort, EntitySelectorType, TargetSelector, ValueCondition,
 };
 
+/// How often the tab list latency of every player is sent, in ticks.
+const LATENCY_UPDATE_INTERVAL: i32 = 600;
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -93,8 +109,14 @@ pub struct Server {
     pub aggregated_tick_times_nanos: AtomicI64,
     /// Total number of ticks processed by the server
     pub tick_count: AtomicI32,
+    /// Players that show the tick time graph of their debug screen
+    pub debug_sample_subscriptions: DebugSampleSubscriptions,
     /// Random unique Server ID used by Bedrock Edition
     pub server_guid: u64,
+    /// Filters player written text, replaceable by plugins
+    content_filter: RwLock<Arc<dyn ContentFilter>>,
+    /// Custom payload channels plugins listen on
+    pub plugin_channels: PluginChannels,
     tasks: TaskTracker,
 
     // world stuff which maybe should be put into a struct
@@ -103,6 +125,8 @@ pub struct Server {
     // Gets unlocked when dropped
     // TODO: Make this a trait
     _locker: Arc<AnvilLevelLocker>,
+    /// The locks of the additional worlds from the config
+    _additional_lockers: Vec<AnvilLevelLocker>,
 }
 
 impl Server {
@@ -116,44 +140,48 @@ impl Server {
 
         let block_registry = super::block::registry::default_registry();
 
-        let level_info = AnvilLevelInfo.read_world_info(&world_path);
-        if let Err(error) = &level_info {
-            match error {
-                // If it doesn't exist, just make a new one
-                WorldInfoError::InfoNotFound => (),
-                WorldInfoError::UnsupportedDataVersion(_version)
-                | WorldInfoError::UnsupportedLevelVersion(_version) => {
-                    log::error!("Failed to load world info!");
-                    log::error!("{error}");
-                    panic!("Unsupported world version! See the logs for more info.");
-                }
-                e => {
-                    panic!("World Error {e}");
-                }
-            }
-        } else {
-            let dat_path = world_path.join(LEVEL_DAT_FILE_NAME);
-            if dat_path.exists() {
-                let backup_path = world_path.join(LEVEL_DAT_BACKUP_FILE_NAME);
-                fs::copy(dat_path, backup_path).unwrap();
-            }
-        }
+        let level_info = read_level_info(&world_path);
         // if we fail to lock, lets crash ???. maybe not the best solution when we have a large server with many worlds and one is locked.
         // So TODO
         let locker = AnvilLevelLocker::lock(&world_path).expect("Failed to lock level");
 
         let world_name = world_path.to_str().unwrap();
 
-        let level_info = level_info.unwrap_or_else(|err| {
-            log::warn!("Failed to get level_info, using default instead: {err}");
+        let level_info = level_info.unwrap_or_else(|| {
+            log::warn!("Failed to get level_info, using default instead");
             LevelData::default()
         });
 
         let seed = level_info.world_gen_settings.seed;
         let level_info = Arc::new(RwLock::new(level_info));
 
+        let (additional_worlds, additional_lockers): (Vec<_>, Vec<_>) = advanced_config()
+            .worlds
+            .additional
+            .iter()
+            .filter(|config| {
+                let shares_folder = config.get_world_path() == world_path;
+                if shares_folder {
+                    log::error!(
+                        "Not loading the world {}, it has the same folder as the default world",
+                        config.name
+                    );
+                }
+                !shares_folder
+            })
+            .map(|config| {
+                let path = config.get_world_path();
+                let level_info =
+                    read_level_info(&path).unwrap_or_else(|| new_additional_level_info(config));
+                let locker = AnvilLevelLocker::lock(&path).expect("Failed to lock level");
+                ((config, apply_world_config(level_info, config)), locker)
+            })
+            .unzip();
+
         let server = Self {
-            cached_registry: Registry::get_synced(),
+            cached_registry: Registry::get_synced_with_biomes(&load_datapack_biomes(
+                &world_path.join("datapacks"),
+            )),
             container_id: 0.into(),
             worlds: RwLock::new(vec![]),
             dimensions: vec![
@@ -181,18 +209,25 @@ impl Server {
             tick_times_nanos: Mutex::new([0; 100]),
             aggregated_tick_times_nanos: AtomicI64::new(0),
             tick_count: AtomicI32::new(0),
+            debug_sample_subscriptions: DebugSampleSubscriptions::default(),
             tasks: TaskTracker::new(),
             server_guid: rand::random(),
+            content_filter: RwLock::new(Arc::new(RegexContentFilter::new(
+                &advanced_config().content_filter.patterns,
+            ))),
+            plugin_channels: PluginChannels::default(),
             mojang_public_keys: Mutex::new(Vec::new()),
             world_info_writer: Arc::new(AnvilLevelInfo),
             level_info: level_info.clone(),
             _locker: Arc::new(locker),
+            _additional_lockers: additional_lockers,
         };
 
         let server = Arc::new(server);
         let weak = Arc::downgrade(&server);
         log::info!("Loading Overworld: {seed}");
         let overworld = World::load(
+            VanillaDimensionType::Overworld.resource_location().path,
             Dimension::Overworld.into_level(world_path.clone(), block_registry.clone(), seed),
             level_info.clone(),
             VanillaDimensionType::Overworld,
@@ -201,6 +236,7 @@ impl Server {
         );
         log::info!("Loading Nether: {seed}");
         let nether = World::load(
+            VanillaDimensionType::TheNether.resource_location().path,
             Dimension::Nether.into_level(world_path.clone(), block_registry.clone(), seed),
             level_info.clone(),
             VanillaDimensionType::TheNether,
@@ -209,17 +245,47 @@ impl Server {
         );
         log::info!("Loading End: {seed}");
         let end = World::load(
+            VanillaDimensionType::TheEnd.resource_location().path,
             Dimension::End.into_level(world_path.clone(), block_registry.clone(), seed),
             level_info,
             VanillaDimensionType::TheEnd,
-            block_registry,
-            weak,
+            block_registry.clone(),
+            weak.clone(),
         );
+        let mut loaded_worlds: Vec<Arc<World>> = vec![overworld.into(), nether.into(), end.into()];
+
+        for (config, level_info) in additional_worlds {
+            let seed = level_info.world_gen_settings.seed;
+            log::info!("Loading {}: {seed}", config.name);
+            let level = Level::from_root_folder(
+                config.get_world_path(),
+                block_registry.clone(),
+                seed,
+                match config.generator {
+                    WorldDimension::Overworld => Dimension::Overworld,
+                    WorldDimension::Nether => Dimension::Nether,
+                    WorldDimension::End => Dimension::End,
+                },
+            );
+            let world = World::load(
+                config.name.clone(),
+                level,
+                Arc::new(RwLock::new(level_info)),
+                match config.dimension_type() {
+                    WorldDimension::Overworld => VanillaDimensionType::Overworld,
+                    WorldDimension::Nether => VanillaDimensionType::TheNether,
+                    WorldDimension::End => VanillaDimensionType::TheEnd,
+                },
+                block_registry.clone(),
+                weak.clone(),
+            );
+            loaded_worlds.push(world.into());
+        }
+
         *server
             .worlds
             .try_write()
-            .expect("Nothing should hold a lock of worlds before server startup") =
-            vec![overworld.into(), nether.into(), end.into()];
+            .expect("Nothing should hold a lock of worlds before server startup") = loaded_worlds;
         server
     }
 
@@ -233,6 +299,16 @@ impl Server {
         self.tasks.spawn(task)
     }
 
+    /// Returns the world with the given name, see [`World::name`].
+    pub async fn get_world(&self, name: &str) -> Option<Arc<World>> {
+        self.worlds
+            .read()
+            .await
+            .iter()
+            .find(|world| world.name == name)
+            .cloned()
+    }
+
     pub async fn get_world_from_dimension(&self, dimension: VanillaDimensionType) -> Arc<World> {
         // TODO: this is really bad
         let world_guard = self.worlds.read().await;
@@ -281,7 +357,13 @@ impl Server {
         let gamemode = self.defaultgamemode.lock().await.gamemode;
 
         let (world, nbt) = if let Ok(Some(data)) = self.player_data_storage.load_data(&profile.id) {
-            if let Some(dimension_key) = data.get_string("Dimension") {
+            let world = match data.get_string("pumpkin:World") {
+                Some(name) => self.get_world(name).await,
+                None => None,
+            };
+            if let Some(world) = world {
+                (world, Some(data))
+            } else if let Some(dimension_key) = data.get_string("Dimension") {
                 if let Some(dimension) =
                     VanillaDimensionType::from_resource_location_string(dimension_key)
                 {
@@ -389,6 +471,19 @@ impl Server {
         {
             log::error!("Failed to save level.dat: {err}");
         }
+        drop(level_data);
+        for config in &advanced_config().worlds.additional {
+            let Some(world) = self.get_world(&config.name).await else {
+                continue;
+            };
+            let level_data = world.level_info.read().await;
+            if let Err(err) = self
+                .world_info_writer
+                .write_world_info(&level_data, &config.get_world_path())
+            {
+                log::error!("Failed to save level.dat of {}: {err}", config.name);
+            }
+        }
         log::info!("Completed worlds");
     }
 
@@ -456,7 +551,10 @@ impl Server {
             // todo: update mob spawn options
 
             for world in &*self.worlds.read().await {
-                world.level_info.write().await.difficulty = level_info.difficulty;
+                // The default worlds share the server's level info, which is already locked
+                if !Arc::ptr_eq(&world.level_info, &self.level_info) {
+                    world.level_info.write().await.difficulty = level_info.difficulty;
+                }
             }
 
             self.broadcast_packet_all(&CChangeDifficulty::new(
@@ -604,12 +702,50 @@ impl Server {
     /// Main server tick method. This now handles both player/network ticking (which always runs)
     /// and world/game logic ticking (which is affected by freeze state).
     pub async fn tick(self: &Arc<Self>) {
+        // Everything a player is sent during the tick goes out with a single flush at its end
+        let players = self.get_all_players().await;
+        for player in &players {
+            player.client.hold_flushes();
+        }
+
         if self.tick_rate_manager.runs_normally() || self.tick_rate_manager.is_sprinting() {
             self.tick_worlds().await;
             // Always run player and network ticking, even when game is frozen
         } else {
             self.tick_players_and_network().await;
         }
+
+        if self.tick_count.load(Ordering::Relaxed) % LATENCY_UPDATE_INTERVAL == 0 {
+            self.broadcast_player_latency().await;
+        }
+
+        for player in &players {
+            player.client.release_flushes();
+        }
+    }
+
+    /// Sends the ping of every player to everyone for the tab list.
+    async fn broadcast_player_latency(&self) {
+        let players = self.get_all_players().await;
+        let actions = players
+            .iter()
+            .map(|player| [PlayerAction::UpdateLatency(VarInt(player.ping() as i32))])
+            .collect::<Vec<_>>();
+        let entries = players
+            .iter()
+            .zip(&actions)
+            .map(
+                |(player, actions)| pumpkin_protocol::java::client::play::Player {
+                    uuid: player.gameprofile.id,
+                    actions,
+                },
+            )
+            .collect::<Vec<_>>();
+        self.broadcast_packet_all(&CPlayerInfoUpdate::new(
+            PlayerInfoFlags::UPDATE_LATENCY.bits(),
+            &entries,
+        ))
+        .await;
     }
 
     /// Ticks essential server functions that must run even when the game is frozen.
@@ -830,3 +966,67 @@ impl Server {
         }
     }
 }
+
+/// Reads the `level.dat` in `world_path`, backing it up first. `None` if the world has none yet.
+fn read_level_info(world_path: &Path) -> Option<LevelData> {
+    match AnvilLevelInfo.read_world_info(world_path) {
+        Ok(level_info) => {
+            let dat_path = world_path.join(LEVEL_DAT_FILE_NAME);
+            if dat_path.exists() {
+                let backup_path = world_path.join(LEVEL_DAT_BACKUP_FILE_NAME);
+                fs::copy(dat_path, backup_path).unwrap();
+            }
+            Some(level_info)
+        }
+        // If it doesn't exist, just make a new one
+        Err(WorldInfoError::InfoNotFound) => None,
+        Err(
+            error @ (WorldInfoError::UnsupportedDataVersion(_)
+            | WorldInfoError::UnsupportedLevelVersion(_)),
+        ) => {
+            log::error!("Failed to load world info!");
+            log::error!("{error}");
+            panic!("Unsupported world version! See the logs for more info.");
+        }
+        Err(e) => {
+            panic!("World Error {e}");
+        }
+    }
+}
+
+fn new_additional_level_info(config: &WorldConfig) -> LevelData {
+    let mut level_info = LevelData::default();
+    level_info.level_name.clone_from(&config.name);
+    level_info.world_gen_settings.seed = Seed::from(config.seed.as_str()).0 as i64;
+    level_info
+}
+
+/// Applies the difficulty and game rules of an additional world's config.
+fn apply_world_config(mut level_info: LevelData, config: &WorldConfig) -> LevelData {
+    if let Some(difficulty) = config.difficulty {
+        level_info.difficulty = if BASIC_CONFIG.hardcore {
+            Difficulty::Hard
+        } else {
+            difficulty
+        };
+    }
+
+    for (name, setting) in &config.gamerules {
+        let Some(rule) = GameRule::all()
+            .iter()
+            .find(|rule| rule.to_string() == *name)
+        else {
+            log::warn!("Unknown game rule {name} for the world {}", config.name);
+            continue;
+        };
+        match (level_info.game_rules.get_mut(rule), setting) {
+            (GameRuleValue::Bool(value), GameRuleSetting::Bool(setting)) => *value = *setting,
+            (GameRuleValue::Int(value), GameRuleSetting::Int(setting)) => *value = *setting,
+            _ => log::warn!(
+                "The game rule {name} for the world {} can't be set to {setting}",
+                config.name
+            ),
+        }
+    }
+    level_info
+}
@@ -1,12 +1,25 @@
-use crate::{SHOULD_STOP, server::Server};
+use crate::{
+    SHOULD_STOP,
+    server::{Server, debug_sample::TickTimeSample},
+};
 use std::{
     sync::{Arc, atomic::Ordering},
     time::{Duration, Instant},
 };
-use tokio::time::sleep;
+use tokio::time::sleep_until;
+
+/// When the server falls this far behind, it stops trying to catch up on the missed ticks.
+const OVERLOAD_THRESHOLD: Duration = Duration::from_secs(2);
+/// The server warns about falling behind at most this often.
+const OVERLOAD_WARNING_INTERVAL: Duration = Duration::from_secs(15);
 
 pub struct Ticker {
-    last_tick: Instant,
+    /// When the next tick is due. Ticks that take too long move it into the past, so the
+    /// following ticks run without waiting until the server has caught up.
+    next_tick: Instant,
+    /// When the previous tick finished, the time until the next one starts is spent idle.
+    last_tick_end: Instant,
+    last_overload_warning: Option<Instant>,
 }
 
 impl Default for Ticker {
@@ -19,14 +32,21 @@ impl Ticker {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            last_tick: Instant::now(),
+            next_tick: Instant::now(),
+            last_tick_end: Instant::now(),
+            last_overload_warning: None,
         }
     }
 
     /// IMPORTANT: Run this in a new thread/tokio task.
     pub async fn run(&mut self, server: &Arc<Server>) {
+        self.next_tick = Instant::now();
+        self.last_tick_end = self.next_tick;
         while !SHOULD_STOP.load(Ordering::Relaxed) {
             let tick_start_time = Instant::now();
+            let idle_nanos = tick_start_time
+                .saturating_duration_since(self.last_tick_end)
+                .as_nanos() as i64;
             let manager = &server.tick_rate_manager;
 
             manager.tick();
@@ -51,25 +71,57 @@ impl Ticker {
             // Record the total time this tick took
             let tick_duration_nanos = tick_start_time.elapsed().as_nanos() as i64;
             server.update_tick_times(tick_duration_nanos).await;
+            server
+                .debug_sample_subscriptions
+                .send_tick_time(
+                    server,
+                    &TickTimeSample {
+                        full_tick: idle_nanos + tick_duration_nanos,
+                        server_tick: tick_duration_nanos,
+                        // Scheduled tasks run as part of the server tick
+                        scheduled_tasks: 0,
+                        idle: idle_nanos,
+                    },
+                )
+                .await;
 
-            // Sleep logic remains the same
             let now = Instant::now();
-            let elapsed = now.duration_since(self.last_tick);
-
-            let tick_interval = if manager.is_sprinting() {
-                Duration::ZERO
-            } else {
-                Duration::from_nanos(manager.nanoseconds_per_tick() as u64)
-            };
+            self.last_tick_end = now;
+            if manager.is_sprinting() {
+                // Sprints run as fast as possible and don't count as falling behind
+                self.next_tick = now;
+                continue;
+            }
 
-            if let Some(sleep_time) = tick_interval.checked_sub(elapsed)
-                && !sleep_time.is_zero()
-            {
-                sleep(sleep_time).await;
+            let tick_interval = Duration::from_nanos(manager.nanoseconds_per_tick() as u64);
+            self.next_tick += tick_interval;
+            let behind = now.saturating_duration_since(self.next_tick);
+            if behind > OVERLOAD_THRESHOLD {
+                self.warn_overloaded(now, behind, tick_interval);
+                // Skip the missed ticks instead of running them all at once
+                self.next_tick = now;
+            } else if !behind.is_zero() {
+                // Catch up by running the next tick right away
+                continue;
             }
 
-            self.last_tick = Instant::now();
+            sleep_until(self.next_tick.into()).await;
         }
         log::debug!("Ticker stopped");
     }
+
+    fn warn_overloaded(&mut self, now: Instant, behind: Duration, tick_interval: Duration) {
+        if self
+            .last_overload_warning
+            .is_some_and(|last| now.duration_since(last) < OVERLOAD_WARNING_INTERVAL)
+        {
+            return;
+        }
+        self.last_overload_warning = Some(now);
+        log::warn!(
+            "Can't keep up! Is the server overloaded? Running {}ms or {} ticks behind",
+            behind.as_millis(),
+            behind.as_nanos() / tick_interval.as_nanos().max(1)
+        );
+    }
 }
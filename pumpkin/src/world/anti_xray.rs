@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_config::{AntiXrayEngineMode, AntiXraySettings};
+use pumpkin_data::{Block, BlockState};
+use pumpkin_registry::VanillaDimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::BlockStateId;
+use pumpkin_world::chunk::ChunkSections;
+use rand::{Rng, rng};
+
+use super::World;
+
+/// Hides blocks nobody can see from the chunks sent to players, see
+/// [`pumpkin_config::AntiXrayConfig`].
+pub struct AntiXray {
+    mode: AntiXrayEngineMode,
+    /// Whether the state with this id is hidden
+    hidden: Box<[bool]>,
+    /// Whether the state with this id is sent as a fake ore, only used with
+    /// [`AntiXrayEngineMode::FakeOres`]
+    replaced: Box<[bool]>,
+    /// The states fake ores are picked from
+    fake_ores: Box<[BlockStateId]>,
+    max_block_height: i32,
+    update_radius: i32,
+    dimension_type: VanillaDimensionType,
+}
+
+impl AntiXray {
+    /// The engine for a world of `dimension_type` with `settings`, `None` if it is disabled.
+    #[must_use]
+    pub fn new(settings: &AntiXraySettings, dimension_type: VanillaDimensionType) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let blocks = |names: &[String]| -> Vec<&'static Block> {
+            names
+                .iter()
+                .filter_map(|name| {
+                    let block = Block::from_name(name);
+                    if block.is_none() {
+                        log::warn!("Unknown anti-xray block {name}");
+                    }
+                    block
+                })
+                .collect()
+        };
+        let hidden_blocks = blocks(&settings.hidden_blocks);
+        let mut replaced_blocks = blocks(&settings.replacement_blocks);
+        replaced_blocks.extend(&hidden_blocks);
+
+        Some(Self {
+            mode: settings.engine_mode,
+            hidden: state_set(&hidden_blocks),
+            replaced: state_set(&replaced_blocks),
+            fake_ores: hidden_blocks
+                .iter()
+                .map(|block| block.default_state.id)
+                .collect(),
+            max_block_height: settings.max_block_height,
+            update_radius: i32::from(settings.update_radius),
+            dimension_type,
+        })
+    }
+
+    /// Whether the block with `state_id` is sent as something else when nobody can see it.
+    fn is_obfuscated(&self, state_id: BlockStateId) -> bool {
+        let states = match self.mode {
+            AntiXrayEngineMode::HideOres => &self.hidden,
+            AntiXrayEngineMode::FakeOres => &self.replaced,
+        };
+        states.get(state_id as usize).copied().unwrap_or(false)
+    }
+
+    /// What a hidden block at height `y` is sent as.
+    fn obfuscated_state(&self, y: i32) -> BlockStateId {
+        match self.mode {
+            AntiXrayEngineMode::HideOres => match self.dimension_type {
+                VanillaDimensionType::TheNether => Block::NETHERRACK.default_state.id,
+                VanillaDimensionType::TheEnd => Block::END_STONE.default_state.id,
+                _ if y < 0 => Block::DEEPSLATE.default_state.id,
+                _ => Block::STONE.default_state.id,
+            },
+            AntiXrayEngineMode::FakeOres => {
+                if self.fake_ores.is_empty() {
+                    Block::STONE.default_state.id
+                } else {
+                    self.fake_ores[rng().random_range(0..self.fake_ores.len())]
+                }
+            }
+        }
+    }
+
+    /// The blocks of a chunk as they are sent to players, `None` if nothing has to be hidden.
+    /// Blocks next to other chunks count as hidden unless something in their own chunk exposes
+    /// them.
+    #[must_use]
+    pub fn obfuscate(&self, sections: &ChunkSections) -> Option<ChunkSections> {
+        let mut obfuscated: Option<ChunkSections> = None;
+        for (i, section) in sections.sections.iter().enumerate() {
+            let base_y = sections.min_y + i as i32 * 16;
+            if base_y >= self.max_block_height
+                || !section
+                    .block_states
+                    .any(|state_id| self.is_obfuscated(state_id))
+            {
+                continue;
+            }
+            for y in 0..16 {
+                let absolute_y = base_y + y as i32;
+                if absolute_y >= self.max_block_height {
+                    break;
+                }
+                for z in 0..16 {
+                    for x in 0..16 {
+                        let state_id = section.block_states.get(x, y, z);
+                        if !self.is_obfuscated(state_id) || is_exposed(sections, x, absolute_y, z) {
+                            continue;
+                        }
+                        obfuscated
+                            .get_or_insert_with(|| sections.clone())
+                            .set_block_absolute_y(
+                                x,
+                                absolute_y,
+                                z,
+                                self.obfuscated_state(absolute_y),
+                            );
+                    }
+                }
+            }
+        }
+        obfuscated
+    }
+
+    /// Adds the real blocks around the changed blocks in `changes` that the changes could have
+    /// exposed, so players see what is actually there.
+    ///
+    /// The areas around the changes are merged first, so every block is only looked at once and
+    /// every chunk only read once. Chunks that aren't loaded are skipped, as nobody can see them.
+    pub async fn reveal_around(&self, world: &World, changes: &mut HashMap<BlockPos, u16>) {
+        let radius = self.update_radius;
+        let mut by_chunk: HashMap<Vector2<i32>, HashSet<BlockPos>> = HashMap::new();
+        for (center, _) in changes.iter().filter(|(_, state_id)| !occludes(**state_id)) {
+            for x in -radius..=radius {
+                for y in -radius..=radius {
+                    for z in -radius..=radius {
+                        let position = center.offset(Vector3::new(x, y, z));
+                        if position.0.y >= self.max_block_height || changes.contains_key(&position)
+                        {
+                            continue;
+                        }
+                        let (chunk_position, _) = position.chunk_and_chunk_relative_position();
+                        by_chunk.entry(chunk_position).or_default().insert(position);
+                    }
+                }
+            }
+        }
+
+        for (chunk_position, positions) in by_chunk {
+            let Some(chunk) = world.level.try_get_chunk(&chunk_position) else {
+                continue;
+            };
+            let chunk = chunk.read().await;
+            for position in positions {
+                let (_, relative) = position.chunk_and_chunk_relative_position();
+                if let Some(state_id) = chunk.section.get_block_absolute_y(
+                    relative.x as usize,
+                    relative.y,
+                    relative.z as usize,
+                ) && self.is_obfuscated(state_id)
+                {
+                    changes.insert(position, state_id);
+                }
+            }
+        }
+    }
+}
+
+/// A lookup table for the states of `blocks`, indexed by state id.
+fn state_set(blocks: &[&'static Block]) -> Box<[bool]> {
+    let size = blocks
+        .iter()
+        .flat_map(|block| block.states)
+        .map(|state| state.id as usize + 1)
+        .max()
+        .unwrap_or(0);
+    let mut set = vec![false; size];
+    for state in blocks.iter().flat_map(|block| block.states) {
+        set[state.id as usize] = true;
+    }
+    set.into_boxed_slice()
+}
+
+/// Whether nothing can be seen through the block with `state_id`.
+fn occludes(state_id: BlockStateId) -> bool {
+    BlockState::from_id(state_id).opacity == 15
+}
+
+/// Whether any neighbour of the block at `x`, `y`, `z` in `sections` lets it be seen.
+fn is_exposed(sections: &ChunkSections, x: usize, y: i32, z: usize) -> bool {
+    let neighbours = [
+        (x.checked_sub(1), y, Some(z)),
+        (Some(x + 1).filter(|x| *x < 16), y, Some(z)),
+        (Some(x), y - 1, Some(z)),
+        (Some(x), y + 1, Some(z)),
+        (Some(x), y, z.checked_sub(1)),
+        (Some(x), y, Some(z + 1).filter(|z| *z < 16)),
+    ];
+    neighbours.into_iter().any(|(x, y, z)| {
+        let (Some(x), Some(z)) = (x, z) else {
+            return false;
+        };
+        sections
+            .get_block_absolute_y(x, y, z)
+            .is_some_and(|state_id| !occludes(state_id))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_world::chunk::SubChunk;
+
+    use super::*;
+
+    const MIN_Y: i32 = -64;
+
+    fn stone_sections() -> ChunkSections {
+        let mut sections =
+            ChunkSections::new(vec![SubChunk::default(); 24].into_boxed_slice(), MIN_Y);
+        for y in 0..16 {
+            for z in 0..16 {
+                for x in 0..16 {
+                    sections.set_block_absolute_y(x, y, z, Block::STONE.default_state.id);
+                }
+            }
+        }
+        sections
+    }
+
+    fn engine() -> AntiXray {
+        let settings = AntiXraySettings {
+            enabled: true,
+            ..Default::default()
+        };
+        AntiXray::new(&settings, VanillaDimensionType::Overworld).unwrap()
+    }
+
+    #[test]
+    fn hides_only_enclosed_ores() {
+        let ore = Block::DIAMOND_ORE.default_state.id;
+        let mut sections = stone_sections();
+        sections.set_block_absolute_y(8, 8, 8, ore);
+        sections.set_block_absolute_y(4, 15, 4, ore);
+
+        let obfuscated = engine().obfuscate(&sections).unwrap();
+        assert_eq!(
+            obfuscated.get_block_absolute_y(8, 8, 8),
+            Some(Block::STONE.default_state.id)
+        );
+        // The air above exposes it
+        assert_eq!(obfuscated.get_block_absolute_y(4, 15, 4), Some(ore));
+    }
+
+    #[test]
+    fn leaves_chunks_without_hidden_blocks_alone() {
+        assert!(engine().obfuscate(&stone_sections()).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use pumpkin_config::advanced_config;
+use pumpkin_data::Block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::chunk::ChunkData;
+use pumpkin_world::level::SyncChunk;
+use rand::{Rng, rng};
+
+use super::World;
+use crate::block::RandomTickArgs;
+
+/// Every tick each chunk section gets `randomTickSpeed` random ticks, spread over its blocks.
+const SECTION_VOLUME: f64 = 4096.0;
+
+impl World {
+    /// Makes up for the time `chunk` spent unloaded if enabled in the config. Blocks that catch
+    /// up get as many random ticks as they would have gotten on average and block entities
+    /// simulate the missed ticks. Only the first call after the chunk was read from disk does
+    /// anything.
+    pub async fn catch_up_chunk(self: &Arc<Self>, chunk: &SyncChunk) {
+        let config = &advanced_config().catch_up;
+        if !config.enabled {
+            return;
+        }
+
+        let world_age = self.level_time.lock().await.world_age;
+        let mut chunk = chunk.write().await;
+        let last_update = std::mem::take(&mut chunk.last_update);
+        let chunk = chunk.downgrade();
+        if last_update == 0 {
+            return;
+        }
+        let ticks = (world_age - last_update).clamp(0, i64::from(config.max_ticks)) as u32;
+        if ticks == 0 {
+            return;
+        }
+
+        let positions = self.catch_up_positions(&chunk);
+        let block_entities: Vec<_> = chunk.block_entities.values().cloned().collect();
+        drop(chunk);
+
+        self.catch_up_random_ticks(&positions, ticks, config.max_random_ticks)
+            .await;
+        for block_entity in block_entities {
+            block_entity.catch_up(self.clone(), ticks).await;
+        }
+    }
+
+    /// The positions of all blocks in `chunk` that catch up on random ticks.
+    fn catch_up_positions(&self, chunk: &ChunkData) -> Vec<BlockPos> {
+        let catches_up = |state_id: u16| {
+            self.block_registry
+                .get_pumpkin_block(Block::from_state_id(state_id))
+                .is_some_and(|block| block.catches_up_random_ticks())
+        };
+
+        let mut positions = Vec::new();
+        for (i, section) in chunk.section.sections.iter().enumerate() {
+            if !section.block_states.any(catches_up) {
+                continue;
+            }
+            let base_y = chunk.section.min_y + i as i32 * 16;
+            for y in 0..16 {
+                for z in 0..16 {
+                    for x in 0..16 {
+                        if catches_up(section.block_states.get(x, y, z)) {
+                            positions.push(BlockPos::new(
+                                chunk.position.x * 16 + x as i32,
+                                base_y + y as i32,
+                                chunk.position.y * 16 + z as i32,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        positions
+    }
+
+    async fn catch_up_random_ticks(
+        self: &Arc<Self>,
+        positions: &[BlockPos],
+        ticks: u32,
+        max_random_ticks: u32,
+    ) {
+        if positions.is_empty() {
+            return;
+        }
+
+        let speed = self
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .random_tick_speed
+            .max(0);
+        let expected = f64::from(ticks) * speed as f64 / SECTION_VOLUME;
+        let rounds = expected.min(f64::from(max_random_ticks) / positions.len() as f64);
+
+        // The fraction of a round left over is given to blocks at random, so short absences still
+        // add up over time
+        let full_rounds = rounds.floor() as u32;
+        let last_round_chance = rounds.fract();
+        for round in 0..=full_rounds {
+            for position in positions {
+                if round == full_rounds && !rng().random_bool(last_round_chance) {
+                    continue;
+                }
+                let block = self.get_block(position).await;
+                if let Some(pumpkin_block) = self.block_registry.get_pumpkin_block(block)
+                    && pumpkin_block.catches_up_random_ticks()
+                {
+                    pumpkin_block
+                        .random_tick(RandomTickArgs {
+                            world: self,
+                            block,
+                            position,
+                        })
+                        .await;
+                }
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use pumpkin_config::BASIC_CONFIG;
 use pumpkin_protocol::java::client::play::{CCenterChunk, CUnloadChunk};
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
 
+use super::debug;
 use crate::{entity::player::Player, net::ClientPlatform};
 
 pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
@@ -25,6 +26,7 @@ pub async fn update_position(player: &Arc<Player>) {
     let new_cylindrical = Cylindrical::new(new_chunk_center, view_distance);
 
     if old_cylindrical != new_cylindrical {
+        debug::send_chunk_border_markers(player, new_chunk_center).await;
         if let ClientPlatform::Java(java) = &player.client {
             java.send_packet_now(&CCenterChunk {
                 chunk_x: new_chunk_center.x.into(),
@@ -57,7 +59,7 @@ pub async fn update_position(player: &Arc<Player>) {
         player.watched_section.store(new_cylindrical);
 
         if !chunks_to_clean.is_empty() {
-            level.clean_chunks(&chunks_to_clean).await;
+            entity.world.clean_chunks(&chunks_to_clean).await;
             for chunk in unloading_chunks {
                 player
                     .client
@@ -0,0 +1,127 @@
+//! Debug shapes sent to clients while developing the server, see [`DebugConfig`].
+//!
+//! [`DebugConfig`]: pumpkin_config::DebugConfig
+
+use std::time::Duration;
+
+use pumpkin_config::advanced_config;
+use pumpkin_protocol::{java::client::play::CCustomPayload, ser::NetworkWriteExt};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use pumpkin_world::poi::PoiRecord;
+
+use super::World;
+use crate::entity::player::Player;
+
+const ADD_MARKER_CHANNEL: &str = "minecraft:debug/game_test_add_marker";
+const CLEAR_MARKERS_CHANNEL: &str = "minecraft:debug/game_test_clear";
+const POI_ADDED_CHANNEL: &str = "minecraft:debug/poi_added";
+const POI_REMOVED_CHANNEL: &str = "minecraft:debug/poi_removed";
+
+const PATH_COLOR: u32 = 0x8000_FF00;
+const PATH_MARKER_DURATION: Duration = Duration::from_secs(1);
+const CHUNK_BORDER_COLOR: u32 = 0x80FF_0000;
+const CHUNK_BORDER_MARKER_DURATION: Duration = Duration::from_secs(5);
+
+/// A translucent box around a block with a label, drawn by the client until it expires.
+pub struct DebugMarker {
+    pub position: BlockPos,
+    /// ARGB, the alpha controls how see-through the box is
+    pub color: u32,
+    pub text: String,
+    pub duration: Duration,
+}
+
+impl DebugMarker {
+    fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.write_i64_be(self.position.as_long()).unwrap();
+        data.write_u32_be(self.color).unwrap();
+        data.write_string(&self.text).unwrap();
+        data.write_i32_be(self.duration.as_millis().try_into().unwrap_or(i32::MAX))
+            .unwrap();
+        data
+    }
+}
+
+fn is_enabled() -> bool {
+    advanced_config().debug.enabled
+}
+
+impl World {
+    /// Shows `marker` to every player in the world if debug shapes are enabled.
+    pub async fn send_debug_marker(&self, marker: &DebugMarker) {
+        if !is_enabled() {
+            return;
+        }
+        self.broadcast_packet_all(&CCustomPayload::new(ADD_MARKER_CHANNEL, &marker.encode()))
+            .await;
+    }
+
+    /// Removes every debug marker the players in the world are seeing.
+    pub async fn clear_debug_markers(&self) {
+        if !is_enabled() {
+            return;
+        }
+        self.broadcast_packet_all(&CCustomPayload::new(CLEAR_MARKERS_CHANNEL, &[]))
+            .await;
+    }
+
+    /// Marks the next step of a mob walking along its path.
+    pub(crate) async fn send_debug_path_step(&self, position: BlockPos) {
+        if !advanced_config().debug.paths {
+            return;
+        }
+        self.send_debug_marker(&DebugMarker {
+            position,
+            color: PATH_COLOR,
+            text: String::new(),
+            duration: PATH_MARKER_DURATION,
+        })
+        .await;
+    }
+
+    /// Tells players about a point of interest that was added at `position`, or removed if
+    /// `record` is `None`.
+    pub(crate) async fn send_debug_poi(&self, position: BlockPos, record: Option<&PoiRecord>) {
+        if !is_enabled() || !advanced_config().debug.poi {
+            return;
+        }
+        let mut data = Vec::new();
+        data.write_i64_be(position.as_long()).unwrap();
+        let channel = if let Some(record) = record {
+            data.write_string(&format!("minecraft:{}", record.poi_type.name()))
+                .unwrap();
+            data.write_i32_be(i32::from(record.free_tickets)).unwrap();
+            POI_ADDED_CHANNEL
+        } else {
+            POI_REMOVED_CHANNEL
+        };
+        self.broadcast_packet_all(&CCustomPayload::new(channel, &data))
+            .await;
+    }
+}
+
+/// Marks the corners of `chunk` at the height of `player` if enabled.
+pub(crate) async fn send_chunk_border_markers(player: &Player, chunk: Vector2<i32>) {
+    if !is_enabled() || !advanced_config().debug.chunk_borders {
+        return;
+    }
+    let y = player.living_entity.entity.block_pos.load().0.y;
+    let (start_x, start_z) = (chunk.x * 16, chunk.y * 16);
+    for (x, z) in [
+        (start_x, start_z),
+        (start_x + 15, start_z),
+        (start_x, start_z + 15),
+        (start_x + 15, start_z + 15),
+    ] {
+        let marker = DebugMarker {
+            position: BlockPos::new(x, y, z),
+            color: CHUNK_BORDER_COLOR,
+            text: format!("{}, {}", chunk.x, chunk.y),
+            duration: CHUNK_BORDER_MARKER_DURATION,
+        };
+        player
+            .send_custom_payload(ADD_MARKER_CHANNEL, &marker.encode())
+            .await;
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+use pumpkin_config::advanced_config;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{BlockStateId, world::BlockFlags};
+use tokio::sync::{Mutex, watch};
+
+use super::World;
+use super::region_lock::RegionLocks;
+
+/// Coordinates of the chunk section a block is in, used to group changes by section.
+type SectionKey = (i32, i32, i32);
+
+const fn section_key(position: &BlockPos) -> SectionKey {
+    (position.0.x >> 4, position.0.y >> 4, position.0.z >> 4)
+}
+
+/// A batch of block changes which is applied over as many ticks as needed.
+///
+/// Changes are queued with [`Self::set_block`] and applied once the session is submitted with
+/// [`World::submit_edit`]. At most `max_world_edit_changes` blocks change per game tick, shared by
+/// all sessions of a world in submission order. Changes are applied one chunk section at a time,
+/// so clients receive them as a few multi block updates instead of one packet per block.
+///
+/// The replaced states are recorded, so a finished (or partially applied) session can be undone.
+pub struct WorldEditSession {
+    flags: BlockFlags,
+    update_neighbors: bool,
+    pending: BTreeMap<SectionKey, VecDeque<(BlockPos, BlockStateId)>>,
+    pending_count: usize,
+    /// Replaced states, in the order they were replaced.
+    history: Vec<(BlockPos, BlockStateId)>,
+}
+
+impl Default for WorldEditSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorldEditSession {
+    /// A session that places blocks like `/fill` does: block callbacks run, the shapes of
+    /// neighbours are updated and neighbours get updated after each batch. Observers don't notice
+    /// the shape updates, so a large edit doesn't set off contraptions block by block.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            flags: BlockFlags::SKIP_OBSERVER_UPDATE,
+            update_neighbors: true,
+            pending: BTreeMap::new(),
+            pending_count: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// A session that only changes the states, without block callbacks or neighbour updates.
+    #[must_use]
+    pub fn strict() -> Self {
+        Self {
+            flags: BlockFlags::CLIENT_ONLY,
+            update_neighbors: false,
+            ..Self::new()
+        }
+    }
+
+    /// Queues a block change. Later changes to the same position win.
+    pub fn set_block(&mut self, position: BlockPos, state_id: BlockStateId) {
+        self.pending
+            .entry(section_key(&position))
+            .or_default()
+            .push_back((position, state_id));
+        self.pending_count += 1;
+    }
+
+    /// Queues filling the cuboid between `from` and `to` (inclusive) with `state_id`.
+    pub fn fill(&mut self, from: BlockPos, to: BlockPos, state_id: BlockStateId) {
+        for position in BlockPos::iterate(from, to) {
+            self.set_block(position, state_id);
+        }
+    }
+
+    /// The number of queued changes that were not applied yet.
+    #[must_use]
+    pub const fn pending_count(&self) -> usize {
+        self.pending_count
+    }
+
+    /// A session restoring every state this session replaced so far, newest first.
+    ///
+    /// Block entity data of replaced blocks is not restored.
+    #[must_use]
+    pub fn undo(&self) -> Self {
+        let mut undo = Self {
+            flags: self.flags,
+            update_neighbors: self.update_neighbors,
+            ..Self::new()
+        };
+        for (position, state_id) in self.history.iter().rev() {
+            undo.set_block(*position, *state_id);
+        }
+        undo
+    }
+
+    /// Applies up to `budget` queued changes, returning how many were applied.
+    async fn apply(&mut self, world: &Arc<World>, budget: usize) -> usize {
+        let mut applied = Vec::new();
+        while applied.len() < budget {
+            let Some(mut section) = self.pending.first_entry() else {
+                break;
+            };
+            let changes = section.get_mut();
+            // Keep entities in this section's region from ticking against a half changed area
+            let Some(&(first, _)) = changes.front() else {
+                section.remove();
+                continue;
+            };
+            let history = &mut self.history;
+            let flags = self.flags;
+            world
+                .region_locks
+                .run_locked([RegionLocks::region_of_block(&first)], async {
+                    while applied.len() < budget
+                        && let Some((position, state_id)) = changes.pop_front()
+                    {
+                        let replaced = world.set_block_state(&position, state_id, flags).await;
+                        if replaced != state_id {
+                            history.push((position, replaced));
+                        }
+                        applied.push(position);
+                    }
+                })
+                .await;
+            if changes.is_empty() {
+                section.remove();
+            }
+        }
+        self.pending_count -= applied.len();
+
+        if self.update_neighbors {
+            for position in &applied {
+                world.update_neighbors(position, None).await;
+            }
+        }
+        applied.len()
+    }
+}
+
+/// A submitted [`WorldEditSession`], used to wait for it or to undo it.
+#[derive(Clone)]
+pub struct EditHandle {
+    session: Arc<Mutex<WorldEditSession>>,
+    finished: watch::Receiver<bool>,
+}
+
+impl EditHandle {
+    /// Waits until every queued change was applied.
+    pub async fn wait(&self) {
+        let mut finished = self.finished.clone();
+        // Only fails if the world was dropped, in which case there is nothing left to wait for
+        let _ = finished.wait_for(|finished| *finished).await;
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        *self.finished.borrow()
+    }
+
+    /// The number of changes that were not applied yet.
+    pub async fn pending_count(&self) -> usize {
+        self.session.lock().await.pending_count()
+    }
+
+    /// A session restoring everything this session changed so far.
+    pub async fn undo(&self) -> WorldEditSession {
+        self.session.lock().await.undo()
+    }
+}
+
+pub(super) struct SubmittedEdit {
+    session: Arc<Mutex<WorldEditSession>>,
+    finished: watch::Sender<bool>,
+}
+
+impl World {
+    /// Queues `session` to be applied during the next ticks.
+    pub async fn submit_edit(&self, session: WorldEditSession) -> EditHandle {
+        let session = Arc::new(Mutex::new(session));
+        let (finished, finished_receiver) = watch::channel(false);
+        self.edit_sessions.lock().await.push_back(SubmittedEdit {
+            session: session.clone(),
+            finished,
+        });
+        EditHandle {
+            session,
+            finished: finished_receiver,
+        }
+    }
+
+    /// Applies submitted edits in order until the per tick budget is used up.
+    pub(super) async fn tick_edit_sessions(self: &Arc<Self>) {
+        let mut budget = advanced_config().tick.max_world_edit_changes;
+        let mut edits = self.edit_sessions.lock().await;
+        while budget > 0
+            && let Some(edit) = edits.front()
+        {
+            let mut session = edit.session.lock().await;
+            budget -= session.apply(self, budget).await;
+            if session.pending_count() > 0 {
+                break;
+            }
+            drop(session);
+            if let Some(edit) = edits.pop_front() {
+                edit.finished.send_replace(true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::Block;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::WorldEditSession;
+    use crate::block::blocks::redstone::simulation::RedstoneSimulation;
+
+    #[tokio::test]
+    async fn applies_and_undoes_fill() {
+        let simulation = RedstoneSimulation::new();
+        let world = &simulation.world;
+        let center = BlockPos::new(1, 0, 1);
+
+        let mut session = WorldEditSession::new();
+        session.fill(
+            BlockPos::new(0, 0, 0),
+            BlockPos::new(2, 0, 2),
+            Block::STONE.default_state.id,
+        );
+        let handle = world.submit_edit(session).await;
+        assert!(!handle.is_finished());
+        assert_eq!(handle.pending_count().await, 9);
+
+        world.tick_edit_sessions().await;
+        assert!(handle.is_finished());
+        assert_eq!(world.get_block(&center).await, &Block::STONE);
+
+        let undo = world.submit_edit(handle.undo().await).await;
+        world.tick_edit_sessions().await;
+        undo.wait().await;
+        assert!(world.get_block_state(&center).await.is_air());
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::{Arc, atomic::Ordering::Relaxed};
+
+use pumpkin_config::advanced_config;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{BlockStateId, world::BlockFlags};
+
+use crate::entity::EntityBase;
+use crate::entity::player::Player;
+use crate::server::Server;
+
+use super::World;
+use super::region_lock::RegionLocks;
+
+/// A change to the world made by an entity while entities tick in parallel.
+pub(super) enum DeferredChange {
+    SetBlockState(BlockPos, BlockStateId, BlockFlags),
+    BreakBlock(BlockPos, Option<Arc<Player>>, BlockFlags),
+    Spawn(Arc<dyn EntityBase>),
+}
+
+tokio::task_local! {
+    /// The changes made by the entities of the region ticked by the current task.
+    static DEFERRED: RefCell<Vec<DeferredChange>>;
+}
+
+/// Queues `change` if the current task ticks an entity region, so it is applied once every region
+/// finished ticking. Otherwise `change` is returned to be applied right away.
+pub(super) fn try_defer(change: DeferredChange) -> Result<(), DeferredChange> {
+    if DEFERRED.try_with(|_| ()).is_err() {
+        return Err(change);
+    }
+    DEFERRED.with(|deferred| deferred.borrow_mut().push(change));
+    Ok(())
+}
+
+impl World {
+    /// Ticks all entities of this world.
+    ///
+    /// Entity updates are either computed serially or, when `parallel_entity_ticking` is enabled,
+    /// concurrently per region. With parallel ticking the blocks entities change or break and the
+    /// entities they spawn are buffered per region and applied once every region finished, region
+    /// by region in ascending order and within a region in the order they were made. Player
+    /// collisions are committed afterwards in ascending entity ID order.
+    pub(super) async fn tick_entities(
+        self: &Arc<Self>,
+        server: &Server,
+        player_positions: &[Vector3<f64>],
+    ) {
+        let mut entities: Vec<_> = self.entities.read().await.values().cloned().collect();
+        entities.sort_unstable_by_key(|entity| entity.get_entity().entity_id);
+
+        let activation_range = &advanced_config().activation_range;
+        for entity in &entities {
+            let base_entity = entity.get_entity();
+            base_entity.age.fetch_add(1, Relaxed);
+            if activation_range.enabled {
+                base_entity.update_activation(player_positions, activation_range);
+            }
+        }
+
+        if advanced_config().tick.parallel_entity_ticking
+            && let Some(server) = self.server.upgrade()
+        {
+            self.tick_entities_parallel(&server, &entities).await;
+        } else {
+            for entity in &entities {
+                entity.tick(entity.clone(), server).await;
+            }
+        }
+
+        self.commit_entity_collisions(&entities).await;
+    }
+
+    async fn tick_entities_parallel(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        entities: &[Arc<dyn EntityBase>],
+    ) {
+        let mut regions: BTreeMap<(i32, i32), Vec<Arc<dyn EntityBase>>> = BTreeMap::new();
+        for entity in entities {
+            let region = RegionLocks::region_of_chunk(entity.get_entity().chunk_pos.load());
+            regions
+                .entry((region.x, region.y))
+                .or_default()
+                .push(entity.clone());
+        }
+
+        // The handles are in region order, so the changes are too
+        let handles: Vec<_> = regions
+            .into_iter()
+            .map(|((x, z), region)| {
+                let server = server.clone();
+                let world = self.clone();
+                tokio::spawn(DEFERRED.scope(RefCell::new(Vec::new()), async move {
+                    world
+                        .region_locks
+                        .run_locked([Vector2::new(x, z)], async {
+                            for entity in region {
+                                entity.tick(entity.clone(), &server).await;
+                            }
+                        })
+                        .await;
+                    DEFERRED.with(RefCell::take)
+                }))
+            })
+            .collect();
+
+        let mut changes = Vec::with_capacity(handles.len());
+        let mut panic = None;
+        for handle in handles {
+            match handle.await {
+                Ok(region_changes) => changes.push(region_changes),
+                Err(err) if err.is_panic() => {
+                    panic.get_or_insert(err.into_panic());
+                }
+                Err(err) => log::error!("Failed to tick entity region: {err}"),
+            }
+        }
+        if let Some(panic) = panic {
+            std::panic::resume_unwind(panic);
+        }
+
+        for change in changes.into_iter().flatten() {
+            match change {
+                DeferredChange::SetBlockState(position, state_id, flags) => {
+                    self.set_block_state(&position, state_id, flags).await;
+                }
+                DeferredChange::BreakBlock(position, cause, flags) => {
+                    self.break_block(&position, cause, flags).await;
+                }
+                DeferredChange::Spawn(entity) => self.add_entity(entity).await,
+            }
+        }
+    }
+
+    async fn commit_entity_collisions(&self, entities: &[Arc<dyn EntityBase>]) {
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        for entity in entities {
+            for player in &players {
+                if player
+                    .living_entity
+                    .entity
+                    .bounding_box
+                    .load()
+                    // This is vanilla, but TODO: change this when is in a vehicle
+                    .expand(1.0, 0.5, 1.0)
+                    .intersects(&entity.get_entity().bounding_box.load())
+                {
+                    entity.on_player_collision(player).await;
+                    break;
+                }
+            }
+        }
+    }
+}
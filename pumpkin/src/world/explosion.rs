@@ -1,16 +1,27 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use pumpkin_data::{Block, BlockState};
-use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_data::{Block, BlockState, damage::DamageType, entity::EntityType};
+use pumpkin_util::{
+    GameMode,
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+};
+use pumpkin_world::item::ItemStack;
 
 use crate::{
-    block::{ExplodeArgs, drop_loot},
+    block::{ExplodeArgs, block_loot, merge_into},
+    entity::{Entity, EntityBase, player::Player},
     world::loot::LootContextParameters,
 };
 
 use super::{BlockFlags, World};
 
+/// Vanilla merges identical explosion drops into stacks of at most this size.
+const MAX_MERGED_DROP_COUNT: u8 = 16;
+
+/// Wind bursts push entities away harder than an explosion of the same size.
+const WIND_BURST_KNOCKBACK: f64 = 1.22;
+
 pub struct Explosion {
     power: f32,
     pos: Vector3<f64>,
@@ -73,14 +84,22 @@ impl Explosion {
         map
     }
 
-    pub async fn explode(&self, world: &Arc<World>) {
+    /// Pushes away entities, destroys the items around and then the blocks.
+    ///
+    /// Players move themselves, so instead of changing their velocity the push for each of them
+    /// is returned, to be sent with the explosion packet.
+    pub async fn explode(&self, world: &Arc<World>) -> Vec<(Arc<Player>, Vector3<f64>)> {
         let blocks = self.get_blocks_to_destroy(world).await;
-        // TODO: Entity damage, fire
+        // Like in vanilla, entities are pushed before any blocks that shield them are destroyed
+        let pushed = self.push_entities(world).await;
+        let mut drops: Vec<(ItemStack, BlockPos)> = Vec::new();
+        // TODO: Damage for other entities than items, fire
         for (pos, (block, state)) in blocks {
             if state.is_air() {
                 continue;
             }
             let pumpkin_block = world.block_registry.get_pumpkin_block(block);
+            let block_entity = world.get_block_entity(&pos).await;
 
             world.set_block_state(&pos, 0, BlockFlags::NOTIFY_ALL).await;
 
@@ -88,9 +107,12 @@ impl Explosion {
                 let params = LootContextParameters {
                     block_state: Some(state),
                     explosion_radius: Some(self.power),
+                    block_entity,
                     ..Default::default()
                 };
-                drop_loot(world, block, &pos, false, params).await;
+                for stack in block_loot(block, params) {
+                    Self::merge_drop(&mut drops, stack, pos);
+                }
             }
             if let Some(pumpkin_block) = pumpkin_block {
                 pumpkin_block
@@ -102,5 +124,266 @@ impl Explosion {
                     .await;
             }
         }
+
+        for (stack, pos) in drops {
+            world.drop_stack(&pos, stack).await;
+        }
+        pushed
+    }
+
+    async fn push_entities(&self, world: &World) -> Vec<(Arc<Player>, Vector3<f64>)> {
+        let range = f64::from(self.power) * 2.0;
+        for entity in world
+            .get_nearby_entities(self.pos, range)
+            .await
+            .into_values()
+        {
+            let base = entity.get_entity();
+            let Some((direction, impact)) = self.impact(world, base).await else {
+                continue;
+            };
+            if base.entity_type == &EntityType::ITEM {
+                // Items that are immune, like nether stars, are still pushed
+                let damage = (impact * impact + impact) / 2.0 * 7.0 * range + 1.0;
+                entity
+                    .damage(entity.clone(), damage as f32, DamageType::EXPLOSION)
+                    .await;
+            }
+            // TODO: Blast protection and the explosion knockback resistance attribute
+            base.add_velocity(direction * impact).await;
+        }
+
+        let mut pushed = Vec::new();
+        for player in world
+            .get_nearby_players(self.pos, range)
+            .await
+            .into_values()
+        {
+            if player.gamemode.load() == GameMode::Spectator
+                || (player.gamemode.load() == GameMode::Creative
+                    && player.abilities.lock().await.flying)
+            {
+                continue;
+            }
+            if let Some((direction, impact)) =
+                self.impact(world, &player.living_entity.entity).await
+            {
+                pushed.push((player, direction * impact));
+            }
+        }
+        pushed
+    }
+
+    /// The direction `entity` is pushed in and how hard it is hit, less the further away and the
+    /// more covered by blocks it is. Entities twice the power away or further are not affected.
+    async fn impact(&self, world: &World, entity: &Entity) -> Option<(Vector3<f64>, f64)> {
+        let feet = entity.pos.load();
+        let distance =
+            feet.squared_distance_to_vec(self.pos).sqrt() / (f64::from(self.power) * 2.0);
+        if distance > 1.0 {
+            return None;
+        }
+        // Primed TNT is pushed from its feet instead of its eyes, like in vanilla
+        let target_y = if entity.entity_type == &EntityType::TNT {
+            feet.y
+        } else {
+            feet.y + f64::from(entity.standing_eye_height)
+        };
+        let direction = Vector3::new(feet.x, target_y, feet.z).sub(&self.pos);
+        if direction.length_squared() == 0.0 {
+            return None;
+        }
+        let exposure = self.exposure(world, &entity.bounding_box.load()).await;
+        Some((direction.normalize(), (1.0 - distance) * exposure))
+    }
+
+    /// The share of points spread over `bounding_box` that have no block between them and the
+    /// explosion.
+    async fn exposure(&self, world: &World, bounding_box: &BoundingBox) -> f64 {
+        let size = bounding_box.max.sub(&bounding_box.min);
+        let step = Vector3::new(
+            1.0 / size.x.mul_add(2.0, 1.0),
+            1.0 / size.y.mul_add(2.0, 1.0),
+            1.0 / size.z.mul_add(2.0, 1.0),
+        );
+        // Centres the grid of points horizontally
+        let offset_x = (1.0 - (1.0 / step.x).floor() * step.x) / 2.0;
+        let offset_z = (1.0 - (1.0 / step.z).floor() * step.z) / 2.0;
+
+        let mut exposed = 0;
+        let mut total = 0;
+        let mut x = 0.0;
+        while x <= 1.0 {
+            let mut y = 0.0;
+            while y <= 1.0 {
+                let mut z = 0.0;
+                while z <= 1.0 {
+                    let point = Vector3::new(
+                        bounding_box.min.x + size.x * x + offset_x,
+                        bounding_box.min.y + size.y * y,
+                        bounding_box.min.z + size.z * z + offset_z,
+                    );
+                    if !world.is_obstructed(point, self.pos).await {
+                        exposed += 1;
+                    }
+                    total += 1;
+                    z += step.z;
+                }
+                y += step.y;
+            }
+            x += step.x;
+        }
+        f64::from(exposed) / f64::from(total.max(1))
+    }
+
+    /// Merges an explosion drop into an identical one (up to 16 items) to spawn fewer item entities.
+    fn merge_drop(drops: &mut Vec<(ItemStack, BlockPos)>, mut stack: ItemStack, pos: BlockPos) {
+        for (existing, _) in drops.iter_mut() {
+            if stack.is_empty() {
+                return;
+            }
+            merge_into(existing, &mut stack, MAX_MERGED_DROP_COUNT);
+        }
+        if !stack.is_empty() {
+            drops.push((stack, pos));
+        }
+    }
+}
+
+/// The burst of a wind charge. It pushes entities away and triggers blocks like doors and
+/// buttons, but never damages or destroys anything.
+pub struct WindBurst {
+    radius: f32,
+    pos: Vector3<f64>,
+}
+
+impl WindBurst {
+    #[must_use]
+    pub fn new(radius: f32, pos: Vector3<f64>) -> Self {
+        Self { radius, pos }
+    }
+
+    /// The push an entity standing at `feet` receives, away from the burst towards `eyes`.
+    /// Entities twice the radius away or further are not affected.
+    fn knockback(&self, feet: Vector3<f64>, eyes: Vector3<f64>) -> Option<Vector3<f64>> {
+        let range = f64::from(self.radius) * 2.0;
+        let distance = feet.squared_distance_to_vec(self.pos).sqrt() / range;
+        if distance > 1.0 {
+            return None;
+        }
+        let direction = eyes.sub(&self.pos);
+        if direction.length_squared() == 0.0 {
+            return None;
+        }
+        Some(direction.normalize() * ((1.0 - distance) * WIND_BURST_KNOCKBACK))
+    }
+
+    /// Pushes away the entities around the burst and resets their fall distance, so riding the
+    /// burst up does not hurt on landing.
+    ///
+    /// Players move themselves, so instead of changing their velocity the push for each of them
+    /// is returned, to be sent along with the burst.
+    pub async fn push_entities(&self, world: &World) -> Vec<(Arc<Player>, Vector3<f64>)> {
+        let range = f64::from(self.radius) * 2.0;
+        for entity in world
+            .get_nearby_entities(self.pos, range)
+            .await
+            .into_values()
+        {
+            let base = entity.get_entity();
+            let feet = base.pos.load();
+            let eyes = feet.add_raw(0.0, f64::from(base.standing_eye_height), 0.0);
+            let Some(knockback) = self.knockback(feet, eyes) else {
+                continue;
+            };
+            base.add_velocity(knockback).await;
+            if let Some(living) = entity.get_living_entity() {
+                living.fall_distance.store(0.0);
+            }
+        }
+
+        let mut pushed = Vec::new();
+        for player in world
+            .get_nearby_players(self.pos, range)
+            .await
+            .into_values()
+        {
+            if player.gamemode.load() == GameMode::Spectator
+                || (player.gamemode.load() == GameMode::Creative
+                    && player.abilities.lock().await.flying)
+            {
+                continue;
+            }
+            let entity = &player.living_entity.entity;
+            let feet = entity.pos.load();
+            let eyes = feet.add_raw(0.0, f64::from(entity.standing_eye_height), 0.0);
+            let Some(knockback) = self.knockback(feet, eyes) else {
+                continue;
+            };
+            player.living_entity.fall_distance.store(0.0);
+            pushed.push((player, knockback));
+        }
+        pushed
+    }
+
+    /// Lets every block within the radius react to the burst.
+    pub async fn trigger_blocks(&self, world: &Arc<World>) {
+        let radius = f64::from(self.radius);
+        let min = BlockPos::floored(
+            self.pos.x - radius,
+            self.pos.y - radius,
+            self.pos.z - radius,
+        );
+        let max = BlockPos::floored(
+            self.pos.x + radius,
+            self.pos.y + radius,
+            self.pos.z + radius,
+        );
+        for x in min.0.x..=max.0.x {
+            for y in min.0.y..=max.0.y {
+                for z in min.0.z..=max.0.z {
+                    let pos = BlockPos::new(x, y, z);
+                    let center = pos.to_centered_f64();
+                    if center.squared_distance_to_vec(self.pos) > radius * radius {
+                        continue;
+                    }
+                    let block = world.get_block(&pos).await;
+                    world.block_registry.on_wind_burst(block, world, &pos).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::WindBurst;
+
+    #[test]
+    fn wind_burst_knockback_falls_off_with_distance() {
+        let burst = WindBurst::new(1.2, Vector3::new(0.0, 0.0, 0.0));
+
+        let close = burst
+            .knockback(Vector3::new(0.5, 0.0, 0.0), Vector3::new(0.5, 0.0, 0.0))
+            .unwrap();
+        let far = burst
+            .knockback(Vector3::new(2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0))
+            .unwrap();
+        assert!(close.x > far.x && far.x > 0.0);
+        assert!(close.y.abs() < 1.0E-9 && close.z.abs() < 1.0E-9);
+
+        // Entities above the burst are launched upwards, which is what wind charge jumps use
+        let above = burst
+            .knockback(Vector3::new(0.0, 0.1, 0.0), Vector3::new(0.0, 1.72, 0.0))
+            .unwrap();
+        assert!(above.y > 1.0);
+
+        assert!(
+            burst
+                .knockback(Vector3::new(2.5, 0.0, 0.0), Vector3::new(2.5, 0.0, 0.0))
+                .is_none()
+        );
     }
 }
@@ -0,0 +1,27 @@
+use pumpkin_data::game_event::GameEvent;
+use pumpkin_util::math::position::BlockPos;
+
+use super::World;
+
+/// How far away most game events can be noticed.
+const DEFAULT_NOTIFICATION_RADIUS: f64 = 16.0;
+/// How far away a jukebox can be heard starting to play.
+const JUKEBOX_NOTIFICATION_RADIUS: f64 = 10.0;
+
+impl World {
+    /// Lets the mobs within range of `position` react to `event` happening there.
+    pub async fn emit_game_event(&self, event: &GameEvent, position: &BlockPos) {
+        let radius = match event {
+            GameEvent::JukeboxPlay | GameEvent::JukeboxStopPlay => JUKEBOX_NOTIFICATION_RADIUS,
+            _ => DEFAULT_NOTIFICATION_RADIUS,
+        };
+        let listeners = self
+            .get_nearby_entities(position.to_centered_f64(), radius)
+            .await;
+        for listener in listeners.into_values() {
+            if let Some(mob) = listener.as_mob() {
+                mob.on_game_event(event, position).await;
+            }
+        }
+    }
+}
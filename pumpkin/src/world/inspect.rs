@@ -0,0 +1,191 @@
+//! `pumpkin inspect`: looks into region files and NBT files such as player data without starting
+//! a server, to find out what is wrong with a chunk or a player that fails to load.
+
+use std::{
+    fs,
+    io::{self, Cursor, Write},
+    path::Path,
+};
+
+use bytes::Bytes;
+use pumpkin_nbt::{
+    Nbt, compound::NbtCompound, deserializer::NbtReadHelper, nbt_compress::read_gzip_compound_tag,
+    path::NbtPath,
+};
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::chunk::format::anvil::{REGION_SIZE, RegionEntry, inspect_region};
+use time::OffsetDateTime;
+
+/// The name of the subcommand on the command line.
+pub const SUBCOMMAND: &str = "inspect";
+const USAGE: &str = "Usage:
+  pumpkin inspect region <r.X.Z.mca>                    List the chunks of a region file
+  pumpkin inspect chunk <r.X.Z.mca> <x> <z> [nbt path]  Print the NBT of the chunk at x, z
+  pumpkin inspect nbt <file.dat> [nbt path]             Print an NBT file, like player data";
+
+/// Runs the subcommand with the arguments following it, logging what went wrong on failure.
+pub fn run(args: impl IntoIterator<Item = String>) -> bool {
+    let args: Vec<String> = args.into_iter().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut out = io::stdout().lock();
+    let result = match args.as_slice() {
+        ["region", file] => list_region(&mut out, Path::new(file)),
+        ["chunk", file, x, z, path @ ..] if path.len() <= 1 => {
+            print_chunk(&mut out, Path::new(file), x, z, path.first().copied())
+        }
+        ["nbt", file, path @ ..] if path.len() <= 1 => {
+            print_nbt_file(&mut out, Path::new(file), path.first().copied())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(error) = result {
+        log::error!("{error}");
+        return false;
+    }
+    true
+}
+
+fn list_region(out: &mut impl Write, file: &Path) -> Result<(), String> {
+    let entries = read_region(file)?;
+    let origin = region_origin(file);
+    if origin.is_none() {
+        log::warn!(
+            "The file is not named r.X.Z.mca, so chunk positions are relative to the region"
+        );
+    }
+
+    let mut damaged = 0;
+    for entry in &entries {
+        let position = origin.map_or(entry.local_position, |origin| origin + entry.local_position);
+        let saved = OffsetDateTime::from_unix_timestamp(i64::from(entry.timestamp))
+            .map_or_else(|_| entry.timestamp.to_string(), |time| time.to_string());
+        let status = match &entry.data {
+            Ok(data) => match read_nbt(data.clone()) {
+                Ok(_) => format!("{} bytes of NBT", data.len()),
+                Err(error) => {
+                    damaged += 1;
+                    format!("DAMAGED: {error}")
+                }
+            },
+            Err(error) => {
+                damaged += 1;
+                format!("DAMAGED: {error}")
+            }
+        };
+        writeln!(
+            out,
+            "chunk {} {}: sectors {}..{}, saved {saved}, {status}",
+            position.x,
+            position.y,
+            entry.sector_offset,
+            entry.sector_offset + entry.sector_count
+        )
+        .map_err(|error| error.to_string())?;
+    }
+    writeln!(out, "{} chunks, {damaged} damaged", entries.len()).map_err(|error| error.to_string())
+}
+
+fn print_chunk(
+    out: &mut impl Write,
+    file: &Path,
+    x: &str,
+    z: &str,
+    path: Option<&str>,
+) -> Result<(), String> {
+    let parse = |coordinate: &str| {
+        coordinate
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid chunk coordinate {coordinate}"))
+    };
+    let position = Vector2::new(parse(x)?, parse(z)?);
+    let region_mask = REGION_SIZE as i32 - 1;
+    let local_position = Vector2::new(position.x & region_mask, position.y & region_mask);
+    if let Some(origin) = region_origin(file)
+        && origin + local_position != position
+    {
+        return Err(format!(
+            "Chunk {} {} is not in this region, which starts at chunk {} {}",
+            position.x, position.y, origin.x, origin.y
+        ));
+    }
+
+    let entry = read_region(file)?
+        .into_iter()
+        .find(|entry| entry.local_position == local_position)
+        .ok_or_else(|| format!("The region has no chunk {} {}", position.x, position.y))?;
+    let data = entry.data.map_err(|error| error.to_string())?;
+    print_nbt(out, &read_nbt(data)?, path)
+}
+
+fn print_nbt_file(out: &mut impl Write, file: &Path, path: Option<&str>) -> Result<(), String> {
+    let bytes =
+        fs::read(file).map_err(|error| format!("Failed to read {}: {error}", file.display()))?;
+    // Player data and level.dat are compressed, other files may not be
+    let nbt = if bytes.starts_with(&[0x1F, 0x8B]) {
+        read_gzip_compound_tag(Cursor::new(bytes)).map_err(|error| error.to_string())?
+    } else {
+        read_nbt(Bytes::from(bytes))?
+    };
+    print_nbt(out, &nbt, path)
+}
+
+fn print_nbt(out: &mut impl Write, nbt: &NbtCompound, path: Option<&str>) -> Result<(), String> {
+    let result = if let Some(path) = path {
+        let path = path.parse::<NbtPath>().map_err(|error| error.to_string())?;
+        let tag = path
+            .get(nbt)
+            .ok_or_else(|| format!("There is no tag at {path}"))?;
+        writeln!(out, "{tag}")
+    } else {
+        writeln!(out, "{nbt}")
+    };
+    result.map_err(|error| error.to_string())
+}
+
+fn read_region(file: &Path) -> Result<Vec<RegionEntry>, String> {
+    let bytes =
+        fs::read(file).map_err(|error| format!("Failed to read {}: {error}", file.display()))?;
+    inspect_region(Bytes::from(bytes))
+        .map_err(|error| format!("{} is not a region file: {error}", file.display()))
+}
+
+fn read_nbt(bytes: Bytes) -> Result<NbtCompound, String> {
+    Nbt::read(&mut NbtReadHelper::new(Cursor::new(bytes)))
+        .map(|nbt| nbt.root_tag)
+        .map_err(|error| error.to_string())
+}
+
+/// The first chunk of the region, taken from a file name like `r.-1.2.mca`.
+fn region_origin(file: &Path) -> Option<Vector2<i32>> {
+    let name = file.file_name()?.to_str()?;
+    let mut parts = name.strip_prefix("r.")?.strip_suffix(".mca")?.split('.');
+    let x: i32 = parts.next()?.parse().ok()?;
+    let z: i32 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(Vector2::new(x, z) * REGION_SIZE as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::region_origin;
+
+    #[test]
+    fn region_origin_from_name() {
+        assert_eq!(
+            region_origin(Path::new("world/region/r.-1.2.mca")),
+            Some(Vector2::new(-32, 64))
+        );
+        assert_eq!(
+            region_origin(Path::new("r.0.0.mca")),
+            Some(Vector2::new(0, 0))
+        );
+        assert_eq!(region_origin(Path::new("r.0.mca")), None);
+        assert_eq!(region_origin(Path::new("chunks.mca")), None);
+    }
+}
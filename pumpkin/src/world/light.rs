@@ -0,0 +1,90 @@
+use std::f64::consts::TAU;
+
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::chunk::LightType;
+use rand::{Rng, rng};
+
+use super::World;
+
+/// The brightness above which animals spawn, see [`World::is_bright_enough_to_spawn`].
+const ANIMAL_SPAWN_BRIGHTNESS: u8 = 8;
+
+impl World {
+    /// The light level at `position`, `None` if its chunk isn't lit.
+    pub async fn get_light(&self, light_type: LightType, position: &BlockPos) -> Option<u8> {
+        self.level.get_light(light_type, position).await
+    }
+
+    /// How much darker the sky light is than in daylight because of the time of day and the
+    /// weather, from 0 to 11.
+    pub async fn sky_darken(&self) -> u8 {
+        let sky_angle = self.level_time.lock().await.sky_angle();
+        let (rain, thunder) = {
+            let weather = self.weather.lock().await;
+            (weather.rain_level, weather.thunder_level)
+        };
+        let rain = 1.0 - f64::from(rain) * 5.0 / 16.0;
+        let thunder = 1.0 - f64::from(thunder) * 5.0 / 16.0;
+        let daylight = 2.0f64.mul_add((sky_angle * TAU).cos().clamp(-0.25, 0.25), 0.5);
+        ((1.0 - daylight * rain * thunder) * 11.0) as u8
+    }
+
+    /// The block light or the sky light darkened by `sky_darken`, whichever is brighter. `None`
+    /// if the chunk isn't lit.
+    pub async fn get_raw_brightness(&self, position: &BlockPos, sky_darken: u8) -> Option<u8> {
+        let sky = self.get_light(LightType::Sky, position).await?;
+        let block = self.get_light(LightType::Block, position).await?;
+        Some(block.max(sky.saturating_sub(sky_darken)))
+    }
+
+    /// Whether it is dark enough at `position` for monsters to spawn, like vanilla's
+    /// `Monster.isDarkEnoughToSpawn`. The light level a monster needs is random, so this may
+    /// change from call to call. Unlit chunks count as dark.
+    pub async fn is_dark_enough_to_spawn(&self, position: &BlockPos) -> bool {
+        let (Some(sky), Some(block)) = (
+            self.get_light(LightType::Sky, position).await,
+            self.get_light(LightType::Block, position).await,
+        ) else {
+            return true;
+        };
+        if sky > rng().random_range(0..32) {
+            return false;
+        }
+
+        let dimension = self.dimension_type.dimension_data();
+        let block_light_limit = dimension.monster_spawn_block_light_limit();
+        if block_light_limit < 15 && i32::from(block) > block_light_limit {
+            return false;
+        }
+        let sky_darken = if self.weather.lock().await.thundering {
+            10
+        } else {
+            self.sky_darken().await
+        };
+        let brightness = block.max(sky.saturating_sub(sky_darken));
+        i32::from(brightness) <= rng().random_range(dimension.monster_spawn_light_level())
+    }
+
+    /// Whether it is bright enough at `position` for animals to spawn, like vanilla's
+    /// `Animal.isBrightEnoughToSpawn`. Unlit chunks count as bright.
+    pub async fn is_bright_enough_to_spawn(&self, position: &BlockPos) -> bool {
+        self.get_raw_brightness(position, 0)
+            .await
+            .is_none_or(|brightness| brightness > ANIMAL_SPAWN_BRIGHTNESS)
+    }
+
+    /// Whether monsters can spawn at `position` as far as light goes, for the brightest light
+    /// level they may pick. `None` if the chunk isn't lit.
+    pub async fn can_monsters_spawn_in_light(&self, position: &BlockPos) -> Option<bool> {
+        let block = self.get_light(LightType::Block, position).await?;
+        let dimension = self.dimension_type.dimension_data();
+        let block_light_limit = dimension.monster_spawn_block_light_limit();
+        if block_light_limit < 15 && i32::from(block) > block_light_limit {
+            return Some(false);
+        }
+        let brightness = self
+            .get_raw_brightness(position, self.sky_darken().await)
+            .await?;
+        Some(i32::from(brightness) <= *dimension.monster_spawn_light_level().end())
+    }
+}
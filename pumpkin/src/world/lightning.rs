@@ -0,0 +1,141 @@
+use std::sync::{Arc, atomic::Ordering};
+
+use pumpkin_data::{Block, entity::EntityType};
+use pumpkin_util::math::{
+    boundingbox::BoundingBox, position::BlockPos, vector2::Vector2, vector3::Vector3,
+};
+use pumpkin_world::{
+    chunk::ChunkHeightmapType::{MotionBlocking, WorldSurface},
+    poi::PoiType,
+};
+use rand::{Rng, rng, seq::IndexedRandom};
+use uuid::Uuid;
+
+use super::World;
+use crate::entity::{
+    Entity, EntityBase, lightning::LightningEntity, mob::skeleton_horse::SkeletonHorse,
+};
+
+/// How far away a lightning rod may attract a bolt.
+const LIGHTNING_ROD_RANGE: i32 = 128;
+/// Chance of a bolt spawning a skeleton horse trap instead of striking.
+// TODO: Scale with the local difficulty
+const SKELETON_TRAP_CHANCE: f64 = 0.0675;
+
+impl World {
+    /// Has a chance of striking a random column of the chunk with lightning during thunderstorms.
+    pub async fn tick_thunder(self: &Arc<Self>, chunk_pos: &Vector2<i32>) {
+        {
+            let weather = self.weather.lock().await;
+            if !weather.raining || !weather.thundering {
+                return;
+            }
+        }
+        if rng().random_range(0..100_000) != 0 {
+            return;
+        }
+
+        let rand_value = rng().random::<i32>() >> 2;
+        let x = (chunk_pos.x << 4) + (rand_value & 15);
+        let z = (chunk_pos.y << 4) + (rand_value >> 8 & 15);
+        let pos = self.get_lightning_pos(x, z).await;
+        // TODO: Only strike where it rains (not in deserts or snowy biomes)
+
+        let do_mob_spawning = self.level_info.read().await.game_rules.do_mob_spawning;
+        let trap_roll = rng().random::<f64>();
+        let skeleton_trap = do_mob_spawning
+            && trap_roll < SKELETON_TRAP_CHANCE
+            && self.get_block(&pos.down()).await != &Block::LIGHTNING_ROD;
+        if skeleton_trap {
+            let horse = SkeletonHorse::make(Entity::new(
+                Uuid::new_v4(),
+                self.clone(),
+                pos.to_f64(),
+                &EntityType::SKELETON_HORSE,
+                false,
+            ));
+            horse.trap.store(true, Ordering::Relaxed);
+            self.spawn_entity(horse).await;
+        }
+        self.spawn_lightning(pos, skeleton_trap).await;
+    }
+
+    /// Spawns a lightning bolt at the bottom center of `pos`. Cosmetic bolts neither start fires
+    /// nor hurt anything.
+    pub async fn spawn_lightning(self: &Arc<Self>, pos: BlockPos, cosmetic: bool) {
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            self.clone(),
+            Vector3::new(
+                f64::from(pos.0.x) + 0.5,
+                f64::from(pos.0.y),
+                f64::from(pos.0.z) + 0.5,
+            ),
+            &EntityType::LIGHTNING_BOLT,
+            false,
+        );
+        self.spawn_entity(Arc::new(LightningEntity::new(entity, cosmetic)))
+            .await;
+    }
+
+    /// Where a bolt aimed at the column `x`, `z` ends up. Lightning rods nearby take precedence,
+    /// then living entities out in the open, then the top of the column.
+    async fn get_lightning_pos(&self, x: i32, z: i32) -> BlockPos {
+        let surface = BlockPos::new(x, self.get_height(MotionBlocking, x, z).await + 1, z);
+        if let Some(rod) = self.find_lightning_rod(&surface).await {
+            return rod.up();
+        }
+
+        let area = BoundingBox::new(
+            Vector3::new(
+                f64::from(x) - 3.0,
+                f64::from(surface.0.y) - 3.0,
+                f64::from(z) - 3.0,
+            ),
+            Vector3::new(
+                f64::from(x) + 4.0,
+                f64::from(self.min_y + self.height) + 4.0,
+                f64::from(z) + 4.0,
+            ),
+        );
+        let mut exposed = Vec::new();
+        for entity in self.get_entities_at_box(&area).await {
+            if entity.get_living_entity().is_none() || !entity.get_entity().is_alive() {
+                continue;
+            }
+            let block_pos = entity.get_entity().block_pos.load();
+            let height = self
+                .get_height(MotionBlocking, block_pos.0.x, block_pos.0.z)
+                .await;
+            if block_pos.0.y > height {
+                exposed.push(block_pos);
+            }
+        }
+        if let Some(target) = exposed.choose(&mut rng()) {
+            return *target;
+        }
+
+        if surface.0.y == self.min_y {
+            // Empty column, strike a bit above the void
+            return surface.up().up();
+        }
+        surface
+    }
+
+    /// The closest lightning rod within range which sits on top of its column.
+    async fn find_lightning_rod(&self, pos: &BlockPos) -> Option<BlockPos> {
+        let mut rods = self
+            .get_loaded_pois_in_range(pos, LIGHTNING_ROD_RANGE, |record| {
+                record.poi_type == PoiType::LightningRod
+            })
+            .await;
+        rods.sort_unstable_by_key(|record| record.pos.squared_distance(*pos));
+        for rod in rods {
+            let rod = rod.pos;
+            if rod.0.y == self.get_height(WorldSurface, rod.0.x, rod.0.z).await {
+                return Some(rod);
+            }
+        }
+        None
+    }
+}
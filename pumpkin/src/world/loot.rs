@@ -1,19 +1,79 @@
-use pumpkin_data::{Block, BlockState, item::Item};
+use pumpkin_data::tag::Taggable;
+use pumpkin_data::{Block, BlockState, Enchantment, item::Item};
 use pumpkin_util::{
     loot_table::{
-        LootCondition, LootFunctionNumberProvider, LootFunctionTypes, LootPoolEntry,
-        LootPoolEntryTypes, LootTable,
+        LootCondition, LootFunctionBonusParameter, LootFunctionNumberProvider, LootFunctionTypes,
+        LootPoolEntry, LootPoolEntryTypes, LootTable,
     },
     random::{RandomGenerator, xoroshiro128::Xoroshiro},
 };
+use pumpkin_world::block::entities::BlockEntity;
 use pumpkin_world::item::ItemStack;
 use rand::Rng;
+use std::sync::Arc;
 
 #[derive(Default)]
 pub struct LootContextParameters {
     pub explosion_radius: Option<f32>,
     pub block_state: Option<&'static BlockState>,
     pub killed_by_player: Option<bool>,
+    /// The tool used to break the block, if any.
+    pub tool: Option<ItemStack>,
+    /// The block entity of the broken block, if it had one.
+    pub block_entity: Option<Arc<dyn BlockEntity>>,
+}
+
+impl LootContextParameters {
+    /// Whether the tool is enchanted with silk touch.
+    #[must_use]
+    pub fn has_silk_touch(&self) -> bool {
+        self.tool
+            .as_ref()
+            .is_some_and(|tool| tool.get_enchantment_level(&Enchantment::SILK_TOUCH) > 0)
+    }
+
+    /// The level of `enchantment` on the tool, 0 without a tool.
+    #[must_use]
+    pub fn enchantment_level(&self, enchantment: &str) -> i32 {
+        self.tool.as_ref().map_or(0, |tool| {
+            Enchantment::from_name(enchantment)
+                .map_or(0, |enchantment| tool.get_enchantment_level(enchantment))
+        })
+    }
+}
+
+/// Raises `count` by the bonus `formula` for the given enchantment `level`, like fortune does
+/// for ores.
+fn apply_bonus(
+    formula: &str,
+    parameters: Option<&LootFunctionBonusParameter>,
+    count: u8,
+    level: i32,
+) -> u8 {
+    let mut rng = rand::rng();
+    let count = i32::from(count);
+    let bonus = match (formula, parameters) {
+        ("minecraft:ore_drops", _) if level > 0 => {
+            // Multiplies the drops by up to `level + 1`
+            let multiplier = (rng.random_range(0..level + 2) - 1).max(0) + 1;
+            count * multiplier
+        }
+        (
+            "minecraft:uniform_bonus_count",
+            Some(LootFunctionBonusParameter::Multiplier { bonus_multiplier }),
+        ) => count + rng.random_range(0..=bonus_multiplier * level),
+        (
+            "minecraft:binomial_with_bonus_count",
+            Some(LootFunctionBonusParameter::Probability { extra, probability }),
+        ) => {
+            count
+                + (0..level + extra)
+                    .filter(|_| rng.random::<f32>() < *probability)
+                    .count() as i32
+        }
+        _ => count,
+    };
+    bonus.clamp(0, i32::from(u8::MAX)) as u8
 }
 
 pub trait LootTableExt {
@@ -102,24 +162,53 @@ impl LootPoolEntryExt for LootPoolEntry {
                             }
                         }
                     }
+                    LootFunctionTypes::CopyComponents { source, include } => {
+                        // Only copying from the block entity is supported for now
+                        if *source == "block_entity"
+                            && let Some(block_entity) = &params.block_entity
+                        {
+                            let components = block_entity.components();
+                            for stack in &mut stacks {
+                                for component in components.iter().filter(|component| {
+                                    include.contains(&component.get_self_enum().to_name())
+                                }) {
+                                    stack.set_data_component(component.clone());
+                                }
+                            }
+                        }
+                    }
                     LootFunctionTypes::ApplyBonus {
-                        enchantment: _,
-                        formula: _,
-                        parameters: _,
+                        enchantment,
+                        formula,
+                        parameters,
+                    } => {
+                        let level = params.enchantment_level(enchantment);
+                        for stack in &mut stacks {
+                            stack.item_count =
+                                apply_bonus(formula, parameters.as_ref(), stack.item_count, level);
+                        }
                     }
-                    | LootFunctionTypes::CopyComponents {
-                        source: _,
-                        include: _,
+                    LootFunctionTypes::ExplosionDecay => {
+                        // Every item only survives the explosion with a chance
+                        if let Some(radius) = params.explosion_radius {
+                            let mut rng = rand::rng();
+                            for stack in &mut stacks {
+                                stack.item_count = (0..stack.item_count)
+                                    .filter(|_| rng.random::<f32>() <= 1.0 / radius)
+                                    .count()
+                                    as u8;
+                            }
+                            stacks.retain(|stack| stack.item_count > 0);
+                        }
                     }
-                    | LootFunctionTypes::CopyState {
+                    LootFunctionTypes::CopyState {
                         block: _,
                         properties: _,
                     }
                     | LootFunctionTypes::EnchantedCountIncrease
                     | LootFunctionTypes::SetOminousBottleAmplifier
                     | LootFunctionTypes::SetPotion
-                    | LootFunctionTypes::FurnaceSmelt
-                    | LootFunctionTypes::ExplosionDecay => {
+                    | LootFunctionTypes::FurnaceSmelt => {
                         // TODO: shouldnt crash here but needs to be implemented someday
                     }
                 }
@@ -143,14 +232,19 @@ impl LootPoolEntryTypesExt for LootPoolEntryTypes {
                 vec![ItemStack::new(1, Item::from_registry_key(key).unwrap())]
             }
             Self::LootTable => todo!(),
-            Self::Dynamic => todo!(),
+            // Dynamic drops are decided by the block entity, like the sherds of a decorated pot
+            Self::Dynamic => params
+                .block_entity
+                .as_ref()
+                .map(|block_entity| block_entity.dynamic_drops())
+                .unwrap_or_default(),
             Self::Tag => todo!(),
+            // Only the first child whose conditions pass is used
             Self::Alternatives(alternative_entry) => alternative_entry
                 .children
                 .iter()
-                .filter_map(|entry| entry.get_loot(params))
-                .flatten()
-                .collect(),
+                .find_map(|entry| entry.get_loot(params))
+                .unwrap_or_default(),
             Self::Sequence => todo!(),
             Self::Group => todo!(),
         }
@@ -172,6 +266,31 @@ impl LootConditionExt for LootCondition {
                 true
             }
             Self::KilledByPlayer => params.killed_by_player.unwrap_or(false),
+            Self::MatchTool {
+                items,
+                enchantments,
+            } => {
+                let Some(tool) = &params.tool else {
+                    return false;
+                };
+                if let Some(items) = items {
+                    let matches = match items.strip_prefix('#') {
+                        Some(tag) => tool.item.is_tagged_with(tag).unwrap_or(false),
+                        None => {
+                            items.strip_prefix("minecraft:").unwrap_or(items)
+                                == tool.item.registry_key
+                        }
+                    };
+                    if !matches {
+                        return false;
+                    }
+                }
+                enchantments.iter().all(|(name, min_level)| {
+                    Enchantment::from_name(name).is_some_and(|enchantment| {
+                        tool.get_enchantment_level(enchantment) >= *min_level
+                    })
+                })
+            }
             Self::BlockStateProperty {
                 block: _,
                 properties,
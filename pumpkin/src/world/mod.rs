@@ -3,17 +3,29 @@ use std::sync::Weak;
 use std::sync::atomic::Ordering::Relaxed;
 use std::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     sync::{Arc, atomic::Ordering},
 };
 
+pub mod anti_xray;
 pub mod chunker;
+pub mod debug;
+pub mod edit_session;
+mod entity_tick;
 pub mod explosion;
+pub mod inspect;
 pub mod loot;
+pub mod neighbor_update;
+pub mod poi;
 pub mod portal;
+pub mod preview;
+pub mod region_lock;
+mod template;
 pub mod time;
 
+use crate::world::anti_xray::AntiXray;
 use crate::world::loot::LootContextParameters;
+use crate::world::portal::PortalTeleport;
 use crate::{
     PLUGIN_MANAGER,
     block::{
@@ -22,22 +34,36 @@ use crate::{
         {OnNeighborUpdateArgs, OnScheduledTickArgs},
     },
     command::client_suggestions,
-    entity::{Entity, EntityBase, player::Player, r#type::from_type},
+    entity::{
+        Entity, EntityBase, RemovalReason,
+        player::{Player, TitleMode},
+        r#type::from_type,
+    },
     error::PumpkinError,
     net::ClientPlatform,
     plugin::{
-        block::block_break::BlockBreakEvent,
+        block::{block_break::BlockBreakEvent, block_physics::BlockPhysicsEvent},
         player::{player_join::PlayerJoinEvent, player_leave::PlayerLeaveEvent},
-        world::{chunk_load::ChunkLoad, chunk_save::ChunkSave, chunk_send::ChunkSend},
+        world::{
+            chunk_load::ChunkLoad, chunk_save::ChunkSave, chunk_send::ChunkSend,
+            chunk_unload::ChunkUnload,
+        },
+    },
+    server::{
+        Server,
+        content_filter::{FilterMask, FilteredText},
     },
-    server::Server,
 };
 use crate::{block::BlockEvent, entity::item::ItemEntity};
 use async_trait::async_trait;
 use border::Worldborder;
 use bytes::BufMut;
-use explosion::Explosion;
-use pumpkin_config::BasicConfiguration;
+use edit_session::SubmittedEdit;
+use entity_tick::{DeferredChange, try_defer};
+use explosion::{Explosion, WindBurst};
+use neighbor_update::NeighborUpdate;
+use pumpkin_config::{BASIC_CONFIG, BasicConfiguration, advanced_config};
+use pumpkin_data::block_properties::BlockProperties;
 use pumpkin_data::data_component_impl::EquipmentSlot;
 use pumpkin_data::entity::MobCategory;
 use pumpkin_data::fluid::{Falling, FluidProperties, FluidState};
@@ -77,8 +103,8 @@ use pumpkin_protocol::{
         client::play::{
             CBlockEntityData, CEntityStatus, CGameEvent, CLogin, CMultiBlockUpdate,
             CPlayerChatMessage, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-            CSetSelectedSlot, CSoundEffect, CSpawnEntity, FilterType, GameEvent, InitChat,
-            PlayerAction, PlayerInfoFlags,
+            CSetSelectedSlot, CSoundEffect, CSpawnEntity, CSystemChatMessage, FilterType,
+            GameEvent, InitChat, PlayerAction, PlayerInfoFlags,
         },
         server::play::SChatMessage,
     },
@@ -97,7 +123,8 @@ use pumpkin_protocol::{
         CWorldEvent,
     },
 };
-use pumpkin_registry::VanillaDimensionType;
+use pumpkin_registry::{SYNCED_REGISTRIES, VanillaDimensionType};
+use pumpkin_util::lock_order::{OrderedReadGuard, OrderedRwLock, OrderedWriteGuard};
 use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 use pumpkin_util::{
@@ -118,10 +145,14 @@ use pumpkin_world::{
     entity::entity_data_flags::{DATA_PLAYER_MAIN_HAND, DATA_PLAYER_MODE_CUSTOMISATION},
     world::GetBlockError,
 };
-use pumpkin_world::{level::Level, tick::TickPriority};
+use pumpkin_world::{
+    level::{Level, SyncChunk},
+    tick::{OrderedTick, TickPriority, budget::TickBudget},
+};
 use pumpkin_world::{world::BlockFlags, world_info::LevelData};
 use rand::seq::SliceRandom;
 use rand::{Rng, rng};
+use region_lock::RegionLocks;
 use scoreboard::Scoreboard;
 use serde::Serialize;
 use time::LevelTime;
@@ -130,20 +161,38 @@ use tokio::sync::RwLock;
 
 pub mod border;
 pub mod bossbar;
+mod catch_up;
 pub mod custom_bossbar;
+mod game_event;
+pub mod light;
+pub mod lightning;
 pub mod natural_spawner;
+pub mod patrol;
+pub mod raid;
 pub mod scoreboard;
+pub mod special_spawner;
+pub mod wandering_trader;
 pub mod weather;
+pub mod zombie_siege;
 
 use crate::world::natural_spawner::{SpawnState, spawn_for_chunk};
 use pumpkin_data::effect::StatusEffect;
-use pumpkin_world::chunk::ChunkHeightmapType::MotionBlocking;
+use pumpkin_world::chunk::ChunkHeightmapType::{self, WorldSurface};
 use pumpkin_world::generation::settings::GenerationSettings;
+use raid::RaidManager;
+use special_spawner::{SpecialSpawner, special_spawners_for};
 use uuid::Uuid;
 use weather::Weather;
 
 type FlowingFluidProperties = pumpkin_data::fluid::FlowingWaterLikeFluidProperties;
 
+/// How far [`World::find_spawn_position`] moves entities up or down.
+const MAX_SPAWN_ADJUSTMENT: f64 = 1.0;
+/// A chunk with more block changes than this in one tick is sent to players again as a whole.
+const FULL_CHUNK_RESEND_THRESHOLD: usize = 512;
+/// How often, in ticks, unloading chunks is retried after a plugin cancelled it.
+const RETRY_UNLOAD_INTERVAL: i64 = 20;
+
 impl PumpkinError for GetBlockError {
     fn is_kick(&self) -> bool {
         false
@@ -168,14 +217,17 @@ impl PumpkinError for GetBlockError {
 /// - Stores and tracks active `Player` entities within the world.
 /// - Provides a central hub for interacting with the world's entities and environment.
 pub struct World {
+    /// The name the world is known by, like `overworld` or the name of an additional world from
+    /// the config.
+    pub name: String,
     /// The underlying level, responsible for chunk management and terrain generation.
     pub level: Arc<Level>,
     pub level_info: Arc<RwLock<LevelData>>,
     /// A map of active players within the world, keyed by their unique UUID.
-    pub players: Arc<RwLock<HashMap<uuid::Uuid, Arc<Player>>>>,
+    pub players: Arc<OrderedRwLock<HashMap<uuid::Uuid, Arc<Player>>>>,
     /// A map of active entities within the world, keyed by their unique UUID.
     /// This does not include players.
-    pub entities: Arc<RwLock<HashMap<uuid::Uuid, Arc<dyn EntityBase>>>>,
+    pub entities: Arc<OrderedRwLock<HashMap<uuid::Uuid, Arc<dyn EntityBase>>>>,
     /// The world's scoreboard, used for tracking scores, objectives, and display information.
     pub scoreboard: Mutex<Scoreboard>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
@@ -185,20 +237,42 @@ pub struct World {
     /// The type of dimension the world is in.
     pub dimension_type: VanillaDimensionType,
     pub sea_level: i32,
+    /// The lowest buildable Y level of this world.
     pub min_y: i32,
+    /// The number of buildable block layers, starting at `min_y`.
+    pub height: i32,
     /// The world's weather, including rain and thunder levels.
     pub weather: Mutex<Weather>,
+    /// The raids currently going on in this world.
+    pub raids: Mutex<RaidManager>,
+    /// Hides ores from the chunks sent to players, `None` if it is disabled for this world.
+    pub anti_xray: Option<AntiXray>,
+    /// Spawners for wandering traders, patrols and the like. See `SpecialSpawner`.
+    pub special_spawners: Mutex<Vec<Box<dyn SpecialSpawner>>>,
     /// Block Behaviour
     pub block_registry: Arc<BlockRegistry>,
     pub server: Weak<Server>,
     synced_block_event_queue: Mutex<Vec<BlockEvent>>,
     /// A map of unsent block changes, keyed by block position.
     unsent_block_changes: Mutex<HashMap<BlockPos, u16>>,
+    /// Scheduled block ticks that did not fit into the per-tick budget.
+    pub block_tick_budget: Mutex<TickBudget<&'static Block>>,
+    /// Scheduled fluid ticks that did not fit into the per-tick budget.
+    pub fluid_tick_budget: Mutex<TickBudget<&'static Fluid>>,
+    /// Entities that went through a portal this tick, see [`PortalTeleport`].
+    pending_portal_teleports: Mutex<Vec<PortalTeleport>>,
+    /// Locks for areas of this world, see [`RegionLocks`].
+    pub region_locks: RegionLocks,
+    /// Submitted edits that are still being applied, see [`edit_session::WorldEditSession`].
+    edit_sessions: Mutex<VecDeque<SubmittedEdit>>,
+    /// Unwatched chunks that stayed loaded because a plugin cancelled their [`ChunkUnload`].
+    retained_chunks: Mutex<HashSet<Vector2<i32>>>,
 }
 
 impl World {
     #[must_use]
     pub fn load(
+        name: String,
         level: Arc<Level>,
         level_info: Arc<RwLock<LevelData>>,
         dimension_type: VanillaDimensionType,
@@ -218,28 +292,61 @@ impl World {
                 GENERATION_SETTINGS.get(&GeneratorSetting::Nether).unwrap()
             }
         };
+        let anti_xray = AntiXray::new(advanced_config().anti_xray.for_world(&name), dimension_type);
 
         Self {
+            name,
             level,
             level_info,
-            players: Arc::new(RwLock::new(HashMap::new())),
-            entities: Arc::new(RwLock::new(HashMap::new())),
+            players: Arc::new(OrderedRwLock::new("world.players", HashMap::new())),
+            entities: Arc::new(OrderedRwLock::new("world.entities", HashMap::new())),
             scoreboard: Mutex::new(Scoreboard::new()),
             worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 30_000_000.0, 0, 0, 0)),
             level_time: Mutex::new(LevelTime::new()),
             dimension_type,
             weather: Mutex::new(Weather::new()),
+            raids: Mutex::new(RaidManager::default()),
+            anti_xray,
+            special_spawners: Mutex::new(special_spawners_for(dimension_type)),
             block_registry,
             sea_level: generation_settings.sea_level,
-            min_y: i32::from(generation_settings.shape.min_y),
+            min_y: dimension_type.min_y(),
+            height: dimension_type.height(),
             synced_block_event_queue: Mutex::new(Vec::new()),
             unsent_block_changes: Mutex::new(HashMap::new()),
+            block_tick_budget: Mutex::new(TickBudget::default()),
+            fluid_tick_budget: Mutex::new(TickBudget::default()),
+            pending_portal_teleports: Mutex::new(Vec::new()),
+            region_locks: RegionLocks::default(),
+            edit_sessions: Mutex::new(VecDeque::new()),
+            retained_chunks: Mutex::new(HashSet::new()),
             server,
         }
     }
 
+    /// The highest buildable Y level of this world (inclusive).
+    #[must_use]
+    pub const fn top_y(&self) -> i32 {
+        self.min_y + self.height - 1
+    }
+
+    #[must_use]
+    pub const fn is_out_of_build_height(&self, pos: &BlockPos) -> bool {
+        pos.0.y < self.min_y || pos.0.y > self.top_y()
+    }
+
     pub async fn shutdown(&self) {
         for (uuid, entity) in self.entities.read().await.iter() {
+            // Vehicles of players and what else rides them are saved along with the player
+            let root_vehicle = entity
+                .get_entity()
+                .root_vehicle()
+                .await
+                .unwrap_or_else(|| entity.clone());
+            if Entity::has_player_passenger(&root_vehicle).await {
+                self.remove_entity_data(entity.get_entity()).await;
+                continue;
+            }
             self.save_entity(uuid, entity).await;
         }
         self.level.shutdown().await;
@@ -399,11 +506,43 @@ impl World {
         }
     }
 
+    /// Broadcasts an unsigned chat message, filtered for every recipient that should not see
+    /// the raw text.
+    pub async fn broadcast_unsigned_player_chat(&self, sender: &Player, message: &FilteredText) {
+        let format = &advanced_config().chat.format;
+        let sender_name = &sender.gameprofile.name;
+        for recipient in self.players.read().await.values() {
+            let text = if sender.should_filter_text_to(recipient).await {
+                let Some(filtered) = message.filtered() else {
+                    continue;
+                };
+                filtered
+            } else {
+                message.raw.clone()
+            };
+            match &recipient.client {
+                ClientPlatform::Java(client) => {
+                    let decorated =
+                        TextComponent::chat_decorated(format.clone(), sender_name.clone(), text);
+                    client
+                        .enqueue_packet(&CSystemChatMessage::new(&decorated, false))
+                        .await;
+                }
+                ClientPlatform::Bedrock(client) => {
+                    client
+                        .send_game_packet(&SText::new(text, sender_name.clone()))
+                        .await;
+                }
+            }
+        }
+    }
+
     pub async fn broadcast_secure_player_chat(
         &self,
         sender: &Arc<Player>,
         chat_message: &SChatMessage,
         decorated_message: &TextComponent,
+        filtered: &FilteredText,
     ) {
         let messages_sent: i32 = sender.chat_session.lock().await.messages_sent;
         let sender_last_seen = {
@@ -412,6 +551,14 @@ impl World {
         };
 
         for recipient in self.players.read().await.values() {
+            let filter_type = if sender.should_filter_text_to(recipient).await {
+                if filtered.mask == FilterMask::FullyFiltered {
+                    continue;
+                }
+                filtered.filter_type()
+            } else {
+                FilterType::PassThrough
+            };
             let messages_received: i32 = recipient.chat_session.lock().await.messages_received;
             let packet = &CPlayerChatMessage::new(
                 VarInt(messages_received),
@@ -423,7 +570,7 @@ impl World {
                 chat_message.salt,
                 sender_last_seen.indexed_for(recipient).await,
                 Some(decorated_message.clone()),
-                FilterType::PassThrough,
+                filter_type,
                 (RAW + 1).into(), // Custom registry chat_type with no sender name
                 TextComponent::text(""), // Not needed since we're injecting the name in the message for custom formatting
                 None,
@@ -571,6 +718,7 @@ impl World {
 
     pub async fn tick(self: &Arc<Self>, server: &Server) {
         let start = tokio::time::Instant::now();
+        self.tick_edit_sessions().await;
         self.flush_block_updates().await;
         // tick block entities
         self.flush_synced_block_events().await;
@@ -579,6 +727,9 @@ impl World {
         {
             let mut level_time = self.level_time.lock().await;
             level_time.tick_time();
+            self.level
+                .world_age
+                .store(level_time.world_age, Ordering::Relaxed);
             let mut weather = self.weather.lock().await;
             weather.tick_weather(self).await;
 
@@ -587,7 +738,8 @@ impl World {
                 level_time.set_time(time - time % 24000);
                 level_time.send_time(self).await;
 
-                for player in self.players.read().await.values() {
+                let players: Vec<_> = self.players.read().await.values().cloned().collect();
+                for player in players {
                     player.wake_up().await;
                 }
 
@@ -601,12 +753,25 @@ impl World {
             }
         }
 
+        if self.level.world_age.load(Ordering::Relaxed) % RETRY_UNLOAD_INTERVAL == 0 {
+            self.retry_chunk_unloads().await;
+        }
+
         let chunk_start = tokio::time::Instant::now();
         log::trace!("Ticking chunks");
         self.tick_chunks().await;
         let elapsed = chunk_start.elapsed();
 
         let players_to_tick: Vec<_> = self.players.read().await.values().cloned().collect();
+        let activation_range = &advanced_config().activation_range;
+        let player_positions: Vec<_> = if activation_range.enabled {
+            players_to_tick
+                .iter()
+                .map(|player| player.living_entity.entity.pos.load())
+                .collect()
+        } else {
+            Vec::new()
+        };
 
         log::trace!("Ticking players");
         // player ticks
@@ -614,27 +779,13 @@ impl World {
             player.tick(server).await;
         }
 
-        let entities_to_tick: Vec<_> = self.entities.read().await.values().cloned().collect();
-
         log::trace!("Ticking entities");
-        // Entity ticks
-        for entity in entities_to_tick {
-            entity.get_entity().age.fetch_add(1, Relaxed);
-            entity.tick(entity.clone(), server).await;
-            for player in self.players.read().await.values() {
-                if player
-                    .living_entity
-                    .entity
-                    .bounding_box
-                    .load()
-                    // This is vanilla, but TODO: change this when is in a vehicle
-                    .expand(1.0, 0.5, 1.0)
-                    .intersects(&entity.get_entity().bounding_box.load())
-                {
-                    entity.on_player_collision(player).await;
-                    break;
-                }
-            }
+        self.tick_entities(server, &player_positions).await;
+        self.apply_portal_teleports().await;
+
+        self.raids.lock().await.tick(self).await;
+        for spawner in self.special_spawners.lock().await.iter_mut() {
+            spawner.tick(self).await;
         }
 
         log::trace!(
@@ -646,38 +797,79 @@ impl World {
     }
 
     pub async fn flush_block_updates(&self) {
-        let mut block_state_updates_by_chunk_section = HashMap::new();
-        for (position, block_state_id) in self.unsent_block_changes.lock().await.drain() {
+        let mut changes = std::mem::take(&mut *self.unsent_block_changes.lock().await);
+        if let Some(anti_xray) = &self.anti_xray {
+            anti_xray.reveal_around(self, &mut changes).await;
+        }
+
+        if changes.is_empty() {
+            return;
+        }
+
+        let mut changes_by_chunk: HashMap<Vector2<i32>, HashMap<Vector3<i32>, Vec<_>>> =
+            HashMap::new();
+        for (position, block_state_id) in changes {
             let chunk_section = chunk_section_from_pos(&position);
-            block_state_updates_by_chunk_section
+            changes_by_chunk
+                .entry(Vector2::new(chunk_section.x, chunk_section.z))
+                .or_default()
                 .entry(chunk_section)
-                .or_insert(Vec::new())
+                .or_default()
                 .push((position, block_state_id));
         }
 
-        // TODO: only send packet to players who have the chunks loaded
         // TODO: Send light updates to update the wire directly next to a broken block
-        for chunk_section in block_state_updates_by_chunk_section.values() {
-            if chunk_section.is_empty() {
-                continue;
-            }
-            if chunk_section.len() == 1 {
-                let (block_pos, block_state_id) = chunk_section[0];
-                self.broadcast_packet_all(&CBlockUpdate::new(
-                    block_pos,
-                    i32::from(block_state_id).into(),
-                ))
-                .await;
-            } else {
-                self.broadcast_packet_all(&CMultiBlockUpdate::new(chunk_section.clone()))
-                    .await;
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        for player in players {
+            let watched_section = player.watched_section.load();
+            // The chunk manager is only locked to pick the sections the player gets, sending them
+            // can wait for the connection
+            let sections = {
+                let mut chunk_manager = player.chunk_manager.lock().await;
+                let mut sections = Vec::new();
+                for (chunk_position, chunk_sections) in &changes_by_chunk {
+                    // A queued chunk is read when it is sent, so it will have these changes anyway
+                    if !watched_section.is_within_distance(chunk_position.x, chunk_position.y)
+                        || chunk_manager.is_chunk_pending(chunk_position)
+                    {
+                        continue;
+                    }
+                    let change_count: usize = chunk_sections.values().map(Vec::len).sum();
+                    if change_count > FULL_CHUNK_RESEND_THRESHOLD
+                        && let Some(chunk) = self.level.try_get_chunk(chunk_position)
+                    {
+                        chunk_manager.push_chunk(*chunk_position, chunk);
+                        continue;
+                    }
+                    sections.extend(chunk_sections.values());
+                }
+                sections
+            };
+            for chunk_section in sections {
+                if let [(block_pos, block_state_id)] = chunk_section.as_slice() {
+                    player
+                        .client
+                        .enqueue_packet(&CBlockUpdate::new(
+                            *block_pos,
+                            i32::from(*block_state_id).into(),
+                        ))
+                        .await;
+                } else {
+                    player
+                        .client
+                        .enqueue_packet(&CMultiBlockUpdate::new(chunk_section.clone()))
+                        .await;
+                }
             }
         }
     }
 
-    pub async fn tick_chunks(self: &Arc<Self>) {
-        let tick_data = self.level.get_tick_data().await;
-        for scheduled_tick in tick_data.block_ticks {
+    /// Runs the given scheduled block ticks in order.
+    pub async fn run_block_ticks(
+        self: &Arc<Self>,
+        block_ticks: impl IntoIterator<Item = OrderedTick<&'static Block>>,
+    ) {
+        for scheduled_tick in block_ticks {
             let block = self.get_block(&scheduled_tick.position).await;
             if let Some(pumpkin_block) = self.block_registry.get_pumpkin_block(block) {
                 pumpkin_block
@@ -689,7 +881,24 @@ impl World {
                     .await;
             }
         }
-        for scheduled_tick in tick_data.fluid_ticks {
+    }
+
+    pub async fn tick_chunks(self: &Arc<Self>) {
+        let tick_data = self.level.get_tick_data().await;
+        let tick_config = &advanced_config().tick;
+        let block_ticks = self
+            .block_tick_budget
+            .lock()
+            .await
+            .take(tick_data.block_ticks, tick_config.max_block_ticks);
+        let fluid_ticks = self
+            .fluid_tick_budget
+            .lock()
+            .await
+            .take(tick_data.fluid_ticks, tick_config.max_fluid_ticks);
+
+        self.run_block_ticks(block_ticks).await;
+        for scheduled_tick in fluid_ticks {
             let fluid = self.get_fluid(&scheduled_tick.position).await;
             if let Some(pumpkin_fluid) = self.block_registry.get_pumpkin_fluid(fluid) {
                 pumpkin_fluid
@@ -734,7 +943,7 @@ impl World {
             }
         }
 
-        let mut spawning_chunks: Vec<(Vector2<i32>, Arc<RwLock<ChunkData>>)> =
+        let mut spawning_chunks: Vec<(Vector2<i32>, SyncChunk)> =
             spawning_chunks_map.into_iter().collect();
 
         let get_chunks_clock = spawn_entity_clock_start.elapsed();
@@ -1076,61 +1285,67 @@ impl World {
         true
     }
 
+    /// Finds where `entity` can be spawned near `position` without getting stuck in blocks. It
+    /// is lifted onto blocks it would be inside of by up to one block, and if it falls, it is put
+    /// down onto ground at most one block below. Returns `None` if there is no room for it.
+    pub async fn find_spawn_position(
+        self: &Arc<Self>,
+        entity: &dyn EntityBase,
+        position: Vector3<f64>,
+    ) -> Option<Vector3<f64>> {
+        let dimensions = entity.get_entity().bounding_box_size.load();
+        let bounding_box =
+            BoundingBox::new_from_pos(position.x, position.y, position.z, &dimensions);
+        let falls = entity.get_gravity() > 0.0;
+        let lowest = if falls {
+            position.y - MAX_SPAWN_ADJUSTMENT
+        } else {
+            position.y
+        };
+        let highest = position.y + MAX_SPAWN_ADJUSTMENT;
+
+        let (collisions, _) = self
+            .get_block_collisions(
+                bounding_box
+                    .stretch(Vector3::new(0.0, lowest - position.y, 0.0))
+                    .stretch(Vector3::new(0.0, MAX_SPAWN_ADJUSTMENT, 0.0)),
+            )
+            .await;
+
+        // The entity can stand where it is or on top of any block near it
+        let mut candidates: Vec<f64> = collisions
+            .iter()
+            .map(|collision| collision.max.y)
+            .filter(|y| (lowest..=highest).contains(y))
+            .collect();
+        candidates.push(position.y);
+        candidates.sort_by(f64::total_cmp);
+
+        candidates
+            .into_iter()
+            // Entities that don't fall stay where they were put if there is room
+            .filter(|&y| falls || y >= position.y)
+            .find(|&y| {
+                // Falling down there must not go through blocks either
+                let shifted = bounding_box
+                    .shift(Vector3::new(0.0, y - position.y, 0.0))
+                    .stretch(Vector3::new(0.0, (position.y - y).max(0.0), 0.0));
+                !collisions
+                    .iter()
+                    .any(|collision| collision.intersects(&shifted))
+            })
+            .map(|y| Vector3::new(position.x, y, position.z))
+    }
+
     pub async fn tick_spawning_chunk(
         self: &Arc<Self>,
         chunk_pos: &Vector2<i32>,
-        chunk: &Arc<RwLock<ChunkData>>,
+        chunk: &SyncChunk,
         spawn_list: &Vec<&'static MobCategory>,
         spawn_state: &mut SpawnState,
     ) {
-        // this.level.tickThunder(chunk);
         //TODO check in simulation distance
-        let weather = self.weather.lock().await;
-        if weather.raining && weather.thundering && rng().random_range(0..100_000) == 0 {
-            let rand_value = rng().random::<i32>() >> 2;
-            let delta = Vector3::new(rand_value & 15, rand_value >> 16 & 15, rand_value >> 8 & 15);
-            let random_pos = Vector3::new(
-                chunk_pos.x << 4,
-                chunk.read().await.heightmap.get_height(
-                    MotionBlocking,
-                    chunk_pos.x << 4,
-                    chunk_pos.y << 4,
-                    self.min_y,
-                ),
-                chunk_pos.y << 4,
-            )
-            .add(&delta);
-            // TODO this.getBrightness(LightLayer.SKY, blockPos) >= 15;
-            // TODO heightmap
-
-            // TODO findLightningRod(blockPos)
-            // TODO encapsulatingFullBlocks
-            if true {
-                // TODO biome.getPrecipitationAt(pos, this.getSeaLevel()) == Biome.Precipitation.RAIN
-                // TODO this.getCurrentDifficultyAt(blockPos);
-                if rng().random::<f32>() < 0.0675
-                    && self.get_block(&random_pos.to_block_pos().down()).await
-                        != &Block::LIGHTNING_ROD
-                {
-                    let entity = Entity::new(
-                        Uuid::new_v4(),
-                        self.clone(),
-                        random_pos.to_f64(),
-                        &EntityType::SKELETON_HORSE,
-                        false,
-                    );
-                    self.spawn_entity(Arc::new(entity)).await;
-                }
-                let entity = Entity::new(
-                    Uuid::new_v4(),
-                    self.clone(),
-                    random_pos.to_f64().add_raw(0.5, 0., 0.5),
-                    &EntityType::LIGHTNING_BOLT,
-                    false,
-                );
-                self.spawn_entity(Arc::new(entity)).await;
-            }
-        }
+        self.tick_thunder(chunk_pos).await;
 
         if spawn_list.is_empty() {
             return;
@@ -1156,20 +1371,35 @@ impl World {
     }
 
     /// Gets the y position of the first non air block from the top down
+    /// Returns the y of the highest non-air block of the column.
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        let generation_settings = self.generation_settings();
-        for y in (i32::from(generation_settings.shape.min_y)
-            ..i32::from(generation_settings.shape.max_y()))
-            .rev()
-        {
-            let pos = BlockPos::new(position.x, y, position.y);
-            let block = self.get_block_state(&pos).await;
-            if block.is_air() {
-                continue;
-            }
-            return y;
-        }
-        i32::from(generation_settings.shape.min_y)
+        self.get_height(WorldSurface, position.x, position.y).await
+    }
+
+    /// Where players spawn in this world, on top of the world spawn's column, and the yaw they
+    /// face.
+    pub async fn get_spawn_position(&self) -> (Vector3<f64>, f32) {
+        let (spawn_x, spawn_z, spawn_angle) = {
+            let info = self.level_info.read().await;
+            (info.spawn_x, info.spawn_z, info.spawn_angle)
+        };
+        let pos_y = self.get_top_block(Vector2::new(spawn_x, spawn_z)).await + 1; // +1 to spawn on top of the block
+        let position = Vector3::new(
+            f64::from(spawn_x) + 0.5,
+            f64::from(pos_y),
+            f64::from(spawn_z) + 0.5,
+        );
+        (position, spawn_angle)
+    }
+
+    /// Returns the y of the highest block of the column counting for `heightmap_type`, or
+    /// `min_y - 1` if there is none.
+    pub async fn get_height(&self, heightmap_type: ChunkHeightmapType, x: i32, z: i32) -> i32 {
+        let chunk = self.level.get_chunk(Vector2::new(x >> 4, z >> 4)).await;
+        let chunk = chunk.read().await;
+        chunk
+            .heightmap
+            .get_height(heightmap_type, x, z, chunk.section.min_y)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -1424,6 +1654,8 @@ impl World {
             client_suggestions::send_c_commands_packet(&player, &command_dispatcher).await;
         };
 
+        player.send_recipe_book().await;
+
         // Spawn in initial chunks
         // This is made before the player teleport so that the player doesn't glitch out when spawning
         chunker::update_position(&player).await;
@@ -1436,16 +1668,7 @@ impl World {
 
             (position, yaw, pitch)
         } else {
-            let info = &self.level_info.read().await;
-            let spawn_position = Vector2::new(info.spawn_x, info.spawn_z);
-            let pos_y = self.get_top_block(spawn_position).await + 1; // +1 to spawn on top of the block
-
-            let position = Vector3::new(
-                f64::from(info.spawn_x) + 0.5,
-                f64::from(pos_y),
-                f64::from(info.spawn_z) + 0.5,
-            );
-            let yaw = info.spawn_angle;
+            let (position, yaw) = self.get_spawn_position().await;
             (position, yaw, 0.0) // Pitch
         };
 
@@ -1460,6 +1683,7 @@ impl World {
         // Firstly, send an info update to our new player, so they can see their skin
         // and also send their info to everyone else.
         log::debug!("Broadcasting player info for {}", player.gameprofile.name);
+        let properties = player.profile_properties().await;
         self.broadcast_packet_all(&CPlayerInfoUpdate::new(
             (PlayerInfoFlags::ADD_PLAYER
                 | PlayerInfoFlags::UPDATE_GAME_MODE
@@ -1470,7 +1694,7 @@ impl World {
                 actions: &[
                     PlayerAction::AddPlayer {
                         name: &gameprofile.name,
-                        properties: &gameprofile.properties,
+                        properties: &properties,
                     },
                     PlayerAction::UpdateGameMode(VarInt(gamemode as i32)),
                     PlayerAction::UpdateListed(true),
@@ -1481,23 +1705,25 @@ impl World {
         let current_players = self.players.read().await;
         // Here, we send all the infos of players who already joined.
         {
-            let mut current_player_data = Vec::new();
-
+            let mut current_player_properties = Vec::new();
             for (_, player) in current_players
                 .iter()
                 .filter(|(c, _)| **c != player.gameprofile.id)
             {
-                let chat_session = player.chat_session.lock().await;
+                current_player_properties.push((player, player.profile_properties().await));
+            }
 
-                let mut player_actions = vec![
-                    PlayerAction::AddPlayer {
-                        name: &player.gameprofile.name,
-                        properties: &player.gameprofile.properties,
-                    },
-                    PlayerAction::UpdateListed(true),
-                ];
+            let mut current_player_data = Vec::new();
+            let mark_afk = advanced_config().afk.mark_in_tab_list;
+            for (player, properties) in &current_player_properties {
+                // Actions have to be in the same order as their flags
+                let mut player_actions = vec![PlayerAction::AddPlayer {
+                    name: &player.gameprofile.name,
+                    properties,
+                }];
 
                 if base_config.allow_chat_reports {
+                    let chat_session = player.chat_session.lock().await;
                     player_actions.push(PlayerAction::InitializeChat(Some(InitChat {
                         session_id: chat_session.session_id,
                         expires_at: chat_session.expires_at,
@@ -1505,15 +1731,28 @@ impl World {
                         signature: chat_session.signature.clone(),
                     })));
                 }
-                drop(chat_session);
+                player_actions.push(PlayerAction::UpdateGameMode(VarInt(
+                    player.gamemode.load() as i32
+                )));
+                player_actions.push(PlayerAction::UpdateListed(true));
+                player_actions.push(PlayerAction::UpdateLatency(VarInt(player.ping() as i32)));
+                if mark_afk {
+                    player_actions.push(PlayerAction::UpdateDisplayName(player.tab_list_name()));
+                }
 
                 current_player_data.push((&player.gameprofile.id, player_actions));
             }
 
-            let mut action_flags = PlayerInfoFlags::ADD_PLAYER | PlayerInfoFlags::UPDATE_LISTED;
+            let mut action_flags = PlayerInfoFlags::ADD_PLAYER
+                | PlayerInfoFlags::UPDATE_GAME_MODE
+                | PlayerInfoFlags::UPDATE_LISTED
+                | PlayerInfoFlags::UPDATE_LATENCY;
             if base_config.allow_chat_reports {
                 action_flags |= PlayerInfoFlags::INITIALIZE_CHAT;
             }
+            if mark_afk {
+                action_flags |= PlayerInfoFlags::UPDATE_DISPLAY_NAME;
+            }
 
             let entries = current_player_data
                 .iter()
@@ -1658,6 +1897,7 @@ impl World {
 
         player.send_active_effects().await;
         self.send_player_equipment(&player).await;
+        player.mount_saved_vehicle().await;
     }
 
     async fn send_player_equipment(&self, from: &Player) {
@@ -1730,7 +1970,7 @@ impl World {
 
     pub async fn explode(self: &Arc<Self>, position: Vector3<f64>, power: f32) {
         let explosion = Explosion::new(power, position);
-        explosion.explode(self).await;
+        let pushed = explosion.explode(self).await;
         let particle = if power < 2.0 {
             Particle::Explosion
         } else {
@@ -1741,11 +1981,48 @@ impl World {
             if player.position().squared_distance_to_vec(position) > 4096.0 {
                 continue;
             }
+            let knockback = pushed
+                .iter()
+                .find(|(pushed, _)| pushed.entity_id() == player.entity_id())
+                .map(|(_, knockback)| *knockback);
+            player
+                .client
+                .enqueue_packet(&CExplosion::new(
+                    position,
+                    knockback,
+                    VarInt(particle as i32),
+                    sound.clone(),
+                ))
+                .await;
+        }
+    }
+
+    /// Bursts a wind charge at `position`, pushing away entities and triggering blocks like doors
+    /// and buttons within `radius` without damaging anything.
+    pub async fn wind_burst(
+        self: &Arc<Self>,
+        position: Vector3<f64>,
+        radius: f32,
+        particle: Particle,
+        sound: Sound,
+    ) {
+        let burst = WindBurst::new(radius, position);
+        let pushed = burst.push_entities(self).await;
+        burst.trigger_blocks(self).await;
+        let sound = IdOr::<SoundEvent>::Id(sound as u16);
+        for player in self.players.read().await.values() {
+            if player.position().squared_distance_to_vec(position) > 4096.0 {
+                continue;
+            }
+            let knockback = pushed
+                .iter()
+                .find(|(pushed, _)| pushed.entity_id() == player.entity_id())
+                .map(|(_, knockback)| *knockback);
             player
                 .client
                 .enqueue_packet(&CExplosion::new(
                     position,
-                    None,
+                    knockback,
                     VarInt(particle as i32),
                     sound.clone(),
                 ))
@@ -1826,23 +2103,79 @@ impl World {
     }
 
     /// Returns true if enough players are sleeping and we should skip the night.
-    pub async fn should_skip_night(&self) -> bool {
+    /// Counts the players that take part in skipping the night, that are not spectating. Returns
+    /// the number of those players, how many of them are sleeping and how many of them have been
+    /// sleeping long enough to skip the night.
+    async fn count_sleeping_players(&self) -> (usize, usize, usize) {
         let players = self.players.read().await;
+        let mut active = 0;
+        let mut sleeping = 0;
+        let mut rested = 0;
+        for player in players.values().filter(|player| !player.is_spectator()) {
+            active += 1;
+            if let Some(since) = player.sleeping_since.load() {
+                sleeping += 1;
+                if since >= 100 {
+                    rested += 1;
+                }
+            }
+        }
+        (active, sleeping, rested)
+    }
 
-        let player_count = players.len();
-        let sleeping_player_count = players
-            .values()
-            .filter(|player| {
-                player
-                    .sleeping_since
-                    .load()
-                    .is_some_and(|since| since >= 100)
-            })
-            .count();
-        drop(players);
+    /// The number of players that have to sleep to skip the night with the
+    /// `playersSleepingPercentage` game rule.
+    async fn sleeping_players_required(&self, active_players: usize) -> usize {
+        let percentage = self
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .players_sleeping_percentage
+            .max(0);
+        ((active_players as f64 * percentage as f64 / 100.0).ceil() as usize).max(1)
+    }
+
+    pub async fn should_skip_night(&self) -> bool {
+        let (active, _, rested) = self.count_sleeping_players().await;
+        rested >= self.sleeping_players_required(active).await
+    }
+
+    /// Tells every sleeping player how many players are sleeping and how many are needed to skip
+    /// the night. Called whenever a player goes to bed, wakes up or leaves.
+    pub async fn update_sleeping_status(&self) {
+        let (active, sleeping, _) = self.count_sleeping_players().await;
+        if sleeping == 0 {
+            return;
+        }
+        let percentage = self
+            .level_info
+            .read()
+            .await
+            .game_rules
+            .players_sleeping_percentage;
+        let required = self.sleeping_players_required(active).await;
+        let message = if percentage > 100 {
+            TextComponent::translate("sleep.not_possible", [])
+        } else if sleeping >= required {
+            TextComponent::translate("sleep.skipping_night", [])
+        } else {
+            TextComponent::translate(
+                "sleep.players_sleeping",
+                [
+                    TextComponent::text(sleeping.to_string()),
+                    TextComponent::text(required.to_string()),
+                ],
+            )
+        };
 
-        // TODO: sleep ratio
-        sleeping_player_count == player_count
+        let players = self.players.read().await;
+        for player in players
+            .values()
+            .filter(|player| player.sleeping_since.load().is_some())
+        {
+            player.show_title(&message, &TitleMode::ActionBar).await;
+        }
     }
 
     // NOTE: This function doesn't actually await on anything, it just spawns two tokio tasks
@@ -1930,6 +2263,7 @@ impl World {
                     }}
                     (event.world, event.chunk)
                 } else {
+                    world.catch_up_chunk(&chunk).await;
                     (world, chunk)
                 };
 
@@ -2076,6 +2410,14 @@ impl World {
         None
     }
 
+    /// Gets an entity or player by its UUID
+    pub async fn get_entity_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<dyn EntityBase>> {
+        if let Some(player) = self.players.read().await.get(&id) {
+            return Some(player.clone() as Arc<dyn EntityBase>);
+        }
+        self.entities.read().await.get(&id).cloned()
+    }
+
     /// Gets a `Player` by a username
     pub async fn get_player_by_name(&self, name: &str) -> Option<Arc<Player>> {
         for player in self.players.read().await.values() {
@@ -2312,11 +2654,15 @@ impl World {
     /// - This function assumes `broadcast_packet_expect` and `remove_entity` are defined elsewhere.
     /// - The disconnect message sending is currently optional. Consider making it a configurable option.
     pub async fn remove_player(&self, player: &Arc<Player>, fire_event: bool) {
+        let entity = player.get_entity();
+        entity.eject_passengers().await;
+        entity.detach_from_vehicle().await;
         self.players
             .write()
             .await
             .remove(&player.gameprofile.id)
             .unwrap();
+        self.update_sleeping_status().await;
         let uuid = player.gameprofile.id;
         self.broadcast_packet_all(&CRemovePlayerInfo::new(&[uuid]))
             .await;
@@ -2344,8 +2690,86 @@ impl World {
         }
     }
 
+    /// Re-sends the list entry of `player` to everyone and respawns their entity for everyone
+    /// else, so changed profile properties like the skin show up.
+    pub async fn refresh_player(&self, player: &Arc<Player>) {
+        let uuid = player.gameprofile.id;
+        let properties = player.profile_properties().await;
+        let allow_chat_reports = BASIC_CONFIG.allow_chat_reports;
+
+        // Actions have to be in the same order as their flags
+        let mut actions = vec![PlayerAction::AddPlayer {
+            name: &player.gameprofile.name,
+            properties: &properties,
+        }];
+        let mut action_flags = PlayerInfoFlags::ADD_PLAYER
+            | PlayerInfoFlags::UPDATE_GAME_MODE
+            | PlayerInfoFlags::UPDATE_LISTED
+            | PlayerInfoFlags::UPDATE_LATENCY;
+        if allow_chat_reports {
+            action_flags |= PlayerInfoFlags::INITIALIZE_CHAT;
+            let chat_session = player.chat_session.lock().await;
+            actions.push(PlayerAction::InitializeChat(Some(InitChat {
+                session_id: chat_session.session_id,
+                expires_at: chat_session.expires_at,
+                public_key: chat_session.public_key.clone(),
+                signature: chat_session.signature.clone(),
+            })));
+        }
+        actions.push(PlayerAction::UpdateGameMode(VarInt(
+            player.gamemode.load() as i32
+        )));
+        actions.push(PlayerAction::UpdateListed(true));
+        actions.push(PlayerAction::UpdateLatency(VarInt(player.ping() as i32)));
+
+        self.broadcast_packet_all(&CRemovePlayerInfo::new(&[uuid]))
+            .await;
+        self.broadcast_packet_all(&CPlayerInfoUpdate::new(
+            action_flags.bits(),
+            &[java::client::play::Player {
+                uuid,
+                actions: &actions,
+            }],
+        ))
+        .await;
+
+        let entity = &player.living_entity.entity;
+        self.broadcast_packet_except(&[uuid], &CRemoveEntities::new(&[entity.entity_id.into()]))
+            .await;
+        self.broadcast_packet_except(
+            &[uuid],
+            &CSpawnEntity::new(
+                entity.entity_id.into(),
+                uuid,
+                i32::from(EntityType::PLAYER.id).into(),
+                entity.pos.load(),
+                entity.pitch.load(),
+                entity.yaw.load(),
+                entity.head_yaw.load(),
+                0.into(),
+                entity.velocity.load(),
+            ),
+        )
+        .await;
+        player.send_client_information().await;
+        player.send_equipment().await;
+    }
+
     /// Adds an entity to the world.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
+        if let Err(DeferredChange::Spawn(entity)) = try_defer(DeferredChange::Spawn(entity)) {
+            self.add_entity(entity).await;
+        }
+    }
+
+    async fn add_entity(&self, entity: Arc<dyn EntityBase>) {
+        let region = RegionLocks::region_of_chunk(entity.get_entity().chunk_pos.load());
+        self.region_locks
+            .run_locked([region], self.add_entity_locked(entity))
+            .await;
+    }
+
+    async fn add_entity_locked(&self, entity: Arc<dyn EntityBase>) {
         let base_entity = entity.get_entity();
         self.broadcast_packet_all(&base_entity.create_spawn_packet())
             .await;
@@ -2367,7 +2791,66 @@ impl World {
         current_entities.insert(base_entity.entity_uuid, entity);
     }
 
+    /// Queues a teleport through a portal, it is applied once all entities have ticked.
+    pub async fn queue_portal_teleport(&self, teleport: PortalTeleport) {
+        self.pending_portal_teleports.lock().await.push(teleport);
+    }
+
+    async fn apply_portal_teleports(self: &Arc<Self>) {
+        let teleports = std::mem::take(&mut *self.pending_portal_teleports.lock().await);
+        for PortalTeleport {
+            entity,
+            world,
+            position,
+        } in teleports
+        {
+            if entity.get_entity().is_removed() {
+                continue;
+            }
+            if entity.get_player().is_some() || Arc::ptr_eq(self, &world) {
+                entity.teleport(position, None, None, world).await;
+            } else {
+                self.transfer_entity(&entity, &world, position).await;
+            }
+        }
+    }
+
+    /// Moves a non-player entity into the `target` world. Like in vanilla, the entity is removed
+    /// here and recreated in the target world from its saved data.
+    pub async fn transfer_entity(
+        &self,
+        entity: &Arc<dyn EntityBase>,
+        target: &Arc<Self>,
+        position: Vector3<f64>,
+    ) {
+        let base_entity = entity.get_entity();
+        let mut nbt = NbtCompound::new();
+        entity.write_nbt(&mut nbt).await;
+        base_entity.remove().await;
+        base_entity
+            .removal_reason
+            .store(Some(RemovalReason::ChangedDimension));
+
+        let moved = from_type(
+            base_entity.entity_type,
+            position,
+            target,
+            base_entity.entity_uuid,
+        )
+        .await;
+        moved.read_nbt_non_mut(&nbt).await;
+        moved.get_entity().set_pos(position);
+        target.spawn_entity(moved).await;
+    }
+
     pub async fn remove_entity(&self, entity: &Entity) {
+        let region = RegionLocks::region_of_chunk(entity.chunk_pos.load());
+        self.region_locks
+            .run_locked([region], self.remove_entity_locked(entity))
+            .await;
+    }
+
+    async fn remove_entity_locked(&self, entity: &Entity) {
         self.entities.write().await.remove(&entity.entity_uuid);
         self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
             .await;
@@ -2384,12 +2867,35 @@ impl World {
     }
 
     /// Sets a block and returns the old block id
-    #[allow(clippy::too_many_lines)]
     pub async fn set_block_state(
         self: &Arc<Self>,
         position: &BlockPos,
         block_state_id: BlockStateId,
         flags: BlockFlags,
+    ) -> BlockStateId {
+        if try_defer(DeferredChange::SetBlockState(
+            *position,
+            block_state_id,
+            flags,
+        ))
+        .is_ok()
+        {
+            return self.get_block_state_id(position).await;
+        }
+        self.region_locks
+            .run_locked(
+                [RegionLocks::region_of_block(position)],
+                self.set_block_state_locked(position, block_state_id, flags),
+            )
+            .await
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn set_block_state_locked(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        block_state_id: BlockStateId,
+        flags: BlockFlags,
     ) -> BlockStateId {
         let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
         let chunk = self.level.get_chunk(chunk_coordinate).await;
@@ -2407,6 +2913,17 @@ impl World {
         if replaced_block_state_id == block_state_id {
             return block_state_id;
         }
+        chunk.update_heightmaps(
+            relative.x as usize,
+            relative.y,
+            relative.z as usize,
+            block_state_id,
+        );
+        let poi_changed =
+            chunk
+                .poi
+                .on_block_changed(*position, replaced_block_state_id, block_state_id);
+        let poi = poi_changed.then(|| chunk.poi.get(position).cloned());
         chunk.mark_dirty(true);
 
         self.unsent_block_changes
@@ -2415,6 +2932,14 @@ impl World {
             .insert(*position, block_state_id);
         drop(chunk);
 
+        if let Some(poi) = poi {
+            self.send_debug_poi(*position, poi.as_ref()).await;
+        }
+
+        if flags.contains(BlockFlags::CLIENT_ONLY) {
+            return replaced_block_state_id;
+        }
+
         let old_block = Block::from_state_id(replaced_block_state_id);
         let new_block = Block::from_state_id(block_state_id);
 
@@ -2427,8 +2952,10 @@ impl World {
             && old_block.default_state.block_entity_type != u16::MAX
             && let Some(entity) = self.get_block_entity(position).await
         {
-            let world: Arc<dyn SimpleWorld> = self.clone();
-            entity.on_block_replaced(world, *position).await;
+            if !flags.contains(BlockFlags::SKIP_BLOCK_ENTITY_REPLACED_CALLBACK) {
+                let world: Arc<dyn SimpleWorld> = self.clone();
+                entity.on_block_replaced(world, *position).await;
+            }
             self.remove_block_entity(position).await;
         }
 
@@ -2554,6 +3081,26 @@ impl World {
         position: &BlockPos,
         cause: Option<Arc<Player>>,
         flags: BlockFlags,
+    ) {
+        let Err(DeferredChange::BreakBlock(position, cause, flags)) =
+            try_defer(DeferredChange::BreakBlock(*position, cause, flags))
+        else {
+            return;
+        };
+        let position = &position;
+        self.region_locks
+            .run_locked(
+                [RegionLocks::region_of_block(position)],
+                self.break_block_locked(position, cause, flags),
+            )
+            .await;
+    }
+
+    async fn break_block_locked(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        cause: Option<Arc<Player>>,
+        flags: BlockFlags,
     ) {
         let (broken_block, broken_block_state) = self.get_block_and_state_id(position).await;
         let event = BlockBreakEvent::new(cause.clone(), broken_block, *position, 0, false);
@@ -2561,6 +3108,12 @@ impl World {
         let event = PLUGIN_MANAGER.fire::<BlockBreakEvent>(event).await;
 
         if !event.cancelled {
+            if let Some(player) = &cause {
+                self.block_registry
+                    .on_break(self, broken_block, broken_block_state, position, player)
+                    .await;
+            }
+
             let new_state_id = if broken_block
                 .properties(broken_block_state)
                 .and_then(|properties| {
@@ -2580,6 +3133,13 @@ impl World {
                 0
             };
 
+            let tool = match &cause {
+                Some(player) if !flags.contains(BlockFlags::SKIP_DROPS) => {
+                    Some(player.inventory.held_item().lock().await.clone())
+                }
+                _ => None,
+            };
+            let block_entity = self.get_block_entity(position).await;
             let broken_state_id = self.set_block_state(position, new_state_id, flags).await;
 
             if Block::from_state_id(broken_state_id) != &Block::FIRE {
@@ -2601,6 +3161,8 @@ impl World {
             if !flags.contains(BlockFlags::SKIP_DROPS) {
                 let params = LootContextParameters {
                     block_state: Some(BlockState::from_id(broken_state_id)),
+                    tool,
+                    block_entity,
                     ..Default::default()
                 };
                 block::drop_loot(self, broken_block, position, true, params).await;
@@ -2621,18 +3183,86 @@ impl World {
         self.spawn_entity(item_entity).await;
     }
 
+    /// Drops a stack out of the given face of a block, like vanilla's `Block.dropStack` with a direction.
+    pub async fn drop_stack_from_face(
+        self: &Arc<Self>,
+        pos: &BlockPos,
+        direction: BlockDirection,
+        stack: ItemStack,
+    ) {
+        let half_width = f64::from(EntityType::ITEM.dimension[0]) / 2.0;
+        let half_height = f64::from(EntityType::ITEM.dimension[1]) / 2.0;
+        let offset = direction.to_offset();
+        let mut rng = rand::rng();
+
+        let mut spread = |offset: i32, half_size: f64| {
+            if offset == 0 {
+                rng.random_range(-0.25..0.25)
+            } else {
+                f64::from(offset) * (0.5 + half_size)
+            }
+        };
+        let position = Vector3::new(
+            f64::from(pos.0.x) + 0.5 + spread(offset.x, half_width),
+            f64::from(pos.0.y) + 0.5 + spread(offset.y, half_height) - half_height,
+            f64::from(pos.0.z) + 0.5 + spread(offset.z, half_width),
+        );
+
+        let mut velocity = |offset: i32| {
+            if offset == 0 {
+                rng.random_range(-0.1..0.1)
+            } else {
+                f64::from(offset) * 0.1
+            }
+        };
+        let velocity_x = velocity(offset.x);
+        let velocity_z = velocity(offset.z);
+        let velocity_y = if offset.y == 0 {
+            rng.random_range(0.0..0.1)
+        } else {
+            f64::from(offset.y) * 0.1 + 0.1
+        };
+
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            self.clone(),
+            position,
+            &EntityType::ITEM,
+            false,
+        );
+        let item_entity = Arc::new(
+            ItemEntity::new_with_velocity(
+                entity,
+                stack,
+                Vector3::new(velocity_x, velocity_y, velocity_z),
+                10,
+            )
+            .await,
+        );
+        self.spawn_entity(item_entity).await;
+    }
+
     /* ItemScatterer.java */
     pub async fn scatter_inventory(
         self: &Arc<Self>,
         position: &BlockPos,
         inventory: &Arc<dyn Inventory>,
     ) {
+        // Identical stacks are merged first, so e.g. a chest full of single items doesn't spawn an entity per slot
+        let mut stacks = Vec::new();
         for i in 0..inventory.size() {
+            let stack = inventory.remove_stack(i).await;
+            if !stack.is_empty() {
+                let max_count = stack.get_max_stack_size();
+                block::merge_stack(&mut stacks, stack, max_count);
+            }
+        }
+        for stack in stacks {
             self.scatter_stack(
                 f64::from(position.0.x),
                 f64::from(position.0.y),
                 f64::from(position.0.z),
-                inventory.remove_stack(i).await,
+                stack,
             )
             .await;
         }
@@ -2815,6 +3445,25 @@ impl World {
         (Block::from_state_id(id), id)
     }
 
+    /// Loads the block state at `position` as `P`, applies `modify` and writes the result back using `flags`.
+    ///
+    /// Returns the old and new state ids, or `None` if the block at `position` does not use `P`.
+    pub async fn modify_block_state<P: BlockProperties + Send>(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        flags: BlockFlags,
+        modify: impl FnOnce(&mut P) + Send,
+    ) -> Option<(BlockStateId, BlockStateId)> {
+        let (block, old_state_id) = self.get_block_and_state_id(position).await;
+        let mut props = block.get::<P>(old_state_id)?;
+        modify(&mut props);
+        let new_state_id = props.to_state_id(block);
+        if new_state_id != old_state_id {
+            self.set_block_state(position, new_state_id, flags).await;
+        }
+        Some((old_state_id, new_state_id))
+    }
+
     /// Updates neighboring blocks of a block
     pub async fn update_neighbors(
         self: &Arc<Self>,
@@ -2822,36 +3471,11 @@ impl World {
         except: Option<BlockDirection>,
     ) {
         let source_block = self.get_block(block_pos).await;
-        for direction in BlockDirection::update_order() {
-            if except.is_some_and(|d| d == direction) {
-                continue;
-            }
-
-            let neighbor_pos = block_pos.offset(direction.to_offset());
-            let (neighbor_block, neighbor_fluid) = self.get_block_and_fluid(&neighbor_pos).await;
-
-            if let Some(neighbor_pumpkin_block) =
-                self.block_registry.get_pumpkin_block(neighbor_block)
-            {
-                neighbor_pumpkin_block
-                    .on_neighbor_update(OnNeighborUpdateArgs {
-                        world: self,
-                        block: neighbor_block,
-                        position: &neighbor_pos,
-                        source_block,
-                        notify: false,
-                    })
-                    .await;
-            }
-
-            if let Some(neighbor_pumpkin_fluid) =
-                self.block_registry.get_pumpkin_fluid(neighbor_fluid)
-            {
-                neighbor_pumpkin_fluid
-                    .on_neighbor_update(self, neighbor_fluid, &neighbor_pos, false)
-                    .await;
-            }
-        }
+        neighbor_update::run(
+            self,
+            NeighborUpdate::neighbors(*block_pos, source_block, except),
+        )
+        .await;
     }
 
     pub async fn update_neighbor(
@@ -2859,20 +3483,117 @@ impl World {
         neighbor_block_pos: &BlockPos,
         source_block: &Block,
     ) {
-        let neighbor_block = self.get_block(neighbor_block_pos).await;
+        neighbor_update::run(
+            self,
+            NeighborUpdate::Neighbor {
+                position: *neighbor_block_pos,
+                source_block: Block::from_id(source_block.id),
+                notify: false,
+            },
+        )
+        .await;
+    }
+
+    /// Tells the block and fluid at `position` that their neighbor `source_block` changed. Only
+    /// called by [`neighbor_update`], which makes sure updates don't recurse.
+    pub(crate) async fn neighbor_changed(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        source_block: &Block,
+        notify: bool,
+    ) {
+        let (block, fluid) = self.get_block_and_fluid(position).await;
 
-        if let Some(neighbor_pumpkin_block) = self.block_registry.get_pumpkin_block(neighbor_block)
+        if !self
+            .allow_block_physics(block, position, source_block)
+            .await
         {
-            neighbor_pumpkin_block
+            return;
+        }
+
+        if let Some(pumpkin_block) = self.block_registry.get_pumpkin_block(block) {
+            pumpkin_block
                 .on_neighbor_update(OnNeighborUpdateArgs {
                     world: self,
-                    block: neighbor_block,
-                    position: neighbor_block_pos,
+                    block,
+                    position,
                     source_block,
-                    notify: false,
+                    notify,
+                })
+                .await;
+        }
+
+        if let Some(pumpkin_fluid) = self.block_registry.get_pumpkin_fluid(fluid) {
+            pumpkin_fluid
+                .on_neighbor_update(self, fluid, position, notify)
+                .await;
+        }
+    }
+
+    /// Fires a [`BlockPhysicsEvent`] for a neighbor update, returning `false` if a plugin cancelled it.
+    async fn allow_block_physics(
+        self: &Arc<Self>,
+        block: &'static Block,
+        position: &BlockPos,
+        source_block: &Block,
+    ) -> bool {
+        if !PLUGIN_MANAGER.has_listeners::<BlockPhysicsEvent>().await {
+            return true;
+        }
+        let event = PLUGIN_MANAGER
+            .fire(BlockPhysicsEvent::new(
+                self.clone(),
+                block,
+                *position,
+                Block::from_id(source_block.id),
+            ))
+            .await;
+        !event.cancelled
+    }
+
+    /// Unloads the given chunks, which should no longer be watched by any player.
+    ///
+    /// Fires a [`ChunkUnload`] event for every chunk; chunks for which it was cancelled stay loaded
+    /// and unloading them is retried every [`RETRY_UNLOAD_INTERVAL`] ticks until it succeeds or a
+    /// player watches them again.
+    pub async fn clean_chunks(self: &Arc<Self>, chunks: &[Vector2<i32>]) {
+        if chunks.is_empty() {
+            return;
+        }
+        self.region_locks.clean();
+        if !PLUGIN_MANAGER.has_listeners::<ChunkUnload>().await {
+            self.level.clean_chunks(chunks).await;
+            return;
+        }
+
+        let mut chunks_to_clean = Vec::with_capacity(chunks.len());
+        let mut retained_chunks = Vec::new();
+        for chunk_position in chunks {
+            let event = PLUGIN_MANAGER
+                .fire(ChunkUnload {
+                    world: self.clone(),
+                    chunk_position: *chunk_position,
+                    cancelled: false,
                 })
                 .await;
+            if event.cancelled {
+                retained_chunks.push(*chunk_position);
+            } else {
+                chunks_to_clean.push(*chunk_position);
+            }
         }
+        self.level.clean_chunks(&chunks_to_clean).await;
+        self.retained_chunks.lock().await.extend(retained_chunks);
+    }
+
+    /// Tries again to unload the chunks a plugin kept loaded, forgetting the ones that are watched
+    /// again, as they are unloaded once no player watches them anymore.
+    async fn retry_chunk_unloads(self: &Arc<Self>) {
+        let chunks: Vec<_> = std::mem::take(&mut *self.retained_chunks.lock().await)
+            .into_iter()
+            .filter(|chunk| !self.level.is_chunk_watched(chunk))
+            .collect();
+        self.clean_chunks(&chunks).await;
     }
 
     pub async fn replace_with_state_for_neighbor_update(
@@ -2883,11 +3604,26 @@ impl World {
     ) {
         let (block, block_state_id) = self.get_block_and_state_id(block_pos).await;
 
+        if let Some(support) = self
+            .block_registry
+            .get_supports(block, block_state_id)
+            .iter()
+            .find(|support| support.direction() == direction)
+            && !support.is_satisfied(self.as_ref(), block_pos).await
+        {
+            self.break_block(block_pos, None, flags & !BlockFlags::SKIP_DROPS)
+                .await;
+            return;
+        }
+
         if flags.contains(BlockFlags::SKIP_REDSTONE_WIRE_STATE_REPLACEMENT)
             && *block == Block::REDSTONE_WIRE
         {
             return;
         }
+        if flags.contains(BlockFlags::SKIP_OBSERVER_UPDATE) && *block == Block::OBSERVER {
+            return;
+        }
 
         let neighbor_pos = block_pos.offset(direction.to_offset());
         let neighbor_state_id = self.get_block_state_id(&neighbor_pos).await;
@@ -2920,7 +3656,7 @@ impl World {
             .level
             .get_chunk(block_pos.chunk_and_chunk_relative_position().0)
             .await;
-        let chunk: tokio::sync::RwLockReadGuard<ChunkData> = chunk.read().await;
+        let chunk: OrderedReadGuard<ChunkData> = chunk.read().await;
 
         chunk.block_entities.get(block_pos).cloned()
     }
@@ -2931,7 +3667,7 @@ impl World {
             .level
             .get_chunk(block_pos.chunk_and_chunk_relative_position().0)
             .await;
-        let mut chunk: tokio::sync::RwLockWriteGuard<ChunkData> = chunk.write().await;
+        let mut chunk: OrderedWriteGuard<ChunkData> = chunk.write().await;
         let block_entity_nbt = block_entity.chunk_data_nbt();
 
         if let Some(nbt) = &block_entity_nbt {
@@ -2954,7 +3690,7 @@ impl World {
             .level
             .get_chunk(block_pos.chunk_and_chunk_relative_position().0)
             .await;
-        let mut chunk: tokio::sync::RwLockWriteGuard<ChunkData> = chunk.write().await;
+        let mut chunk: OrderedWriteGuard<ChunkData> = chunk.write().await;
         chunk.block_entities.remove(block_pos);
         chunk.mark_dirty(true);
     }
@@ -2965,7 +3701,7 @@ impl World {
             .level
             .get_chunk(block_pos.chunk_and_chunk_relative_position().0)
             .await;
-        let mut chunk: tokio::sync::RwLockWriteGuard<ChunkData> = chunk.write().await;
+        let mut chunk: OrderedWriteGuard<ChunkData> = chunk.write().await;
         let block_entity_nbt = block_entity.chunk_data_nbt();
 
         if let Some(nbt) = &block_entity_nbt {
@@ -3040,6 +3776,37 @@ impl World {
         }
     }
 
+    /// Whether the collision shape of a block lies between `from` and `to`, e.g. whether an
+    /// explosion reaches a point.
+    pub async fn is_obstructed(&self, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+        // Steps short enough that only blocks the segment barely clips can be missed
+        let steps = (from.squared_distance_to_vec(to).sqrt() / 0.2)
+            .ceil()
+            .max(1.0) as usize;
+        let mut last_pos = None;
+        for step in 0..=steps {
+            let point = from.lerp(&to, step as f64 / steps as f64);
+            let pos = BlockPos::floored(point.x, point.y, point.z);
+            if last_pos == Some(pos) {
+                continue;
+            }
+            last_pos = Some(pos);
+
+            let state = self.get_block_state(&pos).await;
+            if state.is_air() {
+                continue;
+            }
+            for shape in state.get_block_collision_shapes() {
+                let min = shape.min.add(&pos.0.to_f64());
+                let max = shape.max.add(&pos.0.to_f64());
+                if Self::intersects_aabb_with_direction(from, to, min, max).is_some() {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     async fn ray_outline_check(
         self: &Arc<Self>,
         block_pos: &BlockPos,
@@ -3216,13 +3983,44 @@ impl pumpkin_world::world::SimpleWorld for World {
         self.sync_world_event(world_event, position, data).await;
     }
 
+    async fn play_jukebox_song(&self, position: BlockPos, song: &str) {
+        let song = song.strip_prefix("minecraft:").unwrap_or(song);
+        let Some(song) = SYNCED_REGISTRIES.jukebox_song.get_index_of(song) else {
+            log::error!("Jukebox playable song not registered!");
+            return;
+        };
+        self.sync_world_event(WorldEvent::JukeboxStartsPlaying, position, song as i32)
+            .await;
+        self.emit_game_event(&pumpkin_data::game_event::GameEvent::JukeboxPlay, &position)
+            .await;
+    }
+
     async fn spawn_from_type(
         self: Arc<Self>,
         entity_type: &'static EntityType,
         position: Vector3<f64>,
-    ) {
+    ) -> bool {
         let mob = from_type(entity_type, position, &self, Uuid::new_v4()).await;
+        let Some(position) = self.find_spawn_position(mob.as_ref(), position).await else {
+            return false;
+        };
+        mob.get_entity().set_pos(position);
         self.spawn_entity(mob).await;
+        true
+    }
+
+    async fn spawn_from_nbt(self: Arc<Self>, nbt: &NbtCompound, position: Vector3<f64>) -> bool {
+        let Some(entity_type) = nbt
+            .get_string("id")
+            .and_then(|id| EntityType::from_name(id.strip_prefix("minecraft:").unwrap_or(id)))
+        else {
+            return false;
+        };
+        let entity = from_type(entity_type, position, &self, Uuid::new_v4()).await;
+        entity.read_nbt_non_mut(nbt).await;
+        entity.get_entity().set_pos(position);
+        self.spawn_entity(entity).await;
+        true
     }
 
     async fn remove_block_entity(&self, block_pos: &BlockPos) {
@@ -3233,10 +4031,28 @@ impl pumpkin_world::world::SimpleWorld for World {
         self.get_block_entity(block_pos).await
     }
 
+    async fn get_block_container(
+        self: Arc<Self>,
+        position: &BlockPos,
+    ) -> Option<Arc<dyn Inventory>> {
+        let (block, state) = self.get_block_and_state(position).await;
+        self.block_registry
+            .get_container(&self, block, state, position)
+            .await
+    }
+
     async fn get_world_age(&self) -> i64 {
         self.level_time.lock().await.world_age
     }
 
+    async fn is_night(&self) -> bool {
+        self.level_time.lock().await.is_night()
+    }
+
+    async fn is_raining(&self) -> bool {
+        self.weather.lock().await.raining
+    }
+
     async fn play_sound(&self, sound: Sound, category: SoundCategory, position: &Vector3<f64>) {
         self.play_sound(sound, category, position).await;
     }
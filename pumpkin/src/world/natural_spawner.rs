@@ -9,19 +9,20 @@ use pumpkin_data::tag::Taggable;
 use pumpkin_data::tag::WorldgenBiome::MINECRAFT_REDUCE_WATER_AMBIENT_SPAWNS;
 use pumpkin_data::{Block, BlockDirection, BlockState};
 use pumpkin_util::GameMode;
+use pumpkin_util::lock_order::OrderedRwLock;
 use pumpkin_util::math::boundingbox::{BoundingBox, EntityDimensions};
 use pumpkin_util::math::get_section_cord;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::math::vector3::Vector3;
-use pumpkin_world::chunk::{ChunkData, ChunkHeightmapType};
+use pumpkin_world::chunk::ChunkHeightmapType;
+use pumpkin_world::level::SyncChunk;
 use rand::seq::IndexedRandom;
 use rand::{Rng, rng};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::fmt;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 use uuid::Uuid;
 
 const MAGIC_NUMBER: i32 = 17 * 17;
@@ -189,7 +190,7 @@ impl fmt::Debug for SpawnState {
 impl SpawnState {
     pub async fn new(
         chunk_count: i32,
-        entities: &Arc<RwLock<HashMap<Uuid, Arc<dyn EntityBase>>>>,
+        entities: &Arc<OrderedRwLock<HashMap<Uuid, Arc<dyn EntityBase>>>>,
         world: &Arc<World>,
     ) -> Self {
         let mut potential = PotentialCalculator::default();
@@ -312,7 +313,7 @@ pub fn get_filtered_spawning_categories(
 pub async fn spawn_for_chunk(
     world: &Arc<World>,
     chunk_pos: &Vector2<i32>,
-    chunk: &Arc<RwLock<ChunkData>>,
+    chunk: &SyncChunk,
     spawn_state: &mut SpawnState,
     spawn_list: &Vec<&'static MobCategory>,
 ) {
@@ -334,7 +335,7 @@ pub async fn spawn_for_chunk(
 pub async fn get_random_pos_within(
     min_y: i32,
     chunk_pos: &Vector2<i32>,
-    chunk: &Arc<RwLock<ChunkData>>,
+    chunk: &SyncChunk,
 ) -> BlockPos {
     let x = (chunk_pos.x << 4) + rng().random_range(0..16);
     let z = (chunk_pos.y << 4) + rng().random_range(0..16);
@@ -522,6 +523,9 @@ pub async fn is_valid_spawn_position_for_type(
     if !is_spawn_position_ok(world, block_pos, entity_type).await {
         return false;
     }
+    if !is_light_ok_for_spawn(world, block_pos, category).await {
+        return false;
+    }
     // TODO: we should use getSpawnBox, but this is only modified for slimes and magma slimes
     world
         .is_space_empty(BoundingBox::new_from_pos(
@@ -536,6 +540,21 @@ pub async fn is_valid_spawn_position_for_type(
         .await
 }
 
+/// The light rules of the category: monsters only spawn in the dark and animals in the light.
+pub async fn is_light_ok_for_spawn(
+    world: &Arc<World>,
+    block_pos: &BlockPos,
+    category: &'static MobCategory,
+) -> bool {
+    if category == &MobCategory::MONSTER {
+        world.is_dark_enough_to_spawn(block_pos).await
+    } else if category == &MobCategory::CREATURE {
+        world.is_bright_enough_to_spawn(block_pos).await
+    } else {
+        true
+    }
+}
+
 pub async fn is_spawn_position_ok(
     world: &Arc<World>,
     block_pos: &BlockPos,
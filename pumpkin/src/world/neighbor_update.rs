@@ -0,0 +1,167 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use pumpkin_config::advanced_config;
+use pumpkin_data::{Block, BlockDirection};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::world::BlockFlags;
+
+use super::World;
+
+tokio::task_local! {
+    static UPDATER: RefCell<CollectingNeighborUpdater>;
+}
+
+/// A pending neighbor or shape update.
+pub enum NeighborUpdate {
+    /// Tells the block at `position` that its neighbor `source_block` changed.
+    Neighbor {
+        position: BlockPos,
+        source_block: &'static Block,
+        notify: bool,
+    },
+    /// Tells every neighbor of `position` except the one in `except` that `source_block` changed,
+    /// one neighbor per step.
+    Neighbors {
+        position: BlockPos,
+        source_block: &'static Block,
+        except: Option<BlockDirection>,
+        next: usize,
+    },
+    /// Updates the shape of the block at `position` for the change of its neighbor in `direction`.
+    Shape {
+        position: BlockPos,
+        direction: BlockDirection,
+        flags: BlockFlags,
+    },
+}
+
+impl NeighborUpdate {
+    #[must_use]
+    pub fn neighbors(
+        position: BlockPos,
+        source_block: &'static Block,
+        except: Option<BlockDirection>,
+    ) -> Self {
+        let next = usize::from(except == Some(BlockDirection::update_order()[0]));
+        Self::Neighbors {
+            position,
+            source_block,
+            except,
+            next,
+        }
+    }
+
+    /// Runs the next step of this update, returning whether there are steps left.
+    async fn run_next(&mut self, world: &Arc<World>) -> bool {
+        match self {
+            Self::Neighbor {
+                position,
+                source_block,
+                notify,
+            } => {
+                world
+                    .neighbor_changed(position, *source_block, *notify)
+                    .await;
+                false
+            }
+            Self::Neighbors {
+                position,
+                source_block,
+                except,
+                next,
+            } => {
+                let order = BlockDirection::update_order();
+                let neighbor_pos = position.offset(order[*next].to_offset());
+                *next += 1;
+                if *next < order.len() && *except == Some(order[*next]) {
+                    *next += 1;
+                }
+                world
+                    .neighbor_changed(&neighbor_pos, *source_block, false)
+                    .await;
+                *next < order.len()
+            }
+            Self::Shape {
+                position,
+                direction,
+                flags,
+            } => {
+                world
+                    .replace_with_state_for_neighbor_update(position, *direction, *flags)
+                    .await;
+                false
+            }
+        }
+    }
+}
+
+/// Runs neighbor updates from an explicit stack instead of recursing into them, like vanilla's
+/// `CollectingNeighborUpdater`.
+///
+/// Updates queued while another update runs are collected and run once the current step finished,
+/// before the rest of the update that queued them, so the order matches recursive updates. The
+/// updater belongs to the task that started it, so block changes made concurrently, e.g. by
+/// different players, are counted separately.
+#[derive(Default)]
+struct CollectingNeighborUpdater {
+    stack: Vec<NeighborUpdate>,
+    added_this_layer: Vec<NeighborUpdate>,
+    /// How many updates were queued since the updater started
+    count: usize,
+}
+
+impl CollectingNeighborUpdater {
+    fn add(&mut self, update: NeighborUpdate, max_chained: usize) {
+        if self.count >= max_chained {
+            if self.count == max_chained {
+                log::warn!("Too many chained neighbor updates. Skipping the rest.");
+            }
+            self.count = self.count.saturating_add(1);
+            return;
+        }
+        self.count += 1;
+        self.added_this_layer.push(update);
+    }
+
+    /// Moves the updates added by the last step onto the stack and takes the topmost update.
+    fn next_update(&mut self) -> Option<NeighborUpdate> {
+        // The first added update ends up on top
+        self.stack.extend(self.added_this_layer.drain(..).rev());
+        self.stack.pop()
+    }
+}
+
+/// Queues `update` on the updater of the current task. If the task is not running updates yet,
+/// `update` and everything it chains into is run before this returns.
+///
+/// Updates beyond [`pumpkin_config::TickConfig::max_chained_neighbor_updates`] are skipped.
+pub async fn run(world: &Arc<World>, update: NeighborUpdate) {
+    let max_chained = advanced_config().tick.max_chained_neighbor_updates;
+    if UPDATER.try_with(|_| ()).is_ok() {
+        UPDATER.with(|updater| updater.borrow_mut().add(update, max_chained));
+        return;
+    }
+
+    let mut updater = CollectingNeighborUpdater::default();
+    updater.add(update, max_chained);
+    UPDATER
+        .scope(RefCell::new(updater), run_updates(world))
+        .await;
+}
+
+async fn run_updates(world: &Arc<World>) {
+    while let Some(mut update) = UPDATER.with(|updater| updater.borrow_mut().next_update()) {
+        loop {
+            // Boxed, as running an update can queue new ones through this module again
+            let has_next = Box::pin(update.run_next(world)).await;
+            if !has_next {
+                break;
+            }
+            if UPDATER.with(|updater| !updater.borrow().added_this_layer.is_empty()) {
+                UPDATER.with(|updater| updater.borrow_mut().stack.push(update));
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    data_component_impl::EquipmentSlot,
+    entity::EntityType,
+    item::Item,
+    tag::{Taggable, WorldgenBiome::MINECRAFT_WITHOUT_PATROL_SPAWNS},
+};
+use pumpkin_util::{Difficulty, math::position::BlockPos};
+use pumpkin_world::{chunk::ChunkHeightmapType::MotionBlockingNoLeaves, item::ItemStack};
+use rand::{Rng, rng, seq::IteratorRandom};
+use uuid::Uuid;
+
+use super::{World, natural_spawner::is_spawn_position_ok, special_spawner::SpecialSpawner};
+use crate::entity::{EntityBase, r#type::from_type};
+
+/// Minimum ticks between two patrol attempts. Up to `ATTEMPT_INTERVAL_SPREAD` more are added.
+const ATTEMPT_INTERVAL: i32 = 12_000;
+const ATTEMPT_INTERVAL_SPREAD: i32 = 1200;
+/// Patrols only show up once the world has been running for this many days.
+const FIRST_PATROL_DAY: i64 = 5;
+
+/// Spawns pillager patrols led by a captain some distance away from a random player during the day.
+pub struct PatrolSpawner {
+    cooldown: i32,
+}
+
+impl Default for PatrolSpawner {
+    fn default() -> Self {
+        Self {
+            cooldown: ATTEMPT_INTERVAL,
+        }
+    }
+}
+
+#[async_trait]
+impl SpecialSpawner for PatrolSpawner {
+    async fn tick(&mut self, world: &Arc<World>) {
+        let difficulty = {
+            let level_info = world.level_info.read().await;
+            if !level_info.game_rules.do_patrol_spawning
+                || !level_info.game_rules.do_mob_spawning
+                || level_info.difficulty == Difficulty::Peaceful
+            {
+                return;
+            }
+            level_info.difficulty
+        };
+
+        self.cooldown -= 1;
+        if self.cooldown > 0 {
+            return;
+        }
+        self.cooldown += ATTEMPT_INTERVAL + rng().random_range(0..ATTEMPT_INTERVAL_SPREAD);
+
+        {
+            let level_time = world.level_time.lock().await;
+            if level_time.query_day() < FIRST_PATROL_DAY || level_time.is_night() {
+                return;
+            }
+        }
+        if rng().random_range(0..5) != 0 {
+            return;
+        }
+
+        let Some(player) = world
+            .players
+            .read()
+            .await
+            .values()
+            .choose(&mut rng())
+            .cloned()
+        else {
+            return;
+        };
+        if player.is_spectator() {
+            return;
+        }
+        let player_pos = player.living_entity.entity.block_pos.load();
+        if world.is_near_occupied_poi(&player_pos).await {
+            return;
+        }
+
+        let offset_x = Self::random_offset();
+        let offset_z = Self::random_offset();
+        let mut pos = BlockPos::new(
+            player_pos.0.x + offset_x,
+            player_pos.0.y,
+            player_pos.0.z + offset_z,
+        );
+        let (chunk_pos, _) = pos.chunk_and_chunk_relative_position();
+        if world.level.try_get_chunk(&chunk_pos).is_none() {
+            return;
+        }
+        if world
+            .level
+            .get_rough_biome(&pos)
+            .await
+            .is_tagged_with_by_tag(&MINECRAFT_WITHOUT_PATROL_SPAWNS)
+        {
+            return;
+        }
+
+        // TODO: Scale with the local difficulty instead
+        let patrol_size = difficulty as i32 + 1;
+        for i in 0..patrol_size {
+            pos.0.y = world
+                .get_height(MotionBlockingNoLeaves, pos.0.x, pos.0.z)
+                .await
+                + 1;
+            let spawned = Self::spawn_pillager(world, pos, i == 0).await;
+            if i == 0 && !spawned {
+                break;
+            }
+            let (offset_x, offset_z) = {
+                let mut rng = rng();
+                (
+                    rng.random_range(0..5) - rng.random_range(0..5),
+                    rng.random_range(0..5) - rng.random_range(0..5),
+                )
+            };
+            pos.0.x += offset_x;
+            pos.0.z += offset_z;
+        }
+    }
+}
+
+impl PatrolSpawner {
+    /// Between 24 and 47 blocks in either direction.
+    fn random_offset() -> i32 {
+        let mut rng = rng();
+        let offset = 24 + rng.random_range(0..24);
+        if rng.random_bool(0.5) {
+            -offset
+        } else {
+            offset
+        }
+    }
+
+    async fn spawn_pillager(world: &Arc<World>, pos: BlockPos, captain: bool) -> bool {
+        // TODO: Only spawn in the dark
+        if !is_spawn_position_ok(world, &pos, &EntityType::PILLAGER).await {
+            return false;
+        }
+        let pillager = from_type(&EntityType::PILLAGER, pos.to_f64(), world, Uuid::new_v4()).await;
+        world.spawn_entity(pillager.clone()).await;
+
+        // TODO: Walk towards a random patrol target
+        if captain && let Some(living) = pillager.get_living_entity() {
+            living
+                .equip(EquipmentSlot::HEAD, ItemStack::new(1, &Item::WHITE_BANNER))
+                .await;
+        }
+        true
+    }
+}
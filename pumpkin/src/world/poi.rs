@@ -0,0 +1,149 @@
+use pumpkin_util::math::{
+    position::{BlockPos, chunk_section_from_pos},
+    vector2::Vector2,
+};
+use pumpkin_world::poi::{PoiRecord, PoiType};
+
+use super::World;
+
+impl World {
+    /// Returns every point of interest within `radius` blocks (horizontally) of `center` matching
+    /// `predicate`. Chunks in range are loaded if needed.
+    pub async fn get_pois_in_range(
+        &self,
+        center: &BlockPos,
+        radius: i32,
+        predicate: impl Fn(&PoiRecord) -> bool,
+    ) -> Vec<PoiRecord> {
+        self.collect_pois(center, radius, predicate, true).await
+    }
+
+    /// Like `get_pois_in_range`, but only looks at chunks which are already loaded.
+    pub async fn get_loaded_pois_in_range(
+        &self,
+        center: &BlockPos,
+        radius: i32,
+        predicate: impl Fn(&PoiRecord) -> bool,
+    ) -> Vec<PoiRecord> {
+        self.collect_pois(center, radius, predicate, false).await
+    }
+
+    async fn collect_pois(
+        &self,
+        center: &BlockPos,
+        radius: i32,
+        predicate: impl Fn(&PoiRecord) -> bool,
+        load_chunks: bool,
+    ) -> Vec<PoiRecord> {
+        let min_chunk_x = (center.0.x - radius) >> 4;
+        let max_chunk_x = (center.0.x + radius) >> 4;
+        let min_chunk_z = (center.0.z - radius) >> 4;
+        let max_chunk_z = (center.0.z + radius) >> 4;
+
+        let mut pois = Vec::new();
+        for chunk_x in min_chunk_x..=max_chunk_x {
+            for chunk_z in min_chunk_z..=max_chunk_z {
+                let chunk_pos = Vector2::new(chunk_x, chunk_z);
+                let chunk = if load_chunks {
+                    self.level.get_chunk(chunk_pos).await
+                } else if let Some(chunk) = self.level.try_get_chunk(&chunk_pos) {
+                    chunk
+                } else {
+                    continue;
+                };
+                let chunk = chunk.read().await;
+                pois.extend(
+                    chunk
+                        .poi
+                        .iter()
+                        .filter(|record| {
+                            (record.pos.0.x - center.0.x).abs() <= radius
+                                && (record.pos.0.z - center.0.z).abs() <= radius
+                                && predicate(record)
+                        })
+                        .cloned(),
+                );
+            }
+        }
+        pois
+    }
+
+    /// Finds the closest point of interest of a type matching `poi_type` within `radius` blocks of
+    /// `center`. Only POIs that can still be claimed are considered when `only_free` is set.
+    pub async fn find_nearest_poi(
+        &self,
+        center: &BlockPos,
+        radius: i32,
+        poi_type: impl Fn(PoiType) -> bool,
+        only_free: bool,
+    ) -> Option<BlockPos> {
+        self.get_pois_in_range(center, radius, |record| {
+            poi_type(record.poi_type) && (!only_free || record.is_free())
+        })
+        .await
+        .into_iter()
+        .map(|record| record.pos)
+        .min_by_key(|pos| pos.squared_distance(*center))
+    }
+
+    /// Claims the closest free point of interest of a type matching `poi_type`, e.g. a villager
+    /// claiming a bed or a job site block. The ticket has to be given back using `release_poi`.
+    pub async fn acquire_poi(
+        &self,
+        center: &BlockPos,
+        radius: i32,
+        poi_type: impl Fn(PoiType) -> bool,
+    ) -> Option<BlockPos> {
+        let mut candidates = self
+            .get_pois_in_range(center, radius, |record| {
+                poi_type(record.poi_type) && record.free_tickets > 0
+            })
+            .await;
+        candidates.sort_unstable_by_key(|record| record.pos.squared_distance(*center));
+
+        for candidate in candidates {
+            let (chunk_pos, _) = candidate.pos.chunk_and_chunk_relative_position();
+            let chunk = self.level.get_chunk(chunk_pos).await;
+            let mut chunk = chunk.write().await;
+            // Someone else may have claimed the last ticket in the meantime
+            if chunk.poi.acquire_ticket(&candidate.pos) {
+                chunk.dirty = true;
+                return Some(candidate.pos);
+            }
+        }
+        None
+    }
+
+    /// Gives back a ticket taken with `acquire_poi`.
+    pub async fn release_poi(&self, pos: &BlockPos) -> bool {
+        let (chunk_pos, _) = pos.chunk_and_chunk_relative_position();
+        let chunk = self.level.get_chunk(chunk_pos).await;
+        let mut chunk = chunk.write().await;
+        let released = chunk.poi.release_ticket(pos);
+        if released {
+            chunk.dirty = true;
+        }
+        released
+    }
+
+    /// Whether a claimed point of interest is in the chunk section of `pos` or a neighbouring
+    /// section. This is how villages are detected.
+    pub async fn is_near_occupied_poi(&self, pos: &BlockPos) -> bool {
+        let section = chunk_section_from_pos(pos);
+        let section_center = BlockPos::new(
+            (section.x << 4) + 8,
+            (section.y << 4) + 8,
+            (section.z << 4) + 8,
+        );
+        !self
+            .get_pois_in_range(&section_center, 24, |record| {
+                let poi_section = chunk_section_from_pos(&record.pos);
+                (poi_section.x - section.x).abs() <= 1
+                    && (poi_section.y - section.y).abs() <= 1
+                    && (poi_section.z - section.z).abs() <= 1
+                    && record.is_occupied()
+            })
+            .await
+            .is_empty()
+    }
+}
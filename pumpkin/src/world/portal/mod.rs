@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
-use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
 use super::World;
+use crate::entity::EntityBase;
 
 pub mod end;
 pub mod nether;
@@ -15,6 +16,15 @@ pub struct PortalManager {
     pub in_portal: bool,
 }
 
+/// A teleport through a portal, or of a player to where their ender pearl landed. Teleports are
+/// queued while the world ticks its entities and applied afterwards, so no entity changes worlds
+/// while others are still being ticked.
+pub struct PortalTeleport {
+    pub entity: Arc<dyn EntityBase>,
+    pub world: Arc<World>,
+    pub position: Vector3<f64>,
+}
+
 impl PortalManager {
     pub fn new(portal_delay: u32, portal_world: Arc<World>, pos: BlockPos) -> Self {
         Self {
@@ -6,8 +6,9 @@ use pumpkin_data::{
     tag,
     tag::Taggable,
 };
+use pumpkin_registry::VanillaDimensionType;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::world::BlockFlags;
+use pumpkin_world::{poi::PoiType, world::BlockFlags};
 
 use crate::world::World;
 
@@ -65,6 +66,23 @@ impl NetherPortal {
         }
     }
 
+    /// Finds an existing portal near `pos` to link to, searching 16 blocks in the nether and 128
+    /// blocks in the overworld. Returns the bottom portal block of the closest portal.
+    pub async fn find_linked_portal(world: &World, pos: &BlockPos) -> Option<BlockPos> {
+        let radius = if world.dimension_type == VanillaDimensionType::TheNether {
+            16
+        } else {
+            128
+        };
+        let mut portal = world
+            .find_nearest_poi(pos, radius, |poi| poi == PoiType::NetherPortal, false)
+            .await?;
+        while world.get_block(&portal.down()).await == &Block::NETHER_PORTAL {
+            portal = portal.down();
+        }
+        Some(portal)
+    }
+
     pub async fn get_new_portal(
         world: &World,
         pos: &BlockPos,
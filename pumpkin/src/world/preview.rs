@@ -0,0 +1,280 @@
+//! `pumpkin gen-preview`: generates the chunks around the origin without starting a server and
+//! renders them as a top-down map, coloured by biome and shaded by height.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use flate2::{Compression, Crc, write::ZlibEncoder};
+use pumpkin_data::biome::Biome;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::{chunk::ChunkHeightmapType, dimension::Dimension};
+
+use crate::block::registry::default_registry;
+
+/// The name of the subcommand on the command line.
+pub const SUBCOMMAND: &str = "gen-preview";
+const USAGE: &str = "Usage: pumpkin gen-preview [--seed N] [--radius R] [--out map.png]";
+
+/// Chunks generated in each direction from the origin when no radius is given.
+const DEFAULT_RADIUS: u32 = 8;
+/// Larger previews take very long to generate and are better explored in game.
+const MAX_RADIUS: u32 = 128;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PreviewOptions {
+    pub seed: i64,
+    /// Chunks generated in each direction from the origin.
+    pub radius: u32,
+    pub out: PathBuf,
+}
+
+impl PreviewOptions {
+    /// Parses the arguments following the subcommand.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            seed: 0,
+            radius: DEFAULT_RADIUS,
+            out: PathBuf::from("preview.png"),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--seed" => {
+                    let seed = value()?;
+                    options.seed = seed
+                        .parse()
+                        .map_err(|_| format!("Invalid seed {seed}, expected a number"))?;
+                }
+                "--radius" => {
+                    let radius = value()?;
+                    options.radius = radius
+                        .parse()
+                        .ok()
+                        .filter(|radius| *radius <= MAX_RADIUS)
+                        .ok_or_else(|| {
+                            format!("Invalid radius {radius}, expected 0 to {MAX_RADIUS} chunks")
+                        })?;
+                }
+                "--out" => options.out = PathBuf::from(value()?),
+                _ => return Err(format!("Unknown argument {arg}\n{USAGE}")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Runs the subcommand with the arguments following it, logging what went wrong on failure.
+pub async fn run(args: impl IntoIterator<Item = String>) -> bool {
+    let options = match PreviewOptions::parse(args) {
+        Ok(options) => options,
+        Err(error) => {
+            log::error!("{error}");
+            return false;
+        }
+    };
+    if let Err(error) = render(&options).await {
+        log::error!("Failed to write {}: {error}", options.out.display());
+        return false;
+    }
+    log::info!("Wrote the preview to {}", options.out.display());
+    true
+}
+
+async fn render(options: &PreviewOptions) -> io::Result<()> {
+    // The level needs a folder, but nothing generated here is ever saved
+    let folder = std::env::temp_dir().join(format!("pumpkin-gen-preview-{}", std::process::id()));
+    let level = Dimension::Overworld.into_level(folder.clone(), default_registry(), options.seed);
+
+    let radius = options.radius as i32;
+    let size = (options.radius as usize * 2 + 1) * 16;
+    let positions: Vec<_> = (-radius..=radius)
+        .flat_map(|x| (-radius..=radius).map(move |z| Vector2::new(x, z)))
+        .collect();
+    let total = positions.len();
+    log::info!(
+        "Generating {total} chunks around the origin with seed {}",
+        options.seed
+    );
+
+    let mut pixels = vec![0; size * size * 3];
+    let mut receiver = level.receive_chunks(positions);
+    let mut done = 0;
+    while let Some((chunk, _)) = receiver.recv().await {
+        let chunk = chunk.read().await;
+        let min_y = chunk.section.min_y;
+        let max_y = min_y + chunk.section.sections.len() as i32 * 16;
+        for z in 0..16 {
+            for x in 0..16 {
+                let height =
+                    chunk
+                        .heightmap
+                        .get_height(ChunkHeightmapType::WorldSurface, x, z, min_y);
+                let biome = chunk
+                    .section
+                    .get_rough_biome_absolute_y(x as usize, height.max(min_y), z as usize)
+                    .and_then(Biome::from_id);
+                let color = shade(biome_color(biome), height, min_y, max_y);
+
+                let pixel_x = ((chunk.position.x + radius) * 16 + x) as usize;
+                let pixel_z = ((chunk.position.y + radius) * 16 + z) as usize;
+                let index = (pixel_z * size + pixel_x) * 3;
+                pixels[index..index + 3].copy_from_slice(&color);
+            }
+        }
+
+        done += 1;
+        if done % 64 == 0 {
+            log::info!("Generated {done}/{total} chunks");
+        }
+    }
+
+    let result = write_png(
+        &mut BufWriter::new(File::create(&options.out)?),
+        size as u32,
+        size as u32,
+        &pixels,
+    );
+    let _ = fs::remove_dir_all(folder);
+    result
+}
+
+/// A rough colour for the kind of terrain a biome has, grey for unknown biomes.
+fn biome_color(biome: Option<&Biome>) -> [u8; 3] {
+    let Some(biome) = biome else {
+        return [128, 128, 128];
+    };
+    let name = biome.registry_id;
+    let has = |part: &str| name.contains(part);
+    if has("deep") && has("ocean") {
+        [24, 48, 128]
+    } else if has("ocean") || has("river") {
+        [48, 96, 200]
+    } else if has("frozen") || has("snowy") || has("ice") || has("peaks") || has("grove") {
+        [236, 240, 244]
+    } else if has("desert") || has("beach") {
+        [220, 208, 150]
+    } else if has("badlands") {
+        [200, 112, 56]
+    } else if has("mushroom") {
+        [160, 112, 168]
+    } else if has("jungle") || has("bamboo") {
+        [48, 168, 32]
+    } else if has("swamp") || has("mangrove") {
+        [72, 96, 48]
+    } else if has("savanna") {
+        [168, 160, 72]
+    } else if has("dark_forest") || has("taiga") {
+        [40, 88, 48]
+    } else if has("forest") || has("cherry") {
+        [56, 128, 48]
+    } else if has("stony") || has("windswept") || has("dripstone") {
+        [128, 128, 120]
+    } else if has("plains") || has("meadow") {
+        [112, 176, 72]
+    } else {
+        [96, 144, 80]
+    }
+}
+
+/// Darkens low and brightens high terrain, so hills and valleys show up.
+fn shade(color: [u8; 3], height: i32, min_y: i32, max_y: i32) -> [u8; 3] {
+    let relative = f64::from(height - min_y) / f64::from((max_y - min_y).max(1));
+    let brightness = 0.5 + relative.clamp(0.0, 1.0);
+    color.map(|channel| (f64::from(channel) * brightness).min(255.0) as u8)
+}
+
+/// Writes an 8-bit RGB PNG of the given size. `pixels` holds the rows from top to bottom.
+fn write_png(out: &mut impl Write, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+    const BIT_DEPTH: u8 = 8;
+    const COLOR_TYPE_RGB: u8 = 2;
+
+    out.write_all(&SIGNATURE)?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // No compression, filter or interlace method other than the default exists
+    header.extend_from_slice(&[BIT_DEPTH, COLOR_TYPE_RGB, 0, 0, 0]);
+    write_png_chunk(out, *b"IHDR", &header)?;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks_exact(width as usize * 3) {
+        // Every row starts with the filter it uses, none here
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    write_png_chunk(out, *b"IDAT", &encoder.finish()?)?;
+    write_png_chunk(out, *b"IEND", &[])?;
+    out.flush()
+}
+
+fn write_png_chunk(out: &mut impl Write, kind: [u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(&kind)?;
+    out.write_all(data)?;
+    let mut crc = Crc::new();
+    crc.update(&kind);
+    crc.update(data);
+    out.write_all(&crc.sum().to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parse_options() {
+        assert_eq!(
+            PreviewOptions::parse(args(&[
+                "--seed", "-42", "--radius", "3", "--out", "map.png"
+            ])),
+            Ok(PreviewOptions {
+                seed: -42,
+                radius: 3,
+                out: PathBuf::from("map.png"),
+            })
+        );
+        assert_eq!(
+            PreviewOptions::parse(args(&[])).map(|options| options.radius),
+            Ok(DEFAULT_RADIUS)
+        );
+        assert!(PreviewOptions::parse(args(&["--seed"])).is_err());
+        assert!(PreviewOptions::parse(args(&["--seed", "pumpkin"])).is_err());
+        assert!(PreviewOptions::parse(args(&["--radius", "1000"])).is_err());
+        assert!(PreviewOptions::parse(args(&["--size", "3"])).is_err());
+    }
+
+    #[test]
+    fn png_layout() {
+        let pixels = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+        let mut png = Vec::new();
+        write_png(&mut png, 2, 2, &pixels).unwrap();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        // IHDR is always 13 bytes long and comes first
+        assert_eq!(&png[8..16], b"\0\0\0\x0dIHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        let idat_len = u32::from_be_bytes(png[33..37].try_into().unwrap()) as usize;
+        assert_eq!(&png[37..41], b"IDAT");
+        let mut rows = Vec::new();
+        ZlibDecoder::new(&png[41..41 + idat_len])
+            .read_to_end(&mut rows)
+            .unwrap();
+        assert_eq!(rows, [0, 255, 0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,514 @@
+use std::sync::Arc;
+
+use pumpkin_data::{
+    data_component_impl::EquipmentSlot, effect::StatusEffect, entity::EntityType, item::Item,
+    potion::Effect,
+};
+use pumpkin_util::{
+    Difficulty,
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    text::TextComponent,
+};
+use rand::{Rng, rng};
+use uuid::Uuid;
+
+use super::{
+    World,
+    bossbar::{Bossbar, BossbarColor, BossbarDivisions},
+};
+use crate::entity::{EntityBase, living::LivingEntity, player::Player, r#type::from_type};
+
+/// Players within this distance of the raid center see the raid bar.
+const RAID_RADIUS: f64 = 96.0;
+/// Ticks between two waves.
+const PRE_WAVE_TICKS: i32 = 300;
+/// Raids that take longer than this are stopped.
+const MAX_RAID_TICKS: i64 = 48_000;
+/// Ticks the raid bar stays visible after a victory or defeat.
+const CELEBRATION_TICKS: i32 = 600;
+const BAD_OMEN_DURATION: i32 = 120_000;
+const RAID_OMEN_DURATION: i32 = 600;
+const HERO_OF_THE_VILLAGE_DURATION: i32 = 48_000;
+const MAX_OMEN_AMPLIFIER: u8 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RaiderType {
+    Vindicator,
+    Evoker,
+    Pillager,
+    Witch,
+    Ravager,
+}
+
+impl RaiderType {
+    const ALL: [Self; 5] = [
+        Self::Vindicator,
+        Self::Evoker,
+        Self::Pillager,
+        Self::Witch,
+        Self::Ravager,
+    ];
+
+    const fn entity_type(self) -> &'static EntityType {
+        match self {
+            Self::Vindicator => &EntityType::VINDICATOR,
+            Self::Evoker => &EntityType::EVOKER,
+            Self::Pillager => &EntityType::PILLAGER,
+            Self::Witch => &EntityType::WITCH,
+            Self::Ravager => &EntityType::RAVAGER,
+        }
+    }
+
+    /// How many raiders of this type spawn in each wave, indexed by wave number.
+    const fn wave_counts(self) -> [u8; 8] {
+        match self {
+            Self::Vindicator => [0, 0, 2, 0, 1, 4, 2, 5],
+            Self::Evoker => [0, 0, 0, 0, 0, 1, 1, 2],
+            Self::Pillager => [0, 4, 3, 3, 4, 4, 4, 2],
+            Self::Witch => [0, 0, 0, 0, 3, 0, 0, 1],
+            Self::Ravager => [0, 0, 0, 1, 0, 1, 0, 2],
+        }
+    }
+
+    /// The number of raiders spawned in `wave`. The bonus wave repeats the last regular wave.
+    fn count(self, wave: u8, regular_waves: u8, bonus: bool) -> u8 {
+        let index = if bonus { regular_waves } else { wave };
+        self.wave_counts()[usize::from(index)]
+    }
+
+    /// Extra raiders depending on the difficulty.
+    fn bonus_count(self, difficulty: Difficulty, wave: u8, bonus_wave: bool) -> u8 {
+        let easy = difficulty == Difficulty::Easy;
+        let extra = match self {
+            Self::Vindicator | Self::Pillager => match difficulty {
+                Difficulty::Easy => rng().random_range(0..=1),
+                Difficulty::Normal => 1,
+                _ => 2,
+            },
+            Self::Witch if !easy && wave > 2 && wave != 4 => 1,
+            Self::Ravager if !easy && bonus_wave => 1,
+            _ => 0,
+        };
+        if extra > 0 {
+            rng().random_range(0..=extra)
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RaidStatus {
+    Ongoing,
+    Victory,
+    Defeat,
+    Stopped,
+}
+
+pub struct Raid {
+    pub center: BlockPos,
+    /// Raid omen amplifier + 1
+    pub omen_level: u8,
+    pub status: RaidStatus,
+    waves_spawned: u8,
+    regular_waves: u8,
+    pre_wave_ticks: i32,
+    ticks_active: i64,
+    celebration_ticks: i32,
+    /// Entity ids of the raiders of the current wave
+    raiders: Vec<i32>,
+    wave_size: usize,
+    /// Players that took part in the raid and get rewarded on victory
+    heroes: Vec<Uuid>,
+    bossbar: Bossbar,
+    bossbar_viewers: Vec<Uuid>,
+}
+
+impl Raid {
+    fn new(center: BlockPos, omen_level: u8, difficulty: Difficulty) -> Self {
+        let regular_waves = match difficulty {
+            Difficulty::Peaceful | Difficulty::Easy => 3,
+            Difficulty::Normal => 5,
+            Difficulty::Hard => 7,
+        };
+        let mut bossbar = Bossbar::new(TextComponent::translate("event.minecraft.raid", []));
+        bossbar.color = BossbarColor::Red;
+        bossbar.division = BossbarDivisions::Notches10;
+
+        Self {
+            center,
+            omen_level,
+            status: RaidStatus::Ongoing,
+            waves_spawned: 0,
+            regular_waves,
+            pre_wave_ticks: PRE_WAVE_TICKS,
+            ticks_active: 0,
+            celebration_ticks: 0,
+            raiders: Vec::new(),
+            wave_size: 0,
+            heroes: Vec::new(),
+            bossbar,
+            bossbar_viewers: Vec::new(),
+        }
+    }
+
+    /// A higher omen level adds a bonus wave.
+    fn total_waves(&self) -> u8 {
+        self.regular_waves + u8::from(self.omen_level > 1)
+    }
+
+    fn is_in_raid_area(&self, pos: Vector3<f64>) -> bool {
+        pos.squared_distance_to_vec(self.center.to_centered_f64()) < RAID_RADIUS * RAID_RADIUS
+    }
+
+    async fn tick(&mut self, world: &Arc<World>) {
+        if self.status == RaidStatus::Stopped {
+            return;
+        }
+        self.update_bossbar_viewers(world).await;
+
+        if self.status != RaidStatus::Ongoing {
+            self.celebration_ticks += 1;
+            if self.celebration_ticks >= CELEBRATION_TICKS {
+                self.stop(world).await;
+            }
+            return;
+        }
+
+        self.ticks_active += 1;
+        if self.ticks_active >= MAX_RAID_TICKS
+            || world.level_info.read().await.difficulty == Difficulty::Peaceful
+        {
+            self.stop(world).await;
+            return;
+        }
+
+        if self.ticks_active % 20 == 0 && !world.is_near_occupied_poi(&self.center).await {
+            // The village is gone
+            if self.waves_spawned > 0 {
+                self.finish(world, RaidStatus::Defeat).await;
+            } else {
+                self.stop(world).await;
+            }
+            return;
+        }
+
+        self.remove_dead_raiders(world).await;
+        if self.raiders.is_empty() {
+            if self.waves_spawned >= self.total_waves() {
+                self.finish(world, RaidStatus::Victory).await;
+                return;
+            }
+            if self.pre_wave_ticks > 0 {
+                self.pre_wave_ticks -= 1;
+                self.set_bossbar_title(world, TextComponent::translate("event.minecraft.raid", []))
+                    .await;
+                self.set_bossbar_health(
+                    world,
+                    1.0 - self.pre_wave_ticks as f32 / PRE_WAVE_TICKS as f32,
+                )
+                .await;
+            } else {
+                self.spawn_wave(world).await;
+                self.pre_wave_ticks = PRE_WAVE_TICKS;
+            }
+        } else {
+            let remaining = self.raiders.len();
+            let title = if remaining <= 2 {
+                TextComponent::translate("event.minecraft.raid", [])
+                    .add_text(" - ")
+                    .add_child(TextComponent::translate(
+                        "event.minecraft.raid.raiders_remaining",
+                        [TextComponent::text(remaining.to_string())],
+                    ))
+            } else {
+                TextComponent::translate("event.minecraft.raid", [])
+            };
+            self.set_bossbar_title(world, title).await;
+            self.set_bossbar_health(world, remaining as f32 / self.wave_size.max(1) as f32)
+                .await;
+        }
+    }
+
+    async fn spawn_wave(&mut self, world: &Arc<World>) {
+        let difficulty = world.level_info.read().await.difficulty;
+        let wave = self.waves_spawned + 1;
+        let bonus_wave = wave > self.regular_waves;
+        let spawn_pos = self.find_spawn_position(world).await;
+
+        for raider_type in RaiderType::ALL {
+            let count = raider_type.count(wave, self.regular_waves, bonus_wave)
+                + raider_type.bonus_count(difficulty, wave, bonus_wave);
+            for _ in 0..count {
+                let raider =
+                    from_type(raider_type.entity_type(), spawn_pos, world, Uuid::new_v4()).await;
+                self.raiders.push(raider.get_entity().entity_id);
+                world.spawn_entity(raider).await;
+            }
+        }
+        self.wave_size = self.raiders.len();
+        self.waves_spawned = wave;
+    }
+
+    /// Raiders spawn on the surface about 32 blocks away from the center.
+    async fn find_spawn_position(&self, world: &World) -> Vector3<f64> {
+        let angle = rng().random::<f64>() * std::f64::consts::TAU;
+        let x = self.center.0.x + (angle.cos() * 32.0).floor() as i32 + rng().random_range(0..5);
+        let z = self.center.0.z + (angle.sin() * 32.0).floor() as i32 + rng().random_range(0..5);
+        let y = world.get_top_block(Vector2::new(x, z)).await + 1;
+        BlockPos::new(x, y, z).to_centered_f64()
+    }
+
+    async fn remove_dead_raiders(&mut self, world: &World) {
+        let mut alive = Vec::with_capacity(self.raiders.len());
+        for id in &self.raiders {
+            if let Some(raider) = world.get_entity_by_id(*id).await
+                && raider
+                    .get_living_entity()
+                    .is_none_or(|living| living.health.load() > 0.0)
+            {
+                alive.push(*id);
+            }
+        }
+        self.raiders = alive;
+    }
+
+    async fn finish(&mut self, world: &World, status: RaidStatus) {
+        self.status = status;
+        let title = if status == RaidStatus::Victory {
+            "event.minecraft.raid.victory.full"
+        } else {
+            "event.minecraft.raid.defeat.full"
+        };
+        self.set_bossbar_title(world, TextComponent::translate(title, []))
+            .await;
+        self.set_bossbar_health(world, 0.0).await;
+
+        if status == RaidStatus::Victory {
+            let amplifier = (self.omen_level - 1).min(MAX_OMEN_AMPLIFIER);
+            let players = world.players.read().await;
+            for hero in &self.heroes {
+                if let Some(player) = players.get(hero) {
+                    player
+                        .add_effect(Effect {
+                            effect_type: &StatusEffect::HERO_OF_THE_VILLAGE,
+                            duration: HERO_OF_THE_VILLAGE_DURATION,
+                            amplifier,
+                            ambient: false,
+                            show_particles: false,
+                            show_icon: true,
+                            blend: false,
+                        })
+                        .await;
+                }
+            }
+        }
+    }
+
+    async fn stop(&mut self, world: &World) {
+        self.status = RaidStatus::Stopped;
+        let players = world.players.read().await;
+        for uuid in self.bossbar_viewers.drain(..) {
+            if let Some(player) = players.get(&uuid) {
+                player.remove_bossbar(self.bossbar.uuid).await;
+            }
+        }
+    }
+
+    async fn update_bossbar_viewers(&mut self, world: &World) {
+        let players = world.players.read().await;
+        let mut viewers = Vec::with_capacity(self.bossbar_viewers.len());
+        for player in players.values() {
+            let uuid = player.gameprofile.id;
+            if !self.is_in_raid_area(player.living_entity.entity.pos.load()) {
+                continue;
+            }
+            if !self.bossbar_viewers.contains(&uuid) {
+                player.send_bossbar(&self.bossbar).await;
+            }
+            if self.status == RaidStatus::Ongoing
+                && !player.is_spectator()
+                && !self.heroes.contains(&uuid)
+            {
+                self.heroes.push(uuid);
+            }
+            viewers.push(uuid);
+        }
+        for uuid in &self.bossbar_viewers {
+            if !viewers.contains(uuid)
+                && let Some(player) = players.get(uuid)
+            {
+                player.remove_bossbar(self.bossbar.uuid).await;
+            }
+        }
+        self.bossbar_viewers = viewers;
+    }
+
+    async fn set_bossbar_title(&mut self, world: &World, title: TextComponent) {
+        if self.bossbar.title == title {
+            return;
+        }
+        self.bossbar.title = title;
+        let players = world.players.read().await;
+        for uuid in &self.bossbar_viewers {
+            if let Some(player) = players.get(uuid) {
+                player
+                    .update_bossbar_title(&self.bossbar.uuid, self.bossbar.title.clone())
+                    .await;
+            }
+        }
+    }
+
+    async fn set_bossbar_health(&mut self, world: &World, health: f32) {
+        let health = health.clamp(0.0, 1.0);
+        if (self.bossbar.health - health).abs() < f32::EPSILON {
+            return;
+        }
+        self.bossbar.health = health;
+        let players = world.players.read().await;
+        for uuid in &self.bossbar_viewers {
+            if let Some(player) = players.get(uuid) {
+                player
+                    .update_bossbar_health(&self.bossbar.uuid, health)
+                    .await;
+            }
+        }
+    }
+}
+
+/// The raids of a world.
+#[derive(Default)]
+pub struct RaidManager {
+    raids: Vec<Raid>,
+}
+
+impl RaidManager {
+    pub async fn tick(&mut self, world: &Arc<World>) {
+        for raid in &mut self.raids {
+            raid.tick(world).await;
+        }
+        self.raids.retain(|raid| raid.status != RaidStatus::Stopped);
+    }
+
+    /// Returns the ongoing raid at `pos`, if any.
+    pub fn get_raid_at(&self, pos: &BlockPos) -> Option<&Raid> {
+        self.raids
+            .iter()
+            .find(|raid| raid.is_in_raid_area(pos.to_centered_f64()))
+    }
+
+    /// Starts a raid at `pos` or raises the omen level of the raid already there.
+    pub async fn start_raid(&mut self, world: &World, pos: BlockPos, omen_level: u8) {
+        if world.level_info.read().await.game_rules.disable_raids {
+            return;
+        }
+        let difficulty = world.level_info.read().await.difficulty;
+        if difficulty == Difficulty::Peaceful || !world.is_near_occupied_poi(&pos).await {
+            return;
+        }
+        if let Some(raid) = self
+            .raids
+            .iter_mut()
+            .find(|raid| raid.is_in_raid_area(pos.to_centered_f64()))
+        {
+            if raid.status == RaidStatus::Ongoing {
+                raid.omen_level = raid.omen_level.max(omen_level);
+            }
+            return;
+        }
+        self.raids.push(Raid::new(pos, omen_level, difficulty));
+    }
+}
+
+/// Whether the entity is a raid captain, carrying a banner on its head.
+pub async fn is_raid_captain(living: &LivingEntity) -> bool {
+    if !RaiderType::ALL
+        .iter()
+        .any(|raider| raider.entity_type() == living.entity.entity_type)
+    {
+        return false;
+    }
+    let head = living
+        .entity_equipment
+        .lock()
+        .await
+        .get(&EquipmentSlot::HEAD);
+    head.lock().await.item == &Item::WHITE_BANNER
+}
+
+/// Gives the player that killed a raid captain Bad Omen, or raises its level.
+pub async fn on_raid_captain_killed(player: &Player) {
+    let amplifier = player
+        .living_entity
+        .get_effect(&StatusEffect::BAD_OMEN)
+        .await
+        .map_or(0, |effect| (effect.amplifier + 1).min(MAX_OMEN_AMPLIFIER));
+    player
+        .add_effect(Effect {
+            effect_type: &StatusEffect::BAD_OMEN,
+            duration: BAD_OMEN_DURATION,
+            amplifier,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+            blend: false,
+        })
+        .await;
+}
+
+/// Turns Bad Omen into Raid Omen when the player enters a village, and starts the raid once
+/// Raid Omen runs out.
+pub async fn tick_player_omens(player: &Player) {
+    if player.is_spectator() {
+        return;
+    }
+    let world = player.world();
+    let living = &player.living_entity;
+
+    if let Some(raid_omen) = living.get_effect(&StatusEffect::RAID_OMEN).await {
+        if raid_omen.duration <= 1
+            && let Some(pos) = player.raid_omen_position.take()
+        {
+            world
+                .raids
+                .lock()
+                .await
+                .start_raid(world, pos, raid_omen.amplifier + 1)
+                .await;
+        }
+        return;
+    }
+
+    let Some(bad_omen) = living.get_effect(&StatusEffect::BAD_OMEN).await else {
+        return;
+    };
+    let pos = living.entity.block_pos.load();
+    if world.level_info.read().await.difficulty == Difficulty::Peaceful
+        || !world.is_near_occupied_poi(&pos).await
+    {
+        return;
+    }
+    if world
+        .raids
+        .lock()
+        .await
+        .get_raid_at(&pos)
+        .is_some_and(|raid| raid.omen_level > MAX_OMEN_AMPLIFIER)
+    {
+        return;
+    }
+
+    player.remove_effect(&StatusEffect::BAD_OMEN).await;
+    player.raid_omen_position.store(Some(pos));
+    player
+        .add_effect(Effect {
+            effect_type: &StatusEffect::RAID_OMEN,
+            duration: RAID_OMEN_DURATION,
+            amplifier: bad_omen.amplifier,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+            blend: false,
+        })
+        .await;
+}
@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// A region spans `2^REGION_SHIFT` x `2^REGION_SHIFT` chunks.
+pub const REGION_SHIFT: i32 = 5;
+
+tokio::task_local! {
+    /// The regions locked by the current task.
+    static HELD: RefCell<Vec<Vector2<i32>>>;
+}
+
+/// Locks for areas of a world.
+///
+/// Block changes (`World::set_block_state`, `World::break_block`) and entities being added to or
+/// removed from the world lock the region they happen in, and bulk work (entity shards, edit
+/// sessions, templates) locks all regions it touches. Work in the same regions is serialized while
+/// distant areas are processed concurrently.
+///
+/// The locks are reentrant per task: a task that already holds a region, e.g. while neighbor
+/// updates cascade, does not lock it again. To rule out deadlocks a task only ever waits for a
+/// region that comes after all regions it holds in the canonical order (sorted by x, then z).
+/// Regions before that are only taken if they are free; otherwise the work runs without them.
+#[derive(Default)]
+pub struct RegionLocks {
+    locks: std::sync::Mutex<HashMap<Vector2<i32>, Arc<Mutex<()>>>>,
+}
+
+const fn order(region: &Vector2<i32>) -> (i32, i32) {
+    (region.x, region.y)
+}
+
+impl RegionLocks {
+    #[must_use]
+    pub const fn region_of_chunk(chunk: Vector2<i32>) -> Vector2<i32> {
+        Vector2::new(chunk.x >> REGION_SHIFT, chunk.y >> REGION_SHIFT)
+    }
+
+    #[must_use]
+    pub fn region_of_block(pos: &BlockPos) -> Vector2<i32> {
+        let (chunk, _) = pos.chunk_and_chunk_relative_position();
+        Self::region_of_chunk(chunk)
+    }
+
+    /// Every region intersecting the box spanned by `from` and `to`.
+    pub fn regions_in_area(from: &BlockPos, to: &BlockPos) -> impl Iterator<Item = Vector2<i32>> {
+        let from_region = Self::region_of_block(from);
+        let to_region = Self::region_of_block(to);
+        let (min_x, max_x) = (
+            from_region.x.min(to_region.x),
+            from_region.x.max(to_region.x),
+        );
+        let (min_z, max_z) = (
+            from_region.y.min(to_region.y),
+            from_region.y.max(to_region.y),
+        );
+        (min_x..=max_x).flat_map(move |x| (min_z..=max_z).map(move |z| Vector2::new(x, z)))
+    }
+
+    fn lock_for(&self, region: Vector2<i32>) -> Arc<Mutex<()>> {
+        self.locks
+            .lock()
+            .unwrap()
+            .entry(region)
+            .or_default()
+            .clone()
+    }
+
+    /// Runs `future` while holding the locks of all given regions.
+    pub async fn run_locked<F: Future>(
+        &self,
+        regions: impl IntoIterator<Item = Vector2<i32>>,
+        future: F,
+    ) -> F::Output {
+        let mut regions: Vec<_> = regions.into_iter().collect();
+        regions.sort_unstable_by_key(order);
+        regions.dedup();
+
+        if HELD.try_with(|_| ()).is_err() {
+            let mut guards = Vec::with_capacity(regions.len());
+            for region in &regions {
+                guards.push(self.lock_for(*region).lock_owned().await);
+            }
+            let output = HELD.scope(RefCell::new(regions), future).await;
+            drop(guards);
+            return output;
+        }
+
+        let (locked, guards) = self.lock_missing(regions).await;
+        HELD.with(|held| held.borrow_mut().extend_from_slice(&locked));
+        let output = future.await;
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            for region in &locked {
+                if let Some(index) = held.iter().rposition(|held| held == region) {
+                    held.swap_remove(index);
+                }
+            }
+        });
+        drop(guards);
+        output
+    }
+
+    /// Runs `future` while holding the locks of every region intersecting the box spanned by
+    /// `from` and `to`.
+    pub async fn run_locked_area<F: Future>(
+        &self,
+        from: &BlockPos,
+        to: &BlockPos,
+        future: F,
+    ) -> F::Output {
+        self.run_locked(Self::regions_in_area(from, to), future)
+            .await
+    }
+
+    /// Locks those of the sorted `regions` the current task does not hold yet, returning the newly
+    /// locked regions and their guards.
+    async fn lock_missing(
+        &self,
+        regions: Vec<Vector2<i32>>,
+    ) -> (Vec<Vector2<i32>>, Vec<OwnedMutexGuard<()>>) {
+        let (missing, last_held): (Vec<_>, _) = HELD.with(|held| {
+            let held = held.borrow();
+            let missing = regions
+                .into_iter()
+                .filter(|region| !held.contains(region))
+                .collect();
+            (missing, held.iter().map(order).max())
+        });
+
+        let mut locked = Vec::with_capacity(missing.len());
+        let mut guards = Vec::with_capacity(missing.len());
+        for region in missing {
+            let lock = self.lock_for(region);
+            let guard = if last_held.is_none_or(|last_held| order(&region) > last_held) {
+                Some(lock.lock_owned().await)
+            } else {
+                lock.try_lock_owned().ok()
+            };
+            if let Some(guard) = guard {
+                locked.push(region);
+                guards.push(guard);
+            } else {
+                log::debug!("Region {region:?} is busy, running without its lock");
+            }
+        }
+        (locked, guards)
+    }
+
+    /// Forgets the locks of regions which are currently not held by anyone.
+    pub fn clean(&self) {
+        self.locks
+            .lock()
+            .unwrap()
+            .retain(|_, lock| Arc::strong_count(lock) > 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn nested_locking_is_reentrant() {
+        let locks = RegionLocks::default();
+        let region = Vector2::new(0, 0);
+        let inner = locks.run_locked([region], async {
+            locks
+                .run_locked([region, Vector2::new(1, 0)], async { 42 })
+                .await
+        });
+        let output = tokio::time::timeout(std::time::Duration::from_secs(1), inner).await;
+        assert_eq!(output.ok(), Some(42));
+    }
+
+    #[tokio::test]
+    async fn busy_region_before_held_ones_is_skipped() {
+        let locks = RegionLocks::default();
+        let _busy = locks.lock_for(Vector2::new(0, 0)).lock_owned().await;
+        let output = locks.run_locked([Vector2::new(1, 0)], async {
+            locks
+                .run_locked([Vector2::new(0, 0)], async {
+                    HELD.with(|held| held.borrow().clone())
+                })
+                .await
+        });
+        let held = tokio::time::timeout(std::time::Duration::from_secs(1), output).await;
+        assert_eq!(held.ok(), Some(vec![Vector2::new(1, 0)]));
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_registry::VanillaDimensionType;
+
+use super::{
+    World, patrol::PatrolSpawner, wandering_trader::WanderingTraderSpawner,
+    zombie_siege::ZombieSiege,
+};
+
+/// Spawns mobs outside of the regular natural spawning, e.g. wandering traders or patrols.
+///
+/// Every special spawner is ticked once per world tick and keeps track of its own cooldowns.
+#[async_trait]
+pub trait SpecialSpawner: Send + Sync {
+    async fn tick(&mut self, world: &Arc<World>);
+}
+
+/// The special spawners a world of the given dimension runs.
+pub fn special_spawners_for(dimension_type: VanillaDimensionType) -> Vec<Box<dyn SpecialSpawner>> {
+    match dimension_type {
+        VanillaDimensionType::Overworld => vec![
+            Box::new(PatrolSpawner::default()),
+            Box::new(ZombieSiege::default()),
+            Box::new(WanderingTraderSpawner::default()),
+        ],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use pumpkin_data::Block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{
+    block::entities::block_entity_from_nbt,
+    generation::structure::template::{StructureTemplate, TemplateBlock, TemplatePlacement},
+    world::BlockFlags,
+};
+
+use super::World;
+
+impl World {
+    /// Captures every block and block entity between `from` and `to` (inclusive) into a template.
+    /// Returns `None` if the region holds more than `max_volume` blocks.
+    ///
+    /// Entities are not captured yet.
+    pub async fn export_region(
+        &self,
+        from: BlockPos,
+        to: BlockPos,
+        max_volume: u64,
+    ) -> Option<StructureTemplate> {
+        let min = Vector3::new(
+            from.0.x.min(to.0.x),
+            from.0.y.min(to.0.y),
+            from.0.z.min(to.0.z),
+        );
+        let max = Vector3::new(
+            from.0.x.max(to.0.x),
+            from.0.y.max(to.0.y),
+            from.0.z.max(to.0.z),
+        );
+        let size = max.sub(&min).add_raw(1, 1, 1);
+        let volume = [size.x, size.y, size.z]
+            .into_iter()
+            .map(|length| u64::from(length.unsigned_abs()))
+            .product::<u64>();
+        if volume > max_volume {
+            return None;
+        }
+
+        let mut blocks = Vec::new();
+        for position in BlockPos::iterate(from, to) {
+            let state_id = self.get_block_state_id(&position).await;
+            let nbt = match self.get_block_entity(&position).await {
+                Some(block_entity) => {
+                    let mut nbt = NbtCompound::new();
+                    block_entity.write_internal(&mut nbt).await;
+                    // The position is set again when the template gets placed
+                    nbt.child_tags
+                        .retain(|(name, _)| !matches!(name.as_str(), "x" | "y" | "z"));
+                    Some(nbt)
+                }
+                None => None,
+            };
+            blocks.push(TemplateBlock {
+                pos: position.0.sub(&min),
+                state_id,
+                nbt,
+            });
+        }
+
+        Some(StructureTemplate { size, blocks })
+    }
+
+    /// Places `template` with its origin corner at `origin`, replacing block entities with the
+    /// ones stored in the template. Returns how many blocks were placed.
+    ///
+    /// Neighbours are only updated once every block is in place, so contraptions in the template
+    /// do not react to a half placed copy of themselves.
+    pub async fn place_template(
+        self: &Arc<Self>,
+        template: &StructureTemplate,
+        origin: BlockPos,
+        placement: &TemplatePlacement,
+    ) -> usize {
+        let corner = origin.offset(placement.transform_pos(template.size.sub_raw(1, 1, 1)));
+        self.region_locks
+            .run_locked_area(
+                &origin,
+                &corner,
+                self.place_template_locked(template, origin, placement),
+            )
+            .await
+    }
+
+    async fn place_template_locked(
+        self: &Arc<Self>,
+        template: &StructureTemplate,
+        origin: BlockPos,
+        placement: &TemplatePlacement,
+    ) -> usize {
+        let mut placed = Vec::new();
+        for block in &template.blocks {
+            let state_id = placement.transform_state(block.state_id);
+            let placed_block = Block::from_state_id(state_id);
+            if placed_block == &Block::STRUCTURE_VOID
+                || (placement.ignore_air && placed_block.default_state.is_air())
+            {
+                continue;
+            }
+
+            let position = origin.offset(placement.transform_pos(block.pos));
+            self.remove_block_entity(&position).await;
+            self.set_block_state(
+                &position,
+                state_id,
+                BlockFlags::NOTIFY_LISTENERS
+                    | BlockFlags::FORCE_STATE
+                    | BlockFlags::SKIP_BLOCK_ENTITY_REPLACED_CALLBACK,
+            )
+            .await;
+
+            if let Some(nbt) = &block.nbt {
+                let mut nbt = nbt.clone();
+                nbt.put_int("x", position.0.x);
+                nbt.put_int("y", position.0.y);
+                nbt.put_int("z", position.0.z);
+                if let Some(block_entity) = block_entity_from_nbt(&nbt) {
+                    self.add_block_entity(block_entity).await;
+                }
+            }
+            placed.push(position);
+        }
+
+        for position in &placed {
+            self.update_neighbors(position, None).await;
+        }
+        placed.len()
+    }
+}
@@ -72,4 +72,20 @@ impl LevelTime {
     pub const fn query_day(&self) -> i64 {
         self.time_of_day / 24000
     }
+
+    /// How far the sun has moved across the sky, from 0 to 1, with 0 at noon. The sun moves
+    /// faster around sunrise and sunset.
+    #[must_use]
+    pub fn sky_angle(&self) -> f64 {
+        let angle = (self.query_daytime() as f64 / 24000.0 - 0.25).rem_euclid(1.0);
+        let eased = 0.5 - (angle * std::f64::consts::PI).cos() / 2.0;
+        angle.mul_add(2.0, eased) / 3.0
+    }
+
+    /// Whether it is dark enough for the night to count, the same window in which players can sleep.
+    #[must_use]
+    pub const fn is_night(&self) -> bool {
+        let daytime = self.query_daytime();
+        daytime > 12542 && daytime < 23459
+    }
 }
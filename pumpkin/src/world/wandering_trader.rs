@@ -0,0 +1,157 @@
+use std::sync::{Arc, atomic::Ordering};
+
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use pumpkin_world::{
+    poi::PoiType,
+    world_info::{DEFAULT_WANDERING_TRADER_SPAWN_CHANCE, DEFAULT_WANDERING_TRADER_SPAWN_DELAY},
+};
+use rand::{Rng, rng, seq::IteratorRandom};
+use uuid::Uuid;
+
+use super::{World, special_spawner::SpecialSpawner};
+use crate::entity::{
+    Entity, EntityBase,
+    mob::{trader_llama::TraderLlama, wandering_trader::WanderingTrader},
+};
+
+/// Ticks between two updates of the spawn delay.
+const SPAWN_ATTEMPT_INTERVAL: i32 = 1200;
+const MAX_SPAWN_CHANCE: i32 = 75;
+/// How far from the player (or the village meeting point) the trader may spawn.
+const SPAWN_RADIUS: i32 = 48;
+const TRADER_DESPAWN_DELAY: i32 = 48_000;
+const LLAMA_COUNT: usize = 2;
+
+/// Periodically tries to spawn a wandering trader with its llamas near a random player.
+///
+/// The spawn delay and chance live in the level data so they survive restarts.
+pub struct WanderingTraderSpawner {
+    ticks_until_next_attempt: i32,
+}
+
+impl Default for WanderingTraderSpawner {
+    fn default() -> Self {
+        Self {
+            ticks_until_next_attempt: SPAWN_ATTEMPT_INTERVAL,
+        }
+    }
+}
+
+#[async_trait]
+impl SpecialSpawner for WanderingTraderSpawner {
+    async fn tick(&mut self, world: &Arc<World>) {
+        let chance = {
+            let mut level_info = world.level_info.write().await;
+            if !level_info.game_rules.do_trader_spawning {
+                return;
+            }
+            self.ticks_until_next_attempt -= 1;
+            if self.ticks_until_next_attempt > 0 {
+                return;
+            }
+            self.ticks_until_next_attempt = SPAWN_ATTEMPT_INTERVAL;
+            level_info.wandering_trader_spawn_delay -= SPAWN_ATTEMPT_INTERVAL;
+            if level_info.wandering_trader_spawn_delay > 0 {
+                return;
+            }
+            level_info.wandering_trader_spawn_delay = DEFAULT_WANDERING_TRADER_SPAWN_DELAY;
+            if !level_info.game_rules.do_mob_spawning {
+                return;
+            }
+            let chance = level_info.wandering_trader_spawn_chance;
+            level_info.wandering_trader_spawn_chance = (chance
+                + DEFAULT_WANDERING_TRADER_SPAWN_CHANCE)
+                .clamp(DEFAULT_WANDERING_TRADER_SPAWN_CHANCE, MAX_SPAWN_CHANCE);
+            chance
+        };
+
+        if rng().random_range(0..100) > chance {
+            return;
+        }
+        if Self::try_spawn(world).await {
+            world.level_info.write().await.wandering_trader_spawn_chance =
+                DEFAULT_WANDERING_TRADER_SPAWN_CHANCE;
+        }
+    }
+}
+
+impl WanderingTraderSpawner {
+    async fn try_spawn(world: &Arc<World>) -> bool {
+        let Some(player) = world
+            .players
+            .read()
+            .await
+            .values()
+            .choose(&mut rng())
+            .cloned()
+        else {
+            return true;
+        };
+        if rng().random_range(0..10) != 0 {
+            return false;
+        }
+
+        let player_pos = player.living_entity.entity.block_pos.load();
+        let wander_target = world
+            .find_nearest_poi(
+                &player_pos,
+                SPAWN_RADIUS,
+                |poi_type| poi_type == PoiType::Meeting,
+                false,
+            )
+            .await
+            .unwrap_or(player_pos);
+        let Some(spawn_pos) = Self::find_spawn_pos(world, &wander_target, SPAWN_RADIUS).await
+        else {
+            return false;
+        };
+
+        let trader = WanderingTrader::make(Entity::new(
+            Uuid::new_v4(),
+            world.clone(),
+            spawn_pos.to_f64(),
+            &EntityType::WANDERING_TRADER,
+            false,
+        ))
+        .await;
+        trader
+            .despawn_delay
+            .store(TRADER_DESPAWN_DELAY, Ordering::Relaxed);
+        trader.wander_target.store(Some(wander_target));
+        let trader_id = trader.get_entity().entity_id;
+        world.spawn_entity(trader).await;
+
+        for _ in 0..LLAMA_COUNT {
+            let Some(llama_pos) = Self::find_spawn_pos(world, &spawn_pos, 4).await else {
+                continue;
+            };
+            let llama = TraderLlama::make(Entity::new(
+                Uuid::new_v4(),
+                world.clone(),
+                llama_pos.to_f64(),
+                &EntityType::TRADER_LLAMA,
+                false,
+            ));
+            world.spawn_entity(llama.clone()).await;
+            llama.set_leash_holder(Some(trader_id)).await;
+        }
+        true
+    }
+
+    /// Tries up to 10 random columns around `center` for a dry surface to stand on.
+    async fn find_spawn_pos(world: &World, center: &BlockPos, radius: i32) -> Option<BlockPos> {
+        for _ in 0..10 {
+            let x = center.0.x + rng().random_range(-radius..=radius);
+            let z = center.0.z + rng().random_range(-radius..=radius);
+            let y = world.get_top_block(Vector2::new(x, z)).await;
+            let ground = BlockPos::new(x, y, z);
+            if world.get_block_state(&ground).await.is_liquid() {
+                continue;
+            }
+            return Some(ground.up());
+        }
+        None
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    entity::EntityType,
+    tag::{Taggable, WorldgenBiome::MINECRAFT_WITHOUT_ZOMBIE_SIEGES},
+};
+use pumpkin_util::{
+    Difficulty,
+    math::{position::BlockPos, vector3::Vector3},
+};
+use pumpkin_world::chunk::ChunkHeightmapType::WorldSurface;
+use rand::{Rng, rng};
+use uuid::Uuid;
+
+use super::{World, natural_spawner::is_spawn_position_ok, special_spawner::SpecialSpawner};
+use crate::entity::{EntityBase, r#type::from_type};
+
+/// The day time at which it is decided whether a siege happens this night.
+const MIDNIGHT: i64 = 18_000;
+const ZOMBIES_PER_SIEGE: i32 = 20;
+/// Ticks between two zombies of a siege.
+const SPAWN_INTERVAL: i32 = 2;
+/// How far from the player the siege starts.
+const SIEGE_DISTANCE: f64 = 32.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SiegeState {
+    CanActivate,
+    Tonight,
+    Done,
+}
+
+/// Sends a horde of zombies to the edge of a village a player is in, on some nights.
+pub struct ZombieSiege {
+    state: SiegeState,
+    has_set_up_siege: bool,
+    next_spawn_time: i32,
+    zombies_to_spawn: i32,
+    spawn_pos: Vector3<f64>,
+}
+
+impl Default for ZombieSiege {
+    fn default() -> Self {
+        Self {
+            state: SiegeState::CanActivate,
+            has_set_up_siege: false,
+            next_spawn_time: 0,
+            zombies_to_spawn: 0,
+            spawn_pos: Vector3::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpecialSpawner for ZombieSiege {
+    async fn tick(&mut self, world: &Arc<World>) {
+        let spawn_monsters = {
+            let level_info = world.level_info.read().await;
+            level_info.game_rules.do_mob_spawning && level_info.difficulty != Difficulty::Peaceful
+        };
+        let (is_night, daytime) = {
+            let level_time = world.level_time.lock().await;
+            (level_time.is_night(), level_time.query_daytime())
+        };
+        if !is_night || !spawn_monsters {
+            self.state = SiegeState::Done;
+            self.has_set_up_siege = false;
+            return;
+        }
+
+        if daytime == MIDNIGHT {
+            self.state = if rng().random_range(0..10) == 0 {
+                SiegeState::Tonight
+            } else {
+                SiegeState::Done
+            };
+        }
+        if self.state == SiegeState::Done {
+            return;
+        }
+        if !self.has_set_up_siege {
+            if !self.try_set_up_siege(world).await {
+                return;
+            }
+            self.has_set_up_siege = true;
+        }
+
+        if self.next_spawn_time > 0 {
+            self.next_spawn_time -= 1;
+            return;
+        }
+        self.next_spawn_time = SPAWN_INTERVAL;
+        if self.zombies_to_spawn > 0 {
+            self.try_spawn_zombie(world).await;
+            self.zombies_to_spawn -= 1;
+        } else {
+            self.state = SiegeState::Done;
+        }
+    }
+}
+
+impl ZombieSiege {
+    /// Looks for a player inside a village and picks a spot at its edge for the zombies to come from.
+    async fn try_set_up_siege(&mut self, world: &Arc<World>) -> bool {
+        let players: Vec<_> = world.players.read().await.values().cloned().collect();
+        for player in players {
+            if player.is_spectator() {
+                continue;
+            }
+            let pos = player.living_entity.entity.block_pos.load();
+            if !world.is_near_occupied_poi(&pos).await
+                || world
+                    .level
+                    .get_rough_biome(&pos)
+                    .await
+                    .is_tagged_with_by_tag(&MINECRAFT_WITHOUT_ZOMBIE_SIEGES)
+            {
+                continue;
+            }
+
+            for _ in 0..10 {
+                let angle = rng().random::<f64>() * std::f64::consts::TAU;
+                let edge = BlockPos::new(
+                    pos.0.x + (angle.cos() * SIEGE_DISTANCE).floor() as i32,
+                    pos.0.y,
+                    pos.0.z + (angle.sin() * SIEGE_DISTANCE).floor() as i32,
+                );
+                if let Some(spawn_pos) = Self::find_spawn_pos(world, &edge).await {
+                    self.spawn_pos = spawn_pos;
+                    self.zombies_to_spawn = ZOMBIES_PER_SIEGE;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    async fn try_spawn_zombie(&self, world: &Arc<World>) {
+        let Some(pos) = Self::find_spawn_pos(world, &self.spawn_pos.to_block_pos()).await else {
+            return;
+        };
+        let zombie = from_type(&EntityType::ZOMBIE, pos, world, Uuid::new_v4()).await;
+        world.spawn_entity(zombie).await;
+    }
+
+    /// A random spot on the surface near `center` which still belongs to the village.
+    async fn find_spawn_pos(world: &Arc<World>, center: &BlockPos) -> Option<Vector3<f64>> {
+        for _ in 0..10 {
+            let (offset_x, offset_z) = {
+                let mut rng = rng();
+                (rng.random_range(-8..8), rng.random_range(-8..8))
+            };
+            let x = center.0.x + offset_x;
+            let z = center.0.z + offset_z;
+            let pos = BlockPos::new(x, world.get_height(WorldSurface, x, z).await + 1, z);
+            // TODO: Only spawn in the dark
+            if world.is_near_occupied_poi(&pos).await
+                && is_spawn_position_ok(world, &pos, &EntityType::ZOMBIE).await
+            {
+                return Some(Vector3::new(
+                    f64::from(pos.0.x) + 0.5,
+                    f64::from(pos.0.y),
+                    f64::from(pos.0.z) + 0.5,
+                ));
+            }
+        }
+        None
+    }
+}
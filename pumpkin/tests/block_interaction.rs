@@ -0,0 +1,121 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::TestServer;
+use common::client::read_block_update;
+use pumpkin_data::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::packet::clientbound;
+use pumpkin_protocol::ser::NetworkReadExt;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::block::entities::BlockEntity;
+use pumpkin_world::block::entities::chest::ChestBlockEntity;
+use pumpkin_world::inventory::Inventory;
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::world::BlockFlags;
+
+#[tokio::test]
+async fn breaking_block_sends_update() {
+    let server = TestServer::get();
+    let mut client = server.connect("Breaker").await;
+    let target = client.block_pos().offset(Vector3::new(1, 0, 0));
+
+    let world = server.overworld().await;
+    server
+        .run(move |_| async move {
+            world
+                .set_block_state(
+                    &target,
+                    Block::STONE.default_state.id,
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+        })
+        .await;
+
+    client.break_block(target).await;
+    client
+        .expect_matching(clientbound::PLAY_BLOCK_UPDATE, |packet| {
+            read_block_update(packet) == (target, i32::from(Block::AIR.default_state.id))
+        })
+        .await;
+}
+
+async fn place_chest(server: &TestServer, position: BlockPos, stack: ItemStack) {
+    let world = server.overworld().await;
+    server
+        .run(move |_| async move {
+            world
+                .set_block_state(
+                    &position,
+                    Block::CHEST.default_state.id,
+                    BlockFlags::NOTIFY_ALL,
+                )
+                .await;
+            let chest = Arc::new(ChestBlockEntity::new(position));
+            chest.set_stack(0, stack).await;
+            world.add_block_entity(chest).await;
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn using_chest_opens_screen() {
+    let server = TestServer::get();
+    let mut client = server.connect("Opener").await;
+    let chest = client.block_pos().offset(Vector3::new(-1, 0, 0));
+    place_chest(server, chest, ItemStack::new(1, &Item::DIAMOND)).await;
+
+    client.use_block(chest).await;
+    let screen = client.expect(clientbound::PLAY_OPEN_SCREEN).await;
+    let window_id = (&screen.payload[..]).get_var_int().unwrap().0;
+    let content = client
+        .expect_matching(clientbound::PLAY_CONTAINER_SET_CONTENT, |packet| {
+            (&packet.payload[..]).get_var_int().unwrap().0 == window_id
+        })
+        .await;
+    assert_ne!(window_id, 0, "The chest must not open the player inventory");
+    assert!(!content.payload.is_empty());
+}
+
+#[tokio::test]
+async fn clicking_slots_moves_items_in_chest() {
+    let server = TestServer::get();
+    let mut client = server.connect("Clicker").await;
+    let chest = client.block_pos().offset(Vector3::new(0, 0, 1));
+    place_chest(server, chest, ItemStack::new(3, &Item::DIAMOND)).await;
+
+    client.use_block(chest).await;
+    let screen = client.expect(clientbound::PLAY_OPEN_SCREEN).await;
+    let window_id = (&screen.payload[..]).get_var_int().unwrap().0;
+    let content = client
+        .expect_matching(clientbound::PLAY_CONTAINER_SET_CONTENT, |packet| {
+            (&packet.payload[..]).get_var_int().unwrap().0 == window_id
+        })
+        .await;
+    let mut payload = &content.payload[..];
+    payload.get_var_int().unwrap();
+    let state_id = payload.get_var_int().unwrap().0;
+
+    // Pick up the diamonds and put them down one slot further
+    client.click_slot(window_id, state_id, 0, 0, 0).await;
+    client.click_slot(window_id, state_id, 1, 0, 0).await;
+
+    server
+        .wait_until(move |server| async move {
+            let world = server.worlds.read().await[0].clone();
+            let Some(inventory) = world
+                .get_block_entity(&chest)
+                .await
+                .and_then(BlockEntity::get_inventory)
+            else {
+                return false;
+            };
+            let first = inventory.get_stack(0).await.lock().await.clone();
+            let second = inventory.get_stack(1).await.lock().await.clone();
+            first.is_empty() && second.item.id == Item::DIAMOND.id && second.item_count == 3
+        })
+        .await;
+}
@@ -0,0 +1,236 @@
+use std::net::SocketAddr;
+
+use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
+use pumpkin_data::packet::{clientbound, serverbound};
+use pumpkin_protocol::RawPacket;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::packet_decoder::TCPNetworkDecoder;
+use pumpkin_protocol::java::packet_encoder::TCPNetworkEncoder;
+use pumpkin_protocol::ser::{NetworkReadExt, NetworkWriteExt, WritingError};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+
+use super::TIMEOUT;
+
+/// A bare-bones Java Edition client that speaks just enough of the protocol to join and script
+/// interactions. Everything it receives is handed to the test as a [`RawPacket`], except for keep
+/// alives, which are answered on the fly.
+pub struct HeadlessClient {
+    reader: TCPNetworkDecoder<OwnedReadHalf>,
+    writer: TCPNetworkEncoder<OwnedWriteHalf>,
+    pub name: String,
+    /// Where the server last teleported the client to.
+    pub position: Vector3<f64>,
+    sequence: i32,
+}
+
+impl HeadlessClient {
+    /// Connects to the server and goes through login and configuration until the client is in
+    /// the world.
+    pub async fn join(address: SocketAddr, name: &str) -> Self {
+        let stream = TcpStream::connect(address)
+            .await
+            .expect("Failed to connect to the server");
+        stream.set_nodelay(true).unwrap();
+        let (read, write) = stream.into_split();
+        let mut client = Self {
+            reader: TCPNetworkDecoder::new(read),
+            writer: TCPNetworkEncoder::new(write),
+            name: name.to_string(),
+            position: Vector3::default(),
+            sequence: 0,
+        };
+
+        client
+            .send(serverbound::HANDSHAKE_INTENTION, |buf| {
+                buf.write_var_int(&VarInt(CURRENT_MC_PROTOCOL as i32))?;
+                buf.write_string(&address.ip().to_string())?;
+                buf.write_u16_be(address.port())?;
+                // Login
+                buf.write_var_int(&VarInt(2))
+            })
+            .await;
+        client
+            .send(serverbound::LOGIN_HELLO, |buf| {
+                buf.write_string(name)?;
+                buf.write_uuid(&uuid::Uuid::nil())
+            })
+            .await;
+        client.expect(clientbound::LOGIN_LOGIN_FINISHED).await;
+        client
+            .send(serverbound::LOGIN_LOGIN_ACKNOWLEDGED, |_| Ok(()))
+            .await;
+
+        // Claim to know every pack the server offers so it does not have to send them
+        let known_packs = client.expect(clientbound::CONFIG_SELECT_KNOWN_PACKS).await;
+        client
+            .send(serverbound::CONFIG_SELECT_KNOWN_PACKS, |buf| {
+                buf.write_slice(&known_packs.payload)
+            })
+            .await;
+        client
+            .expect(clientbound::CONFIG_FINISH_CONFIGURATION)
+            .await;
+        client
+            .send(serverbound::CONFIG_FINISH_CONFIGURATION, |_| Ok(()))
+            .await;
+
+        client.expect(clientbound::PLAY_LOGIN).await;
+        let teleport = client.expect(clientbound::PLAY_PLAYER_POSITION).await;
+        let mut payload = &teleport.payload[..];
+        let teleport_id = payload.get_var_int().unwrap();
+        client.position = Vector3::new(
+            payload.get_f64_be().unwrap(),
+            payload.get_f64_be().unwrap(),
+            payload.get_f64_be().unwrap(),
+        );
+        client
+            .send(serverbound::PLAY_ACCEPT_TELEPORTATION, |buf| {
+                buf.write_var_int(&teleport_id)
+            })
+            .await;
+
+        client
+    }
+
+    /// The block the client is standing in.
+    pub fn block_pos(&self) -> BlockPos {
+        BlockPos::floored_v(self.position)
+    }
+
+    /// Sends a packet whose fields are written by `write`.
+    pub async fn send(
+        &mut self,
+        id: i32,
+        write: impl FnOnce(&mut Vec<u8>) -> Result<(), WritingError>,
+    ) {
+        let mut buf = Vec::new();
+        buf.write_var_int(&VarInt(id)).unwrap();
+        write(&mut buf).expect("Failed to write packet");
+        self.writer
+            .write_packet(buf.into())
+            .await
+            .expect("Failed to send packet");
+    }
+
+    /// Waits for the next packet from the server, failing the test on timeout or disconnect.
+    pub async fn receive(&mut self) -> RawPacket {
+        loop {
+            let packet = tokio::time::timeout(TIMEOUT, self.reader.get_raw_packet())
+                .await
+                .expect("Timed out waiting for a packet")
+                .expect("Failed to read packet");
+
+            if packet.id == clientbound::PLAY_KEEP_ALIVE {
+                let payload = packet.payload;
+                self.send(serverbound::PLAY_KEEP_ALIVE, |buf| {
+                    buf.write_slice(&payload)
+                })
+                .await;
+                continue;
+            }
+            assert_ne!(
+                packet.id,
+                clientbound::PLAY_DISCONNECT,
+                "{} was disconnected",
+                self.name
+            );
+            return packet;
+        }
+    }
+
+    /// Skips packets until one with the given id arrives.
+    pub async fn expect(&mut self, id: i32) -> RawPacket {
+        self.expect_matching(id, |_| true).await
+    }
+
+    /// Skips packets until one with the given id arrives for which `predicate` holds.
+    pub async fn expect_matching(
+        &mut self,
+        id: i32,
+        predicate: impl Fn(&RawPacket) -> bool,
+    ) -> RawPacket {
+        loop {
+            let packet = self.receive().await;
+            if packet.id == id && predicate(&packet) {
+                return packet;
+            }
+        }
+    }
+
+    /// Starts digging the block at `position`, which breaks it right away in creative mode.
+    pub async fn break_block(&mut self, position: BlockPos) {
+        let sequence = self.next_sequence();
+        self.send(serverbound::PLAY_PLAYER_ACTION, |buf| {
+            // Started digging
+            buf.write_var_int(&VarInt(0))?;
+            buf.write_i64_be(encode_block_pos(position))?;
+            // Up
+            buf.write_u8(1)?;
+            buf.write_var_int(&VarInt(sequence))
+        })
+        .await;
+    }
+
+    /// Right clicks the top of the block at `position` with the main hand.
+    pub async fn use_block(&mut self, position: BlockPos) {
+        let sequence = self.next_sequence();
+        self.send(serverbound::PLAY_USE_ITEM_ON, |buf| {
+            // Main hand
+            buf.write_var_int(&VarInt(0))?;
+            buf.write_i64_be(encode_block_pos(position))?;
+            // Up
+            buf.write_var_int(&VarInt(1))?;
+            buf.write_f32_be(0.5)?;
+            buf.write_f32_be(1.0)?;
+            buf.write_f32_be(0.5)?;
+            buf.write_bool(false)?;
+            buf.write_bool(false)?;
+            buf.write_var_int(&VarInt(sequence))
+        })
+        .await;
+    }
+
+    /// Clicks a slot of an open screen. The client does not predict the outcome, so it reports no
+    /// changed slots and the server corrects it afterwards.
+    pub async fn click_slot(
+        &mut self,
+        window_id: i32,
+        state_id: i32,
+        slot: i16,
+        button: i8,
+        mode: i32,
+    ) {
+        self.send(serverbound::PLAY_CONTAINER_CLICK, |buf| {
+            buf.write_var_int(&VarInt(window_id))?;
+            buf.write_var_int(&VarInt(state_id))?;
+            buf.write_i16_be(slot)?;
+            buf.write_i8(button)?;
+            buf.write_var_int(&VarInt(mode))?;
+            // No changed slots and an empty cursor
+            buf.write_var_int(&VarInt(0))?;
+            buf.write_bool(false)
+        })
+        .await;
+    }
+
+    fn next_sequence(&mut self) -> i32 {
+        self.sequence += 1;
+        self.sequence
+    }
+}
+
+/// Reads the position and state id of a block update packet.
+pub fn read_block_update(packet: &RawPacket) -> (BlockPos, i32) {
+    let mut payload = &packet.payload[..];
+    let position = BlockPos::from_i64(payload.get_i64_be().unwrap());
+    (position, payload.get_var_int().unwrap().0)
+}
+
+fn encode_block_pos(position: BlockPos) -> i64 {
+    ((i64::from(position.0.x) & 0x3FF_FFFF) << 38)
+        | ((i64::from(position.0.z) & 0x3FF_FFFF) << 12)
+        | (i64::from(position.0.y) & 0xFFF)
+}
@@ -0,0 +1,138 @@
+//! Harness for integration tests that run a whole server in-process and talk to it through
+//! [`HeadlessClient`]s.
+//!
+//! Every test binary shares a single server, started on first use inside a temporary directory
+//! with online mode, encryption and compression turned off so the client can stay minimal. Tests
+//! should join with a unique name and work on their own spot of the world.
+#![allow(dead_code)]
+
+pub mod client;
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock, mpsc};
+use std::time::Duration;
+
+use pumpkin::PumpkinServer;
+use pumpkin::server::Server;
+use pumpkin::world::World;
+use tokio::runtime::Handle;
+
+pub use client::HeadlessClient;
+
+/// How long a scenario waits for the server before failing.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+const CONFIGURATION: &str = r#"
+java_edition_address = "127.0.0.1:0"
+bedrock_edition = false
+bedrock_edition_address = "127.0.0.1:0"
+online_mode = false
+encryption = false
+view_distance = 2
+simulation_distance = 2
+default_gamemode = "Creative"
+"#;
+
+const FEATURES: &str = r"
+[commands]
+use_console = false
+
+[networking.query]
+enabled = false
+
+[networking.lan_broadcast]
+enabled = false
+
+[networking.packet_compression]
+enabled = false
+";
+
+static SERVER: OnceLock<TestServer> = OnceLock::new();
+
+pub struct TestServer {
+    pub server: Arc<Server>,
+    pub address: SocketAddr,
+    /// The runtime the server runs on, which outlives the runtime of any single test.
+    runtime: Handle,
+}
+
+impl TestServer {
+    /// Returns the shared server, starting it if this is the first test to ask for it.
+    pub fn get() -> &'static Self {
+        SERVER.get_or_init(Self::start)
+    }
+
+    fn start() -> Self {
+        let dir = tempfile::tempdir()
+            .expect("Failed to create the server directory")
+            .keep();
+        std::fs::create_dir_all(dir.join("config")).unwrap();
+        std::fs::write(dir.join("config/configuration.toml"), CONFIGURATION).unwrap();
+        std::fs::write(dir.join("config/features.toml"), FEATURES).unwrap();
+        // The configuration and the world are read relative to the working directory
+        std::env::set_current_dir(&dir).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build the server runtime");
+            runtime.block_on(async {
+                let pumpkin_server = PumpkinServer::new().await;
+                let address = pumpkin_server.tcp_listener.local_addr().unwrap();
+                sender
+                    .send((pumpkin_server.server.clone(), address, Handle::current()))
+                    .unwrap();
+                pumpkin_server.start().await;
+            });
+        });
+
+        let (server, address, runtime) = receiver
+            .recv()
+            .expect("The server stopped before it was ready");
+        Self {
+            server,
+            address,
+            runtime,
+        }
+    }
+
+    /// Joins the server with a new headless client.
+    pub async fn connect(&self, name: &str) -> HeadlessClient {
+        HeadlessClient::join(self.address, name).await
+    }
+
+    /// Runs `f` on the server's runtime, for setting up or inspecting the world directly.
+    pub async fn run<F, Fut, T>(&self, f: F) -> T
+    where
+        F: FnOnce(Arc<Server>) -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.runtime
+            .spawn(f(self.server.clone()))
+            .await
+            .expect("Server task panicked")
+    }
+
+    pub async fn overworld(&self) -> Arc<World> {
+        self.server.worlds.read().await[0].clone()
+    }
+
+    /// Polls `condition` on the server until it holds, failing the test after [`TIMEOUT`].
+    pub async fn wait_until<F, Fut>(&self, mut condition: F)
+    where
+        F: FnMut(Arc<Server>) -> Fut,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        tokio::time::timeout(TIMEOUT, async {
+            while !self.run(&mut condition).await {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("Condition was not met in time");
+    }
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::TestServer;
+use pumpkin_data::packet::clientbound;
+
+#[tokio::test]
+async fn player_joins_world() {
+    let server = TestServer::get();
+    let _client = server.connect("Joiner").await;
+
+    let player = server
+        .run(|server| async move { server.get_player_by_name("Joiner").await })
+        .await
+        .expect("The player was not added to the server");
+    assert_eq!(player.gameprofile.name, "Joiner");
+}
+
+#[tokio::test]
+async fn joining_player_is_shown_to_others() {
+    let server = TestServer::get();
+    let mut watcher = server.connect("Watcher").await;
+    let _newcomer = server.connect("Newcomer").await;
+
+    watcher
+        .expect_matching(clientbound::PLAY_PLAYER_INFO_UPDATE, |packet| {
+            packet
+                .payload
+                .windows("Newcomer".len())
+                .any(|name| name == b"Newcomer")
+        })
+        .await;
+}